@@ -73,8 +73,8 @@ impl NeuralNet {
             let mut new_output = Vec::new();
             for i in 0..layer_width {
                 let mut sum = 0_f32;
-                for j in 0..layer_height {
-                    sum += layer[j][i] * output[j];
+                for (row, value) in layer.iter().zip(output.iter()) {
+                    sum += row[i] * value;
                 }
 
                 if i == layer_width - 1 {
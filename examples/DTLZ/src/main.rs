@@ -1,3 +1,5 @@
+#![allow(clippy::needless_range_loop)]
+
 use plotly::{Plot, Scatter3D};
 use radiate::objectives::{Front, Optimize};
 use radiate::*;
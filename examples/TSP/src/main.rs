@@ -1,3 +1,5 @@
+#![allow(clippy::needless_range_loop, clippy::type_complexity)]
+
 use plotters::backend::BitMapBackend;
 use plotters::chart::ChartBuilder;
 use plotters::drawing::IntoDrawingArea;
@@ -18,15 +20,7 @@ fn main() -> io::Result<()> {
         .minimizing()
         .population_size(250)
         .alter(alters!(PMXCrossover::new(0.4), SwapMutator::new(0.05)))
-        .fitness_fn(move |genotype: Vec<usize>| {
-            let mut total_distance = 0.0;
-            for i in 0..genotype.len() {
-                let j = (i + 1) % genotype.len();
-                total_distance += distance_matrix[genotype[i]][genotype[j]];
-            }
-
-            total_distance
-        })
+        .fitness_fn(move |genotype: Vec<usize>| distance_matrix.tour_length(&genotype))
         .build();
 
     let result = engine.run(move |ctx| {
@@ -41,7 +35,7 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
-fn read_tsp_file(file_path: &PathBuf) -> io::Result<(Vec<Vec<f32>>, Vec<(f32, f32)>)> {
+fn read_tsp_file(file_path: &PathBuf) -> io::Result<(DistanceMatrix, Vec<(f32, f32)>)> {
     let file = File::open(file_path)?;
     let lines = io::BufReader::new(file).lines();
 
@@ -66,27 +60,12 @@ fn read_tsp_file(file_path: &PathBuf) -> io::Result<(Vec<Vec<f32>>, Vec<(f32, f3
         }
     }
 
-    let distance_matrix = create_distance_matrix(&edge_weights, dimension);
-    let points = create_points_from_distances(&distance_matrix);
+    let distance_matrix = DistanceMatrix::from_lower_triangle(&edge_weights, dimension);
+    let points = create_points_from_distances(distance_matrix.rows());
 
     Ok((distance_matrix, points))
 }
 
-fn create_distance_matrix(edge_weights: &[f32], dimensions: usize) -> Vec<Vec<f32>> {
-    let mut index = 0;
-    let mut distance_matrix = vec![vec![0.0; dimensions]; dimensions];
-
-    for i in 0..dimensions {
-        for j in 0..=i {
-            distance_matrix[i][j] = edge_weights[index];
-            distance_matrix[j][i] = edge_weights[index];
-            index += 1;
-        }
-    }
-
-    distance_matrix
-}
-
 fn create_points_from_distances(distances: &[Vec<f32>]) -> Vec<(f32, f32)> {
     let n = distances.len();
     let mut points = vec![(0_f32, 0_f32); n];
@@ -9,11 +9,31 @@ static KNAPSACK: LazyLock<Knapsack> = LazyLock::new(|| Knapsack::new(KNAPSACK_SI
 
 fn main() {
     random_provider::set_seed(12345);
-    let codex = SubSetCodex::new(&KNAPSACK.items);
+    // Overweight knapsacks are repaired instead of penalized - the items with the lowest value per
+    // weight are dropped until they fit, then the free capacity is filled greedily. New knapsacks
+    // are repaired when they're encoded, altered ones by the engine.
+    let repair = KnapsackRepair::from_items(
+        &KNAPSACK.items,
+        |item| item.value,
+        |item| item.weight,
+        KNAPSACK.capacity,
+    )
+    .fill();
+
+    let subset = SubSetCodex::new(&KNAPSACK.items);
+    let encode_repair = repair.clone();
+    let codex = FnCodex::new()
+        .with_encoder(move || {
+            let mut genotype = subset.encode();
+            encode_repair.repair(&mut genotype);
+            genotype
+        })
+        .with_decoder(move |genotype| SubSetCodex::new(&KNAPSACK.items).decode(genotype))
+        .with_repair(move |genotype: &mut Genotype<BitChromosome>| repair.repair(genotype));
 
     let engine = GeneticEngine::from_codex(codex)
         .max_age(MAX_EPOCHS)
-        .fitness_fn(move |genotype: Vec<&Item>| Knapsack::fitness(&KNAPSACK.capacity, &genotype))
+        .fitness_fn(move |genotype: Vec<&Item>| Knapsack::value_total(&genotype))
         .build();
 
     let result = engine.run(|ctx| {
@@ -55,21 +75,6 @@ impl Knapsack {
         }
     }
 
-    pub fn fitness(capacity: &f32, genotype: &Vec<&Item>) -> f32 {
-        let mut sum = 0_f32;
-        let mut weight = 0_f32;
-        for item in genotype {
-            sum += item.value;
-            weight += item.weight;
-        }
-
-        if weight > *capacity {
-            0_f32
-        } else {
-            sum
-        }
-    }
-
     pub fn value_total(items: &Vec<&Item>) -> f32 {
         items.iter().fold(0_f32, |acc, item| acc + item.value)
     }
@@ -43,8 +43,8 @@ fn main() {
 
     let board = &result.best[0];
     for i in 0..N_QUEENS {
-        for j in 0..N_QUEENS {
-            if board[j] == i as i8 {
+        for queen in board.iter().take(N_QUEENS) {
+            if *queen == i as i8 {
                 print!("Q ");
             } else {
                 print!(". ");
@@ -18,9 +18,8 @@ fn main() {
         ))
         .fitness_fn(move |genotype: Vec<Vec<f32>>| {
             let mut value = A * N_GENES as f32;
-            for i in 0..N_GENES {
-                value += genotype[0][i].powi(2)
-                    - A * (2.0 * std::f32::consts::PI * genotype[0][i]).cos();
+            for x in genotype[0].iter().take(N_GENES) {
+                value += x.powi(2) - A * (2.0 * std::f32::consts::PI * x).cos();
             }
 
             value
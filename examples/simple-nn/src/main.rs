@@ -74,8 +74,8 @@ impl NeuralNet {
             let mut new_output = Vec::new();
             for i in 0..layer_width {
                 let mut sum = 0_f32;
-                for j in 0..layer_height {
-                    sum += layer[j][i] * output[j];
+                for (row, value) in layer.iter().zip(output.iter()) {
+                    sum += row[i] * value;
                 }
 
                 if i == layer_width - 1 {
@@ -130,7 +130,7 @@ impl Codex<FloatChromosome, NeuralNet> for NeuralNetCodex {
                 chromosome
                     .iter()
                     .as_slice()
-                    .chunks(self.shapes[i].1 as usize)
+                    .chunks(self.shapes[i].1)
                     .map(|chunk| chunk.iter().map(|gene| gene.allele).collect::<Vec<f32>>())
                     .collect::<Vec<Vec<f32>>>(),
             );
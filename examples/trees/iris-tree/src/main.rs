@@ -16,7 +16,7 @@ fn main() {
     let codex = ProgramTreeCodex::new(3, 4)
         .constraint(|node| node.size() < 50)
         .gates(ops::get_math_operations())
-        .leafs((0..4).map(|i| Op::var(i)).collect());
+        .leafs((0..4).map(Op::var).collect());
 
     let engine = GeneticEngine::from_codex(codex)
         .minimizing()
@@ -38,8 +38,8 @@ fn display(
     test: &DataSet,
     result: &EngineContext<TreeChromosome<f32>, ProgramTree>,
 ) {
-    let train_acc = Accuracy::new("train", &train, Loss::MSE);
-    let test_acc = Accuracy::new("test", &test, Loss::MSE);
+    let train_acc = Accuracy::new("train", train, Loss::MSE);
+    let test_acc = Accuracy::new("test", test, Loss::MSE);
     let best = result.best.clone();
 
     let train_acc_result = train_acc.calc(|input| best.eval(input));
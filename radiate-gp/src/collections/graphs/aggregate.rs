@@ -84,9 +84,9 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
             conn.attach((*collection).as_ref());
         }
 
-        for i in 1..collections.len() {
-            conn = conn.one_to_one(previous, collections[i]);
-            previous = collections[i];
+        for collection in collections.iter().skip(1) {
+            conn = conn.one_to_one(previous, collection);
+            previous = collection;
         }
 
         conn
@@ -105,8 +105,8 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
                     .filter(|item| node.outgoing().contains(&item.index()))
                 {
                     self.relationships.push(Relationship {
-                        source_id: &node.id(),
-                        target_id: &outgoing.id(),
+                        source_id: node.id(),
+                        target_id: outgoing.id(),
                     });
                 }
             }
@@ -141,10 +141,10 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
             panic!("OneToOne - oneGroup outputs must be the same length as twoGroup inputs.");
         }
 
-        for (one, two) in one_outputs.into_iter().zip(two_inputs.into_iter()) {
+        for (one, two) in one_outputs.into_iter().zip(two_inputs) {
             self.relationships.push(Relationship {
-                source_id: &one.id(),
-                target_id: &two.id(),
+                source_id: one.id(),
+                target_id: two.id(),
             });
         }
     }
@@ -153,15 +153,15 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
         let one_outputs = self.get_outputs(one);
         let two_inputs = self.get_inputs(two);
 
-        if two_inputs.len() % one_outputs.len() != 0 {
+        if !two_inputs.len().is_multiple_of(one_outputs.len()) {
             panic!("OneToMany - TwoGroup inputs must be a multiple of OneGroup outputs.");
         }
 
         for targets in two_inputs.chunks(one_outputs.len()) {
             for (source, target) in one_outputs.iter().zip(targets.iter()) {
                 self.relationships.push(Relationship {
-                    source_id: &source.id(),
-                    target_id: &target.id(),
+                    source_id: source.id(),
+                    target_id: target.id(),
                 });
             }
         }
@@ -171,15 +171,15 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
         let one_outputs = self.get_outputs(one);
         let two_inputs = self.get_inputs(two);
 
-        if one_outputs.len() % two_inputs.len() != 0 {
+        if !one_outputs.len().is_multiple_of(two_inputs.len()) {
             panic!("ManyToOne - OneGroup outputs must be a multiple of TwoGroup inputs.");
         }
 
         for sources in one_outputs.chunks(two_inputs.len()) {
             for (source, target) in sources.iter().zip(two_inputs.iter()) {
                 self.relationships.push(Relationship {
-                    source_id: &source.id(),
-                    target_id: &target.id(),
+                    source_id: source.id(),
+                    target_id: target.id(),
                 });
             }
         }
@@ -192,8 +192,8 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
         for source in one_outputs {
             for target in two_inputs.iter() {
                 self.relationships.push(Relationship {
-                    source_id: &source.id(),
-                    target_id: &target.id(),
+                    source_id: source.id(),
+                    target_id: target.id(),
                 });
             }
         }
@@ -207,14 +207,14 @@ impl<'a, T: Clone> GraphAggregate<'a, T> {
             panic!("Self - oneGroup outputs must be the same length as twoGroup inputs.");
         }
 
-        for (one, two) in one_outputs.into_iter().zip(two_inputs.into_iter()) {
+        for (one, two) in one_outputs.into_iter().zip(two_inputs) {
             self.relationships.push(Relationship {
-                source_id: &one.id(),
-                target_id: &two.id(),
+                source_id: one.id(),
+                target_id: two.id(),
             });
             self.relationships.push(Relationship {
-                source_id: &two.id(),
-                target_id: &one.id(),
+                source_id: two.id(),
+                target_id: one.id(),
             });
         }
     }
@@ -8,6 +8,7 @@ use radiate::random_provider;
 
 use super::aggregate::GraphAggregate;
 use super::codex::GraphCodex;
+use super::module::GraphModule;
 use super::NodeStore;
 
 /// The `GraphBuilder` is a builder pattern that allows us to create a variety of different
@@ -343,6 +344,139 @@ impl GraphBuilder<f32> {
         self.node_cache = Some(graph.into_iter().collect());
         self
     }
+
+    /// A fully connected, feed forward network - the inputs feed the first hidden layer, each
+    /// hidden layer feeds the next and the last one feeds the outputs, with a weight on every
+    /// connection. The hidden nodes are drawn from `hidden_ops`, which must all accept any number
+    /// of inputs. With no hidden layers the inputs connect straight to the outputs.
+    pub fn layered(
+        mut self,
+        input_size: usize,
+        hidden_layers: &[usize],
+        output_size: usize,
+        hidden_ops: Vec<Op<f32>>,
+        output: Op<f32>,
+    ) -> GraphBuilder<f32> {
+        if hidden_ops.iter().any(|op| op.arity() != Arity::Any) {
+            panic!("Layered hidden operations must accept any number of inputs");
+        }
+
+        if hidden_layers.contains(&0) {
+            panic!("Layered hidden layers must contain at least one node");
+        }
+
+        self.with_values(NodeType::Input, (0..input_size).map(Op::var).collect());
+        self.with_values(NodeType::Vertex, hidden_ops);
+        self.with_values(NodeType::Output, vec![output]);
+        self.ensure_weights();
+
+        let mut graph = Graph::<f32>::default();
+        let mut previous = self.push_nodes(&mut graph, NodeType::Input, input_size);
+
+        for &layer_size in hidden_layers {
+            let layer = self.push_nodes(&mut graph, NodeType::Vertex, layer_size);
+            self.connect_weighted(&mut graph, &previous, &layer);
+            previous = layer;
+        }
+
+        let outputs = self.push_nodes(&mut graph, NodeType::Output, output_size);
+        self.connect_weighted(&mut graph, &previous, &outputs);
+
+        graph.set_cycles(vec![]);
+
+        self.node_cache = Some(graph.into_iter().collect());
+        self
+    }
+
+    /// A compositional pattern producing network - a `layered` network whose hidden nodes are
+    /// drawn from the periodic and symmetric functions of `ops::get_cppn_operations`, with `tanh` outputs.
+    pub fn cppn(
+        self,
+        input_size: usize,
+        hidden_layers: &[usize],
+        output_size: usize,
+    ) -> GraphBuilder<f32> {
+        self.layered(
+            input_size,
+            hidden_layers,
+            output_size,
+            ops::get_cppn_operations(),
+            Op::tanh(),
+        )
+    }
+
+    /// Stamp `copies` of the `module` between the inputs and the outputs. Every input connects to
+    /// the input ports of every copy and the output ports of every copy connect to every output,
+    /// each through a weight. As with the other templates, encoding redraws each node's operation
+    /// from the store when it holds one of the same arity.
+    pub fn modular(
+        mut self,
+        input_size: usize,
+        output_size: usize,
+        module: &GraphModule<f32>,
+        copies: usize,
+        output: Op<f32>,
+    ) -> GraphBuilder<f32> {
+        if copies == 0 {
+            panic!("Modular graphs need at least one copy of the module");
+        }
+
+        self.with_values(NodeType::Input, (0..input_size).map(Op::var).collect());
+        self.with_values(NodeType::Output, vec![output]);
+        self.ensure_weights();
+
+        let mut graph = Graph::<f32>::default();
+        let inputs = self.push_nodes(&mut graph, NodeType::Input, input_size);
+
+        let ports = (0..copies)
+            .map(|_| module.instantiate(&mut graph))
+            .collect::<Vec<(Vec<usize>, Vec<usize>)>>();
+
+        let outputs = self.push_nodes(&mut graph, NodeType::Output, output_size);
+
+        for (module_inputs, module_outputs) in ports.iter() {
+            self.connect_weighted(&mut graph, &inputs, module_inputs);
+            self.connect_weighted(&mut graph, module_outputs, &outputs);
+        }
+
+        graph.set_cycles(vec![]);
+
+        self.node_cache = Some(graph.into_iter().collect());
+        self
+    }
+
+    fn ensure_weights(&self) {
+        let has_weights = self
+            .store
+            .read()
+            .unwrap()
+            .get_values(NodeType::Edge)
+            .is_some_and(|values| !values.is_empty());
+
+        if !has_weights {
+            self.with_values(NodeType::Edge, vec![Op::weight()]);
+        }
+    }
+
+    fn push_nodes(&self, graph: &mut Graph<f32>, node_type: NodeType, size: usize) -> Vec<usize> {
+        let store = self.store.read().unwrap();
+        (0..size)
+            .map(|_| {
+                let index = graph.len();
+                graph.push(store.new_instance((index, node_type)));
+                index
+            })
+            .collect()
+    }
+
+    fn connect_weighted(&self, graph: &mut Graph<f32>, sources: &[usize], targets: &[usize]) {
+        for &source in sources {
+            for &target in targets {
+                let weight = self.push_nodes(graph, NodeType::Edge, 1)[0];
+                graph.attach(source, weight).attach(weight, target);
+            }
+        }
+    }
 }
 
 impl<T: Clone> Builder for GraphBuilder<T> {
@@ -63,9 +63,9 @@ where
     T: Clone + PartialEq + Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Graph {{\n")?;
+        writeln!(f, "Graph {{")?;
         for node in self.as_ref() {
-            write!(f, "  {:?},\n", node)?;
+            writeln!(f, "  {:?},", node)?;
         }
         write!(f, "}}")
     }
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use radiate::{Chromosome, EngineContext, Gene, Optimize, Score};
+
+use crate::collections::{Graph, NodeType};
+use crate::Op;
+
+const PRUNED_WEIGHTS: &str = "Pruned Weights";
+const QUANTIZATION_BITS: &str = "Quantization Bits";
+
+type GraphFitness = Arc<dyn Fn(&Graph<f32>) -> f32 + Send + Sync>;
+
+/// The result of compressing a graph with a `GraphCompressor`.
+#[derive(Clone, Debug)]
+pub struct CompressedGraph {
+    pub graph: Graph<f32>,
+    /// The number of weights set to zero and disabled.
+    pub pruned: usize,
+    /// The bit width the remaining weights were quantized to, if any width kept the fitness in tolerance.
+    pub bits: Option<u8>,
+    pub score: f32,
+    pub original_score: f32,
+}
+
+/// Compresses an evolved `Graph<f32>` into a smaller deployable model while keeping its fitness within
+/// `tolerance` of the original. The weights are the `MutableConst` values of the `Edge` nodes.
+///
+/// The search is greedy. Weights are pruned in order of their magnitude, smallest first - set to zero and
+/// disabled - and a pruning is kept when the fitness stays within tolerance. The remaining weights are then
+/// quantized to the smallest bit width, from 2 up to `max_bits`, that stays within tolerance, rounding them
+/// to a symmetric uniform grid over `[-max |w|, max |w|]`.
+///
+/// Added to an engine with `GeneticEngineParams::post_run`, it compresses the best graph of the run.
+///
+/// # Example
+/// ```rust
+/// use radiate::*;
+/// use radiate_gp::*;
+///
+/// let fitness = |graph: &Graph<f32>| -(graph.eval(&[1.0, 2.0][..])[0] - 3.0).abs();
+/// let compressor = GraphCompressor::new(fitness).tolerance(0.1);
+///
+/// let codex = GraphBuilder::default()
+///     .weighted_acyclic(2, 1, Op::linear())
+///     .into_codex();
+///
+/// let engine = GeneticEngine::from_codex(codex)
+///     .population_size(20)
+///     .fitness_fn(move |graph: Graph<f32>| fitness(&graph))
+///     .post_run(move |ctx| compressor.apply(ctx))
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// assert!(result.metrics.get("Pruned Weights").is_some());
+/// ```
+#[derive(Clone)]
+pub struct GraphCompressor {
+    fitness_fn: GraphFitness,
+    optimize: Optimize,
+    tolerance: f32,
+    max_bits: u8,
+}
+
+impl GraphCompressor {
+    /// Create a compressor that maximizes `fitness_fn` with a tolerance of 0 and up to 8 bits per weight.
+    pub fn new(fitness_fn: impl Fn(&Graph<f32>) -> f32 + Send + Sync + 'static) -> Self {
+        GraphCompressor {
+            fitness_fn: Arc::new(fitness_fn),
+            optimize: Optimize::Maximize,
+            tolerance: 0.0,
+            max_bits: 8,
+        }
+    }
+
+    /// Set how much worse than the original the compressed graph's fitness may be. Default is 0.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        if tolerance < 0.0 {
+            panic!("tolerance must be greater than or equal to 0");
+        }
+
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn minimizing(mut self) -> Self {
+        self.optimize = Optimize::Minimize;
+        self
+    }
+
+    /// Default is maximizing, like the `GeneticEngine`.
+    pub fn maximizing(mut self) -> Self {
+        self.optimize = Optimize::Maximize;
+        self
+    }
+
+    /// Set the largest bit width tried when quantizing the weights. Default is 8.
+    pub fn max_bits(mut self, max_bits: u8) -> Self {
+        if !(2..=32).contains(&max_bits) {
+            panic!("max_bits must be between 2 and 32");
+        }
+
+        self.max_bits = max_bits;
+        self
+    }
+
+    pub fn compress(&self, graph: &Graph<f32>) -> CompressedGraph {
+        let original_score = (self.fitness_fn)(graph);
+        let within = |score: f32| {
+            let worse_by = match self.optimize {
+                Optimize::Minimize => score - original_score,
+                Optimize::Maximize => original_score - score,
+            };
+
+            worse_by <= self.tolerance
+        };
+
+        let mut weights = graph
+            .iter()
+            .filter(|node| node.is_enabled() && node.node_type() == NodeType::Edge)
+            .filter_map(|node| weight(node.value()).map(|value| (node.index(), value)))
+            .collect::<Vec<(usize, f32)>>();
+        weights.sort_by(|one, two| one.1.abs().total_cmp(&two.1.abs()));
+
+        let mut result = CompressedGraph {
+            graph: graph.clone(),
+            pruned: 0,
+            bits: None,
+            score: original_score,
+            original_score,
+        };
+
+        let mut remaining = Vec::new();
+        for (index, value) in weights {
+            let mut candidate = result.graph.clone();
+            set_weight(&mut candidate, index, 0.0);
+            candidate[index].disable();
+
+            let score = (self.fitness_fn)(&candidate);
+            if within(score) {
+                result.graph = candidate;
+                result.score = score;
+                result.pruned += 1;
+            } else {
+                remaining.push((index, value));
+            }
+        }
+
+        let scale = remaining
+            .iter()
+            .fold(0.0_f32, |max, (_, value)| max.max(value.abs()));
+        if scale == 0.0 {
+            return result;
+        }
+
+        for bits in 2..=self.max_bits {
+            let step = scale / ((1_u64 << (bits - 1)) - 1) as f32;
+            let mut candidate = result.graph.clone();
+            for (index, value) in remaining.iter() {
+                set_weight(&mut candidate, *index, (value / step).round() * step);
+            }
+
+            let score = (self.fitness_fn)(&candidate);
+            if within(score) {
+                result.graph = candidate;
+                result.score = score;
+                result.bits = Some(bits);
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Compress the best graph of a run, replacing it and its score, and record the number of pruned
+    /// weights and the bit width in the context's metrics.
+    pub fn apply<C: Chromosome>(&self, ctx: &mut EngineContext<C, Graph<f32>>) {
+        let compressed = self.compress(&ctx.best);
+
+        ctx.metrics
+            .upsert_value(PRUNED_WEIGHTS, compressed.pruned as f32);
+        if let Some(bits) = compressed.bits {
+            ctx.metrics.upsert_value(QUANTIZATION_BITS, bits as f32);
+        }
+
+        ctx.best = compressed.graph;
+        ctx.score = Some(Score::from_f32(compressed.score));
+    }
+}
+
+fn weight(op: &Op<f32>) -> Option<f32> {
+    match op {
+        Op::MutableConst { value, .. } => Some(*value),
+        _ => None,
+    }
+}
+
+fn set_weight(graph: &mut Graph<f32>, index: usize, weight: f32) {
+    let op = match graph[index].value() {
+        Op::MutableConst {
+            name,
+            arity,
+            get_value,
+            modifier,
+            operation,
+            ..
+        } => Op::MutableConst {
+            name,
+            arity: *arity,
+            value: weight,
+            get_value: Arc::clone(get_value),
+            modifier: Arc::clone(modifier),
+            operation: Arc::clone(operation),
+        },
+        other => other.clone(),
+    };
+
+    graph[index] = graph[index].with_allele(&op);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Eval, GraphBuilder};
+    use radiate::Codex;
+
+    #[test]
+    fn test_compress_prunes_unused_weights_and_quantizes() {
+        let codex = GraphBuilder::default()
+            .set_edges(vec![Op::weight()])
+            .weighted_acyclic(3, 1, Op::linear())
+            .into_codex();
+        let graph = codex.decode(&codex.encode());
+
+        // the third input doesn't matter, so its weight can always go
+        let fitness = |graph: &Graph<f32>| -graph.eval(&[1.0, 1.0, 0.0][..])[0].abs();
+        let compressed = GraphCompressor::new(fitness)
+            .tolerance(0.05)
+            .compress(&graph);
+
+        assert!(compressed.pruned >= 1);
+        assert!(compressed.original_score - compressed.score <= 0.05 + 1e-6);
+        assert_eq!(fitness(&compressed.graph), compressed.score);
+        assert_eq!(
+            compressed
+                .graph
+                .iter()
+                .filter(|node| !node.is_enabled())
+                .count(),
+            compressed.pruned
+        );
+    }
+}
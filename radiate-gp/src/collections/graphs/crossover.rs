@@ -5,7 +5,9 @@ use crate::NodeType;
 
 use radiate::engines::genome::*;
 use radiate::timer::Timer;
-use radiate::{indexes, random_provider, Alter, AlterAction, Crossover, EngineCompoment, Metric};
+use radiate::{
+    indexes, random_provider::RngHandle, Alter, AlterAction, Crossover, EngineCompoment, Metric,
+};
 
 const NUM_PARENTS: usize = 2;
 
@@ -28,6 +30,7 @@ impl GraphCrossover {
         population: &Population<GraphChromosome<T>>,
         indexes: &[usize],
         generation: i32,
+        rng: &RngHandle,
     ) -> Option<Phenotype<GraphChromosome<T>>>
     where
         T: Clone + PartialEq + Default + 'static,
@@ -38,7 +41,7 @@ impl GraphCrossover {
         let geno_one = parent_one.genotype();
         let geno_two = parent_two.genotype();
 
-        let rand_idx = random_provider::random::<usize>();
+        let rand_idx = rng.random::<usize>();
         let chromo_index = rand_idx % std::cmp::min(geno_one.len(), geno_two.len());
 
         let chromo_one = &geno_one[chromo_index];
@@ -64,7 +67,7 @@ impl GraphCrossover {
             let node_one = chromo_one.get_gene(i);
             let node_two = chromo_two.get_gene(i);
 
-            if random_provider::random::<f32>() < self.crossover_parent_node_rate {
+            if rng.random::<f32>() < self.crossover_parent_node_rate {
                 new_chromo_one.set_gene(node_one.index(), node_one.with_allele(node_two.allele()));
                 num_crosses += 1;
             }
@@ -108,18 +111,18 @@ where
         &self,
         population: &mut Population<GraphChromosome<T>>,
         generation: i32,
+        rng: &RngHandle,
     ) -> Vec<Metric> {
         let timer = Timer::new();
         let mut count = 0;
         let mut new_phenotypes = HashMap::new();
 
         for index in 0..population.len() {
-            if random_provider::random::<f32>() < self.crossover_rate
-                && population.len() > NUM_PARENTS
-            {
-                let parent_indexes = indexes::individual_indexes(index, population.len(), 2);
+            if rng.random::<f32>() < self.crossover_rate && population.len() > NUM_PARENTS {
+                let parent_indexes =
+                    rng.with(|rng| indexes::individual_indexes(index, population.len(), 2, rng));
 
-                if let Some(phenotype) = self.cross(population, &parent_indexes, generation) {
+                if let Some(phenotype) = self.cross(population, &parent_indexes, generation, rng) {
                     new_phenotypes.insert(index, phenotype);
                     count += 1;
                 }
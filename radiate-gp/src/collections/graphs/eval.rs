@@ -21,7 +21,8 @@ where
     /// Creates a new `GraphEvaluator` with the given `Graph`. Will cache the order of nodes in
     /// the `Graph` on the first iteration. On initialization the `GraphEvaluator` will cache the
     /// output size of the `Graph` to be used in the `reduce` method and create a vec of `Tracer`
-    /// which will be used to evaluate the `Graph` in the `reduce` method.
+    /// which will be used to evaluate the `Graph` in the `reduce` method. The history of the graph's
+    /// `Stateful` ops is cleared, so every evaluator starts from the same state.
     ///
     /// # Arguments
     /// * `graph` - The `Graph` to reduce.
@@ -30,6 +31,7 @@ where
         N: AsRef<[GraphNode<T>]>,
     {
         let nodes = graph.as_ref();
+        nodes.iter().for_each(|node| node.value().reset());
 
         GraphEvaluator {
             nodes,
@@ -69,10 +71,8 @@ where
             if node.incoming().is_empty() {
                 self.outputs[node.index()] = node.value().eval(input);
             } else {
-                let mut count = 0;
-                for incoming in node.incoming() {
+                for (count, incoming) in node.incoming().iter().enumerate() {
                     self.inputs[node.index()][count] = self.outputs[*incoming].clone();
-                    count += 1;
                 }
 
                 self.outputs[node.index()] = node.value().eval(&self.inputs[node.index()]);
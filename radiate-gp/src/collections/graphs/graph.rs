@@ -258,9 +258,7 @@ impl<T> Graph<T> {
         let mut seen = HashSet::new();
         let mut visited = self.get(target).outgoing().iter().collect::<Vec<&usize>>();
 
-        while !visited.is_empty() {
-            let node_index = visited.pop().unwrap();
-
+        while let Some(node_index) = visited.pop() {
             seen.insert(*node_index);
 
             if *node_index == source {
@@ -393,11 +391,86 @@ impl<T> FromIterator<GraphNode<T>> for Graph<T> {
     }
 }
 
+/// Introspection and export of a 'Graph'.
+impl<T> Graph<T> {
+    /// Returns every connection in the graph as a '(source, target)' pair of node indices, in
+    /// order of the source then the target.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = self
+            .nodes
+            .iter()
+            .flat_map(|node| {
+                node.outgoing()
+                    .iter()
+                    .map(move |target| (node.index(), *target))
+            })
+            .collect::<Vec<(usize, usize)>>();
+
+        edges.sort();
+        edges
+    }
+
+    /// Export the graph in the Graphviz DOT format. Every node is labeled with its index and
+    /// operation and shaped by its 'NodeType'. Recurrent nodes are drawn with dashed connections
+    /// and disabled nodes are grayed out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use radiate_gp::{Graph, NodeType, Op};
+    ///
+    /// let mut graph = Graph::<f32>::default();
+    /// let input = graph.insert(NodeType::Input, Op::var(0));
+    /// let output = graph.insert(NodeType::Output, Op::linear());
+    /// graph.attach(input, output);
+    ///
+    /// let dot = graph.to_dot();
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("0 -> 1;"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    rankdir=LR;\n");
+
+        for node in self.nodes.iter() {
+            let shape = match node.node_type() {
+                NodeType::Input => "box",
+                NodeType::Output => "doublecircle",
+                NodeType::Vertex => "circle",
+                NodeType::Edge => "point",
+            };
+            let style = match node.is_enabled() {
+                true => "",
+                false => ", style=filled, fillcolor=lightgray",
+            };
+
+            dot.push_str(&format!(
+                "    {} [label=\"{}: {}\", shape={}{}];\n",
+                node.index(),
+                node.index(),
+                node.value().name(),
+                shape,
+                style
+            ));
+        }
+
+        for (source, target) in self.edges() {
+            let style = match self.nodes[source].is_recurrent() {
+                true => " [style=dashed]",
+                false => "",
+            };
+
+            dot.push_str(&format!("    {} -> {}{};\n", source, target, style));
+        }
+
+        dot.push('}');
+        dot
+    }
+}
+
 impl<T: Debug + PartialEq + Clone> Debug for Graph<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Graph {{\n")?;
+        writeln!(f, "Graph {{")?;
         for node in self.as_ref() {
-            write!(f, "  {:?},\n", node)?;
+            writeln!(f, "  {:?},", node)?;
         }
         write!(f, "}}")
     }
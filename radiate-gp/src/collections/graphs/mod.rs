@@ -2,23 +2,29 @@ mod aggregate;
 mod builder;
 mod chromosome;
 mod codex;
+mod compress;
 mod crossover;
 mod eval;
 mod graph;
 mod iter;
+mod module;
 mod mutation;
 mod node;
 mod store;
+mod structure;
 mod transaction;
 
 pub use aggregate::GraphAggregate;
 pub use builder::GraphBuilder;
 pub use chromosome::GraphChromosome;
+pub use compress::{CompressedGraph, GraphCompressor};
 pub use crossover::GraphCrossover;
 pub use eval::GraphEvaluator;
 pub use graph::Graph;
 pub use iter::GraphTopologicalIterator;
+pub use module::GraphModule;
 pub use mutation::{GraphMutator, NodeMutate};
 pub use node::{Direction, GraphNode, NodeType};
 pub use store::NodeStore;
+pub use structure::{GraphMetric, GraphStructure};
 pub use transaction::GraphTransaction;
@@ -0,0 +1,138 @@
+use crate::collections::{Graph, GraphNode, NodeType};
+use crate::ops::{Arity, Op};
+use crate::Factory;
+
+/// A reusable subgraph that the `GraphBuilder` can stamp into a graph any number of times.
+///
+/// A module is a small collection of vertex and edge nodes, the connections between them, and
+/// the nodes that act as its ports - the `inputs` receive connections from outside the module and
+/// the `outputs` send them out of it. Node indices are local to the module: the first node added is
+/// `0`, the second `1`, and so on.
+///
+/// # Example
+/// ```rust
+/// use radiate_gp::*;
+///
+/// // sum -> weight -> tanh
+/// let module = GraphModule::new()
+///     .node(NodeType::Vertex, Op::linear())
+///     .node(NodeType::Edge, Op::weight())
+///     .node(NodeType::Vertex, Op::tanh())
+///     .connect(0, 1)
+///     .connect(1, 2)
+///     .inputs(vec![0])
+///     .outputs(vec![2]);
+///
+/// let codex = GraphBuilder::default()
+///     .modular(2, 1, &module, 3, Op::linear())
+///     .into_codex();
+/// ```
+#[derive(Clone, Default)]
+pub struct GraphModule<T> {
+    nodes: Vec<(NodeType, Op<T>)>,
+    connections: Vec<(usize, usize)>,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+impl<T: Clone> GraphModule<T> {
+    pub fn new() -> Self {
+        GraphModule {
+            nodes: Vec::new(),
+            connections: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Add a node to the module. Modules can only contain `Vertex` and `Edge` nodes - the inputs
+    /// and outputs of the graph belong to the graph itself.
+    pub fn node(mut self, node_type: NodeType, op: Op<T>) -> Self {
+        if node_type == NodeType::Input || node_type == NodeType::Output {
+            panic!("A GraphModule can only contain Vertex and Edge nodes");
+        }
+
+        self.nodes.push((node_type, op));
+        self
+    }
+
+    /// Connect the node at `from` to the node at `to`, both indices local to the module.
+    pub fn connect(mut self, from: usize, to: usize) -> Self {
+        if from >= self.nodes.len() || to >= self.nodes.len() {
+            panic!(
+                "GraphModule connection ({}, {}) is out of bounds for {} nodes",
+                from,
+                to,
+                self.nodes.len()
+            );
+        }
+
+        self.connections.push((from, to));
+        self
+    }
+
+    /// The nodes that receive connections from outside the module. Since the builder can't know
+    /// how many connections they will receive, their operations must accept any number of inputs.
+    pub fn inputs(mut self, inputs: Vec<usize>) -> Self {
+        for &index in inputs.iter() {
+            match self.nodes.get(index) {
+                Some((NodeType::Vertex, op)) if op.arity() == Arity::Any => {}
+                _ => panic!(
+                    "GraphModule input {} must be a Vertex whose operation accepts any number of inputs",
+                    index
+                ),
+            }
+        }
+
+        self.inputs = inputs;
+        self
+    }
+
+    /// The nodes that send connections out of the module.
+    pub fn outputs(mut self, outputs: Vec<usize>) -> Self {
+        for &index in outputs.iter() {
+            match self.nodes.get(index) {
+                Some((NodeType::Vertex, _)) => {}
+                _ => panic!("GraphModule output {} must be a Vertex", index),
+            }
+        }
+
+        self.outputs = outputs;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Push a copy of the module onto the end of the `graph`, returning the graph indices of the
+    /// copy's input and output ports. Each copy gets fresh instances of the module's operations, so
+    /// copies of a weight don't share its value.
+    pub fn instantiate(&self, graph: &mut Graph<T>) -> (Vec<usize>, Vec<usize>) {
+        if self.inputs.is_empty() || self.outputs.is_empty() {
+            panic!("A GraphModule needs at least one input and one output node");
+        }
+
+        let offset = graph.len();
+        for (index, (node_type, op)) in self.nodes.iter().enumerate() {
+            graph.push(GraphNode::new(
+                offset + index,
+                *node_type,
+                op.new_instance(()),
+            ));
+        }
+
+        for (from, to) in self.connections.iter() {
+            graph.attach(offset + from, offset + to);
+        }
+
+        (
+            self.inputs.iter().map(|index| offset + index).collect(),
+            self.outputs.iter().map(|index| offset + index).collect(),
+        )
+    }
+}
@@ -1,7 +1,7 @@
 use radiate::Chromosome;
 use radiate::{
-    random_provider, timer::Timer, Alter, AlterAction, EngineCompoment, Metric, Mutate, Population,
-    Valid,
+    random_provider::RngHandle, timer::Timer, Alter, AlterAction, EngineCompoment, Metric, Mutate,
+    Population, Valid,
 };
 
 use super::transaction::GraphTransaction;
@@ -68,10 +68,11 @@ impl GraphMutator {
         node_type: &NodeType,
         factory: &NodeStore<T>,
         recurrent: bool,
+        rng: &RngHandle,
     ) -> bool {
         let mut transaction = GraphTransaction::new(graph);
 
-        if !self.try_add_node(&mut transaction, node_type, factory, recurrent) {
+        if !self.try_add_node(&mut transaction, node_type, factory, recurrent, rng) {
             transaction.rollback();
             return false;
         }
@@ -85,6 +86,7 @@ impl GraphMutator {
         node_type: &NodeType,
         factory: &NodeStore<T>,
         is_recurrent: bool,
+        rng: &RngHandle,
     ) -> bool
     where
         T: Clone + Default + PartialEq,
@@ -102,6 +104,7 @@ impl GraphMutator {
                     target_node_index,
                     node_type,
                     factory,
+                    rng,
                 )
             } else {
                 self.try_edge_insertion(
@@ -110,6 +113,7 @@ impl GraphMutator {
                     target_node_index,
                     node_type,
                     factory,
+                    rng,
                 )
             }
         } else {
@@ -120,6 +124,7 @@ impl GraphMutator {
                 node_type,
                 factory,
                 is_recurrent,
+                rng,
             )
         }
     }
@@ -131,6 +136,7 @@ impl GraphMutator {
         target_node: usize,
         node_type: &NodeType,
         factory: &NodeStore<T>,
+        rng: &RngHandle,
     ) -> bool
     where
         T: Clone + Default + PartialEq,
@@ -171,7 +177,7 @@ impl GraphMutator {
             transaction.attach(new_target_edge_index, target_node);
         }
 
-        self.complete_node_arity(transaction, new_node_index, factory, false)
+        self.complete_node_arity(transaction, new_node_index, factory, false, rng)
     }
 
     fn try_backward_edge_insertion<T>(
@@ -181,6 +187,7 @@ impl GraphMutator {
         target_idx: usize,
         node_type: &NodeType,
         factory: &NodeStore<T>,
+        rng: &RngHandle,
     ) -> bool
     where
         T: Clone + Default + PartialEq,
@@ -257,7 +264,7 @@ impl GraphMutator {
                 transaction.attach(new_target_edge_index, outgoing_idx);
             }
 
-            self.complete_node_arity(transaction, new_node_index, factory, true)
+            self.complete_node_arity(transaction, new_node_index, factory, true, rng)
         } else {
             if !&transaction
                 .as_ref()
@@ -274,10 +281,11 @@ impl GraphMutator {
             transaction.attach(new_node_index, target_idx);
             transaction.detach(source_idx, target_idx);
 
-            self.complete_node_arity(transaction, new_node_index, factory, true)
+            self.complete_node_arity(transaction, new_node_index, factory, true, rng)
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn try_normal_insertion<T>(
         &self,
         transaction: &mut GraphTransaction<T>,
@@ -286,6 +294,7 @@ impl GraphMutator {
         node_type: &NodeType,
         factory: &NodeStore<T>,
         is_recurrent: bool,
+        rng: &RngHandle,
     ) -> bool
     where
         T: Clone + Default + PartialEq,
@@ -304,7 +313,7 @@ impl GraphMutator {
         transaction.attach(node_index, target_node);
         transaction.detach(source_node, target_node);
 
-        self.complete_node_arity(transaction, node_index, factory, is_recurrent)
+        self.complete_node_arity(transaction, node_index, factory, is_recurrent, rng)
     }
 
     fn complete_node_arity<T>(
@@ -313,6 +322,7 @@ impl GraphMutator {
         node_index: usize,
         factory: &NodeStore<T>,
         is_recurrent: bool,
+        rng: &RngHandle,
     ) -> bool
     where
         T: Clone + Default + PartialEq,
@@ -326,7 +336,7 @@ impl GraphMutator {
             }
             Arity::Exact(arity) => {
                 for _ in 0..arity - 1 {
-                    if random_provider::random::<f32>() < 0.05 {
+                    if rng.random::<f32>() < 0.05 {
                         let input_node = factory.new_instance((transaction.len(), NodeType::Input));
                         let input_index = transaction.add_node(input_node);
                         transaction.attach(input_index, node_index);
@@ -377,6 +387,7 @@ where
         &self,
         population: &mut Population<GraphChromosome<T>>,
         generation: i32,
+        rng: &RngHandle,
     ) -> Vec<Metric> {
         let timer = Timer::new();
         let mut count = 0;
@@ -384,13 +395,13 @@ where
             let phenotype = &mut population[i];
             let genotype = &mut phenotype.genotype();
 
-            let chromosome_index = random_provider::random::<usize>() % genotype.len();
+            let chromosome_index = rng.random::<usize>() % genotype.len();
 
             let chromosome = &mut phenotype.genotype_mut()[chromosome_index];
 
-            if self.mutate_chromosome(chromosome) > 0 {
+            if self.mutate_chromosome(chromosome, rng) > 0 {
                 count += 1;
-                phenotype.set_score(None);
+                phenotype.mark_dirty();
                 phenotype.generation = generation;
             }
         }
@@ -402,10 +413,10 @@ where
         vec![result]
     }
 
-    fn mutate_chromosome(&self, chromosome: &mut GraphChromosome<T>) -> i32 {
-        let mutation = random_provider::choose(&self.mutations);
+    fn mutate_chromosome(&self, chromosome: &mut GraphChromosome<T>, rng: &RngHandle) -> i32 {
+        let mutation = rng.choose(&self.mutations);
 
-        if random_provider::random::<f32>() > mutation.rate() {
+        if rng.random::<f32>() > mutation.rate() {
             return 0;
         }
 
@@ -418,6 +429,7 @@ where
             &mutation.node_type(),
             &node_fact,
             mutation.is_recurrent(),
+            rng,
         ) {
             chromosome.set_nodes(graph.into_iter().collect::<Vec<GraphNode<T>>>());
             return 1;
@@ -8,6 +8,12 @@ pub struct NodeStore<T> {
     values: HashMap<NodeType, Vec<Op<T>>>,
 }
 
+impl<T> Default for NodeStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> NodeStore<T> {
     pub fn new() -> Self {
         NodeStore {
@@ -0,0 +1,329 @@
+use std::collections::{HashMap, HashSet};
+
+use radiate::{Complexity, Genotype, Measurable};
+
+use crate::collections::{Direction, Graph, GraphChromosome, GraphNode, NodeType};
+
+/// Structural measurements of a graph, taken over its active part - the enabled nodes that lie on
+/// a path from an input to an output. Nodes that can't influence the outputs (or be influenced by
+/// the inputs) are ignored, so two graphs that compute the same thing measure the same.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphStructure {
+    /// The number of active nodes, `Edge` nodes included.
+    pub active_nodes: usize,
+    /// The number of connections between active nodes.
+    pub active_edges: usize,
+    /// The number of `Vertex` nodes on the longest forward path from an input to an output.
+    pub depth: usize,
+    /// The number of active connections that feed a value back - out of a recurrent node or
+    /// from a node to itself.
+    pub recurrent_edges: usize,
+    /// Newman's modularity Q of the active nodes, treating connections as undirected and grouping
+    /// the nodes into communities by label propagation. Close to 0 for a graph without community
+    /// structure, approaching 1 for one made of loosely connected modules.
+    pub modularity: f32,
+}
+
+impl GraphStructure {
+    pub fn of<T>(nodes: &[GraphNode<T>]) -> Self {
+        let active = active_nodes(nodes);
+
+        let connections = active
+            .iter()
+            .flat_map(|&source| {
+                nodes[source]
+                    .outgoing()
+                    .iter()
+                    .filter(|target| active.contains(target))
+                    .map(move |&target| (source, target))
+            })
+            .collect::<Vec<(usize, usize)>>();
+
+        let recurrent_edges = connections
+            .iter()
+            .filter(|(source, target)| is_recurrent(nodes, *source, *target))
+            .count();
+
+        GraphStructure {
+            active_nodes: active.len(),
+            active_edges: connections.len(),
+            depth: depth(nodes, &active),
+            recurrent_edges,
+            modularity: modularity(&active, &connections),
+        }
+    }
+}
+
+/// The structural measurements that can be recorded as per-generation metrics with
+/// `GeneticEngineParams::genotype_metric`, or used as secondary objectives with `objective_fn`.
+///
+/// # Example
+/// ```rust
+/// use radiate::*;
+/// use radiate_gp::*;
+///
+/// let codex = GraphBuilder::default()
+///     .weighted_acyclic(2, 1, Op::linear())
+///     .into_codex();
+///
+/// let engine = GraphMetric::ALL.iter().fold(
+///     GeneticEngine::from_codex(codex).population_size(10),
+///     |params, metric| params.genotype_metric(metric.name(), move |geno| metric.of(geno)),
+/// )
+/// .objective_fn(|graph: Graph<f32>| graph.eval(&[1.0, 2.0][..])[0])
+/// .objective_fn(|graph: Graph<f32>| GraphMetric::ActiveNodes.value(&graph.structure()))
+/// .multi_objective(vec![Optimize::Maximize, Optimize::Minimize])
+/// .offspring_selector(TournamentSelector::new(3))
+/// .survivor_selector(NSGA2Selector::new())
+/// .alter(alters!(GraphMutator::new(vec![NodeMutate::Edge(0.1, false)])))
+/// .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 2);
+/// assert!(result.metrics.get(GraphMetric::Depth.name()).is_some());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphMetric {
+    ActiveNodes,
+    ActiveEdges,
+    Depth,
+    RecurrentEdges,
+    Modularity,
+}
+
+impl GraphMetric {
+    pub const ALL: [GraphMetric; 5] = [
+        GraphMetric::ActiveNodes,
+        GraphMetric::ActiveEdges,
+        GraphMetric::Depth,
+        GraphMetric::RecurrentEdges,
+        GraphMetric::Modularity,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphMetric::ActiveNodes => "Active Nodes",
+            GraphMetric::ActiveEdges => "Active Edges",
+            GraphMetric::Depth => "Graph Depth",
+            GraphMetric::RecurrentEdges => "Recurrent Edges",
+            GraphMetric::Modularity => "Modularity",
+        }
+    }
+
+    pub fn value(&self, structure: &GraphStructure) -> f32 {
+        match self {
+            GraphMetric::ActiveNodes => structure.active_nodes as f32,
+            GraphMetric::ActiveEdges => structure.active_edges as f32,
+            GraphMetric::Depth => structure.depth as f32,
+            GraphMetric::RecurrentEdges => structure.recurrent_edges as f32,
+            GraphMetric::Modularity => structure.modularity,
+        }
+    }
+
+    /// The metric of the graph a genotype decodes to - its first chromosome, like the `GraphCodex`.
+    pub fn of<T>(&self, genotype: &Genotype<GraphChromosome<T>>) -> f32
+    where
+        T: Clone + PartialEq + Default,
+    {
+        genotype
+            .iter()
+            .next()
+            .map(|chromosome| self.value(&chromosome.structure()))
+            .unwrap_or(0.0)
+    }
+}
+
+impl<T> Graph<T> {
+    pub fn structure(&self) -> GraphStructure {
+        GraphStructure::of(self.as_ref())
+    }
+}
+
+impl<T> GraphChromosome<T> {
+    pub fn structure(&self) -> GraphStructure {
+        GraphStructure::of(self.as_ref())
+    }
+}
+
+impl<T> Measurable for GraphChromosome<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complexity(&self, measure: Complexity) -> Option<f32> {
+        match measure {
+            Complexity::GraphEdges => Some(self.structure().active_edges as f32),
+            _ => None,
+        }
+    }
+}
+
+fn is_recurrent<T>(nodes: &[GraphNode<T>], source: usize, target: usize) -> bool {
+    source == target || nodes[source].direction() == Direction::Backward
+}
+
+/// The enabled nodes reachable from an input that can also reach an output, in index order.
+fn active_nodes<T>(nodes: &[GraphNode<T>]) -> Vec<usize> {
+    let reach = |start: NodeType, next: fn(&GraphNode<T>) -> &HashSet<usize>| {
+        let mut seen = HashSet::new();
+        let mut stack = nodes
+            .iter()
+            .filter(|node| node.node_type() == start && node.is_enabled())
+            .map(|node| node.index())
+            .collect::<Vec<usize>>();
+
+        while let Some(index) = stack.pop() {
+            if seen.insert(index) {
+                stack.extend(
+                    next(&nodes[index])
+                        .iter()
+                        .filter(|other| nodes[**other].is_enabled()),
+                );
+            }
+        }
+
+        seen
+    };
+
+    let from_inputs = reach(NodeType::Input, GraphNode::outgoing);
+    let to_outputs = reach(NodeType::Output, GraphNode::incoming);
+
+    let mut active = from_inputs
+        .intersection(&to_outputs)
+        .cloned()
+        .collect::<Vec<usize>>();
+
+    active.sort();
+    active
+}
+
+/// The most `Vertex` nodes on a forward path from an input to an output. Recurrent connections are
+/// skipped, as is any connection that would revisit a node already on the path.
+fn depth<T>(nodes: &[GraphNode<T>], active: &[usize]) -> usize {
+    fn longest<T>(
+        nodes: &[GraphNode<T>],
+        active: &HashSet<usize>,
+        index: usize,
+        path: &mut HashSet<usize>,
+        memo: &mut HashMap<usize, Option<usize>>,
+    ) -> Option<usize> {
+        if let Some(depth) = memo.get(&index) {
+            return *depth;
+        }
+
+        let node = &nodes[index];
+        let own = usize::from(node.node_type() == NodeType::Vertex);
+
+        if node.node_type() == NodeType::Output {
+            memo.insert(index, Some(own));
+            return Some(own);
+        }
+
+        path.insert(index);
+        let mut best = None;
+        for &target in node.outgoing() {
+            if !active.contains(&target)
+                || path.contains(&target)
+                || is_recurrent(nodes, index, target)
+            {
+                continue;
+            }
+
+            if let Some(rest) = longest(nodes, active, target, path, memo) {
+                best = Some(best.unwrap_or(0).max(rest + own));
+            }
+        }
+        path.remove(&index);
+
+        memo.insert(index, best);
+        best
+    }
+
+    let active_set = active.iter().cloned().collect::<HashSet<usize>>();
+    let mut memo = HashMap::new();
+
+    active
+        .iter()
+        .filter(|index| nodes[**index].node_type() == NodeType::Input)
+        .filter_map(|&index| longest(nodes, &active_set, index, &mut HashSet::new(), &mut memo))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Newman's modularity of the undirected graph made of the `connections` between the `active` nodes,
+/// with the communities found by (deterministic) label propagation.
+fn modularity(active: &[usize], connections: &[(usize, usize)]) -> f32 {
+    let links = connections
+        .iter()
+        .filter(|(source, target)| source != target)
+        .map(|(source, target)| (*source.min(target), *source.max(target)))
+        .collect::<HashSet<(usize, usize)>>();
+
+    if links.is_empty() {
+        return 0.0;
+    }
+
+    let mut neighbors = HashMap::<usize, Vec<usize>>::new();
+    for &(one, two) in links.iter() {
+        neighbors.entry(one).or_default().push(two);
+        neighbors.entry(two).or_default().push(one);
+    }
+
+    let mut labels = active
+        .iter()
+        .map(|index| (*index, *index))
+        .collect::<HashMap<usize, usize>>();
+
+    for _ in 0..active.len().max(1) {
+        let mut changed = false;
+        for index in active.iter() {
+            let Some(adjacent) = neighbors.get(index) else {
+                continue;
+            };
+
+            let mut counts = HashMap::<usize, usize>::new();
+            for neighbor in adjacent {
+                *counts.entry(labels[neighbor]).or_default() += 1;
+            }
+
+            let current = labels[index];
+            let most = counts.values().max().cloned().unwrap_or(0);
+            if counts.get(&current) == Some(&most) {
+                continue;
+            }
+
+            let label = counts
+                .iter()
+                .filter(|(_, count)| **count == most)
+                .map(|(label, _)| *label)
+                .min()
+                .unwrap();
+
+            labels.insert(*index, label);
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let edge_count = links.len() as f32;
+    let mut inside = HashMap::<usize, f32>::new();
+    let mut degree = HashMap::<usize, f32>::new();
+
+    for &(one, two) in links.iter() {
+        if labels[&one] == labels[&two] {
+            *inside.entry(labels[&one]).or_default() += 1.0;
+        }
+
+        *degree.entry(labels[&one]).or_default() += 1.0;
+        *degree.entry(labels[&two]).or_default() += 1.0;
+    }
+
+    degree
+        .iter()
+        .map(|(label, total)| {
+            let within = inside.get(label).cloned().unwrap_or(0.0);
+            within / edge_count - (total / (2.0 * edge_count)).powi(2)
+        })
+        .sum()
+}
@@ -41,6 +41,10 @@ impl<'a, T: Clone + Default + PartialEq> GraphTransaction<'a, T> {
         self.graph.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
     pub fn insert_vertex(&mut self, value: impl Into<Op<T>>) -> usize {
         let node = GraphNode::new(self.graph.len(), super::NodeType::Vertex, value);
         self.add_node(node)
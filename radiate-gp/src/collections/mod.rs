@@ -3,8 +3,9 @@ pub mod program;
 pub mod trees;
 
 pub use graphs::{
-    Direction, Graph, GraphAggregate, GraphBuilder, GraphChromosome, GraphCrossover,
-    GraphEvaluator, GraphMutator, GraphNode, GraphTopologicalIterator, NodeMutate, NodeType,
+    CompressedGraph, Direction, Graph, GraphAggregate, GraphBuilder, GraphChromosome,
+    GraphCompressor, GraphCrossover, GraphEvaluator, GraphMetric, GraphModule, GraphMutator,
+    GraphNode, GraphStructure, GraphTopologicalIterator, NodeMutate, NodeType,
 };
 pub use program::Regressor;
 
@@ -28,19 +28,16 @@ where
 {
     fn eval_mut(&mut self, input: &Vec<Vec<T>>) -> Vec<Vec<T>> {
         match self {
-            Regressor::Tree(tree) => input
-                .into_iter()
-                .map(|input| vec![tree.eval(&input)])
-                .collect(),
+            Regressor::Tree(tree) => input.iter().map(|input| vec![tree.eval(input)]).collect(),
             Regressor::Forest(forest) => input
-                .into_iter()
-                .map(|input| forest.iter().map(|tree| tree.eval(&input)).collect())
+                .iter()
+                .map(|input| forest.iter().map(|tree| tree.eval(input)).collect())
                 .collect(),
             Regressor::Graph(graph) => {
                 let mut evaluator = GraphEvaluator::new(graph);
                 input
-                    .into_iter()
-                    .map(|input| evaluator.eval_mut(&input))
+                    .iter()
+                    .map(|input| evaluator.eval_mut(input))
                     .collect()
             }
         }
@@ -1,5 +1,6 @@
 use std::sync::{Arc, RwLock};
 
+use super::chromosome::Constraint;
 use crate::collections::{Tree, TreeNode};
 use crate::{Builder, Op};
 use radiate::random_provider;
@@ -8,7 +9,7 @@ pub struct TreeBuilder<T> {
     depth: usize,
     gates: Arc<RwLock<Vec<Op<T>>>>,
     leafs: Arc<RwLock<Vec<Op<T>>>>,
-    constraint: Option<Arc<Box<dyn Fn(&TreeNode<T>) -> bool>>>,
+    constraint: Option<Constraint<TreeNode<T>>>,
 }
 
 impl<T> TreeBuilder<T> {
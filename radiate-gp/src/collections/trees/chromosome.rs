@@ -1,8 +1,8 @@
 use crate::{Op, TreeNode};
-use radiate::{Chromosome, Valid};
+use radiate::{Chromosome, Complexity, Measurable, Valid};
 use std::sync::{Arc, RwLock};
 
-type Constraint<N> = Arc<Box<dyn Fn(&N) -> bool>>;
+pub(crate) type Constraint<N> = Arc<Box<dyn Fn(&N) -> bool>>;
 
 #[derive(Clone, Default)]
 pub struct TreeChromosome<T> {
@@ -51,6 +51,20 @@ where
     type Gene = TreeNode<T>;
 }
 
+impl<T> Measurable for TreeChromosome<T>
+where
+    T: Clone + PartialEq + Default,
+{
+    fn complexity(&self, measure: Complexity) -> Option<f32> {
+        match measure {
+            Complexity::TreeSize => {
+                Some(self.nodes.iter().map(|node| node.size()).sum::<usize>() as f32)
+            }
+            _ => None,
+        }
+    }
+}
+
 impl<T> Valid for TreeChromosome<T> {
     fn is_valid(&self) -> bool {
         for gene in &self.nodes {
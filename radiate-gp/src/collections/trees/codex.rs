@@ -1,3 +1,4 @@
+use super::chromosome::Constraint;
 use crate::collections::trees::TreeBuilder;
 use crate::collections::{Tree, TreeChromosome, TreeNode};
 
@@ -7,7 +8,7 @@ use std::sync::Arc;
 
 pub struct TreeCodex<T: Clone> {
     builder: TreeBuilder<T>,
-    constraint: Option<Arc<Box<dyn Fn(&TreeNode<T>) -> bool>>>,
+    constraint: Option<Constraint<TreeNode<T>>>,
 }
 
 impl<T: Clone + Default> TreeCodex<T> {
@@ -78,7 +79,7 @@ pub struct ProgramTree {
 pub struct ProgramTreeCodex {
     num_trees: usize,
     builder: TreeBuilder<f32>,
-    constraint: Option<Arc<Box<dyn Fn(&TreeNode<f32>) -> bool>>>,
+    constraint: Option<Constraint<TreeNode<f32>>>,
 }
 
 impl ProgramTreeCodex {
@@ -1,7 +1,7 @@
 use super::TreeChromosome;
 
 use radiate::engines::genome::*;
-use radiate::{random_provider, Alter, AlterAction, Crossover, EngineCompoment};
+use radiate::{random_provider::RngHandle, Alter, AlterAction, Crossover, EngineCompoment};
 
 pub struct TreeCrossover {
     rate: f32,
@@ -41,9 +41,10 @@ where
         &self,
         chrom_one: &mut TreeChromosome<T>,
         chrom_two: &mut TreeChromosome<T>,
+        rng: &RngHandle,
     ) -> i32 {
-        let swap_one_index = random_provider::random::<usize>() % chrom_one.len();
-        let swap_two_index = random_provider::random::<usize>() % chrom_two.len();
+        let swap_one_index = rng.random::<usize>() % chrom_one.len();
+        let swap_two_index = rng.random::<usize>() % chrom_two.len();
 
         let one_node = &mut chrom_one.as_mut()[swap_one_index];
         let two_node = &mut chrom_two.as_mut()[swap_two_index];
@@ -51,8 +52,8 @@ where
         let one_size = one_node.size();
         let two_size = two_node.size();
 
-        let one_rand_index = random_provider::random::<usize>() % one_size;
-        let two_rand_index = random_provider::random::<usize>() % two_size;
+        let one_rand_index = rng.random::<usize>() % one_size;
+        let two_rand_index = rng.random::<usize>() % two_size;
 
         if one_rand_index < 1 || two_rand_index < 1 {
             return 0;
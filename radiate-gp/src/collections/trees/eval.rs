@@ -31,7 +31,7 @@ impl<T: Clone> Eval<[T], T> for TreeNode<T> {
                     let mut inputs = Vec::with_capacity(children.len());
 
                     for child in children {
-                        inputs.push(eval(&child, curr_input));
+                        inputs.push(eval(child, curr_input));
                     }
 
                     return node.value().eval(&inputs);
@@ -75,7 +75,7 @@ mod tests {
         root.add_child(TreeNode::new(Op::value(1.0)));
         root.add_child(TreeNode::new(Op::value(2.0)));
 
-        let result = root.eval(&vec![]);
+        let result = root.eval(&[]);
 
         assert_eq!(result, 3.0);
     }
@@ -96,9 +96,9 @@ mod tests {
                 ),
         );
 
-        let nine = tree.eval(&vec![1_f32]);
-        let ten = tree.eval(&vec![2_f32]);
-        let eleven = tree.eval(&vec![3_f32]);
+        let nine = tree.eval(&[1_f32]);
+        let ten = tree.eval(&[2_f32]);
+        let eleven = tree.eval(&[3_f32]);
 
         assert_eq!(nine, 9.0);
         assert_eq!(ten, 10.0);
@@ -2,23 +2,23 @@ use crate::collections::{Tree, TreeNode};
 use std::collections::VecDeque;
 
 pub trait TreeIterator<T> {
-    fn iter_pre_order(&self) -> PreOrderIterator<T>;
-    fn iter_post_order(&self) -> PostOrderIterator<T>;
-    fn iter_breadth_first(&self) -> TreeBreadthFirstIterator<T>;
+    fn iter_pre_order(&self) -> PreOrderIterator<'_, T>;
+    fn iter_post_order(&self) -> PostOrderIterator<'_, T>;
+    fn iter_breadth_first(&self) -> TreeBreadthFirstIterator<'_, T>;
 }
 
 impl<T> TreeIterator<T> for TreeNode<T> {
-    fn iter_pre_order(&self) -> PreOrderIterator<T> {
+    fn iter_pre_order(&self) -> PreOrderIterator<'_, T> {
         PreOrderIterator { stack: vec![self] }
     }
 
-    fn iter_post_order(&self) -> PostOrderIterator<T> {
+    fn iter_post_order(&self) -> PostOrderIterator<'_, T> {
         PostOrderIterator {
             stack: vec![(self, false)],
         }
     }
 
-    fn iter_breadth_first(&self) -> TreeBreadthFirstIterator<T> {
+    fn iter_breadth_first(&self) -> TreeBreadthFirstIterator<'_, T> {
         TreeBreadthFirstIterator {
             queue: vec![self].into_iter().collect(),
         }
@@ -26,7 +26,7 @@ impl<T> TreeIterator<T> for TreeNode<T> {
 }
 
 impl<T> TreeIterator<T> for Tree<T> {
-    fn iter_pre_order(&self) -> PreOrderIterator<T> {
+    fn iter_pre_order(&self) -> PreOrderIterator<'_, T> {
         PreOrderIterator {
             stack: self
                 .root()
@@ -34,14 +34,14 @@ impl<T> TreeIterator<T> for Tree<T> {
         }
     }
 
-    fn iter_post_order(&self) -> PostOrderIterator<T> {
+    fn iter_post_order(&self) -> PostOrderIterator<'_, T> {
         PostOrderIterator {
             stack: self
                 .root()
                 .map_or(Vec::new(), |root| vec![(root, false)].into_iter().collect()),
         }
     }
-    fn iter_breadth_first(&self) -> TreeBreadthFirstIterator<T> {
+    fn iter_breadth_first(&self) -> TreeBreadthFirstIterator<'_, T> {
         TreeBreadthFirstIterator {
             queue: self
                 .root()
@@ -58,14 +58,13 @@ impl<'a, T> Iterator for PreOrderIterator<'a, T> {
     type Item = &'a TreeNode<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.stack.pop().map(|node| {
+        self.stack.pop().inspect(|node| {
             // Push children in reverse order for correct traversal
             if let Some(children) = node.children() {
                 for child in children.iter().rev() {
                     self.stack.push(child);
                 }
             }
-            node
         })
     }
 }
@@ -1,6 +1,6 @@
 use super::TreeChromosome;
 use crate::{Op, TreeNode};
-use radiate::{random_provider, Alter, AlterAction, EngineCompoment, Gene, Mutate};
+use radiate::{random_provider::RngHandle, Alter, AlterAction, EngineCompoment, Gene, Mutate};
 use std::sync::{Arc, RwLock};
 
 pub struct TreeMutator {
@@ -17,6 +17,7 @@ impl TreeMutator {
         node: &mut TreeNode<T>,
         leafs: &Arc<RwLock<Vec<Op<T>>>>,
         gates: &Arc<RwLock<Vec<Op<T>>>>,
+        rng: &RngHandle,
     ) -> i32
     where
         T: Clone + PartialEq + Default,
@@ -24,14 +25,14 @@ impl TreeMutator {
         let mut count = 0;
 
         if node.is_leaf() {
-            if random_provider::random::<f32>() < self.rate {
-                let new_leaf = random_provider::choose(&(*leafs).read().unwrap()).clone();
+            if rng.random::<f32>() < self.rate {
+                let new_leaf = rng.choose(&(*leafs).read().unwrap()).clone();
                 (*node) = node.with_allele(&new_leaf);
                 count += 1;
             }
         } else {
-            if random_provider::random::<f32>() < self.rate {
-                let new_gate = random_provider::choose(&(*gates).read().unwrap()).clone();
+            if rng.random::<f32>() < self.rate {
+                let new_gate = rng.choose(&(*gates).read().unwrap()).clone();
 
                 if new_gate.arity() == node.value().arity() {
                     (*node) = node.with_allele(&new_gate);
@@ -40,7 +41,7 @@ impl TreeMutator {
             }
 
             for child in node.children_mut().unwrap() {
-                count += self.mutate_node(child, leafs, gates);
+                count += self.mutate_node(child, leafs, gates, rng);
             }
         }
 
@@ -71,11 +72,11 @@ impl<T> Mutate<TreeChromosome<T>> for TreeMutator
 where
     T: Clone + PartialEq + Default,
 {
-    fn mutate_chromosome(&self, chromosome: &mut TreeChromosome<T>) -> i32 {
+    fn mutate_chromosome(&self, chromosome: &mut TreeChromosome<T>, rng: &RngHandle) -> i32 {
         let leafs = chromosome.get_leafs();
         let gates = chromosome.get_gates();
         let root = chromosome.root_mut();
 
-        self.mutate_node(root, &leafs, &gates)
+        self.mutate_node(root, &leafs, &gates, rng)
     }
 }
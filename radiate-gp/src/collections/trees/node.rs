@@ -1,5 +1,6 @@
 use crate::Op;
 use radiate::engines::genome::gene::{Gene, Valid};
+use std::fmt::Display;
 
 use super::TreeIterator;
 use crate::ops::operation::Arity;
@@ -102,6 +103,54 @@ impl<T> TreeNode<T> {
     }
 }
 
+impl<T: Display> TreeNode<T> {
+    /// Format the subtree as an expression string. The arithmetic operations are written infix
+    /// (e.g. `(var_0 + 2) * 3`), every other operation as a function call (e.g. `sin(var_0)`).
+    pub fn to_expression(&self) -> String {
+        self.format_expression(false)
+    }
+
+    fn format_expression(&self, nested: bool) -> String {
+        let children = self.children.as_deref().unwrap_or_default();
+        let symbol = match self.value.name() {
+            "add" => Some("+"),
+            "sub" => Some("-"),
+            "mul" => Some("*"),
+            "div" => Some("/"),
+            "pow" => Some("^"),
+            _ => None,
+        };
+
+        match (&self.value, symbol, children.len()) {
+            (_, Some(symbol), 2) => {
+                let expression = format!(
+                    "{} {} {}",
+                    children[0].format_expression(true),
+                    symbol,
+                    children[1].format_expression(true)
+                );
+
+                match nested {
+                    true => format!("({})", expression),
+                    false => expression,
+                }
+            }
+            (Op::Value(value, _), _, 0) => value.to_string(),
+            (Op::MutableConst { value, .. }, _, 0) => value.to_string(),
+            (op, _, 0) => op.name().to_string(),
+            (op, _, _) => format!(
+                "{}({})",
+                op.name(),
+                children
+                    .iter()
+                    .map(|child| child.format_expression(false))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
 impl<T: Clone> Clone for TreeNode<T> {
     fn clone(&self) -> Self {
         TreeNode {
@@ -151,9 +200,7 @@ impl<T> Valid for TreeNode<T> {
                     }
                 }
                 Arity::Exact(n) => {
-                    if node.children.is_none()
-                        || node.children.as_ref().unwrap().len() != n as usize
-                    {
+                    if node.children.is_none() || node.children.as_ref().unwrap().len() != n {
                         return false;
                     }
                 }
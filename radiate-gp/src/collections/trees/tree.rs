@@ -1,7 +1,7 @@
 use crate::collections::TreeIterator;
 use crate::collections::TreeNode;
 
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 #[derive(Clone, PartialEq, Default)]
 pub struct Tree<T> {
@@ -34,6 +34,16 @@ impl<T> Tree<T> {
     }
 }
 
+impl<T: Display> Tree<T> {
+    /// Format the tree as an expression string (see `TreeNode::to_expression`). An empty tree is
+    /// an empty string.
+    pub fn to_expression(&self) -> String {
+        self.root
+            .as_ref()
+            .map_or(String::new(), |node| node.to_expression())
+    }
+}
+
 impl<T> AsRef<TreeNode<T>> for Tree<T> {
     fn as_ref(&self) -> &TreeNode<T> {
         self.root.as_ref().unwrap()
@@ -48,9 +58,9 @@ impl<T> AsMut<TreeNode<T>> for Tree<T> {
 
 impl<T: Debug> Debug for Tree<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Tree {{\n")?;
+        writeln!(f, "Tree {{")?;
         for node in self.iter_breadth_first() {
-            write!(f, "  {:?},\n", node.value())?;
+            writeln!(f, "  {:?},", node.value())?;
         }
         write!(f, "}}")
     }
@@ -88,4 +98,20 @@ mod test {
 
         assert_eq!(values_one, vec![3.0, 2.0]);
     }
+
+    #[test]
+    fn test_tree_to_expression() {
+        let tree = Tree::new(
+            TreeNode::new(Op::mul())
+                .attach(
+                    TreeNode::new(Op::add())
+                        .attach(TreeNode::new(Op::var(0)))
+                        .attach(TreeNode::new(Op::value(2.0))),
+                )
+                .attach(TreeNode::new(Op::sin()).attach(TreeNode::new(Op::var(1)))),
+        );
+
+        assert_eq!(tree.to_expression(), "(var_0 + 2) * sin(var_1)");
+        assert_eq!(Tree::<f32>::default().to_expression(), "");
+    }
 }
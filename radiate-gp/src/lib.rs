@@ -1,9 +1,16 @@
 pub mod collections;
 pub mod ops;
+pub mod presets;
 pub mod regression;
 
 pub use collections::*;
+#[cfg(feature = "timeseries")]
+pub use ops::get_timeseries_operations;
 pub use ops::{
-    get_activation_operations, get_all_operations, get_math_operations, Op, OperationMutator,
+    get_activation_operations, get_all_operations, get_cppn_operations, get_math_operations,
+    FitnessExpression, Op, OperationMutator,
+};
+pub use presets::{NeuroevolutionPreset, SymbolicRegressionPreset};
+pub use regression::{
+    Accuracy, AccuracyResult, DataSet, Fold, Loss, Prediction, Predictor, Regression, RollingOrigin,
 };
-pub use regression::{Accuracy, AccuracyResult, DataSet, Loss, Regression};
@@ -0,0 +1,306 @@
+use std::str::FromStr;
+
+use crate::collections::{Tree, TreeNode};
+use crate::ops::{get_all_operations, Arity, Op};
+use crate::Eval;
+
+/// A fitness function written as an arithmetic expression over named values, e.g.
+/// `"mse + 0.01 * tree_size"`, so a fitness can come from configuration instead of code.
+///
+/// The expression is parsed into a `Tree<f32>` of the same `Op`s the trees evolve with: numbers
+/// become constants, names become variables, `+ - * / ^` and unary `-` become `add`, `sub`, `mul`,
+/// `div`, `pow` and `neg`, and `name(a, b, ..)` calls the operation of that name from
+/// `get_all_operations` (e.g. `sqrt`, `abs`, `log`, `max`, `tanh`). The values of the variables are
+/// supplied when the expression is evaluated - typically computed from the decoded individual and
+/// the dataset it is scored on.
+///
+/// # Example
+/// ```rust
+/// use radiate::*;
+/// use radiate_gp::*;
+///
+/// let expression = FitnessExpression::parse("abs(target - sum) + 0.1 * max(size, 2)").unwrap();
+/// assert_eq!(expression.variables(), &["target", "sum", "size"]);
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::<i32>::new(1, 5, 0, 10))
+///     .minimizing()
+///     .fitness_fn(expression.fitness_fn(|genes: &Vec<Vec<i32>>, name| match name {
+///         "target" => 20.0,
+///         "sum" => genes[0].iter().sum::<i32>() as f32,
+///         "size" => genes[0].len() as f32,
+///         _ => unreachable!(),
+///     }))
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.score().as_f32() <= 0.5 || ctx.index > 500);
+/// ```
+#[derive(Clone)]
+pub struct FitnessExpression {
+    tree: Tree<f32>,
+    variables: Vec<String>,
+}
+
+impl FitnessExpression {
+    /// Parse an expression, returning a message describing the problem if it isn't valid.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            variables: Vec::new(),
+            operations: get_all_operations(),
+        };
+
+        let root = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("Unexpected '{}' in expression", token));
+        }
+
+        Ok(FitnessExpression {
+            tree: Tree::new(root),
+            variables: parser.variables,
+        })
+    }
+
+    /// The names of the variables in the expression, in order of first appearance.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    pub fn tree(&self) -> &Tree<f32> {
+        &self.tree
+    }
+
+    /// Evaluate the expression, looking up the value of every variable with `value`.
+    pub fn eval<F>(&self, value: F) -> f32
+    where
+        F: Fn(&str) -> f32,
+    {
+        let inputs = self
+            .variables
+            .iter()
+            .map(|name| value(name))
+            .collect::<Vec<f32>>();
+
+        self.tree.eval(inputs.as_slice())
+    }
+
+    /// Turn the expression into a fitness function. `value` computes the value of a variable (by name)
+    /// for the decoded individual being scored.
+    pub fn fitness_fn<T, F>(self, value: F) -> impl Fn(T) -> f32 + Send + Sync
+    where
+        F: Fn(&T, &str) -> f32 + Send + Sync,
+    {
+        move |individual: T| self.eval(|name| value(&individual, name))
+    }
+}
+
+impl FromStr for FitnessExpression {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        FitnessExpression::parse(source)
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&next) = chars.peek() {
+        if next.is_whitespace() {
+            chars.next();
+        } else if next.is_ascii_digit() || next == '.' {
+            let mut number = String::new();
+            while let Some(&digit) = chars.peek() {
+                let exponent_sign = (digit == '-' || digit == '+') && number.ends_with(['e', 'E']);
+                if digit.is_ascii_digit()
+                    || digit == '.'
+                    || digit == 'e'
+                    || digit == 'E'
+                    || exponent_sign
+                {
+                    number.push(digit);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(number);
+        } else if next.is_alphabetic() || next == '_' {
+            let mut name = String::new();
+            while let Some(&letter) = chars.peek() {
+                if letter.is_alphanumeric() || letter == '_' {
+                    name.push(letter);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(name);
+        } else if "+-*/^(),".contains(next) {
+            tokens.push(next.to_string());
+            chars.next();
+        } else {
+            return Err(format!("Unexpected character '{}' in expression", next));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    position: usize,
+    variables: Vec<String>,
+    operations: Vec<Op<f32>>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.position).map(|token| token.as_str())
+    }
+
+    fn next(&mut self) -> Result<String, String> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| "Unexpected end of expression".to_string())?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(format!("Expected '{}' but found '{}'", expected, token)),
+        }
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<TreeNode<f32>, String> {
+        let mut node = self.term()?;
+        while let Some(symbol) = self.peek() {
+            let op = match symbol {
+                "+" => Op::add(),
+                "-" => Op::sub(),
+                _ => break,
+            };
+            self.position += 1;
+            node = TreeNode::with_children(op, vec![node, self.term()?]);
+        }
+
+        Ok(node)
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<TreeNode<f32>, String> {
+        let mut node = self.unary()?;
+        while let Some(symbol) = self.peek() {
+            let op = match symbol {
+                "*" => Op::mul(),
+                "/" => Op::div(),
+                _ => break,
+            };
+            self.position += 1;
+            node = TreeNode::with_children(op, vec![node, self.unary()?]);
+        }
+
+        Ok(node)
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<TreeNode<f32>, String> {
+        if self.peek() == Some("-") {
+            self.position += 1;
+            return Ok(TreeNode::with_children(Op::neg(), vec![self.unary()?]));
+        }
+
+        self.power()
+    }
+
+    // power := primary ('^' unary)?
+    fn power(&mut self) -> Result<TreeNode<f32>, String> {
+        let base = self.primary()?;
+        if self.peek() == Some("^") {
+            self.position += 1;
+            return Ok(TreeNode::with_children(
+                Op::pow(),
+                vec![base, self.unary()?],
+            ));
+        }
+
+        Ok(base)
+    }
+
+    // primary := number | name | name '(' arguments ')' | '(' expression ')'
+    fn primary(&mut self) -> Result<TreeNode<f32>, String> {
+        let token = self.next()?;
+
+        if token == "(" {
+            let node = self.expression()?;
+            self.expect(")")?;
+            return Ok(node);
+        }
+
+        if token.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return token
+                .parse::<f32>()
+                .map(|value| TreeNode::new(Op::value(value)))
+                .map_err(|_| format!("Invalid number '{}'", token));
+        }
+
+        if !token.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return Err(format!("Unexpected '{}' in expression", token));
+        }
+
+        if self.peek() == Some("(") {
+            self.position += 1;
+            let mut arguments = Vec::new();
+            if self.peek() != Some(")") {
+                arguments.push(self.expression()?);
+                while self.peek() == Some(",") {
+                    self.position += 1;
+                    arguments.push(self.expression()?);
+                }
+            }
+            self.expect(")")?;
+
+            return self.call(&token, arguments);
+        }
+
+        let index = match self.variables.iter().position(|name| *name == token) {
+            Some(index) => index,
+            None => {
+                self.variables.push(token.clone());
+                self.variables.len() - 1
+            }
+        };
+
+        let name: &'static str = Box::leak(token.into_boxed_str());
+        Ok(TreeNode::new(Op::Var(name, index)))
+    }
+
+    fn call(&self, name: &str, arguments: Vec<TreeNode<f32>>) -> Result<TreeNode<f32>, String> {
+        let op = self
+            .operations
+            .iter()
+            .find(|op| op.name() == name && op.arity() != Arity::Zero)
+            .ok_or_else(|| format!("Unknown function '{}'", name))?;
+
+        let accepts = match op.arity() {
+            Arity::Exact(count) => arguments.len() == count,
+            _ => !arguments.is_empty(),
+        };
+
+        if !accepts {
+            return Err(format!(
+                "Function '{}' can't take {} arguments",
+                name,
+                arguments.len()
+            ));
+        }
+
+        Ok(TreeNode::with_children(op.clone(), arguments))
+    }
+}
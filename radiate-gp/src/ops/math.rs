@@ -111,6 +111,9 @@ pub enum ActivationOperation {
     Swish,
     Softplus,
     Softmax,
+    Sine,
+    Cosine,
+    Gaussian,
 }
 
 /// Implementations of the `ActivationOperation` enum. These are the basic activation functions used
@@ -162,6 +165,12 @@ impl ActivationOperation {
                 let total = inputs.iter().cloned().map(|x| x.exp()).sum::<f32>();
                 clamp(inputs.iter().cloned().map(|x| x.exp() / total).sum::<f32>())
             }
+            ActivationOperation::Sine => clamp(inputs.iter().cloned().sum::<f32>().sin()),
+            ActivationOperation::Cosine => clamp(inputs.iter().cloned().sum::<f32>().cos()),
+            ActivationOperation::Gaussian => {
+                let x = clamp(inputs.iter().cloned().sum::<f32>());
+                clamp((-(x * x)).exp())
+            }
         }
     }
 }
@@ -416,6 +425,33 @@ impl Op<f32> {
             Arc::new(|inputs: &[f32]| ActivationOperation::Softmax.apply(inputs)),
         )
     }
+
+    /// The sine of the sum of the inputs. Unlike `sin` it accepts any number of inputs.
+    pub fn sine() -> Self {
+        Op::Fn(
+            "sine",
+            Arity::Any,
+            Arc::new(|inputs: &[f32]| ActivationOperation::Sine.apply(inputs)),
+        )
+    }
+
+    /// The cosine of the sum of the inputs. Unlike `cos` it accepts any number of inputs.
+    pub fn cosine() -> Self {
+        Op::Fn(
+            "cosine",
+            Arity::Any,
+            Arc::new(|inputs: &[f32]| ActivationOperation::Cosine.apply(inputs)),
+        )
+    }
+
+    /// The gaussian `e^(-x^2)` of the sum of the inputs.
+    pub fn gaussian() -> Self {
+        Op::Fn(
+            "gaussian",
+            Arity::Any,
+            Arc::new(|inputs: &[f32]| ActivationOperation::Gaussian.apply(inputs)),
+        )
+    }
 }
 
 /// Get a list of all the math operations.
@@ -459,6 +495,19 @@ pub fn get_activation_operations() -> Vec<Op<f32>> {
     ]
 }
 
+/// Get the operations of a compositional pattern producing network (CPPN) - periodic, symmetric and
+/// sigmoidal functions that each accept any number of inputs.
+pub fn get_cppn_operations() -> Vec<Op<f32>> {
+    vec![
+        Op::sine(),
+        Op::cosine(),
+        Op::gaussian(),
+        Op::sigmoid(),
+        Op::tanh(),
+        Op::linear(),
+    ]
+}
+
 /// Get a list of all the operations.
 pub fn get_all_operations() -> Vec<Op<f32>> {
     get_math_operations()
@@ -1,8 +1,17 @@
+pub mod expression;
 pub mod math;
 pub mod mutator;
 pub mod operation;
+#[cfg(feature = "timeseries")]
+pub mod timeseries;
 
 pub use operation::*;
 
-pub use math::{get_activation_operations, get_all_operations, get_math_operations};
+pub use expression::FitnessExpression;
+
+pub use math::{
+    get_activation_operations, get_all_operations, get_cppn_operations, get_math_operations,
+};
 pub use mutator::OperationMutator;
+#[cfg(feature = "timeseries")]
+pub use timeseries::get_timeseries_operations;
@@ -1,7 +1,7 @@
 use crate::ops::operation::Op;
 use crate::{Factory, GraphChromosome, NodeType};
 use radiate::engines::genome::gene::Gene;
-use radiate::{random_provider, Chromosome};
+use radiate::{random_provider::RngHandle, Chromosome};
 use radiate::{Alter, AlterAction, EngineCompoment, Mutate};
 use std::sync::Arc;
 
@@ -12,11 +12,11 @@ pub struct OperationMutator {
 
 impl OperationMutator {
     pub fn new(rate: f32, replace_rate: f32) -> Self {
-        if rate < 0.0 || rate > 1.0 {
+        if !(0.0..=1.0).contains(&rate) {
             panic!("rate must be between 0.0 and 1.0");
         }
 
-        if replace_rate < 0.0 || replace_rate > 1.0 {
+        if !(0.0..=1.0).contains(&replace_rate) {
             panic!("replace_rate must be between 0.0 and 1.0");
         }
 
@@ -53,10 +53,10 @@ where
     T: Clone + PartialEq + Default,
 {
     #[inline]
-    fn mutate_chromosome(&self, chromosome: &mut GraphChromosome<T>) -> i32 {
+    fn mutate_chromosome(&self, chromosome: &mut GraphChromosome<T>, rng: &RngHandle) -> i32 {
         let mutation_indexes = (0..chromosome.len())
             .filter(|index| {
-                random_provider::random::<f32>() < self.rate
+                rng.random::<f32>() < self.rate
                     && chromosome.get_gene(*index).node_type() != NodeType::Input
             })
             .collect::<Vec<usize>>();
@@ -78,7 +78,7 @@ where
                     operation,
                 } => {
                     let new_value = get_value();
-                    let new_value = if random_provider::random::<f32>() < self.replace_rate {
+                    let new_value = if rng.random::<f32>() < self.replace_rate {
                         new_value
                     } else {
                         modifier(value)
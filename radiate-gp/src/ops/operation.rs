@@ -1,7 +1,8 @@
 use std::{
+    collections::VecDeque,
     fmt::{Debug, Display},
     ops::Deref,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use crate::{Eval, Factory};
@@ -36,6 +37,10 @@ impl Deref for Arity {
     }
 }
 
+type OpFn<T> = Arc<dyn Fn(&[T]) -> T>;
+type OpUpdateFn<T> = Arc<dyn Fn(&[T], &T) -> T>;
+type OpHistoryFn<T> = Arc<dyn Fn(&VecDeque<T>) -> T>;
+
 /// A generic operation type that can represent several kinds of “ops”.
 pub enum Op<T> {
     /// 1) A stateless function operation:
@@ -44,7 +49,7 @@ pub enum Op<T> {
     ///    - A `&'static str` name (e.g., "Add", "Sigmoid")
     ///    - Arity (how many inputs it takes)
     ///    - Arc<dyn Fn(&[T]) -> T> for the actual function logic
-    Fn(&'static str, Arity, Arc<dyn Fn(&[T]) -> T>),
+    Fn(&'static str, Arity, OpFn<T>),
     /// 2) A variable-like operation:
     ///
     /// # Arguments
@@ -66,20 +71,38 @@ pub enum Op<T> {
     /// - An `Arc<dyn Fn() -> T>` for retrieving (or resetting) the value
     /// - An `Arc<dyn Fn(&[T], &T) -> T>` for updating or combining inputs & old value -> new value
     ///
-    ///    This suggests a node that can mutate its internal state over time, or
-    ///    one that needs a special function to incorporate the inputs into the next state.
+    /// This suggests a node that can mutate its internal state over time, or
+    /// one that needs a special function to incorporate the inputs into the next state.
     MutableConst {
         name: &'static str,
         arity: Arity,
         value: T,
         get_value: Arc<dyn Fn() -> T>,
         modifier: Arc<dyn Fn(&T) -> T>,
-        operation: Arc<dyn Fn(&[T], &T) -> T>,
+        operation: OpUpdateFn<T>,
     },
     /// 5) A 'Value' operation that can be used to represent a constant value.
-    ///     This is a convenience method for creating a `Const` operation with any given
-    ///     value and arity
+    ///    This is a convenience method for creating a `Const` operation with any given
+    ///    value and arity
     Value(T, Arity),
+    /// 6) A stateful operation over the sequence of values of its single input, e.g. a rolling mean
+    ///    of a time series:
+    ///
+    /// # Arguments
+    /// - `&'static str` name
+    /// - `usize` the number of most recent input values it keeps
+    /// - The history of input values, oldest first, up to `window` of them
+    /// - An `Arc<dyn Fn(&VecDeque<T>) -> T>` computing the output from the history, the current
+    ///   input included
+    ///
+    /// Every evaluation pushes the input onto the history, so the output depends on the order the
+    /// op is evaluated in. `reset` clears the history - a new `GraphEvaluator` resets the ops of its graph.
+    Stateful {
+        name: &'static str,
+        window: usize,
+        history: Mutex<VecDeque<T>>,
+        operation: OpHistoryFn<T>,
+    },
 }
 
 /// Base functionality for operations.
@@ -91,6 +114,7 @@ impl<T> Op<T> {
             Op::Const(name, _) => name,
             Op::MutableConst { name, .. } => name,
             Op::Value(_, _) => "Value",
+            Op::Stateful { name, .. } => name,
         }
     }
 
@@ -101,6 +125,7 @@ impl<T> Op<T> {
             Op::Const(_, _) => Arity::Zero,
             Op::MutableConst { arity, .. } => *arity,
             Op::Value(_, arity) => *arity,
+            Op::Stateful { .. } => Arity::Exact(1),
         }
     }
 
@@ -116,6 +141,35 @@ impl<T> Op<T> {
         Op::Const(name, value)
     }
 
+    /// Create a `Stateful` op keeping the last `window` values of its input.
+    pub fn stateful(
+        name: &'static str,
+        window: usize,
+        operation: impl Fn(&VecDeque<T>) -> T + 'static,
+    ) -> Self {
+        if window < 1 {
+            panic!("window must be greater than 0");
+        }
+
+        Op::Stateful {
+            name,
+            window,
+            history: Mutex::new(VecDeque::with_capacity(window)),
+            operation: Arc::new(operation),
+        }
+    }
+
+    pub fn is_stateful(&self) -> bool {
+        matches!(self, Op::Stateful { .. })
+    }
+
+    /// Forget the input values a `Stateful` op has seen. Does nothing for other ops.
+    pub fn reset(&self) {
+        if let Op::Stateful { history, .. } = self {
+            history.lock().unwrap().clear();
+        }
+    }
+
     pub fn gt() -> Self
     where
         T: Clone + PartialEq + PartialOrd,
@@ -186,6 +240,20 @@ where
                 value, operation, ..
             } => operation(inputs, value),
             Op::Value(value, _) => value.clone(),
+            Op::Stateful {
+                window,
+                history,
+                operation,
+                ..
+            } => {
+                let mut history = history.lock().unwrap();
+                if history.len() == *window {
+                    history.pop_front();
+                }
+
+                history.push_back(inputs[0].clone());
+                operation(&history)
+            }
         }
     }
 }
@@ -217,6 +285,17 @@ where
                 operation: Arc::clone(operation),
             },
             Op::Value(value, arity) => Op::Value(value.clone(), *arity),
+            Op::Stateful {
+                name,
+                window,
+                operation,
+                ..
+            } => Op::Stateful {
+                name,
+                window: *window,
+                history: Mutex::new(VecDeque::with_capacity(*window)),
+                operation: Arc::clone(operation),
+            },
         }
     }
 }
@@ -246,6 +325,17 @@ where
                 operation: Arc::clone(operation),
             },
             Op::Value(value, arity) => Op::Value(value.clone(), *arity),
+            Op::Stateful {
+                name,
+                window,
+                history,
+                operation,
+            } => Op::Stateful {
+                name,
+                window: *window,
+                history: Mutex::new(history.lock().unwrap().clone()),
+                operation: Arc::clone(operation),
+            },
         }
     }
 }
@@ -285,25 +375,26 @@ where
             Op::Const(name, value) => write!(f, "C: {}({:?})", name, value),
             Op::MutableConst { name, value, .. } => write!(f, "{}({:.2?})", name, value),
             Op::Value(value, _) => write!(f, "Value({:?})", value),
+            Op::Stateful { name, window, .. } => write!(f, "{}[{}]", name, window),
         }
     }
 }
 
-impl Into<Op<f32>> for f32 {
-    fn into(self) -> Op<f32> {
-        Op::Value(self, Arity::Any)
+impl From<f32> for Op<f32> {
+    fn from(val: f32) -> Self {
+        Op::Value(val, Arity::Any)
     }
 }
 
-impl Into<Op<i32>> for i32 {
-    fn into(self) -> Op<i32> {
-        Op::Value(self, Arity::Any)
+impl From<i32> for Op<i32> {
+    fn from(val: i32) -> Self {
+        Op::Value(val, Arity::Any)
     }
 }
 
-impl Into<Op<bool>> for bool {
-    fn into(self) -> Op<bool> {
-        Op::Value(self, Arity::Any)
+impl From<bool> for Op<bool> {
+    fn from(val: bool) -> Self {
+        Op::Value(val, Arity::Any)
     }
 }
 
@@ -317,7 +408,7 @@ mod test {
         let op = Op::add();
         assert_eq!(op.name(), "add");
         assert_eq!(op.arity(), Arity::Exact(2));
-        assert_eq!(op.eval(&vec![1_f32, 2_f32]), 3_f32);
+        assert_eq!(op.eval(&[1_f32, 2_f32]), 3_f32);
         assert_eq!(op.new_instance(()), op);
     }
 
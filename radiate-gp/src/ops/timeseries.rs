@@ -0,0 +1,178 @@
+use super::Op;
+use std::collections::VecDeque;
+
+const ZERO: f32 = 0.0_f32;
+
+/// The mean and the (population) standard deviation of a history.
+fn mean_std(history: &VecDeque<f32>) -> (f32, f32) {
+    let n = history.len() as f32;
+    let mean = history.iter().sum::<f32>() / n;
+    let variance = history.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n;
+
+    (mean, variance.sqrt())
+}
+
+fn name(prefix: &str, window: usize) -> &'static str {
+    Box::leak(Box::new(format!("{}_{}", prefix, window)))
+}
+
+/// Technical-indicator operations for evolving signals over a time series - a `DataSet` whose rows are
+/// evaluated in time order. They are `Stateful` ops over the sequence of values of their input.
+///
+/// Every indicator has a warm-up: until it has seen enough values to fill its window it returns 0, so
+/// the first rows of a series - and of every pass after a reset - always evaluate the same way.
+impl Op<f32> {
+    /// The input `lag` evaluations ago. Returns 0 for the first `lag` evaluations.
+    pub fn lag(lag: usize) -> Self {
+        Op::stateful(name("lag", lag), lag + 1, move |history| {
+            if history.len() <= lag {
+                return ZERO;
+            }
+
+            history[0]
+        })
+    }
+
+    /// The mean of the last `window` values.
+    pub fn rolling_mean(window: usize) -> Self {
+        Op::stateful(name("rolling_mean", window), window, move |history| {
+            if history.len() < window {
+                return ZERO;
+            }
+
+            mean_std(history).0
+        })
+    }
+
+    /// The standard deviation of the last `window` values.
+    pub fn rolling_std(window: usize) -> Self {
+        Op::stateful(name("rolling_std", window), window, move |history| {
+            if history.len() < window {
+                return ZERO;
+            }
+
+            mean_std(history).1
+        })
+    }
+
+    /// How far the input is from the mean of the last `window` values, in standard deviations.
+    /// Returns 0 when the values don't vary.
+    pub fn z_score(window: usize) -> Self {
+        Op::stateful(name("z_score", window), window, move |history| {
+            if history.len() < window {
+                return ZERO;
+            }
+
+            let (mean, std) = mean_std(history);
+            match std > ZERO {
+                true => (history[history.len() - 1] - mean) / std,
+                false => ZERO,
+            }
+        })
+    }
+
+    /// An RSI-like momentum oscillator over the changes between the last `window + 1` values - the
+    /// summed gains minus the summed losses over their total, from -1 (only losses) to 1 (only gains).
+    /// The relative strength index maps to it as `RSI = 50 * (1 + value)`. Returns 0 when the values
+    /// don't change.
+    pub fn rsi(window: usize) -> Self {
+        Op::stateful(name("rsi", window), window + 1, move |history| {
+            if history.len() <= window {
+                return ZERO;
+            }
+
+            let (gains, losses) = history.iter().zip(history.iter().skip(1)).fold(
+                (ZERO, ZERO),
+                |(gains, losses), (previous, current)| match current - previous {
+                    change if change > ZERO => (gains + change, losses),
+                    change => (gains, losses - change),
+                },
+            );
+
+            match gains + losses > ZERO {
+                true => (gains - losses) / (gains + losses),
+                false => ZERO,
+            }
+        })
+    }
+}
+
+/// Get every time-series operation for each of the given windows - a lag, rolling mean, rolling
+/// standard deviation, z-score and RSI-like oscillator per window.
+///
+/// # Example
+/// ```rust
+/// use radiate_gp::*;
+///
+/// let gates = get_timeseries_operations(&[3, 10]);
+/// assert_eq!(gates.len(), 10);
+///
+/// // the change of a series since the previous row - 1 for every row of a linear trend
+/// let series = (0..20).map(|i| vec![i as f32]).collect::<Vec<Vec<f32>>>();
+/// let changes = series.iter().map(|_| vec![1.0]).collect::<Vec<Vec<f32>>>();
+/// let regression = Regression::new(DataSet::new(series, changes), Loss::MSE)
+///     .rolling_origin(RollingOrigin::new(1, 5));
+///
+/// let change = Tree::new(
+///     TreeNode::new(Op::sub())
+///         .attach(TreeNode::new(Op::var(0)))
+///         .attach(TreeNode::new(Op::lag(1)).attach(TreeNode::new(Op::var(0)))),
+/// );
+///
+/// // every fold runs the rows before it through the lag first, so it's never warming up
+/// assert_eq!(regression.eval(&change), 0.0);
+/// ```
+pub fn get_timeseries_operations(windows: &[usize]) -> Vec<Op<f32>> {
+    windows
+        .iter()
+        .flat_map(|&window| {
+            vec![
+                Op::lag(window),
+                Op::rolling_mean(window),
+                Op::rolling_std(window),
+                Op::z_score(window),
+                Op::rsi(window),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Eval;
+
+    fn run(op: &Op<f32>, series: &[f32]) -> Vec<f32> {
+        series.iter().map(|x| op.eval(&[*x])).collect()
+    }
+
+    #[test]
+    fn test_indicators_warm_up_then_track_the_series() {
+        let series = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_eq!(run(&Op::lag(2), &series), vec![0.0, 0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(
+            run(&Op::rolling_mean(3), &series),
+            vec![0.0, 0.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(run(&Op::rsi(2), &series), vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+
+        let z_score = run(&Op::z_score(3), &series);
+        assert!((z_score[4] - 1.2247449).abs() < 1e-5);
+        let std = run(&Op::rolling_std(2), &[1.0, 3.0, 3.0]);
+        assert_eq!(std, vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_reset_and_new_instances_forget_the_history() {
+        let op = Op::lag(1);
+        run(&op, &[7.0, 8.0]);
+
+        assert_eq!(op.clone().eval(&[9.0]), 8.0);
+        assert_eq!(crate::Factory::new_instance(&op, ()).eval(&[9.0]), 0.0);
+
+        op.reset();
+        assert_eq!(op.eval(&[9.0]), 0.0);
+        assert_eq!(op.arity(), crate::ops::Arity::Exact(1));
+    }
+}
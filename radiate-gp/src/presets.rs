@@ -0,0 +1,96 @@
+use crate::{
+    ops, DataSet, Eval, Graph, GraphBuilder, GraphChromosome, GraphCrossover, GraphMutator, Loss,
+    NodeMutate, Op, OperationMutator, Regression, Tree, TreeChromosome, TreeCodex, TreeCrossover,
+    TreeMutator,
+};
+use radiate::{alters, Alter, AlterAction, GeneticEngine, GeneticEngineParams, TournamentSelector};
+
+/// Preset for evolving a single expression tree that fits a `DataSet`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+/// use radiate_gp::*;
+///
+/// let inputs = (0..10).map(|i| vec![i as f32]).collect::<Vec<Vec<f32>>>();
+/// let outputs = inputs.iter().map(|x| vec![x[0] * x[0]]).collect::<Vec<Vec<f32>>>();
+///
+/// let engine = GeneticEngine::preset_symbolic_regression(DataSet::new(inputs, outputs)).build();
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// ```
+pub trait SymbolicRegressionPreset {
+    /// Create a `GeneticEngineParams` that minimizes the mean squared error of a `Tree<f32>`
+    /// over the given `DataSet`. The fitness function is already set, so the params can be built
+    /// directly.
+    ///
+    /// Defaults:
+    /// * codex: TreeCodex::new(3) with math gates and one variable leaf per input column
+    /// * offspring_selector: TournamentSelector::new(3)
+    /// * alterers: TreeCrossover::new(0.5), TreeMutator::new(0.03)
+    fn preset_symbolic_regression(
+        data_set: DataSet,
+    ) -> GeneticEngineParams<TreeChromosome<f32>, Tree<f32>>;
+}
+
+/// Preset for evolving a feed forward neural network as a `Graph<f32>`.
+pub trait NeuroevolutionPreset {
+    /// Create a `GeneticEngineParams` for a weighted acyclic graph with `inputs` input nodes and
+    /// `outputs` output nodes. Only the fitness function needs to be supplied.
+    ///
+    /// Defaults:
+    /// * codex: GraphBuilder::default().weighted_acyclic(inputs, outputs, Op::sigmoid())
+    /// * offspring_selector: TournamentSelector::new(3)
+    /// * alterers: GraphCrossover::new(0.5, 0.5), OperationMutator::new(0.05, 0.05),
+    ///   GraphMutator with edge (0.03) and vertex (0.01) mutations
+    fn preset_neuroevolution(
+        inputs: usize,
+        outputs: usize,
+    ) -> GeneticEngineParams<GraphChromosome<f32>, Graph<f32>>;
+}
+
+impl SymbolicRegressionPreset for GeneticEngine<TreeChromosome<f32>, Tree<f32>> {
+    fn preset_symbolic_regression(
+        data_set: DataSet,
+    ) -> GeneticEngineParams<TreeChromosome<f32>, Tree<f32>> {
+        let num_inputs = data_set
+            .iter()
+            .first()
+            .map(|row| row.input().len())
+            .unwrap_or(0);
+
+        let codex = TreeCodex::new(3)
+            .constraint(|node| node.size() < 30)
+            .gates(ops::get_math_operations())
+            .leafs((0..num_inputs).map(Op::var).collect());
+
+        let regression = Regression::new(data_set, Loss::MSE);
+
+        GeneticEngine::from_codex(codex)
+            .minimizing()
+            .offspring_selector(TournamentSelector::new(3))
+            .alter(alters!(TreeCrossover::new(0.5), TreeMutator::new(0.03)))
+            .fitness_fn(move |tree: Tree<f32>| regression.eval(&tree))
+    }
+}
+
+impl NeuroevolutionPreset for GeneticEngine<GraphChromosome<f32>, Graph<f32>> {
+    fn preset_neuroevolution(
+        inputs: usize,
+        outputs: usize,
+    ) -> GeneticEngineParams<GraphChromosome<f32>, Graph<f32>> {
+        let codex = GraphBuilder::default()
+            .weighted_acyclic(inputs, outputs, Op::sigmoid())
+            .into_codex();
+
+        GeneticEngine::from_codex(codex)
+            .offspring_selector(TournamentSelector::new(3))
+            .alter(alters!(
+                GraphCrossover::new(0.5, 0.5),
+                OperationMutator::new(0.05, 0.05),
+                GraphMutator::new(vec![
+                    NodeMutate::Edge(0.03, false),
+                    NodeMutate::Vertex(0.01, false),
+                ]),
+            ))
+    }
+}
@@ -26,7 +26,7 @@ impl<'a> Accuracy<'a> {
         let mut accuracy = 0.0;
         let mut total = 0.0;
 
-        let loss = self.loss_fn.calculate(&self.data_set, &mut eval);
+        let loss = self.loss_fn.calculate(self.data_set, &mut eval);
 
         for row in self.data_set.iter() {
             let output = eval(row.input());
@@ -68,7 +68,7 @@ impl<'a> Accuracy<'a> {
             accuracy,
             outputs,
             loss,
-            loss_fn: self.loss_fn.clone(),
+            loss_fn: self.loss_fn,
             sample_count: self.data_set.len(),
         }
     }
@@ -88,7 +88,7 @@ pub struct AccuracyResult {
 
 impl Debug for AccuracyResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if self.outputs.len() > 0 {
+        if !self.outputs.is_empty() {
             if self.outputs[0].len() == 1 {
                 write!(
                     f,
@@ -1,4 +1,5 @@
 use radiate::random_provider;
+use std::ops::Range;
 
 #[derive(Debug, Clone, Default)]
 pub struct Row {
@@ -32,12 +33,64 @@ pub struct DataSet {
 impl DataSet {
     pub fn new(inputs: Vec<Vec<f32>>, outputs: Vec<Vec<f32>>) -> Self {
         let mut samples = Vec::new();
-        for (input, output) in inputs.into_iter().zip(outputs.into_iter()) {
+        for (input, output) in inputs.into_iter().zip(outputs) {
             samples.push(Row { input, output });
         }
         DataSet { rows: samples }
     }
 
+    /// Create a `DataSet` from column-major data - one `Vec` per input feature and per output - the
+    /// layout dataframe libraries hand out. Panics if the columns don't all have the same length.
+    pub fn from_columns(inputs: Vec<Vec<f32>>, outputs: Vec<Vec<f32>>) -> Self {
+        let len = inputs
+            .iter()
+            .chain(outputs.iter())
+            .map(|column| column.len())
+            .next()
+            .unwrap_or(0);
+        if inputs
+            .iter()
+            .chain(outputs.iter())
+            .any(|column| column.len() != len)
+        {
+            panic!("All columns must have the same length");
+        }
+
+        let rows = (0..len)
+            .map(|i| Row {
+                input: inputs.iter().map(|column| column[i]).collect(),
+                output: outputs.iter().map(|column| column[i]).collect(),
+            })
+            .collect();
+
+        DataSet { rows }
+    }
+
+    /// The inputs as columns, one `Vec` per input feature.
+    pub fn input_columns(&self) -> Vec<Vec<f32>> {
+        Self::columns(self.rows.iter().map(|row| &row.input))
+    }
+
+    /// The outputs as columns, one `Vec` per output.
+    pub fn output_columns(&self) -> Vec<Vec<f32>> {
+        Self::columns(self.rows.iter().map(|row| &row.output))
+    }
+
+    fn columns<'a>(mut rows: impl Iterator<Item = &'a Vec<f32>>) -> Vec<Vec<f32>> {
+        let mut columns = match rows.next() {
+            Some(first) => first.iter().map(|value| vec![*value]).collect::<Vec<_>>(),
+            None => return Vec::new(),
+        };
+
+        for row in rows {
+            for (column, value) in columns.iter_mut().zip(row.iter()) {
+                column.push(*value);
+            }
+        }
+
+        columns
+    }
+
     pub fn iter(&self) -> &[Row] {
         &self.rows
     }
@@ -46,6 +99,17 @@ impl DataSet {
         self.rows.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// A new `DataSet` of the rows in `range`, in order.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        DataSet {
+            rows: self.rows[range].to_vec(),
+        }
+    }
+
     pub fn shuffle(mut self) -> Self {
         random_provider::shuffle(&mut self.rows);
         self
@@ -124,3 +188,22 @@ impl DataSet {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_set_round_trips_columns() {
+        let inputs = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let outputs = vec![vec![7.0, 8.0, 9.0]];
+
+        let data = DataSet::from_columns(inputs.clone(), outputs.clone());
+
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.iter()[1].input(), &vec![2.0, 5.0]);
+        assert_eq!(data.iter()[1].output(), &vec![8.0]);
+        assert_eq!(data.input_columns(), inputs);
+        assert_eq!(data.output_columns(), outputs);
+    }
+}
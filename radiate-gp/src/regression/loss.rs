@@ -36,9 +36,8 @@ impl Loss {
                 for sample in samples.iter() {
                     let output = eval_func(sample.input());
 
-                    for i in 0..sample.output().len() {
-                        let diff = sample.output()[i] - output[i];
-                        sum += diff;
+                    for (target, value) in sample.output().iter().zip(output.iter()) {
+                        sum += target - value;
                     }
                 }
 
@@ -50,8 +49,8 @@ impl Loss {
                 for sample in samples.iter() {
                     let output = eval_func(sample.input());
 
-                    for i in 0..sample.output().len() {
-                        sum += sample.output()[i] * output[i].ln();
+                    for (target, value) in sample.output().iter().zip(output.iter()) {
+                        sum += target * value.ln();
                     }
                 }
 
@@ -62,8 +61,8 @@ impl Loss {
                 for sample in samples.iter() {
                     let output = eval_func(sample.input());
 
-                    for i in 0..sample.output().len() {
-                        sum += (sample.output()[i] - output[i]).abs();
+                    for (target, value) in sample.output().iter().zip(output.iter()) {
+                        sum += (target - value).abs();
                     }
                 }
 
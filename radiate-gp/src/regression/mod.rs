@@ -1,9 +1,12 @@
 mod accuracy;
 mod data;
 mod loss;
+#[allow(clippy::module_inception)]
 mod regression;
+mod validation;
 
 pub use accuracy::{Accuracy, AccuracyResult};
 pub use data::DataSet;
 pub use loss::Loss;
-pub use regression::Regression;
+pub use regression::{Prediction, Predictor, Regression};
+pub use validation::{Fold, RollingOrigin};
@@ -1,9 +1,98 @@
-use super::{DataSet, Loss};
-use crate::{trees::ProgramTree, Eval, EvalMut, Graph, GraphEvaluator, Tree};
+use std::ops::Range;
 
+use super::{DataSet, Fold, Loss, RollingOrigin};
+use crate::{trees::ProgramTree, Eval, EvalMut, Graph, GraphEvaluator, Tree, TreeIterator};
+
+pub type Prediction<'a> = Box<dyn FnMut(&Vec<f32>) -> Vec<f32> + 'a>;
+
+/// A model a `Regression` can score. Every pass over the data gets a new predictor, so the state a
+/// model carries from one row to the next, like a recurrent graph's, starts fresh.
+pub trait Predictor {
+    fn predictor(&self) -> Prediction<'_>;
+
+    /// Whether a prediction depends on the rows before it. Only stateful models are run over the rows
+    /// before the origin of a `RollingOrigin` fold.
+    fn is_stateful(&self) -> bool {
+        false
+    }
+}
+
+impl Predictor for Graph<f32> {
+    fn predictor(&self) -> Prediction<'_> {
+        let mut evaluator = GraphEvaluator::new(self);
+        Box::new(move |input| evaluator.eval_mut(input))
+    }
+
+    fn is_stateful(&self) -> bool {
+        self.iter()
+            .any(|node| node.is_recurrent() || node.value().is_stateful())
+    }
+}
+
+impl Predictor for Tree<f32> {
+    fn predictor(&self) -> Prediction<'_> {
+        self.iter_pre_order().for_each(|node| node.value().reset());
+        Box::new(move |input| vec![self.eval(input)])
+    }
+
+    fn is_stateful(&self) -> bool {
+        self.iter_pre_order().any(|node| node.value().is_stateful())
+    }
+}
+
+impl Predictor for ProgramTree {
+    fn predictor(&self) -> Prediction<'_> {
+        self.trees
+            .iter()
+            .flatten()
+            .flat_map(|tree| tree.iter_pre_order())
+            .for_each(|node| node.value().reset());
+        Box::new(move |input| self.eval(input))
+    }
+
+    fn is_stateful(&self) -> bool {
+        self.trees
+            .iter()
+            .flatten()
+            .flat_map(|tree| tree.iter_pre_order())
+            .any(|node| node.value().is_stateful())
+    }
+}
+
+struct FoldRows {
+    history: Range<usize>,
+    horizon: Range<usize>,
+    history_rows: DataSet,
+    horizon_rows: DataSet,
+}
+
+/// Scores a model by its loss over a `DataSet` - over all of it, or averaged over the folds of a
+/// `RollingOrigin` validation for forecasting problems, where evaluating a model on rows before the
+/// ones it has seen would leak the future into its fitness.
+///
+/// # Example
+/// ```rust
+/// use radiate_gp::*;
+///
+/// let series = (0..20).map(|i| i as f32).collect::<Vec<f32>>();
+/// let inputs = series.iter().map(|x| vec![*x]).collect::<Vec<Vec<f32>>>();
+/// let outputs = series.iter().map(|x| vec![x + 1.0]).collect::<Vec<Vec<f32>>>();
+///
+/// let regression = Regression::new(DataSet::new(inputs, outputs), Loss::MSE)
+///     .rolling_origin(RollingOrigin::new(10, 5).window(5));
+///
+/// let tree = TreeNode::new(Op::add())
+///     .attach(TreeNode::new(Op::var(0)))
+///     .attach(TreeNode::new(Op::value(1.0)));
+/// let tree = Tree::new(tree);
+///
+/// assert_eq!(regression.folds(&tree).len(), 2);
+/// assert_eq!(regression.eval(&tree), 0.0);
+/// ```
 pub struct Regression {
     data_set: DataSet,
     loss_function: Loss,
+    folds: Vec<FoldRows>,
 }
 
 impl Regression {
@@ -11,29 +100,125 @@ impl Regression {
         Regression {
             data_set: sample_set,
             loss_function,
+            folds: Vec::new(),
         }
     }
-}
 
-impl Eval<Graph<f32>, f32> for Regression {
-    fn eval(&self, graph: &Graph<f32>) -> f32 {
-        let mut evaluator = GraphEvaluator::new(graph);
+    /// Score models by their mean loss over the folds of a rolling-origin validation, keeping the rows
+    /// of the `DataSet` in order. Panics if the data set is too short for a single fold. Default is the
+    /// loss over the whole data set.
+    pub fn rolling_origin(mut self, validation: RollingOrigin) -> Self {
+        let splits = validation.splits(self.data_set.len());
+        if splits.is_empty() {
+            panic!("The data set is too short for a single fold");
+        }
+
+        self.folds = splits
+            .into_iter()
+            .map(|(history, horizon)| FoldRows {
+                history_rows: self.data_set.slice(history.clone()),
+                horizon_rows: self.data_set.slice(horizon.clone()),
+                history,
+                horizon,
+            })
+            .collect();
+        self
+    }
+
+    /// The loss of the model on every fold - a single fold over the whole data set without a rolling
+    /// origin.
+    pub fn folds<P: Predictor + ?Sized>(&self, model: &P) -> Vec<Fold> {
+        if self.folds.is_empty() {
+            return vec![Fold {
+                history: 0..0,
+                horizon: 0..self.data_set.len(),
+                loss: self
+                    .loss_function
+                    .calculate(&self.data_set, &mut model.predictor()),
+            }];
+        }
+
+        self.folds
+            .iter()
+            .map(|fold| {
+                let mut predictor = model.predictor();
+                if model.is_stateful() {
+                    for row in fold.history_rows.iter() {
+                        predictor(row.input());
+                    }
+                }
 
-        self.loss_function
-            .calculate(&self.data_set, &mut |input| evaluator.eval_mut(input))
+                Fold {
+                    history: fold.history.clone(),
+                    horizon: fold.horizon.clone(),
+                    loss: self
+                        .loss_function
+                        .calculate(&fold.horizon_rows, &mut predictor),
+                }
+            })
+            .collect()
     }
 }
 
-impl Eval<Tree<f32>, f32> for Regression {
-    fn eval(&self, tree: &Tree<f32>) -> f32 {
-        self.loss_function
-            .calculate(&self.data_set, &mut |input| vec![tree.eval(input)])
+impl<P: Predictor> Eval<P, f32> for Regression {
+    fn eval(&self, model: &P) -> f32 {
+        let folds = self.folds(model);
+        folds.iter().map(|fold| fold.loss).sum::<f32>() / folds.len() as f32
     }
 }
 
-impl Eval<ProgramTree, f32> for Regression {
-    fn eval(&self, program: &ProgramTree) -> f32 {
-        self.loss_function
-            .calculate(&self.data_set, &mut |input| program.eval(input))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Predicts the number of rows it has seen before the current one.
+    struct Counter;
+
+    impl Predictor for Counter {
+        fn predictor(&self) -> Prediction<'_> {
+            let mut seen = 0.0;
+            Box::new(move |_| {
+                seen += 1.0;
+                vec![seen - 1.0]
+            })
+        }
+
+        fn is_stateful(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_rolling_origin_runs_the_history_before_every_fold() {
+        let inputs = (0..12).map(|_| vec![0.0]).collect::<Vec<Vec<f32>>>();
+        let outputs = (0..12).map(|i| vec![i as f32]).collect::<Vec<Vec<f32>>>();
+        let data_set = DataSet::new(inputs, outputs);
+
+        let whole = Regression::new(data_set.clone(), Loss::MSE);
+        assert_eq!(whole.folds(&Counter).len(), 1);
+        assert_eq!(whole.eval(&Counter), 0.0);
+
+        let expanding = Regression::new(data_set.clone(), Loss::MSE)
+            .rolling_origin(RollingOrigin::new(4, 3).step(2));
+        let folds = expanding.folds(&Counter);
+        assert_eq!(
+            folds
+                .iter()
+                .map(|fold| fold.horizon.clone())
+                .collect::<Vec<_>>(),
+            vec![4..7, 6..9, 8..11]
+        );
+        assert!(folds.iter().all(|fold| fold.history.start == 0));
+        assert_eq!(expanding.eval(&Counter), 0.0);
+
+        // with a window the counter is short by the rows before it
+        let windowed =
+            Regression::new(data_set, Loss::MSE).rolling_origin(RollingOrigin::new(4, 2).window(2));
+        let losses = windowed
+            .folds(&Counter)
+            .iter()
+            .map(|fold| fold.loss)
+            .collect::<Vec<f32>>();
+        assert_eq!(losses, vec![4.0, 16.0, 36.0, 64.0]);
     }
 }
@@ -0,0 +1,92 @@
+use std::ops::Range;
+
+/// Rolling-origin cross-validation for time series. The rows of a `DataSet` are taken in order and
+/// split into folds at a moving origin - every fold evaluates the `horizon` rows from its origin on,
+/// after the rows before the origin have run through the model, so a model never sees a row that lies
+/// after the ones it's evaluated on.
+///
+/// The first origin is at row `initial`, and every next one `step` rows later, for as long as a full
+/// horizon fits. The rows before an origin are all the rows from the start of the data - an expanding
+/// window - or only the last `window` of them.
+///
+/// # Example
+/// ```rust
+/// use radiate_gp::*;
+///
+/// let folds = RollingOrigin::new(4, 2).window(3).splits(10);
+///
+/// assert_eq!(folds, vec![(1..4, 4..6), (3..6, 6..8), (5..8, 8..10)]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollingOrigin {
+    initial: usize,
+    horizon: usize,
+    window: Option<usize>,
+    step: usize,
+}
+
+impl RollingOrigin {
+    /// Start at row `initial` and evaluate `horizon` rows per fold, moving the origin by `horizon` rows.
+    pub fn new(initial: usize, horizon: usize) -> Self {
+        if horizon < 1 {
+            panic!("horizon must be greater than 0");
+        }
+
+        RollingOrigin {
+            initial,
+            horizon,
+            window: None,
+            step: horizon,
+        }
+    }
+
+    /// Only run the last `window` rows before an origin through the model. Default is every row from
+    /// the start of the data.
+    pub fn window(mut self, window: usize) -> Self {
+        if window < 1 {
+            panic!("window must be greater than 0");
+        }
+
+        self.window = Some(window);
+        self
+    }
+
+    /// Set the number of rows between two origins. Default is the horizon.
+    pub fn step(mut self, step: usize) -> Self {
+        if step < 1 {
+            panic!("step must be greater than 0");
+        }
+
+        self.step = step;
+        self
+    }
+
+    pub fn horizon(&self) -> usize {
+        self.horizon
+    }
+
+    /// The folds of `len` rows, as the ranges of the rows before their origin and of the rows they
+    /// evaluate.
+    pub fn splits(&self, len: usize) -> Vec<(Range<usize>, Range<usize>)> {
+        (self.initial..)
+            .step_by(self.step)
+            .take_while(|origin| origin + self.horizon <= len)
+            .map(|origin| {
+                let start = self
+                    .window
+                    .map_or(0, |window| origin.saturating_sub(window));
+                (start..origin, origin..origin + self.horizon)
+            })
+            .collect()
+    }
+}
+
+/// The loss of a model on one fold of a `RollingOrigin` validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fold {
+    /// The rows run through the model before the fold's origin.
+    pub history: Range<usize>,
+    /// The rows the loss is calculated over.
+    pub horizon: Range<usize>,
+    pub loss: f32,
+}
@@ -2,7 +2,10 @@
 mod tests {
 
     use radiate::*;
-    use radiate_gp::{Direction, Graph, NodeType};
+    use radiate_gp::{
+        Direction, Eval, Graph, GraphBuilder, GraphCrossover, GraphMetric, GraphModule,
+        GraphMutator, NodeMutate, NodeType, Op,
+    };
 
     #[test]
     fn test_simple_graph() {
@@ -98,4 +101,177 @@ mod tests {
         assert_eq!(graph.get(2).direction(), Direction::Backward);
         assert_eq!(graph.get(3).direction(), Direction::Backward);
     }
+
+    #[test]
+    fn test_layered_graph_template() {
+        let codex = GraphBuilder::default()
+            .layered(2, &[3, 2], 1, vec![Op::sigmoid(), Op::tanh()], Op::linear())
+            .into_codex();
+
+        let graph = codex.decode(&codex.encode());
+
+        // 2 + 3 + 2 + 1 nodes and 2*3 + 3*2 + 2*1 weights
+        assert_eq!(graph.len(), 8 + 14);
+        assert!(graph.is_valid());
+        assert_eq!(
+            graph
+                .iter()
+                .filter(|node| node.node_type() == NodeType::Edge)
+                .count(),
+            14
+        );
+        assert_eq!(graph.eval(&[0.5, -0.5][..]).len(), 1);
+    }
+
+    #[test]
+    fn test_cppn_graph_template() {
+        let codex = GraphBuilder::default().cppn(3, &[4], 2).into_codex();
+        let graph = codex.decode(&codex.encode());
+
+        assert!(graph.is_valid());
+        assert_eq!(graph.eval(&[0.1, 0.2, 0.3][..]).len(), 2);
+    }
+
+    #[test]
+    fn test_modular_graph_template() {
+        let module = GraphModule::new()
+            .node(NodeType::Vertex, Op::linear())
+            .node(NodeType::Edge, Op::weight())
+            .node(NodeType::Vertex, Op::tanh())
+            .connect(0, 1)
+            .connect(1, 2)
+            .inputs(vec![0])
+            .outputs(vec![2]);
+
+        let codex = GraphBuilder::default()
+            .modular(2, 1, &module, 3, Op::linear())
+            .into_codex();
+
+        let graph = codex.decode(&codex.encode());
+
+        // 2 inputs, 3 copies of 3 nodes, 1 output, 3 * (2 + 1) weights
+        assert_eq!(graph.len(), 2 + 9 + 1 + 9);
+        assert!(graph.is_valid());
+        assert_eq!(graph.eval(&[1.0, 2.0][..]).len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_module_inputs_must_accept_any_arity() {
+        GraphModule::<f32>::new()
+            .node(NodeType::Edge, Op::weight())
+            .inputs(vec![0]);
+    }
+
+    #[test]
+    fn test_graph_structure_ignores_inactive_nodes() {
+        let mut graph = Graph::<f32>::default();
+
+        let input = graph.insert(NodeType::Input, Op::var(0));
+        let hidden = graph.insert(NodeType::Vertex, Op::linear());
+        let dead_end = graph.insert(NodeType::Vertex, Op::linear());
+        let output = graph.insert(NodeType::Output, Op::linear());
+
+        graph
+            .attach(input, hidden)
+            .attach(hidden, output)
+            .attach(input, dead_end)
+            .attach(hidden, hidden);
+        graph.set_cycles(vec![]);
+
+        let structure = graph.structure();
+
+        assert_eq!(structure.active_nodes, 3);
+        assert_eq!(structure.active_edges, 3);
+        assert_eq!(structure.depth, 1);
+        assert_eq!(structure.recurrent_edges, 1);
+    }
+
+    #[test]
+    fn test_graph_structure_of_layered_template() {
+        let codex = GraphBuilder::default()
+            .layered(2, &[3, 2], 1, vec![Op::sigmoid()], Op::linear())
+            .into_codex();
+
+        let genotype = codex.encode();
+        let structure = codex.decode(&genotype).structure();
+
+        assert_eq!(structure.active_nodes, 22);
+        assert_eq!(structure.active_edges, 28);
+        assert_eq!(structure.depth, 2);
+        assert_eq!(structure.recurrent_edges, 0);
+        assert_eq!(GraphMetric::Depth.of(&genotype), 2.0);
+    }
+
+    #[test]
+    fn test_modularity_of_separate_modules() {
+        let module = GraphModule::new()
+            .node(NodeType::Vertex, Op::linear())
+            .node(NodeType::Edge, Op::weight())
+            .node(NodeType::Vertex, Op::linear())
+            .connect(0, 1)
+            .connect(1, 2)
+            .inputs(vec![0])
+            .outputs(vec![2]);
+
+        let modular = GraphBuilder::default()
+            .modular(2, 1, &module, 3, Op::linear())
+            .into_codex();
+        let single = GraphBuilder::default()
+            .modular(2, 1, &module, 1, Op::linear())
+            .into_codex();
+
+        let modular = modular.decode(&modular.encode()).structure();
+        let single = single.decode(&single.encode()).structure();
+
+        assert!(modular.modularity > single.modularity);
+        assert!(modular.modularity <= 1.0);
+    }
+
+    #[test]
+    fn test_graph_edges_complexity_objective() {
+        let codex = GraphBuilder::default()
+            .weighted_acyclic(2, 1, Op::linear())
+            .into_codex();
+
+        let genotype = codex.encode();
+        assert_eq!(Complexity::GraphEdges.measure(&genotype), 4.0);
+
+        let engine = GeneticEngine::from_codex(codex)
+            .population_size(10)
+            .fitness_fn(|graph: Graph<f32>| graph.eval(&[1.0, 2.0][..])[0])
+            .complexity_objective(Complexity::GraphEdges)
+            .offspring_selector(TournamentSelector::new(3))
+            .survivor_selector(NSGA2Selector::new())
+            .alter(alters!(GraphMutator::new(vec![NodeMutate::Edge(
+                0.1, false
+            )])))
+            .build();
+
+        let result = engine.run(|ctx| ctx.index > 2);
+        let score = result.population[0].score().unwrap();
+
+        assert_eq!(score.values.len(), 2);
+        assert!(score.values[1] >= 0.0);
+    }
+
+    #[test]
+    fn test_graph_operators_keep_graphs_valid() {
+        let codex = || {
+            GraphBuilder::default()
+                .weighted_acyclic(2, 2, Op::linear())
+                .into_codex()
+        };
+
+        radiate::testing::OperatorCheck::from_codex(codex())
+            .invariant("valid", radiate::testing::is_valid)
+            .check(GraphMutator::new(vec![
+                NodeMutate::Edge(0.1, false),
+                NodeMutate::Vertex(0.1, false),
+            ]));
+
+        radiate::testing::OperatorCheck::from_codex(codex())
+            .invariant("valid", radiate::testing::is_valid)
+            .check(GraphCrossover::new(0.5, 0.5));
+    }
 }
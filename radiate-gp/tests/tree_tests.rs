@@ -15,6 +15,50 @@ mod test {
         assert!(tree.root().unwrap().is_valid());
         assert_eq!(tree.height(), 1);
         assert_eq!(tree.size(), 3);
-        assert_eq!(tree.eval(&vec![]), 3.0);
+        assert_eq!(tree.eval(&[]), 3.0);
+    }
+
+    #[test]
+    fn test_tree_size_complexity() {
+        let codex = TreeCodex::new(3)
+            .gates(vec![Op::add(), Op::mul()])
+            .leafs(vec![Op::var(0), Op::value(1.0)]);
+
+        let genotype = codex.encode();
+        let tree = codex.decode(&genotype);
+
+        assert_eq!(Complexity::TreeSize.measure(&genotype), tree.size() as f32);
+        assert_eq!(genotype[0].complexity(Complexity::GraphEdges), None);
+    }
+
+    #[test]
+    fn test_fitness_expression_follows_precedence() {
+        let expression = FitnessExpression::parse("mse + 0.5 * tree_size ^ 2 - -mse").unwrap();
+
+        assert_eq!(expression.variables(), &["mse", "tree_size"]);
+        let value = expression.eval(|name| match name {
+            "mse" => 1.5,
+            "tree_size" => 4.0,
+            _ => unreachable!(),
+        });
+        assert_eq!(value, 1.5 + 0.5 * 16.0 + 1.5);
+
+        let called = "max(a, 2) / sqrt(b)".parse::<FitnessExpression>().unwrap();
+        assert_eq!(
+            called.eval(|name| if name == "a" { 1.0 } else { 16.0 }),
+            0.5
+        );
+        assert_eq!(called.tree().to_expression(), "max(a, 2) / sqrt(b)");
+    }
+
+    #[test]
+    fn test_fitness_expression_reports_errors() {
+        assert!(FitnessExpression::parse("").is_err());
+        assert!(FitnessExpression::parse("a +").is_err());
+        assert!(FitnessExpression::parse("(a + b").is_err());
+        assert!(FitnessExpression::parse("a $ b").is_err());
+        assert!(FitnessExpression::parse("unknown(a)").is_err());
+        assert!(FitnessExpression::parse("sqrt(a, b)").is_err());
+        assert!(FitnessExpression::parse("a b").is_err());
     }
 }
@@ -0,0 +1,299 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{random_provider, Chromosome, Gene, Genotype};
+
+/// A member of the population of an `Evolver`: a genotype and its score. The score is `None` until
+/// the genotype has been evaluated.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Individual<C: Chromosome> {
+    pub genotype: Genotype<C>,
+    pub score: Option<f32>,
+}
+
+impl<C: Chromosome> Individual<C> {
+    pub fn new(genotype: Genotype<C>) -> Self {
+        Individual {
+            genotype,
+            score: None,
+        }
+    }
+}
+
+/// How the `Evolver` picks the parents of the next generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// The best of `n` random individuals.
+    Tournament(usize),
+    /// A random individual out of the best `n`.
+    Truncation(usize),
+    /// A random individual.
+    Random,
+}
+
+/// A generational genetic algorithm that runs without `std` - see the `embedded` module.
+///
+/// Each call to `step` keeps the best `elitism` individuals, then fills the rest of the next generation
+/// with offspring: two parents are picked by the `Selection`, with probability `crossover_rate` their
+/// genes are swapped uniformly, and every gene of the offspring is replaced by a `new_instance` of itself
+/// with probability `mutation_rate`. The new individuals are scored with the fitness function. Scores are
+/// maximized unless `minimizing` is set.
+///
+/// # Example
+/// ``` rust
+/// use radiate::embedded::*;
+/// use radiate::*;
+///
+/// random_provider::set_seed(42);
+///
+/// let prototype = Genotype::new(vec![BitChromosome::from(vec![false; 16])]);
+/// let mut evolver = Evolver::new(prototype, 20, |genotype: &Genotype<BitChromosome>| {
+///     genotype[0].iter().filter(|gene| *gene.allele()).count() as f32
+/// })
+/// .selection(Selection::Tournament(3))
+/// .mutation_rate(0.05)
+/// .elitism(2);
+///
+/// let best = evolver.run(100);
+/// assert_eq!(best.score, Some(16.0));
+/// ```
+pub struct Evolver<C, F>
+where
+    C: Chromosome,
+    F: Fn(&Genotype<C>) -> f32,
+{
+    population: Vec<Individual<C>>,
+    fitness: F,
+    selection: Selection,
+    mutation_rate: f32,
+    crossover_rate: f32,
+    elitism: usize,
+    minimizing: bool,
+    generation: usize,
+}
+
+impl<C, F> Evolver<C, F>
+where
+    C: Chromosome,
+    F: Fn(&Genotype<C>) -> f32,
+{
+    /// Create an `Evolver` with a population of `size` random genotypes shaped like the `prototype` -
+    /// every gene of the prototype is replaced by a `new_instance` of itself. The size must be greater
+    /// than 0.
+    pub fn new(prototype: Genotype<C>, size: usize, fitness: F) -> Self {
+        if size == 0 {
+            panic!("size must be greater than 0");
+        }
+
+        let population = (0..size)
+            .map(|_| {
+                let mut genotype = prototype.clone();
+                for chromosome in genotype.iter_mut() {
+                    for gene in chromosome.iter_mut() {
+                        *gene = gene.new_instance();
+                    }
+                }
+
+                Individual::new(genotype)
+            })
+            .collect();
+
+        Evolver {
+            population,
+            fitness,
+            selection: Selection::Tournament(3),
+            mutation_rate: 0.01,
+            crossover_rate: 0.7,
+            elitism: 1,
+            minimizing: false,
+            generation: 0,
+        }
+    }
+
+    /// Set how parents are selected. Default is `Selection::Tournament(3)`.
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Set the probability that a gene is mutated. The rate must be between 0.0 and 1.0. Default is 0.01.
+    pub fn mutation_rate(mut self, rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        self.mutation_rate = rate;
+        self
+    }
+
+    /// Set the probability that two parents are crossed. The rate must be between 0.0 and 1.0. Default
+    /// is 0.7.
+    pub fn crossover_rate(mut self, rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        self.crossover_rate = rate;
+        self
+    }
+
+    /// Set the number of best individuals copied unchanged into the next generation. Default is 1.
+    pub fn elitism(mut self, count: usize) -> Self {
+        self.elitism = count;
+        self
+    }
+
+    /// Minimize the scores instead of maximizing them.
+    pub fn minimizing(mut self) -> Self {
+        self.minimizing = true;
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The population, best first once it has been evaluated.
+    pub fn population(&self) -> &[Individual<C>] {
+        &self.population
+    }
+
+    /// The best individual of the current generation.
+    pub fn best(&mut self) -> &Individual<C> {
+        self.evaluate();
+        &self.population[0]
+    }
+
+    /// Run `generations` steps and return the best individual.
+    pub fn run(&mut self, generations: usize) -> &Individual<C> {
+        for _ in 0..generations {
+            self.step();
+        }
+
+        self.best()
+    }
+
+    /// Evolve the population by one generation.
+    pub fn step(&mut self) {
+        self.evaluate();
+
+        let size = self.population.len();
+        let mut offspring = self
+            .population
+            .iter()
+            .take(self.elitism.min(size))
+            .cloned()
+            .collect::<Vec<Individual<C>>>();
+
+        while offspring.len() < size {
+            let mut one = self.select().genotype.clone();
+            let mut two = self.select().genotype.clone();
+
+            if random_provider::random::<f32>() < self.crossover_rate {
+                Self::crossover(&mut one, &mut two);
+            }
+
+            for mut genotype in [one, two] {
+                if offspring.len() < size {
+                    self.mutate(&mut genotype);
+                    offspring.push(Individual::new(genotype));
+                }
+            }
+        }
+
+        self.population = offspring;
+        self.generation += 1;
+        self.evaluate();
+    }
+
+    /// Score the individuals that have no score yet and sort the population best first.
+    fn evaluate(&mut self) {
+        for individual in self.population.iter_mut() {
+            if individual.score.is_none() {
+                individual.score = Some((self.fitness)(&individual.genotype));
+            }
+        }
+
+        let minimizing = self.minimizing;
+        self.population.sort_by(|one, two| {
+            let order = one.score.partial_cmp(&two.score).unwrap_or(Ordering::Equal);
+            match minimizing {
+                true => order,
+                false => order.reverse(),
+            }
+        });
+    }
+
+    /// Pick a parent from the sorted population.
+    fn select(&self) -> &Individual<C> {
+        let size = self.population.len();
+        let index = match self.selection {
+            Selection::Tournament(n) => (0..n.max(1))
+                .map(|_| random_provider::gen_range(0..size))
+                .min()
+                .unwrap_or(0),
+            Selection::Truncation(n) => random_provider::gen_range(0..n.clamp(1, size)),
+            Selection::Random => random_provider::gen_range(0..size),
+        };
+
+        &self.population[index]
+    }
+
+    fn crossover(one: &mut Genotype<C>, two: &mut Genotype<C>) {
+        for (chrom_one, chrom_two) in one.iter_mut().zip(two.iter_mut()) {
+            for (gene_one, gene_two) in chrom_one.iter_mut().zip(chrom_two.iter_mut()) {
+                if random_provider::random::<f32>() < 0.5 {
+                    core::mem::swap(gene_one, gene_two);
+                }
+            }
+        }
+    }
+
+    fn mutate(&self, genotype: &mut Genotype<C>) {
+        for chromosome in genotype.iter_mut() {
+            for gene in chromosome.iter_mut() {
+                if random_provider::random::<f32>() < self.mutation_rate {
+                    *gene = gene.new_instance();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, FloatGene};
+    use alloc::vec;
+
+    #[test]
+    fn test_evolver_minimizes_and_keeps_the_elite() {
+        random_provider::set_seed(7);
+
+        let prototype = Genotype::new(vec![FloatChromosome::new(vec![
+            FloatGene::new(-10.0, 10.0);
+            4
+        ])]);
+        let mut evolver = Evolver::new(prototype, 40, |genotype: &Genotype<FloatChromosome>| {
+            genotype[0]
+                .iter()
+                .map(|gene| gene.allele().abs())
+                .sum::<f32>()
+        })
+        .selection(Selection::Truncation(10))
+        .mutation_rate(0.1)
+        .minimizing();
+
+        let mut previous = evolver.best().score.unwrap();
+        for _ in 0..100 {
+            evolver.step();
+            let score = evolver.best().score.unwrap();
+            assert!(score <= previous);
+            previous = score;
+        }
+
+        assert_eq!(evolver.generation(), 100);
+        assert_eq!(evolver.population().len(), 40);
+        assert!(previous < 4.0);
+    }
+}
@@ -0,0 +1,12 @@
+//! A small genetic algorithm that only needs `core` and `alloc`, for running evolutionary optimizers on
+//! microcontrollers and other targets without `std`. Build radiate with `default-features = false` to
+//! get just the genome (genes, chromosomes and genotypes), the `random_provider` and this module; the
+//! `GeneticEngine`, its selectors, alterers and thread pool need the `std` feature.
+//!
+//! The `Evolver` runs a generational loop on a single thread: selection, uniform crossover, uniform
+//! mutation and elitism. Without `std` the `random_provider` uses a small global xorshift generator -
+//! seed it with `random_provider::set_seed` (e.g. from a hardware RNG or an ADC reading).
+
+pub mod evolver;
+
+pub use evolver::*;
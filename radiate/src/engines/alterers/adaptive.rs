@@ -0,0 +1,470 @@
+use crate::Description;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::objectives::{Objective, Score};
+use crate::{
+    metric_names, random_provider::RngHandle, Chromosome, EngineCompoment, Metric, Phenotype,
+};
+use crate::{Population, Valid};
+
+use super::{alter_group, Alter, AlterAction, Compose};
+
+/// How an `AdaptiveChoice` turns the credit of its operators into the choice of operator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdaptivePolicy {
+    /// Pick operators with a probability proportional to an exponentially smoothed estimate of their
+    /// success rate, never dropping an operator below `min_probability` so it can recover if it
+    /// becomes useful later in the run. `adaptation_rate` is the weight of the latest generation.
+    ProbabilityMatching {
+        min_probability: f32,
+        adaptation_rate: f32,
+    },
+    /// Pick the operator with the highest upper confidence bound (UCB1) on its success rate.
+    /// Higher `exploration` values try under-used operators more often.
+    UpperConfidenceBound { exploration: f32 },
+}
+
+impl Default for AdaptivePolicy {
+    fn default() -> Self {
+        AdaptivePolicy::ProbabilityMatching {
+            min_probability: 0.05,
+            adaptation_rate: 0.3,
+        }
+    }
+}
+
+/// Tags of the applications of every `AdaptiveChoice`, so nested or sequenced ones don't mix them up.
+static NEXT_TAG: AtomicU64 = AtomicU64::new(0);
+
+struct Application {
+    operator: usize,
+    parent: Score,
+}
+
+struct BanditState {
+    quality: Vec<f32>,
+    applications: Vec<f32>,
+    rewards: Vec<f32>,
+    pending: BTreeMap<u64, Application>,
+    generation: i32,
+}
+
+/// Adaptive operator selection. Like a `Choice`, an `AdaptiveChoice` applies one of its alterers to each
+/// individual - but instead of fixed weights it treats the alterers as the arms of a multi-armed bandit.
+/// An application of an alterer is a success when the offspring it produced scores better than the
+/// individual it was applied to, and alterers that succeed more often are picked more often (see
+/// `AdaptivePolicy`).
+///
+/// Every offspring is tagged with the application of the alterer that produced it (see
+/// `Phenotype::tags`), and the alterer is credited from the tag once the engine has evaluated the
+/// offspring - wherever the `AdaptiveChoice` is among the engine's alterers, and however the offspring
+/// are ordered by then. Alterers applied before it that clear the score of an individual leave nothing to
+/// compare against, so put it first.
+///
+/// Every generation it records the probability of picking each alterer (`Operator Selection`) and
+/// each alterer's success rate (`Operator Credit`) as distributions, in the order the alterers were given.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 5, -10.0, 10.0))
+///     .minimizing()
+///     .alter(alters![AdaptiveChoice::new(vec![
+///         GaussianMutator::new(0.1).to_alter(),
+///         UniformMutator::new(0.1).to_alter(),
+///         MeanCrossover::new(0.5).to_alter(),
+///     ])])
+///     .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 20);
+/// let selection = result.metrics.get(metric_names::OPERATOR_SELECTION).unwrap();
+/// assert_eq!(selection.last_sequence().unwrap().len(), 3);
+/// ```
+pub struct AdaptiveChoice<C: Chromosome> {
+    alterers: Vec<AlterAction<C>>,
+    policy: AdaptivePolicy,
+    state: Mutex<BanditState>,
+}
+
+impl<C: Chromosome> AdaptiveChoice<C> {
+    /// Create a new `AdaptiveChoice` over `alterers` using the default `AdaptivePolicy`
+    /// (probability matching). Panics if there are no alterers.
+    pub fn new(alterers: Vec<AlterAction<C>>) -> Self {
+        if alterers.is_empty() {
+            panic!("AdaptiveChoice requires at least one alterer");
+        }
+
+        let count = alterers.len();
+        AdaptiveChoice {
+            alterers,
+            policy: AdaptivePolicy::default(),
+            state: Mutex::new(BanditState {
+                quality: vec![0.5; count],
+                applications: vec![0.0; count],
+                rewards: vec![0.0; count],
+                pending: BTreeMap::new(),
+                generation: i32::MIN,
+            }),
+        }
+    }
+
+    /// Set the policy used to pick alterers. Panics if its parameters are out of range.
+    pub fn with_policy(mut self, policy: AdaptivePolicy) -> Self {
+        match policy {
+            AdaptivePolicy::ProbabilityMatching {
+                min_probability,
+                adaptation_rate,
+            } => {
+                if !(0.0..=1.0).contains(&min_probability) {
+                    panic!("min_probability must be between 0 and 1");
+                }
+
+                if adaptation_rate <= 0.0 || adaptation_rate > 1.0 {
+                    panic!("adaptation_rate must be in (0, 1]");
+                }
+            }
+            AdaptivePolicy::UpperConfidenceBound { exploration } => {
+                if exploration < 0.0 {
+                    panic!("exploration must be non-negative");
+                }
+            }
+        }
+
+        self.policy = policy;
+        self
+    }
+
+    /// The probability of picking each alterer under probability matching. The floor is capped at an
+    /// even split so the probabilities always sum to 1.
+    fn probabilities(&self, quality: &[f32], min_probability: f32) -> Vec<f32> {
+        let count = quality.len() as f32;
+        let floor = min_probability.min(1.0 / count);
+        let total = quality.iter().sum::<f32>();
+
+        quality
+            .iter()
+            .map(|q| match total > 0.0 {
+                true => floor + (1.0 - count * floor) * q / total,
+                false => 1.0 / count,
+            })
+            .collect()
+    }
+
+    /// Pick an alterer for each of `size` individuals, returning the picks along with the probability
+    /// of picking each alterer.
+    fn pick(&self, state: &BanditState, size: usize, rng: &RngHandle) -> (Vec<usize>, Vec<f32>) {
+        match self.policy {
+            AdaptivePolicy::ProbabilityMatching {
+                min_probability, ..
+            } => {
+                let probabilities = self.probabilities(&state.quality, min_probability);
+                let picks = (0..size)
+                    .map(|_| {
+                        let mut value = rng.random::<f32>();
+                        for (operator, probability) in probabilities.iter().enumerate() {
+                            value -= probability;
+                            if value < 0.0 {
+                                return operator;
+                            }
+                        }
+
+                        probabilities.len() - 1
+                    })
+                    .collect();
+
+                (picks, probabilities)
+            }
+            AdaptivePolicy::UpperConfidenceBound { exploration } => {
+                // Each pick counts as an application straight away, so the picks within one
+                // generation spread over the alterers instead of all going to the current best.
+                let mut applications = state.applications.clone();
+                let mut picks = Vec::with_capacity(size);
+                for _ in 0..size {
+                    let total = applications.iter().sum::<f32>().max(1.0);
+                    let operator = (0..applications.len())
+                        .map(|operator| {
+                            let count = applications[operator];
+                            let bound = match count > 0.0 {
+                                true => {
+                                    state.rewards[operator] / count
+                                        + exploration * (2.0 * total.ln() / count).sqrt()
+                                }
+                                false => f32::INFINITY,
+                            };
+
+                            (operator, bound)
+                        })
+                        .fold((0, f32::NEG_INFINITY), |best, next| match next.1 > best.1 {
+                            true => next,
+                            false => best,
+                        })
+                        .0;
+
+                    applications[operator] += 1.0;
+                    picks.push(operator);
+                }
+
+                let mut shares = vec![0.0; self.alterers.len()];
+                for &operator in picks.iter() {
+                    shares[operator] += 1.0 / size.max(1) as f32;
+                }
+
+                (picks, shares)
+            }
+        }
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for AdaptiveChoice<C> {
+    fn name(&self) -> &'static str {
+        "Adaptive Choice"
+    }
+
+    fn describe(&self) -> Description {
+        self.alterers.iter().fold(
+            Description::new(self.name()).param("policy", format!("{:?}", self.policy)),
+            |description, alterer| description.child(alterer.describe()),
+        )
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for AdaptiveChoice<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for AdaptiveChoice<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        let mut state = self.state.lock().unwrap();
+        let (picks, probabilities) = self.pick(&state, population.len(), rng);
+
+        // Applications the engine never got to observe (e.g. the offspring were dropped) are
+        // forgotten with the generation they were made in.
+        if state.generation != generation {
+            state.pending.clear();
+            state.generation = generation;
+        }
+
+        let mut groups = vec![Vec::new(); self.alterers.len()];
+        let mut tags = vec![None; population.len()];
+        for (index, &operator) in picks.iter().enumerate() {
+            groups[operator].push(index);
+            if let Some(parent) = population[index].score() {
+                let tag = NEXT_TAG.fetch_add(1, Ordering::Relaxed);
+                let parent = parent.clone();
+                state.pending.insert(tag, Application { operator, parent });
+                tags[index] = Some(tag);
+            }
+        }
+
+        drop(state);
+
+        let mut metrics = Vec::new();
+        for (alterer, group) in self.alterers.iter().zip(groups.iter()) {
+            metrics.extend(alter_group(alterer, population, group, generation, rng));
+        }
+
+        for (phenotype, tag) in population.iter_mut().zip(tags) {
+            if let Some(tag) = tag {
+                phenotype.tag(tag);
+            }
+        }
+
+        let mut selection = Metric::new_distribution(metric_names::OPERATOR_SELECTION);
+        selection.add_sequence(&probabilities);
+        metrics.push(selection);
+
+        metrics
+    }
+
+    fn observe(&self, offspring: &[Phenotype<C>], objective: &Objective) -> Vec<Metric> {
+        let mut state = self.state.lock().unwrap();
+        if state.pending.is_empty() {
+            return Vec::new();
+        }
+
+        let mut applications = vec![0.0; self.alterers.len()];
+        let mut successes = vec![0.0; self.alterers.len()];
+        for child in offspring {
+            let Some(application) = child
+                .tags()
+                .iter()
+                .find_map(|tag| state.pending.remove(tag))
+            else {
+                continue;
+            };
+
+            if let Some(score) = child.score().filter(|_| child.is_valid()) {
+                applications[application.operator] += 1.0;
+                if objective.is_better(score, &application.parent) {
+                    successes[application.operator] += 1.0;
+                }
+            }
+        }
+
+        let mut credit = Vec::with_capacity(self.alterers.len());
+        for operator in 0..self.alterers.len() {
+            let rate = match applications[operator] > 0.0 {
+                true => successes[operator] / applications[operator],
+                false => 0.0,
+            };
+
+            state.applications[operator] += applications[operator];
+            state.rewards[operator] += successes[operator];
+
+            if let AdaptivePolicy::ProbabilityMatching {
+                adaptation_rate, ..
+            } = self.policy
+            {
+                if applications[operator] > 0.0 {
+                    state.quality[operator] += adaptation_rate * (rate - state.quality[operator]);
+                }
+            }
+
+            credit.push(rate);
+        }
+
+        let mut metric = Metric::new_distribution(metric_names::OPERATOR_CREDIT);
+        metric.add_sequence(&credit);
+        vec![metric]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, Genotype, Optimize};
+
+    fn population(size: usize) -> Population<FloatChromosome> {
+        (0..size)
+            .map(|i| {
+                let genotype = Genotype::new(vec![FloatChromosome::from(&[i as f32; 2][..])]);
+                let mut phenotype = Phenotype::from_genotype(genotype, 0);
+                phenotype.set_score(Some(Score::from_f32(10.0)));
+                phenotype
+            })
+            .collect()
+    }
+
+    /// Mutates every gene, and - scored by `score` below - always improves on its parent.
+    struct Better;
+
+    /// Mutates every gene, and never improves on its parent.
+    struct Worse;
+
+    macro_rules! test_mutator {
+        ($name:ident, $value:expr) => {
+            impl EngineCompoment for $name {
+                fn name(&self) -> &'static str {
+                    stringify!($name)
+                }
+            }
+
+            impl Alter<FloatChromosome> for $name {
+                fn rate(&self) -> f32 {
+                    1.0
+                }
+
+                fn to_alter(self) -> AlterAction<FloatChromosome> {
+                    AlterAction::Mutate(Box::new(self))
+                }
+            }
+
+            impl crate::Mutate<FloatChromosome> for $name {
+                fn mutate_gene(&self, _: &crate::FloatGene, _: &RngHandle) -> crate::FloatGene {
+                    crate::FloatGene::from($value)
+                }
+            }
+        };
+    }
+
+    test_mutator!(Better, 1.0_f32);
+    test_mutator!(Worse, 100.0_f32);
+
+    fn score(population: &mut Population<FloatChromosome>) {
+        for phenotype in population.iter_mut() {
+            let value = phenotype.genotype()[0].genes[0].allele;
+            phenotype.set_score(Some(Score::from_f32(value)));
+        }
+    }
+
+    #[test]
+    fn test_probability_matching_prefers_successful_operator() {
+        let rng = RngHandle::from_entropy();
+        let alterer = AdaptiveChoice::new(vec![Worse.to_alter(), Better.to_alter()]);
+        let objective = Objective::Single(Optimize::Minimize);
+
+        let mut probabilities = Vec::new();
+        for generation in 0..20 {
+            let mut population = population(50);
+            let metrics = alterer.compose(&mut population, generation, &rng);
+            score(&mut population);
+            let credit = alterer.observe(population.as_ref(), &objective);
+
+            probabilities = metrics.last().unwrap().last_sequence().unwrap().clone();
+            assert_eq!(credit[0].last_sequence().unwrap()[0], 0.0);
+        }
+
+        assert!(probabilities[1] > 0.9);
+        assert!(probabilities[0] >= 0.05);
+        assert!((probabilities.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_upper_confidence_bound_prefers_successful_operator() {
+        let rng = RngHandle::from_entropy();
+        let alterer = AdaptiveChoice::new(vec![Worse.to_alter(), Better.to_alter()])
+            .with_policy(AdaptivePolicy::UpperConfidenceBound { exploration: 0.5 });
+        let objective = Objective::Single(Optimize::Minimize);
+
+        let mut shares = Vec::new();
+        for generation in 0..20 {
+            let mut population = population(50);
+            let metrics = alterer.compose(&mut population, generation, &rng);
+            score(&mut population);
+            alterer.observe(population.as_ref(), &objective);
+
+            shares = metrics.last().unwrap().last_sequence().unwrap().clone();
+        }
+
+        assert!(shares[1] > shares[0]);
+    }
+
+    #[test]
+    fn test_credit_follows_the_tags_of_the_offspring() {
+        let rng = RngHandle::from_entropy();
+        let alterer = AdaptiveChoice::new(vec![Worse.to_alter(), Better.to_alter()]);
+        let objective = Objective::Single(Optimize::Minimize);
+
+        let mut population = population(50);
+        alterer.compose(&mut population, 0, &rng);
+        score(&mut population);
+
+        let mut offspring = population.into_iter().collect::<Vec<_>>();
+        offspring.reverse();
+        offspring.truncate(40);
+        let credit = alterer.observe(&offspring, &objective);
+
+        assert_eq!(credit[0].last_sequence().unwrap(), &vec![0.0, 1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_adaptive_choice_panics_without_alterers() {
+        AdaptiveChoice::<FloatChromosome>::new(vec![]);
+    }
+}
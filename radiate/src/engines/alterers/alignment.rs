@@ -0,0 +1,93 @@
+use crate::Description;
+use crate::{
+    alignment, random_provider::RngHandle, Chromosome, EngineCompoment, Gene, SequenceChromosome,
+};
+
+use super::{Alter, AlterAction, Crossover};
+
+/// The `AlignmentCrossover` is a one-point crossover for variable-length `SequenceChromosome`s. The two
+/// parents are aligned (minimum edit distance alignment) and the cut is made at a randomly chosen pair
+/// of matching genes, so the swapped tails start at homologous positions even when the parents have
+/// different lengths. If the parents share no genes, the cut points are chosen at the same relative
+/// position in each parent instead.
+///
+/// Offspring that would fall outside of the chromosome's length limits are discarded and the parents
+/// are left unchanged.
+pub struct AlignmentCrossover {
+    rate: f32,
+}
+
+impl AlignmentCrossover {
+    /// Create a new instance of the `AlignmentCrossover` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        AlignmentCrossover { rate }
+    }
+}
+
+impl EngineCompoment for AlignmentCrossover {
+    fn name(&self) -> &'static str {
+        "AlignmentCrossover"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
+}
+
+impl<G: Gene + 'static> Alter<SequenceChromosome<G>> for AlignmentCrossover {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<SequenceChromosome<G>> {
+        AlterAction::Crossover(Box::new(self))
+    }
+}
+
+impl<G: Gene + 'static> Crossover<SequenceChromosome<G>> for AlignmentCrossover {
+    #[inline]
+    fn cross_chromosomes(
+        &self,
+        chrom_one: &mut SequenceChromosome<G>,
+        chrom_two: &mut SequenceChromosome<G>,
+        rng: &RngHandle,
+    ) -> i32 {
+        let matches = alignment(&chrom_one.genes, &chrom_two.genes);
+
+        let (one_point, two_point) = if matches.is_empty() {
+            let one_point = rng.gen_range(0..chrom_one.len() + 1);
+            let two_point = match chrom_one.len() {
+                0 => 0,
+                len => one_point * chrom_two.len() / len,
+            };
+
+            (one_point, two_point)
+        } else {
+            *rng.choose(&matches)
+        };
+
+        let new_one_len = one_point + chrom_two.len() - two_point;
+        let new_two_len = two_point + chrom_one.len() - one_point;
+
+        if new_one_len < chrom_one.min_len
+            || new_one_len > chrom_one.max_len
+            || new_two_len < chrom_two.min_len
+            || new_two_len > chrom_two.max_len
+        {
+            return 0;
+        }
+
+        let tail_one = chrom_one.genes.split_off(one_point);
+        let tail_two = chrom_two.genes.split_off(two_point);
+
+        chrom_one.genes.extend(tail_two);
+        chrom_two.genes.extend(tail_one);
+
+        1
+    }
+}
@@ -1,10 +1,46 @@
-use crate::{Chromosome, EngineCompoment};
+use crate::objectives::Objective;
+use crate::random_provider::RngHandle;
+use crate::{Chromosome, Description, EngineCompoment, Metric, Phenotype, Population};
 
-use super::{Crossover, Mutate};
+use super::{Compose, Crossover, Mutate};
 
 pub enum AlterAction<C: Chromosome> {
     Mutate(Box<dyn Mutate<C>>),
     Crossover(Box<dyn Crossover<C>>),
+    Compose(Box<dyn Compose<C>>),
+}
+
+impl<C: Chromosome> AlterAction<C> {
+    /// Apply the alterer to the population, drawing from `rng`, and return the metrics it recorded.
+    pub fn alter(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        match self {
+            AlterAction::Mutate(mutator) => mutator.mutate(population, generation, rng),
+            AlterAction::Crossover(crossover) => crossover.crossover(population, generation, rng),
+            AlterAction::Compose(compose) => compose.compose(population, generation, rng),
+        }
+    }
+
+    /// The name and parameters of the alterer (see `EngineCompoment::describe`).
+    pub fn describe(&self) -> Description {
+        match self {
+            AlterAction::Mutate(mutator) => mutator.describe(),
+            AlterAction::Crossover(crossover) => crossover.describe(),
+            AlterAction::Compose(compose) => compose.describe(),
+        }
+    }
+
+    /// Let the alterer learn from the evaluated offspring it produced (see `Compose::observe`).
+    pub fn observe(&self, offspring: &[Phenotype<C>], objective: &Objective) -> Vec<Metric> {
+        match self {
+            AlterAction::Compose(compose) => compose.observe(offspring, objective),
+            _ => Vec::new(),
+        }
+    }
 }
 
 pub trait Alter<C: Chromosome>: EngineCompoment {
@@ -1,4 +1,5 @@
-use crate::{random_provider, Chromosome, EngineCompoment, Gene};
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment, NumericGene};
 use std::ops::{Add, Div, Mul, Sub};
 
 use super::{Alter, AlterAction, Mutate};
@@ -8,8 +9,9 @@ use super::{Alter, AlterAction, Mutate};
 /// a gene will be mutated. The ArithmeticMutator can perform addition, subtraction,
 /// multiplication, and division on genes.
 ///
-/// This is a simple mutator that can be used with any gene that implements the
-/// `Add`, `Sub`, `Mul`, and `Div` traits - `NumericGene` is a good example.
+/// This is a simple mutator that can be used with any `NumericGene` that implements the
+/// `Add`, `Sub`, `Mul`, and `Div` traits. The result is brought back within the gene's bounds
+/// by its `BoundaryPolicy`.
 pub struct ArithmeticMutator {
     rate: f32,
 }
@@ -18,7 +20,7 @@ impl ArithmeticMutator {
     /// Create a new instance of the `ArithmeticMutator` with the given rate.
     /// The rate must be between 0.0 and 1.0.
     pub fn new(rate: f32) -> Self {
-        if rate < 0.0 || rate > 1.0 {
+        if !(0.0..=1.0).contains(&rate) {
             panic!("Rate must be between 0 and 1");
         }
 
@@ -28,26 +30,29 @@ impl ArithmeticMutator {
     /// Mutate a gene by performing an arithmetic operation on it.
     /// Randomly select a number between 0 and 3, and perform the corresponding
     /// arithmetic operation on the gene.
-    pub fn mutate_gene<T>(gene: &T) -> T
+    pub fn mutate_gene<T>(gene: &T, rng: &RngHandle) -> T
     where
-        T: Gene + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+        T: NumericGene + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
     {
         let new_instance = gene.new_instance();
-        let operator = random_provider::gen_range(0..4);
+        let operator = rng.gen_range(0..4);
 
-        match operator {
+        let result = match operator {
             0 => gene.clone() + new_instance,
             1 => gene.clone() - new_instance,
             2 => gene.clone() * new_instance,
             3 => gene.clone() / new_instance,
             _ => panic!("Invalid operator: {}", operator),
-        }
+        };
+
+        gene.bounded(result.allele())
     }
 }
 
 impl<C: Chromosome> Alter<C> for ArithmeticMutator
 where
-    C::Gene: Add<Output = C::Gene>
+    C::Gene: NumericGene
+        + Add<Output = C::Gene>
         + Sub<Output = C::Gene>
         + Mul<Output = C::Gene>
         + Div<Output = C::Gene>,
@@ -65,21 +70,26 @@ impl EngineCompoment for ArithmeticMutator {
     fn name(&self) -> &'static str {
         "ArithmeticMutator"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Mutate<C> for ArithmeticMutator
 where
-    C::Gene: Add<Output = C::Gene>
+    C::Gene: NumericGene
+        + Add<Output = C::Gene>
         + Sub<Output = C::Gene>
         + Mul<Output = C::Gene>
         + Div<Output = C::Gene>,
 {
-    fn mutate_chromosome(&self, chromosome: &mut C) -> i32 {
+    fn mutate_chromosome(&self, chromosome: &mut C, rng: &RngHandle) -> i32 {
         let mut mutations = 0;
         for i in 0..chromosome.len() {
-            if random_provider::random::<f32>() < self.rate {
+            if rng.random::<f32>() < self.rate {
                 let curr_gene = chromosome.get_gene(i);
-                let new_gene = ArithmeticMutator::mutate_gene(curr_gene);
+                let new_gene = ArithmeticMutator::mutate_gene(curr_gene, rng);
 
                 chromosome.set_gene(i, new_gene);
                 mutations += 1;
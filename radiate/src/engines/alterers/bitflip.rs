@@ -0,0 +1,51 @@
+use crate::Description;
+use crate::{random_provider::RngHandle, ByteGene, Chromosome, EngineCompoment};
+
+use super::{Alter, AlterAction, Mutate};
+
+/// The `BitFlipMutator` flips a single random bit of a `ByteGene` - the most basic mutation
+/// used by fuzzers. Pair it with the `InsertionMutator` and `DeletionMutator` to also vary the
+/// length of a `BytesChromosome`.
+pub struct BitFlipMutator {
+    rate: f32,
+}
+
+impl BitFlipMutator {
+    /// Create a new instance of the `BitFlipMutator` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        BitFlipMutator { rate }
+    }
+}
+
+impl EngineCompoment for BitFlipMutator {
+    fn name(&self) -> &'static str {
+        "BitFlipMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
+}
+
+impl<C: Chromosome<Gene = ByteGene>> Alter<C> for BitFlipMutator {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<C: Chromosome<Gene = ByteGene>> Mutate<C> for BitFlipMutator {
+    #[inline]
+    fn mutate_gene(&self, gene: &C::Gene, rng: &RngHandle) -> C::Gene {
+        let bit = rng.gen_range(0..8);
+        ByteGene::from(gene.allele ^ (1 << bit))
+    }
+}
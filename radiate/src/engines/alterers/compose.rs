@@ -0,0 +1,828 @@
+use crate::Description;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::objectives::{Objective, Optimize, Score};
+use crate::timer::Timer;
+use crate::{
+    random_provider::RngHandle, Chromosome, EngineCompoment, Genotype, Metric, Phenotype,
+    Population,
+};
+
+use super::{Alter, AlterAction};
+
+/// An alterer built out of other alterers. Unlike a `Mutate` or a `Crossover`, a `Compose` decides
+/// which individuals each of its inner alterers is applied to.
+pub trait Compose<C: Chromosome>: Alter<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric>;
+
+    /// Called by the engine once the offspring produced by the last call to `compose` have been
+    /// evaluated, in the same order they were altered in, so the alterer can learn from how they scored.
+    fn observe(&self, _offspring: &[Phenotype<C>], _objective: &Objective) -> Vec<Metric> {
+        Vec::new()
+    }
+}
+
+/// Applies one of several alterers to each individual, picked at random in proportion to its weight.
+/// The individuals that picked the same alterer are altered together, so a crossover only mates
+/// individuals that also picked it. Each alterer observes the evaluated offspring it altered (see
+/// `Compose::observe`).
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let alterer: Choice<FloatChromosome> = Choice::new(vec![
+///     (0.7, UniformCrossover::new(0.5).to_alter()),
+///     (0.3, MeanCrossover::new(0.5).to_alter()),
+/// ]);
+/// ```
+pub struct Choice<C: Chromosome> {
+    alterers: Vec<(f32, AlterAction<C>)>,
+    total: f32,
+    groups: Mutex<Vec<Vec<usize>>>,
+}
+
+impl<C: Chromosome> Choice<C> {
+    /// Create a new `Choice` from `(weight, alterer)` pairs. Panics if there are no alterers,
+    /// any weight is negative, or the weights sum to 0.
+    pub fn new(alterers: Vec<(f32, AlterAction<C>)>) -> Self {
+        if alterers.is_empty() {
+            panic!("Choice requires at least one alterer");
+        }
+
+        if alterers.iter().any(|(weight, _)| *weight < 0.0) {
+            panic!("Choice weights must be non-negative");
+        }
+
+        let total = alterers.iter().map(|(weight, _)| weight).sum::<f32>();
+        if total <= 0.0 {
+            panic!("Choice weights must sum to a positive value");
+        }
+
+        Choice {
+            alterers,
+            total,
+            groups: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn pick(&self, rng: &RngHandle) -> usize {
+        let mut value = rng.random::<f32>() * self.total;
+        for (index, (weight, _)) in self.alterers.iter().enumerate() {
+            value -= weight;
+            if value < 0.0 {
+                return index;
+            }
+        }
+
+        self.alterers.len() - 1
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for Choice<C> {
+    fn name(&self) -> &'static str {
+        "Choice"
+    }
+
+    fn describe(&self) -> Description {
+        self.alterers.iter().fold(
+            Description::new(self.name()),
+            |description, (weight, alterer)| {
+                description.child(alterer.describe().param("weight", weight))
+            },
+        )
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for Choice<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for Choice<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        let mut groups = vec![Vec::new(); self.alterers.len()];
+        for index in 0..population.len() {
+            groups[self.pick(rng)].push(index);
+        }
+
+        let mut metrics = Vec::new();
+        for ((_, alterer), group) in self.alterers.iter().zip(groups.iter()) {
+            metrics.extend(alter_group(alterer, population, group, generation, rng));
+        }
+
+        *self.groups.lock().unwrap() = groups;
+        metrics
+    }
+
+    fn observe(&self, offspring: &[Phenotype<C>], objective: &Objective) -> Vec<Metric> {
+        let groups = self.groups.lock().unwrap();
+        self.alterers
+            .iter()
+            .zip(groups.iter())
+            .flat_map(|((_, alterer), group)| observe_group(alterer, offspring, group, objective))
+            .collect()
+    }
+}
+
+/// Applies each of its alterers in order to the whole population. On its own this is the same as
+/// listing the alterers one after the other, but it lets a series of alterers be used as a single
+/// alterer inside a `Choice` or an `If`.
+pub struct Sequence<C: Chromosome> {
+    alterers: Vec<AlterAction<C>>,
+}
+
+impl<C: Chromosome> Sequence<C> {
+    pub fn new(alterers: Vec<AlterAction<C>>) -> Self {
+        Sequence { alterers }
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for Sequence<C> {
+    fn name(&self) -> &'static str {
+        "Sequence"
+    }
+
+    fn describe(&self) -> Description {
+        self.alterers
+            .iter()
+            .fold(Description::new(self.name()), |description, alterer| {
+                description.child(alterer.describe())
+            })
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for Sequence<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for Sequence<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        self.alterers
+            .iter()
+            .flat_map(|alterer| alterer.alter(population, generation, rng))
+            .collect()
+    }
+
+    fn observe(&self, offspring: &[Phenotype<C>], objective: &Objective) -> Vec<Metric> {
+        self.alterers
+            .iter()
+            .flat_map(|alterer| alterer.observe(offspring, objective))
+            .collect()
+    }
+}
+
+type Predicate<C> = Box<dyn Fn(&Phenotype<C>, i32) -> bool>;
+
+/// Applies an alterer only to the individuals that satisfy a predicate. The predicate is given the
+/// individual and the current generation, so it can gate on the individual's age, score or metadata.
+/// The alterer observes the evaluated offspring it altered (see `Compose::observe`).
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Only mutate individuals that have survived more than 5 generations.
+/// let alterer: If<FloatChromosome> = If::new(
+///     |phenotype: &Phenotype<FloatChromosome>, generation| phenotype.age(generation) > 5,
+///     GaussianMutator::new(0.1).to_alter(),
+/// );
+/// ```
+pub struct If<C: Chromosome> {
+    predicate: Predicate<C>,
+    alterer: AlterAction<C>,
+    group: Mutex<Vec<usize>>,
+}
+
+impl<C: Chromosome> If<C> {
+    pub fn new<F>(predicate: F, alterer: AlterAction<C>) -> Self
+    where
+        F: Fn(&Phenotype<C>, i32) -> bool + 'static,
+    {
+        If {
+            predicate: Box::new(predicate),
+            alterer,
+            group: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for If<C> {
+    fn name(&self) -> &'static str {
+        "If"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).child(self.alterer.describe())
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for If<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for If<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        let group = population
+            .iter()
+            .enumerate()
+            .filter(|(_, phenotype)| (self.predicate)(phenotype, generation))
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+
+        let metrics = alter_group(&self.alterer, population, &group, generation, rng);
+        *self.group.lock().unwrap() = group;
+        metrics
+    }
+
+    fn observe(&self, offspring: &[Phenotype<C>], objective: &Objective) -> Vec<Metric> {
+        observe_group(
+            &self.alterer,
+            offspring,
+            &self.group.lock().unwrap(),
+            objective,
+        )
+    }
+}
+
+/// Restricts an alterer - usually a crossover - to mates of the same species, as in NEAT. The individuals
+/// are grouped by their species (see `Phenotype::species`) and every species is altered on its own, except
+/// that each individual joins a shared interspecies group with probability `interspecies_rate`, where it
+/// mates with individuals of other species. Individuals without a species are grouped together, so an
+/// unspeciated population is altered as usual.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let alterer: WithinSpecies<FloatChromosome> =
+///     WithinSpecies::new(UniformCrossover::new(0.5).to_alter()).interspecies_rate(0.01);
+/// ```
+pub struct WithinSpecies<C: Chromosome> {
+    alterer: AlterAction<C>,
+    interspecies_rate: f32,
+}
+
+impl<C: Chromosome> WithinSpecies<C> {
+    /// Create a new `WithinSpecies` with NEAT's interspecies rate of 0.001.
+    pub fn new(alterer: AlterAction<C>) -> Self {
+        WithinSpecies {
+            alterer,
+            interspecies_rate: 0.001,
+        }
+    }
+
+    /// Set the probability that an individual mates outside of its species. Panics if `rate` is not
+    /// between 0 and 1.
+    pub fn interspecies_rate(mut self, rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("interspecies_rate must be between 0 and 1");
+        }
+
+        self.interspecies_rate = rate;
+        self
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for WithinSpecies<C> {
+    fn name(&self) -> &'static str {
+        "WithinSpecies"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("interspecies_rate", self.interspecies_rate)
+            .child(self.alterer.describe())
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for WithinSpecies<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for WithinSpecies<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        let mut species = BTreeMap::<Option<usize>, Vec<usize>>::new();
+        let mut interspecies = Vec::new();
+        for (index, phenotype) in population.iter().enumerate() {
+            if rng.random::<f32>() < self.interspecies_rate {
+                interspecies.push(index);
+            } else {
+                species.entry(phenotype.species()).or_default().push(index);
+            }
+        }
+
+        let mut metrics = Vec::new();
+        for group in species.values().chain(std::iter::once(&interspecies)) {
+            metrics.extend(alter_group(
+                &self.alterer,
+                population,
+                group,
+                generation,
+                rng,
+            ));
+        }
+
+        metrics
+    }
+}
+
+type Screen<C> = Box<dyn Fn(&Genotype<C>) -> Score>;
+
+/// Brood recombination: every pair of parents produces a brood of `brood_size` children with a crossover,
+/// the children are pre-screened with the `screen` function and only the best `keep` of them replace the
+/// parents. Useful when the crossover is destructive (e.g. subtree crossover in tree GP), as most of its
+/// children never reach the population. The screen can be a cheap proxy of the fitness or the fitness
+/// itself (decoding the genotype with the codex).
+///
+/// The parents are paired at random. With `keep` of 1 only the first parent of a pair is replaced and
+/// the other is left as it was. A pair whose brood didn't change at all is left as it was.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Keep the two children with the smallest sum out of a brood of 8.
+/// let alterer: Brood<FloatChromosome> = Brood::new(
+///     UniformCrossover::new(0.5).to_alter(),
+///     8,
+///     |genotype: &Genotype<FloatChromosome>| {
+///         genotype.iter().flat_map(|c| c.iter()).map(|g| g.allele).sum::<f32>()
+///     },
+/// )
+/// .minimizing();
+/// ```
+pub struct Brood<C: Chromosome> {
+    crossover: AlterAction<C>,
+    brood_size: usize,
+    keep: usize,
+    screen: Screen<C>,
+    optimize: Optimize,
+}
+
+impl<C: Chromosome> Brood<C> {
+    /// Create a new `Brood` that keeps the best 2 of `brood_size` children, maximizing the `screen`.
+    /// Panics if `brood_size` is less than 2.
+    pub fn new<F, S>(crossover: AlterAction<C>, brood_size: usize, screen: F) -> Self
+    where
+        F: Fn(&Genotype<C>) -> S + 'static,
+        S: Into<Score>,
+    {
+        if brood_size < 2 {
+            panic!("brood_size must be at least 2");
+        }
+
+        Brood {
+            crossover,
+            brood_size,
+            keep: 2,
+            screen: Box::new(move |genotype| screen(genotype).into()),
+            optimize: Optimize::Maximize,
+        }
+    }
+
+    /// Set the number of children kept per pair of parents. Panics if `keep` is not 1 or 2.
+    pub fn keep(mut self, keep: usize) -> Self {
+        if !(1..=2).contains(&keep) {
+            panic!("keep must be 1 or 2");
+        }
+
+        self.keep = keep;
+        self
+    }
+
+    /// Keep the children with the lowest screen instead of the highest.
+    pub fn minimizing(mut self) -> Self {
+        self.optimize = Optimize::Minimize;
+        self
+    }
+
+    /// Breed the pair at `parents` until the brood is full. Only the children the crossover changed
+    /// count - the parents themselves are never part of the brood.
+    fn breed(
+        &self,
+        population: &Population<C>,
+        parents: &[usize],
+        generation: i32,
+        metrics: &mut Vec<Metric>,
+        rng: &RngHandle,
+    ) -> Vec<Phenotype<C>> {
+        let mut brood = Vec::with_capacity(self.brood_size);
+        for _ in 0..self.brood_size {
+            let mut pair = parents
+                .iter()
+                .map(|&index| population[index].clone())
+                .collect::<Population<C>>();
+
+            metrics.extend(self.crossover.alter(&mut pair, generation, rng));
+            brood.extend(pair.into_iter().filter(|child| {
+                parents
+                    .iter()
+                    .all(|&index| population[index].genotype() != child.genotype())
+            }));
+
+            if brood.len() >= self.brood_size {
+                break;
+            }
+        }
+
+        brood.truncate(self.brood_size);
+        brood
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for Brood<C> {
+    fn name(&self) -> &'static str {
+        "Brood"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("brood_size", self.brood_size)
+            .param("keep", self.keep)
+            .param("optimize", format!("{:?}", self.optimize))
+            .child(self.crossover.describe())
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for Brood<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for Brood<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        let timer = Timer::new();
+        let mut metrics = Vec::new();
+        let mut screened = 0;
+
+        let order = rng.indexes(population.len());
+        for parents in order.chunks_exact(2) {
+            let brood = self.breed(population, parents, generation, &mut metrics, rng);
+            if brood.is_empty() {
+                continue;
+            }
+
+            let mut ranked = brood
+                .into_iter()
+                .map(|child| ((self.screen)(child.genotype()), child))
+                .collect::<Vec<(Score, Phenotype<C>)>>();
+            screened += ranked.len();
+
+            ranked.sort_by(|(one, _), (two, _)| {
+                if self.optimize.is_better(one, two) {
+                    Ordering::Less
+                } else if self.optimize.is_better(two, one) {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            });
+
+            for (&index, (_, child)) in parents.iter().zip(ranked).take(self.keep) {
+                population[index] = child;
+            }
+        }
+
+        let mut metric = Metric::new_operations(self.name());
+        metric.add_value(screened as f32);
+        metric.add_duration(timer.duration());
+        metrics.push(metric);
+
+        metrics
+    }
+}
+
+/// Apply `alterer` to the individuals at `indexes` only, by moving them into a population of their
+/// own and back. A crossover needs at least two individuals, so smaller groups are left unaltered.
+pub(crate) fn alter_group<C: Chromosome>(
+    alterer: &AlterAction<C>,
+    population: &mut Population<C>,
+    indexes: &[usize],
+    generation: i32,
+    rng: &RngHandle,
+) -> Vec<Metric> {
+    let min_size = match alterer {
+        AlterAction::Crossover(_) => 2,
+        _ => 1,
+    };
+
+    if indexes.len() < min_size {
+        return Vec::new();
+    }
+
+    let mut group = indexes
+        .iter()
+        .map(|&index| {
+            std::mem::replace(
+                &mut population[index],
+                Phenotype {
+                    genotype: None,
+                    score: None,
+                    generation,
+                    metadata: None,
+                    species: None,
+                    tags: Vec::new(),
+                    dirty: true,
+                },
+            )
+        })
+        .collect::<Population<C>>();
+
+    let metrics = alterer.alter(&mut group, generation, rng);
+
+    for (&index, phenotype) in indexes.iter().zip(group) {
+        population[index] = phenotype;
+    }
+
+    metrics
+}
+
+/// Let `alterer` observe the offspring at `indexes` - the group `alter_group` applied it to - in the
+/// order it altered them in.
+pub(crate) fn observe_group<C: Chromosome>(
+    alterer: &AlterAction<C>,
+    offspring: &[Phenotype<C>],
+    indexes: &[usize],
+    objective: &Objective,
+) -> Vec<Metric> {
+    if !matches!(alterer, AlterAction::Compose(_)) || indexes.is_empty() {
+        return Vec::new();
+    }
+
+    let group = indexes
+        .iter()
+        .filter_map(|&index| offspring.get(index).cloned())
+        .collect::<Vec<Phenotype<C>>>();
+
+    alterer.observe(&group, objective)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, GaussianMutator, Genotype, UniformCrossover};
+
+    fn population(size: usize) -> Population<FloatChromosome> {
+        (0..size)
+            .map(|i| {
+                let genotype = Genotype::new(vec![FloatChromosome::from(&[i as f32; 4][..])]);
+                Phenotype::from_genotype(genotype, i as i32)
+            })
+            .collect()
+    }
+
+    fn changed(before: &Population<FloatChromosome>, after: &Population<FloatChromosome>) -> usize {
+        before
+            .iter()
+            .zip(after.iter())
+            .filter(|(one, two)| one.genotype() != two.genotype())
+            .count()
+    }
+
+    #[test]
+    fn test_alterers_only_draw_from_the_handle_they_are_given() {
+        let alter = |global_seed: u64| {
+            let alterer: Choice<FloatChromosome> = Choice::new(vec![
+                (0.5, UniformCrossover::new(0.5).to_alter()),
+                (0.5, GaussianMutator::new(0.5).to_alter()),
+            ]);
+
+            let mut population = population(10);
+            RngHandle::seeded(global_seed)
+                .scope(|| alterer.compose(&mut population, 10, &RngHandle::seeded(3)));
+            population
+        };
+
+        let (first, second) = (alter(1), alter(2));
+        assert!(first.iter().eq(second.iter()));
+        assert!(changed(&population(10), &first) > 0);
+    }
+
+    #[test]
+    fn test_if_only_alters_matching_individuals() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(10);
+        let before = population.clone();
+
+        let alterer = If::new(
+            |phenotype: &Phenotype<FloatChromosome>, generation| phenotype.age(generation) >= 5,
+            GaussianMutator::new(1.0).to_alter(),
+        );
+        alterer.compose(&mut population, 10, &rng);
+
+        for (one, two) in before.iter().zip(population.iter()) {
+            assert_eq!(one.generation <= 5, one.genotype() != two.genotype());
+        }
+    }
+
+    #[test]
+    fn test_choice_with_zero_weight_never_picks_alterer() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(20);
+        let before = population.clone();
+
+        let alterer = Choice::new(vec![
+            (0.0, GaussianMutator::new(1.0).to_alter()),
+            (1.0, Sequence::new(vec![]).to_alter()),
+        ]);
+        alterer.compose(&mut population, 20, &rng);
+
+        assert_eq!(changed(&before, &population), 0);
+    }
+
+    #[test]
+    fn test_sequence_applies_every_alterer() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(10);
+        let before = population.clone();
+
+        let alterer = Sequence::new(vec![
+            Sequence::new(vec![]).to_alter(),
+            GaussianMutator::new(1.0).to_alter(),
+        ]);
+        let metrics = alterer.compose(&mut population, 10, &rng);
+
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(changed(&before, &population), 10);
+    }
+
+    #[test]
+    fn test_within_species_only_mates_within_a_species() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(10);
+        for (index, phenotype) in population.iter_mut().enumerate() {
+            phenotype.set_species(Some(index % 2));
+        }
+
+        let alterer =
+            WithinSpecies::new(UniformCrossover::new(1.0).to_alter()).interspecies_rate(0.0);
+        alterer.compose(&mut population, 10, &rng);
+
+        for (index, phenotype) in population.iter().enumerate() {
+            assert!(phenotype.genotype()[0]
+                .iter()
+                .all(|gene| gene.allele as usize % 2 == index % 2));
+        }
+    }
+
+    #[test]
+    fn test_alterers_mark_the_individuals_they_change_dirty() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(10);
+        for phenotype in population.iter_mut() {
+            phenotype.set_score(Some(Score::from_f32(0.0)));
+        }
+        assert!(population.iter().all(|phenotype| !phenotype.is_dirty()));
+
+        let alterer = If::new(
+            |phenotype: &Phenotype<FloatChromosome>, generation| phenotype.age(generation) >= 5,
+            Sequence::new(vec![
+                GaussianMutator::new(1.0).to_alter(),
+                UniformCrossover::new(1.0).to_alter(),
+            ])
+            .to_alter(),
+        );
+        alterer.compose(&mut population, 10, &rng);
+
+        for (index, phenotype) in population.iter().enumerate() {
+            assert_eq!(phenotype.is_dirty(), index <= 5);
+            assert_eq!(phenotype.score().is_none(), index <= 5);
+        }
+    }
+
+    #[test]
+    fn test_choice_and_if_forward_observe_to_the_applied_alterer() {
+        let rng = RngHandle::from_entropy();
+        let objective = Objective::Single(Optimize::Minimize);
+        let adaptive =
+            || crate::AdaptiveChoice::new(vec![GaussianMutator::new(1.0).to_alter()]).to_alter();
+
+        let choice = Choice::new(vec![(1.0, adaptive()), (0.0, adaptive())]);
+        let when = If::new(|_: &Phenotype<FloatChromosome>, _| true, adaptive());
+        let alterers: [&dyn Compose<FloatChromosome>; 2] = [&choice, &when];
+
+        for alterer in alterers {
+            let mut population = population(10);
+            for phenotype in population.iter_mut() {
+                phenotype.set_score(Some(Score::from_f32(0.0)));
+            }
+
+            alterer.compose(&mut population, 10, &rng);
+            for phenotype in population.iter_mut() {
+                phenotype.set_score(Some(Score::from_f32(1.0)));
+            }
+
+            let metrics = alterer.observe(population.as_ref(), &objective);
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics[0].last_sequence().unwrap(), &vec![0.0]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_choice_panics_without_positive_weights() {
+        Choice::<FloatChromosome>::new(vec![(0.0, GaussianMutator::new(1.0).to_alter())]);
+    }
+
+    #[test]
+    fn test_brood_keeps_the_best_screened_child() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(2);
+        for phenotype in population.iter_mut() {
+            phenotype.set_score(Some(Score::from_f32(0.0)));
+        }
+        let before = population.clone();
+
+        let alterer = Brood::new(
+            UniformCrossover::new(0.5).to_alter(),
+            20,
+            |genotype: &Genotype<FloatChromosome>| {
+                genotype
+                    .iter()
+                    .flat_map(|c| c.iter())
+                    .map(|g| g.allele)
+                    .sum::<f32>()
+            },
+        )
+        .keep(1);
+        alterer.compose(&mut population, 3, &rng);
+
+        let sums = population
+            .iter()
+            .map(|phenotype| {
+                phenotype
+                    .genotype()
+                    .iter()
+                    .flat_map(|c| c.iter())
+                    .map(|g| g.allele)
+                    .sum::<f32>()
+            })
+            .collect::<Vec<f32>>();
+        let kept = if population[0].is_dirty() { 0 } else { 1 };
+
+        assert!(sums[kept] >= 2.0);
+        assert!(population[1 - kept].genotype() == before[1 - kept].genotype());
+        assert_eq!(population[kept].generation, 3);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::random_provider::RngHandle;
+use crate::Description;
+use crate::{Chromosome, ControlPanel, EngineCompoment, Knob};
+
+use super::{Alter, AlterAction, Mutate};
+
+/// A mutator whose rate follows the `Knob::MutationRate` of a `ControlPanel`, so it can be changed while
+/// the engine runs. Until the knob is set, the mutator's own rate is used.
+///
+/// The wrapped mutator is applied gene by gene with `Mutate::mutate_gene`, so mutators that work on
+/// whole chromosomes (e.g. the `SwapMutator`) should be steered with their own parameters instead.
+pub struct ControlledMutator<M> {
+    mutator: M,
+    panel: ControlPanel,
+}
+
+impl<M> ControlledMutator<M> {
+    pub fn new(mutator: M, panel: ControlPanel) -> Self {
+        ControlledMutator { mutator, panel }
+    }
+}
+
+impl<M: EngineCompoment> EngineCompoment for ControlledMutator<M> {
+    fn name(&self) -> &'static str {
+        self.mutator.name()
+    }
+
+    fn describe(&self) -> Description {
+        self.mutator
+            .describe()
+            .param("control", Knob::MutationRate.key())
+    }
+}
+
+impl<C, M> Alter<C> for ControlledMutator<M>
+where
+    C: Chromosome + 'static,
+    M: Mutate<C> + 'static,
+{
+    fn rate(&self) -> f32 {
+        self.panel
+            .get(Knob::MutationRate)
+            .unwrap_or_else(|| self.mutator.rate())
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<C, M> Mutate<C> for ControlledMutator<M>
+where
+    C: Chromosome + 'static,
+    M: Mutate<C> + 'static,
+{
+    #[inline]
+    fn mutate_gene(&self, gene: &C::Gene, rng: &RngHandle) -> C::Gene {
+        self.mutator.mutate_gene(gene, rng)
+    }
+}
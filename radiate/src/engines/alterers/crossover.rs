@@ -1,6 +1,5 @@
-use crate::{
-    indexes, random_provider, timer::Timer, Chromosome, Gene, Metric, Phenotype, Population,
-};
+use crate::random_provider::RngHandle;
+use crate::{indexes, timer::Timer, Chromosome, Gene, Metric, Population};
 
 use super::Alter;
 
@@ -15,16 +14,33 @@ use super::Alter;
 /// or a subset of the population. If a struct implements the `Crossover` trait but does not override
 /// any of the methods, the default implementation will perform a simple crossover operation on the
 /// entire population. This is the case with the `UniformCrossover` struct.
+///
+/// Ownership: the offspring handed to an alterer are the engine's own copies (made by the offspring
+/// selector), so every alterer works on them in place - `cross_chromosomes` and `Mutate::mutate_chromosome`
+/// receive `&mut` chromosomes of the individuals in the population and no genotype is cloned along the
+/// way. An individual that was changed loses its score (so it is evaluated again) and is stamped with the
+/// current generation; individuals that weren't changed are left exactly as they were.
+///
+/// Randomness: every method is handed the engine's generator (see `random_provider::RngHandle`) and
+/// draws from it rather than from the `random_provider`, so the embedding application decides where an
+/// alterer's randomness comes from - `GeneticEngineParams::rng` or `seed`. New genes (`Gene::new_instance`)
+/// still come from the `random_provider`, which the engine scopes to the same generator.
 pub trait Crossover<C: Chromosome>: Alter<C> {
     #[inline]
-    fn crossover(&self, population: &mut Population<C>, generation: i32) -> Vec<Metric> {
+    fn crossover(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
         let timer = Timer::new();
         let mut count = 0;
 
         for i in 0..population.len() {
-            if random_provider::random::<f32>() < self.rate() {
-                let parent_indexes = indexes::individual_indexes(i, population.len(), 2);
-                count += self.cross(population, &parent_indexes, generation);
+            if rng.random::<f32>() < self.rate() {
+                let parent_indexes =
+                    rng.with(|rng| indexes::individual_indexes(i, population.len(), 2, rng));
+                count += self.cross(population, &parent_indexes, generation, rng);
             }
         }
 
@@ -41,36 +57,43 @@ pub trait Crossover<C: Chromosome>: Alter<C> {
         population: &mut Population<C>,
         parent_indexes: &[usize],
         generation: i32,
+        rng: &RngHandle,
     ) -> i32 {
         let index_one = parent_indexes[0];
         let index_two = parent_indexes[1];
+        if index_one == index_two {
+            return 0;
+        }
 
-        let mut geno_one = population[index_one].genotype().clone();
-        let mut geno_two = population[index_two].genotype().clone();
+        let (parent_one, parent_two) = population.pair_mut(index_one, index_two);
+        let (geno_one, geno_two) = (parent_one.genotype_mut(), parent_two.genotype_mut());
 
         let chromosome_index =
-            random_provider::random::<usize>() % std::cmp::min(geno_one.len(), geno_two.len());
+            rng.random::<usize>() % std::cmp::min(geno_one.len(), geno_two.len());
 
-        let chrom_one = &mut geno_one[chromosome_index];
-        let chrom_two = &mut geno_two[chromosome_index];
-
-        let cross_count = self.cross_chromosomes(chrom_one, chrom_two);
+        let cross_count = self.cross_chromosomes(
+            &mut geno_one[chromosome_index],
+            &mut geno_two[chromosome_index],
+            rng,
+        );
 
         if cross_count > 0 {
-            population[index_one] = Phenotype::from_genotype(geno_one, generation);
-            population[index_two] = Phenotype::from_genotype(geno_two, generation);
+            for parent in [parent_one, parent_two] {
+                parent.mark_dirty();
+                parent.generation = generation;
+            }
         }
 
         cross_count
     }
 
     #[inline]
-    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C) -> i32 {
+    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C, rng: &RngHandle) -> i32 {
         let rate = self.rate();
         let mut cross_count = 0;
 
         for i in 0..std::cmp::min(chrom_one.len(), chrom_two.len()) {
-            if random_provider::random::<f32>() < rate {
+            if rng.random::<f32>() < rate {
                 let gene_one = chrom_one.get_gene(i);
                 let gene_two = chrom_two.get_gene(i);
 
@@ -1,8 +1,12 @@
-use crate::{random_provider, Chromosome, EngineCompoment, FloatGene, Gene, NumericGene};
+use crate::Description;
+use crate::{
+    random_provider::RngHandle, Chromosome, EngineCompoment, FloatGene, Gene, NumericGene,
+};
 
 use super::{Alter, AlterAction, Mutate};
 
 /// The `GaussianMutator` is a simple mutator that adds a small amount of Gaussian noise to the gene.
+/// The result is brought back within the gene's bounds by its `BoundaryPolicy`.
 ///
 /// This mutator is for use with the `FloatChromosome` or any `Chromosome` which holds `FloatGene`s.
 pub struct GaussianMutator {
@@ -13,7 +17,7 @@ impl GaussianMutator {
     /// Create a new instance of the `GaussianMutator` with the given rate.
     /// The rate must be between 0.0 and 1.0.
     pub fn new(rate: f32) -> Self {
-        if rate < 0.0 || rate > 1.0 {
+        if !(0.0..=1.0).contains(&rate) {
             panic!("Rate must be between 0 and 1");
         }
 
@@ -40,6 +44,10 @@ impl EngineCompoment for GaussianMutator {
     fn name(&self) -> &'static str {
         "GaussianMutator"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome<Gene = FloatGene>> Alter<C> for GaussianMutator {
@@ -54,16 +62,15 @@ impl<C: Chromosome<Gene = FloatGene>> Alter<C> for GaussianMutator {
 
 impl<C: Chromosome<Gene = FloatGene>> Mutate<C> for GaussianMutator {
     #[inline]
-    fn mutate_gene(&self, gene: &C::Gene) -> C::Gene {
+    fn mutate_gene(&self, gene: &C::Gene, rng: &RngHandle) -> C::Gene {
         let min = *gene.min() as f64;
         let max = *gene.max() as f64;
 
         let std_dev = (max - min) * 0.25;
         let value = *gene.allele() as f64;
 
-        let gaussian = random_provider::gaussian(value, std_dev);
+        let gaussian = rng.gaussian(value, std_dev);
 
-        let allele = GaussianMutator::clamp(gaussian, min, max) as f32;
-        gene.with_allele(&allele)
+        gene.bounded(&(gaussian as f32))
     }
 }
@@ -0,0 +1,90 @@
+use crate::Description;
+use crate::{random_provider::RngHandle, EngineCompoment, MixedChromosome, Population};
+
+use super::{Alter, AlterAction, Crossover};
+
+/// The `HomologousCrossover` is a uniform crossover for populations of `MixedChromosome`s whose
+/// individuals differ in structure. Instead of crossing the chromosomes at the same index of both
+/// parents, it picks a random chromosome of the first parent and crosses it with a random chromosome of
+/// the second parent that has the same structure (see `MixedChromosome::is_homologous`) - so a layer is
+/// only ever crossed with a layer of the same kind, wherever it is in the other parent. Every pair of
+/// genes is swapped with probability `rate`. Parents without homologous chromosomes are left unchanged.
+pub struct HomologousCrossover {
+    rate: f32,
+}
+
+impl HomologousCrossover {
+    /// Create a new instance of the `HomologousCrossover` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        HomologousCrossover { rate }
+    }
+}
+
+impl EngineCompoment for HomologousCrossover {
+    fn name(&self) -> &'static str {
+        "HomologousCrossover"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
+}
+
+impl Alter<MixedChromosome> for HomologousCrossover {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<MixedChromosome> {
+        AlterAction::Crossover(Box::new(self))
+    }
+}
+
+impl Crossover<MixedChromosome> for HomologousCrossover {
+    #[inline]
+    fn cross(
+        &self,
+        population: &mut Population<MixedChromosome>,
+        parent_indexes: &[usize],
+        generation: i32,
+        rng: &RngHandle,
+    ) -> i32 {
+        let index_one = parent_indexes[0];
+        let index_two = parent_indexes[1];
+        if index_one == index_two {
+            return 0;
+        }
+
+        let (parent_one, parent_two) = population.pair_mut(index_one, index_two);
+        let (geno_one, geno_two) = (parent_one.genotype_mut(), parent_two.genotype_mut());
+        if geno_one.is_empty() {
+            return 0;
+        }
+
+        let one = rng.gen_range(0..geno_one.len());
+        let homologous = (0..geno_two.len())
+            .filter(|&two| geno_one[one].is_homologous(&geno_two[two]))
+            .collect::<Vec<usize>>();
+
+        if homologous.is_empty() {
+            return 0;
+        }
+
+        let two = *rng.choose(&homologous);
+        let cross_count = self.cross_chromosomes(&mut geno_one[one], &mut geno_two[two], rng);
+
+        if cross_count > 0 {
+            for parent in [parent_one, parent_two] {
+                parent.mark_dirty();
+                parent.generation = generation;
+            }
+        }
+
+        cross_count
+    }
+}
@@ -0,0 +1,190 @@
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment, Gene, SequenceChromosome};
+
+use super::{Alter, AlterAction, Mutate};
+
+/// The `InsertionMutator` inserts a new gene (a new instance of the chromosome's template gene) at a
+/// random position of a `SequenceChromosome`, growing it by one. Chromosomes that are already at
+/// their `max_len` are left untouched.
+pub struct InsertionMutator {
+    rate: f32,
+}
+
+impl InsertionMutator {
+    /// Create a new instance of the `InsertionMutator` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        InsertionMutator { rate }
+    }
+}
+
+impl EngineCompoment for InsertionMutator {
+    fn name(&self) -> &'static str {
+        "InsertionMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
+}
+
+impl<G: Gene + 'static> Alter<SequenceChromosome<G>> for InsertionMutator {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<SequenceChromosome<G>> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<G: Gene + 'static> Mutate<SequenceChromosome<G>> for InsertionMutator {
+    #[inline]
+    fn mutate_chromosome(&self, chromosome: &mut SequenceChromosome<G>, rng: &RngHandle) -> i32 {
+        if !chromosome.can_grow() || rng.random::<f32>() >= self.rate {
+            return 0;
+        }
+
+        let index = rng.gen_range(0..chromosome.len() + 1);
+        chromosome.insert(index);
+        1
+    }
+}
+
+/// The `DeletionMutator` removes a random gene from a `SequenceChromosome`, shrinking it by one.
+/// Chromosomes that are already at their `min_len` are left untouched.
+pub struct DeletionMutator {
+    rate: f32,
+}
+
+impl DeletionMutator {
+    /// Create a new instance of the `DeletionMutator` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        DeletionMutator { rate }
+    }
+}
+
+impl EngineCompoment for DeletionMutator {
+    fn name(&self) -> &'static str {
+        "DeletionMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
+}
+
+impl<G: Gene + 'static> Alter<SequenceChromosome<G>> for DeletionMutator {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<SequenceChromosome<G>> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<G: Gene + 'static> Mutate<SequenceChromosome<G>> for DeletionMutator {
+    #[inline]
+    fn mutate_chromosome(&self, chromosome: &mut SequenceChromosome<G>, rng: &RngHandle) -> i32 {
+        if !chromosome.can_shrink() || rng.random::<f32>() >= self.rate {
+            return 0;
+        }
+
+        let index = rng.gen_range(0..chromosome.len());
+        chromosome.remove(index);
+        1
+    }
+}
+
+/// The `DuplicationMutator` copies a random run of genes of a `SequenceChromosome` and inserts the copy
+/// right after the original - repeating a token, a statement or a motif instead of inserting a new gene.
+/// The run is at most as long as the chromosome can grow, so chromosomes that are already at their
+/// `max_len` are left untouched.
+pub struct DuplicationMutator {
+    rate: f32,
+}
+
+impl DuplicationMutator {
+    /// Create a new instance of the `DuplicationMutator` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        DuplicationMutator { rate }
+    }
+}
+
+impl EngineCompoment for DuplicationMutator {
+    fn name(&self) -> &'static str {
+        "DuplicationMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
+}
+
+impl<G: Gene + 'static> Alter<SequenceChromosome<G>> for DuplicationMutator {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<SequenceChromosome<G>> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<G: Gene + 'static> Mutate<SequenceChromosome<G>> for DuplicationMutator {
+    #[inline]
+    fn mutate_chromosome(&self, chromosome: &mut SequenceChromosome<G>, rng: &RngHandle) -> i32 {
+        if chromosome.is_empty() || !chromosome.can_grow() || rng.random::<f32>() >= self.rate {
+            return 0;
+        }
+
+        let room = chromosome.max_len - chromosome.len();
+        let start = rng.gen_range(0..chromosome.len());
+        let len = rng.gen_range(1..(chromosome.len() - start).min(room) + 1);
+
+        let copy = chromosome.genes[start..start + len].to_vec();
+        let end = start + len;
+        chromosome.genes.splice(end..end, copy);
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CharGene;
+
+    #[test]
+    fn test_duplication_repeats_a_run_within_bounds() {
+        let rng = RngHandle::from_entropy();
+        let genes = "abc".chars().map(CharGene::from).collect();
+        let mut chromosome =
+            SequenceChromosome::from_genes(CharGene::new(), genes).with_len_bounds(1, 5);
+
+        for _ in 0..10 {
+            DuplicationMutator::new(1.0).mutate_chromosome(&mut chromosome, &rng);
+        }
+
+        let value = chromosome
+            .iter()
+            .map(|gene| *gene.allele())
+            .collect::<String>();
+        assert_eq!(value.len(), 5);
+        assert!(value.starts_with('a'));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{random_provider, Chromosome, EngineCompoment, FloatGene, Gene};
+use crate::Description;
+use crate::{
+    random_provider::RngHandle, Chromosome, EngineCompoment, FloatGene, Gene, NumericGene,
+};
 
 use super::{Alter, AlterAction, Crossover};
 
@@ -20,11 +23,11 @@ impl IntermediateCrossover {
     /// Create a new instance of the `IntermediateCrossover` with the given rate and alpha.
     /// The rate must be between 0.0 and 1.0, and the alpha must be between 0.0 and 1.0.
     pub fn new(rate: f32, alpha: f32) -> Self {
-        if rate < 0.0 || rate > 1.0 {
+        if !(0.0..=1.0).contains(&rate) {
             panic!("Rate must be between 0 and 1");
         }
 
-        if alpha < 0.0 || alpha > 1.0 {
+        if !(0.0..=1.0).contains(&alpha) {
             panic!("Alpha must be between 0 and 1");
         }
 
@@ -36,6 +39,12 @@ impl EngineCompoment for IntermediateCrossover {
     fn name(&self) -> &'static str {
         "IntermediateCrossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("rate", self.rate)
+            .param("alpha", self.alpha)
+    }
 }
 
 impl<C: Chromosome<Gene = FloatGene>> Alter<C> for IntermediateCrossover {
@@ -50,21 +59,21 @@ impl<C: Chromosome<Gene = FloatGene>> Alter<C> for IntermediateCrossover {
 
 impl<C: Chromosome<Gene = FloatGene>> Crossover<C> for IntermediateCrossover {
     #[inline]
-    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C) -> i32 {
+    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C, rng: &RngHandle) -> i32 {
         let mut cross_count = 0;
 
         for i in 0..std::cmp::min(chrom_one.len(), chrom_two.len()) {
-            if random_provider::random::<f32>() < self.rate {
+            if rng.random::<f32>() < self.rate {
                 let gene_one = chrom_one.get_gene(i);
                 let gene_two = chrom_two.get_gene(i);
 
                 let allele1 = gene_one.allele();
                 let allele2 = gene_two.allele();
 
-                let alpha = random_provider::gen_range(0.0..self.alpha);
+                let alpha = rng.gen_range(0.0..self.alpha);
                 let allele = allele1 * alpha + allele2 * (1.0 - alpha);
 
-                chrom_one.set_gene(i, gene_one.with_allele(&allele));
+                chrom_one.set_gene(i, gene_one.bounded(&allele));
                 cross_count += 1;
             }
         }
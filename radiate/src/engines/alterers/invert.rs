@@ -1,4 +1,5 @@
-use crate::{random_provider, Chromosome, EngineCompoment};
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment};
 
 use super::{Alter, AlterAction, Mutate};
 
@@ -22,6 +23,10 @@ impl EngineCompoment for InversionMutator {
     fn name(&self) -> &'static str {
         "InversionMutator"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Alter<C> for InversionMutator {
@@ -36,12 +41,12 @@ impl<C: Chromosome> Alter<C> for InversionMutator {
 
 impl<C: Chromosome> Mutate<C> for InversionMutator {
     #[inline]
-    fn mutate_chromosome(&self, chromosome: &mut C) -> i32 {
+    fn mutate_chromosome(&self, chromosome: &mut C, rng: &RngHandle) -> i32 {
         let mut mutations = 0;
 
-        if random_provider::random::<f32>() < self.rate {
-            let start = random_provider::gen_range(0..chromosome.len());
-            let end = random_provider::gen_range(start..chromosome.len());
+        if rng.random::<f32>() < self.rate {
+            let start = rng.gen_range(0..chromosome.len());
+            let end = rng.gen_range(start..chromosome.len());
 
             chromosome.as_mut()[start..end].reverse();
             mutations += 1;
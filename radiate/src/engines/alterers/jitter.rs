@@ -0,0 +1,56 @@
+use crate::random_provider::RngHandle;
+use crate::Description;
+use crate::{Chromosome, EngineCompoment, TimeGene};
+
+use super::{Alter, AlterAction, Mutate};
+
+/// The `JitterMutator` nudges a `TimeGene` by a random number of minutes (at most `max_jitter` in either
+/// direction) instead of re-sampling it. The result is snapped to the gene's granularity and clamped
+/// to its bounds, so small schedule adjustments never produce an invalid time slot.
+///
+/// This mutator is for use with the `TimeChromosome` or any `Chromosome` which holds `TimeGene`s.
+pub struct JitterMutator {
+    rate: f32,
+    max_jitter: i32,
+}
+
+impl JitterMutator {
+    /// Create a new instance of the `JitterMutator` with the given rate and maximum jitter in minutes.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32, max_jitter: i32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        JitterMutator { rate, max_jitter }
+    }
+}
+
+impl EngineCompoment for JitterMutator {
+    fn name(&self) -> &'static str {
+        "JitterMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("rate", self.rate)
+            .param("max_jitter", self.max_jitter)
+    }
+}
+
+impl<C: Chromosome<Gene = TimeGene>> Alter<C> for JitterMutator {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<C: Chromosome<Gene = TimeGene>> Mutate<C> for JitterMutator {
+    #[inline]
+    fn mutate_gene(&self, gene: &C::Gene, rng: &RngHandle) -> C::Gene {
+        gene.jitter_with(self.max_jitter, rng)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{random_provider, Chromosome, EngineCompoment, NumericGene};
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment, Gene, NumericGene};
 
 use super::{Alter, AlterAction, Crossover};
 
@@ -18,7 +19,7 @@ impl MeanCrossover {
     /// Create a new instance of the `MeanCrossover` with the given rate.
     /// The rate must be between 0.0 and 1.0.
     pub fn new(rate: f32) -> Self {
-        if rate < 0.0 || rate > 1.0 {
+        if !(0.0..=1.0).contains(&rate) {
             panic!("The rate must be between 0.0 and 1.0");
         }
 
@@ -30,6 +31,10 @@ impl EngineCompoment for MeanCrossover {
     fn name(&self) -> &'static str {
         "Mean Crossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Alter<C> for MeanCrossover
@@ -50,12 +55,13 @@ where
     C::Gene: NumericGene,
 {
     #[inline]
-    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C) -> i32 {
+    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C, rng: &RngHandle) -> i32 {
         let mut count = 0;
 
         for (gene_one, gene_two) in chrom_one.iter_mut().zip(chrom_two.iter()) {
-            if random_provider::random::<f32>() < self.rate {
-                *gene_one = gene_one.mean(gene_two);
+            if rng.random::<f32>() < self.rate {
+                let mean = gene_one.mean(gene_two);
+                *gene_one = gene_one.bounded(mean.allele());
                 count += 1;
             }
         }
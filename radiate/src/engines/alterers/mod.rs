@@ -1,31 +1,55 @@
+pub mod adaptive;
+pub mod alignment;
 pub mod alter;
 pub mod arithmetic;
+pub mod bitflip;
+pub mod compose;
+pub mod controlled;
 pub mod crossover;
 pub mod gaussian;
+pub mod homologous;
+pub mod indel;
 pub mod intermediate;
 pub mod invert;
+pub mod jitter;
 pub mod mean;
 pub mod multipoint;
 pub mod mutate;
 pub mod pmx;
+pub mod preview;
+pub mod reproduction;
 pub mod scramble;
+pub mod self_adaptive;
 pub mod shuffle;
 pub mod simulated_binary;
+pub mod structure;
 pub mod swap;
 pub mod uniform;
 
+pub use adaptive::*;
+pub use alignment::*;
 pub use alter::*;
 pub use arithmetic::*;
+pub use bitflip::*;
+pub use compose::*;
+pub use controlled::*;
 pub use crossover::*;
 pub use gaussian::*;
+pub use homologous::*;
+pub use indel::*;
 pub use intermediate::*;
 pub use invert::*;
+pub use jitter::*;
 pub use mean::*;
 pub use multipoint::*;
 pub use mutate::*;
 pub use pmx::*;
+pub use preview::*;
+pub use reproduction::*;
 pub use scramble::*;
+pub use self_adaptive::*;
 pub use shuffle::*;
 pub use simulated_binary::*;
+pub use structure::*;
 pub use swap::*;
 pub use uniform::*;
@@ -1,6 +1,7 @@
 use super::{Alter, AlterAction, Crossover};
+use crate::Description;
 
-use crate::{random_provider, Chromosome, EngineCompoment};
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment};
 
 /// The `MultiPointCrossover` is a crossover method that takes two chromosomes and crosses them
 /// by selecting multiple points in the chromosome and swapping the genes between the two chromosomes.
@@ -20,7 +21,7 @@ impl MultiPointCrossover {
     /// The rate must be between 0.0 and 1.0, and the number of points must be between 1 and the length
     /// of the chromosome.
     pub fn new(rate: f32, num_points: usize) -> Self {
-        if rate < 0.0 || rate > 1.0 {
+        if !(0.0..=1.0).contains(&rate) {
             panic!("Rate must be between 0 and 1");
         }
 
@@ -32,6 +33,12 @@ impl EngineCompoment for MultiPointCrossover {
     fn name(&self) -> &'static str {
         "MultiPointCrossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("rate", self.rate)
+            .param("num_points", self.num_points)
+    }
 }
 
 impl<C: Chromosome> Alter<C> for MultiPointCrossover {
@@ -45,7 +52,7 @@ impl<C: Chromosome> Alter<C> for MultiPointCrossover {
 }
 
 impl<C: Chromosome> Crossover<C> for MultiPointCrossover {
-    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C) -> i32 {
+    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C, rng: &RngHandle) -> i32 {
         let length = std::cmp::min(chrom_one.len(), chrom_two.len());
 
         if length < 2 {
@@ -53,7 +60,7 @@ impl<C: Chromosome> Crossover<C> for MultiPointCrossover {
         }
 
         let mut crossover_points: Vec<usize> = (1..length).collect();
-        random_provider::shuffle(&mut crossover_points);
+        rng.shuffle(&mut crossover_points);
 
         let selected_points = &crossover_points[..self.num_points];
 
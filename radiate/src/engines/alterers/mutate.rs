@@ -1,21 +1,29 @@
-use crate::{random_provider, timer::Timer, Chromosome, Gene, Genotype, Metric, Population};
+use crate::random_provider::RngHandle;
+use crate::{timer::Timer, Chromosome, Gene, Genotype, Metric, Population};
 
 use super::Alter;
 
+/// Mutates the offspring in place - see `Crossover` for the ownership model alterers follow, and for
+/// the generator every method is handed.
 pub trait Mutate<C: Chromosome>: Alter<C> {
     #[inline]
-    fn mutate(&self, population: &mut Population<C>, generation: i32) -> Vec<Metric> {
+    fn mutate(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
         let timer = Timer::new();
         let mut count = 0;
 
         for phenotype in population.iter_mut() {
             let genotype = phenotype.genotype_mut();
 
-            let mutation_count = self.mutate_genotype(genotype);
+            let mutation_count = self.mutate_genotype(genotype, rng);
 
             if mutation_count > 0 {
+                phenotype.mark_dirty();
                 phenotype.generation = generation;
-                phenotype.score = None;
                 count += mutation_count;
             }
         }
@@ -28,21 +36,21 @@ pub trait Mutate<C: Chromosome>: Alter<C> {
     }
 
     #[inline]
-    fn mutate_genotype(&self, genotype: &mut Genotype<C>) -> i32 {
+    fn mutate_genotype(&self, genotype: &mut Genotype<C>, rng: &RngHandle) -> i32 {
         let mut count = 0;
         for chromosome in genotype.iter_mut() {
-            count += self.mutate_chromosome(chromosome);
+            count += self.mutate_chromosome(chromosome, rng);
         }
 
         count
     }
 
     #[inline]
-    fn mutate_chromosome(&self, chromosome: &mut C) -> i32 {
+    fn mutate_chromosome(&self, chromosome: &mut C, rng: &RngHandle) -> i32 {
         let mut count = 0;
         for gene in chromosome.iter_mut() {
-            if random_provider::random::<f32>() < self.rate() {
-                *gene = self.mutate_gene(gene);
+            if rng.random::<f32>() < self.rate() {
+                *gene = self.mutate_gene(gene, rng);
                 count += 1;
             }
         }
@@ -51,7 +59,7 @@ pub trait Mutate<C: Chromosome>: Alter<C> {
     }
 
     #[inline]
-    fn mutate_gene(&self, gene: &C::Gene) -> C::Gene {
+    fn mutate_gene(&self, gene: &C::Gene, _rng: &RngHandle) -> C::Gene {
         gene.new_instance()
     }
 }
@@ -1,5 +1,7 @@
 use super::{Alter, AlterAction, Crossover};
 use crate::indexes;
+use crate::random_provider::RngHandle;
+use crate::Description;
 use crate::{Chromosome, EngineCompoment, PermutationChromosome};
 
 pub struct PMXCrossover {
@@ -16,6 +18,10 @@ impl EngineCompoment for PMXCrossover {
     fn name(&self) -> &'static str {
         "PMX Crossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<A: PartialEq + Clone> Alter<PermutationChromosome<A>> for PMXCrossover {
@@ -34,13 +40,14 @@ impl<A: PartialEq + Clone> Crossover<PermutationChromosome<A>> for PMXCrossover
         &self,
         chrom_one: &mut PermutationChromosome<A>,
         chrom_two: &mut PermutationChromosome<A>,
+        rng: &RngHandle,
     ) -> i32 {
         let length = std::cmp::min(chrom_one.genes.len(), chrom_two.genes.len());
         if length < 2 {
             return 0;
         }
 
-        let subset = indexes::subset(chrom_one.genes.len(), 2);
+        let subset = rng.with(|rng| indexes::subset(chrom_one.genes.len(), 2, rng));
         let start = subset[0] as usize;
         let end = subset[1] as usize;
 
@@ -0,0 +1,193 @@
+use crate::random_provider::RngHandle;
+use crate::{Chromosome, Diversity, Genotype, HammingDistance, Phenotype, Population};
+
+use super::AlterAction;
+
+/// A list of alterers applied one after the other, like the engine applies the alterers it's
+/// configured with. Outside of the engine a pipeline can be `preview`ed to see what the alterers
+/// actually do to a genotype - how many individuals and genes they change - before launching a
+/// long run with them.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let pipeline = AlterPipeline::new(alters!(
+///     UniformCrossover::new(0.5),
+///     GaussianMutator::new(0.1),
+/// ));
+///
+/// let genotype = FloatCodex::new(1, 10, 0.0, 1.0).encode();
+/// let preview = pipeline.preview(&genotype, 100);
+///
+/// // every gene mutates with probability 0.1 - about one gene per individual
+/// assert!(preview.mean_changed_genes() > 0.5 && preview.mean_changed_genes() < 1.5);
+/// assert_eq!(preview.stages[0].changed_individuals, 0); // crossing identical copies changes nothing
+/// ```
+pub struct AlterPipeline<C: Chromosome> {
+    alterers: Vec<AlterAction<C>>,
+}
+
+impl<C: Chromosome> AlterPipeline<C> {
+    pub fn new(alterers: Vec<AlterAction<C>>) -> Self {
+        AlterPipeline { alterers }
+    }
+
+    pub fn alterers(&self) -> &[AlterAction<C>] {
+        &self.alterers
+    }
+
+    pub fn into_alterers(self) -> Vec<AlterAction<C>> {
+        self.alterers
+    }
+
+    /// Apply the pipeline once to a population of `samples` copies of the `genotype` and measure how
+    /// they changed. Since the copies are identical, crossovers don't change anything here - use
+    /// `preview_population` with different genotypes to see their effect.
+    pub fn preview(&self, genotype: &Genotype<C>, samples: usize) -> AlterPreview {
+        self.preview_population(&vec![genotype.clone(); samples])
+    }
+
+    /// Apply the pipeline once to a population of the `genotypes` and measure how each of them changed.
+    /// The alterers draw from the calling thread's generator (see `RngHandle::current`).
+    pub fn preview_population(&self, genotypes: &[Genotype<C>]) -> AlterPreview {
+        let mut population = genotypes
+            .iter()
+            .map(|genotype| Phenotype::from_genotype(genotype.clone(), 0))
+            .collect::<Population<C>>();
+
+        let rng = RngHandle::current();
+        let mut stages = Vec::with_capacity(self.alterers.len());
+        for (index, alterer) in self.alterers.iter().enumerate() {
+            let before = population
+                .iter()
+                .map(|individual| individual.genotype().clone())
+                .collect::<Vec<Genotype<C>>>();
+
+            let metrics = alterer.alter(&mut population, 1, &rng);
+            let changes = changed_genes(&before, &population);
+
+            stages.push(StagePreview {
+                name: metrics
+                    .first()
+                    .map(|metric| metric.name())
+                    .unwrap_or("Alterer"),
+                index,
+                operations: metrics.iter().map(|metric| metric.last_value()).sum(),
+                changed_individuals: changes.iter().filter(|count| **count > 0).count(),
+                changed_genes: changes.iter().sum(),
+            });
+        }
+
+        let mut position_rates = Vec::new();
+        for (original, altered) in genotypes.iter().zip(population.iter()) {
+            let positions =
+                original
+                    .iter()
+                    .zip(altered.genotype().iter())
+                    .flat_map(|(one, two)| {
+                        let shared = one.iter().zip(two.iter()).map(|(a, b)| a != b);
+                        shared.chain((two.len()..one.len()).map(|_| true))
+                    });
+
+            for (position, changed) in positions.enumerate() {
+                if position_rates.len() <= position {
+                    position_rates.resize(position + 1, 0.0);
+                }
+
+                if changed {
+                    position_rates[position] += 1.0 / genotypes.len() as f32;
+                }
+            }
+        }
+
+        AlterPreview {
+            samples: genotypes.len(),
+            changed_genes: changed_genes(genotypes, &population),
+            position_rates,
+            stages,
+        }
+    }
+}
+
+impl<C: Chromosome> From<Vec<AlterAction<C>>> for AlterPipeline<C> {
+    fn from(alterers: Vec<AlterAction<C>>) -> Self {
+        AlterPipeline::new(alterers)
+    }
+}
+
+/// The number of genes of each genotype that differ from the (same index) individual of the population.
+fn changed_genes<C: Chromosome>(
+    genotypes: &[Genotype<C>],
+    population: &Population<C>,
+) -> Vec<usize> {
+    genotypes
+        .iter()
+        .zip(population.iter())
+        .map(|(genotype, individual)| {
+            HammingDistance.distance(genotype, individual.genotype()) as usize
+        })
+        .collect()
+}
+
+/// What an `AlterPipeline` did to a sample of genotypes (see `AlterPipeline::preview`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlterPreview {
+    /// The number of genotypes the pipeline was applied to.
+    pub samples: usize,
+    /// For every sample, the number of its genes the whole pipeline changed.
+    pub changed_genes: Vec<usize>,
+    /// For every gene position (the genes of all chromosomes, one after the other), the fraction of
+    /// samples whose gene at that position changed.
+    pub position_rates: Vec<f32>,
+    /// The effect of each alterer on its own, in the order they were applied.
+    pub stages: Vec<StagePreview>,
+}
+
+impl AlterPreview {
+    /// The fraction of samples with at least one changed gene.
+    pub fn changed_fraction(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+
+        self.changed_genes
+            .iter()
+            .filter(|count| **count > 0)
+            .count() as f32
+            / self.samples as f32
+    }
+
+    pub fn mean_changed_genes(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+
+        self.changed_genes.iter().sum::<usize>() as f32 / self.samples as f32
+    }
+
+    /// The number of samples with each number of changed genes - the value at index `k` is the
+    /// number of samples with exactly `k` changed genes.
+    pub fn histogram(&self) -> Vec<usize> {
+        let max = self.changed_genes.iter().max().cloned().unwrap_or(0);
+        let mut histogram = vec![0; max + 1];
+        for count in self.changed_genes.iter() {
+            histogram[*count] += 1;
+        }
+
+        histogram
+    }
+}
+
+/// What a single alterer of an `AlterPipeline` did, measured against the population it received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StagePreview {
+    /// The name of the alterer, taken from the metrics it reports.
+    pub name: &'static str,
+    /// The position of the alterer in the pipeline.
+    pub index: usize,
+    /// The number of operations the alterer reported.
+    pub operations: f32,
+    pub changed_individuals: usize,
+    pub changed_genes: usize,
+}
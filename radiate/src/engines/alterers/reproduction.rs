@@ -0,0 +1,470 @@
+use crate::timer::Timer;
+use crate::Description;
+use crate::{
+    random_provider::RngHandle, Chromosome, Diversity, EngineCompoment, Metric, Phenotype,
+    Population,
+};
+
+use super::{Alter, AlterAction, Compose};
+
+type MatingTypeFn<C> = Box<dyn Fn(&Phenotype<C>) -> usize>;
+type CompatibleFn = Box<dyn Fn(usize, usize) -> bool>;
+
+/// How `Reproduction` picks the mate of a parent. The offspring handed to the alterers are sorted by
+/// the objective, so the position of an individual in the population is its rank.
+pub enum Pairing<C: Chromosome> {
+    /// Any other individual, uniformly at random.
+    Random,
+    /// The fittest of this many random individuals - selection pressure between mates.
+    Tournament(usize),
+    /// Positive assortative mating: the individual most similar to the parent out of `candidates` random
+    /// ones. Speeds up convergence by mating like with like.
+    Assortative {
+        diversity: Box<dyn Diversity<C>>,
+        candidates: usize,
+    },
+    /// Negative assortative mating: the individual most different from the parent out of `candidates`
+    /// random ones. Helps to maintain the diversity of the population.
+    Disassortative {
+        diversity: Box<dyn Diversity<C>>,
+        candidates: usize,
+    },
+    /// Inbreeding avoidance (incest prevention): a random individual at least `min_distance` away from the
+    /// parent. A parent without such a mate doesn't reproduce this generation.
+    AvoidInbreeding {
+        diversity: Box<dyn Diversity<C>>,
+        min_distance: f32,
+    },
+    /// Mating types (or sexes): a random individual whose mating type is compatible with the parent's.
+    /// The mating type can be derived from a gene - e.g. a dedicated 'sex chromosome' that evolves
+    /// with the rest of the genotype - or from anything else about the individual. A parent without a
+    /// compatible mate doesn't reproduce this generation.
+    MatingTypes {
+        mating_type: MatingTypeFn<C>,
+        compatible: CompatibleFn,
+    },
+}
+
+impl<C: Chromosome> Pairing<C> {
+    /// A short label of the pairing and its parameters, as shown in the `Reproduction`'s description.
+    pub fn label(&self) -> String {
+        match self {
+            Pairing::Random => "random".to_string(),
+            Pairing::Tournament(size) => format!("tournament({})", size),
+            Pairing::Assortative { candidates, .. } => format!("assortative({})", candidates),
+            Pairing::Disassortative { candidates, .. } => format!("disassortative({})", candidates),
+            Pairing::AvoidInbreeding { min_distance, .. } => {
+                format!("avoid_inbreeding({})", min_distance)
+            }
+            Pairing::MatingTypes { .. } => "mating_types".to_string(),
+        }
+    }
+
+    /// Mating types where only individuals of different types are compatible.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// // The last chromosome is a 'sex chromosome' of a single gene - its parity is the mating type.
+    /// let pairing = Pairing::mating_types(|phenotype: &Phenotype<IntChromosome<i32>>| {
+    ///     let genotype = phenotype.genotype();
+    ///     *genotype[genotype.len() - 1].get_gene(0).allele() as usize % 2
+    /// });
+    ///
+    /// let alterer = Reproduction::new(UniformCrossover::new(0.5).to_alter()).pairing(pairing);
+    /// ```
+    pub fn mating_types<F>(mating_type: F) -> Self
+    where
+        F: Fn(&Phenotype<C>) -> usize + 'static,
+    {
+        Pairing::mating_types_with(mating_type, |one, two| one != two)
+    }
+
+    /// Mating types with a custom compatibility rule between the parent's type and the mate's.
+    pub fn mating_types_with<F, P>(mating_type: F, compatible: P) -> Self
+    where
+        F: Fn(&Phenotype<C>) -> usize + 'static,
+        P: Fn(usize, usize) -> bool + 'static,
+    {
+        Pairing::MatingTypes {
+            mating_type: Box::new(mating_type),
+            compatible: Box::new(compatible),
+        }
+    }
+
+    /// Positive assortative mating by `diversity`, choosing among the whole population (see `candidates`).
+    pub fn assortative<D: Diversity<C> + 'static>(diversity: D) -> Self {
+        Pairing::Assortative {
+            diversity: Box::new(diversity),
+            candidates: usize::MAX,
+        }
+    }
+
+    /// Negative assortative mating by `diversity`, choosing among the whole population (see `candidates`).
+    pub fn disassortative<D: Diversity<C> + 'static>(diversity: D) -> Self {
+        Pairing::Disassortative {
+            diversity: Box::new(diversity),
+            candidates: usize::MAX,
+        }
+    }
+
+    pub fn avoid_inbreeding<D: Diversity<C> + 'static>(diversity: D, min_distance: f32) -> Self {
+        Pairing::AvoidInbreeding {
+            diversity: Box::new(diversity),
+            min_distance,
+        }
+    }
+
+    /// Set the size of the random pool an assortative mate is chosen from. Smaller pools weaken the
+    /// preference and are cheaper, as the parent is only compared to the pool. Panics if `candidates`
+    /// is 0 or the pairing isn't assortative.
+    pub fn candidates(self, candidates: usize) -> Self {
+        if candidates < 1 {
+            panic!("candidates must be greater than 0");
+        }
+
+        match self {
+            Pairing::Assortative { diversity, .. } => Pairing::Assortative {
+                diversity,
+                candidates,
+            },
+            Pairing::Disassortative { diversity, .. } => Pairing::Disassortative {
+                diversity,
+                candidates,
+            },
+            _ => panic!("candidates only applies to assortative pairings"),
+        }
+    }
+
+    /// Pick the mate of the individual at `parent`, if it has one.
+    pub fn mate(
+        &self,
+        population: &Population<C>,
+        parent: usize,
+        rng: &RngHandle,
+    ) -> Option<usize> {
+        let mut others = (0..population.len())
+            .filter(|&index| index != parent)
+            .collect::<Vec<usize>>();
+        if others.is_empty() {
+            return None;
+        }
+
+        let genotype = population[parent].genotype();
+        let distance_to = |diversity: &dyn Diversity<C>, index: usize| {
+            diversity.distance(genotype, population[index].genotype())
+        };
+        let pick = |allowed: &[usize]| {
+            if allowed.is_empty() {
+                None
+            } else {
+                Some(allowed[rng.gen_range(0..allowed.len())])
+            }
+        };
+        let mut pool = |candidates: usize| {
+            if candidates < others.len() {
+                rng.shuffle(&mut others);
+                others.truncate(candidates);
+            }
+
+            others.clone()
+        };
+
+        match self {
+            Pairing::Random => pick(&others),
+            Pairing::Tournament(size) => (0..(*size).max(1))
+                .map(|_| others[rng.gen_range(0..others.len())])
+                .min(),
+            Pairing::Assortative {
+                diversity,
+                candidates,
+            } => pool(*candidates).into_iter().min_by(|&a, &b| {
+                distance_to(diversity.as_ref(), a).total_cmp(&distance_to(diversity.as_ref(), b))
+            }),
+            Pairing::Disassortative {
+                diversity,
+                candidates,
+            } => pool(*candidates).into_iter().max_by(|&a, &b| {
+                distance_to(diversity.as_ref(), a).total_cmp(&distance_to(diversity.as_ref(), b))
+            }),
+            Pairing::AvoidInbreeding {
+                diversity,
+                min_distance,
+            } => {
+                let allowed = others
+                    .iter()
+                    .copied()
+                    .filter(|&index| distance_to(diversity.as_ref(), index) >= *min_distance)
+                    .collect::<Vec<usize>>();
+
+                pick(&allowed)
+            }
+            Pairing::MatingTypes {
+                mating_type,
+                compatible,
+            } => {
+                let own = mating_type(&population[parent]);
+                let allowed = others
+                    .iter()
+                    .copied()
+                    .filter(|&index| compatible(own, mating_type(&population[index])))
+                    .collect::<Vec<usize>>();
+
+                pick(&allowed)
+            }
+        }
+    }
+}
+
+/// An explicit reproduction step: who mates with whom and how many children every pair has. Every
+/// individual of the population in turn (in random order) picks a mate with the `Pairing`, and the
+/// pair produces `children` children with the crossover, until the population is replaced by as many
+/// children as it had parents. An individual that finds no mate is carried over as it was.
+///
+/// A crossover on its own mates every individual with a random other one and produces two children
+/// per pair - `Reproduction` makes these decisions configurable.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let alterer = Reproduction::new(MultiPointCrossover::new(1.0, 2).to_alter())
+///     .pairing(Pairing::avoid_inbreeding(HammingDistance, 2.0))
+///     .children(1);
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 10))
+///     .population_size(20)
+///     .alter(vec![alterer.to_alter()])
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// assert_eq!(result.population.len(), 20);
+/// ```
+pub struct Reproduction<C: Chromosome> {
+    crossover: AlterAction<C>,
+    pairing: Pairing<C>,
+    children: usize,
+}
+
+impl<C: Chromosome> Reproduction<C> {
+    /// Create a new `Reproduction` with random pairing and 2 children per pair.
+    pub fn new(crossover: AlterAction<C>) -> Self {
+        Reproduction {
+            crossover,
+            pairing: Pairing::Random,
+            children: 2,
+        }
+    }
+
+    pub fn pairing(mut self, pairing: Pairing<C>) -> Self {
+        self.pairing = pairing;
+        self
+    }
+
+    /// Set the number of children every pair produces. Panics if `children` is 0.
+    pub fn children(mut self, children: usize) -> Self {
+        if children < 1 {
+            panic!("children must be greater than 0");
+        }
+
+        self.children = children;
+        self
+    }
+
+    /// Cross copies of the two parents until the pair has `count` children.
+    fn breed(
+        &self,
+        one: &Phenotype<C>,
+        two: &Phenotype<C>,
+        count: usize,
+        generation: i32,
+        metrics: &mut Vec<Metric>,
+        rng: &RngHandle,
+    ) -> Vec<Phenotype<C>> {
+        let mut children = Vec::with_capacity(count + 1);
+        while children.len() < count {
+            let mut pair = Population::new(vec![one.clone(), two.clone()]);
+            metrics.extend(self.crossover.alter(&mut pair, generation, rng));
+            children.extend(pair);
+        }
+
+        children.truncate(count);
+        children
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for Reproduction<C> {
+    fn name(&self) -> &'static str {
+        "Reproduction"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("pairing", self.pairing.label())
+            .param("children", self.children)
+            .child(self.crossover.describe())
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for Reproduction<C> {
+    fn rate(&self) -> f32 {
+        1.0
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Compose(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Compose<C> for Reproduction<C> {
+    fn compose(
+        &self,
+        population: &mut Population<C>,
+        generation: i32,
+        rng: &RngHandle,
+    ) -> Vec<Metric> {
+        let timer = Timer::new();
+        let mut metrics = Vec::new();
+        let mut pairs = 0;
+
+        let size = population.len();
+        let mut children = Vec::with_capacity(size);
+        let order = rng.indexes(size);
+
+        for parent in order {
+            if children.len() >= size {
+                break;
+            }
+
+            match self.pairing.mate(population, parent, rng) {
+                Some(mate) => {
+                    let count = self.children.min(size - children.len());
+                    let brood = self.breed(
+                        &population[parent],
+                        &population[mate],
+                        count,
+                        generation,
+                        &mut metrics,
+                        rng,
+                    );
+
+                    children.extend(brood);
+                    pairs += 1;
+                }
+                None => children.push(population[parent].clone()),
+            }
+        }
+
+        *population = Population::new(children);
+
+        let mut metric = Metric::new_operations(self.name());
+        metric.add_value(pairs as f32);
+        metric.add_duration(timer.duration());
+        metrics.push(metric);
+
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EuclideanDistance, FloatChromosome, Gene, Genotype, HammingDistance, UniformCrossover,
+    };
+
+    fn population(values: &[f32]) -> Population<FloatChromosome> {
+        values
+            .iter()
+            .map(|&value| {
+                let genotype = Genotype::new(vec![FloatChromosome::from(&[value; 4][..])]);
+                Phenotype::from_genotype(genotype, 0)
+            })
+            .collect()
+    }
+
+    fn distance(one: &Genotype<FloatChromosome>, two: &Genotype<FloatChromosome>) -> f32 {
+        (one[0].get_gene(0).allele() - two[0].get_gene(0).allele()).abs()
+    }
+
+    #[test]
+    fn test_pairing_picks_mates_by_distance() {
+        let rng = RngHandle::from_entropy();
+        let population = population(&[0.0, 1.0, 5.0, 10.0]);
+
+        assert_eq!(
+            Pairing::assortative(distance).mate(&population, 0, &rng),
+            Some(1)
+        );
+        assert_eq!(
+            Pairing::disassortative(distance).mate(&population, 0, &rng),
+            Some(3)
+        );
+
+        let avoid = Pairing::avoid_inbreeding(distance, 4.0);
+        for _ in 0..10 {
+            assert!(matches!(
+                avoid.mate(&population, 0, &rng),
+                Some(2) | Some(3)
+            ));
+        }
+        assert_eq!(
+            Pairing::avoid_inbreeding(distance, 20.0).mate(&population, 0, &rng),
+            None
+        );
+        assert_eq!(Pairing::Tournament(50).mate(&population, 0, &rng), Some(1));
+    }
+
+    #[test]
+    fn test_assortative_pairing_chooses_among_candidates() {
+        let rng = RngHandle::from_entropy();
+        let population = population(&[0.0, 1.0, 5.0, 10.0]);
+
+        let everyone = Pairing::disassortative(EuclideanDistance);
+        assert_eq!(everyone.mate(&population, 0, &rng), Some(3));
+        assert_eq!(
+            HammingDistance.distance(population[0].genotype(), population[1].genotype()),
+            4.0
+        );
+
+        let pooled = Pairing::disassortative(EuclideanDistance).candidates(1);
+        let mates = (0..50)
+            .filter_map(|_| pooled.mate(&population, 0, &rng))
+            .collect::<Vec<usize>>();
+        assert!(mates.contains(&1) && mates.contains(&2));
+    }
+
+    #[test]
+    fn test_mating_types_restrict_mates_to_compatible_types() {
+        let rng = RngHandle::from_entropy();
+        let population = population(&[0.0, 1.0, 2.0, 3.0]);
+        let sex = |phenotype: &Phenotype<FloatChromosome>| {
+            *phenotype.genotype()[0].get_gene(0).allele() as usize % 2
+        };
+
+        let pairing = Pairing::mating_types(sex);
+        for _ in 0..20 {
+            assert!(matches!(
+                pairing.mate(&population, 0, &rng),
+                Some(1) | Some(3)
+            ));
+        }
+
+        let same = Pairing::mating_types_with(sex, |one, two| one == two);
+        assert_eq!(same.mate(&population, 1, &rng), Some(3));
+    }
+
+    #[test]
+    fn test_reproduction_keeps_the_population_size() {
+        let rng = RngHandle::from_entropy();
+        let mut population = population(&[0.0, 1.0, 2.0, 3.0, 4.0]);
+
+        let alterer = Reproduction::new(UniformCrossover::new(0.5).to_alter()).children(3);
+        let metrics = alterer.compose(&mut population, 1, &rng);
+
+        assert_eq!(population.len(), 5);
+        assert!(metrics.iter().any(|metric| metric.name() == "Reproduction"));
+    }
+}
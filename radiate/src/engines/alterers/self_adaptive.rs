@@ -0,0 +1,149 @@
+use crate::{
+    random_provider::RngHandle, Chromosome, Description, EngineCompoment, NumericGene, StrategyGene,
+};
+
+use super::{Alter, AlterAction, Mutate};
+
+/// The `SelfAdaptiveMutator` is the mutation of a self-adaptive evolution strategy (uncorrelated
+/// mutation with one step size per gene). Every mutated `StrategyGene` first mutates its own step size
+/// log-normally, then adds Gaussian noise with the new step size to its allele:
+///
+/// ```text
+/// sigma' = sigma * exp(tau_global * N(0, 1) + tau * N_i(0, 1))
+/// allele' = allele + sigma' * N_i(0, 1)
+/// ```
+///
+/// The global draw is shared by the genes of a chromosome and the other two are drawn per gene. The
+/// learning rates are the usual `tau_global = 1 / sqrt(2n)` and `tau = 1 / sqrt(2 sqrt(n))` for a
+/// chromosome of `n` genes. Offspring with good step sizes tend to be the good offspring, so selection
+/// tunes the step sizes along with the alleles - large while the population is far from an optimum and
+/// shrinking as it closes in - without a schedule. Step sizes never drop below `min_sigma`.
+///
+/// Evolution strategies typically mutate every gene, so the rate is usually 1. The result is brought
+/// back within the gene's bounds by its `BoundaryPolicy`. Used through a `ControlledMutator`, which
+/// mutates gene by gene, a gene's step size is mutated without the global draw.
+///
+/// This mutator is for use with the `StrategyChromosome` (see `StrategyCodex`).
+pub struct SelfAdaptiveMutator {
+    rate: f32,
+    min_sigma: f32,
+}
+
+impl SelfAdaptiveMutator {
+    /// Create a new instance of the `SelfAdaptiveMutator` with the given rate.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new(rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        SelfAdaptiveMutator {
+            rate,
+            min_sigma: 1e-6,
+        }
+    }
+
+    /// Set the smallest step size a gene can adapt to. Default is `1e-6`.
+    pub fn with_min_sigma(mut self, min_sigma: f32) -> Self {
+        if min_sigma.is_nan() || min_sigma <= 0.0 {
+            panic!("min_sigma must be greater than 0");
+        }
+
+        self.min_sigma = min_sigma;
+        self
+    }
+
+    fn mutate_strategy(
+        &self,
+        gene: &StrategyGene,
+        global_step: f64,
+        tau: f64,
+        rng: &RngHandle,
+    ) -> StrategyGene {
+        let step = global_step + tau * rng.gaussian(0.0, 1.0);
+        let sigma = ((gene.sigma as f64) * step.exp()).max(self.min_sigma as f64);
+        let allele = rng.gaussian(gene.allele as f64, sigma);
+
+        StrategyGene {
+            sigma: sigma as f32,
+            ..gene.bounded(&(allele as f32))
+        }
+    }
+}
+
+impl EngineCompoment for SelfAdaptiveMutator {
+    fn name(&self) -> &'static str {
+        "SelfAdaptiveMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("rate", self.rate)
+            .param("min_sigma", self.min_sigma)
+    }
+}
+
+impl<C: Chromosome<Gene = StrategyGene>> Alter<C> for SelfAdaptiveMutator {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<C: Chromosome<Gene = StrategyGene>> Mutate<C> for SelfAdaptiveMutator {
+    #[inline]
+    fn mutate_chromosome(&self, chromosome: &mut C, rng: &RngHandle) -> i32 {
+        let n = chromosome.len().max(1) as f64;
+        let tau_global = 1.0 / (2.0 * n).sqrt();
+        let tau = 1.0 / (2.0 * n.sqrt()).sqrt();
+        let global_step = tau_global * rng.gaussian(0.0, 1.0);
+
+        let mut count = 0;
+        for gene in chromosome.iter_mut() {
+            if rng.random::<f32>() < self.rate {
+                *gene = self.mutate_strategy(gene, global_step, tau, rng);
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    #[inline]
+    fn mutate_gene(&self, gene: &C::Gene, rng: &RngHandle) -> C::Gene {
+        self.mutate_strategy(gene, 0.0, 1.0 / 2_f64.sqrt(), rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StrategyChromosome, Valid};
+
+    #[test]
+    fn test_step_sizes_are_mutated_with_the_alleles() {
+        let rng = RngHandle::from_entropy();
+        let mutator = SelfAdaptiveMutator::new(1.0).with_min_sigma(0.01);
+        let genes = (0..10)
+            .map(|_| StrategyGene::new(-100.0, 100.0).with_sigma(0.02))
+            .collect::<Vec<StrategyGene>>();
+        let original = StrategyChromosome::new(genes);
+
+        let mut changed = false;
+        for _ in 0..20 {
+            let mut chromosome = original.clone();
+            let count =
+                Mutate::<StrategyChromosome>::mutate_chromosome(&mutator, &mut chromosome, &rng);
+
+            assert_eq!(count, 10);
+            assert!(chromosome.sigmas().iter().all(|sigma| *sigma >= 0.01));
+            assert!(chromosome.is_valid());
+            changed |= chromosome.sigmas() != original.sigmas();
+        }
+
+        assert!(changed);
+    }
+}
@@ -1,5 +1,6 @@
 use super::{Alter, AlterAction, Crossover};
-use crate::{random_provider, Chromosome, EngineCompoment};
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment};
 
 pub struct ShuffleCrossover {
     rate: f32,
@@ -15,6 +16,10 @@ impl EngineCompoment for ShuffleCrossover {
     fn name(&self) -> &'static str {
         "ShuffleCrossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Alter<C> for ShuffleCrossover {
@@ -29,14 +34,14 @@ impl<C: Chromosome> Alter<C> for ShuffleCrossover {
 
 impl<C: Chromosome> Crossover<C> for ShuffleCrossover {
     #[inline]
-    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C) -> i32 {
+    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C, rng: &RngHandle) -> i32 {
         let length = std::cmp::min(chrom_one.len(), chrom_two.len());
         if length < 2 {
             return 0;
         }
 
         let mut indices: Vec<usize> = (0..length).collect();
-        random_provider::shuffle(&mut indices);
+        rng.shuffle(&mut indices);
 
         let temp_chrom_one = chrom_one.clone();
         let temp_chrom_two = chrom_two.clone();
@@ -1,5 +1,8 @@
 use super::{Alter, AlterAction, Crossover};
-use crate::{random_provider, Chromosome, EngineCompoment, FloatGene, Gene, NumericGene};
+use crate::Description;
+use crate::{
+    random_provider::RngHandle, Chromosome, EngineCompoment, FloatGene, Gene, NumericGene,
+};
 
 pub struct SimulatedBinaryCrossover {
     contiguty: f32,
@@ -29,6 +32,12 @@ impl EngineCompoment for SimulatedBinaryCrossover {
     fn name(&self) -> &'static str {
         "Simulated Binary Crossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("rate", self.crossover_rate)
+            .param("contiguity", self.contiguty)
+    }
 }
 
 impl<C: Chromosome<Gene = FloatGene>> Alter<C> for SimulatedBinaryCrossover {
@@ -43,7 +52,7 @@ impl<C: Chromosome<Gene = FloatGene>> Alter<C> for SimulatedBinaryCrossover {
 
 impl<C: Chromosome<Gene = FloatGene>> Crossover<C> for SimulatedBinaryCrossover {
     #[inline]
-    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C) -> i32 {
+    fn cross_chromosomes(&self, chrom_one: &mut C, chrom_two: &mut C, rng: &RngHandle) -> i32 {
         let length = std::cmp::min(chrom_one.len(), chrom_two.len());
 
         if length < 2 {
@@ -53,8 +62,8 @@ impl<C: Chromosome<Gene = FloatGene>> Crossover<C> for SimulatedBinaryCrossover
         let mut count = 0;
 
         for i in 0..length {
-            if random_provider::gen_range(0..2) == 0 {
-                let u = random_provider::random::<f32>();
+            if rng.gen_range(0..2) == 0 {
+                let u = rng.random::<f32>();
                 let beta = if u <= 0.5 {
                     (2.0 * u).powf(1.0 / (self.contiguty + 1.0))
                 } else {
@@ -64,21 +73,15 @@ impl<C: Chromosome<Gene = FloatGene>> Crossover<C> for SimulatedBinaryCrossover
                 let v1 = chrom_one.get_gene(i).allele();
                 let v2 = chrom_two.get_gene(i).allele();
 
-                let v = if random_provider::gen_range(0..2) == 0 {
+                let v = if rng.gen_range(0..2) == 0 {
                     (v1 - v2) * 0.5 - (beta * 0.5 * (v1 - v2).abs())
                 } else {
                     (v1 - v2) * 0.5 + (beta * 0.5 * (v1 - v2).abs())
                 };
 
-                let new_gene = SimulatedBinaryCrossover::clamp(
-                    v,
-                    *chrom_one.get_gene(i).min(),
-                    *chrom_one.get_gene(i).max(),
-                );
-
                 count += 1;
 
-                chrom_one.set_gene(i, chrom_one.get_gene(i).with_allele(&new_gene));
+                chrom_one.set_gene(i, chrom_one.get_gene(i).bounded(&v));
             }
         }
 
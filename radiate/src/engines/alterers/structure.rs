@@ -0,0 +1,134 @@
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment, Genotype};
+
+use super::{Alter, AlterAction, Mutate};
+
+type ChromosomeFactory<C> = Box<dyn Fn() -> C>;
+
+/// The `StructureMutator` changes the structure of a genotype rather than its genes: with probability
+/// `rate` it either inserts a new chromosome (made by the `factory`) at a random position or removes a
+/// random chromosome. With a `MixedChromosome` per layer (see `MixedChromosome`) this grows and shrinks
+/// networks in a variable architecture search - the factory can return chromosomes of any structure.
+///
+/// The number of chromosomes is kept within the limits set with `with_limits` (at least 1 by default);
+/// at a limit, the mutator only makes the change that stays within it.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let layers = StructureMutator::new(0.1, || {
+///     MixedChromosome::new(vec![IntGene::from_min_max(1, 64).into(), BitGene::new().into()])
+/// })
+/// .with_limits(1, 8);
+/// ```
+pub struct StructureMutator<C: Chromosome> {
+    rate: f32,
+    factory: ChromosomeFactory<C>,
+    min_chromosomes: usize,
+    max_chromosomes: usize,
+}
+
+impl<C: Chromosome> StructureMutator<C> {
+    /// Create a new instance of the `StructureMutator` with the given rate and chromosome factory.
+    /// The rate must be between 0.0 and 1.0.
+    pub fn new<F>(rate: f32, factory: F) -> Self
+    where
+        F: Fn() -> C + 'static,
+    {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("Rate must be between 0 and 1");
+        }
+
+        StructureMutator {
+            rate,
+            factory: Box::new(factory),
+            min_chromosomes: 1,
+            max_chromosomes: usize::MAX,
+        }
+    }
+
+    /// Keep the number of chromosomes of a genotype between `min` and `max` (inclusive).
+    pub fn with_limits(mut self, min: usize, max: usize) -> Self {
+        if min > max {
+            panic!("min must be less than or equal to max");
+        }
+
+        self.min_chromosomes = min;
+        self.max_chromosomes = max;
+        self
+    }
+}
+
+impl<C: Chromosome> EngineCompoment for StructureMutator<C> {
+    fn name(&self) -> &'static str {
+        "StructureMutator"
+    }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+            .param("rate", self.rate)
+            .param("min_chromosomes", self.min_chromosomes)
+            .param("max_chromosomes", self.max_chromosomes)
+    }
+}
+
+impl<C: Chromosome + 'static> Alter<C> for StructureMutator<C> {
+    fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    fn to_alter(self) -> AlterAction<C> {
+        AlterAction::Mutate(Box::new(self))
+    }
+}
+
+impl<C: Chromosome + 'static> Mutate<C> for StructureMutator<C> {
+    #[inline]
+    fn mutate_genotype(&self, genotype: &mut Genotype<C>, rng: &RngHandle) -> i32 {
+        if rng.random::<f32>() >= self.rate {
+            return 0;
+        }
+
+        let len = genotype.len();
+        let can_grow = len < self.max_chromosomes;
+        let can_shrink = len > self.min_chromosomes && len > 0;
+
+        let grow = match (can_grow, can_shrink) {
+            (true, true) => rng.random::<f32>() < 0.5,
+            (grow, shrink) if grow || shrink => grow,
+            _ => return 0,
+        };
+
+        if grow {
+            let index = rng.gen_range(0..len + 1);
+            genotype.chromosomes.insert(index, (self.factory)());
+        } else {
+            let index = rng.gen_range(0..len);
+            genotype.chromosomes.remove(index);
+        }
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatGene, MixedChromosome};
+
+    #[test]
+    fn test_structure_stays_within_limits() {
+        let rng = RngHandle::from_entropy();
+        let mutator = StructureMutator::new(1.0, || {
+            MixedChromosome::new(vec![FloatGene::new(0.0, 1.0).into()])
+        })
+        .with_limits(2, 4);
+
+        let mut genotype = Genotype::new(vec![MixedChromosome::default(); 2]);
+        for _ in 0..100 {
+            assert_eq!(mutator.mutate_genotype(&mut genotype, &rng), 1);
+            assert!((2..=4).contains(&genotype.len()));
+        }
+    }
+}
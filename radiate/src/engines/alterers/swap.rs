@@ -1,5 +1,6 @@
 use super::{Alter, AlterAction, Mutate};
-use crate::{random_provider, Chromosome, EngineCompoment};
+use crate::Description;
+use crate::{random_provider::RngHandle, Chromosome, EngineCompoment};
 
 pub struct SwapMutator {
     rate: f32,
@@ -15,6 +16,10 @@ impl EngineCompoment for SwapMutator {
     fn name(&self) -> &'static str {
         "SwapMutator"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Alter<C> for SwapMutator {
@@ -29,12 +34,12 @@ impl<C: Chromosome> Alter<C> for SwapMutator {
 
 impl<C: Chromosome> Mutate<C> for SwapMutator {
     #[inline]
-    fn mutate_chromosome(&self, chromosome: &mut C) -> i32 {
+    fn mutate_chromosome(&self, chromosome: &mut C, rng: &RngHandle) -> i32 {
         let mut mutations = 0;
 
         for i in 0..chromosome.len() {
-            if random_provider::random::<f32>() < self.rate {
-                let swap_index = random_provider::gen_range(0..chromosome.len());
+            if rng.random::<f32>() < self.rate {
+                let swap_index = rng.gen_range(0..chromosome.len());
 
                 if swap_index == i {
                     continue;
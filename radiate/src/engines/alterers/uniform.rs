@@ -1,3 +1,4 @@
+use crate::Description;
 use crate::{Chromosome, EngineCompoment};
 
 use super::Alter;
@@ -29,6 +30,10 @@ impl EngineCompoment for UniformCrossover {
     fn name(&self) -> &'static str {
         "UniformCrossover"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Crossover<C> for UniformCrossover {}
@@ -47,6 +52,10 @@ impl EngineCompoment for UniformMutator {
     fn name(&self) -> &'static str {
         "UniformMutator"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("rate", self.rate)
+    }
 }
 
 impl<C: Chromosome> Alter<C> for UniformMutator {
@@ -0,0 +1,509 @@
+use std::sync::Arc;
+
+use super::problem::EngineProblem;
+use super::{random_provider, Codex, Genotype, Optimize, PermutationChromosome, Problem, Score};
+
+/// Heuristic information - the desirability of visiting element `j` right after element `i`.
+type Heuristic = Arc<dyn Fn(usize, usize) -> f32 + Send + Sync>;
+
+/// A square matrix of the distances between the elements of a routing problem, e.g. the cities of a TSP.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let matrix = DistanceMatrix::from_points(&[(0.0, 0.0), (3.0, 0.0), (3.0, 4.0)]);
+///
+/// assert_eq!(matrix.distance(0, 2), 5.0);
+/// assert_eq!(matrix.tour_length(&[0, 1, 2]), 12.0);
+/// assert_eq!(matrix.path_length(&[0, 1, 2]), 7.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceMatrix {
+    distances: Vec<Vec<f32>>,
+}
+
+impl DistanceMatrix {
+    /// Panics if the matrix isn't square.
+    pub fn new(distances: Vec<Vec<f32>>) -> Self {
+        if distances.iter().any(|row| row.len() != distances.len()) {
+            panic!("Distance matrix must be square");
+        }
+
+        DistanceMatrix { distances }
+    }
+
+    /// The euclidean distances between points.
+    pub fn from_points(points: &[(f32, f32)]) -> Self {
+        DistanceMatrix::new(
+            points
+                .iter()
+                .map(|a| {
+                    points
+                        .iter()
+                        .map(|b| ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// A symmetric matrix from its lower triangle, diagonal included, row by row - the
+    /// `LOWER_DIAG_ROW` edge weights of TSPLIB files. Panics if there are too few weights.
+    pub fn from_lower_triangle(weights: &[f32], size: usize) -> Self {
+        if weights.len() < size * (size + 1) / 2 {
+            panic!("Expected {} weights", size * (size + 1) / 2);
+        }
+
+        let row = |i: usize| &weights[i * (i + 1) / 2..(i + 1) * (i + 2) / 2];
+        let distances = (0..size)
+            .map(|i| {
+                (0..size)
+                    .map(|j| if j <= i { row(i)[j] } else { row(j)[i] })
+                    .collect()
+            })
+            .collect();
+
+        DistanceMatrix { distances }
+    }
+
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    pub fn distance(&self, i: usize, j: usize) -> f32 {
+        self.distances[i][j]
+    }
+
+    /// The length of a path visiting the elements in order.
+    pub fn path_length(&self, order: &[usize]) -> f32 {
+        order
+            .windows(2)
+            .map(|pair| self.distances[pair[0]][pair[1]])
+            .sum()
+    }
+
+    /// The length of a closed tour visiting the elements in order and returning to the first.
+    pub fn tour_length(&self, order: &[usize]) -> f32 {
+        match (order.first(), order.last()) {
+            (Some(first), Some(last)) => self.path_length(order) + self.distances[*last][*first],
+            _ => 0.0,
+        }
+    }
+
+    /// The usual heuristic information of an ant colony - the inverse of the distance.
+    pub fn visibility(&self, i: usize, j: usize) -> f32 {
+        1.0 / self.distances[i][j].max(f32::EPSILON)
+    }
+
+    pub fn rows(&self) -> &[Vec<f32>] {
+        &self.distances
+    }
+}
+
+/// How an `AntColony` deposits pheromone after every iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PheromoneUpdate {
+    /// Ant system - every ant deposits pheromone on the edges of its permutation.
+    #[default]
+    AntSystem,
+    /// Max-min ant system - only the best permutation found so far deposits, and the pheromone is kept
+    /// between `max = 1 / (evaporation * best cost)` and `min = max / (2 * elements)`, so no edge is
+    /// ever certain or impossible.
+    MaxMin,
+}
+
+/// The state of an `AntColony` after an iteration.
+#[derive(Clone, Debug)]
+pub struct ColonyContext<A: PartialEq + Clone, T> {
+    /// The number of iterations run.
+    pub index: usize,
+    pub best: T,
+    pub best_genotype: Genotype<PermutationChromosome<A>>,
+    pub score: Score,
+    /// The pheromone on the edge from every element to every other element.
+    pub pheromones: Vec<Vec<f32>>,
+    pub evaluations: usize,
+}
+
+impl<A: PartialEq + Clone, T> ColonyContext<A, T> {
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+}
+
+/// An ant colony optimizer for problems over a `PermutationChromosome`, e.g. routing and scheduling.
+/// Every iteration, each ant builds a permutation one element at a time, starting from a random element
+/// and moving from element `i` to an unvisited element `j` with a probability proportional to
+/// `pheromone(i, j)^alpha * heuristic(i, j)^beta`. The permutations are scored with the `Problem`, the
+/// pheromone evaporates and the ants deposit pheromone on the edges between consecutive elements of
+/// their permutations (see `PheromoneUpdate`), in proportion to their quality - `deposit / cost` when
+/// minimizing and `deposit * score` when maximizing, so scores should be positive.
+///
+/// The colony shares the `Problem` and `Codex` abstractions with the `GeneticEngine`. Only the first
+/// chromosome of a genotype is built by the ants.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let points = (0..10)
+///     .map(|i| {
+///         let angle = i as f32 / 10.0 * std::f32::consts::TAU;
+///         (angle.cos(), angle.sin())
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let matrix = DistanceMatrix::from_points(&points);
+/// let tours = matrix.clone();
+///
+/// let colony = AntColony::from_codex(
+///     PermutationCodex::new((0..10).collect()),
+///     move |order: Vec<usize>| tours.tour_length(&order),
+/// )
+/// .heuristic(move |i, j| matrix.visibility(i, j))
+/// .pheromone_update(PheromoneUpdate::MaxMin);
+///
+/// let result = colony.run(|ctx| ctx.index >= 50);
+/// assert!(result.score().as_f32() < 6.2);
+/// ```
+pub struct AntColony<A: PartialEq + Clone, T> {
+    problem: Arc<Box<dyn Problem<PermutationChromosome<A>, T>>>,
+    heuristic: Option<Heuristic>,
+    optimize: Optimize,
+    update: PheromoneUpdate,
+    ants: usize,
+    alpha: f32,
+    beta: f32,
+    evaporation: f32,
+    deposit: f32,
+    initial_pheromone: f32,
+}
+
+impl<A, T> AntColony<A, T>
+where
+    A: PartialEq + Clone + 'static,
+    T: Clone + 'static,
+{
+    pub fn new(problem: impl Problem<PermutationChromosome<A>, T> + 'static) -> Self {
+        AntColony {
+            problem: Arc::new(Box::new(problem)),
+            heuristic: None,
+            optimize: Optimize::Minimize,
+            update: PheromoneUpdate::AntSystem,
+            ants: 20,
+            alpha: 1.0,
+            beta: 2.0,
+            evaporation: 0.1,
+            deposit: 1.0,
+            initial_pheromone: 1.0,
+        }
+    }
+
+    /// Score the permutations of a codex with a fitness function like `GeneticEngineParams::fitness_fn`.
+    pub fn from_codex<S: Into<Score>>(
+        codex: impl Codex<PermutationChromosome<A>, T> + 'static,
+        fitness_fn: impl Fn(T) -> S + Send + Sync + 'static,
+    ) -> Self {
+        AntColony::new(EngineProblem {
+            codex: Arc::new(Box::new(codex)),
+            fitness_fn: Arc::new(move |value| fitness_fn(value).into()),
+            parts: Vec::new(),
+        })
+    }
+
+    /// Set the heuristic information - the desirability of visiting element `j` right after element `i`,
+    /// e.g. `DistanceMatrix::visibility`. Default is 1 for every edge, leaving the choice to the pheromone.
+    pub fn heuristic<F>(mut self, heuristic: F) -> Self
+    where
+        F: Fn(usize, usize) -> f32 + Send + Sync + 'static,
+    {
+        self.heuristic = Some(Arc::new(heuristic));
+        self
+    }
+
+    /// Default is minimizing - the score is a cost.
+    pub fn minimizing(mut self) -> Self {
+        self.optimize = Optimize::Minimize;
+        self
+    }
+
+    pub fn maximizing(mut self) -> Self {
+        self.optimize = Optimize::Maximize;
+        self
+    }
+
+    /// Default is `PheromoneUpdate::AntSystem`.
+    pub fn pheromone_update(mut self, update: PheromoneUpdate) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Set the number of ants - permutations built and evaluated - per iteration. Default is 20.
+    pub fn ants(mut self, ants: usize) -> Self {
+        if ants < 1 {
+            panic!("ants must be greater than 0");
+        }
+
+        self.ants = ants;
+        self
+    }
+
+    /// Set the weight of the pheromone in an ant's choice. Default is 1.
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        if alpha < 0.0 {
+            panic!("alpha must be greater than or equal to 0");
+        }
+
+        self.alpha = alpha;
+        self
+    }
+
+    /// Set the weight of the heuristic information in an ant's choice. Default is 2.
+    pub fn beta(mut self, beta: f32) -> Self {
+        if beta < 0.0 {
+            panic!("beta must be greater than or equal to 0");
+        }
+
+        self.beta = beta;
+        self
+    }
+
+    /// Set the fraction of the pheromone that evaporates every iteration. Default is 0.1.
+    pub fn evaporation(mut self, evaporation: f32) -> Self {
+        if evaporation <= 0.0 || evaporation > 1.0 {
+            panic!("evaporation must be in (0, 1]");
+        }
+
+        self.evaporation = evaporation;
+        self
+    }
+
+    /// Set the amount of pheromone an ant deposits, scaled by its quality. Default is 1.
+    pub fn deposit(mut self, deposit: f32) -> Self {
+        if deposit <= 0.0 {
+            panic!("deposit must be greater than 0");
+        }
+
+        self.deposit = deposit;
+        self
+    }
+
+    /// Set the pheromone every edge starts with. Default is 1.
+    pub fn initial_pheromone(mut self, pheromone: f32) -> Self {
+        if pheromone <= 0.0 {
+            panic!("initial pheromone must be greater than 0");
+        }
+
+        self.initial_pheromone = pheromone;
+        self
+    }
+
+    /// Run iterations until `limit` returns true, returning the state after the last one.
+    pub fn run<F>(&self, limit: F) -> ColonyContext<A, T>
+    where
+        F: Fn(&ColonyContext<A, T>) -> bool,
+    {
+        let template = self.problem.encode();
+        let size = template[0].genes.len();
+        let mut context = ColonyContext {
+            index: 0,
+            best: self.problem.decode(&template),
+            score: self.problem.eval(&template),
+            best_genotype: template,
+            pheromones: vec![vec![self.initial_pheromone; size]; size],
+            evaluations: 1,
+        };
+
+        loop {
+            self.iterate(&mut context);
+            if limit(&context) {
+                return context;
+            }
+        }
+    }
+
+    fn iterate(&self, context: &mut ColonyContext<A, T>) {
+        let mut tours = Vec::with_capacity(self.ants);
+        for _ in 0..self.ants {
+            let order = self.construct(&context.pheromones);
+            let mut genotype = context.best_genotype.clone();
+            for (gene, index) in genotype[0].genes.iter_mut().zip(order.iter()) {
+                gene.index = *index;
+            }
+
+            let score = self.problem.eval(&genotype);
+            context.evaluations += 1;
+
+            if self.optimize.is_better(&score, &context.score) {
+                context.best = self.problem.decode(&genotype);
+                context.best_genotype = genotype;
+                context.score = score.clone();
+            }
+
+            tours.push((order, score));
+        }
+
+        for row in context.pheromones.iter_mut() {
+            for pheromone in row.iter_mut() {
+                *pheromone *= 1.0 - self.evaporation;
+            }
+        }
+
+        match self.update {
+            PheromoneUpdate::AntSystem => {
+                for (order, score) in tours.iter() {
+                    self.lay(&mut context.pheromones, order, score);
+                }
+            }
+            PheromoneUpdate::MaxMin => {
+                let best = context.best_genotype[0]
+                    .genes
+                    .iter()
+                    .map(|gene| gene.index)
+                    .collect::<Vec<usize>>();
+                self.lay(&mut context.pheromones, &best, &context.score);
+
+                let max = self.quality(&context.score) / self.evaporation;
+                let min = max / (2.0 * best.len().max(1) as f32);
+                for row in context.pheromones.iter_mut() {
+                    for pheromone in row.iter_mut() {
+                        *pheromone = pheromone.clamp(min, max);
+                    }
+                }
+            }
+        }
+
+        context.index += 1;
+    }
+
+    /// Build a permutation, visiting the next element with a probability proportional to its pheromone
+    /// and heuristic information.
+    fn construct(&self, pheromones: &[Vec<f32>]) -> Vec<usize> {
+        let size = pheromones.len();
+        let mut visited = vec![false; size];
+        let mut order = Vec::with_capacity(size);
+        if size == 0 {
+            return order;
+        }
+
+        let mut current = random_provider::gen_range(0..size);
+        visited[current] = true;
+        order.push(current);
+
+        let mut weights = vec![0.0; size];
+        while order.len() < size {
+            let mut total = 0.0;
+            for next in 0..size {
+                weights[next] = if visited[next] {
+                    0.0
+                } else {
+                    let heuristic = match &self.heuristic {
+                        Some(heuristic) => heuristic(current, next).max(0.0),
+                        None => 1.0,
+                    };
+
+                    pheromones[current][next].powf(self.alpha) * heuristic.powf(self.beta)
+                };
+
+                total += weights[next];
+            }
+
+            let unvisited = (0..size).filter(|i| !visited[*i]);
+            current = if total > 0.0 && total.is_finite() {
+                let mut target = random_provider::random::<f32>() * total;
+                let mut choice = None;
+                for next in unvisited {
+                    choice = Some(next);
+                    target -= weights[next];
+                    if target <= 0.0 {
+                        break;
+                    }
+                }
+
+                choice.unwrap()
+            } else {
+                *random_provider::choose(&unvisited.collect::<Vec<usize>>())
+            };
+
+            visited[current] = true;
+            order.push(current);
+        }
+
+        order
+    }
+
+    fn quality(&self, score: &Score) -> f32 {
+        match self.optimize {
+            Optimize::Minimize => self.deposit / score.as_f32().max(f32::EPSILON),
+            Optimize::Maximize => self.deposit * score.as_f32().max(0.0),
+        }
+    }
+
+    fn lay(&self, pheromones: &mut [Vec<f32>], order: &[usize], score: &Score) {
+        let quality = self.quality(score);
+        for pair in order.windows(2) {
+            pheromones[pair[0]][pair[1]] += quality;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PermutationCodex;
+
+    #[test]
+    fn test_distance_matrix_from_lower_triangle() {
+        let matrix = DistanceMatrix::from_lower_triangle(&[0.0, 1.0, 0.0, 2.0, 3.0, 0.0], 3);
+
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.distance(0, 2), 2.0);
+        assert_eq!(matrix.distance(2, 1), 3.0);
+        assert_eq!(matrix.tour_length(&[0, 1, 2]), 6.0);
+    }
+
+    #[test]
+    fn test_ant_system_orders_a_line() {
+        // the shortest path through points on a line visits them in order
+        let matrix = DistanceMatrix::from_points(
+            &(0..8).map(|i| (i as f32, 0.0)).collect::<Vec<(f32, f32)>>(),
+        );
+        let paths = matrix.clone();
+
+        let colony = AntColony::from_codex(
+            PermutationCodex::new((0..8).collect::<Vec<usize>>()),
+            move |order: Vec<usize>| paths.path_length(&order),
+        )
+        .heuristic(move |i, j| matrix.visibility(i, j))
+        .ants(10);
+
+        let result = colony.run(|ctx| ctx.score().as_f32() == 7.0 || ctx.index >= 100);
+
+        assert_eq!(result.score().as_f32(), 7.0);
+        assert_eq!(result.evaluations, 1 + 10 * result.index);
+        assert!(result.best == (0..8).collect::<Vec<usize>>() || result.best[0] == 7);
+    }
+
+    #[test]
+    fn test_max_min_keeps_pheromone_in_bounds() {
+        let colony = AntColony::from_codex(
+            PermutationCodex::new((0..5).collect::<Vec<usize>>()),
+            |order: Vec<usize>| 1.0 + order[0] as f32,
+        )
+        .pheromone_update(PheromoneUpdate::MaxMin)
+        .evaporation(0.5);
+
+        let result = colony.run(|ctx| ctx.index >= 20);
+
+        let max = 1.0 / (0.5 * result.score().as_f32());
+        for pheromone in result.pheromones.iter().flatten() {
+            assert!(*pheromone <= max + 1e-6);
+            assert!(*pheromone >= max / 10.0 - 1e-6);
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::MemoryFootprint;
+
+/// A shared memory budget. Like the `HallOfFame`, a `MemoryBudget` is cheap to clone and all clones
+/// share the same usage, so one clone can be given to the engine (which reports the approximate memory
+/// of its population every generation) and others to the archives that should stay within the budget.
+/// A `SpillArchive` spills its least recently used values to disk whenever the population and the values
+/// it holds in memory together exceed the limit.
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    limit: usize,
+    population: Arc<AtomicUsize>,
+}
+
+impl MemoryBudget {
+    /// Create a new budget of `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        MemoryBudget {
+            limit,
+            population: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The approximate memory of the engine's population in bytes, as last reported.
+    pub fn population(&self) -> usize {
+        self.population.load(Ordering::SeqCst)
+    }
+
+    pub fn set_population(&self, bytes: usize) {
+        self.population.store(bytes, Ordering::SeqCst);
+    }
+
+    /// The number of bytes left for archives once the population is accounted for.
+    pub fn available(&self) -> usize {
+        self.limit.saturating_sub(self.population())
+    }
+}
+
+/// A value that can be written to and read back from disk by a `SpillArchive`. With the `serde` feature
+/// enabled every `Serialize + DeserializeOwned` type is `Spillable` (as JSON).
+pub trait Spillable: Sized {
+    fn spill(&self) -> std::io::Result<Vec<u8>>;
+    fn restore(bytes: &[u8]) -> std::io::Result<Self>;
+}
+
+#[cfg(feature = "serde")]
+impl<T> Spillable for T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn spill(&self) -> std::io::Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(std::io::Error::other)
+    }
+
+    fn restore(bytes: &[u8]) -> std::io::Result<Self> {
+        serde_json::from_slice(bytes).map_err(std::io::Error::other)
+    }
+}
+
+enum Slot<T> {
+    Resident { value: T, bytes: usize, used: u64 },
+    Spilled { path: PathBuf },
+}
+
+/// An append-only archive of cold values - past champions, elites, a novelty archive, etc - that keeps
+/// its memory within a `MemoryBudget`. When the budget is exceeded the least recently used values are
+/// written to files in the archive's directory and dropped from memory. They are reloaded transparently
+/// the next time they are accessed with `get`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// struct Elite(Vec<u8>);
+///
+/// impl Spillable for Elite {
+///     fn spill(&self) -> std::io::Result<Vec<u8>> {
+///         Ok(self.0.clone())
+///     }
+///
+///     fn restore(bytes: &[u8]) -> std::io::Result<Self> {
+///         Ok(Elite(bytes.to_vec()))
+///     }
+/// }
+///
+/// impl MemoryFootprint for Elite {
+///     fn footprint(&self) -> usize {
+///         self.0.footprint()
+///     }
+/// }
+///
+/// let dir = std::env::temp_dir().join(format!("radiate_doc_archive_{}", std::process::id()));
+/// let mut archive = SpillArchive::new(&dir, MemoryBudget::new(100)).unwrap();
+///
+/// for i in 0..4 {
+///     archive.push(Elite(vec![i; 40])).unwrap();
+/// }
+///
+/// assert!(archive.spilled() > 0);
+/// assert_eq!(archive.get(0).unwrap().0, vec![0; 40]);
+/// ```
+pub struct SpillArchive<T: Spillable + MemoryFootprint> {
+    dir: PathBuf,
+    budget: MemoryBudget,
+    slots: Vec<Slot<T>>,
+    resident: usize,
+    clock: u64,
+}
+
+impl<T: Spillable + MemoryFootprint> SpillArchive<T> {
+    /// Create a new archive that spills into `dir` (created if needed) when `budget` is exceeded.
+    pub fn new(dir: impl AsRef<Path>, budget: MemoryBudget) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+
+        Ok(SpillArchive {
+            dir: dir.as_ref().to_path_buf(),
+            budget,
+            slots: Vec::new(),
+            resident: 0,
+            clock: 0,
+        })
+    }
+
+    /// Add a value to the archive and return its index.
+    pub fn push(&mut self, value: T) -> std::io::Result<usize> {
+        let bytes = value.footprint();
+        self.clock += 1;
+        self.resident += bytes;
+        self.slots.push(Slot::Resident {
+            value,
+            bytes,
+            used: self.clock,
+        });
+
+        let index = self.slots.len() - 1;
+        self.enforce_budget(index)?;
+        Ok(index)
+    }
+
+    /// Get the value at `index`, reloading it from disk if it was spilled. Panics if `index` is out of range.
+    pub fn get(&mut self, index: usize) -> std::io::Result<&T> {
+        self.clock += 1;
+
+        if let Slot::Spilled { path } = &self.slots[index] {
+            let value = T::restore(&std::fs::read(path)?)?;
+            std::fs::remove_file(path)?;
+
+            let bytes = value.footprint();
+            self.resident += bytes;
+            self.slots[index] = Slot::Resident {
+                value,
+                bytes,
+                used: self.clock,
+            };
+        } else if let Slot::Resident { used, .. } = &mut self.slots[index] {
+            *used = self.clock;
+        }
+
+        self.enforce_budget(index)?;
+
+        match &self.slots[index] {
+            Slot::Resident { value, .. } => Ok(value),
+            Slot::Spilled { .. } => unreachable!("the accessed value is never spilled"),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// The approximate number of bytes held in memory.
+    pub fn resident_bytes(&self) -> usize {
+        self.resident
+    }
+
+    /// The number of values currently on disk.
+    pub fn spilled(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| matches!(slot, Slot::Spilled { .. }))
+            .count()
+    }
+
+    pub fn budget(&self) -> &MemoryBudget {
+        &self.budget
+    }
+
+    /// Spill the least recently used values (never `keep`) until the archive fits in the budget.
+    fn enforce_budget(&mut self, keep: usize) -> std::io::Result<()> {
+        while self.resident > self.budget.available() {
+            let coldest = self
+                .slots
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != keep)
+                .filter_map(|(index, slot)| match slot {
+                    Slot::Resident { used, .. } => Some((index, *used)),
+                    Slot::Spilled { .. } => None,
+                })
+                .min_by_key(|(_, used)| *used)
+                .map(|(index, _)| index);
+
+            match coldest {
+                Some(index) => self.spill(index)?,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn spill(&mut self, index: usize) -> std::io::Result<()> {
+        let path = self.dir.join(format!("{:08}.spill", index));
+        if let Slot::Resident { value, bytes, .. } = &self.slots[index] {
+            std::fs::write(&path, value.spill()?)?;
+            self.resident -= bytes;
+        }
+
+        self.slots[index] = Slot::Spilled { path };
+        Ok(())
+    }
+}
+
+/// Spilled values are only meaningful to the archive that wrote them, so their files are removed with it.
+impl<T: Spillable + MemoryFootprint> Drop for SpillArchive<T> {
+    fn drop(&mut self) {
+        for slot in self.slots.iter() {
+            if let Slot::Spilled { path } = slot {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Blob(Vec<u8>);
+
+    impl Spillable for Blob {
+        fn spill(&self) -> std::io::Result<Vec<u8>> {
+            Ok(self.0.clone())
+        }
+
+        fn restore(bytes: &[u8]) -> std::io::Result<Self> {
+            Ok(Blob(bytes.to_vec()))
+        }
+    }
+
+    impl MemoryFootprint for Blob {
+        fn footprint(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn test_least_recently_used_values_are_spilled_and_reloaded() {
+        let dir = std::env::temp_dir().join(format!("radiate_archive_{}", std::process::id()));
+        let budget = MemoryBudget::new(100);
+        let mut archive = SpillArchive::new(&dir, budget.clone()).unwrap();
+
+        archive.push(Blob(vec![0; 30])).unwrap();
+        archive.push(Blob(vec![1; 30])).unwrap();
+        archive.push(Blob(vec![2; 30])).unwrap();
+        assert_eq!(archive.spilled(), 0);
+
+        // Touch the oldest value so the second one becomes the coldest.
+        archive.get(0).unwrap();
+        archive.push(Blob(vec![3; 30])).unwrap();
+        assert_eq!(archive.spilled(), 1);
+        assert!(archive.resident_bytes() <= 100);
+
+        assert_eq!(archive.get(1).unwrap(), &Blob(vec![1; 30]));
+        assert_eq!(archive.spilled(), 1);
+
+        // A larger population leaves less room for the archive.
+        budget.set_population(50);
+        archive.get(3).unwrap();
+        assert_eq!(archive.spilled(), 3);
+        assert_eq!(archive.len(), 4);
+
+        drop(archive);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        std::fs::remove_dir(&dir).unwrap();
+    }
+}
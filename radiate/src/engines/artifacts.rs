@@ -0,0 +1,270 @@
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind, Result, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::stats::history::reading;
+use super::{Chromosome, EngineContext, EngineEvent, Subscriber};
+
+pub const CHECKPOINT_FILE: &str = "checkpoint.rdwf";
+pub const METRICS_FILE: &str = "metrics.csv";
+pub const FRONT_FILE: &str = "front.csv";
+pub const REPORT_FILE: &str = "report.txt";
+pub const BEST_FILE: &str = "best.txt";
+
+/// The directory of a single run, named after the experiment and the time the run started
+/// (`<root>/<name>-<YYYYMMDD>-<HHMMSS>`, in UTC), and the layout of the files the run writes into it:
+///
+/// * `metrics.csv` - a `generation,metric,value` row per metric and generation, with the same reading
+///   a `MetricHistory` records.
+/// * `front.csv` - the Pareto front of the run, a row of objective values per score.
+/// * `report.txt` - the result of the run, its configuration and its metrics.
+/// * `best.txt` - the best individual, decoded and formatted with `Debug`.
+/// * `checkpoint.rdwf` - the path to give `GeneticEngineParams::checkpoint_every` (with the `wire`
+///   feature).
+///
+/// Given to an engine with `GeneticEngineParams::artifacts`, the metrics are written as the engine
+/// runs and the rest when it stops. Like the `MetricHistory`, a `RunArtifacts` is cheap to clone and
+/// all clones share the same metrics file. If the metrics can't be written, the first error is kept and
+/// returned by `write_result` - which the engine reports as an `EngineEvent::Error` when it stops.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let root = std::env::temp_dir().join("radiate-artifacts-doc");
+/// let artifacts = RunArtifacts::create(&root, "min-sum").unwrap();
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+///     .minimizing()
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .artifacts(artifacts.clone())
+///     .build();
+///
+/// engine.run(|ctx| ctx.index >= 20);
+///
+/// assert!(artifacts.report().exists());
+/// let metrics = std::fs::read_to_string(artifacts.metrics()).unwrap();
+/// assert!(metrics.starts_with("generation,metric,value"));
+/// # std::fs::remove_dir_all(&root).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct RunArtifacts {
+    dir: PathBuf,
+    metrics: Arc<Mutex<Option<BufWriter<File>>>>,
+    failure: Arc<Mutex<Option<std::io::Error>>>,
+}
+
+impl RunArtifacts {
+    /// Create the directory of a new run of the experiment `name` under `root`, creating `root` if it
+    /// doesn't exist. Runs started within the same second get a `-1`, `-2`, ... suffix.
+    pub fn create(root: impl AsRef<Path>, name: &str) -> Result<Self> {
+        let root = root.as_ref();
+        std::fs::create_dir_all(root)?;
+
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let stem = format!("{}-{}", name, timestamp(seconds));
+
+        let mut attempt = 0;
+        loop {
+            let dir = match attempt {
+                0 => root.join(&stem),
+                n => root.join(format!("{}-{}", stem, n)),
+            };
+
+            match std::fs::create_dir(&dir) {
+                Ok(()) => return Ok(RunArtifacts::open(dir)),
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => attempt += 1,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Use an existing directory - e.g. to keep writing into the directory of a resumed run.
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        RunArtifacts {
+            dir: dir.into(),
+            metrics: Arc::new(Mutex::new(None)),
+            failure: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path of any other file of the run, e.g. a plot.
+    pub fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    pub fn checkpoint(&self) -> PathBuf {
+        self.path(CHECKPOINT_FILE)
+    }
+
+    pub fn metrics(&self) -> PathBuf {
+        self.path(METRICS_FILE)
+    }
+
+    pub fn front(&self) -> PathBuf {
+        self.path(FRONT_FILE)
+    }
+
+    pub fn report(&self) -> PathBuf {
+        self.path(REPORT_FILE)
+    }
+
+    pub fn best(&self) -> PathBuf {
+        self.path(BEST_FILE)
+    }
+
+    /// Append a row per metric of the given generation to `metrics.csv`, creating it with a header
+    /// first if needed.
+    pub fn write_metrics(&self, generation: i32, metrics: &super::MetricSet) -> Result<()> {
+        let mut writer = self.metrics.lock().unwrap();
+        if writer.is_none() {
+            let path = self.metrics();
+            let is_new = !path.exists();
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+
+            let mut file = BufWriter::new(file);
+            if is_new {
+                writeln!(file, "generation,metric,value")?;
+            }
+
+            *writer = Some(file);
+        }
+
+        let writer = writer.as_mut().unwrap();
+        for name in metrics.names() {
+            if let Some(value) = metrics.get(name).and_then(reading) {
+                writeln!(writer, "{},{},{}", generation, csv_field(name), value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the result of a run - `front.csv`, `report.txt` and `best.txt` - and flush `metrics.csv`.
+    /// Fails with the first error writing the metrics as a subscriber ran into, if any.
+    pub fn write_result<C, T>(&self, ctx: &EngineContext<C, T>) -> Result<()>
+    where
+        C: Chromosome,
+        T: Debug,
+    {
+        if let Some(writer) = self.metrics.lock().unwrap().as_mut() {
+            writer.flush()?;
+        }
+
+        let front = ctx
+            .front
+            .lock()
+            .unwrap()
+            .scores()
+            .iter()
+            .map(|score| {
+                score
+                    .values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect::<Vec<String>>();
+        let objectives = ctx.score.as_ref().map_or(1, |score| score.values.len());
+        let header = (0..objectives)
+            .map(|i| format!("objective_{}", i))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let rows = std::iter::once(header)
+            .chain(front)
+            .map(|row| row + "\n")
+            .collect::<String>();
+
+        std::fs::write(self.front(), rows)?;
+        std::fs::write(self.report(), format!("{:?}\n", ctx))?;
+        std::fs::write(self.best(), format!("{:#?}\n", ctx.best))?;
+
+        match self.failure.lock().unwrap().take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> Subscriber<T> for RunArtifacts {
+    fn on_event(&self, event: &EngineEvent<T>) {
+        if let EngineEvent::EpochComplete { index, metrics, .. } = event {
+            if let Err(error) = self.write_metrics(*index, metrics) {
+                self.failure.lock().unwrap().get_or_insert(error);
+            }
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+/// `YYYYMMDD-HHMMSS` of a UNIX time, in UTC.
+fn timestamp(seconds: u64) -> String {
+    let (days, time) = (seconds / 86_400, seconds % 86_400);
+
+    // days to the civil date, from Howard Hinnant's `civil_from_days`
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0), "19700101-000000");
+        assert_eq!(timestamp(951_782_400 + 3_723), "20000229-010203");
+        assert_eq!(timestamp(1_790_000_000), "20260921-141320");
+    }
+
+    #[test]
+    fn test_runs_get_their_own_directories() {
+        let root = std::env::temp_dir().join("radiate-artifacts-test");
+        let first = RunArtifacts::create(&root, "run").unwrap();
+        let second = RunArtifacts::create(&root, "run").unwrap();
+
+        assert_ne!(first.dir(), second.dir());
+        assert!(first.dir().is_dir() && second.dir().is_dir());
+        assert_eq!(first.best(), first.dir().join(BEST_FILE));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use super::{Chromosome, EngineContext, GeneticEngine, Genotype, Population, Score};
+
+/// An ask/tell session with a `GeneticEngine`, created with `GeneticEngine::ask_tell`, for evaluation
+/// loops owned by another system - a lab, a hardware-in-the-loop rig or another optimizer. Instead of
+/// calling the fitness function, the engine hands out candidates with `ask` and takes their scores
+/// back with `tell`:
+///
+/// * `ask(n)` returns `n` genotypes to evaluate. The first asks hand out the engine's initial
+///   population; after that the candidates are offspring of the scored population, produced by the
+///   offspring selector and the alterers like the offspring of a generation.
+/// * `tell(scored)` adds the scored genotypes to the population, keeps the best `population_size`
+///   individuals and completes a generation - the best individual, the metrics, the subscribers and
+///   everything else the engine audits are updated as in `run`.
+///
+/// Any genotype can be told, not only those that were asked for, so candidates from other sources can
+/// be mixed in. The engine's fitness function isn't called during a session.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .population_size(20)
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let mut session = engine.ask_tell();
+/// for _ in 0..30 {
+///     let scored = session
+///         .ask(10)
+///         .into_iter()
+///         .map(|genotype| {
+///             // Measured outside of the engine.
+///             let sum = genotype[0].iter().map(|gene| *gene.allele()).sum::<i32>();
+///             (genotype, Score::from_int(sum))
+///         })
+///         .collect();
+///
+///     session.tell(scored);
+/// }
+///
+/// assert_eq!(session.context().index, 30);
+/// assert!(session.context().score().as_i32() < 100);
+/// ```
+pub struct AskTell<'a, C, T>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    engine: &'a GeneticEngine<C, T>,
+    context: EngineContext<C, T>,
+    initial: Vec<Genotype<C>>,
+}
+
+impl<'a, C, T> AskTell<'a, C, T>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    pub fn new(engine: &'a GeneticEngine<C, T>) -> Self {
+        let mut context = engine.start();
+        let (scored, unscored) =
+            std::mem::replace(&mut context.population, Population::new(Vec::new()))
+                .into_iter()
+                .partition::<Vec<_>, _>(|individual| !individual.is_dirty());
+
+        // A session resumed from a checkpoint starts from its scored population.
+        context.population = Population::new(scored);
+        let initial = unscored
+            .into_iter()
+            .map(|mut individual| individual.take_genotype())
+            .rev()
+            .collect();
+
+        AskTell {
+            engine,
+            context,
+            initial,
+        }
+    }
+
+    /// The next `count` genotypes to evaluate.
+    pub fn ask(&mut self, count: usize) -> Vec<Genotype<C>> {
+        let initial = count.min(self.initial.len());
+        let mut genotypes = (0..initial)
+            .filter_map(|_| self.initial.pop())
+            .collect::<Vec<Genotype<C>>>();
+
+        genotypes.extend(self.engine.breed(&mut self.context, count - initial));
+        genotypes
+    }
+
+    /// Feed the scores of evaluated genotypes back and complete a generation. Nothing happens if there
+    /// are no scores.
+    pub fn tell(&mut self, scored: Vec<(Genotype<C>, Score)>) {
+        self.engine.absorb(&mut self.context, scored);
+    }
+
+    /// The number of genotypes of the initial population that haven't been asked for yet.
+    pub fn remaining_initial(&self) -> usize {
+        self.initial.len()
+    }
+
+    /// The state of the session - the population holds the scored individuals, and `best`, `score`
+    /// and `metrics` are available once something has been told.
+    pub fn context(&self) -> &EngineContext<C, T> {
+        &self.context
+    }
+
+    pub fn into_context(self) -> EngineContext<C, T> {
+        self.context
+    }
+}
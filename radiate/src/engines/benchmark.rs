@@ -0,0 +1,365 @@
+use std::sync::Arc;
+
+use super::objectives::{Objective, Optimize, Score};
+use super::selectors::Select;
+use super::{random_provider, Chromosome, Genotype, Phenotype, Population, Problem};
+
+/// A synthetic fitness landscape of a `SelectorBenchmark` - the distribution the scores of its
+/// population are drawn from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Landscape {
+    /// Scores spread evenly between 0 and 1.
+    Uniform,
+    /// Normally distributed scores.
+    Normal,
+    /// Most scores are low and a few are outstanding, as early in many runs.
+    Skewed,
+    /// Only four distinct scores, so most individuals tie, as on a plateau.
+    Plateau,
+}
+
+impl Landscape {
+    /// The landscapes a `SelectorBenchmark` runs on by default.
+    pub fn all() -> Vec<Landscape> {
+        vec![
+            Landscape::Uniform,
+            Landscape::Normal,
+            Landscape::Skewed,
+            Landscape::Plateau,
+        ]
+    }
+
+    /// Draw the quality of an individual - higher is better, whatever the objective.
+    fn sample(&self) -> f32 {
+        match self {
+            Landscape::Uniform => random_provider::random::<f32>(),
+            Landscape::Normal => random_provider::gaussian(0.0, 1.0) as f32,
+            Landscape::Skewed => random_provider::random::<f32>().powi(4),
+            Landscape::Plateau => (random_provider::random::<f32>() * 4.0).floor(),
+        }
+    }
+}
+
+/// An opt-in benchmark of selectors, run when the engine is built, to choose a selector by its
+/// measured behavior instead of by folklore. Every selector is run on a suite of synthetic
+/// `Landscape`s: a population of individuals encoded by the codex is given scores drawn from the
+/// landscape, then selected from - without any variation - for a few rounds. For every selector and
+/// landscape the benchmark reports:
+///
+/// * intensity - the standardized selection differential of the first round: how many standard
+///   deviations the mean score of the selected individuals is better than the mean of the population.
+/// * diversity loss - the fraction of the population that wasn't selected in the first round.
+/// * takeover - the round by which every selected individual is a copy of the best one, if it happens
+///   within the budget.
+///
+/// The scores are synthetic, so the benchmark costs no fitness evaluations - its budget is `rounds`
+/// selections from `population_size` individuals per selector and landscape. The engine's survivor and
+/// offspring selectors are always benchmarked, and other `candidate`s can be added to compare against.
+/// The results are available from `GeneticEngine::selector_benchmark`, and the mean intensity and
+/// diversity loss of the engine's selectors are recorded in the metrics.
+///
+/// With several objectives, every objective gets the same score, so the intensity reflects the
+/// selector's pressure towards the Pareto front.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .survivor_selector(EliteSelector::new())
+///     .offspring_selector(TournamentSelector::new(3))
+///     .benchmark_selectors(SelectorBenchmark::new().candidate(RandomSelector::new()))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let benchmark = engine.selector_benchmark().unwrap();
+/// let tournament = benchmark.intensity("offspring").unwrap();
+/// let random = benchmark.intensity("candidate").unwrap();
+///
+/// assert!(tournament > random);
+/// ```
+pub struct SelectorBenchmark<C: Chromosome> {
+    landscapes: Vec<Landscape>,
+    rounds: usize,
+    population_size: Option<usize>,
+    candidates: Vec<Box<dyn Select<C>>>,
+}
+
+impl<C: Chromosome + 'static> SelectorBenchmark<C> {
+    /// Create a benchmark of the engine's selectors on every `Landscape`, for 10 rounds on a population
+    /// of the engine's size.
+    pub fn new() -> Self {
+        SelectorBenchmark {
+            landscapes: Landscape::all(),
+            rounds: 10,
+            population_size: None,
+            candidates: Vec::new(),
+        }
+    }
+
+    /// The landscapes to run on. Panics if there are none.
+    pub fn landscapes(mut self, landscapes: Vec<Landscape>) -> Self {
+        if landscapes.is_empty() {
+            panic!("landscapes must not be empty");
+        }
+
+        self.landscapes = landscapes;
+        self
+    }
+
+    /// The number of rounds of selection per selector and landscape. Panics if `rounds` is 0.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        if rounds < 1 {
+            panic!("rounds must be greater than 0");
+        }
+
+        self.rounds = rounds;
+        self
+    }
+
+    /// The size of the synthetic populations. Default is the population size of the engine. Panics if
+    /// `population_size` is less than 2.
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        if population_size < 2 {
+            panic!("population_size must be greater than 1");
+        }
+
+        self.population_size = Some(population_size);
+        self
+    }
+
+    /// Also benchmark `selector`, which isn't used by the engine. Its trials are reported with the role
+    /// `candidate`.
+    pub fn candidate<S: Select<C> + 'static>(mut self, selector: S) -> Self {
+        self.candidates.push(Box::new(selector));
+        self
+    }
+
+    /// Benchmark the engine's selectors and the candidates on populations encoded by the `problem`.
+    pub(crate) fn run<T>(
+        &self,
+        problem: &Arc<Box<dyn Problem<C, T>>>,
+        survivor_selector: &dyn Select<C>,
+        offspring_selector: &dyn Select<C>,
+        objective: &Objective,
+        population_size: usize,
+    ) -> SelectorBenchmarkResult {
+        let size = self.population_size.unwrap_or(population_size).max(2);
+        let genotypes = (0..size)
+            .map(|_| problem.encode())
+            .collect::<Vec<Genotype<C>>>();
+
+        let selectors = [
+            ("survivor", survivor_selector),
+            ("offspring", offspring_selector),
+        ]
+        .into_iter()
+        .chain(
+            self.candidates
+                .iter()
+                .map(|candidate| ("candidate", candidate.as_ref())),
+        );
+
+        let mut trials = Vec::new();
+        for (role, selector) in selectors {
+            for landscape in self.landscapes.iter() {
+                trials.push(self.trial(role, selector, *landscape, &genotypes, objective));
+            }
+        }
+
+        SelectorBenchmarkResult { trials }
+    }
+
+    fn trial(
+        &self,
+        role: &'static str,
+        selector: &dyn Select<C>,
+        landscape: Landscape,
+        genotypes: &[Genotype<C>],
+        objective: &Objective,
+    ) -> SelectorTrial {
+        let qualities = genotypes
+            .iter()
+            .map(|_| landscape.sample())
+            .collect::<Vec<f32>>();
+
+        // The generation of a synthetic individual is its index, so the selected individuals can be
+        // traced back to their quality - selectors don't look at it.
+        let mut population = genotypes
+            .iter()
+            .zip(qualities.iter())
+            .enumerate()
+            .map(|(index, (genotype, quality))| {
+                let mut individual = Phenotype::from_genotype(genotype.clone(), index as i32);
+                individual.set_score(Some(score_of(*quality, objective)));
+                individual
+            })
+            .collect::<Population<C>>();
+        objective.sort(&mut population);
+
+        let best = population[0].generation;
+        let (mean, std_dev) = mean_and_std_dev(&qualities);
+
+        let mut intensity = 0.0;
+        let mut diversity_loss = 0.0;
+        let mut takeover = None;
+        for round in 1..=self.rounds {
+            let mut selected = selector.select(&population, objective, genotypes.len());
+            if selected.is_empty() {
+                break;
+            }
+
+            if round == 1 {
+                let selected_qualities = selected
+                    .iter()
+                    .map(|individual| qualities[individual.generation as usize])
+                    .collect::<Vec<f32>>();
+                let (selected_mean, _) = mean_and_std_dev(&selected_qualities);
+
+                let mut unique = selected
+                    .iter()
+                    .map(|individual| individual.generation)
+                    .collect::<Vec<i32>>();
+                unique.sort_unstable();
+                unique.dedup();
+
+                if std_dev > 0.0 {
+                    intensity = (selected_mean - mean) / std_dev;
+                }
+                diversity_loss = 1.0 - unique.len() as f32 / genotypes.len() as f32;
+            }
+
+            if selected
+                .iter()
+                .all(|individual| individual.generation == best)
+            {
+                takeover = Some(round);
+                break;
+            }
+
+            objective.sort(&mut selected);
+            population = selected;
+        }
+
+        SelectorTrial {
+            selector: selector.name(),
+            role,
+            landscape,
+            intensity,
+            diversity_loss,
+            takeover,
+        }
+    }
+}
+
+impl<C: Chromosome + 'static> Default for SelectorBenchmark<C> {
+    fn default() -> Self {
+        SelectorBenchmark::new()
+    }
+}
+
+/// The score of an individual of the given quality - the quality for every maximized objective and its
+/// negation for every minimized one.
+fn score_of(quality: f32, objective: &Objective) -> Score {
+    let value = |optimize: &Optimize| match optimize {
+        Optimize::Maximize => quality,
+        Optimize::Minimize => -quality,
+    };
+
+    match objective {
+        Objective::Single(optimize) => Score::from_f32(value(optimize)),
+        Objective::Multi(objectives) => Score::from_vec(objectives.iter().map(value).collect()),
+    }
+}
+
+fn mean_and_std_dev(values: &[f32]) -> (f32, f32) {
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f32>()
+        / values.len() as f32;
+
+    (mean, variance.sqrt())
+}
+
+/// The outcome of a `SelectorBenchmark` - a trial for every selector and landscape.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectorBenchmarkResult {
+    pub trials: Vec<SelectorTrial>,
+}
+
+impl SelectorBenchmarkResult {
+    /// The trials of the selectors with the given role: `survivor`, `offspring` or `candidate`.
+    pub fn trials_of<'a>(&'a self, role: &'a str) -> impl Iterator<Item = &'a SelectorTrial> + 'a {
+        self.trials.iter().filter(move |trial| trial.role == role)
+    }
+
+    /// The mean intensity of the selectors with the given role over every landscape.
+    pub fn intensity(&self, role: &str) -> Option<f32> {
+        self.mean_of(role, |trial| trial.intensity)
+    }
+
+    /// The mean diversity loss of the selectors with the given role over every landscape.
+    pub fn diversity_loss(&self, role: &str) -> Option<f32> {
+        self.mean_of(role, |trial| trial.diversity_loss)
+    }
+
+    fn mean_of(&self, role: &str, value: impl Fn(&SelectorTrial) -> f32) -> Option<f32> {
+        let values = self.trials_of(role).map(value).collect::<Vec<f32>>();
+        match values.is_empty() {
+            true => None,
+            false => Some(values.iter().sum::<f32>() / values.len() as f32),
+        }
+    }
+}
+
+/// The behavior of a selector on one landscape of a `SelectorBenchmark`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectorTrial {
+    pub selector: &'static str,
+    /// `survivor` or `offspring` for the engine's selectors, `candidate` for the others.
+    pub role: &'static str,
+    pub landscape: Landscape,
+    /// The standardized selection differential of the first round of selection.
+    pub intensity: f32,
+    /// The fraction of the population that wasn't selected in the first round.
+    pub diversity_loss: f32,
+    /// The round by which every selected individual was a copy of the best, if it happened.
+    pub takeover: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EliteSelector, GeneticEngine, IntCodex, RandomSelector, TournamentSelector};
+
+    #[test]
+    fn test_benchmark_measures_selection_intensity_and_diversity_loss() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .minimizing()
+            .population_size(100)
+            .survivor_selector(EliteSelector::new())
+            .offspring_selector(RandomSelector::new())
+            .benchmark_selectors(
+                SelectorBenchmark::new()
+                    .landscapes(vec![Landscape::Uniform])
+                    .candidate(TournamentSelector::new(8)),
+            )
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.selector_benchmark().unwrap();
+        let elite = result.trials_of("survivor").next().unwrap();
+        let random = result.trials_of("offspring").next().unwrap();
+        let tournament = result.trials_of("candidate").next().unwrap();
+
+        assert_eq!(result.trials.len(), 3);
+        assert_eq!(elite.diversity_loss, 0.0);
+        assert_eq!(elite.takeover, None);
+        assert!(tournament.intensity > 0.5);
+        assert!(random.intensity.abs() < 0.5);
+        assert!(tournament.takeover.is_some());
+    }
+}
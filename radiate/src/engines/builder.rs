@@ -0,0 +1,170 @@
+use std::marker::PhantomData;
+#[cfg(feature = "wire")]
+use std::path::PathBuf;
+
+use super::codexes::Codex;
+#[cfg(feature = "wire")]
+use super::wire::WireAllele;
+#[cfg(feature = "wire")]
+use super::Gene;
+use super::{Chromosome, FitnessInput, GeneticEngine, GeneticEngineParams, Problem, Score};
+
+/// Typestate of an `EngineBuilder` that doesn't have a codex or problem yet.
+pub struct NeedsCodex;
+
+/// Typestate of an `EngineBuilder` that has a codex but no fitness function yet.
+pub struct NeedsFitness;
+
+/// Typestate of an `EngineBuilder` that can be built.
+pub struct Ready;
+
+/// A typed alternative to building a `GeneticEngine` from `GeneticEngineParams`. The builder tracks
+/// which of the required pieces have been provided in its type, so `build` only exists once the
+/// engine has either a codex and a fitness function, or a problem - forgetting one is a compile
+/// error instead of a panic when the engine is built.
+///
+/// Every other option is set on the underlying `GeneticEngineParams` with `configure`, so the
+/// typed and dynamic APIs share the same settings and defaults.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::builder()
+///     .codex(IntCodex::new(1, 5, 0, 100))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .configure(|params| params.minimizing().population_size(50))
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.score().as_i32() == 0);
+/// ```
+///
+/// Without a fitness function there is nothing to build:
+/// ``` compile_fail
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::builder()
+///     .codex(IntCodex::new(1, 5, 0, 100))
+///     .build();
+/// ```
+pub struct EngineBuilder<C, T, S>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    params: GeneticEngineParams<C, T>,
+    _state: PhantomData<S>,
+}
+
+impl<C, T, S> EngineBuilder<C, T, S>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    /// Set any of the optional parameters of the engine - population size, selectors, alterers, etc.
+    pub fn configure<F>(mut self, configure: F) -> Self
+    where
+        F: FnOnce(GeneticEngineParams<C, T>) -> GeneticEngineParams<C, T>,
+    {
+        self.params = configure(self.params);
+        self
+    }
+
+    /// Drop the typestate and continue with the dynamic `GeneticEngineParams` API.
+    pub fn into_params(self) -> GeneticEngineParams<C, T> {
+        self.params
+    }
+
+    fn transition<N>(self) -> EngineBuilder<C, T, N> {
+        EngineBuilder {
+            params: self.params,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<C, T> EngineBuilder<C, T, NeedsCodex>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    pub fn new() -> Self {
+        EngineBuilder {
+            params: GeneticEngineParams::new(),
+            _state: PhantomData,
+        }
+    }
+
+    /// Start a builder that resumes the run saved in the `Checkpoint` at `path` (see
+    /// `GeneticEngineParams::resume_from`). The codex and fitness function are still needed, and should
+    /// be the ones of the engine that wrote the checkpoint.
+    #[cfg(feature = "wire")]
+    pub fn from_checkpoint(path: impl Into<PathBuf>) -> Self
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        EngineBuilder {
+            params: GeneticEngineParams::new().resume_from(path),
+            _state: PhantomData,
+        }
+    }
+
+    /// Set the codex. A fitness function is still needed before the engine can be built.
+    pub fn codex(mut self, codex: impl Codex<C, T> + 'static) -> EngineBuilder<C, T, NeedsFitness> {
+        self.params = self.params.codex(codex);
+        self.transition()
+    }
+
+    /// Set the problem, which provides both the encoding and the fitness function.
+    pub fn problem(mut self, problem: impl Problem<C, T> + 'static) -> EngineBuilder<C, T, Ready> {
+        self.params = self.params.problem(problem);
+        self.transition()
+    }
+}
+
+impl<C, T> Default for EngineBuilder<C, T, NeedsCodex>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, T> EngineBuilder<C, T, NeedsFitness>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    pub fn fitness_fn<S: Into<Score>>(
+        mut self,
+        fitness_fn: impl Fn(T) -> S + Send + Sync + 'static,
+    ) -> EngineBuilder<C, T, Ready> {
+        self.params = self.params.fitness_fn(fitness_fn);
+        self.transition()
+    }
+
+    /// Set the fitness function, naming the type it takes (see `GeneticEngineParams::fitness_fn_for`).
+    pub fn fitness_fn_for<X, S>(
+        mut self,
+        fitness_fn: impl Fn(X) -> S + Send + Sync + 'static,
+    ) -> EngineBuilder<C, T, Ready>
+    where
+        T: FitnessInput<X>,
+        S: Into<Score>,
+    {
+        self.params = self.params.fitness_fn_for(fitness_fn);
+        self.transition()
+    }
+}
+
+impl<C, T> EngineBuilder<C, T, Ready>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    pub fn build(self) -> GeneticEngine<C, T> {
+        self.params.build()
+    }
+}
@@ -0,0 +1,318 @@
+use super::genome::wire::{self, WireAllele};
+use super::{Chromosome, Gene, Genotype, Problem, Score};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A map that holds at most `capacity` entries, evicting the least recently used entry to make room
+/// for a new one. Both `get` and `insert` count as a use.
+///
+/// # Example
+/// ``` rust
+/// use radiate::LruCache;
+///
+/// let mut cache = LruCache::new(2);
+/// cache.insert("a", 1);
+/// cache.insert("b", 2);
+/// cache.get(&"a");
+/// cache.insert("c", 3);
+///
+/// assert_eq!(cache.get(&"a"), Some(&1));
+/// assert_eq!(cache.get(&"b"), None);
+/// assert_eq!(cache.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        if capacity < 1 {
+            panic!("capacity must be greater than 0");
+        }
+
+        LruCache {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let tick = self.next_tick();
+        let (value, used) = self.entries.get_mut(key)?;
+
+        let key = self.order.remove(used).unwrap();
+        self.order.insert(tick, key);
+        *used = tick;
+
+        Some(value)
+    }
+
+    /// Insert a value, replacing the value of an existing key. Evicts the least recently used entry
+    /// if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        let tick = self.next_tick();
+        if let Some((_, used)) = self.entries.remove(&key) {
+            self.order.remove(&used);
+        } else if self.entries.len() == self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.order.insert(tick, key.clone());
+        self.entries.insert(key, (value, tick));
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+/// Encodes a genotype to the key it's cached under.
+pub(crate) type GenotypeKey<C> = Arc<dyn Fn(&Genotype<C>) -> Vec<u8> + Send + Sync>;
+
+/// A cache of the scores of the most recently evaluated genotypes (see
+/// `GeneticEngineParams::cache_fitness`). Genotypes are keyed by the bytes of their alleles (see
+/// `wire`), so an individual identical to one evaluated before gets its score without evaluating it
+/// again, and two different genotypes never share a score. Counts the lookups that hit and missed since
+/// they were last taken.
+pub struct FitnessCache<C: Chromosome> {
+    scores: Mutex<LruCache<Vec<u8>, Score>>,
+    key: GenotypeKey<C>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<C: Chromosome> FitnessCache<C> {
+    pub fn new(capacity: usize) -> Self
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        FitnessCache {
+            scores: Mutex::new(LruCache::new(capacity)),
+            key: Arc::new(|genotype: &Genotype<C>| {
+                let mut bytes = Vec::new();
+                wire::write_genotype(genotype, &mut bytes);
+                bytes
+            }),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn key(&self, genotype: &Genotype<C>) -> Vec<u8> {
+        (self.key)(genotype)
+    }
+
+    /// The cached score of the genotype with the given key, counting the lookup as a hit or a miss.
+    pub fn get(&self, key: &[u8]) -> Option<Score> {
+        let score = self.scores.lock().unwrap().get(key).cloned();
+        match score {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+
+        score
+    }
+
+    pub fn insert(&self, key: Vec<u8>, score: Score) {
+        self.scores.lock().unwrap().insert(key, score);
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of hits and misses since the last call, resetting both to zero.
+    pub fn take_counts(&self) -> (usize, usize) {
+        (
+            self.hits.swap(0, Ordering::Relaxed),
+            self.misses.swap(0, Ordering::Relaxed),
+        )
+    }
+}
+
+/// A `Problem` that looks scores up in a `FitnessCache` before evaluating, and caches the scores it
+/// evaluates. Scores evaluated in parts (see `Problem::parts`) aren't cached.
+pub(crate) struct CachedProblem<C: Chromosome, T> {
+    pub problem: Arc<Box<dyn Problem<C, T>>>,
+    pub cache: Arc<FitnessCache<C>>,
+}
+
+impl<C: Chromosome, T> Problem<C, T> for CachedProblem<C, T> {
+    fn encode(&self) -> Genotype<C> {
+        self.problem.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.problem.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.problem.encode_value(value)
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.problem.repair(genotype);
+    }
+
+    fn eval(&self, individual: &Genotype<C>) -> Score {
+        let key = self.cache.key(individual);
+        if let Some(score) = self.cache.get(&key) {
+            return score;
+        }
+
+        let score = self.problem.eval(individual);
+        self.cache.insert(key, score.clone());
+        score
+    }
+
+    fn parts(&self) -> usize {
+        self.problem.parts()
+    }
+
+    fn eval_part(&self, individual: &Genotype<C>, part: usize) -> f32 {
+        self.problem.eval_part(individual, part)
+    }
+
+    fn batch_size(&self) -> usize {
+        self.problem.batch_size()
+    }
+
+    /// Only the individuals that aren't cached are passed on to the problem, as one batch. Failed
+    /// evaluations aren't cached.
+    fn eval_batch(&self, individuals: &[Genotype<C>]) -> Vec<Result<Score, String>> {
+        let keys = individuals
+            .iter()
+            .map(|individual| self.cache.key(individual))
+            .collect::<Vec<Vec<u8>>>();
+        let mut results = keys
+            .iter()
+            .map(|key| self.cache.get(key).map(Ok))
+            .collect::<Vec<Option<Result<Score, String>>>>();
+
+        let missing = (0..individuals.len())
+            .filter(|i| results[*i].is_none())
+            .collect::<Vec<usize>>();
+
+        if !missing.is_empty() {
+            let batch = missing
+                .iter()
+                .map(|i| individuals[*i].clone())
+                .collect::<Vec<Genotype<C>>>();
+
+            let evaluated = self.problem.eval_batch(&batch);
+            for (i, result) in missing.into_iter().zip(evaluated) {
+                if let Ok(score) = &result {
+                    self.cache.insert(keys[i].clone(), score.clone());
+                }
+
+                results[i] = Some(result);
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| Err("Missing batch result".to_string())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IntChromosome, IntGene};
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let mut cache = LruCache::new(3);
+        for i in 0..3 {
+            cache.insert(i, i * 10);
+        }
+
+        cache.get(&0);
+        cache.insert(3, 30);
+        cache.insert(1, 11);
+
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some(&11));
+        assert_eq!(cache.get(&0), Some(&0));
+    }
+
+    #[test]
+    fn test_fitness_cache_counts_hits_and_misses() {
+        let genotype = |value: i32| {
+            Genotype::new(vec![IntChromosome {
+                genes: vec![IntGene::from(value)],
+            }])
+        };
+
+        let cache = FitnessCache::<IntChromosome<i32>>::new(10);
+        let (one, two) = (cache.key(&genotype(1)), cache.key(&genotype(2)));
+        assert_ne!(one, two);
+        assert_eq!(one, cache.key(&genotype(1)));
+
+        assert!(cache.get(&one).is_none());
+        cache.insert(one.clone(), Score::from_int(1));
+        assert_eq!(cache.get(&one), Some(Score::from_int(1)));
+
+        assert_eq!(cache.take_counts(), (1, 1));
+        assert_eq!(cache.take_counts(), (0, 0));
+    }
+
+    #[test]
+    fn test_fitness_cache_never_shares_scores_between_genotypes() {
+        let genotype = |values: [i32; 2]| {
+            Genotype::new(vec![IntChromosome {
+                genes: values.iter().map(|value| IntGene::from(*value)).collect(),
+            }])
+        };
+
+        let cache = FitnessCache::<IntChromosome<i32>>::new(10_000);
+        for i in -50..50 {
+            for j in -50..50 {
+                cache.insert(cache.key(&genotype([i, j])), Score::from_int(i * 100 + j));
+            }
+        }
+
+        for i in -50..50 {
+            for j in -50..50 {
+                let score = cache.get(&cache.key(&genotype([i, j])));
+                assert_eq!(score, Some(Score::from_int(i * 100 + j)));
+            }
+        }
+
+        assert_eq!(cache.len(), 10_000);
+    }
+}
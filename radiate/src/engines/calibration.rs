@@ -0,0 +1,241 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::thread_pool::{ThreadPool, WorkResult};
+use super::timer::Timer;
+use super::{Chromosome, Genotype, Problem};
+use crate::metadata;
+
+/// A warm-up phase run when the engine is built, which picks the executor configuration that
+/// evaluates fastest instead of leaving the thread count and batch size to guesswork. A sample of
+/// genotypes is encoded and evaluated under every combination of the candidate thread counts and
+/// batch sizes, the fastest combination is used for the run, and the decision is recorded in the
+/// `Calibrated Threads` and `Calibrated Batch Size` metrics.
+///
+/// Thread counts are only tuned for the engine's own workers - when the engine delegates to another
+/// executor or evaluates on dedicated threads, only the batch size is. Batch sizes are only tuned for
+/// problems evaluated one individual at a time (no `objective_fn` parts, racing or group evaluation).
+///
+/// **Note**: The sample genotypes are evaluated with the fitness function like any other, so a fitness
+/// function with side effects sees the calibration's evaluations too.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+///     .minimizing()
+///     .calibrate(Calibration::new().thread_counts(vec![1, 2]).batch_sizes(vec![1, 10]))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// assert!(result.metrics.get(metric_names::CALIBRATED_THREADS).is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Calibration {
+    thread_counts: Vec<usize>,
+    batch_sizes: Vec<usize>,
+    samples: Option<usize>,
+    rounds: usize,
+}
+
+impl Calibration {
+    /// Create a calibration trying 1 thread, every power of two up to the available parallelism and the
+    /// available parallelism itself, keeping the problem's batch size, with one population's worth of
+    /// samples and the best of 2 rounds per configuration.
+    pub fn new() -> Self {
+        let available = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1);
+
+        let mut thread_counts = (0..)
+            .map(|power| 1 << power)
+            .take_while(|count| *count < available)
+            .collect::<Vec<usize>>();
+        thread_counts.push(available);
+
+        Calibration {
+            thread_counts,
+            batch_sizes: Vec::new(),
+            samples: None,
+            rounds: 2,
+        }
+    }
+
+    /// The thread counts to try. Panics if there are none or any of them is 0.
+    pub fn thread_counts(mut self, thread_counts: Vec<usize>) -> Self {
+        if thread_counts.is_empty() || thread_counts.contains(&0) {
+            panic!("thread_counts must be greater than 0");
+        }
+
+        self.thread_counts = thread_counts;
+        self
+    }
+
+    /// The batch sizes to try. Default is only the problem's own batch size. Panics if there are none
+    /// or any of them is 0.
+    pub fn batch_sizes(mut self, batch_sizes: Vec<usize>) -> Self {
+        if batch_sizes.is_empty() || batch_sizes.contains(&0) {
+            panic!("batch_sizes must be greater than 0");
+        }
+
+        self.batch_sizes = batch_sizes;
+        self
+    }
+
+    /// The number of genotypes evaluated under every configuration. Default is the population size.
+    /// Panics if `samples` is 0.
+    pub fn samples(mut self, samples: usize) -> Self {
+        if samples < 1 {
+            panic!("samples must be greater than 0");
+        }
+
+        self.samples = Some(samples);
+        self
+    }
+
+    /// The number of times every configuration is timed - the fastest time counts. Panics if `rounds` is 0.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        if rounds < 1 {
+            panic!("rounds must be greater than 0");
+        }
+
+        self.rounds = rounds;
+        self
+    }
+
+    /// Time the candidate configurations on `samples` genotypes encoded by the `problem`. With a `fixed`
+    /// pool only its size is tried, and unless `tune_batches` only the problem's batch size is.
+    pub(crate) fn run<C: Chromosome + 'static, T: 'static>(
+        &self,
+        problem: &Arc<Box<dyn Problem<C, T>>>,
+        fixed: Option<&ThreadPool>,
+        samples: usize,
+        tune_batches: bool,
+    ) -> CalibrationResult {
+        let genotypes = (0..self.samples.unwrap_or(samples).max(1))
+            .map(|_| problem.encode())
+            .collect::<Vec<Genotype<C>>>();
+        let genotypes = Arc::new(genotypes);
+
+        let batch_sizes = match tune_batches && !self.batch_sizes.is_empty() {
+            true => self.batch_sizes.clone(),
+            false => vec![problem.batch_size()],
+        };
+
+        let mut trials = Vec::new();
+        match fixed {
+            Some(pool) => {
+                for batch_size in batch_sizes.iter() {
+                    let duration = self.time(pool, problem, &genotypes, *batch_size);
+                    trials.push(CalibrationTrial {
+                        threads: threads_of(pool),
+                        batch_size: *batch_size,
+                        duration,
+                    });
+                }
+            }
+            None => {
+                for threads in self.thread_counts.iter() {
+                    let pool = ThreadPool::new(*threads);
+                    for batch_size in batch_sizes.iter() {
+                        let duration = self.time(&pool, problem, &genotypes, *batch_size);
+                        trials.push(CalibrationTrial {
+                            threads: *threads,
+                            batch_size: *batch_size,
+                            duration,
+                        });
+                    }
+                }
+            }
+        }
+
+        let best = trials
+            .iter()
+            .fold(None::<&CalibrationTrial>, |best, trial| match best {
+                Some(best) if best.duration <= trial.duration => Some(best),
+                _ => Some(trial),
+            })
+            .map(|best| (best.threads, best.batch_size))
+            .unwrap();
+
+        CalibrationResult {
+            threads: best.0,
+            batch_size: best.1,
+            trials,
+        }
+    }
+
+    /// The fastest of `rounds` evaluations of all the genotypes, in jobs of `batch_size` genotypes -
+    /// submitted like the engine submits its evaluations.
+    fn time<C: Chromosome + 'static, T: 'static>(
+        &self,
+        pool: &ThreadPool,
+        problem: &Arc<Box<dyn Problem<C, T>>>,
+        genotypes: &Arc<Vec<Genotype<C>>>,
+        batch_size: usize,
+    ) -> Duration {
+        (0..self.rounds)
+            .map(|_| {
+                let timer = Timer::new();
+                let work_results = (0..genotypes.len())
+                    .step_by(batch_size)
+                    .map(|start| {
+                        let end = (start + batch_size).min(genotypes.len());
+                        let problem = Arc::clone(problem);
+                        let genotypes = Arc::clone(genotypes);
+                        let job = move || {
+                            problem.eval_batch(&genotypes[start..end]);
+                            metadata::take();
+                        };
+
+                        match pool.num_dedicated() {
+                            0 => pool.submit_with_result(job),
+                            _ => pool.submit_pinned(job),
+                        }
+                    })
+                    .collect::<Vec<WorkResult<()>>>();
+
+                for work_result in work_results {
+                    work_result.result();
+                }
+
+                timer.duration()
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Calibration::new()
+    }
+}
+
+/// The number of threads evaluations run on in a pool the calibration can't resize.
+fn threads_of(pool: &ThreadPool) -> usize {
+    match pool.num_dedicated() {
+        0 => pool.size(),
+        dedicated => dedicated,
+    }
+}
+
+/// The outcome of a `Calibration` - the configuration the engine runs with, and the time every
+/// configuration took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationResult {
+    /// The number of threads evaluations run on - 0 when they're delegated to another executor.
+    pub threads: usize,
+    pub batch_size: usize,
+    pub trials: Vec<CalibrationTrial>,
+}
+
+/// The fastest time a configuration took to evaluate the calibration's samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationTrial {
+    pub threads: usize,
+    pub batch_size: usize,
+    pub duration: Duration,
+}
@@ -0,0 +1,124 @@
+#[cfg(feature = "wire")]
+use super::genome::wire::{self, WireAllele};
+use super::random_provider::RngState;
+#[cfg(feature = "wire")]
+use super::Gene;
+use super::{Chromosome, EngineContext, Genotype, MetricSet, Population, Score};
+use std::io::Result;
+#[cfg(feature = "wire")]
+use std::path::Path;
+use std::sync::Arc;
+
+/// Writes a checkpoint to the path given to `GeneticEngineParams::checkpoint_every`.
+pub type CheckpointWriter<C> = Arc<dyn Fn(&Checkpoint<C>) -> Result<()> + Send + Sync>;
+
+/// Reads the checkpoint at the path given to `GeneticEngineParams::resume_from`, decoding its genes
+/// against a template genotype.
+pub type CheckpointReader<C> = Arc<dyn Fn(&Genotype<C>) -> Result<Checkpoint<C>> + Send + Sync>;
+
+/// The state of a `GeneticEngine` after a generation - enough to resume the run after a crash. The
+/// engine writes one every few generations with `GeneticEngineParams::checkpoint_every`, and a new
+/// engine picks the run up from it with `GeneticEngineParams::resume_from` (or
+/// `EngineBuilder::from_checkpoint`).
+///
+/// A checkpoint holds the population (genotypes, scores and ages), the generation index, the best score
+/// and the stagnation count, the Pareto front and the metrics. The best individual is decoded from the
+/// best of the population when the run resumes. The state of the engine's random number generator is
+/// stored as well (see `random_provider::RngState`) without drawing from it, so writing checkpoints
+/// doesn't change the run, and a resumed run draws the same numbers the original run did from that
+/// generation on. An engine that writes checkpoints without a generator of its own gets one seeded from
+/// the operating system for this. Only a `ChaCha12Rng`, like the one `seed` creates, can be stored, so a
+/// run with another generator set with `GeneticEngineParams::rng` resumes with whatever generator the
+/// resuming engine is given.
+///
+/// The state of the selectors, alterers and other components the engine is built from isn't part of the
+/// checkpoint - they start fresh when the run resumes, so the engine should be built with the same
+/// parameters as the one that wrote the checkpoint.
+///
+/// Checkpoints are written in the `wire` format (`wire::encode_checkpoint`), to a temporary file that
+/// replaces the previous checkpoint once it's complete, so a crash while writing doesn't lose it. If a
+/// checkpoint can't be written the engine keeps the previous one and carries on, publishing an
+/// `EngineEvent::Error` and counting the failure in the metrics. Writing and reading checkpoints requires
+/// the `wire` feature.
+///
+/// # Example
+/// ``` rust
+/// # #[cfg(feature = "wire")]
+/// # {
+/// use radiate::*;
+///
+/// let path = std::env::temp_dir().join("radiate-checkpoint-doc.rdwf");
+/// let params = || {
+///     GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+///         .minimizing()
+///         .seed(3)
+///         .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+/// };
+///
+/// let engine = params().checkpoint_every(10, &path).build();
+/// engine.run(|ctx| ctx.index >= 25);
+///
+/// let resumed = params().resume_from(&path).build();
+/// let result = resumed.run(|ctx| ctx.index >= 30);
+///
+/// assert_eq!(result.index, 30);
+/// # std::fs::remove_file(&path).unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Checkpoint<C: Chromosome> {
+    pub index: i32,
+    pub stagnation: i32,
+    pub score: Option<Score>,
+    /// The state of the engine's random number generator after this generation, if it can be saved.
+    pub rng: Option<RngState>,
+    pub population: Population<C>,
+    pub front: Vec<Score>,
+    pub metrics: MetricSet,
+}
+
+impl<C: Chromosome> Checkpoint<C> {
+    /// Write the checkpoint to `path` - first to a temporary file next to it, which then replaces it.
+    #[cfg(feature = "wire")]
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()>
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        let written = std::fs::write(&temporary, wire::encode_checkpoint(self))
+            .and_then(|_| std::fs::rename(&temporary, path));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temporary);
+        }
+
+        written
+    }
+
+    /// Read the checkpoint at `path`, creating its genes from the genes of `template` (see `wire`).
+    #[cfg(feature = "wire")]
+    pub fn read(path: impl AsRef<Path>, template: &Genotype<C>) -> Result<Self>
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        wire::decode_checkpoint(&std::fs::read(path)?, template)
+    }
+}
+
+/// The checkpoint of a generation, without the state of the random number generator - which the
+/// context doesn't hold.
+impl<C: Chromosome, T> From<&EngineContext<C, T>> for Checkpoint<C> {
+    fn from(context: &EngineContext<C, T>) -> Self {
+        Checkpoint {
+            index: context.index,
+            stagnation: context.stagnation,
+            score: context.score.clone(),
+            rng: None,
+            population: context.population.clone(),
+            front: context.front.lock().unwrap().scores().clone(),
+            metrics: context.metrics.clone(),
+        }
+    }
+}
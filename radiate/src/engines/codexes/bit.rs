@@ -65,6 +65,10 @@ impl Codex<BitChromosome, Vec<Vec<bool>>> for BitCodex {
             })
             .collect::<Vec<Vec<bool>>>()
     }
+
+    fn encode_value(&self, value: &Vec<Vec<bool>>) -> Option<Genotype<BitChromosome>> {
+        super::encode_alleles(self.encode(), value)
+    }
 }
 
 impl Default for BitCodex {
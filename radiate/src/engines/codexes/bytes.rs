@@ -0,0 +1,67 @@
+use crate::engines::genome::genotype::Genotype;
+use crate::{random_provider, ByteGene, BytesChromosome, SequenceChromosome};
+
+use super::Codex;
+
+/// A `Codex` for a `Genotype` with a single variable-length `BytesChromosome`. The `encode` function creates
+/// random inputs between `min_len` and `max_len` bytes long, or - if a seed corpus is given - picks a random entry
+/// from the corpus. The `decode` function returns the raw bytes.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let codex = BytesCodex::new(0, 16).with_corpus(vec![b"GET / HTTP/1.1".to_vec()]);
+/// let genotype = codex.encode();
+///
+/// assert_eq!(codex.decode(&genotype), b"GET / HTTP/1.1".to_vec());
+/// ```
+#[derive(Clone)]
+pub struct BytesCodex {
+    min_len: usize,
+    max_len: usize,
+    corpus: Vec<Vec<u8>>,
+}
+
+impl BytesCodex {
+    pub fn new(min_len: usize, max_len: usize) -> Self {
+        if min_len > max_len {
+            panic!("min_len must be less than or equal to max_len");
+        }
+
+        BytesCodex {
+            min_len,
+            max_len,
+            corpus: Vec::new(),
+        }
+    }
+
+    /// Seed the initial population from the given inputs. Inputs longer than `max_len` are truncated.
+    pub fn with_corpus(mut self, corpus: Vec<Vec<u8>>) -> Self {
+        self.corpus = corpus;
+        self
+    }
+}
+
+impl Codex<BytesChromosome, Vec<u8>> for BytesCodex {
+    fn encode(&self) -> Genotype<BytesChromosome> {
+        let chromosome = if self.corpus.is_empty() {
+            SequenceChromosome::new(ByteGene::default(), self.min_len, self.max_len)
+        } else {
+            let input = random_provider::choose(&self.corpus);
+            let input = &input[..input.len().min(self.max_len)];
+            BytesChromosome::from_bytes(input, self.min_len.min(input.len()), self.max_len)
+        };
+
+        Genotype {
+            chromosomes: vec![chromosome],
+        }
+    }
+
+    fn decode(&self, genotype: &Genotype<BytesChromosome>) -> Vec<u8> {
+        genotype
+            .iter()
+            .flat_map(|chromosome| chromosome.bytes())
+            .collect()
+    }
+}
@@ -47,4 +47,8 @@ impl Codex<CharChromosome, Vec<Vec<char>>> for CharCodex {
             })
             .collect::<Vec<Vec<char>>>()
     }
+
+    fn encode_value(&self, value: &Vec<Vec<char>>) -> Option<Genotype<CharChromosome>> {
+        super::encode_alleles(self.encode(), value)
+    }
 }
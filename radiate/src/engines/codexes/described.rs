@@ -0,0 +1,46 @@
+use super::Codex;
+use crate::{Chromosome, GeneSchema, Genotype};
+
+/// A `Codex` with a `GeneSchema` attached, created with `Codex::with_schema`. Encoding and decoding
+/// are left to the wrapped codex.
+#[derive(Clone)]
+pub struct DescribedCodex<X> {
+    codex: X,
+    schema: GeneSchema,
+}
+
+impl<X> DescribedCodex<X> {
+    pub fn new(codex: X, schema: GeneSchema) -> Self {
+        DescribedCodex { codex, schema }
+    }
+
+    pub fn inner(&self) -> &X {
+        &self.codex
+    }
+}
+
+impl<C, T, X> Codex<C, T> for DescribedCodex<X>
+where
+    C: Chromosome,
+    X: Codex<C, T>,
+{
+    fn encode(&self) -> Genotype<C> {
+        self.codex.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.codex.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.codex.encode_value(value)
+    }
+
+    fn schema(&self) -> Option<GeneSchema> {
+        Some(self.schema.clone())
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.codex.repair(genotype);
+    }
+}
@@ -1,6 +1,6 @@
 use super::Codex;
 use crate::engines::genome::float::FloatGene;
-use crate::engines::genome::gene::{BoundGene, Gene};
+use crate::engines::genome::gene::{BoundGene, BoundaryPolicy, Gene, NumericGene, Valid};
 use crate::engines::genome::genotype::Genotype;
 use crate::{Chromosome, FloatChromosome};
 
@@ -18,6 +18,7 @@ pub struct FloatCodex {
     max: f32,
     lower_bound: f32,
     upper_bound: f32,
+    boundary: BoundaryPolicy,
 }
 
 impl FloatCodex {
@@ -31,6 +32,7 @@ impl FloatCodex {
             max,
             lower_bound: min,
             upper_bound: max,
+            boundary: BoundaryPolicy::default(),
         }
     }
 
@@ -40,6 +42,71 @@ impl FloatCodex {
         self.upper_bound = upper_bound;
         self
     }
+
+    /// Set the `BoundaryPolicy` of the genes - how an allele pushed outside of the gene's valid range
+    /// by an alterer is brought back. Default is `BoundaryPolicy::Clamp`.
+    pub fn with_boundary(mut self, boundary: BoundaryPolicy) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// The shape of the decoded values as `(num_chromosomes, num_genes)` - (rows, columns).
+    pub fn shape(&self) -> (usize, usize) {
+        (self.num_chromosomes, self.num_genes)
+    }
+
+    /// Decode the `Genotype` into a single row-major `Vec<f32>` of length `rows * cols` (see `shape`)
+    /// instead of a `Vec<Vec<f32>>`. This is a single allocation and is the layout expected by
+    /// matrix types such as `ndarray::Array2::from_shape_vec` or `nalgebra::DMatrix::from_row_slice`.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let codex = FloatCodex::new(2, 3, 0.0, 1.0);
+    /// let genotype = codex.encode_from_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    ///
+    /// assert_eq!(codex.decode_flat(&genotype), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    /// assert_eq!(codex.decode(&genotype), vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    /// ```
+    pub fn decode_flat(&self, genotype: &Genotype<FloatChromosome>) -> Vec<f32> {
+        let mut values = Vec::with_capacity(self.num_chromosomes * self.num_genes);
+        for chromosome in genotype.iter() {
+            values.extend(chromosome.iter().map(|gene| gene.allele));
+        }
+
+        values
+    }
+
+    /// Encode a `Genotype` from row-major values, the inverse of `decode_flat`. The genes keep the
+    /// min, max, and bounds of this codex. Panics if `values.len()` doesn't match the codex's shape.
+    pub fn encode_from_slice(&self, values: &[f32]) -> Genotype<FloatChromosome> {
+        if values.len() != self.num_chromosomes * self.num_genes {
+            panic!(
+                "Expected {} values for shape {:?}, got {}",
+                self.num_chromosomes * self.num_genes,
+                self.shape(),
+                values.len()
+            );
+        }
+
+        Genotype {
+            chromosomes: values
+                .chunks(self.num_genes.max(1))
+                .map(|row| FloatChromosome {
+                    genes: row
+                        .iter()
+                        .map(|value| {
+                            FloatGene::new(self.min, self.max)
+                                .with_bounds(self.lower_bound, self.upper_bound)
+                                .with_boundary(self.boundary)
+                                .with_allele(value)
+                        })
+                        .collect::<Vec<FloatGene>>(),
+                })
+                .collect::<Vec<FloatChromosome>>(),
+        }
+    }
 }
 
 impl Codex<FloatChromosome, Vec<Vec<f32>>> for FloatCodex {
@@ -51,6 +118,7 @@ impl Codex<FloatChromosome, Vec<Vec<f32>>> for FloatCodex {
                         .map(|_| {
                             FloatGene::new(self.min, self.max)
                                 .with_bounds(self.lower_bound, self.upper_bound)
+                                .with_boundary(self.boundary)
                         })
                         .collect::<Vec<FloatGene>>(),
                 })
@@ -61,14 +129,25 @@ impl Codex<FloatChromosome, Vec<Vec<f32>>> for FloatCodex {
     fn decode(&self, genotype: &Genotype<FloatChromosome>) -> Vec<Vec<f32>> {
         genotype
             .iter()
-            .map(|chromosome| {
-                chromosome
-                    .iter()
-                    .map(|gene| *gene.allele())
-                    .collect::<Vec<f32>>()
-            })
+            .map(|chromosome| chromosome.view().alleles())
             .collect::<Vec<Vec<f32>>>()
     }
+
+    fn encode_value(&self, value: &Vec<Vec<f32>>) -> Option<Genotype<FloatChromosome>> {
+        super::encode_alleles(self.encode(), value)
+    }
+
+    /// Bring every gene outside of its bounds back in with its `BoundaryPolicy`.
+    fn repair(&self, genotype: &mut Genotype<FloatChromosome>) {
+        for gene in genotype
+            .iter_mut()
+            .flat_map(|chromosome| chromosome.iter_mut())
+        {
+            if !gene.is_valid() {
+                *gene = gene.bounded(&gene.allele);
+            }
+        }
+    }
 }
 
 impl Default for FloatCodex {
@@ -80,6 +159,7 @@ impl Default for FloatCodex {
             max: f32::MAX,
             lower_bound: f32::MIN,
             upper_bound: f32::MAX,
+            boundary: BoundaryPolicy::default(),
         }
     }
 }
@@ -1,6 +1,6 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use crate::{Chromosome, Codex, Genotype};
+use crate::{Chromosome, Codex, Gene, Genotype};
 
 /// A `Codex` that uses functions to encode and decode a `Genotype` to and from a type `T`.
 /// Most of the other codexes in this module are more specialized and are used to create `Genotypes` of specific types of `Chromosomes`.
@@ -39,10 +39,15 @@ use crate::{Chromosome, Codex, Genotype};
 /// # Type Parameters
 /// - `C`: The type of chromosome used in the genotype, which must implement the `Chromosome` trait.
 /// - `T`: The type that the genotype will be decoded to.
+type Encoder<C> = Arc<dyn Fn() -> Genotype<C>>;
+type Decoder<C, T> = Arc<dyn Fn(&Genotype<C>) -> T>;
+type Inverse<C, T> = Arc<dyn Fn(&T) -> Genotype<C>>;
+
 #[derive(Default, Clone)]
 pub struct FnCodex<C: Chromosome, T> {
-    encoder: Option<Arc<dyn Fn() -> Genotype<C>>>,
-    decoder: Option<Arc<dyn Fn(&Genotype<C>) -> T>>,
+    encoder: Option<Encoder<C>>,
+    decoder: Option<Decoder<C, T>>,
+    inverse: Option<Inverse<C, T>>,
 }
 
 impl<C: Chromosome, T> FnCodex<C, T> {
@@ -50,9 +55,43 @@ impl<C: Chromosome, T> FnCodex<C, T> {
         FnCodex {
             encoder: None,
             decoder: None,
+            inverse: None,
         }
     }
 
+    /// Create a codex that encodes genotypes with the shape of `prototype` - the same chromosomes with
+    /// the same number of genes - with every gene a new instance of the prototype's gene (see
+    /// `Gene::new_instance`), so bounds and other gene settings come from the prototype. Only a decoder
+    /// is still needed.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let prototype = Genotype::new(vec![FloatChromosome::new(vec![FloatGene::new(0.0, 1.0); 3])]);
+    /// let codex = FnCodex::from_prototype(prototype)
+    ///     .with_decoder(|genotype| genotype[0].iter().map(|gene| *gene.allele()).sum::<f32>());
+    ///
+    /// let genotype = codex.encode();
+    /// assert_eq!(genotype[0].len(), 3);
+    /// assert!((0.0..3.0).contains(&codex.decode(&genotype)));
+    /// ```
+    pub fn from_prototype(prototype: Genotype<C>) -> Self
+    where
+        C: 'static,
+    {
+        FnCodex::new().with_encoder(move || {
+            let mut genotype = prototype.clone();
+            for chromosome in genotype.iter_mut() {
+                for gene in chromosome.iter_mut() {
+                    *gene = gene.new_instance();
+                }
+            }
+
+            genotype
+        })
+    }
+
     pub fn with_encoder<F>(mut self, encoder: F) -> Self
     where
         F: Fn() -> Genotype<C> + 'static,
@@ -68,6 +107,54 @@ impl<C: Chromosome, T> FnCodex<C, T> {
         self.decoder = Some(Arc::new(decoder));
         self
     }
+
+    /// Set the inverse of the decoder, which encodes a decoded value back into a genotype (see
+    /// `Codex::encode_value`), so known solutions can seed the population.
+    pub fn with_inverse<F>(mut self, inverse: F) -> Self
+    where
+        F: Fn(&T) -> Genotype<C> + 'static,
+    {
+        self.inverse = Some(Arc::new(inverse));
+        self
+    }
+
+    /// Set an encoder that keeps state between calls, e.g. a counter or a cache. The engine may encode on
+    /// several threads, so the encoder is called behind a lock and only has to be `Send`.
+    pub fn with_encoder_mut<F>(self, encoder: F) -> Self
+    where
+        F: FnMut() -> Genotype<C> + Send + 'static,
+    {
+        let encoder = Mutex::new(encoder);
+        self.with_encoder(move || (encoder.lock().unwrap())())
+    }
+
+    /// Set a decoder that keeps state between calls (see `with_encoder_mut`). Decoding happens during
+    /// every evaluation, so a slow stateful decoder serializes the evaluations.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut cache = HashMap::new();
+    /// let codex = FnCodex::from_prototype(Genotype::new(vec![IntChromosome::new(vec![IntGene::from_min_max(0, 4); 2])]))
+    ///     .with_decoder_mut(move |genotype: &Genotype<IntChromosome<i32>>| {
+    ///         let key = genotype[0].iter().map(|gene| *gene.allele()).collect::<Vec<i32>>();
+    ///         let decoded = cache.len();
+    ///         *cache.entry(key).or_insert(decoded)
+    ///     });
+    ///
+    /// let genotype = codex.encode();
+    /// assert_eq!(codex.decode(&genotype), codex.decode(&genotype));
+    /// ```
+    pub fn with_decoder_mut<F>(self, decoder: F) -> Self
+    where
+        F: FnMut(&Genotype<C>) -> T + Send + 'static,
+        T: 'static,
+    {
+        let decoder = Mutex::new(decoder);
+        self.with_decoder(move |genotype| (decoder.lock().unwrap())(genotype))
+    }
 }
 
 impl<C: Chromosome, T: Clone> Codex<C, T> for FnCodex<C, T> {
@@ -84,4 +171,8 @@ impl<C: Chromosome, T: Clone> Codex<C, T> for FnCodex<C, T> {
             None => panic!("Decoder function is not set"),
         }
     }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.inverse.as_ref().map(|inverse| inverse(value))
+    }
 }
@@ -0,0 +1,600 @@
+use std::collections::BTreeMap;
+
+use crate::engines::genome::gene::Gene;
+use crate::engines::genome::genotype::Genotype;
+use crate::engines::genome::int::IntGene;
+use crate::IntChromosome;
+
+use super::Codex;
+
+const PRINTABLE: std::ops::Range<u8> = 32..127;
+
+/// A symbol in the right hand side of a `Grammar` rule.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Symbol {
+    Terminal(String),
+    NonTerminal(String),
+}
+
+impl Symbol {
+    pub fn terminal(value: impl Into<String>) -> Self {
+        Symbol::Terminal(value.into())
+    }
+
+    pub fn non_terminal(name: impl Into<String>) -> Self {
+        Symbol::NonTerminal(name.into())
+    }
+}
+
+/// A context-free grammar used to generate strings. Every rule has one or more alternatives, each
+/// a sequence of `Symbol`s. Strings are derived by walking the grammar from the `start` rule, using
+/// a sequence of choices (codons) to pick the alternative at each step - the same mapping used in
+/// grammatical evolution. Because every walk follows the grammar, every derived string is valid.
+///
+/// A `Grammar` can be built by hand or compiled from a regular expression with `Grammar::from_regex`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let grammar = Grammar::new("greeting")
+///     .rule("greeting", vec![
+///         vec![Symbol::terminal("hello "), Symbol::non_terminal("name")],
+///         vec![Symbol::terminal("hi "), Symbol::non_terminal("name")],
+///     ])
+///     .rule("name", vec![vec![Symbol::terminal("world")], vec![Symbol::terminal("radiate")]]);
+///
+/// assert_eq!(grammar.derive(&[0, 1], 10), "hello radiate");
+/// assert_eq!(grammar.derive(&[1, 0], 10), "hi world");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grammar {
+    start: String,
+    rules: BTreeMap<String, Vec<Vec<Symbol>>>,
+    depths: BTreeMap<String, usize>,
+}
+
+impl Grammar {
+    pub fn new(start: impl Into<String>) -> Self {
+        Grammar {
+            start: start.into(),
+            rules: BTreeMap::new(),
+            depths: BTreeMap::new(),
+        }
+    }
+
+    /// Add a rule with the given alternatives. Adding a rule with an existing name replaces it.
+    pub fn rule(mut self, name: impl Into<String>, alternatives: Vec<Vec<Symbol>>) -> Self {
+        if alternatives.is_empty() {
+            panic!("A grammar rule must have at least one alternative");
+        }
+
+        self.rules.insert(name.into(), alternatives);
+        self.depths = self.min_depths();
+        self
+    }
+
+    /// Compile a regular expression into a `Grammar` whose derived strings all match the expression.
+    ///
+    /// Supported syntax: literals, `.`, escapes (`\d`, `\w`, `\s` and escaped meta characters),
+    /// character classes (`[abc]`, `[a-z]`, `[^0-9]`), groups, alternation (`|`) and the quantifiers
+    /// `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}`. Anchors (`^`, `$`) are accepted and ignored. The
+    /// wildcard and negated classes draw from printable ASCII. Panics if the pattern can't be parsed.
+    pub fn from_regex(pattern: &str) -> Self {
+        let mut parser = RegexParser {
+            chars: pattern.chars().collect(),
+            position: 0,
+            grammar: Grammar::new("start"),
+            count: 0,
+        };
+
+        let start = parser.parse_alternation();
+        if parser.position != parser.chars.len() {
+            panic!(
+                "Unexpected '{}' at position {} in regex {}",
+                parser.chars[parser.position], parser.position, pattern
+            );
+        }
+
+        parser.grammar.rule("start", start)
+    }
+
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Derive a string from the grammar using the given choices. Each time a rule with more than
+    /// one alternative is expanded the next choice picks the alternative (wrapping around the choices
+    /// if they run out). Once the derivation is `max_depth` rules deep, only the alternatives that
+    /// terminate the quickest are considered, which bounds both recursion and repetition.
+    pub fn derive(&self, choices: &[u32], max_depth: usize) -> String {
+        let mut result = String::new();
+        let mut position = 0;
+        self.expand(
+            &self.start,
+            0,
+            max_depth,
+            choices,
+            &mut position,
+            &mut result,
+        );
+        result
+    }
+
+    fn expand(
+        &self,
+        name: &str,
+        depth: usize,
+        max_depth: usize,
+        choices: &[u32],
+        position: &mut usize,
+        result: &mut String,
+    ) {
+        let alternatives = match self.rules.get(name) {
+            Some(alternatives) => alternatives,
+            None => panic!("Grammar rule '{}' is not defined", name),
+        };
+
+        let costs = alternatives
+            .iter()
+            .map(|alternative| self.alternative_depth(alternative))
+            .collect::<Vec<usize>>();
+
+        let mut allowed = (0..alternatives.len())
+            .filter(|i| depth + costs[*i] <= max_depth)
+            .collect::<Vec<usize>>();
+
+        if allowed.is_empty() {
+            let min_cost = costs.iter().min().copied().unwrap_or(0);
+            allowed = (0..alternatives.len())
+                .filter(|i| costs[*i] == min_cost)
+                .collect();
+        }
+
+        let index = if allowed.len() == 1 || choices.is_empty() {
+            allowed[0]
+        } else {
+            let choice = choices[*position % choices.len()] as usize;
+            *position += 1;
+            allowed[choice % allowed.len()]
+        };
+
+        for symbol in alternatives[index].iter() {
+            match symbol {
+                Symbol::Terminal(value) => result.push_str(value),
+                Symbol::NonTerminal(name) => {
+                    self.expand(name, depth + 1, max_depth, choices, position, result)
+                }
+            }
+        }
+    }
+
+    fn alternative_depth(&self, alternative: &[Symbol]) -> usize {
+        alternative
+            .iter()
+            .map(|symbol| match symbol {
+                Symbol::Terminal(_) => 0,
+                Symbol::NonTerminal(name) => 1 + self.depths.get(name).copied().unwrap_or(0),
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The minimum number of rule expansions needed to fully terminate each rule. Rules that reference
+    /// rules which aren't defined (yet) are treated as terminating immediately.
+    fn min_depths(&self) -> BTreeMap<String, usize> {
+        let mut depths = BTreeMap::new();
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+            for (name, alternatives) in self.rules.iter() {
+                let best = alternatives
+                    .iter()
+                    .filter_map(|alternative| {
+                        alternative.iter().try_fold(0, |acc, symbol| match symbol {
+                            Symbol::Terminal(_) => Some(acc),
+                            Symbol::NonTerminal(other) if !self.rules.contains_key(other) => {
+                                Some(acc.max(1))
+                            }
+                            Symbol::NonTerminal(other) => {
+                                depths.get(other).map(|depth: &usize| acc.max(depth + 1))
+                            }
+                        })
+                    })
+                    .min();
+
+                if let Some(best) = best {
+                    if depths.get(name) != Some(&best) {
+                        depths.insert(name.clone(), best);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        depths
+    }
+}
+
+/// A `Codex` for evolving strings constrained by a `Grammar` (or a regular expression). The `Genotype` is a
+/// single `IntChromosome<u32>` of `num_codons` choices, and `decode` derives the string by walking the grammar
+/// with those choices. Since every walk follows the grammar, every decoded string is valid no matter how
+/// the choices are mutated or crossed over, so any `IntGene` alterer can be used. This makes it useful
+/// for fuzzing and generating structured test cases.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let codex = GrammarCodex::from_regex(r"[a-f]{2,4}-\d+", 32);
+/// let genotype = codex.encode();
+/// let value = codex.decode(&genotype);
+///
+/// let (letters, digits) = value.split_once('-').unwrap();
+/// assert!(letters.len() >= 2 && letters.len() <= 4);
+/// assert!(digits.chars().all(|c| c.is_ascii_digit()));
+/// ```
+#[derive(Clone)]
+pub struct GrammarCodex {
+    grammar: Grammar,
+    num_codons: usize,
+    max_depth: usize,
+}
+
+impl GrammarCodex {
+    pub fn new(grammar: Grammar, num_codons: usize) -> Self {
+        if num_codons == 0 {
+            panic!("num_codons must be greater than 0");
+        }
+
+        GrammarCodex {
+            grammar,
+            num_codons,
+            max_depth: 32,
+        }
+    }
+
+    pub fn from_regex(pattern: &str, num_codons: usize) -> Self {
+        GrammarCodex::new(Grammar::from_regex(pattern), num_codons)
+    }
+
+    /// Set the maximum derivation depth. Defaults to 32.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+}
+
+impl Codex<IntChromosome<u32>, String> for GrammarCodex {
+    fn encode(&self) -> Genotype<IntChromosome<u32>> {
+        Genotype {
+            chromosomes: vec![IntChromosome {
+                genes: (0..self.num_codons)
+                    .map(|_| IntGene::from_min_max(0, u16::MAX as u32))
+                    .collect::<Vec<IntGene<u32>>>(),
+            }],
+        }
+    }
+
+    fn decode(&self, genotype: &Genotype<IntChromosome<u32>>) -> String {
+        let choices = genotype
+            .iter()
+            .flat_map(|chromosome| chromosome.genes.iter().map(|gene| *gene.allele()))
+            .collect::<Vec<u32>>();
+
+        self.grammar.derive(&choices, self.max_depth)
+    }
+}
+
+/// A small recursive descent parser that compiles a regular expression directly into grammar rules.
+struct RegexParser {
+    chars: Vec<char>,
+    position: usize,
+    grammar: Grammar,
+    count: usize,
+}
+
+impl RegexParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let next = self.peek();
+        self.position += 1;
+        next
+    }
+
+    fn expect(&mut self, expected: char) {
+        match self.next() {
+            Some(value) if value == expected => {}
+            other => panic!(
+                "Expected '{}' at position {} in regex, found {:?}",
+                expected,
+                self.position - 1,
+                other
+            ),
+        }
+    }
+
+    fn add_rule(&mut self, alternatives: Vec<Vec<Symbol>>) -> Symbol {
+        let name = format!("_{}", self.count);
+        self.count += 1;
+        self.grammar = std::mem::replace(&mut self.grammar, Grammar::new("start"))
+            .rule(name.clone(), alternatives);
+        Symbol::NonTerminal(name)
+    }
+
+    fn chars_rule(&mut self, chars: Vec<char>) -> Symbol {
+        if chars.is_empty() {
+            panic!("Character class in regex matches no characters");
+        }
+
+        let alternatives = chars
+            .into_iter()
+            .map(|value| vec![Symbol::Terminal(value.to_string())])
+            .collect();
+        self.add_rule(alternatives)
+    }
+
+    fn parse_alternation(&mut self) -> Vec<Vec<Symbol>> {
+        let mut alternatives = vec![self.parse_sequence()];
+        while self.peek() == Some('|') {
+            self.next();
+            alternatives.push(self.parse_sequence());
+        }
+
+        alternatives
+    }
+
+    fn parse_sequence(&mut self) -> Vec<Symbol> {
+        let mut sequence = Vec::new();
+        while let Some(next) = self.peek() {
+            if next == '|' || next == ')' {
+                break;
+            }
+
+            if next == '^' || next == '$' {
+                self.next();
+                continue;
+            }
+
+            let atom = self.parse_atom();
+            sequence.push(self.parse_quantifier(atom));
+        }
+
+        sequence
+    }
+
+    fn parse_atom(&mut self) -> Symbol {
+        match self.next() {
+            Some('(') => {
+                if self.peek() == Some('?') {
+                    self.next();
+                    self.expect(':');
+                }
+
+                let alternatives = self.parse_alternation();
+                self.expect(')');
+                self.add_rule(alternatives)
+            }
+            Some('[') => {
+                let chars = self.parse_class();
+                self.chars_rule(chars)
+            }
+            Some('.') => self.chars_rule(PRINTABLE.map(char::from).collect()),
+            Some('\\') => {
+                let chars = self.parse_escape();
+                if chars.len() == 1 {
+                    Symbol::Terminal(chars[0].to_string())
+                } else {
+                    self.chars_rule(chars)
+                }
+            }
+            Some(value) if "*+?{".contains(value) => {
+                panic!(
+                    "Quantifier '{}' at position {} has nothing to repeat",
+                    value,
+                    self.position - 1
+                )
+            }
+            Some(value) => Symbol::Terminal(value.to_string()),
+            None => panic!("Unexpected end of regex"),
+        }
+    }
+
+    fn parse_escape(&mut self) -> Vec<char> {
+        match self.next() {
+            Some('d') => ('0'..='9').collect(),
+            Some('w') => ('a'..='z')
+                .chain('A'..='Z')
+                .chain('0'..='9')
+                .chain(std::iter::once('_'))
+                .collect(),
+            Some('s') => vec![' ', '\t', '\n'],
+            Some('n') => vec!['\n'],
+            Some('t') => vec!['\t'],
+            Some(value) => vec![value],
+            None => panic!("Unexpected end of regex after '\\'"),
+        }
+    }
+
+    fn parse_class(&mut self) -> Vec<char> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.next();
+        }
+
+        let mut chars = Vec::new();
+        let mut first = true;
+        loop {
+            let value = match self.next() {
+                Some(']') if !first => break,
+                Some('\\') => {
+                    chars.extend(self.parse_escape());
+                    first = false;
+                    continue;
+                }
+                Some(value) => value,
+                None => panic!("Unterminated character class in regex"),
+            };
+
+            first = false;
+            if self.peek() == Some('-') && self.chars.get(self.position + 1) != Some(&']') {
+                self.next();
+                let end = match self.next() {
+                    Some(end) => end,
+                    None => panic!("Unterminated character class in regex"),
+                };
+
+                chars.extend(value..=end);
+            } else {
+                chars.push(value);
+            }
+        }
+
+        if negated {
+            return PRINTABLE
+                .map(char::from)
+                .filter(|value| !chars.contains(value))
+                .collect();
+        }
+
+        chars.dedup();
+        chars
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.position;
+        while self.peek().is_some_and(|value| value.is_ascii_digit()) {
+            self.next();
+        }
+
+        self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    fn parse_quantifier(&mut self, atom: Symbol) -> Symbol {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.next();
+                let min = self.parse_number().unwrap_or(0);
+                let max = if self.peek() == Some(',') {
+                    self.next();
+                    self.parse_number()
+                } else {
+                    Some(min)
+                };
+
+                if max.is_some_and(|max| max < min) {
+                    panic!("Invalid repetition {{{}, {:?}}} in regex", min, max);
+                }
+
+                self.expect('}');
+                return self.repeat(atom, min, max);
+            }
+            _ => return atom,
+        };
+
+        self.next();
+        self.repeat(atom, min, max)
+    }
+
+    /// Build a rule that repeats `atom` between `min` and `max` times (unbounded if `max` is `None`).
+    fn repeat(&mut self, atom: Symbol, min: usize, max: Option<usize>) -> Symbol {
+        let tail = match max {
+            None => {
+                let name = format!("_{}", self.count);
+                self.count += 1;
+                let star = Symbol::NonTerminal(name.clone());
+                self.grammar = std::mem::replace(&mut self.grammar, Grammar::new("start"))
+                    .rule(name, vec![vec![], vec![atom.clone(), star.clone()]]);
+                vec![star]
+            }
+            Some(max) => {
+                let mut optional = Vec::new();
+                for _ in min..max {
+                    let mut alternative = vec![atom.clone()];
+                    alternative.extend(optional);
+                    optional = vec![self.add_rule(vec![vec![], alternative])];
+                }
+
+                optional
+            }
+        };
+
+        let mut sequence = vec![atom; min];
+        sequence.extend(tail);
+        self.add_rule(vec![sequence])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random_provider;
+
+    fn random_choices(len: usize) -> Vec<u32> {
+        (0..len).map(|_| random_provider::random::<u32>()).collect()
+    }
+
+    #[test]
+    fn test_regex_derivations_match() {
+        let grammar = Grammar::from_regex(r"^[a-c]{2,4}-(x|yz)\d?$");
+        for _ in 0..200 {
+            let value = grammar.derive(&random_choices(16), 32);
+            let (letters, rest) = value.split_once('-').unwrap();
+
+            assert!(letters.len() >= 2 && letters.len() <= 4);
+            assert!(letters.chars().all(|c| ('a'..='c').contains(&c)));
+            assert!(rest.starts_with('x') || rest.starts_with("yz"));
+
+            let digits = rest.trim_start_matches('x').trim_start_matches("yz");
+            assert!(digits.len() <= 1);
+            assert!(digits.chars().all(|c| c.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn test_unbounded_repetition_terminates() {
+        let grammar = Grammar::from_regex("a+b*");
+        let value = grammar.derive(&[1; 8], 8);
+
+        assert!(value.starts_with('a'));
+        assert!(value.len() <= 16);
+    }
+
+    #[test]
+    fn test_negated_class() {
+        let grammar = Grammar::from_regex("[^a-z]");
+        for _ in 0..100 {
+            let value = grammar.derive(&random_choices(1), 8);
+            assert!(!value.chars().next().unwrap().is_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_regex_panics() {
+        Grammar::from_regex("(ab");
+    }
+}
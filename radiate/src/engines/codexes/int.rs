@@ -1,6 +1,6 @@
 use rand::distributions::Standard;
 
-use crate::engines::genome::gene::{BoundGene, Gene};
+use crate::engines::genome::gene::{BoundGene, BoundaryPolicy, Gene, NumericGene, Valid};
 use crate::engines::genome::genotype::Genotype;
 use crate::engines::genome::int::IntGene;
 use crate::{Chromosome, IntChromosome, Integer};
@@ -25,6 +25,7 @@ where
     max: T,
     lower_bound: T,
     upper_bound: T,
+    boundary: BoundaryPolicy,
 }
 
 impl<T: Integer<T>> IntCodex<T>
@@ -39,6 +40,7 @@ where
             max,
             lower_bound: T::MIN,
             upper_bound: T::MAX,
+            boundary: BoundaryPolicy::default(),
         }
     }
 
@@ -47,6 +49,13 @@ where
         self.upper_bound = upper_bound;
         self
     }
+
+    /// Set the `BoundaryPolicy` of the genes - how an allele pushed outside of the gene's valid range
+    /// by an alterer is brought back. Default is `BoundaryPolicy::Clamp`.
+    pub fn with_boundary(mut self, boundary: BoundaryPolicy) -> Self {
+        self.boundary = boundary;
+        self
+    }
 }
 
 impl<T: Integer<T>> Codex<IntChromosome<T>, Vec<Vec<T>>> for IntCodex<T>
@@ -61,6 +70,7 @@ where
                         .map(|_| {
                             IntGene::from_min_max(self.min, self.max)
                                 .with_bounds(self.lower_bound, self.upper_bound)
+                                .with_boundary(self.boundary)
                         })
                         .collect::<Vec<IntGene<T>>>(),
                 })
@@ -79,6 +89,22 @@ where
             })
             .collect::<Vec<Vec<T>>>()
     }
+
+    fn encode_value(&self, value: &Vec<Vec<T>>) -> Option<Genotype<IntChromosome<T>>> {
+        super::encode_alleles(self.encode(), value)
+    }
+
+    /// Bring every gene outside of its range back in with its `BoundaryPolicy`.
+    fn repair(&self, genotype: &mut Genotype<IntChromosome<T>>) {
+        for gene in genotype
+            .iter_mut()
+            .flat_map(|chromosome| chromosome.iter_mut())
+        {
+            if !gene.is_valid() {
+                *gene = gene.bounded(gene.allele());
+            }
+        }
+    }
 }
 
 impl<T: Integer<T>> Default for IntCodex<T>
@@ -93,6 +119,7 @@ where
             max: T::MAX,
             lower_bound: T::MIN,
             upper_bound: T::MAX,
+            boundary: BoundaryPolicy::default(),
         }
     }
 }
@@ -3,21 +3,37 @@ use super::genome::phenotype::Phenotype;
 use super::genome::population::Population;
 
 pub mod bit;
+pub mod bytes;
 pub mod char;
+pub mod described;
 pub mod float;
 pub mod function;
+pub mod grammar;
 pub mod int;
 pub mod permutation;
+pub mod quantized;
+pub mod repaired;
+pub mod sequence;
+pub mod strategy;
 pub mod subset;
+pub mod token;
 
-use crate::Chromosome;
+use crate::{Chromosome, Gene, GeneSchema};
 pub use bit::BitCodex;
+pub use bytes::BytesCodex;
 pub use char::CharCodex;
+pub use described::DescribedCodex;
 pub use float::FloatCodex;
 pub use function::FnCodex;
+pub use grammar::{Grammar, GrammarCodex, Symbol};
 pub use int::IntCodex;
 pub use permutation::PermutationCodex;
+pub use quantized::QuantizedCodex;
+pub use repaired::RepairedCodex;
+pub use sequence::SequenceCodex;
+pub use strategy::StrategyCodex;
 pub use subset::SubSetCodex;
+pub use token::TokenCodex;
 
 /// The `Codex` is a core concept in Radiate, as it allows for the encoding and decoding from
 /// a `Genotype` to the type `T` (commonly called Phenotype in biology) that is being optimized.
@@ -85,11 +101,68 @@ pub use subset::SubSetCodex;
 /// - `C`: The type of the Chromosome that is being optimized - the 'problem space'.
 /// - `T`: The type of the Phenotype that is being optimized the expression of the 'problem space'.
 ///
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isn't a codex that encodes `{C}` genotypes and decodes them to `{T}`",
+    note = "the fitness function takes the type the codex decodes to - check both agree"
+)]
 pub trait Codex<C: Chromosome, T> {
     fn encode(&self) -> Genotype<C>;
 
     fn decode(&self, genotype: &Genotype<C>) -> T;
 
+    /// Encode a decoded value back into a genotype - the inverse of `decode` - so known solutions can seed
+    /// the population (see `GeneticEngineParams::seed_population`). Returns `None` if the codex can't,
+    /// which is the default.
+    fn encode_value(&self, _value: &T) -> Option<Genotype<C>> {
+        None
+    }
+
+    /// The metadata of the genes this codex encodes, if it has any (see `with_schema`).
+    fn schema(&self) -> Option<GeneSchema> {
+        None
+    }
+
+    /// Attach a `GeneSchema` describing the genes of this codex - names, units, hints - so it's
+    /// available to the engine, reports and exports.
+    fn with_schema(self, schema: GeneSchema) -> DescribedCodex<Self>
+    where
+        Self: Sized,
+    {
+        DescribedCodex::new(self, schema)
+    }
+
+    /// Bring a genotype that an alterer changed back into the valid space of this codex - e.g. clamp
+    /// values into their bounds or turn a broken permutation back into a permutation. The engine repairs
+    /// every changed individual after alteration, before invalid individuals are filtered out and before
+    /// it is decoded and evaluated, so custom alterers don't have to maintain validity themselves.
+    /// Defaults to leaving the genotype as it is.
+    fn repair(&self, _genotype: &mut Genotype<C>) {}
+
+    /// Attach a repair function to this codex, run after the codex's own `repair`.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// // Keep the values of every chromosome sorted.
+    /// let codex = FloatCodex::new(1, 5, 0.0, 1.0).with_repair(|genotype: &mut Genotype<FloatChromosome>| {
+    ///     for chromosome in genotype.iter_mut() {
+    ///         chromosome.genes.sort_by(|a, b| a.allele.total_cmp(&b.allele));
+    ///     }
+    /// });
+    ///
+    /// let mut genotype = codex.encode();
+    /// codex.repair(&mut genotype);
+    /// assert!(genotype[0].genes.windows(2).all(|pair| pair[0].allele <= pair[1].allele));
+    /// ```
+    fn with_repair<F>(self, repair: F) -> RepairedCodex<C, Self>
+    where
+        Self: Sized,
+        F: Fn(&mut Genotype<C>) + 'static,
+    {
+        RepairedCodex::new(self, repair)
+    }
+
     /// Spawn a new instance of `T` from the `Codex`. This will encode `num` new `Genotype`s and then
     /// decode it to a new instance of `T`.
     fn spawn(&self, num: usize) -> Vec<T> {
@@ -112,3 +185,27 @@ pub trait Codex<C: Chromosome, T> {
             .collect::<Population<C>>()
     }
 }
+
+/// Encode `values` - one `Vec` of alleles per chromosome - into a genotype shaped like `template`, keeping
+/// the settings of its genes. Returns `None` if the shapes don't match.
+fn encode_alleles<C: Chromosome>(
+    mut template: Genotype<C>,
+    values: &[Vec<<C::Gene as Gene>::Allele>],
+) -> Option<Genotype<C>> {
+    if template.len() != values.len()
+        || template
+            .iter()
+            .zip(values.iter())
+            .any(|(chromosome, alleles)| chromosome.len() != alleles.len())
+    {
+        return None;
+    }
+
+    for (chromosome, alleles) in template.iter_mut().zip(values.iter()) {
+        for (gene, allele) in chromosome.iter_mut().zip(alleles.iter()) {
+            *gene = gene.with_allele(allele);
+        }
+    }
+
+    Some(template)
+}
@@ -32,4 +32,24 @@ impl<A: PartialEq + Clone> Codex<PermutationChromosome<A>, Vec<A>> for Permutati
             .flat_map(|chromosome| chromosome.genes.iter().map(|gene| gene.allele().clone()))
             .collect()
     }
+
+    /// Turn every chromosome back into a permutation: the first occurrence of an index is kept and
+    /// repeated (or out of range) indexes are replaced by the missing ones, in ascending order.
+    fn repair(&self, genotype: &mut Genotype<PermutationChromosome<A>>) {
+        for chromosome in genotype.iter_mut() {
+            let mut seen = vec![false; self.alleles.len()];
+            let mut repeated = Vec::new();
+            for (position, gene) in chromosome.genes.iter().enumerate() {
+                match seen.get_mut(gene.index) {
+                    Some(seen) if !*seen => *seen = true,
+                    _ => repeated.push(position),
+                }
+            }
+
+            let missing = (0..self.alleles.len()).filter(|&index| !seen[index]);
+            for (position, index) in repeated.into_iter().zip(missing) {
+                chromosome.genes[position].index = index;
+            }
+        }
+    }
 }
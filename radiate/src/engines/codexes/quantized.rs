@@ -0,0 +1,85 @@
+use rand::distributions::Standard;
+use std::marker::PhantomData;
+
+use crate::engines::genome::genotype::Genotype;
+use crate::{Integer, QuantizedChromosome, QuantizedGene};
+
+use super::Codex;
+
+/// A `Codex` for a `Genotype` of `QuantizedGenes`. The `encode` function creates a `Genotype` with `num_chromosomes`
+/// chromosomes and `num_genes` genes per chromosome, each gene holding a random level between `min` and `max`
+/// quantized with the given `scale`. The `decode` function creates a `Vec<Vec<f32>>` of the de-quantized values -
+/// every value is an exact multiple of `scale`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Evolve i8 weights between -1.0 and 1.0.
+/// let codex = QuantizedCodex::<i8>::new(1, 4, -1.0, 1.0, 1.0 / 127.0);
+/// let genotype = codex.encode();
+/// let weights = codex.decode(&genotype);
+///
+/// assert_eq!(weights[0].len(), 4);
+/// assert!(weights[0].iter().all(|w| *w >= -1.0 && *w <= 1.0));
+/// ```
+#[derive(Clone)]
+pub struct QuantizedCodex<T: Integer<T>>
+where
+    Standard: rand::distributions::Distribution<T>,
+{
+    num_chromosomes: usize,
+    num_genes: usize,
+    min: f32,
+    max: f32,
+    scale: f32,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Integer<T>> QuantizedCodex<T>
+where
+    Standard: rand::distributions::Distribution<T>,
+{
+    pub fn new(num_chromosomes: usize, num_genes: usize, min: f32, max: f32, scale: f32) -> Self {
+        QuantizedCodex {
+            num_chromosomes,
+            num_genes,
+            min,
+            max,
+            scale,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Decode the `Genotype` into the raw quantization levels, e.g. to write them to fixed-point hardware.
+    pub fn decode_levels(&self, genotype: &Genotype<QuantizedChromosome<T>>) -> Vec<Vec<T>> {
+        genotype
+            .iter()
+            .map(|chromosome| chromosome.genes.iter().map(|gene| gene.allele).collect())
+            .collect()
+    }
+}
+
+impl<T: Integer<T>> Codex<QuantizedChromosome<T>, Vec<Vec<f32>>> for QuantizedCodex<T>
+where
+    Standard: rand::distributions::Distribution<T>,
+{
+    fn encode(&self) -> Genotype<QuantizedChromosome<T>> {
+        Genotype {
+            chromosomes: (0..self.num_chromosomes)
+                .map(|_| QuantizedChromosome {
+                    genes: (0..self.num_genes)
+                        .map(|_| QuantizedGene::from_range(self.min, self.max, self.scale))
+                        .collect::<Vec<QuantizedGene<T>>>(),
+                })
+                .collect::<Vec<QuantizedChromosome<T>>>(),
+        }
+    }
+
+    fn decode(&self, genotype: &Genotype<QuantizedChromosome<T>>) -> Vec<Vec<f32>> {
+        genotype
+            .iter()
+            .map(|chromosome| chromosome.values())
+            .collect::<Vec<Vec<f32>>>()
+    }
+}
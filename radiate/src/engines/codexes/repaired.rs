@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use super::Codex;
+use crate::{Chromosome, GeneSchema, Genotype};
+
+type Repair<C> = Arc<dyn Fn(&mut Genotype<C>)>;
+
+/// A `Codex` with a repair function attached, created with `Codex::with_repair`. The wrapped codex's
+/// own repair runs first, then the attached one.
+pub struct RepairedCodex<C: Chromosome, X> {
+    codex: X,
+    repair: Repair<C>,
+}
+
+impl<C: Chromosome, X> RepairedCodex<C, X> {
+    pub fn new<F>(codex: X, repair: F) -> Self
+    where
+        F: Fn(&mut Genotype<C>) + 'static,
+    {
+        RepairedCodex {
+            codex,
+            repair: Arc::new(repair),
+        }
+    }
+
+    pub fn inner(&self) -> &X {
+        &self.codex
+    }
+}
+
+impl<C: Chromosome, X: Clone> Clone for RepairedCodex<C, X> {
+    fn clone(&self) -> Self {
+        RepairedCodex {
+            codex: self.codex.clone(),
+            repair: Arc::clone(&self.repair),
+        }
+    }
+}
+
+impl<C, T, X> Codex<C, T> for RepairedCodex<C, X>
+where
+    C: Chromosome,
+    X: Codex<C, T>,
+{
+    fn encode(&self) -> Genotype<C> {
+        self.codex.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.codex.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.codex.encode_value(value)
+    }
+
+    fn schema(&self) -> Option<GeneSchema> {
+        self.codex.schema()
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.codex.repair(genotype);
+        (self.repair)(genotype);
+    }
+}
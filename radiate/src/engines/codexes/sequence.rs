@@ -0,0 +1,64 @@
+use crate::engines::genome::genotype::Genotype;
+use crate::{Gene, SequenceChromosome};
+
+use super::Codex;
+
+/// A `Codex` for a `Genotype` of variable-length `SequenceChromosome`s. The `encode` function creates a `Genotype`
+/// with `num_chromosomes` chromosomes, each with a random length between `min_len` and `max_len` and filled with new
+/// instances of the `template` gene. The `decode` function creates a `Vec<Vec<G::Allele>>` of the alleles in each chromosome.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let codex = SequenceCodex::new(1, CharGene::new(), 1, 8);
+/// let genotype = codex.encode();
+/// let sequences = codex.decode(&genotype);
+///
+/// assert!(sequences[0].len() >= 1 && sequences[0].len() <= 8);
+/// ```
+#[derive(Clone)]
+pub struct SequenceCodex<G: Gene> {
+    num_chromosomes: usize,
+    template: G,
+    min_len: usize,
+    max_len: usize,
+}
+
+impl<G: Gene> SequenceCodex<G> {
+    pub fn new(num_chromosomes: usize, template: G, min_len: usize, max_len: usize) -> Self {
+        SequenceCodex {
+            num_chromosomes,
+            template,
+            min_len,
+            max_len,
+        }
+    }
+}
+
+impl<G> Codex<SequenceChromosome<G>, Vec<Vec<G::Allele>>> for SequenceCodex<G>
+where
+    G: Gene,
+    G::Allele: Clone,
+{
+    fn encode(&self) -> Genotype<SequenceChromosome<G>> {
+        Genotype {
+            chromosomes: (0..self.num_chromosomes)
+                .map(|_| SequenceChromosome::new(self.template.clone(), self.min_len, self.max_len))
+                .collect::<Vec<SequenceChromosome<G>>>(),
+        }
+    }
+
+    fn decode(&self, genotype: &Genotype<SequenceChromosome<G>>) -> Vec<Vec<G::Allele>> {
+        genotype
+            .iter()
+            .map(|chromosome| {
+                chromosome
+                    .genes
+                    .iter()
+                    .map(|gene| gene.allele().clone())
+                    .collect::<Vec<G::Allele>>()
+            })
+            .collect::<Vec<Vec<G::Allele>>>()
+    }
+}
@@ -0,0 +1,119 @@
+use super::Codex;
+use crate::engines::genome::gene::{BoundGene, BoundaryPolicy, NumericGene, Valid};
+use crate::engines::genome::genotype::Genotype;
+use crate::{Chromosome, StrategyChromosome, StrategyGene};
+
+/// A `Codex` for a `Genotype` of `StrategyGene`s - real valued parameters that carry their own mutation
+/// step sizes, for self-adaptive evolution strategies. The `encode` function creates a `Genotype` with
+/// `num_chromosomes` chromosomes and `num_genes` genes per chromosome, every gene starting with the step
+/// size `sigma`. The `decode` function creates a `Vec<Vec<f32>>` of the alleles - the step sizes aren't
+/// part of the decoded value, so a fitness function written for a `FloatCodex` works unchanged.
+///
+/// The step sizes only change when the genes are mutated by the `SelfAdaptiveMutator`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(StrategyCodex::new(1, 5, -5.0, 5.0, 1.0))
+///     .minimizing()
+///     .alter(alters![SelfAdaptiveMutator::new(1.0), UniformCrossover::new(0.5)])
+///     .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 50);
+///
+/// assert!(result.score().as_f32() < 1.0);
+/// ```
+#[derive(Clone)]
+pub struct StrategyCodex {
+    num_chromosomes: usize,
+    num_genes: usize,
+    min: f32,
+    max: f32,
+    sigma: f32,
+    lower_bound: f32,
+    upper_bound: f32,
+    boundary: BoundaryPolicy,
+}
+
+impl StrategyCodex {
+    /// Create a new `StrategyCodex` with the given number of chromosomes and genes. The alleles are
+    /// generated between `min` and `max`, which are also the bounds, and every gene starts with the step
+    /// size `sigma`. Panics if `sigma` isn't greater than 0.
+    pub fn new(num_chromosomes: usize, num_genes: usize, min: f32, max: f32, sigma: f32) -> Self {
+        if sigma.is_nan() || sigma <= 0.0 {
+            panic!("sigma must be greater than 0");
+        }
+
+        StrategyCodex {
+            num_chromosomes,
+            num_genes,
+            min,
+            max,
+            sigma,
+            lower_bound: min,
+            upper_bound: max,
+            boundary: BoundaryPolicy::default(),
+        }
+    }
+
+    /// Set the bounds of the genes. The default bounds are `min` and `max`.
+    pub fn with_bounds(mut self, lower_bound: f32, upper_bound: f32) -> Self {
+        self.lower_bound = lower_bound;
+        self.upper_bound = upper_bound;
+        self
+    }
+
+    /// Set the `BoundaryPolicy` of the genes. Default is `BoundaryPolicy::Clamp`.
+    pub fn with_boundary(mut self, boundary: BoundaryPolicy) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Decode the step sizes of the `Genotype` - the same shape as `decode`.
+    pub fn decode_sigmas(&self, genotype: &Genotype<StrategyChromosome>) -> Vec<Vec<f32>> {
+        genotype
+            .iter()
+            .map(|chromosome| chromosome.sigmas())
+            .collect()
+    }
+}
+
+impl Codex<StrategyChromosome, Vec<Vec<f32>>> for StrategyCodex {
+    fn encode(&self) -> Genotype<StrategyChromosome> {
+        Genotype {
+            chromosomes: (0..self.num_chromosomes)
+                .map(|_| StrategyChromosome {
+                    genes: (0..self.num_genes)
+                        .map(|_| {
+                            StrategyGene::new(self.min, self.max)
+                                .with_sigma(self.sigma)
+                                .with_bounds(self.lower_bound, self.upper_bound)
+                                .with_boundary(self.boundary)
+                        })
+                        .collect::<Vec<StrategyGene>>(),
+                })
+                .collect::<Vec<StrategyChromosome>>(),
+        }
+    }
+
+    fn decode(&self, genotype: &Genotype<StrategyChromosome>) -> Vec<Vec<f32>> {
+        genotype
+            .iter()
+            .map(|chromosome| chromosome.iter().map(|gene| gene.allele).collect())
+            .collect::<Vec<Vec<f32>>>()
+    }
+
+    /// Bring every gene outside of its bounds back in with its `BoundaryPolicy`.
+    fn repair(&self, genotype: &mut Genotype<StrategyChromosome>) {
+        for gene in genotype
+            .iter_mut()
+            .flat_map(|chromosome| chromosome.iter_mut())
+        {
+            if !gene.is_valid() {
+                *gene = gene.bounded(&gene.allele);
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use crate::engines::genome::gene::Gene;
+use crate::engines::genome::genotype::Genotype;
+use crate::engines::genome::int::IntGene;
+use crate::SequenceChromosome;
+
+use super::Codex;
+
+type TokenMask<T> = Arc<dyn Fn(&[T], &T) -> bool + Send + Sync>;
+
+/// A `Codex` for evolving sequences of tokens from a vocabulary - e.g. the tokens of a small programming
+/// language for program synthesis. The `Genotype` is a single variable-length `SequenceChromosome` of
+/// codons, and `decode` turns it into tokens one position at a time: the mask decides which tokens of the
+/// vocabulary are legal after the tokens decoded so far, and the codon picks one of them - the same
+/// mapping `GrammarCodex` uses to pick a grammar alternative. When no token is legal the sequence ends.
+///
+/// Because every token is picked from the legal ones, every decoded sequence respects the mask no matter
+/// how the codons are altered. The alterers work at token granularity: the `UniformMutator` substitutes a
+/// token, the `InsertionMutator`, `DeletionMutator` and `DuplicationMutator` add and remove tokens, and the
+/// `AlignmentCrossover` exchanges the tails of two sequences.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// const OPERATORS: [&str; 2] = ["+", "*"];
+///
+/// let vocabulary = vec!["x", "1", "+", "*"];
+///
+/// // operands and operators alternate, starting with an operand
+/// let codex = TokenCodex::new(vocabulary, 1, 9).with_mask(|prefix: &[&str], token: &&str| {
+///     let operand_next = prefix.last().is_none_or(|last| OPERATORS.contains(last));
+///     operand_next != OPERATORS.contains(token)
+/// });
+///
+/// let tokens = codex.decode(&codex.encode());
+/// assert!(!OPERATORS.contains(&tokens[0]));
+///
+/// let genotype = codex.encode_value(&vec!["x", "*", "1"]).unwrap();
+/// assert_eq!(codex.decode(&genotype), vec!["x", "*", "1"]);
+/// ```
+#[derive(Clone)]
+pub struct TokenCodex<T> {
+    vocabulary: Vec<T>,
+    min_len: usize,
+    max_len: usize,
+    mask: Option<TokenMask<T>>,
+}
+
+impl<T: Clone + PartialEq> TokenCodex<T> {
+    /// Create a codex for sequences of between `min_len` and `max_len` codons over the `vocabulary`.
+    /// Without a mask every token is legal at every position.
+    pub fn new(vocabulary: Vec<T>, min_len: usize, max_len: usize) -> Self {
+        if vocabulary.is_empty() {
+            panic!("vocabulary must not be empty");
+        }
+
+        if min_len > max_len {
+            panic!("min_len must be less than or equal to max_len");
+        }
+
+        TokenCodex {
+            vocabulary,
+            min_len,
+            max_len,
+            mask: None,
+        }
+    }
+
+    /// Set the mask - whether a token is legal after the tokens decoded before it.
+    pub fn with_mask<F>(mut self, mask: F) -> Self
+    where
+        F: Fn(&[T], &T) -> bool + Send + Sync + 'static,
+    {
+        self.mask = Some(Arc::new(mask));
+        self
+    }
+
+    pub fn vocabulary(&self) -> &[T] {
+        &self.vocabulary
+    }
+
+    /// The indices of the vocabulary's tokens that are legal after `prefix`.
+    pub fn legal(&self, prefix: &[T]) -> Vec<usize> {
+        (0..self.vocabulary.len())
+            .filter(|i| match &self.mask {
+                Some(mask) => mask(prefix, &self.vocabulary[*i]),
+                None => true,
+            })
+            .collect()
+    }
+
+    fn codon() -> IntGene<u32> {
+        IntGene::from_min_max(0, u16::MAX as u32)
+    }
+}
+
+impl<T: Clone + PartialEq> Codex<SequenceChromosome<IntGene<u32>>, Vec<T>> for TokenCodex<T> {
+    fn encode(&self) -> Genotype<SequenceChromosome<IntGene<u32>>> {
+        Genotype::new(vec![SequenceChromosome::new(
+            Self::codon(),
+            self.min_len,
+            self.max_len,
+        )])
+    }
+
+    fn decode(&self, genotype: &Genotype<SequenceChromosome<IntGene<u32>>>) -> Vec<T> {
+        let mut tokens = Vec::with_capacity(genotype[0].genes.len());
+        for gene in genotype[0].genes.iter() {
+            let legal = self.legal(&tokens);
+            if legal.is_empty() {
+                break;
+            }
+
+            let choice = *gene.allele() as usize % legal.len();
+            tokens.push(self.vocabulary[legal[choice]].clone());
+        }
+
+        tokens
+    }
+
+    /// Encode a sequence of tokens by picking, at every position, the codon of the token among the
+    /// legal ones. Returns `None` if a token isn't legal where it is or the sequence is too long or short.
+    fn encode_value(&self, value: &Vec<T>) -> Option<Genotype<SequenceChromosome<IntGene<u32>>>> {
+        if value.len() < self.min_len || value.len() > self.max_len {
+            return None;
+        }
+
+        let mut genes = Vec::with_capacity(value.len());
+        for (i, token) in value.iter().enumerate() {
+            let position = self
+                .legal(&value[..i])
+                .iter()
+                .position(|index| self.vocabulary[*index] == *token)?;
+
+            genes.push(Self::codon().with_allele(&(position as u32)));
+        }
+
+        let chromosome = SequenceChromosome::from_genes(Self::codon(), genes)
+            .with_len_bounds(self.min_len, self.max_len);
+
+        Some(Genotype::new(vec![chromosome]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random_provider;
+
+    #[test]
+    fn test_decoded_tokens_respect_the_mask() {
+        // balanced parentheses, at most two deep
+        let codex = TokenCodex::new(vec!['(', ')'], 0, 12).with_mask(|prefix: &[char], token| {
+            let depth = prefix
+                .iter()
+                .fold(0, |depth, c| if *c == '(' { depth + 1 } else { depth - 1 });
+
+            match token {
+                '(' => depth < 2,
+                _ => depth > 0,
+            }
+        });
+
+        for _ in 0..100 {
+            let mut genotype = codex.encode();
+            for gene in genotype[0].genes.iter_mut() {
+                *gene = gene.with_allele(&random_provider::gen_range(0..1000));
+            }
+
+            let tokens = codex.decode(&genotype);
+            let mut depth = 0;
+            for token in tokens {
+                depth += if token == '(' { 1 } else { -1 };
+                assert!((0..=2).contains(&depth));
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_value_rejects_illegal_tokens() {
+        let codex = TokenCodex::new(vec!["a", "b"], 1, 3)
+            .with_mask(|prefix: &[&str], token| prefix.last() != Some(token));
+
+        assert!(codex.encode_value(&vec!["a", "a"]).is_none());
+        assert!(codex.encode_value(&vec!["c"]).is_none());
+        assert!(codex.encode_value(&vec![]).is_none());
+
+        let genotype = codex.encode_value(&vec!["b", "a", "b"]).unwrap();
+        assert_eq!(codex.decode(&genotype), vec!["b", "a", "b"]);
+    }
+}
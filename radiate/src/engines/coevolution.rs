@@ -0,0 +1,268 @@
+use std::sync::{Arc, RwLock};
+
+use super::{
+    random_provider, EngineContext, FloatChromosome, FloatCodex, GeneticEngine,
+    GeneticEngineParams, Optimize, Score, TournamentSelector,
+};
+
+type SpeciesParams = GeneticEngineParams<FloatChromosome, Vec<Vec<f32>>>;
+type Configure = Arc<dyn Fn(SpeciesParams) -> SpeciesParams + Send + Sync>;
+type VectorFitness = Arc<dyn Fn(&[f32]) -> Score + Send + Sync>;
+
+/// The state of a `CooperativeCoevolution` after a cycle.
+#[derive(Clone, Debug)]
+pub struct CoevolutionContext {
+    /// The number of cycles run - every species evolves one generation per cycle.
+    pub index: usize,
+    /// The best full vector found - the representatives of every species, put together.
+    pub best: Vec<f32>,
+    pub score: Score,
+    /// The representative of every species - its best individual.
+    pub representatives: Vec<Vec<f32>>,
+}
+
+impl CoevolutionContext {
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+}
+
+/// Cooperative coevolution for high-dimensional problems over a vector of `dimensions` floats. The vector
+/// is split into `species` contiguous blocks of (nearly) equal size, and every block is evolved by its own
+/// `GeneticEngine` - one species - whose individuals are scored by putting them together with the
+/// representatives of the other species, the best individual of each. Small populations search a block
+/// well where a single population converges poorly on the whole vector.
+///
+/// Every cycle runs one generation of each species in turn, updating its representative right after, and
+/// scores the full vector of representatives. A species' population is evaluated again at the start of
+/// its generation, since the representatives of the other species have changed since it was scored.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let coevolution = CooperativeCoevolution::new(100, -5.0, 5.0, 10)
+///     .minimizing()
+///     .population_size(20)
+///     .fitness_fn(|x: &[f32]| x.iter().map(|v| v * v).sum::<f32>());
+///
+/// let result = coevolution.run(|ctx| ctx.index >= 30);
+///
+/// assert_eq!(result.best.len(), 100);
+/// assert_eq!(result.representatives.len(), 10);
+/// assert!(result.score().as_f32() < 100.0);
+/// ```
+pub struct CooperativeCoevolution {
+    dimensions: usize,
+    min: f32,
+    max: f32,
+    species: usize,
+    optimize: Optimize,
+    population_size: usize,
+    fitness_fn: Option<VectorFitness>,
+    configure: Option<Configure>,
+}
+
+impl CooperativeCoevolution {
+    /// Decompose a vector of `dimensions` values between `min` and `max` into `species` blocks.
+    pub fn new(dimensions: usize, min: f32, max: f32, species: usize) -> Self {
+        if species < 1 || species > dimensions {
+            panic!("species must be between 1 and the number of dimensions");
+        }
+
+        CooperativeCoevolution {
+            dimensions,
+            min,
+            max,
+            species,
+            optimize: Optimize::Maximize,
+            population_size: 100,
+            fitness_fn: None,
+            configure: None,
+        }
+    }
+
+    /// Set the fitness function of the full vector.
+    pub fn fitness_fn<S: Into<Score>>(
+        mut self,
+        fitness_fn: impl Fn(&[f32]) -> S + Send + Sync + 'static,
+    ) -> Self {
+        self.fitness_fn = Some(Arc::new(move |values| fitness_fn(values).into()));
+        self
+    }
+
+    pub fn minimizing(mut self) -> Self {
+        self.optimize = Optimize::Minimize;
+        self
+    }
+
+    /// Default is maximizing, like the `GeneticEngine`.
+    pub fn maximizing(mut self) -> Self {
+        self.optimize = Optimize::Maximize;
+        self
+    }
+
+    /// Set the population size of every species. Default is 100.
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        if population_size < 1 {
+            panic!("population_size must be greater than 0");
+        }
+
+        self.population_size = population_size;
+        self
+    }
+
+    /// Configure the engine of every species - e.g. its alterers and selectors. The codex, the fitness
+    /// function, the objective and the population size are set before the function is called, and the
+    /// offspring selector is a `TournamentSelector` - a species' scores include the rest of the vector, so
+    /// their differences are small next to their size and selectors that work on the scores themselves,
+    /// like the default `RouletteSelector`, barely tell the individuals apart.
+    pub fn configure<F>(mut self, configure: F) -> Self
+    where
+        F: Fn(SpeciesParams) -> SpeciesParams + Send + Sync + 'static,
+    {
+        self.configure = Some(Arc::new(configure));
+        self
+    }
+
+    /// The block of the vector every species evolves, as `start..end` ranges.
+    pub fn blocks(&self) -> Vec<std::ops::Range<usize>> {
+        let (size, extra) = (
+            self.dimensions / self.species,
+            self.dimensions % self.species,
+        );
+
+        let mut start = 0;
+        (0..self.species)
+            .map(|i| {
+                let end = start + size + usize::from(i < extra);
+                let block = start..end;
+                start = end;
+                block
+            })
+            .collect()
+    }
+
+    /// Run cycles until `limit` returns true, returning the state after the last one.
+    pub fn run<F>(&self, limit: F) -> CoevolutionContext
+    where
+        F: Fn(&CoevolutionContext) -> bool,
+    {
+        let fitness_fn = match &self.fitness_fn {
+            Some(fitness_fn) => Arc::clone(fitness_fn),
+            None => panic!("Fitness function not set"),
+        };
+
+        let full = (0..self.dimensions)
+            .map(|_| random_provider::gen_range(self.min..self.max))
+            .collect::<Vec<f32>>();
+        let shared = Arc::new(RwLock::new(full.clone()));
+
+        let engines = self
+            .blocks()
+            .into_iter()
+            .map(|block| {
+                (
+                    block.clone(),
+                    self.species_engine(block, &shared, &fitness_fn),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut contexts = engines
+            .iter()
+            .map(|(_, engine)| engine.start())
+            .collect::<Vec<EngineContext<FloatChromosome, Vec<Vec<f32>>>>>();
+
+        let mut context = CoevolutionContext {
+            index: 0,
+            score: fitness_fn(&full),
+            best: full,
+            representatives: Vec::new(),
+        };
+
+        loop {
+            for ((block, engine), species) in engines.iter().zip(contexts.iter_mut()) {
+                // the other species moved on, so the scores of the population are out of date
+                species.score = None;
+                species
+                    .population
+                    .iter_mut()
+                    .for_each(|individual| individual.mark_dirty());
+
+                engine.step(species);
+                shared.write().unwrap()[block.clone()].copy_from_slice(&species.best[0]);
+            }
+
+            let full = shared.read().unwrap().clone();
+            let score = fitness_fn(&full);
+            if !self.optimize.is_better(&context.score, &score) {
+                context.best = full;
+                context.score = score;
+            }
+
+            context.representatives = contexts
+                .iter()
+                .map(|species| species.best[0].clone())
+                .collect();
+            context.index += 1;
+
+            if limit(&context) {
+                return context;
+            }
+        }
+    }
+
+    fn species_engine(
+        &self,
+        block: std::ops::Range<usize>,
+        shared: &Arc<RwLock<Vec<f32>>>,
+        fitness_fn: &VectorFitness,
+    ) -> GeneticEngine<FloatChromosome, Vec<Vec<f32>>> {
+        let (shared, fitness_fn) = (Arc::clone(shared), Arc::clone(fitness_fn));
+        let mut params =
+            GeneticEngine::from_codex(FloatCodex::new(1, block.len(), self.min, self.max))
+                .population_size(self.population_size)
+                .offspring_selector(TournamentSelector::new(3))
+                .fitness_fn(move |values: Vec<Vec<f32>>| {
+                    let mut full = shared.read().unwrap().clone();
+                    full[block.clone()].copy_from_slice(&values[0]);
+                    fitness_fn(&full)
+                });
+
+        params = match self.optimize {
+            Optimize::Minimize => params.minimizing(),
+            Optimize::Maximize => params.maximizing(),
+        };
+
+        match &self.configure {
+            Some(configure) => configure(params).build(),
+            None => params.build(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_cover_the_vector() {
+        let coevolution = CooperativeCoevolution::new(10, 0.0, 1.0, 4);
+        assert_eq!(coevolution.blocks(), vec![0..3, 3..6, 6..8, 8..10]);
+    }
+
+    #[test]
+    fn test_coevolution_improves_a_separable_problem() {
+        let coevolution = CooperativeCoevolution::new(200, -5.0, 5.0, 20)
+            .minimizing()
+            .population_size(20)
+            .fitness_fn(|x: &[f32]| x.iter().map(|v| v.abs()).sum::<f32>());
+
+        let result = coevolution.run(|ctx| ctx.index >= 40);
+
+        // a random vector scores 500 on average - uniform values in [-5, 5] are 2.5 away from 0
+        assert!(result.score().as_f32() < 120.0);
+        assert_eq!(result.best.len(), 200);
+        assert_eq!(result.representatives.concat().len(), 200);
+    }
+}
@@ -0,0 +1,378 @@
+use super::{Chromosome, Genotype, Objective, Optimize, Problem, Score};
+use std::sync::Arc;
+
+/// Scores of infeasible individuals (see `ConstraintMode::Feasibility`) lie between this magnitude and
+/// twice it, on the worse side of zero. Feasible scores are assumed to be smaller in magnitude, so every
+/// feasible individual beats every infeasible one.
+const INFEASIBLE: f32 = 1e18;
+
+type Violation<T> = Arc<dyn Fn(&T) -> f32 + Send + Sync>;
+type Repair<T> = Arc<dyn Fn(&T) -> T + Send + Sync>;
+
+/// A constraint on the decoded value of an individual. The `violation` is how far the value is from
+/// satisfying the constraint - zero (or less) when it is satisfied - so the engine can tell slightly
+/// infeasible individuals from hopeless ones. Any `Fn(&T) -> f32` is a constraint.
+///
+/// Constraints are added to the engine with `GeneticEngineParams::constraint`, each with the
+/// `ConstraintMode` that decides what happens to the individuals that violate it.
+pub trait Constraint<T>: Send + Sync {
+    fn violation(&self, value: &T) -> f32;
+
+    /// Move a violating value back into the feasible space, for `ConstraintMode::Repair`. Defaults to
+    /// `None` - the value can't be repaired.
+    fn repair(&self, _value: &T) -> Option<T> {
+        None
+    }
+}
+
+impl<T, F> Constraint<T> for F
+where
+    F: Fn(&T) -> f32 + Send + Sync,
+{
+    fn violation(&self, value: &T) -> f32 {
+        self(value)
+    }
+}
+
+/// A `Constraint` made of closures - a violation and, optionally, a repair.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let budget = FnConstraint::new(|value: &Vec<Vec<f32>>| value[0].iter().sum::<f32>() - 1.0)
+///     .with_repair(|value: &Vec<Vec<f32>>| {
+///         let sum = value[0].iter().sum::<f32>();
+///         vec![value[0].iter().map(|x| x / sum).collect()]
+///     });
+///
+/// assert_eq!(budget.violation(&vec![vec![1.0, 1.0]]), 1.0);
+/// assert_eq!(budget.repair(&vec![vec![1.0, 1.0]]), Some(vec![vec![0.5, 0.5]]));
+/// ```
+pub struct FnConstraint<T> {
+    violation: Violation<T>,
+    repair: Option<Repair<T>>,
+}
+
+impl<T> FnConstraint<T> {
+    pub fn new(violation: impl Fn(&T) -> f32 + Send + Sync + 'static) -> Self {
+        FnConstraint {
+            violation: Arc::new(violation),
+            repair: None,
+        }
+    }
+
+    pub fn with_repair(mut self, repair: impl Fn(&T) -> T + Send + Sync + 'static) -> Self {
+        self.repair = Some(Arc::new(repair));
+        self
+    }
+}
+
+impl<T> Constraint<T> for FnConstraint<T> {
+    fn violation(&self, value: &T) -> f32 {
+        (self.violation)(value)
+    }
+
+    fn repair(&self, value: &T) -> Option<T> {
+        self.repair.as_ref().map(|repair| repair(value))
+    }
+}
+
+/// What the engine does with the individuals that violate a `Constraint`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ConstraintMode {
+    /// Worsen every value of the score by the violation times the weight. A soft constraint - an
+    /// infeasible individual with a good enough score can still beat a feasible one.
+    Penalty(f32),
+    /// Repair the individuals the alterers change with `Constraint::repair`, re-encoding the repaired
+    /// value with the codex (see `Codex::encode_value`). Individuals that stay infeasible are ranked
+    /// like with `Feasibility`.
+    Repair,
+    /// Give violating individuals the worst possible score without evaluating them.
+    DeathPenalty,
+    /// Feasible individuals always beat infeasible ones, and infeasible ones are ranked by their total
+    /// violation - the smaller the better - regardless of their raw score.
+    #[default]
+    Feasibility,
+}
+
+impl ConstraintMode {
+    /// A short label for the mode, e.g. `penalty(10)` or `feasibility`.
+    pub fn label(&self) -> String {
+        match self {
+            ConstraintMode::Penalty(weight) => format!("penalty({})", weight),
+            ConstraintMode::Repair => "repair".to_string(),
+            ConstraintMode::DeathPenalty => "death_penalty".to_string(),
+            ConstraintMode::Feasibility => "feasibility".to_string(),
+        }
+    }
+}
+
+/// A constraint along with its mode, as kept by the `GeneticEngineParams`.
+pub type ConstraintEntry<T> = (Arc<dyn Constraint<T>>, ConstraintMode);
+
+/// The violations of one individual, summed by how they affect its score.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Violations {
+    penalty: f32,
+    infeasible: f32,
+    dead: bool,
+}
+
+/// A `Problem` whose scores take its constraints into account (see `ConstraintMode`). Scores of
+/// infeasible individuals are moved past every feasible score, on every objective, so feasibility
+/// is respected by any selector and the best individual of a run is feasible whenever one was found.
+pub(crate) struct ConstraintProblem<C: Chromosome, T> {
+    pub problem: Arc<Box<dyn Problem<C, T>>>,
+    pub constraints: Vec<ConstraintEntry<T>>,
+    pub objective: Objective,
+}
+
+impl<C: Chromosome, T> ConstraintProblem<C, T> {
+    fn violations(&self, individual: &Genotype<C>) -> Violations {
+        let value = self.problem.decode(individual);
+        let mut violations = Violations::default();
+
+        for (constraint, mode) in self.constraints.iter() {
+            let violation = constraint.violation(&value).max(0.0);
+            if violation == 0.0 {
+                continue;
+            }
+
+            match mode {
+                ConstraintMode::Penalty(weight) => violations.penalty += weight * violation,
+                ConstraintMode::DeathPenalty => violations.dead = true,
+                ConstraintMode::Repair | ConstraintMode::Feasibility => {
+                    violations.infeasible += violation
+                }
+            }
+        }
+
+        violations
+    }
+
+    fn constrain(&self, value: f32, optimize: Optimize, violations: &Violations) -> f32 {
+        if violations.dead {
+            return optimize.worst_value();
+        }
+
+        if violations.infeasible > 0.0 {
+            let infeasible = violations.infeasible;
+            let magnitude = INFEASIBLE * (1.0 + infeasible / (1.0 + infeasible));
+            return match optimize {
+                Optimize::Minimize => magnitude,
+                Optimize::Maximize => -magnitude,
+            };
+        }
+
+        match optimize {
+            Optimize::Minimize => value + violations.penalty,
+            Optimize::Maximize => value - violations.penalty,
+        }
+    }
+
+    fn constrain_score(&self, score: Score, violations: &Violations) -> Score {
+        if *violations == Violations::default() {
+            return score;
+        }
+
+        let objectives = self.objective.as_ref();
+        let values = score
+            .values
+            .iter()
+            .zip(objectives.iter().cycle())
+            .map(|(value, optimize)| self.constrain(*value, *optimize, violations))
+            .collect();
+
+        Score::from_vec(values)
+    }
+
+    fn optimize(&self, part: usize) -> Optimize {
+        let objectives = self.objective.as_ref();
+        objectives[part % objectives.len()]
+    }
+}
+
+impl<C: Chromosome, T> Problem<C, T> for ConstraintProblem<C, T> {
+    fn encode(&self) -> Genotype<C> {
+        self.problem.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.problem.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.problem.encode_value(value)
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.problem.repair(genotype);
+
+        let repairs = self
+            .constraints
+            .iter()
+            .filter(|(_, mode)| *mode == ConstraintMode::Repair)
+            .map(|(constraint, _)| constraint);
+
+        let mut value = None;
+        for constraint in repairs {
+            let current = value.get_or_insert_with(|| self.problem.decode(genotype));
+            if constraint.violation(current) <= 0.0 {
+                continue;
+            }
+
+            if let Some(repaired) = constraint.repair(current) {
+                *current = repaired;
+                if let Some(encoded) = self.problem.encode_value(current) {
+                    *genotype = encoded;
+                }
+            }
+        }
+    }
+
+    fn eval(&self, individual: &Genotype<C>) -> Score {
+        let violations = self.violations(individual);
+        if violations.dead {
+            return self.objective.worst_score();
+        }
+
+        self.constrain_score(self.problem.eval(individual), &violations)
+    }
+
+    fn parts(&self) -> usize {
+        self.problem.parts()
+    }
+
+    fn eval_part(&self, individual: &Genotype<C>, part: usize) -> f32 {
+        let violations = self.violations(individual);
+        let optimize = self.optimize(part);
+        match violations.dead {
+            true => optimize.worst_value(),
+            false => self.constrain(
+                self.problem.eval_part(individual, part),
+                optimize,
+                &violations,
+            ),
+        }
+    }
+
+    fn batch_size(&self) -> usize {
+        self.problem.batch_size()
+    }
+
+    fn eval_batch(&self, individuals: &[Genotype<C>]) -> Vec<Result<Score, String>> {
+        self.problem
+            .eval_batch(individuals)
+            .into_iter()
+            .zip(individuals.iter())
+            .map(|(result, individual)| {
+                result.map(|score| self.constrain_score(score, &self.violations(individual)))
+            })
+            .collect()
+    }
+}
+
+/// Whether a score was moved past the feasible scores by a hard constraint (see `ConstraintMode`).
+pub(crate) fn is_infeasible(score: &Score) -> bool {
+    score
+        .values
+        .first()
+        .is_some_and(|value| value.abs() >= INFEASIBLE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Codex, FloatChromosome, FloatCodex};
+
+    /// Scores a genotype of floats with its first gene.
+    struct First(FloatCodex);
+
+    impl Problem<FloatChromosome, Vec<Vec<f32>>> for First {
+        fn encode(&self) -> Genotype<FloatChromosome> {
+            self.0.encode()
+        }
+
+        fn decode(&self, genotype: &Genotype<FloatChromosome>) -> Vec<Vec<f32>> {
+            self.0.decode(genotype)
+        }
+
+        fn encode_value(&self, value: &Vec<Vec<f32>>) -> Option<Genotype<FloatChromosome>> {
+            self.0.encode_value(value)
+        }
+
+        fn eval(&self, individual: &Genotype<FloatChromosome>) -> Score {
+            Score::from_f32(self.decode(individual)[0][0])
+        }
+    }
+
+    fn problem(
+        constraints: Vec<ConstraintEntry<Vec<Vec<f32>>>>,
+        objective: Objective,
+    ) -> ConstraintProblem<FloatChromosome, Vec<Vec<f32>>> {
+        ConstraintProblem {
+            problem: Arc::new(Box::new(First(FloatCodex::new(1, 2, 0.0, 1.0)))),
+            constraints,
+            objective,
+        }
+    }
+
+    fn genotype(values: &[f32]) -> Genotype<FloatChromosome> {
+        Genotype::new(vec![FloatChromosome::from(values)])
+    }
+
+    #[test]
+    fn test_feasible_beats_infeasible() {
+        let at_most_half: Arc<dyn Constraint<Vec<Vec<f32>>>> =
+            Arc::new(|value: &Vec<Vec<f32>>| value[0][1] - 0.5);
+        let objective = Objective::Single(Optimize::Maximize);
+        let problem = problem(
+            vec![(at_most_half, ConstraintMode::Feasibility)],
+            objective.clone(),
+        );
+
+        let feasible = problem.eval(&genotype(&[0.1, 0.5]));
+        let slightly = problem.eval(&genotype(&[0.9, 0.6]));
+        let badly = problem.eval(&genotype(&[0.9, 1.0]));
+
+        assert_eq!(feasible.as_f32(), 0.1);
+        assert!(objective.is_better(&feasible, &slightly));
+        assert!(objective.is_better(&slightly, &badly));
+        assert!(!is_infeasible(&feasible));
+        assert!(is_infeasible(&slightly));
+    }
+
+    #[test]
+    fn test_penalty_and_death_penalty() {
+        let positive: Arc<dyn Constraint<Vec<Vec<f32>>>> =
+            Arc::new(|value: &Vec<Vec<f32>>| value[0][1] - 0.5);
+        let penalized = problem(
+            vec![(positive.clone(), ConstraintMode::Penalty(2.0))],
+            Objective::Single(Optimize::Minimize),
+        );
+        let dead = problem(
+            vec![(positive, ConstraintMode::DeathPenalty)],
+            Objective::Single(Optimize::Minimize),
+        );
+
+        assert_eq!(penalized.eval(&genotype(&[0.5, 0.75])).as_f32(), 1.0);
+        assert_eq!(penalized.eval(&genotype(&[0.5, 0.25])).as_f32(), 0.5);
+        assert_eq!(dead.eval(&genotype(&[0.5, 0.75])).as_f32(), f32::MAX);
+    }
+
+    #[test]
+    fn test_repair_re_encodes_the_value() {
+        let capped = FnConstraint::new(|value: &Vec<Vec<f32>>| value[0][1] - 0.5)
+            .with_repair(|value: &Vec<Vec<f32>>| vec![vec![value[0][0], 0.5]]);
+        let problem = problem(
+            vec![(Arc::new(capped), ConstraintMode::Repair)],
+            Objective::Single(Optimize::Maximize),
+        );
+
+        let mut individual = genotype(&[0.25, 0.75]);
+        problem.repair(&mut individual);
+
+        assert_eq!(problem.decode(&individual), vec![vec![0.25, 0.5]]);
+    }
+}
@@ -1,5 +1,7 @@
 use super::objectives::Score;
-use super::MetricSet;
+use super::{
+    Description, EliteArchive, GeneSchema, MetricSet, PopulationSnapshot, Recording, Speciation,
+};
 use crate::engines::domain::timer::Timer;
 use crate::engines::genome::population::Population;
 use crate::objectives::Front;
@@ -16,7 +18,14 @@ use std::time::Duration;
 /// * timer - the duration of time the engine has been running
 /// * metrics - a set of metrics that are collected during the run
 /// * current best score - the score of the current best individual
+/// * stagnation - the number of generations since the best score last improved
 /// * front - the current pareto front of the population (if multi-objective)
+/// * recording - the recording of the last generation's best individual (if a recorder is set)
+/// * snapshot - the per gene mean and variance of the last generation's population (if population movement is tracked)
+/// * schema - the names and other metadata of the genes (if the codex has a `GeneSchema`)
+/// * elites - the best individuals ever seen (if an elite archive is kept)
+/// * species - the species of the population (if the population is speciated)
+/// * configuration - the description of the engine's parameters, selectors and alterers
 ///
 /// The EngineContext is passed to the user-defined closure that is executed each generation. The user
 /// can use the EngineContext to access the current state of the genetic engine and make decisions based
@@ -35,7 +44,14 @@ where
     pub timer: Timer,
     pub metrics: MetricSet,
     pub score: Option<Score>,
+    pub stagnation: i32,
     pub front: Arc<Mutex<Front>>,
+    pub recording: Option<Recording>,
+    pub snapshot: Option<PopulationSnapshot>,
+    pub schema: Option<GeneSchema>,
+    pub elites: Option<EliteArchive<C>>,
+    pub species: Option<Speciation<C>>,
+    pub configuration: Description,
 }
 
 impl<C, T> EngineContext<C, T>
@@ -71,7 +87,14 @@ where
             timer: self.timer.clone(),
             metrics: self.metrics.clone(),
             score: self.score.clone(),
+            stagnation: self.stagnation,
             front: self.front.clone(),
+            recording: self.recording.clone(),
+            snapshot: self.snapshot.clone(),
+            schema: self.schema.clone(),
+            elites: self.elites.clone(),
+            species: self.species.clone(),
+            configuration: self.configuration.clone(),
         }
     }
 }
@@ -81,13 +104,17 @@ where
     C: Chromosome,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "EngineOutput {{\n")?;
-        write!(f, "  best: {:?},\n", self.best)?;
-        write!(f, "  score: {:?},\n", self.score())?;
-        write!(f, "  index: {:?},\n", self.index)?;
-        write!(f, "  size: {:?},\n", self.population.len())?;
-        write!(f, "  duration: {:?},\n", self.timer.duration())?;
-        write!(f, "  metrics: {:?},\n", self.metrics)?;
+        writeln!(f, "EngineOutput {{")?;
+        writeln!(f, "  best: {:?},", self.best)?;
+        writeln!(f, "  score: {:?},", self.score())?;
+        if let Some(interval) = self.score().confidence_interval(0.95) {
+            writeln!(f, "  score 95% CI: {:?},", interval)?;
+        }
+        writeln!(f, "  index: {:?},", self.index)?;
+        writeln!(f, "  size: {:?},", self.population.len())?;
+        writeln!(f, "  duration: {:?},", self.timer.duration())?;
+        writeln!(f, "  metrics: {:?},", self.metrics)?;
+        writeln!(f, "  configuration: {},", self.configuration)?;
         write!(f, "}}")
     }
 }
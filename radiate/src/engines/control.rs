@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use super::stats::metric_names;
+
+/// A parameter of a running engine that can be changed through a `ControlPanel`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Knob {
+    /// The rate of the mutators wrapped in a `ControlledMutator`, between 0 and 1.
+    MutationRate,
+    /// The temperature of a `BoltzmannSelector` given the panel with `with_control`, above 0.
+    Temperature,
+    /// The size of the population, at least 1. Takes the place of the `population_size` - a
+    /// `PopulationSchedule` still applies on top of it.
+    PopulationSize,
+}
+
+impl Knob {
+    pub const ALL: [Knob; 3] = [Knob::MutationRate, Knob::Temperature, Knob::PopulationSize];
+
+    /// The key of the knob in a control file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Knob::MutationRate => "mutation_rate",
+            Knob::Temperature => "temperature",
+            Knob::PopulationSize => "population_size",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Knob> {
+        Knob::ALL.into_iter().find(|knob| knob.key() == key)
+    }
+
+    /// The metric the engine records the knob's value in when it changes.
+    pub fn metric(&self) -> &'static str {
+        match self {
+            Knob::MutationRate => metric_names::CONTROL_MUTATION_RATE,
+            Knob::Temperature => metric_names::CONTROL_TEMPERATURE,
+            Knob::PopulationSize => metric_names::CONTROL_POPULATION_SIZE,
+        }
+    }
+
+    /// Whether `value` is in the knob's range.
+    pub fn accepts(&self, value: f32) -> bool {
+        match self {
+            Knob::MutationRate => (0.0..=1.0).contains(&value),
+            Knob::Temperature => value > 0.0 && value.is_finite(),
+            Knob::PopulationSize => value >= 1.0 && value.is_finite(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ControlState {
+    values: BTreeMap<Knob, f32>,
+    pending: BTreeMap<Knob, f32>,
+    file: Option<(PathBuf, Option<SystemTime>)>,
+    rejected: Vec<String>,
+}
+
+/// Steers a long run without restarting it. A `ControlPanel` holds values for some of the engine's
+/// parameters (see `Knob`) that can be changed while the engine runs - from another thread through
+/// `set`, or by editing a control file the panel watches.
+///
+/// Changes take effect at the next generation boundary: the engine applies them before it starts a
+/// generation and records every new value in the knob's metric (see `Knob::metric`), so the metrics of
+/// the run show when and how it was steered. Until a knob is set, the engine uses its own parameter.
+///
+/// Like the `HallOfFame`, a `ControlPanel` is cheap to clone and all clones share the same values -
+/// one clone goes to the engine with `GeneticEngineParams::control`, and to the `ControlledMutator`s and
+/// the `BoltzmannSelector` that should follow it, and another stays with whoever steers the run.
+///
+/// The control file is a flat TOML file of `key = value` lines (see `Knob::key`); comments, blank lines
+/// and table headers are ignored. The file is read again whenever it is modified. Unknown keys and
+/// values out of range are rejected and reported by `rejected`, leaving the knob as it was.
+///
+/// ```text
+/// # steering.toml
+/// mutation_rate = 0.05
+/// population_size = 200
+/// ```
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let panel = ControlPanel::new();
+/// let steering = panel.clone();
+///
+/// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 5, 0.0, 1.0))
+///     .control(panel.clone())
+///     .alter(alters![ControlledMutator::new(GaussianMutator::new(0.1), panel)])
+///     .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().sum::<f32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| {
+///     if ctx.index == 10 {
+///         steering.set(Knob::MutationRate, 0.01);
+///         steering.set(Knob::PopulationSize, 50.0);
+///     }
+///
+///     ctx.index >= 15
+/// });
+///
+/// assert_eq!(result.population.len(), 50);
+/// assert_eq!(steering.get(Knob::MutationRate), Some(0.01));
+/// ```
+#[derive(Clone, Default)]
+pub struct ControlPanel {
+    state: Arc<Mutex<ControlState>>,
+}
+
+impl ControlPanel {
+    pub fn new() -> Self {
+        ControlPanel::default()
+    }
+
+    /// Watch the control file at `path`. It doesn't have to exist yet.
+    pub fn watch(self, path: impl Into<PathBuf>) -> Self {
+        self.state.lock().unwrap().file = Some((path.into(), None));
+        self
+    }
+
+    /// Change a knob at the next generation boundary. Panics if the value is out of the knob's range.
+    pub fn set(&self, knob: Knob, value: f32) {
+        if !knob.accepts(value) {
+            panic!("{} is out of range for {}", value, knob.key());
+        }
+
+        self.state.lock().unwrap().pending.insert(knob, value);
+    }
+
+    /// The value of a knob the engine is using, if it has been set.
+    pub fn get(&self, knob: Knob) -> Option<f32> {
+        self.state.lock().unwrap().values.get(&knob).copied()
+    }
+
+    /// The lines of the control file that were rejected the last time it was read.
+    pub fn rejected(&self) -> Vec<String> {
+        self.state.lock().unwrap().rejected.clone()
+    }
+
+    /// Read the control file if it changed and apply the pending changes. Returns the knobs whose value
+    /// changed.
+    pub(crate) fn apply(&self) -> Vec<(Knob, f32)> {
+        let mut state = self.state.lock().unwrap();
+        Self::poll(&mut state);
+
+        let pending = std::mem::take(&mut state.pending);
+        let mut changed = Vec::new();
+        for (knob, value) in pending {
+            if state.values.insert(knob, value) != Some(value) {
+                changed.push((knob, value));
+            }
+        }
+
+        changed
+    }
+
+    fn poll(state: &mut ControlState) {
+        let Some((path, seen)) = state.file.as_mut() else {
+            return;
+        };
+
+        let Ok(modified) = std::fs::metadata(&path).and_then(|meta| meta.modified()) else {
+            return;
+        };
+
+        if *seen == Some(modified) {
+            return;
+        }
+
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return;
+        };
+
+        *seen = Some(modified);
+        let (values, rejected) = parse(&text);
+        state.pending.extend(values);
+        state.rejected = rejected;
+    }
+}
+
+/// Parse the `key = value` lines of a control file into knob values and rejected lines.
+fn parse(text: &str) -> (Vec<(Knob, f32)>, Vec<String>) {
+    let mut values = Vec::new();
+    let mut rejected = Vec::new();
+
+    for line in text.lines() {
+        let content = line.split('#').next().unwrap_or("").trim();
+        if content.is_empty() || content.starts_with('[') {
+            continue;
+        }
+
+        let parsed = content.split_once('=').and_then(|(key, value)| {
+            let knob = Knob::from_key(key.trim())?;
+            let value = value.trim().parse::<f32>().ok()?;
+            knob.accepts(value).then_some((knob, value))
+        });
+
+        match parsed {
+            Some(value) => values.push(value),
+            None => rejected.push(line.to_string()),
+        }
+    }
+
+    (values, rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_file_is_parsed_and_reloaded() {
+        let path =
+            std::env::temp_dir().join(format!("radiate-control-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "# steering\n[engine]\nmutation_rate = 0.2\nspeed = 3\ntemperature = -1\n",
+        )
+        .unwrap();
+
+        let panel = ControlPanel::new().watch(&path);
+        assert_eq!(panel.apply(), vec![(Knob::MutationRate, 0.2)]);
+        assert_eq!(panel.get(Knob::MutationRate), Some(0.2));
+        assert_eq!(
+            panel.rejected(),
+            vec!["speed = 3".to_string(), "temperature = -1".to_string()]
+        );
+
+        // Nothing changed since the file was read.
+        assert!(panel.apply().is_empty());
+
+        panel.set(Knob::PopulationSize, 30.0);
+        assert_eq!(panel.get(Knob::PopulationSize), None);
+        assert_eq!(panel.apply(), vec![(Knob::PopulationSize, 30.0)]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
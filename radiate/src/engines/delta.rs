@@ -0,0 +1,98 @@
+use super::{Chromosome, Genotype, Score};
+
+/// A gene an alterer (or the codex's repair) changed, at position `index` of chromosome `chromosome`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneChange<G> {
+    pub chromosome: usize,
+    pub index: usize,
+    pub old: G,
+    pub new: G,
+}
+
+/// A fitness function that decomposes over the genes - e.g. the sum of the values of the items
+/// in a knapsack, or the energy of a QUBO - so an altered individual can be scored from the score
+/// of the genotype it was altered from and the genes that changed, in `O(changes)` instead of
+/// decoding and scoring the whole genotype again.
+///
+/// The engine keeps a copy of every offspring before the alterers run, and once they have, hands
+/// each changed offspring's parent score and gene changes to `delta`. New individuals, and offspring
+/// whose shape changed (e.g. by an indel mutator), are scored with `fitness`, as is every individual
+/// `delta` returns `None` for.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// struct Ones;
+///
+/// impl DeltaFitness<BitChromosome, Vec<Vec<bool>>> for Ones {
+///     fn fitness(&self, individual: Vec<Vec<bool>>) -> Score {
+///         Score::from_usize(individual[0].iter().filter(|bit| **bit).count())
+///     }
+///
+///     fn delta(
+///         &self,
+///         parent: &Score,
+///         changes: &[GeneChange<BitGene>],
+///         _: &Genotype<BitChromosome>,
+///     ) -> Option<Score> {
+///         let gained = changes
+///             .iter()
+///             .map(|change| *change.new.allele() as i32 - *change.old.allele() as i32)
+///             .sum::<i32>();
+///         Some(Score::from_f32(parent.as_f32() + gained as f32))
+///     }
+/// }
+///
+/// let engine = GeneticEngine::from_codex(BitCodex::new(1, 50))
+///     .delta_fitness(Ones)
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.score().as_usize() == 50 || ctx.index > 200);
+/// assert!(result.metrics.get(metric_names::DELTA_EVALUATIONS).is_some());
+/// ```
+pub trait DeltaFitness<C: Chromosome, T>: Send + Sync {
+    /// Score a decoded individual from scratch.
+    fn fitness(&self, individual: T) -> Score;
+
+    /// Score an altered individual from the `parent`'s score and the genes that changed. The altered
+    /// `genotype` is there for terms that depend on genes other than the changed ones (e.g. the couplings
+    /// of a QUBO). Returns `None` when the individual should be scored with `fitness` instead - e.g.
+    /// when so many genes changed that starting over is cheaper.
+    fn delta(
+        &self,
+        parent: &Score,
+        changes: &[GeneChange<C::Gene>],
+        genotype: &Genotype<C>,
+    ) -> Option<Score>;
+}
+
+impl<C: Chromosome> Genotype<C> {
+    /// The genes that differ between `parent` and this genotype, or `None` if their shapes differ -
+    /// a different number of chromosomes, or of genes in any chromosome.
+    pub fn changes_from(&self, parent: &Genotype<C>) -> Option<Vec<GeneChange<C::Gene>>> {
+        if self.len() != parent.len() {
+            return None;
+        }
+
+        let mut changes = Vec::new();
+        for (chromosome, (old, new)) in parent.iter().zip(self.iter()).enumerate() {
+            if old.len() != new.len() {
+                return None;
+            }
+
+            for (index, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+                if old != new {
+                    changes.push(GeneChange {
+                        chromosome,
+                        index,
+                        old: old.clone(),
+                        new: new.clone(),
+                    });
+                }
+            }
+        }
+
+        Some(changes)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::{Display, Formatter};
+
+/// The name and parameters of an engine component - an alterer, a selector or the engine itself - as
+/// returned by `EngineCompoment::describe`. Components built out of others (e.g. a `Choice`) describe
+/// them as their children.
+///
+/// The engine describes its configuration when it starts (see `EngineContext::configuration`), so the
+/// results of a run carry the exact settings that produced them. Parameter values are kept as text, as
+/// they are displayed.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let alterer = GaussianMutator::new(0.1);
+/// let description = alterer.describe();
+///
+/// assert_eq!(description.name, "GaussianMutator");
+/// assert_eq!(description.get("rate"), Some("0.1"));
+/// assert_eq!(description.to_string(), "GaussianMutator(rate=0.1)");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Description {
+    pub name: &'static str,
+    pub parameters: Vec<(&'static str, String)>,
+    pub children: Vec<Description>,
+}
+
+impl Description {
+    pub fn new(name: &'static str) -> Self {
+        Description {
+            name,
+            parameters: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn param(mut self, name: &'static str, value: impl Display) -> Self {
+        self.parameters.push((name, value.to_string()));
+        self
+    }
+
+    pub fn child(mut self, child: Description) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// The value of the parameter called `name`, if the component has one.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(parameter, _)| *parameter == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Display for Description {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if !self.parameters.is_empty() {
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<String>>();
+            write!(f, "({})", parameters.join(", "))?;
+        }
+
+        if !self.children.is_empty() {
+            let children = self
+                .children
+                .iter()
+                .map(|child| child.to_string())
+                .collect::<Vec<String>>();
+            write!(f, "[{}]", children.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,17 @@
-use crate::random_provider;
+use alloc::{vec, vec::Vec};
+use rand::{Rng, RngCore};
 
 /// * Generates a sorted vector of unique indices for a given size and order, ensuring the specified index is included.
 /// * Calls the subset function to get a subset of indices.
 /// * Replaces an index in the subset with the specified index if it fits the criteria.
 /// * Sorts and returns the result.
-pub fn individual_indexes(index: usize, size: usize, order: usize) -> Vec<usize> {
-    let mut sub_set = subset(size, order);
+pub fn individual_indexes(
+    index: usize,
+    size: usize,
+    order: usize,
+    rng: &mut dyn RngCore,
+) -> Vec<usize> {
+    let mut sub_set = subset(size, order, rng);
     let mut i = 0;
     while i < sub_set.len() && sub_set[i] < index as i32 {
         i += 1;
@@ -18,14 +24,14 @@ pub fn individual_indexes(index: usize, size: usize, order: usize) -> Vec<usize>
     result
 }
 
-/// * Generates a subset of indices of size k from a total of n elements.
+/// * Generates a subset of indices of size k from a total of n elements, drawing from `rng`.
 /// * Calls the next function to fill the subset.
-pub fn subset(n: usize, k: usize) -> Vec<i32> {
+pub fn subset(n: usize, k: usize, rng: &mut dyn RngCore) -> Vec<i32> {
     if n < k {
         panic!("n smaller than k: {} < {}.", n, k);
     }
     let mut sub = vec![0; k];
-    next(n as i32, &mut sub);
+    next(n as i32, &mut sub, rng);
     sub
 }
 
@@ -37,7 +43,7 @@ pub fn subset(n: usize, k: usize) -> Vec<i32> {
 /// * Ensures the subset size and range are valid.
 /// * Initializes the subset with evenly spaced indices.
 /// * Adjusts the subset by randomly selecting indices and ensuring they are unique.
-fn next(num: i32, a: &mut [i32]) {
+fn next(num: i32, a: &mut [i32], rng: &mut dyn RngCore) {
     let k = a.len() as i32;
     if k == num {
         for i in 0..k {
@@ -45,7 +51,7 @@ fn next(num: i32, a: &mut [i32]) {
         }
         return;
     }
-    build_subset(num, a);
+    build_subset(num, a, rng);
     if k > num - k {
         invert(num, a);
     }
@@ -53,7 +59,7 @@ fn next(num: i32, a: &mut [i32]) {
 
 /// * Inverts the subset to ensure all indices are unique and within the specified range.
 /// * Uses a helper vector to track used indices and fills the subset with the remaining indices.
-fn build_subset(n: i32, sub: &mut [i32]) {
+fn build_subset(n: i32, sub: &mut [i32], rng: &mut dyn RngCore) {
     let k = sub.len() as i32;
     check_subset(n, k);
 
@@ -65,7 +71,7 @@ fn build_subset(n: i32, sub: &mut [i32]) {
         let mut ix;
         let mut l;
         loop {
-            ix = random_provider::gen_range(1..n);
+            ix = rng.gen_range(1..n);
             l = (ix * k - 1) / n;
             if sub[l as usize] < ix {
                 break;
@@ -101,7 +107,7 @@ fn build_subset(n: i32, sub: &mut [i32]) {
             let ir = l;
             let m0 = 1 + (sub[l as usize - 1] - 1) * n / k;
             let m = sub[l as usize - 1] * n / k - m0 + 1;
-            let ix = random_provider::gen_range(m0..m0 + m - 1);
+            let ix = rng.gen_range(m0..m0 + m - 1);
             let mut i = l + 1;
             while i <= ir && ix >= sub[i as usize - 1] {
                 sub[i as usize - 2] = sub[i as usize - 1];
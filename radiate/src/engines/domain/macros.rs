@@ -64,77 +64,77 @@ macro_rules! div_impl {
 
                 #[inline]
                 fn div(self, other: $t) -> $t {
-                    if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<FloatGene>() {
+                    if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<FloatGene>() {
                         if *other.allele() == 0.0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1.0 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<i8>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<i8>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<i16>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<i16>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<i32>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<i32>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<i64>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<i64>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<i128>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<i128>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<u8>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<u8>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<u16>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<u16>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<u32>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<u32>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<u64>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<u64>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
                                 ..self
                             }
                         }
-                    } else if std::any::TypeId::of::<$t>() == std::any::TypeId::of::<IntGene<u128>>() {
+                    } else if ::core::any::TypeId::of::<$t>() == ::core::any::TypeId::of::<IntGene<u128>>() {
                         if *other.allele() == 0 as <$t as Gene>::Allele {
                             return Self {
                                 allele: self.allele() / 1 as <$t as Gene>::Allele,
@@ -176,6 +176,14 @@ macro_rules! impl_integer {
                 fn from_i32(value: i32) -> $t {
                     value as $t
                 }
+
+                fn from_f32(value: f32) -> $t {
+                    value as $t
+                }
+
+                fn to_f32(self) -> f32 {
+                    self as f32
+                }
             }
         )*
     };
@@ -185,10 +193,7 @@ macro_rules! impl_integer {
 macro_rules! alters {
     ($($struct_instance:expr),* $(,)?) => {
         {
-            let mut vec: Vec<AlterAction<_>> = Vec::new();
-            $(
-                vec.push($struct_instance.to_alter());
-            )*
+            let vec: Vec<AlterAction<_>> = vec![$($struct_instance.to_alter()),*];
             vec
         }
     };
@@ -0,0 +1,97 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Structured metadata attached to a `Phenotype` during evaluation. Metadata is a set of
+/// key/value pairs along with an optional non-fatal error - for example
+/// `"steps" -> "120"` or `error: "simulation diverged"`. It is stored alongside the score
+/// of the individual, so it can be queried from the population after the fact instead of
+/// printing from inside the fitness function.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+    pub values: BTreeMap<String, String>,
+    pub error: Option<String>,
+}
+
+impl Metadata {
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.values.get(key)
+    }
+
+    pub fn error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.error.is_none()
+    }
+
+    /// Add the values of `other`, overwriting existing keys. The first error is kept.
+    pub fn merge(&mut self, other: Metadata) {
+        self.values.extend(other.values);
+        if self.error.is_none() {
+            self.error = other.error;
+        }
+    }
+}
+
+/// The key under which the engine records the position of an individual in the population on the copy
+/// it selects from when fitness is shaped, so the selected copies can be traced back to it.
+pub(crate) const SOURCE: &str = "source";
+
+thread_local! {
+    static CURRENT: RefCell<Metadata> = RefCell::new(Metadata::default());
+}
+
+/// Attach a key/value pair to the individual currently being evaluated on this thread.
+/// Must be called from inside the fitness function.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| {
+///         let sum = geno[0].iter().sum::<i32>();
+///         if sum > 40 {
+///             metadata::error("sum too large");
+///         }
+///
+///         metadata::insert("sum", sum);
+///         sum
+///     })
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// for individual in result.population.iter() {
+///     assert!(individual.metadata().unwrap().get("sum").is_some());
+/// }
+/// ```
+pub fn insert(key: impl Into<String>, value: impl ToString) {
+    CURRENT.with(|current| {
+        current
+            .borrow_mut()
+            .values
+            .insert(key.into(), value.to_string());
+    });
+}
+
+/// Report a non-fatal error for the individual currently being evaluated on this thread.
+/// The individual is still scored as usual.
+pub fn error(message: impl Into<String>) {
+    CURRENT.with(|current| {
+        current.borrow_mut().error = Some(message.into());
+    });
+}
+
+/// Take the metadata collected on this thread since the last call, leaving it empty.
+/// Returns `None` if nothing was recorded.
+pub fn take() -> Option<Metadata> {
+    CURRENT.with(|current| {
+        let metadata = std::mem::take(&mut *current.borrow_mut());
+        if metadata.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        }
+    })
+}
@@ -1,5 +1,12 @@
 pub mod indexes;
 pub mod macros;
 pub mod random_provider;
+
+#[cfg(feature = "std")]
+pub mod metadata;
+#[cfg(feature = "std")]
+pub mod scratch;
+#[cfg(feature = "std")]
 pub mod thread_pool;
+#[cfg(feature = "std")]
 pub mod timer;
@@ -1,69 +1,333 @@
+use alloc::vec::Vec;
 use rand::distributions::Standard;
 use rand::distributions::{uniform::SampleUniform, Distribution};
-use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::Rng;
+use rand::RngCore;
+#[cfg(feature = "std")]
 use rand::SeedableRng;
-use std::sync::{Arc, Mutex, OnceLock};
+#[cfg(feature = "std")]
+use rand_chacha::ChaCha12Rng;
+#[cfg(feature = "std")]
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt::Debug,
+    sync::{Arc, Mutex, OnceLock},
+};
 
-struct RandomProvider {
-    rng: Arc<Mutex<StdRng>>,
+/// A shared handle to a random number generator - any `RngCore`, e.g. `StdRng`, a `Xoshiro` or a `ChaCha`
+/// generator from the `rand_*` crates. Clones share the same generator.
+///
+/// The alterers are handed the engine's handle explicitly (see `Mutate` and `Crossover`) and draw from it
+/// with its `random`, `gen_range`, `gaussian`, `choose` and `shuffle`. Every function of the
+/// `random_provider` draws from the handle scoped on the calling thread with `RngHandle::scope`, or from
+/// the global generator when there is none. The `GeneticEngine` passes the generator set with
+/// `GeneticEngineParams::rng` (or `seed`) to its alterers and scopes it around everything else it runs -
+/// including the evaluations on its worker threads - so the selectors and codices it calls draw from the
+/// same generator and engines running side by side don't share (or reseed) each other's randomness.
+///
+/// The handles created by `seeded` and `from_entropy` - and the global generator - hold a `ChaCha12Rng`,
+/// the generator behind `StdRng`, so they draw the same numbers a `StdRng` with the same seed would. Its
+/// state can be saved and restored (see `RngHandle::state`), which is how engine checkpoints store the
+/// generator. Other generators are used as they are, but their state can't be saved.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let handle = random_provider::RngHandle::seeded(42);
+/// let first = handle.random::<f32>();
+///
+/// let again = random_provider::RngHandle::seeded(42);
+/// assert_eq!(again.scope(|| random_provider::random::<f32>()), first);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct RngHandle {
+    rng: Arc<Mutex<Generator>>,
+}
+
+/// The saved state of a `ChaCha12Rng` - its seed, stream and position in the stream. See
+/// `RngHandle::state`.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RngState {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+#[cfg(feature = "std")]
+enum Generator {
+    ChaCha(Box<ChaCha12Rng>),
+    Other(Box<dyn RngCore + Send>),
+}
+
+#[cfg(feature = "std")]
+impl Generator {
+    fn new(rng: impl RngCore + Send + 'static) -> Self {
+        let mut rng = Some(rng);
+        if let Some(chacha) = (&mut rng as &mut dyn Any).downcast_mut::<Option<ChaCha12Rng>>() {
+            return Generator::ChaCha(Box::new(chacha.take().unwrap()));
+        }
+
+        Generator::Other(Box::new(rng.unwrap()))
+    }
+
+    fn as_rng(&mut self) -> &mut dyn RngCore {
+        match self {
+            Generator::ChaCha(rng) => &mut **rng,
+            Generator::Other(rng) => &mut **rng,
+        }
+    }
 }
 
-impl RandomProvider {
-    /// Returns the global instance of the registry.
-    pub(self) fn global() -> &'static RandomProvider {
-        static INSTANCE: OnceLock<RandomProvider> = OnceLock::new();
+#[cfg(feature = "std")]
+impl RngHandle {
+    pub fn new(rng: impl RngCore + Send + 'static) -> Self {
+        RngHandle {
+            rng: Arc::new(Mutex::new(Generator::new(rng))),
+        }
+    }
 
-        INSTANCE.get_or_init(|| RandomProvider {
-            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
-        })
+    /// A handle to a `ChaCha12Rng` seeded with `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        RngHandle::new(ChaCha12Rng::seed_from_u64(seed))
     }
 
-    pub(self) fn replace_global(other: StdRng) {
-        let instance = RandomProvider::global();
-        let mut rng = instance.rng.lock().unwrap();
-        *rng = other;
+    /// A handle to a `ChaCha12Rng` seeded from the operating system.
+    pub fn from_entropy() -> Self {
+        RngHandle::new(ChaCha12Rng::from_entropy())
+    }
+
+    /// A handle to a `ChaCha12Rng` continuing from a saved `state`.
+    pub fn from_state(state: &RngState) -> Self {
+        let mut rng = ChaCha12Rng::from_seed(state.seed);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        RngHandle::new(rng)
+    }
+
+    /// The handle scoped on the calling thread, or else a handle to the global generator.
+    pub fn current() -> Self {
+        current().unwrap_or_else(|| global().clone())
+    }
+
+    /// Run `func` with this handle as the calling thread's generator, restoring the previous one
+    /// afterwards (even if `func` panics).
+    pub fn scope<R>(&self, func: impl FnOnce() -> R) -> R {
+        struct Restore(Option<RngHandle>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                SCOPED.with(|scoped| *scoped.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(SCOPED.with(|scoped| scoped.borrow_mut().replace(self.clone())));
+        func()
     }
 
-    pub(self) fn get_global() -> StdRng {
-        let instance = RandomProvider::global();
-        let rng = instance.rng.lock().unwrap();
-        rng.clone()
+    /// Run `func` with exclusive access to the generator.
+    pub fn with<R>(&self, func: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+        func(self.rng.lock().unwrap().as_rng())
     }
 
-    /// Sets a new seed for the global RNG.
-    pub(self) fn set_seed(seed: u64) {
-        let instance = RandomProvider::global();
-        let mut rng = instance.rng.lock().unwrap();
-        *rng = StdRng::seed_from_u64(seed);
+    /// Replace the generator, for every clone of the handle.
+    pub fn replace(&self, rng: impl RngCore + Send + 'static) {
+        *self.rng.lock().unwrap() = Generator::new(rng);
     }
 
-    /// Generates a random number using the global RNG.
-    pub(self) fn random<T>() -> T
+    /// Generates a random number of type T - see `random_provider::random`.
+    pub fn random<T>(&self) -> T
     where
         T: SampleUniform,
         Standard: Distribution<T>,
     {
-        let instance = RandomProvider::global();
-        let mut rng = instance.rng.lock().unwrap();
-        rng.gen()
+        self.with(|rng| rng.gen())
     }
 
-    pub(self) fn gen_range<T>(range: std::ops::Range<T>) -> T
+    /// Generates a random number of type T in the given range.
+    pub fn gen_range<T>(&self, range: core::ops::Range<T>) -> T
     where
         T: SampleUniform + PartialOrd,
         Standard: Distribution<T>,
     {
-        let instance = RandomProvider::global();
-        let mut rng = instance.rng.lock().unwrap();
-        rng.gen_range(range)
+        self.with(|rng| rng.gen_range(range))
+    }
+
+    /// Chooses a random item from the given slice.
+    pub fn choose<'a, T>(&self, items: &'a [T]) -> &'a T {
+        &items[self.gen_range(0..items.len())]
+    }
+
+    /// Generates a random number from a Gaussian distribution - see `random_provider::gaussian`.
+    pub fn gaussian(&self, mean: f64, std_dev: f64) -> f64 {
+        let u1: f64 = self.random();
+        let u2: f64 = self.random();
+
+        let z0 = (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos();
+
+        mean + std_dev * z0
+    }
+
+    /// Shuffles the given slice in place.
+    pub fn shuffle<T>(&self, items: &mut [T]) {
+        self.with(|rng| items.shuffle(rng));
+    }
+
+    /// Generates a vector of indexes from 0 to n-1 in random order.
+    pub fn indexes(&self, n: usize) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut indexes);
+        indexes
+    }
+
+    /// The current state of the generator, without drawing from it - `None` unless it's a `ChaCha12Rng`.
+    /// A handle created `from_state` continues exactly like this one from here on.
+    pub fn state(&self) -> Option<RngState> {
+        match &*self.rng.lock().unwrap() {
+            Generator::ChaCha(rng) => Some(RngState {
+                seed: rng.get_seed(),
+                stream: rng.get_stream(),
+                word_pos: rng.get_word_pos(),
+            }),
+            Generator::Other(_) => None,
+        }
     }
 }
 
-/// Seeds the thread-local random number generator with the given seed.
+#[cfg(feature = "std")]
+impl RngCore for RngHandle {
+    fn next_u32(&mut self) -> u32 {
+        self.with(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.with(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.with(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.with(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Debug for RngHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RngHandle").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static SCOPED: RefCell<Option<RngHandle>> = const { RefCell::new(None) };
+}
+
+/// The global generator, used on threads without a scoped handle.
+#[cfg(feature = "std")]
+fn global() -> &'static RngHandle {
+    static INSTANCE: OnceLock<RngHandle> = OnceLock::new();
+    INSTANCE.get_or_init(RngHandle::from_entropy)
+}
+
+/// Run `func` with the calling thread's generator - the scoped handle, or else the global one.
+#[cfg(feature = "std")]
+fn with_rng<R>(func: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+    SCOPED.with(|scoped| match &*scoped.borrow() {
+        Some(handle) => handle.with(func),
+        None => global().with(func),
+    })
+}
+
+/// The handle scoped on the calling thread, if any.
+#[cfg(feature = "std")]
+pub fn current() -> Option<RngHandle> {
+    SCOPED.with(|scoped| scoped.borrow().clone())
+}
+
+/// Seeds the global random number generator with the given seed.
+#[cfg(feature = "std")]
 pub fn set_seed(seed: u64) {
-    RandomProvider::set_seed(seed);
+    global().replace(ChaCha12Rng::seed_from_u64(seed));
+}
+
+/// Replaces the global random number generator, e.g. with a faster or a cryptographically secure one.
+#[cfg(feature = "std")]
+pub fn set_rng(rng: impl RngCore + Send + 'static) {
+    global().replace(rng);
+}
+
+/// Without the `std` feature there are no threads to scope generators on, so every function of the
+/// `random_provider` draws from a single global xorshift generator. Its state is an `AtomicU32`, so
+/// drawing a number is lock free and safe from interrupt handlers - but needs a target with atomic
+/// compare-and-swap (e.g. Cortex-M3 and up, RISC-V with the A extension, or the ESP32). Seed it from a
+/// hardware source with `set_seed`; until then it starts from a fixed seed.
+#[cfg(not(feature = "std"))]
+mod global {
+    use core::sync::atomic::{AtomicU32, Ordering};
+    use rand::RngCore;
+
+    static STATE: AtomicU32 = AtomicU32::new(0x9E37_79B9);
+
+    pub struct Global;
+
+    pub fn seed(seed: u64) {
+        let folded = (seed ^ (seed >> 32)) as u32;
+        STATE.store(
+            if folded == 0 { 0x9E37_79B9 } else { folded },
+            Ordering::Relaxed,
+        );
+    }
+
+    fn xorshift(mut x: u32) -> u32 {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        x
+    }
+
+    impl RngCore for Global {
+        fn next_u32(&mut self) -> u32 {
+            let previous = STATE
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| Some(xorshift(x)))
+                .unwrap_or_default();
+            xorshift(previous)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(4) {
+                let bytes = self.next_u32().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+}
+
+/// Run `func` with the global generator.
+#[cfg(not(feature = "std"))]
+fn with_rng<R>(func: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+    func(&mut global::Global)
+}
+
+/// Seeds the global random number generator with the given seed.
+#[cfg(not(feature = "std"))]
+pub fn set_seed(seed: u64) {
+    global::seed(seed);
 }
 
 /// Generates a random number of type T.
@@ -75,16 +339,16 @@ where
     T: SampleUniform,
     Standard: Distribution<T>,
 {
-    RandomProvider::random()
+    with_rng(|rng| rng.gen())
 }
 
 /// Generates a random number of type T in the given range.
-pub fn gen_range<T>(range: std::ops::Range<T>) -> T
+pub fn gen_range<T>(range: core::ops::Range<T>) -> T
 where
     T: SampleUniform + PartialOrd,
     Standard: Distribution<T>,
 {
-    RandomProvider::gen_range(range)
+    with_rng(|rng| rng.gen_range(range))
 }
 
 /// Chooses a random item from the given slice.
@@ -95,19 +359,24 @@ pub fn choose<T>(items: &[T]) -> &T {
 
 /// Generates a random number from a Gaussian distribution with the given mean and standard deviation.
 /// The Box-Muller transform is used to generate the random number.
+#[cfg(feature = "std")]
 pub fn gaussian(mean: f64, std_dev: f64) -> f64 {
-    let u1: f64 = RandomProvider::random();
-    let u2: f64 = RandomProvider::random();
+    RngHandle::current().gaussian(mean, std_dev)
+}
 
-    let z0 = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+/// Generates a random number from an approximately Gaussian distribution with the given mean and
+/// standard deviation. Without `std` there is no `ln`, `sqrt` or `cos`, so the sum of 12 uniform
+/// samples (Irwin-Hall) is used instead - it never strays more than 6 standard deviations from the mean.
+#[cfg(not(feature = "std"))]
+pub fn gaussian(mean: f64, std_dev: f64) -> f64 {
+    let z0 = (0..12).map(|_| random::<f64>()).sum::<f64>() - 6.0;
 
     mean + std_dev * z0
 }
 
 /// Shuffles the given slice in place.
 pub fn shuffle<T>(items: &mut [T]) {
-    let instance = RandomProvider::global();
-    items.shuffle(&mut *instance.rng.lock().unwrap());
+    with_rng(|rng| items.shuffle(rng));
 }
 
 /// Generates a vector of indexes from 0 to n-1 in random order.
@@ -117,15 +386,22 @@ pub fn indexes(n: usize) -> Vec<usize> {
     indexes
 }
 
+/// Runs `func` with the global generator seeded with `seed`, restoring the previous generator afterwards.
+#[cfg(feature = "std")]
 pub fn scoped_seed<F>(seed: u64, func: F)
 where
     F: FnOnce(),
 {
-    let current_rng = RandomProvider::get_global();
+    let current = {
+        let mut rng = global().rng.lock().unwrap();
+        std::mem::replace(
+            &mut *rng,
+            Generator::ChaCha(Box::new(ChaCha12Rng::seed_from_u64(seed))),
+        )
+    };
 
-    RandomProvider::replace_global(StdRng::seed_from_u64(seed));
     func();
-    RandomProvider::replace_global(current_rng);
+    *global().rng.lock().unwrap() = current;
 }
 
 #[cfg(test)]
@@ -137,7 +413,7 @@ mod tests {
     fn test_random() {
         for _ in 0..100 {
             let value: f64 = random();
-            assert!(value >= 0.0 && value < 1.0);
+            assert!((0.0..1.0).contains(&value));
         }
     }
 
@@ -145,7 +421,7 @@ mod tests {
     fn test_gen_range() {
         for _ in 0..100 {
             let value: f64 = gen_range(0.0..100.0);
-            assert!(value >= 0.0 && value < 100.0);
+            assert!((0.0..100.0).contains(&value));
         }
     }
 
@@ -171,4 +447,60 @@ mod tests {
         assert_eq!(indexes.len(), 10);
         assert_ne!(indexes, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
+
+    #[test]
+    fn test_scoped_handles_are_reproducible() {
+        let draw = || (0..10).map(|_| random::<u64>()).collect::<Vec<u64>>();
+
+        let first = RngHandle::seeded(7).scope(draw);
+        let second = RngHandle::seeded(7).scope(|| {
+            assert!(current().is_some());
+            draw()
+        });
+
+        assert_eq!(first, second);
+        assert!(current().is_none());
+
+        let shared = RngHandle::seeded(7);
+        let on_thread = {
+            let shared = shared.clone();
+            std::thread::spawn(move || shared.scope(draw))
+                .join()
+                .unwrap()
+        };
+        assert_eq!(on_thread, first);
+        assert_ne!(shared.scope(draw), first);
+    }
+
+    #[test]
+    fn test_saved_state_continues_the_generator() {
+        let draw = || (0..10).map(|_| random::<u64>()).collect::<Vec<u64>>();
+
+        let handle = RngHandle::seeded(7);
+        let mut std_rng = rand::rngs::StdRng::seed_from_u64(7);
+        assert_eq!(
+            handle.scope(draw),
+            (0..10).map(|_| std_rng.next_u64()).collect::<Vec<_>>()
+        );
+
+        let state = handle.state().unwrap();
+        let restored = RngHandle::from_state(&state);
+        assert_eq!(restored.scope(draw), handle.scope(draw));
+
+        assert!(RngHandle::new(rand::rngs::mock::StepRng::new(0, 1))
+            .state()
+            .is_none());
+    }
+
+    #[test]
+    fn test_handles_draw_like_the_scoped_provider() {
+        let handle = RngHandle::seeded(7);
+        let drawn = (0..10)
+            .map(|_| handle.gen_range(0..100))
+            .collect::<Vec<i32>>();
+
+        let scoped =
+            RngHandle::seeded(7).scope(|| (0..10).map(|_| gen_range(0..100)).collect::<Vec<i32>>());
+        assert_eq!(drawn, scoped);
+    }
 }
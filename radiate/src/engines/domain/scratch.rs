@@ -0,0 +1,139 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A pool of reusable scratch state for fitness functions - simulator instances, buffers, etc that are
+/// expensive to create. An evaluation checks a value out of the pool with `get` and it goes back to the
+/// pool when the returned `FitnessCtx` is dropped, so at most one value is created per evaluation running
+/// at the same time - i.e. once per worker - and every later evaluation starts warm.
+///
+/// Unlike a `thread_local!`, the pool doesn't depend on which threads run the evaluations, so it works
+/// the same with the engine's own threads, dedicated threads or an external executor.
+///
+/// # Example
+/// ``` rust
+/// use radiate::scratch::ScratchPool;
+///
+/// let pool = ScratchPool::new(|| Vec::<f32>::with_capacity(1024));
+///
+/// {
+///     let mut buffer = pool.get();
+///     buffer.push(1.0);
+/// }
+///
+/// // The buffer was returned to the pool and is reused instead of creating a new one.
+/// assert_eq!(pool.get().len(), 1);
+/// assert_eq!(pool.created(), 1);
+/// ```
+pub struct ScratchPool<S> {
+    init: Box<dyn Fn() -> S + Send + Sync>,
+    free: Mutex<Vec<S>>,
+    created: AtomicUsize,
+}
+
+impl<S> ScratchPool<S> {
+    /// Create a new, empty pool that creates scratch state with `init` when none is free.
+    pub fn new(init: impl Fn() -> S + Send + Sync + 'static) -> Self {
+        ScratchPool {
+            init: Box::new(init),
+            free: Mutex::new(Vec::new()),
+            created: AtomicUsize::new(0),
+        }
+    }
+
+    /// Check out a value, creating one if none is free.
+    pub fn get(&self) -> FitnessCtx<'_, S> {
+        let scratch = self.free.lock().unwrap().pop();
+        let scratch = scratch.unwrap_or_else(|| {
+            self.created.fetch_add(1, Ordering::SeqCst);
+            (self.init)()
+        });
+
+        FitnessCtx {
+            pool: self,
+            scratch: Some(scratch),
+        }
+    }
+
+    /// The number of values the pool has created.
+    pub fn created(&self) -> usize {
+        self.created.load(Ordering::SeqCst)
+    }
+
+    /// The number of values that are currently free.
+    pub fn available(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+}
+
+/// A handle to scratch state checked out of a `ScratchPool`. It dereferences to the state and returns
+/// it to the pool when dropped.
+pub struct FitnessCtx<'a, S> {
+    pool: &'a ScratchPool<S>,
+    scratch: Option<S>,
+}
+
+impl<S> Deref for FitnessCtx<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        self.scratch.as_ref().unwrap()
+    }
+}
+
+impl<S> DerefMut for FitnessCtx<'_, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        self.scratch.as_mut().unwrap()
+    }
+}
+
+impl<S> Drop for FitnessCtx<'_, S> {
+    fn drop(&mut self) {
+        if let Some(scratch) = self.scratch.take() {
+            // Never panic in drop - if the lock is poisoned the value is dropped instead.
+            if let Ok(mut free) = self.pool.free.lock() {
+                free.push(scratch);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+
+    #[test]
+    fn test_scratch_is_created_once_per_concurrent_user() {
+        let pool = Arc::new(ScratchPool::new(|| 0_usize));
+        let barrier = Arc::new(Barrier::new(3));
+
+        let handles = (0..3)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    for i in 0..10 {
+                        let mut count = pool.get();
+                        if i == 0 {
+                            // Hold the first checkout until every thread has one.
+                            barrier.wait();
+                        }
+
+                        *count += 1;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(pool.created(), 3);
+        assert_eq!(pool.available(), 3);
+
+        let held = (0..3).map(|_| pool.get()).collect::<Vec<_>>();
+        assert_eq!(held.iter().map(|count| **count).sum::<usize>(), 30);
+    }
+}
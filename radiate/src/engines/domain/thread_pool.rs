@@ -1,5 +1,10 @@
 use std::{
-    sync::{mpsc, Arc, Mutex},
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    sync::{
+        atomic::{self, AtomicU64, AtomicUsize},
+        mpsc, Arc, Condvar, Mutex,
+    },
     thread::{self},
 };
 
@@ -17,9 +22,51 @@ impl<T> WorkResult<T> {
     }
 }
 
+/// The priority of a job submitted to the `ThreadPool`. Higher priority jobs are picked up before
+/// lower priority ones, jobs of the same priority run in the order they were submitted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// A snapshot of the `ThreadPool`'s queues, useful for tuning the number of threads.
+/// * `queued` - jobs waiting in the work-stealing queues
+/// * `pinned_queued` - jobs waiting for a dedicated thread
+/// * `active` - jobs currently running
+/// * `completed` - jobs finished since the pool was created
+/// * `stolen` - jobs a worker took from another worker's queue
+/// * `max_queue_depth` - the most jobs that have been waiting at once
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ThreadPoolStats {
+    pub queued: usize,
+    pub pinned_queued: usize,
+    pub active: usize,
+    pub completed: usize,
+    pub stolen: usize,
+    pub max_queue_depth: usize,
+}
+
+/// A function that runs a job on some other executor, e.g. `rayon::spawn`. See `ThreadPool::delegating`.
+pub type Spawner = Arc<dyn Fn(Job) + Send + Sync>;
+
+/// A work-stealing thread pool with job priorities.
+///
+/// Every worker owns a priority queue. Jobs are spread over the queues round robin and each worker runs
+/// the highest priority job in its own queue, stealing the highest priority job from the other queues
+/// once its own is empty. Optionally, a number of dedicated threads can be added with `with_dedicated`
+/// to run jobs submitted with `submit_pinned` - e.g. heavy evaluations - so they never hold up the
+/// regular workers.
+///
+/// Applications that already run a thread pool (rayon, tokio's blocking pool, ...) can avoid a second one
+/// by creating a `delegating` pool which hands every job to that executor instead.
 pub struct ThreadPool {
-    sender: mpsc::Sender<Message>,
+    shared: Arc<Shared>,
     workers: Vec<Worker>,
+    dedicated: Vec<Worker>,
+    spawner: Option<Spawner>,
 }
 
 impl ThreadPool {
@@ -27,53 +74,152 @@ impl ThreadPool {
     ///
     /// Create a new ThreadPool with the given size.
     pub fn new(size: usize) -> Self {
-        let (sender, receiver) = mpsc::channel();
-        let receiver = Arc::new(Mutex::new(receiver));
+        let shared = Arc::new(Shared::new(size));
 
         ThreadPool {
-            sender,
             workers: (0..size)
-                .map(|_| Worker::new(Arc::clone(&receiver)))
+                .map(|index| Worker::new(Arc::clone(&shared), index))
                 .collect(),
+            dedicated: Vec::new(),
+            spawner: None,
+            shared,
         }
     }
 
+    /// Create a ThreadPool without workers of its own which hands every job to `spawn` - e.g. rayon's global pool
+    /// with `ThreadPool::delegating(|job| rayon::spawn(job))`, or a specific pool with `move |job| pool.spawn(job)`.
+    /// Priorities are left to the executor, but dedicated threads added with `with_dedicated` still run pinned jobs.
+    ///
+    /// **Note**: The engine blocks while waiting for evaluations, so don't run it on a thread of the pool it
+    /// delegates to unless that pool has other threads to run the jobs.
+    pub fn delegating<S>(spawn: S) -> Self
+    where
+        S: Fn(Job) + Send + Sync + 'static,
+    {
+        let mut pool = ThreadPool::new(0);
+        pool.spawner = Some(Arc::new(spawn));
+        pool
+    }
+
+    /// Add `count` dedicated threads which only run jobs submitted with `submit_pinned`.
+    pub fn with_dedicated(mut self, count: usize) -> Self {
+        self.dedicated
+            .extend((0..count).map(|_| Worker::dedicated(Arc::clone(&self.shared))));
+        self
+    }
+
+    /// The executor this pool delegates to, if it's a `delegating` pool.
+    pub fn spawner(&self) -> Option<&Spawner> {
+        self.spawner.as_ref()
+    }
+
+    /// The number of work-stealing workers.
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// The number of dedicated threads.
+    pub fn num_dedicated(&self) -> usize {
+        self.dedicated.len()
+    }
+
     /// Execute a job in the thread pool. This is a 'fire and forget' method.
     pub fn submit<F>(&self, f: F)
     where
         F: FnOnce() + Send + 'static,
     {
-        let job = Box::new(f);
-        self.sender.send(Message::NewJob(job)).unwrap();
+        self.submit_with_priority(Priority::Normal, f);
+    }
+
+    /// Execute a job in the thread pool with the given priority. This is a 'fire and forget' method.
+    pub fn submit_with_priority<F>(&self, priority: Priority, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        match &self.spawner {
+            Some(spawn) => {
+                let shared = Arc::clone(&self.shared);
+                spawn(Box::new(move || shared.run(Box::new(f))));
+            }
+            None => self.shared.push(priority, Box::new(f)),
+        }
     }
 
     /// Execute a job in the thread pool and return a WorkResult that can be used to get the result of the job.
     pub fn submit_with_result<F, T>(&self, f: F) -> WorkResult<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.submit_with_result_and_priority(Priority::Normal, f)
+    }
+
+    /// Execute a job with the given priority and return a WorkResult that can be used to get the result of the job.
+    pub fn submit_with_result_and_priority<F, T>(&self, priority: Priority, f: F) -> WorkResult<T>
     where
         F: FnOnce() -> T + Send + 'static,
         T: Send + 'static,
     {
         let (tx, rx) = mpsc::sync_channel(1);
-        let job = Box::new(move || tx.send(f()).unwrap());
+        self.submit_with_priority(priority, move || tx.send(f()).unwrap());
+        WorkResult { receiver: rx }
+    }
+
+    /// Execute a job on one of the dedicated threads and return a WorkResult that can be used to get the result.
+    /// If the pool has no dedicated threads, the job is submitted to the regular workers with `Priority::Normal`.
+    pub fn submit_pinned<F, T>(&self, f: F) -> WorkResult<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.dedicated.is_empty() {
+            return self.submit_with_result(f);
+        }
 
-        self.sender.send(Message::NewJob(job)).unwrap();
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.shared
+            .push_pinned(Box::new(move || tx.send(f()).unwrap()));
         WorkResult { receiver: rx }
     }
 
+    /// The number of jobs waiting to be picked up, excluding pinned jobs.
+    pub fn queue_depth(&self) -> usize {
+        self.shared.pending.load(atomic::Ordering::SeqCst)
+    }
+
+    pub fn stats(&self) -> ThreadPoolStats {
+        ThreadPoolStats {
+            queued: self.shared.pending.load(atomic::Ordering::SeqCst),
+            pinned_queued: self.shared.pinned.lock().unwrap().len(),
+            active: self.shared.active.load(atomic::Ordering::SeqCst),
+            completed: self.shared.completed.load(atomic::Ordering::SeqCst),
+            stolen: self.shared.stolen.load(atomic::Ordering::SeqCst),
+            max_queue_depth: self.shared.max_depth.load(atomic::Ordering::SeqCst),
+        }
+    }
+
     pub fn is_alive(&self) -> bool {
-        self.workers.iter().any(|worker| worker.is_alive())
+        self.workers
+            .iter()
+            .chain(self.dedicated.iter())
+            .any(|worker| worker.is_alive())
     }
 }
 
 /// Drop implementation for ThreadPool. This will terminate all workers when the ThreadPool is dropped.
-/// We need to make sure that all workers are terminated before the ThreadPool is dropped.
+/// We need to make sure that all workers are terminated before the ThreadPool is dropped. Workers finish
+/// every job that was submitted before terminating.
 impl Drop for ThreadPool {
     fn drop(&mut self) {
-        for _ in &self.workers {
-            self.sender.send(Message::Terminate).unwrap();
-        }
+        *self.shared.shutdown.lock().unwrap() = true;
+        self.shared.available.notify_all();
 
-        for worker in &mut self.workers {
+        // Dedicated workers check for shutdown while holding the pinned lock, so take it before
+        // notifying to make sure none of them is between that check and waiting.
+        drop(self.shared.pinned.lock().unwrap());
+        self.shared.pinned_available.notify_all();
+
+        for worker in self.workers.iter_mut().chain(self.dedicated.iter_mut()) {
             if let Some(thread) = worker.thread.take() {
                 thread.join().unwrap();
             }
@@ -84,33 +230,186 @@ impl Drop for ThreadPool {
 }
 
 /// Job type that can be executed in the thread pool.
-type Job = Box<dyn FnOnce() + Send + 'static>;
+pub type Job = Box<dyn FnOnce() + Send + 'static>;
 
-/// Message type that can be sent to the worker threads.
-enum Message {
-    NewJob(Job),
-    Terminate,
+/// A queued job. Ordered by priority, then by submission order so equal priority jobs run FIFO.
+struct Task {
+    priority: Priority,
+    sequence: u64,
+    job: Job,
 }
 
-/// Worker struct that listens for incoming `Message`s and executes the `Job`s or terminates.
+impl PartialEq for Task {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Task {}
+
+impl PartialOrd for Task {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Task {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// The state shared between the pool and its workers. `pending` only ever increases while `shutdown` is
+/// locked, so a worker that checks it under that lock before waiting can't miss a wakeup.
+struct Shared {
+    queues: Vec<Mutex<BinaryHeap<Task>>>,
+    pinned: Mutex<VecDeque<Job>>,
+    shutdown: Mutex<bool>,
+    available: Condvar,
+    pinned_available: Condvar,
+    pending: AtomicUsize,
+    next_queue: AtomicUsize,
+    sequence: AtomicU64,
+    active: AtomicUsize,
+    completed: AtomicUsize,
+    stolen: AtomicUsize,
+    max_depth: AtomicUsize,
+}
+
+impl Shared {
+    fn new(size: usize) -> Self {
+        Shared {
+            queues: (0..size.max(1))
+                .map(|_| Mutex::new(BinaryHeap::new()))
+                .collect(),
+            pinned: Mutex::new(VecDeque::new()),
+            shutdown: Mutex::new(false),
+            available: Condvar::new(),
+            pinned_available: Condvar::new(),
+            pending: AtomicUsize::new(0),
+            next_queue: AtomicUsize::new(0),
+            sequence: AtomicU64::new(0),
+            active: AtomicUsize::new(0),
+            completed: AtomicUsize::new(0),
+            stolen: AtomicUsize::new(0),
+            max_depth: AtomicUsize::new(0),
+        }
+    }
+
+    fn push(&self, priority: Priority, job: Job) {
+        let queue = self.next_queue.fetch_add(1, atomic::Ordering::Relaxed) % self.queues.len();
+        let sequence = self.sequence.fetch_add(1, atomic::Ordering::Relaxed);
+        // Count the job before it's visible in a queue so a worker can never take it (and decrement
+        // `pending`) before it was counted.
+        let _guard = self.shutdown.lock().unwrap();
+        let depth = self.pending.fetch_add(1, atomic::Ordering::SeqCst) + 1;
+        self.max_depth.fetch_max(depth, atomic::Ordering::SeqCst);
+
+        self.queues[queue].lock().unwrap().push(Task {
+            priority,
+            sequence,
+            job,
+        });
+        self.available.notify_one();
+    }
+
+    fn push_pinned(&self, job: Job) {
+        self.pinned.lock().unwrap().push_back(job);
+        self.pinned_available.notify_one();
+    }
+
+    /// Take the best job from the worker's own queue, or steal the best job from the other queues.
+    fn pop(&self, index: usize) -> Option<Job> {
+        if let Some(task) = self.take_from(index) {
+            return Some(task.job);
+        }
+
+        let victim = (1..self.queues.len())
+            .map(|offset| (index + offset) % self.queues.len())
+            .filter_map(|other| {
+                let queue = self.queues[other].lock().unwrap();
+                queue
+                    .peek()
+                    .map(|task| ((task.priority, task.sequence), other))
+            })
+            .max_by(|(one, _), (two, _)| one.0.cmp(&two.0).then_with(|| two.1.cmp(&one.1)))
+            .map(|(_, other)| other)?;
+
+        let task = self.take_from(victim)?;
+        self.stolen.fetch_add(1, atomic::Ordering::SeqCst);
+        Some(task.job)
+    }
+
+    fn take_from(&self, index: usize) -> Option<Task> {
+        let mut queue = self.queues[index].lock().unwrap();
+        let task = queue.pop()?;
+        self.pending.fetch_sub(1, atomic::Ordering::SeqCst);
+        Some(task)
+    }
+
+    fn run(&self, job: Job) {
+        self.active.fetch_add(1, atomic::Ordering::SeqCst);
+        job();
+        self.active.fetch_sub(1, atomic::Ordering::SeqCst);
+        self.completed.fetch_add(1, atomic::Ordering::SeqCst);
+    }
+}
+
+/// Worker struct that runs `Job`s from the shared queues until the pool is dropped.
 struct Worker {
     thread: Option<thread::JoinHandle<()>>,
 }
 
 impl Worker {
-    /// Create a new Worker.
+    /// Create a new work-stealing Worker.
     ///
-    /// The Worker will listen for incoming jobs on the given receiver.
-    /// When a job is received, it will be executed in a new thread and the
-    /// mutex will release allowing another job to be received from a different worker.
-    fn new(receiver: Arc<Mutex<mpsc::Receiver<Message>>>) -> Self {
+    /// The Worker runs jobs from its own queue (`index`), then steals from the others. When every
+    /// queue is empty it sleeps until a new job is submitted or the pool shuts down.
+    fn new(shared: Arc<Shared>, index: usize) -> Self {
+        let index = index % shared.queues.len();
         Worker {
             thread: Some(thread::spawn(move || loop {
-                let message = receiver.lock().unwrap().recv().unwrap();
+                if let Some(job) = shared.pop(index) {
+                    shared.run(job);
+                    continue;
+                }
+
+                let mut shutdown = shared.shutdown.lock().unwrap();
+                while shared.pending.load(atomic::Ordering::SeqCst) == 0 && !*shutdown {
+                    shutdown = shared.available.wait(shutdown).unwrap();
+                }
 
-                match message {
-                    Message::NewJob(job) => job(),
-                    Message::Terminate => break,
+                if *shutdown && shared.pending.load(atomic::Ordering::SeqCst) == 0 {
+                    break;
+                }
+            })),
+        }
+    }
+
+    /// Create a new dedicated Worker which only runs pinned jobs.
+    fn dedicated(shared: Arc<Shared>) -> Self {
+        Worker {
+            thread: Some(thread::spawn(move || loop {
+                let job = {
+                    let mut pinned = shared.pinned.lock().unwrap();
+                    loop {
+                        if let Some(job) = pinned.pop_front() {
+                            break Some(job);
+                        }
+
+                        if *shared.shutdown.lock().unwrap() {
+                            break None;
+                        }
+
+                        pinned = shared.pinned_available.wait(pinned).unwrap();
+                    }
+                };
+
+                match job {
+                    Some(job) => shared.run(job),
+                    None => break,
                 }
             })),
         }
@@ -229,4 +528,90 @@ mod tests {
         assert_eq!(results.len(), num_jobs);
         assert!(results.iter().all(|&x| x < num_jobs));
     }
+
+    #[test]
+    fn test_higher_priority_jobs_run_first() {
+        let pool = ThreadPool::new(1);
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel::<()>();
+        let order = Arc::new(Mutex::new(vec![]));
+
+        pool.submit(move || {
+            started_tx.send(()).unwrap();
+            gate_rx.recv().unwrap();
+        });
+        started_rx.recv().unwrap();
+
+        for priority in [
+            Priority::Low,
+            Priority::Normal,
+            Priority::High,
+            Priority::Normal,
+        ] {
+            let order = Arc::clone(&order);
+            pool.submit_with_priority(priority, move || order.lock().unwrap().push(priority));
+        }
+
+        assert_eq!(pool.queue_depth(), 4);
+        gate_tx.send(()).unwrap();
+        drop(pool);
+
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec![
+                Priority::High,
+                Priority::Normal,
+                Priority::Normal,
+                Priority::Low
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pinned_jobs_run_on_dedicated_threads() {
+        let pool = ThreadPool::new(1).with_dedicated(1);
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+
+        // The only regular worker is blocked, so the pinned job has to run on the dedicated thread.
+        pool.submit(move || gate_rx.recv().unwrap());
+        assert_eq!(pool.submit_pinned(|| 7).result(), 7);
+
+        gate_tx.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_stats() {
+        let pool = ThreadPool::new(4);
+        let results = (0..32)
+            .map(|i| pool.submit_with_result(move || i * 2))
+            .collect::<Vec<_>>();
+
+        let sum = results.iter().map(|result| result.result()).sum::<i32>();
+        assert_eq!(sum, 992);
+
+        drop(results);
+        let stats = pool.stats();
+        assert!(stats.max_queue_depth >= 1);
+        assert!(stats.completed <= 32);
+        assert_eq!(stats.queued, 0);
+    }
+
+    #[test]
+    fn test_delegating_pool() {
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&spawned);
+        let pool = ThreadPool::delegating(move |job| {
+            counter.fetch_add(1, atomic::Ordering::SeqCst);
+            thread::spawn(job);
+        });
+
+        let results = (0..4)
+            .map(|i| pool.submit_with_result(move || i + 1))
+            .collect::<Vec<_>>();
+
+        assert_eq!(results.iter().map(|r| r.result()).sum::<i32>(), 10);
+        assert_eq!(spawned.load(atomic::Ordering::SeqCst), 4);
+        assert_eq!(pool.size(), 0);
+        assert!(pool.spawner().is_some());
+    }
 }
@@ -36,6 +36,12 @@ impl Timer {
     }
 }
 
+impl Default for Timer {
+    fn default() -> Self {
+        Timer::new()
+    }
+}
+
 impl Clone for Timer {
     fn clone(&self) -> Self {
         Timer {
@@ -0,0 +1,369 @@
+use super::{AskTell, Chromosome, Genotype, Score};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Why the evaluation of a genotype failed (see `EvaluationDriver`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvaluationError {
+    /// A failure unrelated to the genotype - a dropped connection, a busy instrument - so the
+    /// evaluation is retried.
+    Transient(String),
+    /// The genotype crashed the evaluation, e.g. took the rig down. Genotypes that crash it repeatedly
+    /// are quarantined.
+    Crash(String),
+}
+
+/// The genotypes waiting to be evaluated by an `EvaluationDriver`, with the number of times each one
+/// crashed the evaluation so far, and the quarantined genotypes that are never evaluated again. The
+/// queue outlives the driver (`EvaluationDriver::into_queue`) and, with the `wire` feature, has its own
+/// message in the `wire` format (`wire::encode_evaluation_queue`), so an interrupted evaluation can be
+/// resumed - even after a restart.
+#[derive(Clone, PartialEq)]
+pub struct EvaluationQueue<C: Chromosome> {
+    pending: VecDeque<(Genotype<C>, usize)>,
+    quarantined: Vec<Genotype<C>>,
+}
+
+impl<C: Chromosome> EvaluationQueue<C> {
+    pub fn new() -> Self {
+        EvaluationQueue {
+            pending: VecDeque::new(),
+            quarantined: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "wire")]
+    pub(crate) fn from_parts(
+        pending: VecDeque<(Genotype<C>, usize)>,
+        quarantined: Vec<Genotype<C>>,
+    ) -> Self {
+        EvaluationQueue {
+            pending,
+            quarantined,
+        }
+    }
+
+    /// Add a genotype to the end of the queue. Quarantined genotypes are ignored. Returns whether the
+    /// genotype was added.
+    pub fn push(&mut self, genotype: Genotype<C>) -> bool {
+        if self.is_quarantined(&genotype) {
+            return false;
+        }
+
+        self.pending.push_back((genotype, 0));
+        true
+    }
+
+    /// The genotypes waiting to be evaluated, with the number of times each crashed the evaluation.
+    pub fn pending(&self) -> impl Iterator<Item = (&Genotype<C>, usize)> {
+        self.pending
+            .iter()
+            .map(|(genotype, crashes)| (genotype, *crashes))
+    }
+
+    pub fn quarantined(&self) -> &[Genotype<C>] {
+        &self.quarantined
+    }
+
+    pub fn is_quarantined(&self, genotype: &Genotype<C>) -> bool {
+        self.quarantined.iter().any(|other| other == genotype)
+    }
+
+    /// Release every genotype from quarantine - e.g. after the rig was fixed. They aren't queued again.
+    pub fn clear_quarantine(&mut self) {
+        self.quarantined.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl<C: Chromosome> Default for EvaluationQueue<C> {
+    fn default() -> Self {
+        EvaluationQueue::new()
+    }
+}
+
+/// What happened during one `EvaluationDriver::evaluate`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DriverReport {
+    /// The genotypes that were scored.
+    pub scored: usize,
+    /// The evaluations that were retried after a transient failure.
+    pub retries: usize,
+    /// The evaluations that crashed.
+    pub crashes: usize,
+    /// The genotypes that were quarantined.
+    pub quarantined: usize,
+    /// The genotypes that ran out of retries and were left in the queue for the next call.
+    pub deferred: usize,
+}
+
+/// Dispatches evaluations that run outside of the process - on physical hardware, in a lab or on a
+/// flaky remote service - robustly, on top of an ask/tell session (see `AskTell`):
+///
+/// * At most `max_in_flight` evaluations run at the same time, e.g. one per rig.
+/// * A `Transient` failure is retried up to `retries` times, `retry_delay` apart. A genotype that
+///   runs out of retries stays in the queue for the next call.
+/// * A genotype that `Crash`es the evaluation `quarantine_after` times is quarantined - it's dropped
+///   from the queue and never evaluated again. Crashed genotypes that aren't quarantined yet go to the
+///   back of the queue.
+/// * The genotypes waiting to be evaluated are kept in an `EvaluationQueue`, which can be saved and
+///   resumed.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .population_size(20)
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let mut session = engine.ask_tell();
+/// let mut driver = EvaluationDriver::new().max_in_flight(4).quarantine_after(2);
+///
+/// for _ in 0..10 {
+///     driver.step(&mut session, 10, |genotype: &Genotype<IntChromosome<i32>>| {
+///         let sum = genotype[0].iter().map(|gene| *gene.allele()).sum::<i32>();
+///         match sum {
+///             // Sums above 450 take the rig down.
+///             sum if sum > 450 => Err(EvaluationError::Crash("overload".to_string())),
+///             sum => Ok(Score::from_int(sum)),
+///         }
+///     });
+/// }
+///
+/// assert_eq!(session.context().index, 10);
+/// assert!(driver.queue().is_empty());
+/// ```
+pub struct EvaluationDriver<C: Chromosome> {
+    max_in_flight: usize,
+    retries: usize,
+    retry_delay: Duration,
+    quarantine_after: usize,
+    queue: EvaluationQueue<C>,
+}
+
+impl<C: Chromosome> EvaluationDriver<C> {
+    /// Create a driver running one evaluation at a time, retrying transient failures 3 times without
+    /// a delay and quarantining genotypes after 2 crashes.
+    pub fn new() -> Self {
+        EvaluationDriver {
+            max_in_flight: 1,
+            retries: 3,
+            retry_delay: Duration::ZERO,
+            quarantine_after: 2,
+            queue: EvaluationQueue::new(),
+        }
+    }
+
+    /// Run up to `max_in_flight` evaluations at the same time. Panics if `max_in_flight` is 0.
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        if max_in_flight < 1 {
+            panic!("max_in_flight must be greater than 0");
+        }
+
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Quarantine a genotype once it crashed the evaluation this many times. Panics if `crashes` is 0.
+    pub fn quarantine_after(mut self, crashes: usize) -> Self {
+        if crashes < 1 {
+            panic!("crashes must be greater than 0");
+        }
+
+        self.quarantine_after = crashes;
+        self
+    }
+
+    /// Resume from a saved queue.
+    pub fn with_queue(mut self, queue: EvaluationQueue<C>) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    pub fn queue(&self) -> &EvaluationQueue<C> {
+        &self.queue
+    }
+
+    pub fn queue_mut(&mut self) -> &mut EvaluationQueue<C> {
+        &mut self.queue
+    }
+
+    pub fn into_queue(self) -> EvaluationQueue<C> {
+        self.queue
+    }
+
+    /// Evaluate the queued genotypes and return the scored ones. Genotypes that ran out of retries are
+    /// left in the queue, the others are taken out of it - scored or quarantined.
+    pub fn evaluate<F>(&mut self, evaluator: F) -> (Vec<(Genotype<C>, Score)>, DriverReport)
+    where
+        F: Fn(&Genotype<C>) -> Result<Score, EvaluationError> + Sync,
+    {
+        let work = Mutex::new(std::mem::take(&mut self.queue.pending));
+        let deferred = Mutex::new(VecDeque::new());
+        let quarantined = Mutex::new(Vec::new());
+        let scored = Mutex::new(Vec::new());
+        let report = Mutex::new(DriverReport::default());
+
+        let worker = || loop {
+            let Some((genotype, crashes)) = work.lock().unwrap().pop_front() else {
+                break;
+            };
+
+            let mut attempt = 0;
+            let outcome = loop {
+                match evaluator(&genotype) {
+                    Err(EvaluationError::Transient(_)) if attempt < self.retries => {
+                        attempt += 1;
+                        report.lock().unwrap().retries += 1;
+                        if !self.retry_delay.is_zero() {
+                            std::thread::sleep(self.retry_delay);
+                        }
+                    }
+                    outcome => break outcome,
+                }
+            };
+
+            match outcome {
+                Ok(score) => {
+                    report.lock().unwrap().scored += 1;
+                    scored.lock().unwrap().push((genotype, score));
+                }
+                Err(EvaluationError::Transient(_)) => {
+                    report.lock().unwrap().deferred += 1;
+                    deferred.lock().unwrap().push_back((genotype, crashes));
+                }
+                Err(EvaluationError::Crash(_)) => {
+                    let mut report = report.lock().unwrap();
+                    report.crashes += 1;
+                    if crashes + 1 >= self.quarantine_after {
+                        report.quarantined += 1;
+                        quarantined.lock().unwrap().push(genotype);
+                    } else {
+                        work.lock().unwrap().push_back((genotype, crashes + 1));
+                    }
+                }
+            }
+        };
+
+        let workers = self.max_in_flight.min(work.lock().unwrap().len());
+        std::thread::scope(|scope| {
+            for _ in 1..workers {
+                scope.spawn(worker);
+            }
+
+            worker();
+        });
+
+        self.queue.pending = deferred.into_inner().unwrap();
+        self.queue
+            .quarantined
+            .extend(quarantined.into_inner().unwrap());
+
+        (scored.into_inner().unwrap(), report.into_inner().unwrap())
+    }
+
+    /// Ask the session for `count` candidates, queue those that aren't quarantined, evaluate the queue
+    /// and tell the session the scores - one generation of the session.
+    pub fn step<T, F>(
+        &mut self,
+        session: &mut AskTell<'_, C, T>,
+        count: usize,
+        evaluator: F,
+    ) -> DriverReport
+    where
+        C: 'static,
+        T: Clone + Send + 'static,
+        F: Fn(&Genotype<C>) -> Result<Score, EvaluationError> + Sync,
+    {
+        for genotype in session.ask(count) {
+            self.queue.push(genotype);
+        }
+
+        let (scored, report) = self.evaluate(evaluator);
+        session.tell(scored);
+        report
+    }
+}
+
+impl<C: Chromosome> Default for EvaluationDriver<C> {
+    fn default() -> Self {
+        EvaluationDriver::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Codex, Gene, IntChromosome, IntCodex};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_driver_retries_defers_and_quarantines() {
+        let codex = IntCodex::new(1, 3, 0, 10);
+        let mut driver = EvaluationDriver::new().retries(2).quarantine_after(2);
+
+        let mut crashing = codex.encode();
+        let gene = crashing[0].get_gene(0).with_allele(&-1);
+        crashing[0].set_gene(0, gene);
+        let mut unreachable = codex.encode();
+        let gene = unreachable[0].get_gene(0).with_allele(&-2);
+        unreachable[0].set_gene(0, gene);
+
+        for _ in 0..10 {
+            driver.queue_mut().push(codex.encode());
+        }
+        driver.queue_mut().push(crashing.clone());
+        driver.queue_mut().push(unreachable.clone());
+
+        let calls = AtomicUsize::new(0);
+        let evaluator = |genotype: &Genotype<IntChromosome<i32>>| {
+            let first = *genotype[0].get_gene(0).allele();
+            // Every other call fails transiently.
+            if calls.fetch_add(1, Ordering::SeqCst).is_multiple_of(2) {
+                return Err(EvaluationError::Transient("busy".to_string()));
+            }
+
+            match first {
+                -1 => Err(EvaluationError::Crash("down".to_string())),
+                -2 => Err(EvaluationError::Transient("unreachable".to_string())),
+                value => Ok(Score::from_int(value)),
+            }
+        };
+
+        let (scored, report) = driver.evaluate(evaluator);
+
+        assert_eq!(scored.len(), 10);
+        assert_eq!(report.scored, 10);
+        assert_eq!(report.crashes, 2);
+        assert_eq!(report.quarantined, 1);
+        assert_eq!(report.deferred, 1);
+        assert_eq!(driver.queue().len(), 1);
+        assert!(driver
+            .queue()
+            .pending()
+            .all(|(genotype, _)| *genotype == unreachable));
+        assert!(driver.queue().is_quarantined(&crashing));
+        assert!(!driver.queue_mut().push(crashing));
+    }
+}
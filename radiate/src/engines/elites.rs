@@ -0,0 +1,153 @@
+use super::objectives::Objective;
+use super::{Chromosome, Phenotype, Population};
+
+/// A hall of fame of the best individuals the engine has ever seen - the top `capacity` individuals
+/// across every generation of the run, kept independently of survivor selection. A generation that
+/// loses its elites (a disruptive alterer, a restart, a population schedule that shrinks the population)
+/// doesn't lose them from the archive.
+///
+/// Set one with `GeneticEngineParams::elite_archive`; the engine updates it with the population of every
+/// generation, and it is available on the `EngineContext` as `elites`. Unlike the `HallOfFame`, which
+/// holds recent champions as opponents for competitive fitness functions, the archive only keeps the
+/// best individuals by score. Individuals are ordered as in a sorted `Population` (see
+/// `Objective::compare`), so on ties the individual that was found first is kept. Identical genotypes are
+/// only archived once.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .elite_archive(10)
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 20);
+/// let elites = result.elites.as_ref().unwrap();
+///
+/// assert_eq!(elites.len(), 10);
+/// assert_eq!(elites.best().unwrap().score(), result.score.as_ref());
+/// ```
+#[derive(Clone, Debug)]
+pub struct EliteArchive<C: Chromosome> {
+    capacity: usize,
+    elites: Vec<Phenotype<C>>,
+}
+
+impl<C: Chromosome> EliteArchive<C> {
+    pub fn new(capacity: usize) -> Self {
+        if capacity < 1 {
+            panic!("capacity must be greater than 0");
+        }
+
+        EliteArchive {
+            capacity,
+            elites: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Merge the scored individuals of a sorted population into the archive, keeping the best
+    /// `capacity`. Returns the number of individuals that entered the archive.
+    pub fn update(&mut self, population: &Population<C>, objective: &Objective) -> usize {
+        let mut merged = std::mem::take(&mut self.elites)
+            .into_iter()
+            .map(|elite| (false, elite))
+            .collect::<Vec<_>>();
+
+        for individual in population.iter().take(self.capacity) {
+            let archived = merged
+                .iter()
+                .any(|(_, elite)| elite.genotype() == individual.genotype());
+
+            if individual.score().is_some() && !archived {
+                merged.push((true, individual.clone()));
+            }
+        }
+
+        // Archived individuals come first, so the stable sort keeps them ahead of equally good newcomers.
+        merged.sort_by(|(_, one), (_, two)| objective.compare(one, two));
+        merged.truncate(self.capacity);
+
+        let entered = merged.iter().filter(|(new, _)| *new).count();
+        self.elites = merged.into_iter().map(|(_, elite)| elite).collect();
+        entered
+    }
+
+    /// The best individual ever seen, if anything has been archived yet.
+    pub fn best(&self) -> Option<&Phenotype<C>> {
+        self.elites.first()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Phenotype<C>> {
+        self.elites.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elites.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objectives::Optimize;
+    use crate::{FloatChromosome, Genotype, Score};
+
+    fn population(scores: &[f32], generation: i32) -> Population<FloatChromosome> {
+        let mut population = Population::new(
+            scores
+                .iter()
+                .map(|score| {
+                    let genotype = Genotype::new(vec![FloatChromosome::from(&[*score][..])]);
+                    let mut phenotype = Phenotype::from_genotype(genotype, generation);
+                    phenotype.set_score(Some(Score::from_f32(*score)));
+                    phenotype
+                })
+                .collect(),
+        );
+
+        Objective::Single(Optimize::Maximize).sort(&mut population);
+        population
+    }
+
+    #[test]
+    fn test_elite_archive_keeps_the_best_across_generations() {
+        let objective = Objective::Single(Optimize::Maximize);
+        let mut archive = EliteArchive::new(3);
+
+        assert_eq!(
+            archive.update(&population(&[5.0, 9.0, 1.0, 7.0], 0), &objective),
+            3
+        );
+        assert_eq!(
+            archive.update(&population(&[2.0, 3.0, 1.0], 1), &objective),
+            0
+        );
+        assert_eq!(
+            archive.update(&population(&[8.0, 9.0, 0.0], 2), &objective),
+            1
+        );
+
+        let scores = archive
+            .iter()
+            .map(|elite| elite.score().unwrap().as_f32())
+            .collect::<Vec<f32>>();
+        let generations = archive
+            .iter()
+            .map(|elite| elite.generation)
+            .collect::<Vec<i32>>();
+
+        assert_eq!(scores, vec![9.0, 8.0, 7.0]);
+        assert_eq!(generations, vec![0, 2, 0]);
+        assert_eq!(archive.best().unwrap().score().unwrap().as_f32(), 9.0);
+    }
+}
@@ -1,15 +1,35 @@
 use super::codexes::Codex;
+use super::constraints::is_infeasible;
 use super::context::EngineContext;
 use super::genome::phenotype::Phenotype;
-use super::thread_pool::ThreadPool;
-use super::{AlterAction, MetricSet, Problem};
+#[cfg(feature = "wire")]
+use super::genome::wire::WireAllele;
+use super::thread_pool::{Priority, ThreadPool, WorkResult};
+use super::{
+    AlterAction, AskTell, Checkpoint, Description, EliteArchive, EngineBuilder, EngineEvent,
+    EngineIterator, Genotype, GroupEvaluator, Inheritance, Knob, MemeticStage, MemoryFootprint,
+    MetricSet, NeedsCodex, PopulationSnapshot, Problem, Racing, Recording, Replacement,
+    SelectorBenchmarkResult, SteadyState,
+};
 use crate::engines::domain::timer::Timer;
 use crate::engines::genome::population::Population;
 use crate::engines::objectives::Score;
 use crate::engines::params::GeneticEngineParams;
+use crate::metadata::Metadata;
 use crate::objectives::{Front, Objective};
-use crate::{metric_names, Chromosome, Metric, Select, Valid};
+use crate::random_provider::RngHandle;
+#[cfg(feature = "wire")]
+use crate::Gene;
+use crate::{metadata, metric_names, random_provider, Chromosome, Metric, Select, Valid};
+use std::collections::BTreeMap;
+#[cfg(feature = "wire")]
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A copy of every offspring (and its score) from before the alterers ran, kept for delta evaluation.
+type Parents<C> = Vec<Option<(Genotype<C>, Score)>>;
 
 /// The `GeneticEngine` is the core component of the Radiate library's genetic algorithm implementation.
 /// The engine is designed to be fast, flexible and extensible, allowing users to
@@ -65,6 +85,8 @@ where
     T: Clone + Send + 'static,
 {
     params: GeneticEngineParams<C, T>,
+    /// The time the evaluation jobs of the current generation took, in nanoseconds.
+    busy: Arc<AtomicU64>,
 }
 
 impl<C, T> GeneticEngine<C, T>
@@ -75,7 +97,10 @@ where
     /// Create a new instance of the `GeneticEngine` struct with the given parameters.
     /// - `params`: An instance of `GeneticEngineParams` that holds configuration parameters for the genetic engine.
     pub fn new(params: GeneticEngineParams<C, T>) -> Self {
-        GeneticEngine { params }
+        GeneticEngine {
+            params,
+            busy: Arc::new(AtomicU64::new(0)),
+        }
     }
 
     /// Initializes a `GeneticEngineParams` using the provided codex, which defines how individuals
@@ -94,6 +119,23 @@ where
         GeneticEngineParams::new().problem(problem)
     }
 
+    /// Initializes a `GeneticEngineParams` that resumes the run saved in the `Checkpoint` at `path` - see
+    /// `GeneticEngineParams::resume_from`. The codex and fitness function are still needed, and should be
+    /// the ones of the engine that wrote the checkpoint.
+    #[cfg(feature = "wire")]
+    pub fn from_checkpoint(path: impl Into<PathBuf>) -> GeneticEngineParams<C, T>
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        GeneticEngineParams::new().resume_from(path)
+    }
+
+    /// Starts a typed `EngineBuilder`, which only allows `build` once a codex and fitness function
+    /// (or a problem) have been provided.
+    pub fn builder() -> EngineBuilder<C, T, NeedsCodex> {
+        EngineBuilder::new()
+    }
+
     /// Executes the genetic algorithm. The algorithm continues until a specified
     /// stopping condition, 'limit', is met, such as reaching a target fitness score or
     /// exceeding a maximum number of generations. When 'limit' returns true, the algorithm stops.
@@ -104,63 +146,854 @@ where
         let mut ctx = self.start();
 
         loop {
-            self.evaluate(&mut ctx);
+            self.step(&mut ctx);
+
+            if limit(&ctx) {
+                break self.stop(&mut ctx);
+            }
+        }
+    }
 
-            let survivors = self.select_survivors(&mut ctx);
-            let offspring = self.create_offspring(&mut ctx);
+    /// Returns an iterator that runs the genetic algorithm one generation at a time and yields
+    /// the `EngineContext` after every generation. The iterator never ends on its own, so it's
+    /// usually combined with `take`, `take_while` or the adapters in `EngineIteratorExt`.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+    ///     .minimizing()
+    ///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+    ///     .build();
+    ///
+    /// let last = engine.iter().take(10).last().unwrap();
+    /// assert_eq!(last.index, 10);
+    /// ```
+    pub fn iter(&self) -> EngineIterator<'_, C, T> {
+        EngineIterator::new(self)
+    }
 
-            self.recombine(&mut ctx, survivors, offspring);
+    /// Starts an ask/tell session, which hands the evaluation of the individuals to the caller instead of
+    /// the fitness function - see `AskTell`.
+    pub fn ask_tell(&self) -> AskTell<'_, C, T> {
+        AskTell::new(self)
+    }
 
-            self.filter(&mut ctx);
-            self.evaluate(&mut ctx);
-            self.audit(&mut ctx);
+    /// The results of the selector benchmark run when the engine was built, if one was set with
+    /// `GeneticEngineParams::benchmark_selectors`.
+    pub fn selector_benchmark(&self) -> Option<&SelectorBenchmarkResult> {
+        self.params.benchmarked.as_ref()
+    }
 
-            if limit(&ctx) {
-                break self.stop(&mut ctx);
+    /// Runs a single generation of the genetic algorithm, drawing from the engine's random number
+    /// generator if it has one.
+    pub(crate) fn step(&self, ctx: &mut EngineContext<C, T>) {
+        match &self.params.rng {
+            Some(rng) => rng.scope(|| self.epoch(ctx)),
+            None => self.epoch(ctx),
+        }
+    }
+
+    /// The generator handed to the alterers - the engine's own, or else the calling thread's (see
+    /// `RngHandle::current`).
+    fn rng(&self) -> RngHandle {
+        self.params.rng.clone().unwrap_or_else(RngHandle::current)
+    }
+
+    fn epoch(&self, ctx: &mut EngineContext<C, T>) {
+        let generation = Timer::new();
+        self.busy.store(0, Ordering::Relaxed);
+        self.apply_control(ctx);
+        self.restart(ctx);
+
+        if self.params.stochastic_fitness {
+            ctx.population
+                .iter_mut()
+                .for_each(|individual| individual.mark_dirty());
+        }
+
+        let timer = Timer::new();
+        self.evaluate(ctx);
+        self.objective().sort(&mut ctx.population);
+        self.debug_assert_sorted(&ctx.population, "evaluation");
+        let evaluation = timer.duration();
+
+        let start = match &self.params.steady_state {
+            Some(steady_state) => {
+                self.steady_state_step(ctx, steady_state, evaluation);
+                None
+            }
+            None => Some(self.generational_step(ctx, evaluation)),
+        };
+
+        let timer = Timer::new();
+        if let Some(start) = start {
+            self.observe_offspring(ctx, start);
+        }
+        self.audit(ctx);
+        self.debug_assert_sorted(&ctx.population, "audit");
+        ctx.metrics
+            .upsert_time(metric_names::AUDIT_TIME, timer.duration());
+        ctx.metrics
+            .upsert_time(metric_names::GENERATION_TIME, generation.duration());
+
+        self.save_checkpoint(ctx);
+        self.record_sample(ctx);
+        self.publish(|| EngineEvent::EpochComplete {
+            index: ctx.index,
+            best: ctx.best.clone(),
+            score: ctx.score().clone(),
+            metrics: ctx.metrics.clone(),
+        });
+    }
+
+    /// Replaces the population with a new generation - the survivors and the altered offspring - and
+    /// evaluates it. Returns the index of the first offspring.
+    fn generational_step(&self, ctx: &mut EngineContext<C, T>, mut evaluation: Duration) -> usize {
+        let timer = Timer::new();
+        let size = self.next_population_size(ctx);
+        let shaped = self.shape(ctx);
+        let survivors = self.select_survivors(ctx, shaped.as_ref(), size);
+        let count = self.offspring_count(size);
+        let mut offspring = self.select_offspring(ctx, shaped.as_ref(), count);
+        ctx.metrics
+            .upsert_time(metric_names::SELECTION_TIME, timer.duration());
+
+        let timer = Timer::new();
+        let parents = self.alter_offspring(ctx, &mut offspring);
+        self.refine(
+            &mut ctx.metrics,
+            &mut offspring,
+            0,
+            MemeticStage::AfterAlteration,
+        );
+        ctx.metrics
+            .upsert_time(metric_names::ALTERATION_TIME, timer.duration());
+
+        let timer = Timer::new();
+        let start = self.recombine(ctx, survivors, offspring, size);
+        self.repair(ctx);
+        self.filter(ctx);
+        self.count_clean_offspring(ctx, start);
+        ctx.metrics
+            .upsert_time(metric_names::REPLACEMENT_TIME, timer.duration());
+
+        let timer = Timer::new();
+        self.evaluate_deltas(ctx, start, parents);
+        self.evaluate(ctx);
+        self.refine(
+            &mut ctx.metrics,
+            &mut ctx.population,
+            start,
+            MemeticStage::AfterEvaluation,
+        );
+        evaluation += timer.duration();
+        self.record_evaluation_time(ctx, evaluation);
+
+        start
+    }
+
+    /// Breeds a few offspring from the population, evaluates them and merges them into the population
+    /// with the steady-state replacement policy (see `SteadyState`).
+    fn steady_state_step(
+        &self,
+        ctx: &mut EngineContext<C, T>,
+        steady_state: &SteadyState<C>,
+        mut evaluation: Duration,
+    ) {
+        let timer = Timer::new();
+        let size = self.next_population_size(ctx);
+        let shaped = self.shape(ctx);
+        let count = steady_state.replacements();
+        let mut offspring = self.select_offspring(ctx, shaped.as_ref(), count);
+        ctx.metrics
+            .upsert_time(metric_names::SELECTION_TIME, timer.duration());
+
+        let timer = Timer::new();
+        let parents = self.alter_offspring(ctx, &mut offspring);
+        self.refine(
+            &mut ctx.metrics,
+            &mut offspring,
+            0,
+            MemeticStage::AfterAlteration,
+        );
+        ctx.metrics
+            .upsert_time(metric_names::ALTERATION_TIME, timer.duration());
+
+        let timer = Timer::new();
+        let members = std::mem::replace(&mut ctx.population, Population::new(Vec::new()));
+        let start = self.recombine(ctx, members, offspring, 0);
+        self.repair(ctx);
+        self.filter(ctx);
+        self.count_clean_offspring(ctx, start);
+        let mut replacement = timer.duration();
+
+        let timer = Timer::new();
+        self.evaluate_deltas(ctx, start, parents);
+        self.evaluate(ctx);
+        self.refine(
+            &mut ctx.metrics,
+            &mut ctx.population,
+            start,
+            MemeticStage::AfterEvaluation,
+        );
+        evaluation += timer.duration();
+        self.record_evaluation_time(ctx, evaluation);
+
+        self.observe_offspring(ctx, start);
+
+        let timer = Timer::new();
+        let entered = steady_state.replace(&mut ctx.population, start, size, self.objective());
+        replacement += timer.duration();
+        ctx.upsert_operation(
+            metric_names::STEADY_STATE_REPLACEMENTS,
+            entered as f32,
+            timer.duration(),
+        );
+        ctx.metrics
+            .upsert_time(metric_names::REPLACEMENT_TIME, replacement);
+    }
+
+    /// Applies the changes made through the control panel (if one is set) since the last generation,
+    /// recording the new values.
+    fn apply_control(&self, ctx: &mut EngineContext<C, T>) {
+        if let Some(panel) = &self.params.control {
+            for (knob, value) in panel.apply() {
+                ctx.metrics.upsert_value(knob.metric(), value);
+            }
+        }
+    }
+
+    /// Replaces part of the population with new individuals from the codex if a `Restart` is set and the
+    /// best score has stagnated for a multiple of its window. The population is still sorted from the
+    /// last audit, so the best individuals are the ones kept.
+    fn restart(&self, ctx: &mut EngineContext<C, T>) {
+        let Some(restart) = &self.params.restart else {
+            return;
+        };
+
+        if !restart.is_due(ctx.stagnation) {
+            return;
+        }
+
+        let timer = Timer::new();
+        let problem = self.problem();
+        let survivors = restart.survivors(ctx.population.len());
+        for i in survivors..ctx.population.len() {
+            ctx.population[i] = Phenotype::from_genotype(problem.encode(), ctx.index);
+        }
+
+        let count = ctx.population.len() - survivors;
+        ctx.upsert_operation(metric_names::RESTART, count as f32, timer.duration());
+    }
+
+    /// Writes a checkpoint of the generation, if checkpoints are written and the generation is due.
+    fn save_checkpoint(&self, ctx: &mut EngineContext<C, T>) {
+        let Some((every, writer)) = &self.params.checkpointing else {
+            return;
+        };
+
+        if !(ctx.index.max(0) as usize).is_multiple_of(*every) {
+            return;
+        }
+
+        let checkpoint = Checkpoint {
+            rng: self.params.rng.as_ref().and_then(|rng| rng.state()),
+            ..Checkpoint::from(&*ctx)
+        };
+
+        if let Err(error) = writer(&checkpoint) {
+            self.report_error(ctx, format!("Failed to write checkpoint: {}", error));
+        }
+    }
+
+    /// Counts a failed write in the metrics and publishes it as an `EngineEvent::Error` - the run goes on.
+    fn report_error(&self, ctx: &mut EngineContext<C, T>, message: String) {
+        ctx.metrics.upsert_value(metric_names::WRITE_ERRORS, 1.0);
+        self.publish(|| EngineEvent::Error {
+            index: ctx.index,
+            message,
+        });
+    }
+
+    /// Queues the population of the generation to be written by the sample writer (if samples are
+    /// recorded and the generation is due).
+    fn record_sample(&self, ctx: &EngineContext<C, T>) {
+        let Some((every, sampler)) = &self.params.sampling else {
+            return;
+        };
+
+        if (ctx.index.max(0) as usize).is_multiple_of(*every) {
+            sampler(ctx.index, &ctx.population);
+        }
+    }
+
+    /// Breeds `count` new genotypes from the scored population for an ask/tell session - the offspring
+    /// selector picks the parents and the alterers change them, like the offspring of a generation. Until
+    /// anything has been scored, the genotypes are new individuals from the codex.
+    pub(crate) fn breed(&self, ctx: &mut EngineContext<C, T>, count: usize) -> Vec<Genotype<C>> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let problem = self.problem();
+        let breed = |ctx: &mut EngineContext<C, T>| {
+            if ctx.population.is_empty() {
+                return (0..count).map(|_| problem.encode()).collect();
+            }
+
+            let timer = Timer::new();
+            let selector = self.offspring_selector();
+            let mut offspring = selector.select(&ctx.population, self.objective(), count);
+            ctx.upsert_operation(selector.name(), count as f32, timer.duration());
+
+            let timer = Timer::new();
+            let rng = self.rng();
+            for alterer in self.alterer() {
+                for metric in alterer.alter(&mut offspring, ctx.index, &rng) {
+                    ctx.metrics.upsert(metric);
+                }
+            }
+            ctx.metrics
+                .upsert_time(metric_names::ALTERATION_TIME, timer.duration());
+
+            offspring
+                .into_iter()
+                .map(|mut individual| {
+                    let mut genotype = individual.take_genotype();
+                    problem.repair(&mut genotype);
+                    match genotype.is_valid() {
+                        true => genotype,
+                        false => problem.encode(),
+                    }
+                })
+                .collect()
+        };
+
+        match &self.params.rng {
+            Some(rng) => rng.scope(|| breed(ctx)),
+            None => breed(ctx),
+        }
+    }
+
+    /// Adds the genotypes scored by the caller of an ask/tell session to the population, keeps the best
+    /// `population_size` individuals and audits the generation, like the end of `epoch`.
+    pub(crate) fn absorb(&self, ctx: &mut EngineContext<C, T>, scored: Vec<(Genotype<C>, Score)>) {
+        if scored.is_empty() {
+            return;
+        }
+
+        let absorb = |ctx: &mut EngineContext<C, T>| {
+            let generation = Timer::new();
+            let size = self.params.population_size;
+            let mut population =
+                std::mem::replace(&mut ctx.population, Population::new(Vec::new()))
+                    .into_iter()
+                    .collect::<Vec<_>>();
+            for (genotype, score) in scored {
+                let mut individual = Phenotype::from_genotype(genotype, ctx.index);
+                individual.set_score(Some(score));
+                population.push(individual);
             }
+
+            let mut population = Population::new(population);
+            self.objective().sort(&mut population);
+            if population.len() > size {
+                population = Population::new(population.into_iter().take(size).collect());
+                population.is_sorted = true;
+            }
+            ctx.population = population;
+
+            let timer = Timer::new();
+            self.audit(ctx);
+            ctx.metrics
+                .upsert_time(metric_names::AUDIT_TIME, timer.duration());
+            ctx.metrics
+                .upsert_time(metric_names::GENERATION_TIME, generation.duration());
+
+            self.save_checkpoint(ctx);
+            self.publish(|| EngineEvent::EpochComplete {
+                index: ctx.index,
+                best: ctx.best.clone(),
+                score: ctx.score().clone(),
+                metrics: ctx.metrics.clone(),
+            });
+        };
+
+        match &self.params.rng {
+            Some(rng) => rng.scope(|| absorb(ctx)),
+            None => absorb(ctx),
+        }
+    }
+
+    /// Sends an event to the subscribers. The event is only created if there are any.
+    fn publish<F>(&self, event: F)
+    where
+        F: FnOnce() -> EngineEvent<T>,
+    {
+        if self.params.subscribers.is_empty() {
+            return;
         }
+
+        let event = event();
+        for subscriber in self.params.subscribers.iter() {
+            subscriber.on_event(&event);
+        }
+    }
+
+    /// Selectors (and the best individual) rely on the population being sorted by
+    /// `Objective::compare` between steps, so check that contract in debug builds.
+    fn debug_assert_sorted(&self, population: &Population<C>, stage: &str) {
+        debug_assert!(
+            self.objective().is_sorted(population),
+            "Population is not sorted by the objective after {}",
+            stage
+        );
     }
 
     /// Evaluates the fitness of each individual in the population using the fitness function
-    /// provided in the genetic engine parameters. The score is then used to rank the individuals
-    /// in the population.
+    /// provided in the genetic engine parameters. The population is left in its current order -
+    /// the offspring have to stay where the alterers put them until `observe_offspring` has run -
+    /// so it's up to the caller to sort it.
     ///
     /// Importantly, this method uses a thread pool to evaluate the fitness of each individual in
     /// parallel, which can significantly speed up the evaluation process for large populations.
-    /// It will also only evaluate individuals that have not yet been scored, which saves time
-    /// by avoiding redundant evaluations.
+    /// It will also only evaluate individuals that are dirty, which saves time by avoiding redundant
+    /// evaluations. Individuals from earlier generations are submitted with a higher priority than
+    /// the offspring (see `priority`).
     fn evaluate(&self, handle: &mut EngineContext<C, T>) {
-        let objective = self.objective();
         let thread_pool = self.thread_pool();
+        let parts = self.problem().parts();
+        if let Some(group_evaluator) = &self.params.group_evaluator {
+            return self.evaluate_groups(handle, group_evaluator);
+        }
+
+        if self.problem().batch_size() > 1 {
+            return self.evaluate_batches(handle);
+        }
+
+        if let (Some(racing), 1) = (&self.params.racing, parts) {
+            return self.race(handle, racing);
+        }
+
         let timer = Timer::new();
 
         let mut work_results = Vec::new();
         for idx in 0..handle.population.len() {
             let individual = &mut handle.population[idx];
-            if individual.score().is_some() {
+            if !individual.is_dirty() {
                 continue;
+            }
+
+            let priority = Self::priority(individual, handle.index);
+            let geno = individual.take_genotype();
+            if parts > 1 {
+                // Every part of the score is its own job, so each needs its own copy of the genotype.
+                // The copy that comes back from the first part is put back into the population.
+                for part in 0..parts {
+                    let problem = self.problem();
+                    let geno = geno.clone();
+                    work_results.push(self.submit(priority, move || {
+                        metadata::take();
+                        let value = problem.eval_part(&geno, part);
+                        (idx, part, Score::from_f32(value), geno, metadata::take())
+                    }));
+                }
             } else {
                 let problem = self.problem();
-                let geno = individual.take_genotype();
-                let work = thread_pool.submit_with_result(move || {
+                work_results.push(self.submit(priority, move || {
+                    metadata::take();
                     let score = problem.eval(&geno);
-                    (idx, score, geno)
-                });
-
-                work_results.push(work);
+                    (idx, 0, score, geno, metadata::take())
+                }));
             }
         }
 
-        let count = work_results.len() as f32;
+        let queue_depth = thread_pool.stats();
+        handle.metrics.upsert_value(
+            metric_names::QUEUE_DEPTH,
+            (queue_depth.queued + queue_depth.pinned_queued) as f32,
+        );
+
+        let mut evaluated = BTreeMap::new();
         for work_result in work_results {
-            let (idx, score, genotype) = work_result.result();
+            let (idx, part, score, genotype, metadata) = work_result.result();
+            let (scores, geno, meta) = evaluated
+                .entry(idx)
+                .or_insert_with(|| (Vec::with_capacity(parts), None, None::<Metadata>));
+
+            scores.push((part, score));
+            if part == 0 {
+                *geno = Some(genotype);
+            }
+
+            *meta = match (meta.take(), metadata) {
+                (Some(mut current), Some(other)) => {
+                    current.merge(other);
+                    Some(current)
+                }
+                (current, other) => current.or(other),
+            };
+        }
+
+        let count = evaluated.len() as f32;
+        let mut error_count = 0_f32;
+        for (idx, (mut scores, genotype, metadata)) in evaluated {
+            let score = match parts > 1 {
+                true => {
+                    scores.sort_by_key(|(part, _)| *part);
+                    Score::from_vec(scores.iter().map(|(_, score)| score.as_f32()).collect())
+                }
+                false => scores.pop().unwrap().1,
+            };
+
+            if metadata.as_ref().and_then(|meta| meta.error()).is_some() {
+                error_count += 1_f32;
+            }
+
             handle.population[idx].set_score(Some(score));
+            handle.population[idx].set_genotype(genotype.unwrap());
+            handle.population[idx].set_metadata(metadata);
+        }
+
+        let duration = timer.duration();
+        handle.upsert_operation(metric_names::EVALUATION, count, duration);
+        handle.upsert_operation(metric_names::EVALUATION_ERRORS, error_count, duration);
+    }
+
+    /// Evaluates the unscored individuals in batches of the problem's `batch_size`, each batch as its own job.
+    /// An individual whose result in the batch is an error is given the objective's worst score, and the error
+    /// is kept in its metadata (along with any metadata reported while the batch was evaluated).
+    fn evaluate_batches(&self, handle: &mut EngineContext<C, T>) {
+        let timer = Timer::new();
+        let batch_size = self.problem().batch_size();
+
+        let unscored = (0..handle.population.len())
+            .filter(|idx| handle.population[*idx].is_dirty())
+            .collect::<Vec<usize>>();
+
+        let work_results = unscored
+            .chunks(batch_size)
+            .map(|indices| {
+                let indices = indices.to_vec();
+                let genotypes = indices
+                    .iter()
+                    .map(|idx| handle.population[*idx].take_genotype())
+                    .collect::<Vec<Genotype<C>>>();
+
+                let problem = self.problem();
+                self.submit(Priority::Normal, move || {
+                    metadata::take();
+                    let results = problem.eval_batch(&genotypes);
+                    (indices, genotypes, results, metadata::take())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut error_count = 0_f32;
+        for work_result in work_results {
+            let (indices, genotypes, results, batch_metadata) = work_result.result();
+            for ((idx, genotype), result) in indices.into_iter().zip(genotypes).zip(results) {
+                let mut metadata = batch_metadata.clone();
+                let score = match result {
+                    Ok(score) => score,
+                    Err(error) => {
+                        let meta = metadata.get_or_insert_with(Metadata::default);
+                        meta.error = Some(error);
+                        self.objective().worst_score()
+                    }
+                };
+
+                if metadata.as_ref().and_then(|meta| meta.error()).is_some() {
+                    error_count += 1_f32;
+                }
+
+                handle.population[idx].set_score(Some(score));
+                handle.population[idx].set_genotype(genotype);
+                handle.population[idx].set_metadata(metadata);
+            }
+        }
+
+        let duration = timer.duration();
+        handle.upsert_operation(metric_names::EVALUATION, unscored.len() as f32, duration);
+        handle.upsert_operation(metric_names::EVALUATION_ERRORS, error_count, duration);
+    }
+
+    /// Evaluates the unscored individuals together with a `GroupEvaluator`. Every group is its own job, and
+    /// the score of an individual is the combination of its scores in all the groups it was scored in. The
+    /// metadata reported while a group was evaluated is attached to the members it scored.
+    fn evaluate_groups(
+        &self,
+        handle: &mut EngineContext<C, T>,
+        group_evaluator: &GroupEvaluator<T>,
+    ) {
+        let timer = Timer::new();
+
+        let unscored = (0..handle.population.len())
+            .filter(|idx| handle.population[*idx].is_dirty())
+            .collect::<Vec<usize>>();
+
+        let work_results = group_evaluator
+            .groups(&unscored, handle.population.len())
+            .into_iter()
+            .map(|(members, scored)| {
+                let genotypes = members
+                    .iter()
+                    .map(|idx| handle.population[*idx].genotype().clone())
+                    .collect::<Vec<Genotype<C>>>();
+
+                let (problem, evaluator) = (self.problem(), group_evaluator.clone());
+                self.submit(Priority::Normal, move || {
+                    metadata::take();
+                    let decoded = genotypes
+                        .iter()
+                        .map(|genotype| problem.decode(genotype))
+                        .collect::<Vec<T>>();
+                    let scores = evaluator.evaluate(&decoded);
+                    (members, scored, scores, metadata::take())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut samples = BTreeMap::<usize, Vec<Score>>::new();
+        let mut metadata = BTreeMap::<usize, Metadata>::new();
+        for work_result in work_results {
+            let (members, scored, scores, meta) = work_result.result();
+            for (idx, score) in members.into_iter().zip(scores).take(scored) {
+                samples.entry(idx).or_default().push(score);
+                if let Some(meta) = meta.clone() {
+                    match metadata.get_mut(&idx) {
+                        Some(current) => current.merge(meta),
+                        None => {
+                            metadata.insert(idx, meta);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut error_count = 0_f32;
+        for (idx, scores) in samples {
+            let metadata = metadata.remove(&idx);
+            if metadata.as_ref().and_then(|meta| meta.error()).is_some() {
+                error_count += 1_f32;
+            }
+
+            handle.population[idx].set_score(Some(group_evaluator.combine(&scores)));
+            handle.population[idx].set_metadata(metadata);
+        }
+
+        let duration = timer.duration();
+        handle.upsert_operation(metric_names::EVALUATION, unscored.len() as f32, duration);
+        handle.upsert_operation(metric_names::EVALUATION_ERRORS, error_count, duration);
+    }
+
+    /// Evaluates the unscored individuals with a `Racing` evaluation - one replication of every individual
+    /// still in the race per round, until the race is decided. Each replication is its own job, and the
+    /// number of individuals eliminated before the last round is recorded as the `Race Eliminations` metric.
+    fn race(&self, handle: &mut EngineContext<C, T>, racing: &Racing) {
+        let timer = Timer::new();
+
+        let mut genotypes = BTreeMap::new();
+        for idx in 0..handle.population.len() {
+            if handle.population[idx].is_dirty() {
+                genotypes.insert(idx, handle.population[idx].take_genotype());
+            }
+        }
+
+        let mut samples = BTreeMap::<usize, Vec<Score>>::new();
+        let mut metadata = BTreeMap::<usize, Metadata>::new();
+        let mut contenders = genotypes.keys().copied().collect::<Vec<usize>>();
+        let mut count = 0_f32;
+        let mut eliminated = 0_f32;
+
+        for round in 1..=racing.max_replications() {
+            let work_results = contenders
+                .iter()
+                .map(|idx| {
+                    let (idx, problem, geno) = (*idx, self.problem(), genotypes[idx].clone());
+                    let priority = Self::priority(&handle.population[idx], handle.index);
+                    self.submit(priority, move || {
+                        metadata::take();
+                        let score = problem.eval(&geno);
+                        (idx, score, metadata::take())
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for work_result in work_results {
+                let (idx, score, meta) = work_result.result();
+                samples.entry(idx).or_default().push(score);
+                if let Some(meta) = meta {
+                    match metadata.get_mut(&idx) {
+                        Some(current) => current.merge(meta),
+                        None => {
+                            metadata.insert(idx, meta);
+                        }
+                    }
+                }
+            }
+
+            count += contenders.len() as f32;
+            if round == racing.max_replications() {
+                break;
+            }
+
+            let scores = contenders
+                .iter()
+                .map(|idx| (*idx, Score::from_samples(&samples[idx])))
+                .collect::<Vec<(usize, Score)>>();
+            let survivors = racing.survivors(round, &scores, self.objective());
+
+            eliminated += (contenders.len() - survivors.len()) as f32;
+            contenders = survivors;
+            if contenders.len() <= 1 {
+                break;
+            }
+        }
+
+        let mut error_count = 0_f32;
+        for (idx, genotype) in genotypes {
+            let metadata = metadata.remove(&idx);
+            if metadata.as_ref().and_then(|meta| meta.error()).is_some() {
+                error_count += 1_f32;
+            }
+
+            handle.population[idx].set_score(Some(Score::from_samples(&samples[&idx])));
             handle.population[idx].set_genotype(genotype);
+            handle.population[idx].set_metadata(metadata);
+        }
+
+        let duration = timer.duration();
+        handle.upsert_operation(metric_names::EVALUATION, count, duration);
+        handle.upsert_operation(metric_names::EVALUATION_ERRORS, error_count, duration);
+        handle
+            .metrics
+            .upsert_value(metric_names::RACE_ELIMINATIONS, eliminated);
+    }
+
+    /// Submits an evaluation job with the given priority, or pins it to a dedicated thread if the
+    /// thread pool has any (which run their jobs in the order they were submitted). The job draws from
+    /// the engine's random number generator if it has one, and its time is added to the generation's
+    /// busy time.
+    fn submit<F, R>(&self, priority: Priority, job: F) -> WorkResult<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let rng = self.params.rng.clone();
+        let busy = Arc::clone(&self.busy);
+        let job = move || {
+            let timer = Timer::new();
+            let result = match rng {
+                Some(rng) => rng.scope(job),
+                None => job(),
+            };
+
+            busy.fetch_add(timer.duration().as_nanos() as u64, Ordering::Relaxed);
+            result
+        };
+
+        let thread_pool = self.thread_pool();
+        match thread_pool.num_dedicated() {
+            0 => thread_pool.submit_with_result_and_priority(priority, job),
+            _ => thread_pool.submit_pinned(job),
         }
+    }
 
-        handle.upsert_operation(metric_names::EVALUATION, count, timer.duration());
+    /// The priority of the evaluation of an individual in the given generation. Individuals from
+    /// earlier generations - survivors, elites and the parents of the offspring, e.g. re-evaluated
+    /// with a `stochastic_fitness` - are evaluated before the offspring, so the individuals selection
+    /// trusts most get their scores first.
+    fn priority(individual: &Phenotype<C>, generation: i32) -> Priority {
+        match individual.generation < generation {
+            true => Priority::High,
+            false => Priority::Normal,
+        }
+    }
 
-        objective.sort(&mut handle.population);
+    /// Records the time the generation spent evaluating, and how busy the evaluation threads were
+    /// meanwhile - the time the evaluation jobs took over the wall time of the evaluations times the
+    /// number of threads they ran on (the available parallelism when they're delegated).
+    fn record_evaluation_time(&self, ctx: &mut EngineContext<C, T>, wall: Duration) {
+        ctx.metrics.upsert_time(metric_names::EVALUATION_TIME, wall);
+
+        let busy = Duration::from_nanos(self.busy.load(Ordering::Relaxed));
+        if busy.is_zero() || wall.is_zero() {
+            return;
+        }
+
+        let thread_pool = self.thread_pool();
+        let threads = match (thread_pool.num_dedicated(), thread_pool.size()) {
+            (0, 0) => std::thread::available_parallelism().map_or(1, |count| count.get()),
+            (0, size) => size,
+            (dedicated, _) => dedicated,
+        };
+
+        let efficiency = busy.as_secs_f32() / (wall.as_secs_f32() * threads as f32);
+        ctx.metrics
+            .upsert_value(metric_names::PARALLEL_EFFICIENCY, efficiency);
+    }
+
+    /// Speciates the population (if speciation is set) and applies the fitness sharing of its species
+    /// and the fitness shaping specified in the genetic engine parameters (if any) to a copy of the
+    /// population. The shaped population is only used for selection - the scores of the actual
+    /// population are left as they are so the best individual and the metrics always reflect the raw
+    /// output of the fitness function.
+    fn shape(&self, ctx: &mut EngineContext<C, T>) -> Option<Population<C>> {
+        if self.params.shaping.is_none() && ctx.species.is_none() {
+            return None;
+        }
+
+        let timer = Timer::new();
+        if let Some(speciation) = ctx.species.as_mut() {
+            speciation.speciate(&mut ctx.population);
+        }
+
+        let mut shaped = ctx.population.clone();
+        for (index, individual) in shaped.iter_mut().enumerate() {
+            individual
+                .metadata
+                .get_or_insert_with(Metadata::default)
+                .values
+                .insert(metadata::SOURCE.to_string(), index.to_string());
+        }
+
+        if let Some(speciation) = ctx.species.as_ref() {
+            speciation.share(&mut shaped, self.objective());
+
+            let count = speciation.len();
+            ctx.upsert_operation(metric_names::SPECIATION, count as f32, timer.duration());
+        }
+
+        if let Some(shaping) = &self.params.shaping {
+            let timer = Timer::new();
+            shaping.shape(&mut shaped, self.objective());
+            ctx.upsert_operation(
+                metric_names::FITNESS_SHAPING,
+                shaped.len() as f32,
+                timer.duration(),
+            );
+        }
+
+        self.objective().sort(&mut shaped);
+        Some(shaped)
+    }
+
+    /// Selectors clone the individuals they pick, so when selecting from a shaped population the raw
+    /// scores (and metadata) have to be copied back from the individual each shaped copy was made from.
+    fn unshape(&self, population: &Population<C>, selected: &mut Population<C>) {
+        for individual in selected.iter_mut() {
+            let source = individual
+                .metadata()
+                .and_then(|metadata| metadata.get(metadata::SOURCE))
+                .and_then(|index| index.parse::<usize>().ok())
+                .and_then(|index| population.as_ref().get(index));
+
+            individual.set_score(source.and_then(|source| source.score().cloned()));
+            individual.set_metadata(source.and_then(|source| source.metadata().cloned()));
+        }
     }
 
     /// Selects the individuals that will survive to the next generation. The number of survivors
@@ -171,13 +1004,22 @@ where
     /// fraction is 0.8, then 20 individuals will be selected as survivors.
     ///
     /// This method returns a new population containing only the selected survivors.
-    fn select_survivors(&self, ctx: &mut EngineContext<C, T>) -> Population<C> {
+    fn select_survivors(
+        &self,
+        ctx: &mut EngineContext<C, T>,
+        shaped: Option<&Population<C>>,
+        size: usize,
+    ) -> Population<C> {
         let selector = self.survivor_selector();
-        let count = self.survivor_count();
+        let count = self.survivor_count(size);
         let objective = self.objective();
 
         let timer = Timer::new();
-        let result = selector.select(&ctx.population, objective, count);
+        let mut result = selector.select(shaped.unwrap_or(&ctx.population), objective, count);
+
+        if shaped.is_some() {
+            self.unshape(&ctx.population, &mut result);
+        }
 
         ctx.upsert_operation(selector.name(), count as f32, timer.duration());
 
@@ -191,35 +1033,162 @@ where
     /// like tournament selection or roulette wheel selection. For example, if the population size is 100
     /// and the offspring fraction is 0.8, then 80 individuals will be selected as offspring which will
     /// be used to create the next generation through crossover and mutation.
-    ///
-    /// Alters the offspring population using the alterers specified in the genetic engine parameters.
-    /// The alterer in this case is going to be a ```CompositeAlterer``` and is responsible for applying
-    /// the provided mutation and crossover operations to the offspring population.
-    fn create_offspring(&self, ctx: &mut EngineContext<C, T>) -> Population<C> {
+    fn select_offspring(
+        &self,
+        ctx: &mut EngineContext<C, T>,
+        shaped: Option<&Population<C>>,
+        count: usize,
+    ) -> Population<C> {
         let selector = self.offspring_selector();
-        let count = self.offspring_count();
         let objective = self.objective();
-        let alterer = self.alterer();
 
         let timer = Timer::new();
-        let mut offspring = selector.select(&ctx.population, objective, count);
+        let mut offspring = selector.select(shaped.unwrap_or(&ctx.population), objective, count);
+
+        if shaped.is_some() {
+            self.unshape(&ctx.population, &mut offspring);
+        }
 
         ctx.upsert_operation(selector.name(), count as f32, timer.duration());
 
         objective.sort(&mut offspring);
+        offspring
+    }
 
-        for alterer in alterer {
-            let alter_metrics = match alterer {
-                AlterAction::Mutate(mutator) => mutator.mutate(&mut offspring, ctx.index),
-                AlterAction::Crossover(crossover) => crossover.crossover(&mut offspring, ctx.index),
-            };
+    /// Alters the offspring population using the alterers specified in the genetic engine parameters.
+    /// The alterer in this case is going to be a ```CompositeAlterer``` and is responsible for applying
+    /// the provided mutation and crossover operations to the offspring population. Returns a copy of the
+    /// offspring from before they were altered when there is a delta fitness.
+    fn alter_offspring(
+        &self,
+        ctx: &mut EngineContext<C, T>,
+        offspring: &mut Population<C>,
+    ) -> Parents<C> {
+        let parents = match self.params.delta_fitness {
+            Some(_) => offspring
+                .iter()
+                .map(|individual| {
+                    let score = individual.score()?.clone();
+                    Some((individual.genotype().clone(), score))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
 
-            for metric in alter_metrics {
+        let rng = self.rng();
+        for alterer in self.alterer() {
+            for metric in alterer.alter(offspring, ctx.index, &rng) {
                 ctx.metrics.upsert(metric);
             }
         }
 
-        offspring
+        parents
+    }
+
+    /// Refines the offspring - the individuals of `population` from `start` on - with the memetic local
+    /// search (if one is set and runs at `stage`), in parallel. Offspring without a score are evaluated
+    /// first. Lamarckian refinement replaces an offspring's genotype and score, Baldwinian only its score.
+    fn refine(
+        &self,
+        metrics: &mut MetricSet,
+        population: &mut Population<C>,
+        start: usize,
+        stage: MemeticStage,
+    ) {
+        let Some(memetic) = &self.params.memetic else {
+            return;
+        };
+
+        if memetic.memetic_stage() != stage {
+            return;
+        }
+
+        let timer = Timer::new();
+        let optimize = self.objective().as_ref()[0];
+        let mut work_results = Vec::new();
+        for idx in start..population.len() {
+            if random_provider::random::<f32>() >= memetic.refinement_rate() {
+                continue;
+            }
+
+            let (problem, search) = (self.problem(), memetic.search());
+            let genotype = population[idx].genotype().clone();
+            let score = population[idx].score().cloned();
+            work_results.push(self.submit(Priority::Normal, move || {
+                let (score, evaluated) = match score {
+                    Some(score) => (score, 0),
+                    None => (problem.eval(&genotype), 1),
+                };
+
+                let mut refinement =
+                    search.refine(problem.as_ref().as_ref(), optimize, genotype, score);
+                refinement.evaluations += evaluated;
+                (idx, refinement)
+            }));
+        }
+
+        let mut evaluations = 0;
+        for work_result in work_results {
+            let (idx, refinement) = work_result.result();
+            evaluations += refinement.evaluations;
+
+            let individual = &mut population[idx];
+            if memetic.inheritance() == Inheritance::Lamarckian {
+                individual.set_genotype(refinement.genotype);
+            }
+
+            individual.set_score(Some(refinement.score));
+        }
+
+        metrics.upsert_operations(
+            metric_names::LOCAL_SEARCH,
+            evaluations as f32,
+            timer.duration(),
+        );
+    }
+
+    /// Scores the changed offspring with the delta fitness (if any) from their parent's score and the genes
+    /// that changed since the alterers ran - the repair and filter included. The offspring it can't score are
+    /// left for `evaluate`.
+    fn evaluate_deltas(&self, ctx: &mut EngineContext<C, T>, start: usize, parents: Parents<C>) {
+        let Some(delta_fitness) = &self.params.delta_fitness else {
+            return;
+        };
+
+        let timer = Timer::new();
+        let mut count = 0_f32;
+        for (individual, parent) in ctx.population.iter_mut().skip(start).zip(parents) {
+            let Some((genotype, score)) = parent else {
+                continue;
+            };
+
+            if !individual.is_dirty() {
+                continue;
+            }
+
+            let delta = individual
+                .genotype()
+                .changes_from(&genotype)
+                .and_then(|changes| delta_fitness.delta(&score, &changes, individual.genotype()));
+
+            if let Some(score) = delta {
+                individual.set_score(Some(score));
+                count += 1_f32;
+            }
+        }
+
+        ctx.upsert_operation(metric_names::DELTA_EVALUATIONS, count, timer.duration());
+    }
+
+    /// Repairs every individual an alterer changed with the codex's repair function (see `Codex::repair`),
+    /// so individuals that can be brought back into the valid space aren't thrown away by `filter`.
+    fn repair(&self, ctx: &mut EngineContext<C, T>) {
+        let problem = self.problem();
+        for individual in ctx.population.iter_mut() {
+            if individual.is_dirty() {
+                problem.repair(individual.genotype_mut());
+            }
+        }
     }
 
     /// Filters the population to remove individuals that are too old or invalid. The maximum age
@@ -260,16 +1229,60 @@ where
     /// offspring are the individuals that were selected from the previous generation then altered.
     /// This method combines the survivors and offspring populations into a single population that
     /// will be used in the next iteration of the genetic algorithm.
+    ///
+    /// Selectors that pick without replacement (e.g. the `EliteSelector`) can't select more individuals
+    /// than the population has, so when the population grows the gap is filled with new individuals,
+    /// placed between the survivors and the offspring. Returns the index of the first offspring.
     fn recombine(
         &self,
         handle: &mut EngineContext<C, T>,
         survivors: Population<C>,
         offspring: Population<C>,
-    ) {
+        size: usize,
+    ) -> usize {
+        let problem = self.problem();
+        let generation = handle.index;
+        let missing = size.saturating_sub(survivors.len() + offspring.len());
+        let start = survivors.len() + missing;
+
         handle.population = survivors
             .into_iter()
+            .chain((0..missing).map(|_| Phenotype::from_genotype(problem.encode(), generation)))
             .chain(offspring)
             .collect::<Population<C>>();
+
+        start
+    }
+
+    /// Records the number of offspring that passed through the alterers untouched - they keep their score,
+    /// so their evaluation is skipped.
+    fn count_clean_offspring(&self, ctx: &mut EngineContext<C, T>, start: usize) {
+        let start = start.min(ctx.population.len());
+        let clean = ctx.population.as_ref()[start..]
+            .iter()
+            .filter(|individual| !individual.is_dirty())
+            .count();
+
+        ctx.metrics
+            .upsert_value(metric_names::SKIPPED_EVALUATIONS, clean as f32);
+    }
+
+    /// Hands the evaluated offspring back to the alterers that produced them and clears the tags the
+    /// alterers put on them. This has to happen before the population is sorted, while the offspring
+    /// are still at the end of the population in the order they were altered in.
+    fn observe_offspring(&self, ctx: &mut EngineContext<C, T>, start: usize) {
+        let objective = self.objective();
+        let start = start.min(ctx.population.len());
+
+        for alterer in self.alterer() {
+            for metric in alterer.observe(&ctx.population.as_ref()[start..], objective) {
+                ctx.metrics.upsert(metric);
+            }
+        }
+
+        for individual in ctx.population.iter_mut().skip(start) {
+            individual.clear_tags();
+        }
     }
 
     /// Audits the current state of the genetic algorithm, updating the best individual found so far
@@ -288,6 +1301,9 @@ where
                 if optimize.is_better(best_score, current_score) {
                     output.score = Some(best_score.clone());
                     output.best = problem.decode(output.population[0].genotype());
+                    output.stagnation = 0;
+                } else {
+                    output.stagnation += 1;
                 }
             }
         } else {
@@ -296,6 +1312,14 @@ where
         }
 
         self.update_front(output);
+        self.update_hall_of_fame(output);
+        self.update_elites(output);
+        self.update_recording(output);
+        self.update_movement(output);
+        self.update_embedding(output);
+        self.update_memory(output);
+        self.update_genotype_metrics(output);
+        self.update_cache(output);
         self.update_metrics(output);
 
         output.index += 1;
@@ -312,14 +1336,17 @@ where
 
         if let Objective::Multi(_) = objective {
             let timer = Timer::new();
+            // Individuals whose evaluation failed have the worst score and never belong on the front.
+            let worst = objective.worst_score();
             let scores = output
                 .population
                 .iter()
                 .map(|individual| individual.score().unwrap().clone())
+                .filter(|score| *score != worst)
                 .collect::<Vec<Score>>();
 
             let front = Arc::clone(&output.front);
-            thread_pool.submit(move || {
+            thread_pool.submit_with_priority(Priority::High, move || {
                 front.lock().unwrap().update_front(&scores);
             });
 
@@ -327,6 +1354,50 @@ where
         }
     }
 
+    /// Adds the champion of the current generation to the hall of fame (if one is set) and records
+    /// the win rate of the games played against the hall of fame during this generation's evaluations.
+    fn update_hall_of_fame(&self, output: &mut EngineContext<C, T>) {
+        if let Some(hall_of_fame) = &self.params.hall_of_fame {
+            if let Some(win_rate) = hall_of_fame.take_win_rate() {
+                output
+                    .metrics
+                    .upsert_value(metric_names::HALL_OF_FAME_WIN_RATE, win_rate);
+            }
+
+            let champion = self.problem().decode(output.population[0].genotype());
+            hall_of_fame.add(champion, output.index as usize);
+        }
+    }
+
+    /// Merges the population into the elite archive (if one is kept), recording how many individuals
+    /// entered it this generation.
+    fn update_elites(&self, output: &mut EngineContext<C, T>) {
+        if let Some(elites) = output.elites.as_mut() {
+            let timer = Timer::new();
+            let entered = elites.update(&output.population, self.objective());
+            output.upsert_operation(
+                metric_names::ELITE_ARCHIVE,
+                entered as f32,
+                timer.duration(),
+            );
+        }
+    }
+
+    /// Re-runs the recorder (if one is set) on the best individual of the current generation and
+    /// stores the resulting `Recording` on the output context.
+    fn update_recording(&self, output: &mut EngineContext<C, T>) {
+        if let Some(recorder) = &self.params.recorder {
+            let timer = Timer::new();
+            let best = self.problem().decode(output.population[0].genotype());
+            let mut recording = Recording::new(output.index);
+
+            recorder(best, &mut recording);
+
+            output.recording = Some(recording);
+            output.upsert_operation(metric_names::RECORDING, 1.0, timer.duration());
+        }
+    }
+
     /// Adds various metrics to the output context, including the age of individuals, the score of individuals,
     /// and the number of unique scores in the population. These metrics can be used to monitor the progress of
     /// the genetic algorithm and to identify potential issues or areas for improvement.
@@ -369,6 +1440,71 @@ where
         output.metrics.upsert(score_metric);
         output.metrics.upsert(unique_metric);
         output.metrics.upsert(size_metric);
+
+        if !self.params.constraints.is_empty() {
+            let infeasible = output
+                .population
+                .iter()
+                .filter(|individual| is_infeasible(individual.score().unwrap()))
+                .count();
+
+            output
+                .metrics
+                .upsert_value(metric_names::INFEASIBLE, infeasible as f32);
+        }
+    }
+
+    /// Describes the configuration of the engine - its own parameters and those of its selectors and
+    /// alterers (see `EngineCompoment::describe`).
+    fn describe(&self) -> Description {
+        let mut engine = Description::new("GeneticEngine")
+            .param("population_size", self.params.population_size)
+            .param("offspring_fraction", self.params.offspring_fraction)
+            .param("max_age", self.params.max_age)
+            .param("objective", format!("{:?}", self.objective()));
+
+        if let Some(steady_state) = &self.params.steady_state {
+            let policy = match steady_state.policy() {
+                Replacement::Worst => "worst",
+                Replacement::Oldest => "oldest",
+                Replacement::Crowding(_) => "crowding",
+            };
+
+            engine = engine
+                .param("steady_state", steady_state.replacements())
+                .param("replacement", policy);
+        }
+
+        if let Some(restart) = &self.params.restart {
+            engine = engine
+                .param("restart_window", restart.window())
+                .param("restart", restart.strategy().label());
+        }
+
+        if !self.params.constraints.is_empty() {
+            let modes = self
+                .params
+                .constraints
+                .iter()
+                .map(|(_, mode)| mode.label())
+                .collect::<Vec<String>>();
+
+            engine = engine.param("constraints", modes.join(", "));
+        }
+
+        let alterers = self
+            .alterer()
+            .iter()
+            .fold(Description::new("alterers"), |alterers, alterer| {
+                alterers.child(alterer.describe())
+            });
+
+        engine
+            .child(Description::new("survivor_selector").child(self.survivor_selector().describe()))
+            .child(
+                Description::new("offspring_selector").child(self.offspring_selector().describe()),
+            )
+            .child(alterers)
     }
 
     fn survivor_selector(&self) -> &dyn Select<C> {
@@ -395,42 +1531,234 @@ where
         &self.params.objective
     }
 
-    fn survivor_count(&self) -> usize {
-        self.params.population_size - self.offspring_count()
+    fn survivor_count(&self, size: usize) -> usize {
+        size - self.offspring_count(size)
+    }
+
+    fn offspring_count(&self, size: usize) -> usize {
+        (size as f32 * self.params.offspring_fraction) as usize
     }
 
-    fn offspring_count(&self) -> usize {
-        (self.params.population_size as f32 * self.params.offspring_fraction) as usize
+    /// The size of the population the current generation produces - the `population_size` (or the
+    /// `Knob::PopulationSize` of the control panel, once set) unless a `PopulationSchedule` is set.
+    fn next_population_size(&self, ctx: &mut EngineContext<C, T>) -> usize {
+        let base = self
+            .params
+            .control
+            .as_ref()
+            .and_then(|panel| panel.get(Knob::PopulationSize))
+            .map(|size| size as usize)
+            .unwrap_or(self.params.population_size);
+
+        let Some(schedule) = &self.params.population_schedule else {
+            return base;
+        };
+
+        let size = schedule.size(base, ctx.index, ctx.population.len(), ctx.stagnation);
+
+        ctx.metrics
+            .upsert_value(metric_names::POPULATION_SIZE, size as f32);
+
+        size
     }
 
+    /// The maximum age of an individual in engine steps. In steady-state mode a step only breeds a few
+    /// offspring, so the `max_age` (in generations) is scaled by the number of steps it takes to breed as
+    /// many offspring as the population has individuals.
     fn max_age(&self) -> i32 {
-        self.params.max_age
+        match &self.params.steady_state {
+            Some(steady_state) => {
+                let steps = (self.params.population_size / steady_state.replacements()).max(1);
+                self.params.max_age.saturating_mul(steps as i32)
+            }
+            None => self.params.max_age,
+        }
+    }
+
+    /// Takes a snapshot of the population (if population movement is tracked) and records how far it
+    /// moved since the previous generation's snapshot.
+    fn update_movement(&self, output: &mut EngineContext<C, T>) {
+        if let Some(gene_value) = &self.params.gene_value {
+            let snapshot = PopulationSnapshot::new(&output.population, |gene| gene_value(gene));
+
+            if let Some(previous) = &output.snapshot {
+                let movement = snapshot.movement(previous);
+
+                output.metrics.upsert_value(
+                    metric_names::MEAN_ALLELE_CHANGE,
+                    movement.mean_allele_change,
+                );
+                output
+                    .metrics
+                    .upsert_value(metric_names::CENTROID_DRIFT, movement.centroid_drift);
+                output.metrics.upsert_sequence(
+                    metric_names::VARIANCE_SHRINKAGE,
+                    &movement.variance_shrinkage,
+                );
+            }
+
+            output.snapshot = Some(snapshot);
+        }
+    }
+
+    /// Adds the embedding of the population to the embedding trace (if one is set) when the generation is due.
+    fn update_embedding(&self, output: &mut EngineContext<C, T>) {
+        let Some((trace, gene_value)) = &self.params.embedding else {
+            return;
+        };
+
+        if !trace.is_due(output.index) {
+            return;
+        }
+
+        let points = output
+            .population
+            .iter()
+            .map(|individual| {
+                individual
+                    .genotype()
+                    .iter()
+                    .flat_map(|chromosome| chromosome.iter())
+                    .map(|gene| gene_value(gene))
+                    .collect()
+            })
+            .collect::<Vec<Vec<f32>>>();
+        let scores = output
+            .population
+            .iter()
+            .map(|individual| individual.score().map_or(f32::NAN, |score| score.as_f32()))
+            .collect();
+
+        trace.record(output.index, &points, scores);
+    }
+
+    /// Reports the approximate memory of the population to the memory budget (if one is set).
+    fn update_memory(&self, output: &mut EngineContext<C, T>) {
+        if let Some(budget) = &self.params.memory_budget {
+            let bytes = output.population.footprint();
+            budget.set_population(bytes);
+
+            output
+                .metrics
+                .upsert_value(metric_names::POPULATION_MEMORY, bytes as f32);
+        }
+    }
+
+    /// Records the hits and misses of the fitness cache (if one is set) since the last generation.
+    fn update_cache(&self, output: &mut EngineContext<C, T>) {
+        if let Some(cache) = &self.params.fitness_cache {
+            let (hits, misses) = cache.take_counts();
+            output
+                .metrics
+                .upsert_value(metric_names::CACHE_HITS, hits as f32);
+            output
+                .metrics
+                .upsert_value(metric_names::CACHE_MISSES, misses as f32);
+        }
+    }
+
+    /// Records the user defined genotype metrics over the population.
+    fn update_genotype_metrics(&self, output: &mut EngineContext<C, T>) {
+        for (name, metric) in self.params.genotype_metrics.iter() {
+            let values = output
+                .population
+                .iter()
+                .map(|individual| metric(individual.genotype()))
+                .collect::<Vec<f32>>();
+
+            output.metrics.upsert_sequence(name, &values);
+        }
     }
 
     fn thread_pool(&self) -> &ThreadPool {
         &self.params.thread_pool
     }
 
-    fn start(&self) -> EngineContext<C, T> {
+    pub(crate) fn start(&self) -> EngineContext<C, T> {
         let population = self.population();
+        self.publish(|| EngineEvent::Start);
+
+        let mut metrics = MetricSet::new();
+        if let Some(calibrated) = &self.params.calibrated {
+            metrics.upsert_value(metric_names::CALIBRATED_THREADS, calibrated.threads as f32);
+            metrics.upsert_value(
+                metric_names::CALIBRATED_BATCH_SIZE,
+                calibrated.batch_size as f32,
+            );
+        }
+
+        if let Some(benchmarked) = &self.params.benchmarked {
+            let roles = [
+                (
+                    "survivor",
+                    metric_names::SURVIVOR_SELECTION_INTENSITY,
+                    metric_names::SURVIVOR_DIVERSITY_LOSS,
+                ),
+                (
+                    "offspring",
+                    metric_names::OFFSPRING_SELECTION_INTENSITY,
+                    metric_names::OFFSPRING_DIVERSITY_LOSS,
+                ),
+            ];
+
+            for (role, intensity, diversity_loss) in roles {
+                if let Some(value) = benchmarked.intensity(role) {
+                    metrics.upsert_value(intensity, value);
+                }
+                if let Some(value) = benchmarked.diversity_loss(role) {
+                    metrics.upsert_value(diversity_loss, value);
+                }
+            }
+        }
+
+        let mut front = Front::new(
+            self.params.min_front_size,
+            self.params.max_front_size,
+            self.objective().clone(),
+        );
+
+        let (index, score, stagnation) = match &self.params.resumed {
+            Some(resumed) => {
+                metrics = resumed.metrics.clone();
+                front.update_front(&resumed.front);
+                (resumed.index, resumed.score.clone(), resumed.stagnation)
+            }
+            None => (0, None, 0),
+        };
 
         EngineContext {
             population: population.clone(),
             best: self.problem().decode(population[0].genotype()),
-            index: 0,
+            index,
             timer: Timer::new(),
-            metrics: MetricSet::new(),
-            score: None,
-            front: Arc::new(Mutex::new(Front::new(
-                self.params.min_front_size,
-                self.params.max_front_size,
-                self.objective().clone(),
-            ))),
+            metrics,
+            score,
+            stagnation,
+            front: Arc::new(Mutex::new(front)),
+            recording: None,
+            snapshot: None,
+            schema: self.params.schema.clone(),
+            elites: self.params.elite_archive.map(EliteArchive::new),
+            species: self.params.speciation.clone(),
+            configuration: self.describe(),
         }
     }
 
     fn stop(&self, output: &mut EngineContext<C, T>) -> EngineContext<C, T> {
         output.timer.stop();
+        for stage in &self.params.post_run {
+            if let Err(error) = stage(output) {
+                self.report_error(output, error.to_string());
+            }
+        }
+
+        self.publish(|| EngineEvent::Stop {
+            index: output.index,
+            best: output.best.clone(),
+            score: output.score().clone(),
+            metrics: output.metrics.clone(),
+        });
+
         output.clone()
     }
 }
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+/// An `Environment` is a (gym style) control problem that an individual interacts with over a
+/// series of steps. Each step the individual is given the current observation and
+/// returns an action, the environment then advances and produces a reward.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Move a point on a line towards zero.
+/// struct Line { position: f32, steps: usize }
+///
+/// impl Environment for Line {
+///     type Observation = f32;
+///     type Action = f32;
+///
+///     fn reset(&mut self) -> f32 {
+///         self.position = 5.0;
+///         self.steps = 0;
+///         self.position
+///     }
+///
+///     fn step(&mut self, action: f32) {
+///         self.position += action.clamp(-1.0, 1.0);
+///         self.steps += 1;
+///     }
+///
+///     fn observation(&self) -> f32 { self.position }
+///     fn reward(&self) -> f32 { -self.position.abs() }
+///     fn done(&self) -> bool { self.steps >= 10 }
+/// }
+///
+/// let runner = EpisodeRunner::new(|| Line { position: 0.0, steps: 0 });
+/// let score = runner.run(|obs: &f32| -obs);
+/// assert!(score > -15.0);
+/// ```
+pub trait Environment {
+    type Observation;
+    type Action;
+
+    /// Reset the environment to its initial state and return the first observation.
+    fn reset(&mut self) -> Self::Observation;
+
+    /// Apply the action to the environment, advancing it by one step.
+    fn step(&mut self, action: Self::Action);
+
+    /// The current observation of the environment.
+    fn observation(&self) -> Self::Observation;
+
+    /// The reward produced by the last step.
+    fn reward(&self) -> f32;
+
+    /// Whether or not the current episode has finished.
+    fn done(&self) -> bool;
+}
+
+/// How the total rewards of multiple episodes are combined into a single fitness value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EpisodeAggregate {
+    Mean,
+    Sum,
+    Min,
+    Max,
+    Median,
+}
+
+impl EpisodeAggregate {
+    pub fn apply(&self, values: &[f32]) -> f32 {
+        if values.is_empty() {
+            return 0.0;
+        }
+
+        match self {
+            EpisodeAggregate::Mean => values.iter().sum::<f32>() / values.len() as f32,
+            EpisodeAggregate::Sum => values.iter().sum::<f32>(),
+            EpisodeAggregate::Min => values.iter().cloned().fold(f32::INFINITY, f32::min),
+            EpisodeAggregate::Max => values.iter().cloned().fold(f32::NEG_INFINITY, f32::max),
+            EpisodeAggregate::Median => {
+                let mut sorted = values.to_vec();
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let mid = sorted.len() / 2;
+                if sorted.len().is_multiple_of(2) {
+                    (sorted[mid - 1] + sorted[mid]) / 2.0
+                } else {
+                    sorted[mid]
+                }
+            }
+        }
+    }
+}
+
+/// Scores a policy by rolling it out in one or more episodes of an `Environment`. A fresh
+/// environment is created for every episode, so the runner can be shared across the threads
+/// of the engine's thread pool and used directly inside a fitness function.
+///
+/// Defaults:
+/// * episodes: 1
+/// * max_steps: 1000
+/// * aggregate: EpisodeAggregate::Mean
+pub struct EpisodeRunner<E: Environment> {
+    factory: Arc<dyn Fn() -> E + Send + Sync>,
+    episodes: usize,
+    max_steps: usize,
+    aggregate: EpisodeAggregate,
+}
+
+impl<E: Environment> EpisodeRunner<E> {
+    pub fn new<F>(factory: F) -> Self
+    where
+        F: Fn() -> E + Send + Sync + 'static,
+    {
+        EpisodeRunner {
+            factory: Arc::new(factory),
+            episodes: 1,
+            max_steps: 1000,
+            aggregate: EpisodeAggregate::Mean,
+        }
+    }
+
+    /// Set the number of episodes each policy is rolled out for.
+    pub fn episodes(mut self, episodes: usize) -> Self {
+        if episodes < 1 {
+            panic!("episodes must be greater than 0");
+        }
+
+        self.episodes = episodes;
+        self
+    }
+
+    /// Set the maximum number of steps in an episode. An episode ends when the environment
+    /// is done or this number of steps is reached, whichever comes first.
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        if max_steps < 1 {
+            panic!("max_steps must be greater than 0");
+        }
+
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Set how the total rewards of each episode are combined.
+    pub fn aggregate(mut self, aggregate: EpisodeAggregate) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    /// Roll out a single episode and return the total reward collected.
+    pub fn episode<P>(&self, policy: &mut P) -> f32
+    where
+        P: FnMut(&E::Observation) -> E::Action,
+    {
+        let mut env = (self.factory)();
+        let mut observation = env.reset();
+        let mut total = 0.0;
+
+        for _ in 0..self.max_steps {
+            env.step(policy(&observation));
+            total += env.reward();
+
+            if env.done() {
+                break;
+            }
+
+            observation = env.observation();
+        }
+
+        total
+    }
+
+    /// Roll out all episodes with the given policy and return the aggregated total reward.
+    pub fn run<P>(&self, mut policy: P) -> f32
+    where
+        P: FnMut(&E::Observation) -> E::Action,
+    {
+        let rewards = (0..self.episodes)
+            .map(|_| self.episode(&mut policy))
+            .collect::<Vec<f32>>();
+
+        self.aggregate.apply(&rewards)
+    }
+}
+
+impl<E: Environment> Clone for EpisodeRunner<E> {
+    fn clone(&self) -> Self {
+        EpisodeRunner {
+            factory: Arc::clone(&self.factory),
+            episodes: self.episodes,
+            max_steps: self.max_steps,
+            aggregate: self.aggregate,
+        }
+    }
+}
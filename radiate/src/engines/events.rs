@@ -0,0 +1,351 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+use super::{MetricSet, Score};
+
+/// An event emitted by the engine to its subscribers (see `GeneticEngineParams::subscribe`).
+#[derive(Clone)]
+pub enum EngineEvent<T> {
+    /// The engine started a run.
+    Start,
+    /// A generation finished - `best` and `score` are the best individual so far.
+    EpochComplete {
+        index: i32,
+        best: T,
+        score: Score,
+        metrics: MetricSet,
+    },
+    /// The engine finished a run.
+    Stop {
+        index: i32,
+        best: T,
+        score: Score,
+        metrics: MetricSet,
+    },
+    /// Writing one of the run's files - a checkpoint, its artifacts or samples - failed. These are side
+    /// effects of the search, so the run carries on, and the failure is counted in the metrics.
+    Error { index: i32, message: String },
+}
+
+impl<T> EngineEvent<T> {
+    pub fn is_epoch(&self) -> bool {
+        matches!(self, EngineEvent::EpochComplete { .. })
+    }
+}
+
+/// Receives the engine's events. Subscribers are called on the engine's thread, in the order they were
+/// added, so a slow subscriber slows the engine down - wrap it in a `BufferedSubscriber` to move the
+/// delivery to its own thread. Any `Fn(&EngineEvent<T>)` is a subscriber.
+pub trait Subscriber<T>: Send + Sync {
+    fn on_event(&self, event: &EngineEvent<T>);
+}
+
+impl<T, F> Subscriber<T> for F
+where
+    F: Fn(&EngineEvent<T>) + Send + Sync,
+{
+    fn on_event(&self, event: &EngineEvent<T>) {
+        self(event)
+    }
+}
+
+/// What a `BufferedSubscriber` does with a new event when its queue is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Wait for the delivery thread to make room - back-pressure on the engine, nothing is lost.
+    Block,
+    /// Drop the new event.
+    DropNewest,
+    /// Drop the oldest queued event.
+    DropOldest,
+    /// Replace the newest queued `EpochComplete` with the new event, so the subscriber always sees the
+    /// latest generation. `Start` and `Stop` are only dropped if nothing else is queued.
+    #[default]
+    Coalesce,
+}
+
+struct Queue<T> {
+    events: VecDeque<EngineEvent<T>>,
+    policy: OverflowPolicy,
+    closed: bool,
+    delivering: bool,
+}
+
+struct Shared<T> {
+    queue: Mutex<Queue<T>>,
+    changed: Condvar,
+    capacity: usize,
+    dropped: AtomicUsize,
+}
+
+/// A `Subscriber` that hands events to a bounded queue and delivers them to the wrapped subscriber on a
+/// dedicated thread, so expensive handlers (telemetry, plotting, writing to disk) can't stall evolution.
+/// When the queue is full the `OverflowPolicy` decides what happens - by default the latest generation
+/// replaces the queued one. Queued events are still delivered when the `BufferedSubscriber` is dropped.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+/// use std::sync::{Arc, Mutex};
+///
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let handler = Arc::clone(&seen);
+///
+/// let subscriber = BufferedSubscriber::new(
+///     move |event: &EngineEvent<Vec<Vec<i32>>>| {
+///         if let EngineEvent::EpochComplete { index, .. } = event {
+///             handler.lock().unwrap().push(*index);
+///         }
+///     },
+///     16,
+/// )
+/// .with_policy(OverflowPolicy::DropOldest);
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .subscribe(subscriber)
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// engine.run(|ctx| ctx.index >= 5);
+/// drop(engine);
+///
+/// assert_eq!(seen.lock().unwrap().last(), Some(&5));
+/// ```
+pub struct BufferedSubscriber<T> {
+    shared: Arc<Shared<T>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<T: Clone + Send + 'static> BufferedSubscriber<T> {
+    /// Wrap `subscriber`, queueing at most `capacity` events. Panics if `capacity` is 0.
+    pub fn new(subscriber: impl Subscriber<T> + 'static, capacity: usize) -> Self {
+        if capacity == 0 {
+            panic!("capacity must be greater than 0");
+        }
+
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue {
+                events: VecDeque::with_capacity(capacity),
+                policy: OverflowPolicy::default(),
+                closed: false,
+                delivering: false,
+            }),
+            changed: Condvar::new(),
+            capacity,
+            dropped: AtomicUsize::new(0),
+        });
+
+        let worker = Arc::clone(&shared);
+        let handle = std::thread::spawn(move || deliver(&worker, &subscriber));
+
+        BufferedSubscriber {
+            shared,
+            handle: Some(handle),
+        }
+    }
+
+    /// Set the `OverflowPolicy`. Default is `OverflowPolicy::Coalesce`.
+    pub fn with_policy(self, policy: OverflowPolicy) -> Self {
+        self.shared.queue.lock().unwrap().policy = policy;
+        self
+    }
+
+    /// The number of events dropped or coalesced away because the queue was full.
+    pub fn dropped(&self) -> usize {
+        self.shared.dropped.load(Ordering::SeqCst)
+    }
+
+    /// Wait until every queued event has been delivered.
+    pub fn flush(&self) {
+        let mut queue = self.shared.queue.lock().unwrap();
+        while !queue.events.is_empty() || queue.delivering {
+            queue = self.shared.changed.wait(queue).unwrap();
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> Subscriber<T> for BufferedSubscriber<T> {
+    fn on_event(&self, event: &EngineEvent<T>) {
+        let shared = &self.shared;
+        let mut queue = shared.queue.lock().unwrap();
+
+        if queue.events.len() >= shared.capacity {
+            match queue.policy {
+                OverflowPolicy::Block => {
+                    while queue.events.len() >= shared.capacity {
+                        queue = shared.changed.wait(queue).unwrap();
+                    }
+                }
+                OverflowPolicy::DropNewest => {
+                    shared.dropped.fetch_add(1, Ordering::SeqCst);
+                    return;
+                }
+                OverflowPolicy::DropOldest => {
+                    queue.events.pop_front();
+                    shared.dropped.fetch_add(1, Ordering::SeqCst);
+                }
+                OverflowPolicy::Coalesce => {
+                    shared.dropped.fetch_add(1, Ordering::SeqCst);
+                    let newest_epoch = queue.events.iter().rposition(|queued| queued.is_epoch());
+                    match (newest_epoch, event.is_epoch()) {
+                        (Some(position), true) => {
+                            queue.events[position] = event.clone();
+                            return;
+                        }
+                        (Some(position), false) => {
+                            queue.events.remove(position);
+                        }
+                        // Only `Start`/`Stop` are queued - make room at the front.
+                        (None, _) => {
+                            queue.events.pop_front();
+                        }
+                    }
+                }
+            }
+        }
+
+        queue.events.push_back(event.clone());
+        drop(queue);
+        shared.changed.notify_all();
+    }
+}
+
+impl<T> Drop for BufferedSubscriber<T> {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = self.shared.queue.lock() {
+            queue.closed = true;
+        }
+
+        self.shared.changed.notify_all();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn deliver<T>(shared: &Shared<T>, subscriber: &dyn Subscriber<T>) {
+    loop {
+        let event = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                if let Some(event) = queue.events.pop_front() {
+                    queue.delivering = true;
+                    break Some(event);
+                }
+
+                if queue.closed {
+                    break None;
+                }
+
+                queue = shared.changed.wait(queue).unwrap();
+            }
+        };
+
+        // Wake up an engine blocked on a full queue.
+        shared.changed.notify_all();
+        match event {
+            Some(event) => {
+                subscriber.on_event(&event);
+                shared.queue.lock().unwrap().delivering = false;
+                shared.changed.notify_all();
+            }
+            None => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn epoch(index: i32) -> EngineEvent<i32> {
+        EngineEvent::EpochComplete {
+            index,
+            best: index,
+            score: Score::from_int(index),
+            metrics: MetricSet::new(),
+        }
+    }
+
+    fn index(event: &EngineEvent<i32>) -> i32 {
+        match event {
+            EngineEvent::Start => -1,
+            EngineEvent::EpochComplete { index, .. } => *index,
+            EngineEvent::Stop { .. } => -2,
+            EngineEvent::Error { .. } => -3,
+        }
+    }
+
+    /// A subscriber that records what it sees and holds the first event until `release` is sent,
+    /// so the queue fills up behind it.
+    fn gated() -> (
+        BufferedSubscriber<i32>,
+        mpsc::Sender<()>,
+        Arc<Mutex<Vec<i32>>>,
+    ) {
+        let (release, gate) = mpsc::channel::<()>();
+        let gate = Mutex::new(gate);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let record = Arc::clone(&seen);
+
+        let subscriber = BufferedSubscriber::new(
+            move |event: &EngineEvent<i32>| {
+                if record.lock().unwrap().is_empty() {
+                    gate.lock().unwrap().recv().unwrap();
+                }
+
+                record.lock().unwrap().push(index(event));
+            },
+            2,
+        );
+
+        (subscriber, release, seen)
+    }
+
+    #[test]
+    fn test_coalesce_keeps_the_latest_epoch() {
+        let (subscriber, release, seen) = gated();
+        let subscriber = subscriber.with_policy(OverflowPolicy::Coalesce);
+
+        subscriber.on_event(&EngineEvent::Start);
+        // Wait for the delivery thread to pick up `Start` and block on the gate.
+        while !subscriber.shared.queue.lock().unwrap().events.is_empty() {
+            std::thread::yield_now();
+        }
+
+        for i in 1..=5 {
+            subscriber.on_event(&epoch(i));
+        }
+
+        release.send(()).unwrap();
+        subscriber.flush();
+
+        assert_eq!(*seen.lock().unwrap(), vec![-1, 1, 5]);
+        assert_eq!(subscriber.dropped(), 3);
+    }
+
+    #[test]
+    fn test_block_delivers_every_event() {
+        let (subscriber, release, seen) = gated();
+        let subscriber = Arc::new(subscriber.with_policy(OverflowPolicy::Block));
+
+        let producer = {
+            let subscriber = Arc::clone(&subscriber);
+            std::thread::spawn(move || {
+                for i in 1..=6 {
+                    subscriber.on_event(&epoch(i));
+                }
+            })
+        };
+
+        release.send(()).unwrap();
+        producer.join().unwrap();
+        subscriber.flush();
+
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(subscriber.dropped(), 0);
+    }
+}
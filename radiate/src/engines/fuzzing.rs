@@ -0,0 +1,208 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// A byte-oriented target to fuzz. Executing the target on an input returns the ids of the coverage
+/// points (edges, blocks, states...) that the input reached. How coverage is collected is up to the
+/// implementation - instrumentation counters, a simulator, a parser's state machine, etc.
+///
+/// Any `Fn(&[u8]) -> Vec<usize>` is a `FuzzTarget`.
+pub trait FuzzTarget: Send + Sync {
+    fn execute(&self, input: &[u8]) -> Vec<usize>;
+}
+
+impl<F> FuzzTarget for F
+where
+    F: Fn(&[u8]) -> Vec<usize> + Send + Sync,
+{
+    fn execute(&self, input: &[u8]) -> Vec<usize> {
+        self(input)
+    }
+}
+
+/// A collection of interesting inputs. A `Corpus` can seed the initial population (see `BytesCodex::with_corpus`)
+/// and is grown by the `CoverageFitness` with every input that reaches new coverage. It can be imported from and
+/// exported to a directory with one input per file, the layout used by most fuzzers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Corpus {
+    entries: Vec<Vec<u8>>,
+}
+
+impl Corpus {
+    pub fn new() -> Self {
+        Corpus {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Read every file in `dir` as a corpus entry. Entries are sorted by file name.
+    pub fn import_dir(dir: impl AsRef<Path>) -> std::io::Result<Corpus> {
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.is_file());
+        paths.sort();
+
+        let entries = paths
+            .iter()
+            .map(std::fs::read)
+            .collect::<std::io::Result<Vec<Vec<u8>>>>()?;
+
+        Ok(Corpus { entries })
+    }
+
+    /// Write every entry to its own file (`000000`, `000001`, ...) in `dir`, creating it if needed.
+    pub fn export_dir(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            std::fs::write(dir.join(format!("{:06}", index)), entry)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn add(&mut self, input: Vec<u8>) {
+        self.entries.push(input);
+    }
+
+    pub fn entries(&self) -> &[Vec<u8>] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl From<Vec<Vec<u8>>> for Corpus {
+    fn from(entries: Vec<Vec<u8>>) -> Self {
+        Corpus { entries }
+    }
+}
+
+/// Adapts a `FuzzTarget` into a coverage-guided fitness function. The fitness of an input is the number of
+/// distinct coverage points it reaches plus a `novelty_bonus` for every point no previous input has reached.
+/// Inputs that reach new coverage are added to the shared `Corpus`.
+///
+/// Like the `HallOfFame`, the `CoverageFitness` is cheap to clone and all clones share the same global
+/// coverage and corpus, so one clone can be moved into the fitness function and another kept to
+/// inspect or export the results after the run.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // A toy target - each matching prefix byte of "FUZZ" is a new coverage point.
+/// let target = |input: &[u8]| {
+///     input.iter().zip(b"FUZZ").take_while(|(a, b)| a == b).enumerate().map(|(i, _)| i).collect()
+/// };
+///
+/// let coverage = CoverageFitness::new(target);
+/// let fitness = coverage.clone();
+///
+/// let engine = GeneticEngine::from_codex(BytesCodex::new(1, 8).with_corpus(vec![b"AAAA".to_vec()]))
+///     .alter(alters!(
+///         UniformMutator::new(0.1),
+///         InsertionMutator::new(0.1),
+///         DeletionMutator::new(0.1)
+///     ))
+///     .fitness_fn(move |input: Vec<u8>| fitness.evaluate(&input))
+///     .build();
+///
+/// engine.run(|ctx| ctx.index > 10);
+/// // Every corpus entry reached at least one new coverage point.
+/// assert!(coverage.corpus().len() <= coverage.coverage().len());
+/// ```
+#[derive(Clone)]
+pub struct CoverageFitness {
+    target: Arc<dyn FuzzTarget>,
+    coverage: Arc<Mutex<BTreeSet<usize>>>,
+    corpus: Arc<Mutex<Corpus>>,
+    novelty_bonus: f32,
+}
+
+impl CoverageFitness {
+    pub fn new(target: impl FuzzTarget + 'static) -> Self {
+        CoverageFitness {
+            target: Arc::new(target),
+            coverage: Arc::new(Mutex::new(BTreeSet::new())),
+            corpus: Arc::new(Mutex::new(Corpus::new())),
+            novelty_bonus: 10.0,
+        }
+    }
+
+    /// Set the bonus awarded for every newly reached coverage point. Default is 10.0.
+    pub fn novelty_bonus(mut self, novelty_bonus: f32) -> Self {
+        self.novelty_bonus = novelty_bonus;
+        self
+    }
+
+    /// Execute the target on the input, record its coverage and return its fitness.
+    pub fn evaluate(&self, input: &[u8]) -> f32 {
+        let reached = self
+            .target
+            .execute(input)
+            .into_iter()
+            .collect::<BTreeSet<usize>>();
+
+        let new_points = {
+            let mut coverage = self.coverage.lock().unwrap();
+            reached
+                .iter()
+                .filter(|point| coverage.insert(**point))
+                .count()
+        };
+
+        if new_points > 0 {
+            self.corpus.lock().unwrap().add(input.to_vec());
+        }
+
+        reached.len() as f32 + new_points as f32 * self.novelty_bonus
+    }
+
+    /// All coverage points reached so far.
+    pub fn coverage(&self) -> BTreeSet<usize> {
+        self.coverage.lock().unwrap().clone()
+    }
+
+    /// The inputs that reached new coverage, in the order they were found.
+    pub fn corpus(&self) -> Corpus {
+        self.corpus.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_coverage_is_rewarded_once() {
+        let fitness =
+            CoverageFitness::new(|input: &[u8]| input.iter().map(|b| *b as usize).collect());
+
+        assert_eq!(fitness.evaluate(&[1, 2]), 22.0);
+        assert_eq!(fitness.evaluate(&[1, 2]), 2.0);
+        assert_eq!(fitness.evaluate(&[2, 3]), 12.0);
+
+        assert_eq!(fitness.coverage().len(), 3);
+        assert_eq!(fitness.corpus().entries(), &[vec![1, 2], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_corpus_round_trip() {
+        let dir = std::env::temp_dir().join(format!("radiate_corpus_{}", std::process::id()));
+        let corpus = Corpus::from(vec![b"one".to_vec(), vec![0, 255], Vec::new()]);
+
+        corpus.export_dir(&dir).unwrap();
+        let imported = Corpus::import_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(imported, corpus);
+    }
+}
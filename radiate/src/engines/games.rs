@@ -0,0 +1,316 @@
+use super::{
+    random_provider, FloatChromosome, FloatCodex, GeneticEngine, GeneticEngineParams,
+    GroupEvaluator, HallOfFame, Outcome, TournamentSelector,
+};
+
+/// A two-player, turn-based game of perfect information - the environment competitive co-evolution
+/// plays its agents in. Players are numbered 0 (who moves first) and 1.
+pub trait Game: Clone {
+    type Move: Clone;
+
+    /// The player whose turn it is.
+    fn to_move(&self) -> usize;
+
+    /// The legal moves of the player to move - empty once the game is over.
+    fn moves(&self) -> Vec<Self::Move>;
+
+    fn play(&mut self, mv: &Self::Move);
+
+    /// The winner, once there is one.
+    fn winner(&self) -> Option<usize>;
+
+    fn is_over(&self) -> bool {
+        self.winner().is_some() || self.moves().is_empty()
+    }
+}
+
+/// Play a game to the end, asking `first` for the moves of player 0 and `second` for those of
+/// player 1. Returns the outcome for `first`.
+pub fn play_game<G, A, B>(mut game: G, first: A, second: B) -> Outcome
+where
+    G: Game,
+    A: Fn(&G) -> G::Move,
+    B: Fn(&G) -> G::Move,
+{
+    while !game.is_over() {
+        let mv = match game.to_move() {
+            0 => first(&game),
+            _ => second(&game),
+        };
+
+        game.play(&mv);
+    }
+
+    match game.winner() {
+        Some(0) => Outcome::Win,
+        Some(_) => Outcome::Loss,
+        None => Outcome::Draw,
+    }
+}
+
+/// Play `rounds` pairs of games between two agents - one with each agent moving first, to even out the
+/// advantage of the first move - and return the points of agent `one` per game (see `Outcome::points`).
+pub fn play_match<G, A, B>(game: &G, one: A, two: B, rounds: usize) -> f32
+where
+    G: Game,
+    A: Fn(&G) -> G::Move,
+    B: Fn(&G) -> G::Move,
+{
+    if rounds == 0 {
+        return 0.5;
+    }
+
+    let points = (0..rounds)
+        .map(|_| {
+            let first = play_game(game.clone(), &one, &two).points();
+            let second = 1.0 - play_game(game.clone(), &two, &one).points();
+            first + second
+        })
+        .sum::<f32>();
+
+    points / (2 * rounds) as f32
+}
+
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// Tic-tac-toe on a 3x3 board, cells numbered 0 to 8 row by row. It ships with a reference
+/// competitive co-evolution setup (`TicTacToe::coevolution`) - agents are heuristic players whose
+/// genes weigh the `FEATURES` of every legal move, scored by playing each other in a `GroupEvaluator`
+/// and against the champions of earlier generations in a `HallOfFame`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = TicTacToe::coevolution(HallOfFame::new(3))
+///     .population_size(20)
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+///
+/// let agent = &result.best[0];
+/// let points = play_match(
+///     &TicTacToe::new(),
+///     |game| game.agent_move(agent),
+///     TicTacToe::random_move,
+///     10,
+/// );
+/// assert!((0.0..=1.0).contains(&points));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TicTacToe {
+    cells: [Option<usize>; 9],
+}
+
+impl TicTacToe {
+    /// The number of features describing a move, and so the number of genes of an agent.
+    pub const FEATURES: usize = 7;
+
+    /// An empty board.
+    pub fn new() -> Self {
+        TicTacToe { cells: [None; 9] }
+    }
+
+    /// The player holding each cell.
+    pub fn cells(&self) -> &[Option<usize>; 9] {
+        &self.cells
+    }
+
+    /// The features of playing `cell` for the player to move, which an agent weighs to pick its move:
+    /// the move wins, it blocks a line the opponent would win next, it takes the center, a corner or
+    /// an edge, the number of lines it leaves the player one move from winning, and the number of such
+    /// lines it leaves the opponent.
+    pub fn features(&self, cell: usize) -> [f32; TicTacToe::FEATURES] {
+        let me = self.to_move();
+        let mut after = self.clone();
+        after.cells[cell] = Some(me);
+
+        let mut blocked = self.clone();
+        blocked.cells[cell] = Some(1 - me);
+
+        let corner = [0, 2, 6, 8].contains(&cell);
+        [
+            (after.winner() == Some(me)) as i32 as f32,
+            (blocked.winner() == Some(1 - me)) as i32 as f32,
+            (cell == 4) as i32 as f32,
+            corner as i32 as f32,
+            (cell != 4 && !corner) as i32 as f32,
+            after.threats(me) as f32,
+            after.threats(1 - me) as f32,
+        ]
+    }
+
+    /// The move of an agent - the legal move whose features weigh the most with the agent's
+    /// `weights` (ties are broken at random). Panics if the game is over.
+    pub fn agent_move(&self, weights: &[f32]) -> usize {
+        let moves = self.moves();
+        let value = |cell: usize| {
+            self.features(cell)
+                .iter()
+                .zip(weights.iter())
+                .map(|(feature, weight)| feature * weight)
+                .sum::<f32>()
+        };
+
+        let best = moves
+            .iter()
+            .map(|cell| value(*cell))
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        let candidates = moves
+            .into_iter()
+            .filter(|cell| value(*cell) >= best)
+            .collect::<Vec<usize>>();
+
+        *random_provider::choose(&candidates)
+    }
+
+    /// A player that picks one of the legal moves at random. Panics if the game is over.
+    pub fn random_move(&self) -> usize {
+        *random_provider::choose(&self.moves())
+    }
+
+    /// A codex for agents - one chromosome with a weight per feature.
+    pub fn codex() -> FloatCodex {
+        FloatCodex::new(1, TicTacToe::FEATURES, -1.0, 1.0)
+    }
+
+    /// A competitive co-evolution of tic-tac-toe agents. Every individual plays a match against a
+    /// group mate in each of 3 rounds (a `GroupEvaluator` of pairs), and against the most recent
+    /// champions of the `hall_of_fame`, which the engine adds every generation's champion to. Its
+    /// score is the mean of its points from the two. The returned parameters can be configured further.
+    pub fn coevolution(
+        hall_of_fame: HallOfFame<Vec<Vec<f32>>>,
+    ) -> GeneticEngineParams<FloatChromosome, Vec<Vec<f32>>> {
+        let archive = hall_of_fame.clone();
+        let game = TicTacToe::new();
+
+        let evaluator = GroupEvaluator::new(2, move |agents: &[Vec<Vec<f32>>]| {
+            let champions = |agent: &Vec<Vec<f32>>| {
+                archive.evaluate(agent, |me, champion| {
+                    play_game(
+                        game.clone(),
+                        |board: &TicTacToe| board.agent_move(&me[0]),
+                        |board: &TicTacToe| board.agent_move(&champion[0]),
+                    )
+                })
+            };
+
+            match agents {
+                [one, two] => {
+                    let points = play_match(
+                        &game,
+                        |board: &TicTacToe| board.agent_move(&one[0]),
+                        |board: &TicTacToe| board.agent_move(&two[0]),
+                        2,
+                    );
+
+                    vec![
+                        (points + champions(one)) / 2.0,
+                        (1.0 - points + champions(two)) / 2.0,
+                    ]
+                }
+                _ => agents.iter().map(champions).collect(),
+            }
+        })
+        .rounds(3);
+
+        GeneticEngine::from_codex(TicTacToe::codex())
+            .offspring_selector(TournamentSelector::new(3))
+            .group_evaluator(evaluator)
+            .hall_of_fame(hall_of_fame)
+    }
+
+    /// The number of lines the player holds two cells of, with the third empty.
+    fn threats(&self, player: usize) -> usize {
+        LINES
+            .iter()
+            .filter(|line| {
+                let mine = line
+                    .iter()
+                    .filter(|cell| self.cells[**cell] == Some(player));
+                let empty = line.iter().filter(|cell| self.cells[**cell].is_none());
+                mine.count() == 2 && empty.count() == 1
+            })
+            .count()
+    }
+}
+
+impl Game for TicTacToe {
+    type Move = usize;
+
+    fn to_move(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_some()).count() % 2
+    }
+
+    fn moves(&self) -> Vec<usize> {
+        if self.winner().is_some() {
+            return Vec::new();
+        }
+
+        (0..9).filter(|cell| self.cells[*cell].is_none()).collect()
+    }
+
+    /// Panics if the cell is taken.
+    fn play(&mut self, cell: &usize) {
+        if self.cells[*cell].is_some() {
+            panic!("Cell {} is already taken", cell);
+        }
+
+        self.cells[*cell] = Some(self.to_move());
+    }
+
+    fn winner(&self) -> Option<usize> {
+        LINES.iter().find_map(|line| {
+            let first = self.cells[line[0]]?;
+            line.iter()
+                .all(|cell| self.cells[*cell] == Some(first))
+                .then_some(first)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tic_tac_toe_rules() {
+        let mut game = TicTacToe::new();
+        for cell in [0, 3, 1, 4] {
+            game.play(&cell);
+        }
+
+        assert_eq!(game.to_move(), 0);
+        assert_eq!(game.features(2)[0], 1.0);
+        assert_eq!(game.features(5)[1], 1.0);
+        assert_eq!(game.agent_move(&[1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]), 2);
+
+        game.play(&2);
+        assert_eq!(game.winner(), Some(0));
+        assert!(game.is_over());
+        assert!(game.moves().is_empty());
+    }
+
+    #[test]
+    fn test_a_sensible_agent_beats_a_random_player() {
+        let sensible = [1.0, 0.8, 0.3, 0.2, 0.0, 0.1, -0.5];
+        let points = play_match(
+            &TicTacToe::new(),
+            |game| game.agent_move(&sensible),
+            TicTacToe::random_move,
+            50,
+        );
+
+        assert!(points > 0.8);
+    }
+}
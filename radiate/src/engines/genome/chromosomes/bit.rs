@@ -1,5 +1,6 @@
 use crate::{random_provider, Chromosome, Gene, Valid};
-use std::fmt::Debug;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
 /// A gene that represents a single bit. The `allele` is a `bool` that is randomly assigned.
 /// The `allele` is either `true` or `false`. This is the simplest form of a gene and
@@ -64,7 +65,7 @@ impl Default for BitGene {
 }
 
 impl Debug for BitGene {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", if self.allele { 1 } else { 0 })
     }
 }
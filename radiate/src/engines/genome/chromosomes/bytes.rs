@@ -0,0 +1,64 @@
+use super::{gene::Valid, Gene, SequenceChromosome};
+use crate::random_provider;
+
+/// A `Gene` that represents a single byte of an input, e.g. a test case for a fuzzing target.
+/// New instances are uniformly random bytes.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct ByteGene {
+    pub allele: u8,
+}
+
+impl ByteGene {
+    pub fn new() -> Self {
+        ByteGene {
+            allele: random_provider::random::<u8>(),
+        }
+    }
+}
+
+impl Gene for ByteGene {
+    type Allele = u8;
+
+    fn allele(&self) -> &u8 {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> ByteGene {
+        ByteGene::new()
+    }
+
+    fn with_allele(&self, allele: &u8) -> ByteGene {
+        ByteGene { allele: *allele }
+    }
+}
+
+impl Valid for ByteGene {}
+
+impl std::fmt::Debug for ByteGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02x}", self.allele)
+    }
+}
+
+impl From<u8> for ByteGene {
+    fn from(allele: u8) -> Self {
+        ByteGene { allele }
+    }
+}
+
+/// A variable-length chromosome of raw bytes. Being a `SequenceChromosome`, it can grow and shrink
+/// with the `InsertionMutator`, `DeletionMutator` and `AlignmentCrossover`, and the `BitFlipMutator`
+/// provides the classic fuzzer bit flips.
+pub type BytesChromosome = SequenceChromosome<ByteGene>;
+
+impl SequenceChromosome<ByteGene> {
+    /// Create a `BytesChromosome` holding the given bytes with a length limit of `min_len` to `max_len`.
+    pub fn from_bytes(bytes: &[u8], min_len: usize, max_len: usize) -> Self {
+        let genes = bytes.iter().map(|byte| ByteGene::from(*byte)).collect();
+        SequenceChromosome::from_genes(ByteGene::default(), genes).with_len_bounds(min_len, max_len)
+    }
+
+    pub fn bytes(&self) -> Vec<u8> {
+        self.genes.iter().map(|gene| gene.allele).collect()
+    }
+}
@@ -1,4 +1,5 @@
 use crate::random_provider;
+use alloc::vec::Vec;
 
 use super::{
     gene::{Gene, Valid},
@@ -29,6 +30,12 @@ pub struct CharGene {
     pub allele: char,
 }
 
+impl Default for CharGene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CharGene {
     pub fn new() -> Self {
         let index = random_provider::random::<usize>() % ALPHABET.len();
@@ -59,8 +66,8 @@ impl Gene for CharGene {
 
 impl Valid for CharGene {}
 
-impl std::fmt::Debug for CharGene {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for CharGene {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.allele)
     }
 }
@@ -1,4 +1,4 @@
-use super::{gene::Gene, Valid};
+use super::{gene::Gene, view::ChromosomeView, Valid};
 
 /// The `Chromosome` struct represents a collection of `Gene` instances. The `Chromosome` is part of the
 /// genetic makeup of an individual. It is a collection of `Gene` instances, it is essentially a
@@ -43,11 +43,20 @@ pub trait Chromosome:
         self.as_ref().len()
     }
 
-    fn iter(&self) -> std::slice::Iter<Self::Gene> {
+    fn is_empty(&self) -> bool {
+        self.as_ref().is_empty()
+    }
+
+    fn iter(&self) -> core::slice::Iter<'_, Self::Gene> {
         self.as_ref().iter()
     }
 
-    fn iter_mut(&mut self) -> std::slice::IterMut<Self::Gene> {
+    fn iter_mut(&mut self) -> core::slice::IterMut<'_, Self::Gene> {
         self.as_mut().iter_mut()
     }
+
+    /// Returns a read-only `ChromosomeView` over all of the genes in the chromosome.
+    fn view(&self) -> ChromosomeView<'_, Self::Gene> {
+        ChromosomeView::new(self.as_ref())
+    }
 }
@@ -0,0 +1,274 @@
+use super::{
+    gene::{BoundGene, Gene, NumericGene, Valid},
+    Chromosome,
+};
+use crate::random_provider;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A complex number with `f32` real and imaginary parts.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub fn new(re: f32, im: f32) -> Self {
+        Complex { re, im }
+    }
+
+    /// The magnitude (absolute value) of the complex number.
+    pub fn norm(&self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    /// The phase angle of the complex number in radians.
+    pub fn arg(&self) -> f32 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    /// Complex division. Dividing by zero returns the numerator unchanged.
+    fn div(self, other: Complex) -> Complex {
+        let denominator = other.re * other.re + other.im * other.im;
+        if denominator == 0.0 {
+            return self;
+        }
+
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+/// A `Gene` that represents a complex number, e.g. a pole or zero of a filter or transfer function.
+/// The real and imaginary parts are generated independently between the real and imaginary parts
+/// of `min` and `max`, and the `upper_bound` and `lower_bound` box the allele in the complex plane.
+///
+/// Arithmetic between `ComplexGene`s is proper complex arithmetic, so the `ArithmeticMutator` and
+/// `MeanCrossover` work on the complex value rather than treating it as two unrelated floats.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let gene = ComplexGene::new(Complex::new(-1.0, -1.0), Complex::new(1.0, 1.0));
+/// assert!(gene.allele().norm() <= 2.0_f32.sqrt());
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct ComplexGene {
+    pub allele: Complex,
+    pub min: Complex,
+    pub max: Complex,
+    pub upper_bound: Complex,
+    pub lower_bound: Complex,
+}
+
+impl ComplexGene {
+    /// Create a new `ComplexGene` with random real and imaginary parts between those of `min` and `max`.
+    /// The bounds are set to `min` and `max`.
+    pub fn new(min: Complex, max: Complex) -> Self {
+        ComplexGene {
+            allele: Self::random(min, max),
+            min,
+            max,
+            upper_bound: max,
+            lower_bound: min,
+        }
+    }
+
+    fn random(min: Complex, max: Complex) -> Complex {
+        Complex::new(
+            random_provider::gen_range(min.re..max.re),
+            random_provider::gen_range(min.im..max.im),
+        )
+    }
+}
+
+impl Gene for ComplexGene {
+    type Allele = Complex;
+
+    fn allele(&self) -> &Complex {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> ComplexGene {
+        ComplexGene {
+            allele: Self::random(self.min, self.max),
+            ..*self
+        }
+    }
+
+    fn with_allele(&self, allele: &Complex) -> ComplexGene {
+        ComplexGene {
+            allele: *allele,
+            ..*self
+        }
+    }
+}
+
+/// A `ComplexGene` is valid if both parts of the allele are within the bounds.
+impl Valid for ComplexGene {
+    fn is_valid(&self) -> bool {
+        self.allele.re >= self.lower_bound.re
+            && self.allele.re <= self.upper_bound.re
+            && self.allele.im >= self.lower_bound.im
+            && self.allele.im <= self.upper_bound.im
+    }
+}
+
+impl BoundGene for ComplexGene {
+    fn upper_bound(&self) -> &Complex {
+        &self.upper_bound
+    }
+
+    fn lower_bound(&self) -> &Complex {
+        &self.lower_bound
+    }
+
+    fn with_bounds(self, upper_bound: Complex, lower_bound: Complex) -> ComplexGene {
+        ComplexGene {
+            upper_bound,
+            lower_bound,
+            ..self
+        }
+    }
+}
+
+impl NumericGene for ComplexGene {
+    fn min(&self) -> &Complex {
+        &self.min
+    }
+
+    fn max(&self) -> &Complex {
+        &self.max
+    }
+
+    fn mean(&self, other: &ComplexGene) -> ComplexGene {
+        self.with_allele(&Complex::new(
+            (self.allele.re + other.allele.re) / 2.0,
+            (self.allele.im + other.allele.im) / 2.0,
+        ))
+    }
+}
+
+macro_rules! complex_gene_op_impl {
+    ($trait:ident, $method:ident) => {
+        impl $trait for ComplexGene {
+            type Output = ComplexGene;
+
+            #[inline]
+            fn $method(self, other: ComplexGene) -> ComplexGene {
+                ComplexGene {
+                    allele: self.allele.$method(other.allele),
+                    ..self
+                }
+            }
+        }
+    };
+}
+
+complex_gene_op_impl!(Add, add);
+complex_gene_op_impl!(Sub, sub);
+complex_gene_op_impl!(Mul, mul);
+complex_gene_op_impl!(Div, div);
+
+impl std::fmt::Debug for ComplexGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{:+}i", self.allele.re, self.allele.im)
+    }
+}
+
+/// Represents a chromosome composed of `ComplexGene`s.
+#[derive(Clone, PartialEq, Default)]
+pub struct ComplexChromosome {
+    pub genes: Vec<ComplexGene>,
+}
+
+impl ComplexChromosome {
+    pub fn new(genes: Vec<ComplexGene>) -> Self {
+        ComplexChromosome { genes }
+    }
+}
+
+impl Chromosome for ComplexChromosome {
+    type Gene = ComplexGene;
+}
+
+impl Valid for ComplexChromosome {
+    fn is_valid(&self) -> bool {
+        self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl AsRef<[ComplexGene]> for ComplexChromosome {
+    fn as_ref(&self) -> &[ComplexGene] {
+        &self.genes
+    }
+}
+
+impl AsMut<[ComplexGene]> for ComplexChromosome {
+    fn as_mut(&mut self) -> &mut [ComplexGene] {
+        &mut self.genes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+
+        assert_eq!(a + b, Complex::new(4.0, 1.0));
+        assert_eq!(a - b, Complex::new(-2.0, 3.0));
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert_eq!((a * b) / b, a);
+        assert_eq!(a / Complex::default(), a);
+    }
+
+    #[test]
+    fn test_new_instance_is_valid() {
+        let gene = ComplexGene::new(Complex::new(-1.0, -2.0), Complex::new(1.0, 2.0));
+        for _ in 0..100 {
+            assert!(gene.new_instance().is_valid());
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use super::{
-    gene::{BoundGene, Gene, NumericGene, Valid},
+    gene::{BoundGene, BoundaryPolicy, Gene, NumericGene, Valid},
     Chromosome,
 };
 use crate::random_provider;
-use std::{fmt::Debug, ops::Range};
+use alloc::vec::Vec;
+use core::{fmt::Debug, ops::Range};
 
 /// A `Gene` that represents a floating point number.
 /// The `allele` is the in the case of the `FloatGene` a f32. The `min` and `max` values
@@ -11,7 +12,8 @@ use std::{fmt::Debug, ops::Range};
 /// generate a random number between the `min` and `max` values, which is the `allele` of the `FloatGene`.
 /// The `upper_bound` and `lower_bound` are used to set the bounds of the `FloatGene` when it is used
 /// in a `BoundGene` context (crossover or mutation). The `upper_bound` and `lower_bound`
-/// default to f32::MAX and f32::MIN respectively.
+/// default to f32::MAX and f32::MIN respectively. An allele that a numeric alterer pushes outside
+/// of the bounds is brought back by the gene's `BoundaryPolicy` - clamped by default.
 ///
 /// # Example
 /// ``` rust
@@ -34,6 +36,7 @@ pub struct FloatGene {
     pub max: f32,
     pub upper_bound: f32,
     pub lower_bound: f32,
+    pub boundary: BoundaryPolicy,
 }
 
 impl FloatGene {
@@ -44,8 +47,14 @@ impl FloatGene {
             max,
             upper_bound: f32::MAX,
             lower_bound: f32::MIN,
+            boundary: BoundaryPolicy::default(),
         }
     }
+
+    /// Set the `BoundaryPolicy` used to bring alleles back within the gene's bounds.
+    pub fn with_boundary(self, boundary: BoundaryPolicy) -> Self {
+        FloatGene { boundary, ..self }
+    }
 }
 
 /// Implement the `Valid` trait for the `FloatGene`.
@@ -73,6 +82,7 @@ impl Gene for FloatGene {
             max: self.max,
             upper_bound: self.upper_bound,
             lower_bound: self.lower_bound,
+            boundary: self.boundary,
         }
     }
 
@@ -83,6 +93,7 @@ impl Gene for FloatGene {
             max: self.max,
             upper_bound: self.upper_bound,
             lower_bound: self.lower_bound,
+            boundary: self.boundary,
         }
     }
 }
@@ -120,10 +131,19 @@ impl NumericGene for FloatGene {
             ..*self
         }
     }
+
+    fn bounded(&self, allele: &f32) -> FloatGene {
+        let allele = self.boundary.apply(
+            *allele as f64,
+            self.lower_bound as f64,
+            self.upper_bound as f64,
+        );
+        self.with_allele(&(allele as f32))
+    }
 }
 
 impl Debug for FloatGene {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.allele)
     }
 }
@@ -142,6 +162,7 @@ impl From<f32> for FloatGene {
             max: f32::MAX,
             upper_bound: f32::MAX,
             lower_bound: f32::MIN,
+            boundary: BoundaryPolicy::default(),
         }
     }
 }
@@ -154,6 +175,7 @@ impl From<&f32> for FloatGene {
             max: f32::MAX,
             upper_bound: f32::MAX,
             lower_bound: f32::MIN,
+            boundary: BoundaryPolicy::default(),
         }
     }
 }
@@ -211,6 +233,19 @@ impl From<Range<i32>> for FloatChromosome {
     }
 }
 
+impl FloatChromosome {
+    /// Set the `BoundaryPolicy` of every gene of the chromosome.
+    pub fn with_boundary(self, boundary: BoundaryPolicy) -> Self {
+        FloatChromosome {
+            genes: self
+                .genes
+                .into_iter()
+                .map(|gene| gene.with_boundary(boundary))
+                .collect(),
+        }
+    }
+}
+
 impl From<&[f32]> for FloatChromosome {
     fn from(alleles: &[f32]) -> Self {
         let genes = alleles.iter().map(FloatGene::from).collect();
@@ -249,4 +284,30 @@ mod tests {
         assert!(gene.is_valid());
         assert!(gene.allele >= 0_f32 && gene.allele <= 1_f32);
     }
+
+    #[test]
+    fn test_bounded_honors_boundary_policy() {
+        let gene = FloatGene::new(0_f32, 10_f32).with_bounds(0.0, 10.0);
+
+        assert_eq!(gene.bounded(&10.0).allele, 10.0);
+        assert_eq!(gene.bounded(&0.0).allele, 0.0);
+        assert_eq!(gene.bounded(&12.0).allele, 10.0);
+        assert_eq!(gene.bounded(&-3.0).allele, 0.0);
+
+        let reflect = gene.clone().with_boundary(BoundaryPolicy::Reflect);
+        assert_eq!(reflect.bounded(&12.0).allele, 8.0);
+        assert_eq!(reflect.bounded(&-3.0).allele, 3.0);
+        assert_eq!(reflect.bounded(&25.0).allele, 5.0);
+
+        let wrap = gene.clone().with_boundary(BoundaryPolicy::Wrap);
+        assert_eq!(wrap.bounded(&12.0).allele, 2.0);
+        assert_eq!(wrap.bounded(&-3.0).allele, 7.0);
+
+        let resample = gene.with_boundary(BoundaryPolicy::Resample);
+        for _ in 0..20 {
+            let allele = resample.bounded(&f32::NAN).allele;
+            assert!((0.0..=10.0).contains(&allele));
+            assert!((0.0..=10.0).contains(&resample.bounded(&-50.0).allele));
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::random_provider;
+
 /// A `Valid` type is a type that can be checked for validity. This is used for checking if a gene
 /// or a chromosome is valid. For example, a gene that represents a number between 0 and 1 can be checked
 /// for validity by ensuring that the allele is between 0 and 1.
@@ -91,4 +93,71 @@ pub trait NumericGene: BoundGene {
 
     /// Get the value of the gene as a number.
     fn mean(&self, other: &Self) -> Self;
+
+    /// Create a new `Gene` with the given `allele`, brought back within the gene's valid range by its
+    /// `BoundaryPolicy`. Numeric mutators and crossovers use this instead of `with_allele`, so the
+    /// policy is honored by every one of them. Defaults to `with_allele`.
+    fn bounded(&self, allele: &Self::Allele) -> Self {
+        self.with_allele(allele)
+    }
+}
+
+/// How a numeric gene handles an allele that an alterer pushed outside of its valid range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BoundaryPolicy {
+    /// Move the allele to the nearest bound.
+    #[default]
+    Clamp,
+    /// Mirror the allele back into the range at the bound it crossed.
+    Reflect,
+    /// Wrap the allele around to the other side of the range, as if the range were periodic.
+    Wrap,
+    /// Replace the allele with a uniformly random value within the range.
+    Resample,
+}
+
+impl BoundaryPolicy {
+    /// Bring `value` within `[lower, upper]`. Values within the range are returned as they are. An
+    /// infinite value is clamped and NaN is resampled, whatever the policy.
+    pub fn apply(&self, value: f64, lower: f64, upper: f64) -> f64 {
+        if (lower..=upper).contains(&value) {
+            return value;
+        }
+
+        let width = upper - lower;
+        if width <= 0.0 {
+            return lower;
+        }
+
+        if value.is_nan() {
+            return lower + random_provider::random::<f64>() * width;
+        }
+
+        if value.is_infinite() {
+            return value.clamp(lower, upper);
+        }
+
+        match self {
+            BoundaryPolicy::Clamp => value.clamp(lower, upper),
+            BoundaryPolicy::Reflect => {
+                let offset = rem_euclid(value - lower, 2.0 * width);
+                match offset > width {
+                    true => lower + 2.0 * width - offset,
+                    false => lower + offset,
+                }
+            }
+            BoundaryPolicy::Wrap => lower + rem_euclid(value - lower, width),
+            BoundaryPolicy::Resample => lower + random_provider::random::<f64>() * width,
+        }
+        .clamp(lower, upper)
+    }
+}
+
+/// `f64::rem_euclid`, which is only available with `std`.
+fn rem_euclid(value: f64, modulus: f64) -> f64 {
+    let rem = value % modulus;
+    match rem < 0.0 {
+        true => rem + modulus,
+        false => rem,
+    }
 }
@@ -1,8 +1,9 @@
 use super::{
-    gene::{BoundGene, Gene, NumericGene, Valid},
+    gene::{BoundGene, BoundaryPolicy, Gene, NumericGene, Valid},
     Chromosome, Integer,
 };
 use crate::random_provider;
+use alloc::vec::Vec;
 use rand::distributions::Distribution;
 use rand::distributions::Standard;
 
@@ -11,6 +12,8 @@ use rand::distributions::Standard;
 /// `allele` is the integer value itself, the min and max values are the minimum and maximum values
 /// that the integer can be generated from, and the upper and lower bounds are the upper and lower bounds the gene will
 /// be subject to during crossover and mutation. If the `allele` exceedes the bounds, the `Gene` will be considered invalid.
+/// An allele that a numeric alterer pushes outside of the valid range (`min` to `max`) is brought back by the
+/// gene's `BoundaryPolicy` - clamped by default.
 ///
 /// `IntGene` is generic over `T` - the type of integer. The `Integer` trait is implemented
 /// for `i8`, `i16`, `i32`, `i64`, `i128`, `u8`, `u16`, `u32`, `u64`, and `u128`.
@@ -49,6 +52,7 @@ where
     pub max: T,
     pub upper_bound: T,
     pub lower_bound: T,
+    pub boundary: BoundaryPolicy,
 }
 
 impl<T: Integer<T>> IntGene<T>
@@ -65,6 +69,7 @@ where
             max: T::MAX,
             upper_bound: T::MAX,
             lower_bound: T::MIN,
+            boundary: BoundaryPolicy::default(),
         }
     }
 
@@ -79,8 +84,14 @@ where
             max,
             upper_bound: T::MAX,
             lower_bound: T::MIN,
+            boundary: BoundaryPolicy::default(),
         }
     }
+
+    /// Set the `BoundaryPolicy` used to bring alleles back within the gene's valid range.
+    pub fn with_boundary(self, boundary: BoundaryPolicy) -> Self {
+        IntGene { boundary, ..self }
+    }
 }
 
 /// Implement the `Gene` trait for `IntGene`. This allows the `IntGene` to be used in a genetic algorithm.
@@ -102,6 +113,7 @@ where
             max: self.max,
             upper_bound: self.upper_bound,
             lower_bound: self.lower_bound,
+            boundary: self.boundary,
         }
     }
 
@@ -112,6 +124,7 @@ where
             max: self.max,
             upper_bound: self.upper_bound,
             lower_bound: self.lower_bound,
+            boundary: self.boundary,
         }
     }
 }
@@ -168,13 +181,26 @@ where
             ..*self
         }
     }
+
+    fn bounded(&self, allele: &T) -> IntGene<T> {
+        if *allele >= self.min && *allele <= self.max {
+            return self.with_allele(allele);
+        }
+
+        let allele = self.boundary.apply(
+            allele.to_f32() as f64,
+            self.min.to_f32() as f64,
+            self.max.to_f32() as f64,
+        );
+        self.with_allele(&T::from_f32(round(allele) as f32))
+    }
 }
 
-impl<T: Integer<T>> std::fmt::Debug for IntGene<T>
+impl<T: Integer<T>> core::fmt::Debug for IntGene<T>
 where
     Standard: Distribution<T>,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.allele)
     }
 }
@@ -286,6 +312,17 @@ where
     pub fn new(genes: Vec<IntGene<I>>) -> Self {
         IntChromosome { genes }
     }
+
+    /// Set the `BoundaryPolicy` of every gene of the chromosome.
+    pub fn with_boundary(self, boundary: BoundaryPolicy) -> Self {
+        IntChromosome {
+            genes: self
+                .genes
+                .into_iter()
+                .map(|gene| gene.with_boundary(boundary))
+                .collect(),
+        }
+    }
 }
 
 impl<I: Integer<I>> Chromosome for IntChromosome<I>
@@ -332,6 +369,14 @@ where
     }
 }
 
+/// `f64::round` (half away from zero), which is only available with `std`.
+fn round(value: f64) -> f64 {
+    match value < 0.0 {
+        true => (value - 0.5) as i64 as f64,
+        false => (value + 0.5) as i64 as f64,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,4 +439,27 @@ mod tests {
         let i: i32 = gene.into();
         assert_eq!(i, 5);
     }
+
+    #[test]
+    fn test_bounded_honors_boundary_policy() {
+        let gene = IntGene::from_min_max(0, 10);
+
+        assert_eq!(gene.bounded(&0).allele, 0);
+        assert_eq!(gene.bounded(&10).allele, 10);
+        assert_eq!(gene.bounded(&13).allele, 10);
+        assert_eq!(gene.bounded(&-2).allele, 0);
+
+        let reflect = gene.clone().with_boundary(BoundaryPolicy::Reflect);
+        assert_eq!(reflect.bounded(&13).allele, 7);
+        assert_eq!(reflect.bounded(&-2).allele, 2);
+
+        let wrap = gene.clone().with_boundary(BoundaryPolicy::Wrap);
+        assert_eq!(wrap.bounded(&13).allele, 3);
+        assert_eq!(wrap.bounded(&-2).allele, 8);
+
+        let resample = gene.with_boundary(BoundaryPolicy::Resample);
+        for _ in 0..20 {
+            assert!(resample.bounded(&100).is_valid());
+        }
+    }
 }
@@ -0,0 +1,313 @@
+use super::{
+    gene::{BoundGene, Gene, NumericGene, Valid},
+    Chromosome,
+};
+use crate::random_provider;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A closed interval `[lower, upper]` of `f32` values where `lower <= upper`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Interval {
+    pub lower: f32,
+    pub upper: f32,
+}
+
+impl Interval {
+    /// Create a new `Interval`. The endpoints are swapped if `lower > upper`.
+    pub fn new(lower: f32, upper: f32) -> Self {
+        if lower > upper {
+            Interval {
+                lower: upper,
+                upper: lower,
+            }
+        } else {
+            Interval { lower, upper }
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.upper - self.lower
+    }
+
+    pub fn midpoint(&self) -> f32 {
+        (self.lower + self.upper) / 2.0
+    }
+
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.lower && value <= self.upper
+    }
+
+    fn from_products(values: [f32; 4]) -> Interval {
+        Interval {
+            lower: values.iter().cloned().fold(f32::INFINITY, f32::min),
+            upper: values.iter().cloned().fold(f32::NEG_INFINITY, f32::max),
+        }
+    }
+}
+
+/// Interval arithmetic - the result of every operation is again a valid interval (`lower <= upper`).
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, other: Interval) -> Interval {
+        Interval {
+            lower: self.lower + other.lower,
+            upper: self.upper + other.upper,
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    fn sub(self, other: Interval) -> Interval {
+        Interval {
+            lower: self.lower - other.upper,
+            upper: self.upper - other.lower,
+        }
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+
+    fn mul(self, other: Interval) -> Interval {
+        Interval::from_products([
+            self.lower * other.lower,
+            self.lower * other.upper,
+            self.upper * other.lower,
+            self.upper * other.upper,
+        ])
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+
+    /// Interval division. Dividing by an interval that contains zero returns the numerator unchanged.
+    fn div(self, other: Interval) -> Interval {
+        if other.contains(0.0) {
+            return self;
+        }
+
+        Interval::from_products([
+            self.lower / other.lower,
+            self.lower / other.upper,
+            self.upper / other.lower,
+            self.upper / other.upper,
+        ])
+    }
+}
+
+/// A `Gene` that represents an interval (a lower/upper pair). Unlike evolving the endpoints as two
+/// independent floats, every operation on an `IntervalGene` keeps `lower <= upper`.
+///
+/// New alleles are generated with both endpoints between `min` and `max`. The bounds are stored as
+/// degenerate intervals - `lower_bound` is `[lo, lo]` and `upper_bound` is `[hi, hi]` - and the gene is
+/// valid as long as the allele lies within `[lo, hi]`. Results of arithmetic are clamped to the bounds.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let gene = IntervalGene::new(0.0, 10.0);
+/// let allele = gene.allele();
+/// assert!(allele.lower <= allele.upper);
+/// assert!(gene.is_valid());
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct IntervalGene {
+    pub allele: Interval,
+    pub min: Interval,
+    pub max: Interval,
+    pub upper_bound: Interval,
+    pub lower_bound: Interval,
+}
+
+impl IntervalGene {
+    /// Create a new `IntervalGene` with random endpoints between `min` and `max`. The bounds are set to `min` and `max`.
+    pub fn new(min: f32, max: f32) -> Self {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        let min = Interval::new(min, min);
+        let max = Interval::new(max, max);
+
+        IntervalGene {
+            allele: Self::random(min, max),
+            min,
+            max,
+            upper_bound: max,
+            lower_bound: min,
+        }
+    }
+
+    fn random(min: Interval, max: Interval) -> Interval {
+        Interval::new(
+            random_provider::gen_range(min.lower..max.upper),
+            random_provider::gen_range(min.lower..max.upper),
+        )
+    }
+
+    fn clamp(&self, interval: Interval) -> Interval {
+        let lower = self.lower_bound.lower;
+        let upper = self.upper_bound.upper;
+        Interval::new(
+            interval.lower.clamp(lower, upper),
+            interval.upper.clamp(lower, upper),
+        )
+    }
+}
+
+impl Gene for IntervalGene {
+    type Allele = Interval;
+
+    fn allele(&self) -> &Interval {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> IntervalGene {
+        IntervalGene {
+            allele: Self::random(self.min, self.max),
+            ..*self
+        }
+    }
+
+    fn with_allele(&self, allele: &Interval) -> IntervalGene {
+        IntervalGene {
+            allele: *allele,
+            ..*self
+        }
+    }
+}
+
+impl Valid for IntervalGene {
+    fn is_valid(&self) -> bool {
+        self.allele.lower <= self.allele.upper
+            && self.allele.lower >= self.lower_bound.lower
+            && self.allele.upper <= self.upper_bound.upper
+    }
+}
+
+impl BoundGene for IntervalGene {
+    fn upper_bound(&self) -> &Interval {
+        &self.upper_bound
+    }
+
+    fn lower_bound(&self) -> &Interval {
+        &self.lower_bound
+    }
+
+    fn with_bounds(self, upper_bound: Interval, lower_bound: Interval) -> IntervalGene {
+        IntervalGene {
+            upper_bound,
+            lower_bound,
+            ..self
+        }
+    }
+}
+
+impl NumericGene for IntervalGene {
+    fn min(&self) -> &Interval {
+        &self.min
+    }
+
+    fn max(&self) -> &Interval {
+        &self.max
+    }
+
+    fn mean(&self, other: &IntervalGene) -> IntervalGene {
+        self.with_allele(&Interval::new(
+            (self.allele.lower + other.allele.lower) / 2.0,
+            (self.allele.upper + other.allele.upper) / 2.0,
+        ))
+    }
+}
+
+macro_rules! interval_gene_op_impl {
+    ($trait:ident, $method:ident) => {
+        impl $trait for IntervalGene {
+            type Output = IntervalGene;
+
+            #[inline]
+            fn $method(self, other: IntervalGene) -> IntervalGene {
+                let allele = self.clamp(self.allele.$method(other.allele));
+                IntervalGene { allele, ..self }
+            }
+        }
+    };
+}
+
+interval_gene_op_impl!(Add, add);
+interval_gene_op_impl!(Sub, sub);
+interval_gene_op_impl!(Mul, mul);
+interval_gene_op_impl!(Div, div);
+
+impl std::fmt::Debug for IntervalGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.allele.lower, self.allele.upper)
+    }
+}
+
+/// Represents a chromosome composed of `IntervalGene`s.
+#[derive(Clone, PartialEq, Default)]
+pub struct IntervalChromosome {
+    pub genes: Vec<IntervalGene>,
+}
+
+impl IntervalChromosome {
+    pub fn new(genes: Vec<IntervalGene>) -> Self {
+        IntervalChromosome { genes }
+    }
+}
+
+impl Chromosome for IntervalChromosome {
+    type Gene = IntervalGene;
+}
+
+impl Valid for IntervalChromosome {
+    fn is_valid(&self) -> bool {
+        self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl AsRef<[IntervalGene]> for IntervalChromosome {
+    fn as_ref(&self) -> &[IntervalGene] {
+        &self.genes
+    }
+}
+
+impl AsMut<[IntervalGene]> for IntervalChromosome {
+    fn as_mut(&mut self) -> &mut [IntervalGene] {
+        &mut self.genes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_arithmetic() {
+        let a = Interval::new(1.0, 2.0);
+        let b = Interval::new(-1.0, 3.0);
+
+        assert_eq!(a + b, Interval::new(0.0, 5.0));
+        assert_eq!(a - b, Interval::new(-2.0, 3.0));
+        assert_eq!(a * b, Interval::new(-2.0, 6.0));
+        assert_eq!(a / b, a);
+        assert_eq!(b / Interval::new(2.0, 4.0), Interval::new(-0.5, 1.5));
+    }
+
+    #[test]
+    fn test_operations_keep_interval_valid() {
+        let gene = IntervalGene::new(-5.0, 5.0);
+        for _ in 0..100 {
+            let other = gene.new_instance();
+            assert!(other.is_valid());
+            assert!((gene.clone() + other.clone()).is_valid());
+            assert!((gene.clone() - other.clone()).is_valid());
+            assert!((gene.clone() * other.clone()).is_valid());
+            assert!((gene.clone() / other.clone()).is_valid());
+            assert!(gene.mean(&other).is_valid());
+        }
+    }
+}
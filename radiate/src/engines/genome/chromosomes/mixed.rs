@@ -0,0 +1,294 @@
+use super::{BitGene, CharGene, Chromosome, FloatGene, Gene, IntGene, Valid};
+use std::fmt::Debug;
+
+/// The kind of a `MixedGene`. The kinds of the genes of a `MixedChromosome`, in order, are its
+/// structure (see `MixedChromosome::structure`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GeneKind {
+    Float,
+    Int,
+    Bit,
+    Char,
+}
+
+/// The allele of a `MixedGene` - one of the alleles of the built-in genes it can hold.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MixedAllele {
+    Float(f32),
+    Int(i32),
+    Bit(bool),
+    Char(char),
+}
+
+impl MixedAllele {
+    pub fn kind(&self) -> GeneKind {
+        match self {
+            MixedAllele::Float(_) => GeneKind::Float,
+            MixedAllele::Int(_) => GeneKind::Int,
+            MixedAllele::Bit(_) => GeneKind::Bit,
+            MixedAllele::Char(_) => GeneKind::Char,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            MixedAllele::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            MixedAllele::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MixedAllele::Bit(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            MixedAllele::Char(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// A `Gene` that holds any one of the built-in `FloatGene`, `IntGene<i32>`, `BitGene` or `CharGene`. Along
+/// with the `MixedChromosome` it lets the individuals of one population have different structures - a
+/// different number of chromosomes, chromosomes of different lengths, and different kinds of genes at
+/// the same position - while the engine still works with a single chromosome type.
+///
+/// A gene only takes an allele of its own kind: `with_allele` with an allele of another kind returns the
+/// gene unchanged, so generic alterers can never turn a gene into another kind. Mutators that use
+/// `new_instance` (e.g. the `UniformMutator`) work on every kind, and the `HomologousCrossover` only
+/// crosses chromosomes of the same structure.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let gene = MixedGene::from(FloatGene::new(0.0, 1.0));
+/// assert_eq!(gene.kind(), GeneKind::Float);
+///
+/// // An allele of another kind is ignored.
+/// assert_eq!(gene.with_allele(&MixedAllele::Bit(true)), gene);
+/// assert_eq!(gene.with_allele(&MixedAllele::Float(0.5)).allele().as_f32(), Some(0.5));
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct MixedGene {
+    inner: MixedInner,
+    allele: MixedAllele,
+}
+
+#[derive(Clone, PartialEq)]
+enum MixedInner {
+    Float(FloatGene),
+    Int(IntGene<i32>),
+    Bit(BitGene),
+    Char(CharGene),
+}
+
+impl MixedGene {
+    fn from_inner(inner: MixedInner) -> Self {
+        let allele = match &inner {
+            MixedInner::Float(gene) => MixedAllele::Float(*gene.allele()),
+            MixedInner::Int(gene) => MixedAllele::Int(*gene.allele()),
+            MixedInner::Bit(gene) => MixedAllele::Bit(*gene.allele()),
+            MixedInner::Char(gene) => MixedAllele::Char(*gene.allele()),
+        };
+
+        MixedGene { inner, allele }
+    }
+
+    pub fn kind(&self) -> GeneKind {
+        self.allele.kind()
+    }
+
+    pub fn as_float(&self) -> Option<&FloatGene> {
+        match &self.inner {
+            MixedInner::Float(gene) => Some(gene),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<&IntGene<i32>> {
+        match &self.inner {
+            MixedInner::Int(gene) => Some(gene),
+            _ => None,
+        }
+    }
+
+    pub fn as_bit(&self) -> Option<&BitGene> {
+        match &self.inner {
+            MixedInner::Bit(gene) => Some(gene),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> Option<&CharGene> {
+        match &self.inner {
+            MixedInner::Char(gene) => Some(gene),
+            _ => None,
+        }
+    }
+}
+
+impl Gene for MixedGene {
+    type Allele = MixedAllele;
+
+    fn allele(&self) -> &MixedAllele {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> MixedGene {
+        MixedGene::from_inner(match &self.inner {
+            MixedInner::Float(gene) => MixedInner::Float(gene.new_instance()),
+            MixedInner::Int(gene) => MixedInner::Int(gene.new_instance()),
+            MixedInner::Bit(gene) => MixedInner::Bit(gene.new_instance()),
+            MixedInner::Char(gene) => MixedInner::Char(gene.new_instance()),
+        })
+    }
+
+    fn with_allele(&self, allele: &MixedAllele) -> MixedGene {
+        let inner = match (&self.inner, allele) {
+            (MixedInner::Float(gene), MixedAllele::Float(value)) => {
+                MixedInner::Float(gene.with_allele(value))
+            }
+            (MixedInner::Int(gene), MixedAllele::Int(value)) => {
+                MixedInner::Int(gene.with_allele(value))
+            }
+            (MixedInner::Bit(gene), MixedAllele::Bit(value)) => {
+                MixedInner::Bit(gene.with_allele(value))
+            }
+            (MixedInner::Char(gene), MixedAllele::Char(value)) => {
+                MixedInner::Char(gene.with_allele(value))
+            }
+            _ => return self.clone(),
+        };
+
+        MixedGene::from_inner(inner)
+    }
+}
+
+impl Valid for MixedGene {
+    fn is_valid(&self) -> bool {
+        match &self.inner {
+            MixedInner::Float(gene) => gene.is_valid(),
+            MixedInner::Int(gene) => gene.is_valid(),
+            MixedInner::Bit(gene) => gene.is_valid(),
+            MixedInner::Char(gene) => gene.is_valid(),
+        }
+    }
+}
+
+impl Debug for MixedGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.inner {
+            MixedInner::Float(gene) => write!(f, "{:?}", gene),
+            MixedInner::Int(gene) => write!(f, "{:?}", gene),
+            MixedInner::Bit(gene) => write!(f, "{:?}", gene),
+            MixedInner::Char(gene) => write!(f, "{:?}", gene),
+        }
+    }
+}
+
+impl From<FloatGene> for MixedGene {
+    fn from(gene: FloatGene) -> Self {
+        MixedGene::from_inner(MixedInner::Float(gene))
+    }
+}
+
+impl From<IntGene<i32>> for MixedGene {
+    fn from(gene: IntGene<i32>) -> Self {
+        MixedGene::from_inner(MixedInner::Int(gene))
+    }
+}
+
+impl From<BitGene> for MixedGene {
+    fn from(gene: BitGene) -> Self {
+        MixedGene::from_inner(MixedInner::Bit(gene))
+    }
+}
+
+impl From<CharGene> for MixedGene {
+    fn from(gene: CharGene) -> Self {
+        MixedGene::from_inner(MixedInner::Char(gene))
+    }
+}
+
+/// A chromosome of `MixedGene`s. The individuals of a population of `MixedChromosome`s can differ in
+/// structure - e.g. in a variable architecture search every chromosome can describe one layer of a
+/// network, and networks can have a different number of layers of different kinds. Create the
+/// individuals with an `FnCodex` and change their structure with the `StructureMutator`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let layer = MixedChromosome::new(vec![
+///     IntGene::from_min_max(1, 64).into(),
+///     FloatGene::new(0.0, 0.5).into(),
+///     BitGene::new().into(),
+/// ]);
+///
+/// assert_eq!(layer.structure(), vec![GeneKind::Int, GeneKind::Float, GeneKind::Bit]);
+/// ```
+#[derive(Clone, PartialEq, Default)]
+pub struct MixedChromosome {
+    pub genes: Vec<MixedGene>,
+}
+
+impl MixedChromosome {
+    pub fn new(genes: Vec<MixedGene>) -> Self {
+        MixedChromosome { genes }
+    }
+
+    /// The kinds of the genes, in order.
+    pub fn structure(&self) -> Vec<GeneKind> {
+        self.genes.iter().map(|gene| gene.kind()).collect()
+    }
+
+    /// Whether the chromosome has the same structure as `other`.
+    pub fn is_homologous(&self, other: &MixedChromosome) -> bool {
+        self.genes.len() == other.genes.len()
+            && self
+                .genes
+                .iter()
+                .zip(other.genes.iter())
+                .all(|(one, two)| one.kind() == two.kind())
+    }
+}
+
+impl Chromosome for MixedChromosome {
+    type Gene = MixedGene;
+}
+
+impl Valid for MixedChromosome {
+    fn is_valid(&self) -> bool {
+        self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl AsRef<[MixedGene]> for MixedChromosome {
+    fn as_ref(&self) -> &[MixedGene] {
+        &self.genes
+    }
+}
+
+impl AsMut<[MixedGene]> for MixedChromosome {
+    fn as_mut(&mut self) -> &mut [MixedGene] {
+        &mut self.genes
+    }
+}
+
+impl Debug for MixedChromosome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.genes.iter()).finish()
+    }
+}
@@ -1,31 +1,68 @@
 pub mod bit;
-pub mod chromosome;
-
-pub use bit::{BitChromosome, BitGene};
-pub use chromosome::*;
 pub mod char;
+pub mod chromosome;
 pub mod float;
 pub mod gene;
 pub mod int;
-pub mod permutation;
+pub mod view;
 
-use rand::{
-    distributions::{uniform::SampleUniform, Standard},
-    prelude::Distribution,
-};
-use std::{
+use core::{
     fmt::Debug,
     fmt::Display,
     ops::{Add, Div, Mul, Sub},
 };
+use rand::{
+    distributions::{uniform::SampleUniform, Standard},
+    prelude::Distribution,
+};
 
 use crate::{add_impl, arithmetic_impl, div_impl, impl_integer, mul_impl, sub_impl};
 
+pub use bit::{BitChromosome, BitGene};
 pub use char::{CharChromosome, CharGene};
+pub use chromosome::*;
 pub use float::{FloatChromosome, FloatGene};
-pub use gene::{BoundGene, Gene, NumericGene, Valid};
+pub use gene::{BoundGene, BoundaryPolicy, Gene, NumericGene, Valid};
 pub use int::{IntChromosome, IntGene};
+pub use view::{ChromosomeView, Layout};
+
+#[cfg(feature = "std")]
+pub mod bytes;
+#[cfg(feature = "std")]
+pub mod complex;
+#[cfg(feature = "std")]
+pub mod interval;
+#[cfg(feature = "std")]
+pub mod mixed;
+#[cfg(feature = "std")]
+pub mod permutation;
+#[cfg(feature = "std")]
+pub mod quantized;
+#[cfg(feature = "std")]
+pub mod sequence;
+#[cfg(feature = "std")]
+pub mod strategy;
+#[cfg(feature = "std")]
+pub mod time;
+
+#[cfg(feature = "std")]
+pub use bytes::{ByteGene, BytesChromosome};
+#[cfg(feature = "std")]
+pub use complex::{Complex, ComplexChromosome, ComplexGene};
+#[cfg(feature = "std")]
+pub use interval::{Interval, IntervalChromosome, IntervalGene};
+#[cfg(feature = "std")]
+pub use mixed::{GeneKind, MixedAllele, MixedChromosome, MixedGene};
+#[cfg(feature = "std")]
 pub use permutation::{PermutationChromosome, PermutationGene};
+#[cfg(feature = "std")]
+pub use quantized::{QuantizedChromosome, QuantizedGene};
+#[cfg(feature = "std")]
+pub use sequence::{alignment, levenshtein, SequenceChromosome};
+#[cfg(feature = "std")]
+pub use strategy::{StrategyChromosome, StrategyGene};
+#[cfg(feature = "std")]
+pub use time::{TimeChromosome, TimeFormat, TimeGene};
 
 pub trait Integer<T>:
     Copy
@@ -46,6 +83,10 @@ where
     const MAX: T;
 
     fn from_i32(value: i32) -> T;
+
+    fn from_f32(value: f32) -> T;
+
+    fn to_f32(self) -> f32;
 }
 
 // Implement Integer for i8, i16, i32, i64, i128, u8, u16, u32, u64, and u128
@@ -0,0 +1,312 @@
+use super::{
+    gene::{BoundGene, Gene, NumericGene, Valid},
+    Chromosome, Integer,
+};
+use crate::random_provider;
+use rand::distributions::{Distribution, Standard};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A `Gene` that represents a fixed-point (quantized) number. The `allele` is the integer level
+/// (e.g. an `i8` or `i16`) and the real value it represents is `allele * scale`. Because the allele is
+/// always an integer level, every mutation or crossover produces a value that lands exactly on the
+/// quantization grid, so evolved solutions can be deployed to fixed-point hardware as-is.
+///
+/// Like the `IntGene`, the `min` and `max` are the levels new alleles are generated between, and the
+/// `upper_bound` and `lower_bound` are the levels the gene is clamped to during arithmetic.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // An i8 gene representing values between -1.0 and 1.0 in steps of 1/127.
+/// let gene = QuantizedGene::<i8>::from_range(-1.0, 1.0, 1.0 / 127.0);
+/// assert!(gene.value() >= -1.0 && gene.value() <= 1.0);
+///
+/// let gene = gene.with_value(0.5);
+/// assert_eq!(*gene.allele(), 64);
+/// ```
+///
+/// # Type Parameters
+/// - `T`: The integer type used for the quantization levels.
+#[derive(Clone, PartialEq)]
+pub struct QuantizedGene<T: Integer<T>>
+where
+    Standard: Distribution<T>,
+{
+    pub allele: T,
+    pub scale: f32,
+    pub min: T,
+    pub max: T,
+    pub upper_bound: T,
+    pub lower_bound: T,
+}
+
+impl<T: Integer<T>> QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    /// Create a new `QuantizedGene` with a random level between `min` and `max` (inclusive).
+    pub fn new(min: T, max: T, scale: f32) -> Self {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+
+        QuantizedGene {
+            allele: random_level(min, max),
+            scale,
+            min,
+            max,
+            upper_bound: max,
+            lower_bound: min,
+        }
+    }
+
+    /// Create a new `QuantizedGene` with a random value between the real values `min` and `max`
+    /// quantized with the given `scale` (step size).
+    pub fn from_range(min: f32, max: f32, scale: f32) -> Self {
+        if scale <= 0.0 {
+            panic!("scale must be greater than 0");
+        }
+
+        QuantizedGene::new(
+            T::from_f32((min / scale).round()),
+            T::from_f32((max / scale).round()),
+            scale,
+        )
+    }
+
+    /// The real value this gene represents - `allele * scale`.
+    pub fn value(&self) -> f32 {
+        self.allele.to_f32() * self.scale
+    }
+
+    /// Create a new gene from the given real value, rounded to the nearest level and clamped to the bounds.
+    pub fn with_value(&self, value: f32) -> Self {
+        self.with_allele(&self.quantize(value))
+    }
+
+    fn quantize(&self, value: f32) -> T {
+        let level = (value / self.scale).round();
+        let level = level.clamp(self.lower_bound.to_f32(), self.upper_bound.to_f32());
+        T::from_f32(level)
+    }
+}
+
+impl<T: Integer<T>> Gene for QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    type Allele = T;
+
+    fn allele(&self) -> &T {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> QuantizedGene<T> {
+        QuantizedGene {
+            allele: random_level(self.min, self.max),
+            ..*self
+        }
+    }
+
+    fn with_allele(&self, allele: &T) -> QuantizedGene<T> {
+        QuantizedGene {
+            allele: *allele,
+            ..*self
+        }
+    }
+}
+
+impl<T: Integer<T>> Valid for QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    fn is_valid(&self) -> bool {
+        self.allele >= self.lower_bound && self.allele <= self.upper_bound
+    }
+}
+
+impl<T: Integer<T>> BoundGene for QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    fn upper_bound(&self) -> &T {
+        &self.upper_bound
+    }
+
+    fn lower_bound(&self) -> &T {
+        &self.lower_bound
+    }
+
+    fn with_bounds(self, upper_bound: T, lower_bound: T) -> QuantizedGene<T> {
+        QuantizedGene {
+            upper_bound,
+            lower_bound,
+            ..self
+        }
+    }
+}
+
+impl<T: Integer<T>> NumericGene for QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    fn min(&self) -> &T {
+        &self.min
+    }
+
+    fn max(&self) -> &T {
+        &self.max
+    }
+
+    fn mean(&self, other: &QuantizedGene<T>) -> QuantizedGene<T> {
+        self.with_value((self.value() + other.value()) / 2.0)
+    }
+}
+
+/// A random level between `min` and `max` (inclusive).
+fn random_level<T: Integer<T>>(min: T, max: T) -> T
+where
+    Standard: Distribution<T>,
+{
+    let level = random_provider::gen_range::<f32>(min.to_f32()..max.to_f32() + 1.0).floor();
+    T::from_f32(level.min(max.to_f32()))
+}
+
+/// Arithmetic on `QuantizedGene`s is done on the real values and the result is re-quantized
+/// and clamped to the bounds, so the operations never overflow the underlying integer type.
+macro_rules! quantized_op_impl {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl<T: Integer<T>> $trait for QuantizedGene<T>
+        where
+            Standard: Distribution<T>,
+        {
+            type Output = QuantizedGene<T>;
+
+            #[inline]
+            fn $method(self, other: QuantizedGene<T>) -> QuantizedGene<T> {
+                self.with_value(self.value() $op other.value())
+            }
+        }
+    };
+}
+
+quantized_op_impl!(Add, add, +);
+quantized_op_impl!(Sub, sub, -);
+quantized_op_impl!(Mul, mul, *);
+
+impl<T: Integer<T>> Div for QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    type Output = QuantizedGene<T>;
+
+    #[inline]
+    fn div(self, other: QuantizedGene<T>) -> QuantizedGene<T> {
+        let denominator = other.value();
+        if denominator == 0.0 {
+            return self;
+        }
+
+        self.with_value(self.value() / denominator)
+    }
+}
+
+impl<T: Integer<T>> std::fmt::Debug for QuantizedGene<T>
+where
+    Standard: Distribution<T>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+/// Represents a chromosome composed of `QuantizedGene`s.
+#[derive(Clone, PartialEq, Default)]
+pub struct QuantizedChromosome<T: Integer<T>>
+where
+    Standard: Distribution<T>,
+{
+    pub genes: Vec<QuantizedGene<T>>,
+}
+
+impl<T: Integer<T>> QuantizedChromosome<T>
+where
+    Standard: Distribution<T>,
+{
+    pub fn new(genes: Vec<QuantizedGene<T>>) -> Self {
+        QuantizedChromosome { genes }
+    }
+
+    /// The real values of the genes in this chromosome.
+    pub fn values(&self) -> Vec<f32> {
+        self.genes.iter().map(|gene| gene.value()).collect()
+    }
+}
+
+impl<T: Integer<T>> Chromosome for QuantizedChromosome<T>
+where
+    Standard: Distribution<T>,
+{
+    type Gene = QuantizedGene<T>;
+}
+
+impl<T: Integer<T>> Valid for QuantizedChromosome<T>
+where
+    Standard: Distribution<T>,
+{
+    fn is_valid(&self) -> bool {
+        self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl<T: Integer<T>> AsRef<[QuantizedGene<T>]> for QuantizedChromosome<T>
+where
+    Standard: Distribution<T>,
+{
+    fn as_ref(&self) -> &[QuantizedGene<T>] {
+        &self.genes
+    }
+}
+
+impl<T: Integer<T>> AsMut<[QuantizedGene<T>]> for QuantizedChromosome<T>
+where
+    Standard: Distribution<T>,
+{
+    fn as_mut(&mut self) -> &mut [QuantizedGene<T>] {
+        &mut self.genes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_instance_is_on_grid() {
+        let gene = QuantizedGene::<i8>::from_range(-1.0, 1.0, 0.25);
+        for _ in 0..100 {
+            let new_gene = gene.new_instance();
+            assert!(new_gene.is_valid());
+            assert_eq!(new_gene.value() % 0.25, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_clamps_to_bounds() {
+        let gene = QuantizedGene::<i8>::new(-100, 100, 0.5).with_allele(&100);
+        let result = gene.clone() + gene.clone();
+        assert_eq!(result.allele, 100);
+
+        let result = gene.clone() * gene.clone();
+        assert_eq!(result.allele, 100);
+
+        let zero = gene.with_allele(&0);
+        assert_eq!((gene.clone() / zero).allele, 100);
+    }
+
+    #[test]
+    fn test_mean() {
+        let one = QuantizedGene::<i16>::new(-10, 10, 0.1).with_allele(&2);
+        let two = one.with_allele(&6);
+        assert_eq!(one.mean(&two).allele, 4);
+    }
+}
@@ -0,0 +1,211 @@
+use super::{gene::Valid, Chromosome, Gene};
+use crate::random_provider;
+
+/// A variable-length `Chromosome` for evolving sequences - DNA-like strings, regexes, token strings, etc.
+/// Unlike the fixed-length chromosomes, the number of genes can grow and shrink between `min_len` and
+/// `max_len` through the `InsertionMutator`, `DeletionMutator` and `AlignmentCrossover`. Substitution
+/// is just the regular `UniformMutator`.
+///
+/// New genes are created from the `template` gene with `Gene::new_instance`, so the template
+/// decides the alphabet the sequence is built from.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let chromosome = SequenceChromosome::new(CharGene::new(), 3, 10);
+/// assert!(chromosome.len() >= 3 && chromosome.len() <= 10);
+///
+/// let kitten = SequenceChromosome::from_genes(CharGene::new(), "kitten".chars().map(CharGene::from).collect());
+/// let sitting = SequenceChromosome::from_genes(CharGene::new(), "sitting".chars().map(CharGene::from).collect());
+/// assert_eq!(kitten.distance(&sitting), 3);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct SequenceChromosome<G: Gene> {
+    pub genes: Vec<G>,
+    pub template: G,
+    pub min_len: usize,
+    pub max_len: usize,
+}
+
+impl<G: Gene> SequenceChromosome<G> {
+    /// Create a new `SequenceChromosome` of random length between `min_len` and `max_len` (inclusive)
+    /// filled with new instances of the `template` gene.
+    pub fn new(template: G, min_len: usize, max_len: usize) -> Self {
+        if min_len > max_len {
+            panic!("min_len must be less than or equal to max_len");
+        }
+
+        let len = random_provider::gen_range(min_len..max_len + 1);
+        let genes = (0..len).map(|_| template.new_instance()).collect();
+
+        SequenceChromosome {
+            genes,
+            template,
+            min_len,
+            max_len,
+        }
+    }
+
+    /// Create a new `SequenceChromosome` from the given genes. The length limits are set to
+    /// `0` and `usize::MAX` and can be narrowed with `with_len_bounds`.
+    pub fn from_genes(template: G, genes: Vec<G>) -> Self {
+        SequenceChromosome {
+            genes,
+            template,
+            min_len: 0,
+            max_len: usize::MAX,
+        }
+    }
+
+    pub fn with_len_bounds(mut self, min_len: usize, max_len: usize) -> Self {
+        self.min_len = min_len;
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn can_grow(&self) -> bool {
+        self.genes.len() < self.max_len
+    }
+
+    pub fn can_shrink(&self) -> bool {
+        self.genes.len() > self.min_len
+    }
+
+    /// Insert a new instance of the template gene at `index`.
+    pub fn insert(&mut self, index: usize) {
+        self.genes.insert(index, self.template.new_instance());
+    }
+
+    pub fn remove(&mut self, index: usize) -> G {
+        self.genes.remove(index)
+    }
+
+    /// The Levenshtein (edit) distance between the genes of this chromosome and another.
+    pub fn distance(&self, other: &SequenceChromosome<G>) -> usize {
+        levenshtein(&self.genes, &other.genes)
+    }
+}
+
+impl<G: Gene> Chromosome for SequenceChromosome<G> {
+    type Gene = G;
+}
+
+/// A `SequenceChromosome` is valid if its length is within the limits and all of its genes are valid.
+impl<G: Gene> Valid for SequenceChromosome<G> {
+    fn is_valid(&self) -> bool {
+        self.genes.len() >= self.min_len
+            && self.genes.len() <= self.max_len
+            && self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl<G: Gene> AsRef<[G]> for SequenceChromosome<G> {
+    fn as_ref(&self) -> &[G] {
+        &self.genes
+    }
+}
+
+impl<G: Gene> AsMut<[G]> for SequenceChromosome<G> {
+    fn as_mut(&mut self) -> &mut [G] {
+        &mut self.genes
+    }
+}
+
+/// The Levenshtein distance between two sequences - the minimum number of single element insertions,
+/// deletions and substitutions needed to turn `one` into `two`.
+pub fn levenshtein<T: PartialEq>(one: &[T], two: &[T]) -> usize {
+    let mut previous = (0..=two.len()).collect::<Vec<usize>>();
+    let mut current = vec![0; two.len() + 1];
+
+    for (i, a) in one.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in two.iter().enumerate() {
+            let substitution = previous[j] + if a == b { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[two.len()]
+}
+
+/// The index pairs `(i, j)` where `one[i] == two[j]` in an optimal (minimum edit distance) alignment
+/// of the two sequences, in order.
+pub fn alignment<T: PartialEq>(one: &[T], two: &[T]) -> Vec<(usize, usize)> {
+    let rows = one.len() + 1;
+    let cols = two.len() + 1;
+    let mut table = vec![0; rows * cols];
+
+    for i in 0..rows {
+        table[i * cols] = i;
+    }
+
+    for (j, cell) in table.iter_mut().enumerate().take(cols) {
+        *cell = j;
+    }
+
+    for i in 1..rows {
+        for j in 1..cols {
+            let cost = if one[i - 1] == two[j - 1] { 0 } else { 1 };
+            table[i * cols + j] = (table[(i - 1) * cols + j - 1] + cost)
+                .min(table[(i - 1) * cols + j] + 1)
+                .min(table[i * cols + j - 1] + 1);
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (one.len(), two.len());
+    while i > 0 && j > 0 {
+        let current = table[i * cols + j];
+        if one[i - 1] == two[j - 1] && current == table[(i - 1) * cols + j - 1] {
+            matches.push((i - 1, j - 1));
+            i -= 1;
+            j -= 1;
+        } else if current == table[(i - 1) * cols + j - 1] + 1 {
+            i -= 1;
+            j -= 1;
+        } else if current == table[(i - 1) * cols + j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    matches.reverse();
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CharGene;
+
+    fn chars(value: &str) -> Vec<char> {
+        value.chars().collect()
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("flaw"), &chars("lawn")), 2);
+        assert_eq!(levenshtein(&chars("same"), &chars("same")), 0);
+    }
+
+    #[test]
+    fn test_alignment() {
+        let matches = alignment(&chars("ACGT"), &chars("AGT"));
+        assert_eq!(matches, vec![(0, 0), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn test_new_respects_len_bounds() {
+        for _ in 0..100 {
+            let chromosome = SequenceChromosome::new(CharGene::new(), 2, 5);
+            assert!(chromosome.len() >= 2 && chromosome.len() <= 5);
+            assert!(chromosome.is_valid());
+        }
+    }
+}
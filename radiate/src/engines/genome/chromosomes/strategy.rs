@@ -0,0 +1,204 @@
+use super::{
+    gene::{BoundGene, BoundaryPolicy, Gene, NumericGene, Valid},
+    Chromosome,
+};
+use crate::random_provider;
+use std::fmt::Debug;
+
+/// A `Gene` for evolution strategies: a floating point `allele` that carries its own mutation step
+/// size, `sigma`. The step size is a strategy parameter - it isn't part of the decoded value, but it is
+/// inherited and mutated along with the allele by the `SelfAdaptiveMutator`, so the step sizes that
+/// produce good offspring spread through the population and every gene adapts its own.
+///
+/// Apart from the `sigma`, the gene behaves like a `FloatGene`: the allele is generated between `min`
+/// and `max`, and an allele pushed outside of the bounds is brought back by the `BoundaryPolicy`.
+/// Crossovers exchange whole genes (or, for numeric crossovers, set the allele of a gene), so a gene
+/// keeps the step size of the parent it came from.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let gene = StrategyGene::new(0.0, 1.0).with_sigma(0.1);
+///
+/// assert_eq!(gene.sigma, 0.1);
+/// assert!(*gene.allele() >= 0.0 && *gene.allele() < 1.0);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct StrategyGene {
+    pub allele: f32,
+    pub sigma: f32,
+    pub min: f32,
+    pub max: f32,
+    pub upper_bound: f32,
+    pub lower_bound: f32,
+    pub boundary: BoundaryPolicy,
+}
+
+impl StrategyGene {
+    /// Create a new `StrategyGene` with a random allele between `min` and `max`. The bounds are set to
+    /// `min` and `max`, and the `sigma` to a tenth of the range.
+    pub fn new(min: f32, max: f32) -> Self {
+        StrategyGene {
+            allele: random_provider::random::<f32>() * (max - min) + min,
+            sigma: (max - min) * 0.1,
+            min,
+            max,
+            upper_bound: max,
+            lower_bound: min,
+            boundary: BoundaryPolicy::default(),
+        }
+    }
+
+    /// Set the mutation step size of the gene. Panics if `sigma` isn't greater than 0.
+    pub fn with_sigma(self, sigma: f32) -> Self {
+        if sigma.is_nan() || sigma <= 0.0 {
+            panic!("sigma must be greater than 0");
+        }
+
+        StrategyGene { sigma, ..self }
+    }
+
+    /// Set the `BoundaryPolicy` used to bring alleles back within the gene's bounds.
+    pub fn with_boundary(self, boundary: BoundaryPolicy) -> Self {
+        StrategyGene { boundary, ..self }
+    }
+}
+
+impl Valid for StrategyGene {
+    fn is_valid(&self) -> bool {
+        self.allele >= self.lower_bound && self.allele <= self.upper_bound && self.sigma > 0.0
+    }
+}
+
+/// A new instance gets a new random allele and keeps the `sigma` of the gene it was created from.
+impl Gene for StrategyGene {
+    type Allele = f32;
+
+    fn allele(&self) -> &f32 {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> StrategyGene {
+        StrategyGene {
+            allele: random_provider::random::<f32>() * (self.max - self.min) + self.min,
+            ..*self
+        }
+    }
+
+    fn with_allele(&self, allele: &f32) -> StrategyGene {
+        StrategyGene {
+            allele: *allele,
+            ..*self
+        }
+    }
+}
+
+impl BoundGene for StrategyGene {
+    fn upper_bound(&self) -> &f32 {
+        &self.upper_bound
+    }
+
+    fn lower_bound(&self) -> &f32 {
+        &self.lower_bound
+    }
+
+    fn with_bounds(self, lower_bound: f32, upper_bound: f32) -> StrategyGene {
+        StrategyGene {
+            upper_bound,
+            lower_bound,
+            ..self
+        }
+    }
+}
+
+impl NumericGene for StrategyGene {
+    fn min(&self) -> &f32 {
+        &self.min
+    }
+
+    fn max(&self) -> &f32 {
+        &self.max
+    }
+
+    /// The mean of both the alleles and the step sizes (intermediate recombination).
+    fn mean(&self, other: &StrategyGene) -> StrategyGene {
+        StrategyGene {
+            allele: (self.allele + other.allele) / 2_f32,
+            sigma: (self.sigma + other.sigma) / 2_f32,
+            ..*self
+        }
+    }
+
+    fn bounded(&self, allele: &f32) -> StrategyGene {
+        let allele = self.boundary.apply(
+            *allele as f64,
+            self.lower_bound as f64,
+            self.upper_bound as f64,
+        );
+        self.with_allele(&(allele as f32))
+    }
+}
+
+impl Debug for StrategyGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (sigma {})", self.allele, self.sigma)
+    }
+}
+
+/// Represents a chromosome composed of `StrategyGene`s - the object parameters of an evolution
+/// strategy together with one mutation step size per parameter.
+#[derive(Clone, PartialEq, Default)]
+pub struct StrategyChromosome {
+    pub genes: Vec<StrategyGene>,
+}
+
+impl StrategyChromosome {
+    pub fn new(genes: Vec<StrategyGene>) -> Self {
+        StrategyChromosome { genes }
+    }
+
+    /// The step sizes of the genes.
+    pub fn sigmas(&self) -> Vec<f32> {
+        self.genes.iter().map(|gene| gene.sigma).collect()
+    }
+}
+
+impl Chromosome for StrategyChromosome {
+    type Gene = StrategyGene;
+}
+
+impl Valid for StrategyChromosome {
+    fn is_valid(&self) -> bool {
+        self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl AsRef<[StrategyGene]> for StrategyChromosome {
+    fn as_ref(&self) -> &[StrategyGene] {
+        &self.genes
+    }
+}
+
+impl AsMut<[StrategyGene]> for StrategyChromosome {
+    fn as_mut(&mut self) -> &mut [StrategyGene] {
+        &mut self.genes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_gene_keeps_its_sigma() {
+        let gene = StrategyGene::new(0.0, 10.0).with_sigma(0.5);
+
+        assert_eq!(gene.with_allele(&3.0).sigma, 0.5);
+        assert_eq!(gene.new_instance().sigma, 0.5);
+        assert_eq!(gene.bounded(&12.0).allele, 10.0);
+
+        let other = StrategyGene::new(0.0, 10.0).with_sigma(1.5);
+        assert_eq!(gene.mean(&other).sigma, 1.0);
+    }
+}
@@ -0,0 +1,323 @@
+use super::{
+    gene::{BoundGene, Gene, NumericGene, Valid},
+    Chromosome,
+};
+use crate::random_provider::{self, RngHandle};
+use std::ops::{Add, Div, Mul, Sub};
+
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// How a `TimeGene` is displayed. The allele is the same (a number of minutes) either way.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TimeFormat {
+    /// A time of day, displayed as `HH:MM` (wrapping at midnight).
+    #[default]
+    Clock,
+    /// A duration, displayed as e.g. `1h 30m`.
+    Duration,
+}
+
+/// A `Gene` that represents a time of day or a duration for scheduling/timetabling problems. The `allele`
+/// is a whole number of minutes which is always snapped to the `granularity` (e.g. every 15 minutes)
+/// relative to `min`, so evolved schedules only ever contain valid time slots.
+///
+/// The `min` and `max` are the minutes new alleles are generated between, and the `upper_bound` and
+/// `lower_bound` are the minutes the gene is clamped to during arithmetic and jitter. Pair it with
+/// the `JitterMutator` to nudge times by a few minutes instead of re-sampling them.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // A meeting start time between 09:00 and 17:00 on a 15 minute grid.
+/// let gene = TimeGene::new(TimeGene::hm(9, 0), TimeGene::hm(17, 0), 15);
+/// assert_eq!(gene.allele() % 15, 0);
+///
+/// let gene = gene.with_minutes(TimeGene::hm(10, 7));
+/// assert_eq!(gene.to_string(), "10:00");
+///
+/// let gene = TimeGene::new(30, 180, 30).with_format(TimeFormat::Duration).with_minutes(90);
+/// assert_eq!(gene.to_string(), "1h 30m");
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct TimeGene {
+    pub allele: i32,
+    pub granularity: i32,
+    pub format: TimeFormat,
+    pub min: i32,
+    pub max: i32,
+    pub upper_bound: i32,
+    pub lower_bound: i32,
+}
+
+impl TimeGene {
+    /// Create a new `TimeGene` with a random time between `min` and `max` minutes (inclusive) snapped to
+    /// `granularity` minutes. The bounds are set to `min` and `max`.
+    pub fn new(min: i32, max: i32, granularity: i32) -> Self {
+        if granularity <= 0 {
+            panic!("granularity must be greater than 0");
+        }
+
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        let mut gene = TimeGene {
+            allele: min,
+            granularity,
+            format: TimeFormat::default(),
+            min,
+            max,
+            upper_bound: max,
+            lower_bound: min,
+        };
+
+        gene.allele = gene.random_minutes();
+        gene
+    }
+
+    /// Convert hours and minutes to a number of minutes, e.g. `TimeGene::hm(9, 30) == 570`.
+    pub fn hm(hours: i32, minutes: i32) -> i32 {
+        hours * 60 + minutes
+    }
+
+    pub fn with_format(mut self, format: TimeFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn hours(&self) -> i32 {
+        self.allele / 60
+    }
+
+    pub fn minutes(&self) -> i32 {
+        self.allele % 60
+    }
+
+    /// Create a new gene from the given minutes, snapped to the granularity and clamped to the bounds.
+    pub fn with_minutes(&self, minutes: i32) -> Self {
+        self.with_allele(&self.snap(minutes))
+    }
+
+    /// Move the time by a random offset of at most `max_jitter` minutes in either direction.
+    pub fn jitter(&self, max_jitter: i32) -> Self {
+        self.jitter_with(max_jitter, &RngHandle::current())
+    }
+
+    /// Like `jitter`, drawing the offset from `rng`.
+    pub fn jitter_with(&self, max_jitter: i32, rng: &RngHandle) -> Self {
+        let max_jitter = max_jitter.abs().max(self.granularity);
+        let offset = rng.gen_range(-max_jitter..max_jitter + 1);
+        self.with_minutes(self.allele + offset)
+    }
+
+    /// Round the minutes to the nearest multiple of the granularity (relative to `min`) that lies within the bounds.
+    pub fn snap(&self, minutes: i32) -> i32 {
+        let steps = ((minutes - self.min) as f32 / self.granularity as f32).round() as i32;
+        let lowest = self.min + self.steps_above_min(self.lower_bound, true) * self.granularity;
+        let highest = self.min + self.steps_above_min(self.upper_bound, false) * self.granularity;
+
+        (self.min + steps * self.granularity).clamp(lowest, highest.max(lowest))
+    }
+
+    fn steps_above_min(&self, minutes: i32, round_up: bool) -> i32 {
+        let steps = (minutes - self.min) as f32 / self.granularity as f32;
+        if round_up {
+            steps.ceil() as i32
+        } else {
+            steps.floor() as i32
+        }
+    }
+
+    fn random_minutes(&self) -> i32 {
+        let slots = (self.max - self.min) / self.granularity;
+        self.min + random_provider::gen_range(0..slots + 1) * self.granularity
+    }
+}
+
+impl Gene for TimeGene {
+    type Allele = i32;
+
+    fn allele(&self) -> &i32 {
+        &self.allele
+    }
+
+    fn new_instance(&self) -> TimeGene {
+        TimeGene {
+            allele: self.random_minutes(),
+            ..*self
+        }
+    }
+
+    fn with_allele(&self, allele: &i32) -> TimeGene {
+        TimeGene {
+            allele: *allele,
+            ..*self
+        }
+    }
+}
+
+/// A `TimeGene` is valid if the allele is within the bounds and on the granularity grid.
+impl Valid for TimeGene {
+    fn is_valid(&self) -> bool {
+        self.allele >= self.lower_bound
+            && self.allele <= self.upper_bound
+            && (self.allele - self.min) % self.granularity == 0
+    }
+}
+
+impl BoundGene for TimeGene {
+    fn upper_bound(&self) -> &i32 {
+        &self.upper_bound
+    }
+
+    fn lower_bound(&self) -> &i32 {
+        &self.lower_bound
+    }
+
+    fn with_bounds(self, upper_bound: i32, lower_bound: i32) -> TimeGene {
+        TimeGene {
+            upper_bound,
+            lower_bound,
+            ..self
+        }
+    }
+}
+
+impl NumericGene for TimeGene {
+    fn min(&self) -> &i32 {
+        &self.min
+    }
+
+    fn max(&self) -> &i32 {
+        &self.max
+    }
+
+    fn mean(&self, other: &TimeGene) -> TimeGene {
+        self.with_minutes((self.allele + other.allele) / 2)
+    }
+}
+
+/// Arithmetic on `TimeGene`s is done on the minutes and the result is snapped back onto the grid.
+macro_rules! time_gene_op_impl {
+    ($trait:ident, $method:ident, $saturating:ident) => {
+        impl $trait for TimeGene {
+            type Output = TimeGene;
+
+            #[inline]
+            fn $method(self, other: TimeGene) -> TimeGene {
+                self.with_minutes(self.allele.$saturating(other.allele))
+            }
+        }
+    };
+}
+
+time_gene_op_impl!(Add, add, saturating_add);
+time_gene_op_impl!(Sub, sub, saturating_sub);
+time_gene_op_impl!(Mul, mul, saturating_mul);
+
+impl Div for TimeGene {
+    type Output = TimeGene;
+
+    #[inline]
+    fn div(self, other: TimeGene) -> TimeGene {
+        if other.allele == 0 {
+            return self;
+        }
+
+        self.with_minutes(self.allele / other.allele)
+    }
+}
+
+impl std::fmt::Display for TimeGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.format {
+            TimeFormat::Clock => {
+                let minutes = self.allele.rem_euclid(MINUTES_PER_DAY);
+                write!(f, "{:02}:{:02}", minutes / 60, minutes % 60)
+            }
+            TimeFormat::Duration => {
+                let sign = if self.allele < 0 { "-" } else { "" };
+                let minutes = self.allele.abs();
+                match (minutes / 60, minutes % 60) {
+                    (0, m) => write!(f, "{}{}m", sign, m),
+                    (h, 0) => write!(f, "{}{}h", sign, h),
+                    (h, m) => write!(f, "{}{}h {}m", sign, h, m),
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for TimeGene {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Represents a chromosome composed of `TimeGene`s, e.g. the start times of the events in a schedule.
+#[derive(Clone, PartialEq, Default)]
+pub struct TimeChromosome {
+    pub genes: Vec<TimeGene>,
+}
+
+impl TimeChromosome {
+    pub fn new(genes: Vec<TimeGene>) -> Self {
+        TimeChromosome { genes }
+    }
+}
+
+impl Chromosome for TimeChromosome {
+    type Gene = TimeGene;
+}
+
+impl Valid for TimeChromosome {
+    fn is_valid(&self) -> bool {
+        self.genes.iter().all(|gene| gene.is_valid())
+    }
+}
+
+impl AsRef<[TimeGene]> for TimeChromosome {
+    fn as_ref(&self) -> &[TimeGene] {
+        &self.genes
+    }
+}
+
+impl AsMut<[TimeGene]> for TimeChromosome {
+    fn as_mut(&mut self) -> &mut [TimeGene] {
+        &mut self.genes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_instance_is_on_grid() {
+        let gene = TimeGene::new(TimeGene::hm(8, 0), TimeGene::hm(18, 0), 15);
+        for _ in 0..100 {
+            let new_gene = gene.new_instance();
+            assert!(new_gene.is_valid());
+            assert_eq!(new_gene.allele % 15, 0);
+        }
+    }
+
+    #[test]
+    fn test_jitter_stays_on_grid_and_in_bounds() {
+        let gene = TimeGene::new(0, 120, 10).with_minutes(0);
+        for _ in 0..100 {
+            let jittered = gene.jitter(25);
+            assert!(jittered.is_valid());
+            assert!(jittered.allele <= 30);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let gene = TimeGene::new(0, MINUTES_PER_DAY, 5).with_minutes(TimeGene::hm(7, 5));
+        assert_eq!(gene.to_string(), "07:05");
+
+        let gene = gene.with_format(TimeFormat::Duration);
+        assert_eq!(gene.to_string(), "7h 5m");
+        assert_eq!(gene.with_minutes(45).to_string(), "45m");
+        assert_eq!(gene.with_minutes(120).to_string(), "2h");
+    }
+}
@@ -0,0 +1,175 @@
+use super::gene::Gene;
+use alloc::{string::String, vec::Vec};
+
+/// A read-only view over a contiguous slice of genes. Views make it easy to write `Codex::decode`
+/// implementations without manual index math - a chromosome can be split into windows, chunked into
+/// the rows of a matrix, or split into named segments using a `Layout`.
+///
+/// A view is created from any chromosome with `Chromosome::view`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let chromosome = FloatChromosome::from(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0][..]);
+///
+/// // The first four genes are a 2x2 matrix, the last two are a bias vector.
+/// let view = chromosome.view();
+/// let weights = view.window(0, 4).matrix(2);
+/// let bias = view.window(4, 2).alleles();
+///
+/// assert_eq!(weights, vec![vec![0.0, 1.0], vec![2.0, 3.0]]);
+/// assert_eq!(bias, vec![4.0, 5.0]);
+/// ```
+#[derive(Debug)]
+pub struct ChromosomeView<'a, G: Gene> {
+    genes: &'a [G],
+}
+
+impl<G: Gene> Clone for ChromosomeView<'_, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: Gene> Copy for ChromosomeView<'_, G> {}
+
+impl<'a, G: Gene> ChromosomeView<'a, G> {
+    pub fn new(genes: &'a [G]) -> Self {
+        ChromosomeView { genes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    pub fn genes(&self) -> &'a [G] {
+        self.genes
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'a, G> {
+        self.genes.iter()
+    }
+
+    /// A view of `len` genes starting at `start`. Panics if the window is out of range.
+    pub fn window(&self, start: usize, len: usize) -> ChromosomeView<'a, G> {
+        if start + len > self.genes.len() {
+            panic!(
+                "window {}..{} out of range for view of length {}",
+                start,
+                start + len,
+                self.genes.len()
+            );
+        }
+
+        ChromosomeView::new(&self.genes[start..start + len])
+    }
+
+    /// Split the view into consecutive views of `size` genes. The last view may be shorter.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = ChromosomeView<'a, G>> {
+        self.genes.chunks(size).map(ChromosomeView::new)
+    }
+
+    /// Split the view into named segments using the given `Layout`.
+    pub fn segments<'l>(
+        &self,
+        layout: &'l Layout,
+    ) -> impl Iterator<Item = (&'l str, ChromosomeView<'a, G>)> + 'l
+    where
+        'a: 'l,
+    {
+        let view = *self;
+        layout
+            .segments
+            .iter()
+            .map(move |(name, start, len)| (name.as_str(), view.window(*start, *len)))
+    }
+
+    /// Get the segment with the given name from the `Layout`.
+    pub fn segment(&self, layout: &Layout, name: &str) -> Option<ChromosomeView<'a, G>> {
+        layout.get(name).map(|(start, len)| self.window(start, len))
+    }
+}
+
+impl<G> ChromosomeView<'_, G>
+where
+    G: Gene,
+    G::Allele: Clone,
+{
+    /// The alleles of the genes in this view.
+    pub fn alleles(&self) -> Vec<G::Allele> {
+        self.genes
+            .iter()
+            .map(|gene| gene.allele().clone())
+            .collect()
+    }
+
+    /// The alleles of this view as the rows of a matrix with `cols` columns. Panics if the
+    /// length of the view isn't a multiple of `cols`.
+    pub fn matrix(&self, cols: usize) -> Vec<Vec<G::Allele>> {
+        if cols == 0 || !self.genes.len().is_multiple_of(cols) {
+            panic!(
+                "view of length {} can't be split into rows of {} columns",
+                self.genes.len(),
+                cols
+            );
+        }
+
+        self.chunks(cols).map(|row| row.alleles()).collect()
+    }
+}
+
+/// A `Layout` describes how a chromosome is split into contiguous, named segments. Segments are
+/// laid out in the order they are added.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let layout = Layout::new().segment("weights", 4).segment("bias", 2);
+/// let chromosome = FloatChromosome::from(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0][..]);
+///
+/// let bias = chromosome.view().segment(&layout, "bias").unwrap();
+/// assert_eq!(bias.alleles(), vec![4.0, 5.0]);
+/// assert_eq!(layout.len(), 6);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Layout {
+    segments: Vec<(String, usize, usize)>,
+}
+
+impl Layout {
+    pub fn new() -> Self {
+        Layout {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Add a segment of `len` genes directly after the previous segment.
+    pub fn segment(mut self, name: impl Into<String>, len: usize) -> Self {
+        let start = self.len();
+        self.segments.push((name.into(), start, len));
+        self
+    }
+
+    /// Get the `(start, len)` of the segment with the given name.
+    pub fn get(&self, name: &str) -> Option<(usize, usize)> {
+        self.segments
+            .iter()
+            .find(|(segment, _, _)| segment == name)
+            .map(|(_, start, len)| (*start, *len))
+    }
+
+    /// The total number of genes covered by the layout.
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|(_, _, len)| len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
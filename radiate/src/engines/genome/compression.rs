@@ -0,0 +1,231 @@
+use super::{Chromosome, Genotype, MemoryFootprint};
+
+/// A genotype stored as the genes that differ from the archive's reference, or whole when its shape
+/// differs from the reference's.
+#[derive(Clone, PartialEq)]
+pub(crate) enum DeltaEntry<C: Chromosome> {
+    Delta(Vec<(usize, usize, C::Gene)>),
+    Full(Genotype<C>),
+}
+
+/// A compact archive of similar genotypes - e.g. a converged population, or the elites of every
+/// generation of a run - for checkpoints and archives of populations too large to keep whole. Every
+/// genotype is stored as the genes that differ from a reference genotype (by position), so a
+/// population that shares most of its genes takes a fraction of the memory. Genotypes whose shape
+/// differs from the reference (e.g. after an indel mutation) are stored whole.
+///
+/// Genotypes are decompressed transparently with `get` and `iter`. For storage, the `wire` feature gives
+/// the archive its own message in the `wire` format (`wire::encode_delta_archive`), which can also be
+/// compressed with zstd by enabling the `zstd` feature.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let codex = IntCodex::new(1, 100, 0, 10);
+/// let reference = codex.encode();
+///
+/// let mut archive = DeltaArchive::new(reference.clone());
+/// for i in 0..10 {
+///     let mut genotype = reference.clone();
+///     let gene = genotype[0].get_gene(i).with_allele(&11);
+///     genotype[0].set_gene(i, gene);
+///     archive.push(&genotype);
+/// }
+///
+/// assert_eq!(*archive.get(3)[0].get_gene(3).allele(), 11);
+/// assert_eq!(archive.stored_genes(), 10);
+/// assert!(archive.compression_ratio() > 5.0);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct DeltaArchive<C: Chromosome> {
+    reference: Genotype<C>,
+    entries: Vec<DeltaEntry<C>>,
+}
+
+impl<C: Chromosome> DeltaArchive<C> {
+    /// Create an empty archive storing genotypes against `reference`. The closer the reference is to
+    /// the genotypes, the better they compress - the best individual of a converged population is
+    /// usually a good choice.
+    pub fn new(reference: Genotype<C>) -> Self {
+        DeltaArchive {
+            reference,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Create an archive of the genotypes, using the first as the reference. Panics if there are none.
+    pub fn from_genotypes(genotypes: &[Genotype<C>]) -> Self {
+        let Some(reference) = genotypes.first() else {
+            panic!("A delta archive needs at least one genotype");
+        };
+
+        let mut archive = DeltaArchive::new(reference.clone());
+        genotypes.iter().for_each(|genotype| {
+            archive.push(genotype);
+        });
+        archive
+    }
+
+    #[cfg(feature = "wire")]
+    pub(crate) fn from_entries(reference: Genotype<C>, entries: Vec<DeltaEntry<C>>) -> Self {
+        DeltaArchive { reference, entries }
+    }
+
+    /// Add a genotype to the archive and return its index.
+    pub fn push(&mut self, genotype: &Genotype<C>) -> usize {
+        let entry = match self.delta(genotype) {
+            Some(changes) => DeltaEntry::Delta(changes),
+            None => DeltaEntry::Full(genotype.clone()),
+        };
+
+        self.entries.push(entry);
+        self.entries.len() - 1
+    }
+
+    /// Decompress the genotype at `index`. Panics if `index` is out of range.
+    pub fn get(&self, index: usize) -> Genotype<C> {
+        match &self.entries[index] {
+            DeltaEntry::Full(genotype) => genotype.clone(),
+            DeltaEntry::Delta(changes) => {
+                let mut genotype = self.reference.clone();
+                for (chromosome, index, gene) in changes.iter() {
+                    genotype[*chromosome].set_gene(*index, gene.clone());
+                }
+
+                genotype
+            }
+        }
+    }
+
+    /// Decompress the genotypes in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = Genotype<C>> + '_ {
+        (0..self.entries.len()).map(|index| self.get(index))
+    }
+
+    pub fn reference(&self) -> &Genotype<C> {
+        &self.reference
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of genes stored for the archived genotypes - the changed genes of the compressed
+    /// ones and every gene of those stored whole. The reference's genes aren't counted.
+    pub fn stored_genes(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                DeltaEntry::Delta(changes) => changes.len(),
+                DeltaEntry::Full(genotype) => genes_of(genotype),
+            })
+            .sum()
+    }
+
+    /// The number of genes of the archived genotypes uncompressed, over the number stored (the
+    /// reference's genes included). An archive of identical genotypes has the highest ratio.
+    pub fn compression_ratio(&self) -> f32 {
+        let uncompressed = self
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                DeltaEntry::Delta(_) => genes_of(&self.reference),
+                DeltaEntry::Full(genotype) => genes_of(genotype),
+            })
+            .sum::<usize>();
+
+        uncompressed as f32 / (self.stored_genes() + genes_of(&self.reference)).max(1) as f32
+    }
+
+    #[cfg(feature = "wire")]
+    pub(crate) fn entries(&self) -> &[DeltaEntry<C>] {
+        &self.entries
+    }
+
+    /// The genes of `genotype` that differ from the reference, or `None` if its shape differs.
+    fn delta(&self, genotype: &Genotype<C>) -> Option<Vec<(usize, usize, C::Gene)>> {
+        if genotype.len() != self.reference.len() {
+            return None;
+        }
+
+        let mut changes = Vec::new();
+        for (chromosome, (reference, other)) in
+            self.reference.iter().zip(genotype.iter()).enumerate()
+        {
+            if reference.len() != other.len() {
+                return None;
+            }
+
+            for (index, (old, new)) in reference.iter().zip(other.iter()).enumerate() {
+                if old != new {
+                    changes.push((chromosome, index, new.clone()));
+                }
+            }
+        }
+
+        Some(changes)
+    }
+}
+
+impl<C: Chromosome> MemoryFootprint for DeltaArchive<C> {
+    fn footprint(&self) -> usize {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                DeltaEntry::Delta(changes) => {
+                    changes.capacity() * std::mem::size_of::<(usize, usize, C::Gene)>()
+                }
+                DeltaEntry::Full(genotype) => genotype.footprint(),
+            })
+            .sum::<usize>();
+
+        std::mem::size_of::<DeltaArchive<C>>()
+            + self.reference.footprint()
+            + self.entries.capacity() * std::mem::size_of::<DeltaEntry<C>>()
+            + entries
+    }
+}
+
+fn genes_of<C: Chromosome>(genotype: &Genotype<C>) -> usize {
+    genotype.iter().map(|chromosome| chromosome.len()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Codex, FloatCodex, Gene};
+
+    #[test]
+    fn test_delta_archive_round_trips_genotypes() {
+        let codex = FloatCodex::new(2, 50, 0.0, 1.0);
+        let reference = codex.encode();
+
+        let mut genotypes = vec![reference.clone(), codex.encode()];
+        for i in 0..20 {
+            let mut genotype = reference.clone();
+            let gene = genotype[i % 2].get_gene(i).with_allele(&2.0);
+            genotype[i % 2].set_gene(i, gene);
+            genotypes.push(genotype);
+        }
+
+        let mut shorter = reference.clone();
+        shorter[0].genes.pop();
+        genotypes.push(shorter);
+
+        let archive = DeltaArchive::from_genotypes(&genotypes);
+
+        assert_eq!(archive.len(), genotypes.len());
+        assert!(archive
+            .iter()
+            .zip(genotypes.iter())
+            .all(|(one, two)| one == *two));
+        assert_eq!(archive.stored_genes(), 100 + 20 + 99);
+        assert!(archive.footprint() < genotypes.footprint());
+    }
+}
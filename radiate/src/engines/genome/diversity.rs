@@ -0,0 +1,57 @@
+use super::{Chromosome, FloatChromosome, Genotype};
+
+/// A measure of how different two genotypes are - 0 for identical genotypes, growing as they diverge.
+/// Used wherever individuals are compared by their genes rather than their scores, e.g. assortative
+/// mating (see `Pairing`).
+///
+/// Any `Fn(&Genotype<C>, &Genotype<C>) -> f32` is a `Diversity`.
+pub trait Diversity<C: Chromosome> {
+    fn distance(&self, one: &Genotype<C>, two: &Genotype<C>) -> f32;
+}
+
+impl<C, F> Diversity<C> for F
+where
+    C: Chromosome,
+    F: Fn(&Genotype<C>, &Genotype<C>) -> f32,
+{
+    fn distance(&self, one: &Genotype<C>, two: &Genotype<C>) -> f32 {
+        self(one, two)
+    }
+}
+
+/// The number of genes that differ between the two genotypes, position by position. Genes one of the
+/// genotypes doesn't have (when their chromosomes differ in length) count as different.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HammingDistance;
+
+impl<C: Chromosome> Diversity<C> for HammingDistance {
+    fn distance(&self, one: &Genotype<C>, two: &Genotype<C>) -> f32 {
+        one.iter()
+            .zip(two.iter())
+            .map(|(chrom_one, chrom_two)| {
+                let differing = chrom_one
+                    .iter()
+                    .zip(chrom_two.iter())
+                    .filter(|(gene_one, gene_two)| gene_one != gene_two)
+                    .count();
+
+                differing + chrom_one.len().abs_diff(chrom_two.len())
+            })
+            .sum::<usize>() as f32
+    }
+}
+
+/// The euclidean distance between the alleles of two float genotypes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EuclideanDistance;
+
+impl Diversity<FloatChromosome> for EuclideanDistance {
+    fn distance(&self, one: &Genotype<FloatChromosome>, two: &Genotype<FloatChromosome>) -> f32 {
+        one.iter()
+            .zip(two.iter())
+            .flat_map(|(chrom_one, chrom_two)| chrom_one.iter().zip(chrom_two.iter()))
+            .map(|(gene_one, gene_two)| (gene_one.allele - gene_two.allele).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
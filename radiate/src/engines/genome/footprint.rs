@@ -0,0 +1,93 @@
+use super::{Chromosome, Genotype, Phenotype, Population};
+
+/// An approximate number of bytes a value occupies in memory, including what it owns on the heap.
+/// It's meant for budgeting (see `MemoryBudget`), not exact accounting - genes are counted by their
+/// inline size, so genes that own heap memory themselves (e.g. graph nodes) are undercounted.
+pub trait MemoryFootprint {
+    fn footprint(&self) -> usize;
+}
+
+macro_rules! impl_inline_footprint {
+    ($($t:ty),*) => {
+        $(
+            impl MemoryFootprint for $t {
+                fn footprint(&self) -> usize {
+                    std::mem::size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+impl_inline_footprint!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64, bool, char);
+
+impl MemoryFootprint for String {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<String>() + self.capacity()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Vec<T> {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Vec<T>>()
+            + self.iter().map(|item| item.footprint()).sum::<usize>()
+            + (self.capacity() - self.len()) * std::mem::size_of::<T>()
+    }
+}
+
+impl<C: Chromosome> MemoryFootprint for Genotype<C> {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Genotype<C>>()
+            + self
+                .iter()
+                .map(|chromosome| {
+                    std::mem::size_of::<C>() + chromosome.len() * std::mem::size_of::<C::Gene>()
+                })
+                .sum::<usize>()
+    }
+}
+
+impl<C: Chromosome> MemoryFootprint for Phenotype<C> {
+    fn footprint(&self) -> usize {
+        let genotype = self.genotype.as_ref().map_or(0, |genotype| {
+            genotype.footprint() - std::mem::size_of::<Genotype<C>>()
+        });
+        let score = self
+            .score
+            .as_ref()
+            .map_or(0, |score| score.values.len() * std::mem::size_of::<f32>());
+
+        std::mem::size_of::<Phenotype<C>>() + genotype + score
+    }
+}
+
+impl<C: Chromosome> MemoryFootprint for Population<C> {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Population<C>>()
+            + self
+                .iter()
+                .map(|phenotype| phenotype.footprint())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, FloatGene};
+
+    #[test]
+    fn test_genotype_footprint_grows_with_genes() {
+        let small = Genotype::new(vec![FloatChromosome::from(&[1.0][..])]);
+        let large = Genotype::new(vec![FloatChromosome::from(&[1.0, 2.0, 3.0][..])]);
+
+        assert_eq!(
+            large.footprint() - small.footprint(),
+            2 * std::mem::size_of::<FloatGene>()
+        );
+        assert_eq!(
+            vec![1_u8, 2, 3].footprint(),
+            std::mem::size_of::<Vec<u8>>() + 3
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use std::ops::{Index, IndexMut};
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut};
 
 use crate::{Chromosome, Valid};
 
@@ -35,11 +36,11 @@ impl<C: Chromosome> Genotype<C> {
         self.chromosomes.len()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<C> {
+    pub fn iter(&self) -> core::slice::Iter<'_, C> {
         self.chromosomes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<C> {
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, C> {
         self.chromosomes.iter_mut()
     }
 
@@ -1,11 +1,39 @@
 pub mod chromosomes;
-
 pub mod genotype;
-pub mod phenotype;
-pub mod population;
 
 pub use chromosomes::*;
-
 pub use genotype::*;
+
+#[cfg(feature = "std")]
+pub mod compression;
+#[cfg(feature = "std")]
+pub mod diversity;
+#[cfg(feature = "std")]
+pub mod footprint;
+#[cfg(feature = "std")]
+pub mod phenotype;
+#[cfg(feature = "std")]
+pub mod population;
+#[cfg(feature = "std")]
+pub mod schema;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod tabular;
+#[cfg(feature = "std")]
+pub mod wire;
+
+#[cfg(feature = "std")]
+pub use compression::*;
+#[cfg(feature = "std")]
+pub use diversity::*;
+#[cfg(feature = "std")]
+pub use footprint::*;
+#[cfg(feature = "std")]
 pub use phenotype::*;
+#[cfg(feature = "std")]
 pub use population::*;
+#[cfg(feature = "std")]
+pub use schema::*;
+#[cfg(feature = "std")]
+pub use shared::*;
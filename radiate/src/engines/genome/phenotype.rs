@@ -1,4 +1,5 @@
 use crate::engines::objectives::Score;
+use crate::metadata::Metadata;
 use crate::Chromosome;
 
 use super::{genotype::Genotype, Valid};
@@ -7,6 +8,10 @@ use super::{genotype::Genotype, Valid};
 /// * `Genotype` - the genetic representation of the individual
 /// * `Score` - the score (fitness) of the individual as calculated by the fitness function
 /// * `Generation` - the generation in which the individual was created
+/// * `Metadata` - optional metadata (or a non-fatal error) attached by the fitness function
+/// * `Species` - the species the individual belongs to, if the population is speciated
+/// * `Tags` - tags the alterers put on the individual while producing it
+/// * `Dirty` - whether the individual changed since it was last scored, and needs to be evaluated
 ///
 /// The `Phenotype` is a wrapper around the `Genotype` that adds additional information about the individual.
 /// In traditional (biological) genetics, a phenotype is "the set of observable characteristics of an individual resulting
@@ -22,6 +27,10 @@ pub struct Phenotype<C: Chromosome> {
     pub genotype: Option<Genotype<C>>,
     pub score: Option<Score>,
     pub generation: i32,
+    pub metadata: Option<Metadata>,
+    pub species: Option<usize>,
+    pub tags: Vec<u64>,
+    pub dirty: bool,
 }
 
 impl<C: Chromosome> Phenotype<C> {
@@ -31,6 +40,10 @@ impl<C: Chromosome> Phenotype<C> {
             genotype: Some(genotype),
             score: None,
             generation,
+            metadata: None,
+            species: None,
+            tags: Vec::new(),
+            dirty: true,
         }
     }
 
@@ -42,6 +55,10 @@ impl<C: Chromosome> Phenotype<C> {
             genotype: Some(Genotype::new(chromosomes)),
             score: None,
             generation,
+            metadata: None,
+            species: None,
+            tags: Vec::new(),
+            dirty: true,
         }
     }
 
@@ -55,6 +72,7 @@ impl<C: Chromosome> Phenotype<C> {
 
     pub fn take_genotype(&mut self) -> Genotype<C> {
         self.score = None;
+        self.dirty = true;
         self.genotype.take().unwrap()
     }
 
@@ -69,10 +87,68 @@ impl<C: Chromosome> Phenotype<C> {
         }
     }
 
+    /// Set the score of the individual. A score marks the individual clean and taking it away marks it
+    /// dirty (see `is_dirty`).
     pub fn set_score(&mut self, score: Option<Score>) {
+        self.dirty = score.is_none();
         self.score = score;
     }
 
+    /// Whether the individual needs to be evaluated - it is new, or an alterer changed its genotype and
+    /// marked it with `mark_dirty`. Individuals that pass through a generation untouched stay clean and
+    /// are not evaluated again (unless the engine is set to `stochastic_fitness`).
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Mark the individual as changed, dropping its score (and metadata) so the engine evaluates it
+    /// again. Alterers call this on every individual whose genotype they change.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.score = None;
+        self.metadata = None;
+    }
+
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    pub fn set_metadata(&mut self, metadata: Option<Metadata>) {
+        self.metadata = metadata;
+    }
+
+    /// The id of the species the individual belongs to. Offspring inherit the species of the
+    /// individual they were selected from, and species-aware alterers (e.g. `WithinSpecies`)
+    /// only mate individuals of the same species.
+    pub fn species(&self) -> Option<usize> {
+        self.species
+    }
+
+    pub fn set_species(&mut self, species: Option<usize>) {
+        self.species = species;
+    }
+
+    /// The tags the alterers put on the individual while producing it - e.g. an `AdaptiveChoice` tags
+    /// each offspring with the application of the alterer that produced it, and credits the alterer from
+    /// the tag once the offspring is evaluated. The engine clears the tags once the alterers have
+    /// observed the offspring (see `Compose::observe`).
+    pub fn tags(&self) -> &[u64] {
+        &self.tags
+    }
+
+    pub fn tag(&mut self, tag: u64) {
+        self.tags.push(tag);
+    }
+
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    /// Get the non-fatal error reported by the fitness function for this individual, if any.
+    pub fn error(&self) -> Option<&String> {
+        self.metadata.as_ref().and_then(|metadata| metadata.error())
+    }
+
     /// Get the age of the individual in generations. The age is calculated as the
     /// difference between the given generation and the generation in which the individual was created.
     pub fn age(&self, generation: i32) -> i32 {
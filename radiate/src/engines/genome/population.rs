@@ -15,6 +15,9 @@ use std::ops::{Index, IndexMut};
 /// you should create a new `Population` instance with the new individuals. To further facilitate this way of
 /// thinking, the `Population` struct and everything it contains implements the `Clone` trait.
 ///
+/// The engine keeps its population sorted by `Objective::compare` - best first, ties broken by age -
+/// after every evaluation, and selectors may rely on that order. Any mutable access clears `is_sorted`.
+///
 /// # Type Parameters
 /// - `C`: The type of chromosome used in the genotype, which must implement the `Chromosome` trait.
 
@@ -34,11 +37,11 @@ impl<C: Chromosome> Population<C> {
         }
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Phenotype<C>> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Phenotype<C>> {
         self.individuals.iter()
     }
 
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<Phenotype<C>> {
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Phenotype<C>> {
         self.is_sorted = false;
         self.individuals.iter_mut()
     }
@@ -47,6 +50,23 @@ impl<C: Chromosome> Population<C> {
         self.individuals.len()
     }
 
+    /// Mutable references to two different individuals at once, e.g. the parents of a crossover.
+    /// This will set the is_sorted flag to false. Panics if `a` and `b` are equal.
+    pub fn pair_mut(&mut self, a: usize, b: usize) -> (&mut Phenotype<C>, &mut Phenotype<C>) {
+        if a == b {
+            panic!("pair_mut requires two different indices");
+        }
+
+        self.is_sorted = false;
+        if a < b {
+            let (left, right) = self.individuals.split_at_mut(b);
+            (&mut left[a], &mut right[0])
+        } else {
+            let (left, right) = self.individuals.split_at_mut(a);
+            (&mut right[0], &mut left[b])
+        }
+    }
+
     /// Swap the individuals at the given indices. This will set the is_sorted flag to false
     /// because the order of the individuals has changed and we don't know if the order
     /// has changed to benefit the order or not. Therefore, don't use this method to
@@ -56,8 +76,10 @@ impl<C: Chromosome> Population<C> {
         self.individuals.swap(a, b);
     }
 
-    /// Sort the individuals in the population using the given closure.
-    /// This will set the is_sorted flag to true.
+    /// Sort the individuals in the population using the given closure. The sort is stable, so
+    /// individuals that compare equal keep their relative order. This will set the is_sorted flag
+    /// to true - if the flag is already set the population is left as it is, so use
+    /// `Objective::sort` to get the engine's ordering (see `Objective::compare`).
     pub fn sort_by<F>(&mut self, f: F)
     where
         F: FnMut(&Phenotype<C>, &Phenotype<C>) -> std::cmp::Ordering,
@@ -92,6 +114,22 @@ impl<C: Chromosome> Population<C> {
         self.individuals.is_empty()
     }
 
+    /// Get the individuals whose fitness function reported a non-fatal error.
+    pub fn with_errors(&self) -> Vec<&Phenotype<C>> {
+        self.individuals
+            .iter()
+            .filter(|individual| individual.error().is_some())
+            .collect()
+    }
+
+    /// Get the metadata value with the given key for each individual that has one.
+    pub fn metadata_values(&self, key: &str) -> Vec<&String> {
+        self.individuals
+            .iter()
+            .filter_map(|individual| individual.metadata().and_then(|meta| meta.get(key)))
+            .collect()
+    }
+
     pub fn get_scores_ref(&self) -> Vec<&Score> {
         self.individuals
             .iter()
@@ -172,6 +210,21 @@ mod test {
         assert_eq!(population.len(), individuals.len());
     }
 
+    #[test]
+    fn test_pair_mut() {
+        let mut population = Population::new(vec![
+            Phenotype::from_chromosomes(vec![CharChromosome::from("hello")], 0),
+            Phenotype::from_chromosomes(vec![CharChromosome::from("world")], 0),
+        ]);
+
+        let (second, first) = population.pair_mut(1, 0);
+        std::mem::swap(second.genotype_mut(), first.genotype_mut());
+
+        assert!(population[0].genotype()[0] == CharChromosome::from("world"));
+        assert!(population[1].genotype()[0] == CharChromosome::from("hello"));
+        assert!(!population.is_sorted);
+    }
+
     #[test]
     fn test_from_fn() {
         let population = Population::from_fn(10, || {
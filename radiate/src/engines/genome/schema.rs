@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use super::{Chromosome, Gene, Genotype};
+
+/// User metadata describing a single gene - a human-readable name, a unit and any free-form hints
+/// (e.g. `"scale" -> "log"`) that operators, reports or a UI can use.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeneInfo {
+    pub name: String,
+    pub unit: Option<String>,
+    pub hints: BTreeMap<String, String>,
+}
+
+impl GeneInfo {
+    pub fn new(name: impl Into<String>) -> Self {
+        GeneInfo {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    pub fn with_hint(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.hints.insert(key.into(), value.to_string());
+        self
+    }
+
+    pub fn hint(&self, key: &str) -> Option<&String> {
+        self.hints.get(key)
+    }
+}
+
+/// Metadata for the genes of a `Genotype`, by position - one `Vec<GeneInfo>` per chromosome. The schema
+/// describes the shape a `Codex` encodes rather than living in every gene, so it costs nothing per
+/// individual, and clones of it share the same data. Attach it to a codex with `Codex::with_schema`
+/// and the engine makes it available in the `EngineContext`. Operators that want to use it (e.g.
+/// scaling hints) can be given their own clone.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let schema = GeneSchema::new().chromosome(vec![
+///     GeneInfo::new("learning_rate").with_hint("scale", "log"),
+///     GeneInfo::new("width").with_unit("px"),
+/// ]);
+///
+/// let genotype = Genotype::new(vec![FloatChromosome::from(&[0.01, 64.0][..])]);
+///
+/// assert_eq!(schema.index_of("width"), Some((0, 1)));
+/// assert_eq!(schema.get(0, 0).unwrap().hint("scale").unwrap(), "log");
+/// assert_eq!(
+///     schema.describe(&genotype),
+///     vec![
+///         ("learning_rate".to_string(), "0.01".to_string()),
+///         ("width".to_string(), "64.0 px".to_string()),
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeneSchema {
+    chromosomes: Arc<Vec<Vec<GeneInfo>>>,
+}
+
+impl GeneSchema {
+    pub fn new() -> Self {
+        GeneSchema::default()
+    }
+
+    /// A schema for a single chromosome where every gene only has a name.
+    pub fn from_names(names: &[&str]) -> Self {
+        GeneSchema::new().chromosome(names.iter().map(|name| GeneInfo::new(*name)).collect())
+    }
+
+    /// Add the metadata of the next chromosome.
+    pub fn chromosome(mut self, genes: Vec<GeneInfo>) -> Self {
+        Arc::make_mut(&mut self.chromosomes).push(genes);
+        self
+    }
+
+    /// The metadata of the gene at `gene` in the chromosome at `chromosome`, if there is any.
+    pub fn get(&self, chromosome: usize, gene: usize) -> Option<&GeneInfo> {
+        self.chromosomes.get(chromosome)?.get(gene)
+    }
+
+    /// The name of a gene, falling back to its position (`"chromosome.gene"`) when it has none.
+    pub fn name(&self, chromosome: usize, gene: usize) -> String {
+        match self.get(chromosome, gene) {
+            Some(info) => info.name.clone(),
+            None => format!("{}.{}", chromosome, gene),
+        }
+    }
+
+    /// The position (chromosome, gene) of the gene with the given name.
+    pub fn index_of(&self, name: &str) -> Option<(usize, usize)> {
+        self.chromosomes
+            .iter()
+            .enumerate()
+            .find_map(|(chromosome, genes)| {
+                genes
+                    .iter()
+                    .position(|info| info.name == name)
+                    .map(|gene| (chromosome, gene))
+            })
+    }
+
+    /// Pair the name of every gene in the genotype with its allele (followed by its unit, if any) -
+    /// a readable form for reports and exports.
+    pub fn describe<C>(&self, genotype: &Genotype<C>) -> Vec<(String, String)>
+    where
+        C: Chromosome,
+        <C::Gene as Gene>::Allele: Debug,
+    {
+        let mut described = Vec::new();
+        for (chromosome_index, chromosome) in genotype.iter().enumerate() {
+            for (gene_index, gene) in chromosome.iter().enumerate() {
+                let unit = self
+                    .get(chromosome_index, gene_index)
+                    .and_then(|info| info.unit.as_ref());
+                let value = match unit {
+                    Some(unit) => format!("{:?} {}", gene.allele(), unit),
+                    None => format!("{:?}", gene.allele()),
+                };
+
+                described.push((self.name(chromosome_index, gene_index), value));
+            }
+        }
+
+        described
+    }
+}
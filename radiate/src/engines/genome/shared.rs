@@ -0,0 +1,299 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::{Chromosome, Population};
+
+const MAGIC: &[u8; 4] = b"RDSP";
+const HEADER_SIZE: usize = 16;
+const SCORE_SIZE: usize = 5;
+
+/// A plain-old-data allele that can be stored in a `SharedPopulationStore` as its raw little endian bytes.
+pub trait Pod: Copy + Default {
+    const SIZE: usize;
+
+    fn write_le(&self, bytes: &mut [u8]);
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(
+            impl Pod for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn write_le(&self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    let mut buffer = [0; std::mem::size_of::<$t>()];
+                    buffer.copy_from_slice(bytes);
+                    <$t>::from_le_bytes(buffer)
+                }
+            }
+        )*
+    };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+/// A fixed-layout population store in a file shared between processes. Each individual has a slot holding
+/// its alleles as raw bytes followed by its score, so evaluation workers in separate processes can read the
+/// genes they were assigned and write back scores without any serialization - only fixed size reads and
+/// writes at known offsets, which the operating system serves from the shared page cache.
+///
+/// The usual pattern is for the main process to `create` the store and `write_population` each generation,
+/// hand the path and an index range to each worker process, then collect the results with `scores`.
+/// Workers `open` the same path, `read_alleles` for their individuals and `write_score` for each.
+/// Workers must write to disjoint slots.
+///
+/// Layout: a 16 byte header (`RDSP`, allele size, capacity and genes per individual as little endian `u32`s)
+/// followed by `capacity` slots of `genes * A::SIZE` allele bytes, a little endian `f32` score and a
+/// one byte 'evaluated' flag.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let path = std::env::temp_dir().join(format!("radiate_doc_store_{}", std::process::id()));
+/// let store = SharedPopulationStore::<f32>::create(&path, 2, 3).unwrap();
+/// store.write_alleles(1, &[1.0, 2.0, 3.0]).unwrap();
+///
+/// // In a worker process...
+/// let worker = SharedPopulationStore::<f32>::open(&path).unwrap();
+/// let alleles = worker.read_alleles(1).unwrap();
+/// worker.write_score(1, alleles.iter().sum()).unwrap();
+///
+/// assert_eq!(store.scores().unwrap(), vec![None, Some(6.0)]);
+/// std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct SharedPopulationStore<A: Pod> {
+    path: PathBuf,
+    file: Mutex<File>,
+    capacity: usize,
+    genes: usize,
+    _allele: std::marker::PhantomData<A>,
+}
+
+impl<A: Pod> SharedPopulationStore<A> {
+    /// Create (or truncate) the store at `path` with room for `capacity` individuals of `genes` alleles each.
+    pub fn create(path: impl AsRef<Path>, capacity: usize, genes: usize) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path.as_ref())?;
+
+        let mut header = [0; HEADER_SIZE];
+        header[..4].copy_from_slice(MAGIC);
+        header[4..8].copy_from_slice(&(A::SIZE as u32).to_le_bytes());
+        header[8..12].copy_from_slice(&(capacity as u32).to_le_bytes());
+        header[12..16].copy_from_slice(&(genes as u32).to_le_bytes());
+
+        file.write_all(&header)?;
+        file.set_len((HEADER_SIZE + capacity * (genes * A::SIZE + SCORE_SIZE)) as u64)?;
+
+        Ok(SharedPopulationStore {
+            path: path.as_ref().to_path_buf(),
+            file: Mutex::new(file),
+            capacity,
+            genes,
+            _allele: std::marker::PhantomData,
+        })
+    }
+
+    /// Open an existing store, e.g. from a worker process.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path.as_ref())?;
+
+        let mut header = [0; HEADER_SIZE];
+        file.read_exact(&mut header)?;
+
+        let read_u32 =
+            |start: usize| u32::from_le_bytes(header[start..start + 4].try_into().unwrap());
+        if &header[..4] != MAGIC || read_u32(4) as usize != A::SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "not a shared population store for this allele type",
+            ));
+        }
+
+        Ok(SharedPopulationStore {
+            path: path.as_ref().to_path_buf(),
+            file: Mutex::new(file),
+            capacity: read_u32(8) as usize,
+            genes: read_u32(12) as usize,
+            _allele: std::marker::PhantomData,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn genes(&self) -> usize {
+        self.genes
+    }
+
+    /// Write the alleles of every individual in the population (flattened across chromosomes) using `allele`
+    /// to map each gene to its POD value, and clear all scores. Panics if the population doesn't fit the store.
+    pub fn write_population<C, F>(
+        &self,
+        population: &Population<C>,
+        allele: F,
+    ) -> std::io::Result<()>
+    where
+        C: Chromosome,
+        F: Fn(&C::Gene) -> A,
+    {
+        if population.len() > self.capacity {
+            panic!(
+                "population of {} doesn't fit in a store with capacity {}",
+                population.len(),
+                self.capacity
+            );
+        }
+
+        for (index, phenotype) in population.iter().enumerate() {
+            let alleles = phenotype
+                .genotype()
+                .iter()
+                .flat_map(|chromosome| chromosome.iter().map(&allele))
+                .collect::<Vec<A>>();
+
+            self.write_alleles(index, &alleles)?;
+        }
+
+        self.clear_scores()
+    }
+
+    /// Write the alleles of the individual at `index`. Panics if the number of alleles doesn't match the store.
+    pub fn write_alleles(&self, index: usize, alleles: &[A]) -> std::io::Result<()> {
+        if alleles.len() != self.genes {
+            panic!(
+                "expected {} alleles per individual, got {}",
+                self.genes,
+                alleles.len()
+            );
+        }
+
+        let mut bytes = vec![0; self.genes * A::SIZE];
+        for (allele, chunk) in alleles.iter().zip(bytes.chunks_mut(A::SIZE)) {
+            allele.write_le(chunk);
+        }
+
+        self.write_at(self.slot_offset(index), &bytes)
+    }
+
+    pub fn read_alleles(&self, index: usize) -> std::io::Result<Vec<A>> {
+        let mut bytes = vec![0; self.genes * A::SIZE];
+        self.read_at(self.slot_offset(index), &mut bytes)?;
+
+        Ok(bytes.chunks(A::SIZE).map(A::read_le).collect())
+    }
+
+    pub fn write_score(&self, index: usize, score: f32) -> std::io::Result<()> {
+        let mut bytes = [1; SCORE_SIZE];
+        bytes[..4].copy_from_slice(&score.to_le_bytes());
+        self.write_at(self.score_offset(index), &bytes)
+    }
+
+    /// The score of the individual at `index`, or `None` if it hasn't been written since the last clear.
+    pub fn read_score(&self, index: usize) -> std::io::Result<Option<f32>> {
+        let mut bytes = [0; SCORE_SIZE];
+        self.read_at(self.score_offset(index), &mut bytes)?;
+        Ok(Self::parse_score(&bytes))
+    }
+
+    /// The scores of every slot in the store.
+    pub fn scores(&self) -> std::io::Result<Vec<Option<f32>>> {
+        (0..self.capacity)
+            .map(|index| self.read_score(index))
+            .collect()
+    }
+
+    pub fn clear_scores(&self) -> std::io::Result<()> {
+        for index in 0..self.capacity {
+            self.write_at(self.score_offset(index), &[0; SCORE_SIZE])?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_score(bytes: &[u8]) -> Option<f32> {
+        match bytes[4] {
+            0 => None,
+            _ => Some(f32::from_le_bytes(bytes[..4].try_into().unwrap())),
+        }
+    }
+
+    fn slot_offset(&self, index: usize) -> u64 {
+        if index >= self.capacity {
+            panic!(
+                "index {} out of range for store with capacity {}",
+                index, self.capacity
+            );
+        }
+
+        (HEADER_SIZE + index * (self.genes * A::SIZE + SCORE_SIZE)) as u64
+    }
+
+    fn score_offset(&self, index: usize) -> u64 {
+        self.slot_offset(index) + (self.genes * A::SIZE) as u64
+    }
+
+    fn write_at(&self, offset: u64, bytes: &[u8]) -> std::io::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(bytes)
+    }
+
+    fn read_at(&self, offset: u64, bytes: &mut [u8]) -> std::io::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, Gene, Genotype, Phenotype};
+
+    #[test]
+    fn test_population_round_trip_between_handles() {
+        let path = std::env::temp_dir().join(format!("radiate_store_{}", std::process::id()));
+        let population = (0..3)
+            .map(|i| {
+                let values = [i as f32, i as f32 * 2.0];
+                let genotype = Genotype::new(vec![FloatChromosome::from(&values[..])]);
+                Phenotype::from_genotype(genotype, 0)
+            })
+            .collect::<Population<FloatChromosome>>();
+
+        let store = SharedPopulationStore::<f32>::create(&path, 4, 2).unwrap();
+        store
+            .write_population(&population, |gene| *gene.allele())
+            .unwrap();
+
+        let worker = SharedPopulationStore::<f32>::open(&path).unwrap();
+        assert_eq!(worker.capacity(), 4);
+        assert_eq!(worker.read_alleles(2).unwrap(), vec![2.0, 4.0]);
+
+        worker.write_score(2, 6.0).unwrap();
+        assert_eq!(store.scores().unwrap(), vec![None, None, Some(6.0), None]);
+
+        assert!(SharedPopulationStore::<u8>::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
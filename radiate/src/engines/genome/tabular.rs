@@ -0,0 +1,393 @@
+//! Plain text import and export of populations - CSV and, with the `serde` feature, JSON - for editing
+//! individuals by hand, analyzing them with other tools or seeding an engine from a spreadsheet.
+//!
+//! A CSV has one row per individual and one column per gene, named `g<chromosome>_<gene>`, plus the
+//! score columns if any individual is scored - `score` for a single objective, `score_0`, `score_1`,
+//! ... for more. A score cell left empty means the individual isn't scored. Columns may come in any
+//! order; score columns are recognized by name and the gene columns are taken in order.
+//!
+//! ```text
+//! score,g0_0,g0_1,g0_2
+//! 1.5,0.25,0.5,0.75
+//! ,0.1,0.2,0.3
+//! ```
+//!
+//! The JSON form is an array of objects with the individual's `genotype` (one array of alleles per
+//! chromosome) and optionally its `score` (an array of values) and `generation`:
+//!
+//! ```text
+//! [{ "score": [1.5], "generation": 3, "genotype": [[0.25, 0.5, 0.75]] }]
+//! ```
+//!
+//! As in the `wire` format, only alleles are written. Reading creates the genes from the genes of a
+//! template genotype with the same shape - usually `codex.encode()` - so bounds and other gene settings
+//! come from the reader's codex. Individuals read from a CSV are of generation 0. Reading fails with
+//! `std::io::ErrorKind::InvalidData` for text that is malformed or doesn't match the template.
+//!
+//! # Example
+//! ``` rust
+//! use radiate::*;
+//!
+//! let codex = IntCodex::new(1, 3, 0, 10);
+//! let csv = "score,g0_0,g0_1,g0_2\n6,1,2,3\n,4,5,6\n";
+//!
+//! let population = tabular::population_from_csv(csv, &codex.encode()).unwrap();
+//!
+//! assert_eq!(population.len(), 2);
+//! assert_eq!(population[0].score().unwrap().as_i32(), 6);
+//! assert!(population[1].score().is_none());
+//! assert_eq!(tabular::population_to_csv(&population), csv);
+//!
+//! // Seed an engine with the individuals of the spreadsheet.
+//! let engine = GeneticEngine::from_codex(codex)
+//!     .population(population)
+//!     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+//!     .build();
+//! ```
+
+use std::fmt::Display;
+use std::io::{Error, ErrorKind, Result};
+use std::str::FromStr;
+
+use super::{Chromosome, Gene, Genotype, Phenotype, Population};
+use crate::objectives::Score;
+
+/// Write the population as CSV, one row per individual in the population's order.
+pub fn population_to_csv<C>(population: &Population<C>) -> String
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: Display,
+{
+    let objectives = population
+        .iter()
+        .filter_map(|individual| individual.score().map(|score| score.values.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut header = match objectives {
+        0 => Vec::new(),
+        1 => vec!["score".to_string()],
+        _ => (0..objectives).map(|i| format!("score_{}", i)).collect(),
+    };
+
+    if let Some(first) = population.iter().next() {
+        for (i, chromosome) in first.genotype().iter().enumerate() {
+            header.extend((0..chromosome.len()).map(|j| format!("g{}_{}", i, j)));
+        }
+    }
+
+    let mut csv = header.join(",");
+    csv.push('\n');
+
+    for individual in population.iter() {
+        let mut row = (0..objectives)
+            .map(|i| match individual.score() {
+                Some(score) if i < score.values.len() => score.values[i].to_string(),
+                _ => String::new(),
+            })
+            .collect::<Vec<String>>();
+
+        row.extend(
+            individual
+                .genotype()
+                .iter()
+                .flat_map(|chromosome| chromosome.iter())
+                .map(|gene| quote(&gene.allele().to_string())),
+        );
+
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Read a population from CSV, creating its genes from the genes of `template`.
+pub fn population_from_csv<C>(csv: &str, template: &Genotype<C>) -> Result<Population<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: FromStr,
+{
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let header = match lines.next() {
+        Some((_, line)) => split(line)?,
+        None => return Err(invalid("missing header")),
+    };
+
+    let score_columns = header
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| name.trim().starts_with("score"))
+        .map(|(i, _)| i)
+        .collect::<Vec<usize>>();
+
+    let gene_columns = (0..header.len())
+        .filter(|i| !score_columns.contains(i))
+        .collect::<Vec<usize>>();
+
+    let genes = template
+        .iter()
+        .map(|chromosome| chromosome.len())
+        .sum::<usize>();
+    if gene_columns.len() != genes {
+        return Err(invalid(format!(
+            "expected {} gene columns but found {}",
+            genes,
+            gene_columns.len()
+        )));
+    }
+
+    let mut individuals = Vec::new();
+    for (number, line) in lines {
+        let cells = split(line)?;
+        if cells.len() != header.len() {
+            return Err(invalid(format!(
+                "line {}: expected {} cells but found {}",
+                number + 1,
+                header.len(),
+                cells.len()
+            )));
+        }
+
+        let mut alleles = gene_columns.iter().map(|&i| cells[i].as_str());
+        let mut genotype = template.clone();
+        for gene in genotype
+            .iter_mut()
+            .flat_map(|chromosome| chromosome.iter_mut())
+        {
+            let cell = alleles.next().unwrap();
+            let allele = cell
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("line {}: invalid allele '{}'", number + 1, cell)))?;
+            *gene = gene.with_allele(&allele);
+        }
+
+        let mut values = Vec::new();
+        for &i in score_columns.iter() {
+            let cell = cells[i].trim();
+            if !cell.is_empty() {
+                values.push(cell.parse::<f32>().map_err(|_| {
+                    invalid(format!("line {}: invalid score '{}'", number + 1, cell))
+                })?);
+            }
+        }
+
+        let mut individual = Phenotype::from_genotype(genotype, 0);
+        match values.len() {
+            0 => {}
+            n if n == score_columns.len() => individual.set_score(Some(Score::from_vec(values))),
+            _ => return Err(invalid(format!("line {}: incomplete score", number + 1))),
+        }
+
+        individuals.push(individual);
+    }
+
+    Ok(Population::new(individuals))
+}
+
+/// Write the population as pretty printed JSON.
+#[cfg(feature = "serde")]
+pub fn population_to_json<C>(population: &Population<C>) -> String
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: serde::Serialize + Clone,
+{
+    let rows = population
+        .iter()
+        .map(|individual| Row {
+            score: individual.score().map(|score| score.values.to_vec()),
+            generation: individual.generation,
+            genotype: individual
+                .genotype()
+                .iter()
+                .map(|chromosome| {
+                    chromosome
+                        .iter()
+                        .map(|gene| gene.allele().clone())
+                        .collect()
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&rows).unwrap()
+}
+
+/// Read a population from JSON, creating its genes from the genes of `template`.
+#[cfg(feature = "serde")]
+pub fn population_from_json<C>(json: &str, template: &Genotype<C>) -> Result<Population<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: serde::de::DeserializeOwned,
+{
+    let rows = serde_json::from_str::<Vec<Row<<C::Gene as Gene>::Allele>>>(json)
+        .map_err(|error| invalid(error.to_string()))?;
+
+    let mut individuals = Vec::with_capacity(rows.len());
+    for (number, row) in rows.into_iter().enumerate() {
+        if row.genotype.len() != template.len() {
+            return Err(invalid(format!(
+                "individual {}: expected {} chromosomes but found {}",
+                number,
+                template.len(),
+                row.genotype.len()
+            )));
+        }
+
+        let mut genotype = template.clone();
+        for (chromosome, alleles) in genotype.iter_mut().zip(row.genotype) {
+            if alleles.len() != chromosome.len() {
+                return Err(invalid(format!(
+                    "individual {}: expected {} genes but found {}",
+                    number,
+                    chromosome.len(),
+                    alleles.len()
+                )));
+            }
+
+            for (gene, allele) in chromosome.iter_mut().zip(alleles) {
+                *gene = gene.with_allele(&allele);
+            }
+        }
+
+        let mut individual = Phenotype::from_genotype(genotype, row.generation);
+        individual.set_score(row.score.map(Score::from_vec));
+        individuals.push(individual);
+    }
+
+    Ok(Population::new(individuals))
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Row<A> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    score: Option<Vec<f32>>,
+    #[serde(default)]
+    generation: i32,
+    genotype: Vec<Vec<A>>,
+}
+
+/// Quote a cell if it contains a separator, a quote or a line break.
+fn quote(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Split a CSV line into its cells, unquoting quoted cells.
+fn split(line: &str) -> Result<Vec<String>> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if cell.trim().is_empty() => {
+                cell.clear();
+                quoted = true;
+            }
+            (',', false) => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(invalid("unterminated quote"));
+    }
+
+    cells.push(cell);
+    Ok(cells)
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharChromosome, Codex, FloatCodex};
+
+    #[test]
+    fn test_csv_round_trips_populations() {
+        let codex = FloatCodex::new(2, 3, 0.0, 1.0);
+        let mut population = Population::new(
+            (0..4)
+                .map(|i| Phenotype::from_genotype(codex.encode(), i))
+                .collect(),
+        );
+        population[0].set_score(Some(Score::from_vec(vec![1.0, -2.5])));
+        population[2].set_score(Some(Score::from_vec(vec![0.5, 3.0])));
+
+        let csv = population_to_csv(&population);
+        assert!(csv.starts_with("score_0,score_1,g0_0,g0_1,g0_2,g1_0"));
+
+        let read = population_from_csv(&csv, &codex.encode()).unwrap();
+        assert_eq!(read.len(), 4);
+        for (one, two) in read.iter().zip(population.iter()) {
+            assert!(one.genotype() == two.genotype());
+            assert_eq!(one.score(), two.score());
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_separators() {
+        let template = Genotype::new(vec![CharChromosome::from("ab,\"")]);
+        let population = Population::new(vec![Phenotype::from_genotype(template.clone(), 0)]);
+
+        let csv = population_to_csv(&population);
+        assert_eq!(csv, "g0_0,g0_1,g0_2,g0_3\na,b,\",\",\"\"\"\"\n");
+
+        let read = population_from_csv(&csv, &template).unwrap();
+        assert!(*read[0].genotype() == template);
+    }
+
+    #[test]
+    fn test_csv_rejects_mismatched_shapes() {
+        let codex = FloatCodex::new(1, 3, 0.0, 1.0);
+
+        let mismatched = population_from_csv("g0_0,g0_1\n0.1,0.2\n", &codex.encode());
+        assert!(matches!(mismatched, Err(error) if error.kind() == ErrorKind::InvalidData));
+        assert!(population_from_csv("g0_0,g0_1,g0_2\n0.1,x,0.2\n", &codex.encode()).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trips_populations() {
+        let codex = FloatCodex::new(2, 2, 0.0, 1.0);
+        let mut population = Population::new(
+            (0..3)
+                .map(|i| Phenotype::from_genotype(codex.encode(), i))
+                .collect(),
+        );
+        population[1].set_score(Some(Score::from_f32(2.0)));
+
+        let json = population_to_json(&population);
+        let read = population_from_json(&json, &codex.encode()).unwrap();
+
+        for (one, two) in read.iter().zip(population.iter()) {
+            assert!(one.genotype() == two.genotype());
+            assert_eq!(one.score(), two.score());
+            assert_eq!(one.generation, two.generation);
+        }
+
+        let edited = r#"[{ "genotype": [[0.5, 0.25], [1.0, 0.0]] }]"#;
+        let read = population_from_json(edited, &codex.encode()).unwrap();
+        assert!(read[0].score().is_none());
+        assert_eq!(*read[0].genotype()[0].get_gene(1).allele(), 0.25);
+    }
+}
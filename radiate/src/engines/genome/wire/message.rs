@@ -0,0 +1,779 @@
+use std::collections::BTreeSet;
+use std::io::Result;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{invalid, write_genotype, WireAllele, WireReader};
+use crate::genome::compression::DeltaEntry;
+use crate::objectives::{Score, ScoreStats};
+use crate::random_provider::RngState;
+use crate::{
+    Checkpoint, Chromosome, DeltaArchive, Distribution, EvaluationQueue, Gene, GenerationSample,
+    Genotype, Metric, MetricSet, Phenotype, Population, Statistic, TimeStatistic,
+};
+
+/// The version written by this crate. Decoders accept every version up to this one.
+pub const VERSION: u16 = 1;
+
+const MAGIC: &[u8; 4] = b"RDWF";
+const SCORE: u8 = 1;
+const GENOTYPE: u8 = 2;
+const PHENOTYPE: u8 = 3;
+const DELTA_ARCHIVE: u8 = 4;
+const EVALUATION_QUEUE: u8 = 5;
+const CHECKPOINT: u8 = 6;
+const SAMPLE: u8 = 7;
+
+/// The magic bytes every zstd frame starts with.
+const ZSTD_MAGIC: &[u8; 4] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+impl WireReader<'_> {
+    fn read_len(&mut self) -> Result<usize> {
+        Ok(self.read::<u32>()? as usize)
+    }
+
+    fn header(&mut self, kind: u8) -> Result<()> {
+        if self.take(4)? != MAGIC {
+            return Err(invalid("not a radiate wire message"));
+        }
+
+        let version = self.read::<u16>()?;
+        if version == 0 || version > VERSION {
+            return Err(invalid(format!("unsupported wire version {}", version)));
+        }
+
+        let found = self.read::<u8>()?;
+        if found != kind {
+            return Err(invalid(format!(
+                "expected message kind {} but found {}",
+                kind, found
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        match self.position == self.bytes.len() {
+            true => Ok(()),
+            false => Err(invalid("unexpected bytes after message")),
+        }
+    }
+}
+
+pub fn encode_score(score: &Score) -> Vec<u8> {
+    let mut out = header(SCORE);
+    write_score(score, &mut out);
+    out
+}
+
+pub fn decode_score(bytes: &[u8]) -> Result<Score> {
+    let mut reader = WireReader::new(bytes);
+    reader.header(SCORE)?;
+    let score = read_score(&mut reader)?;
+    reader.finish()?;
+    Ok(score)
+}
+
+pub fn encode_genotype<C>(genotype: &Genotype<C>) -> Vec<u8>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut out = header(GENOTYPE);
+    write_genotype(genotype, &mut out);
+    out
+}
+
+/// Decode a genotype, creating its genes from the genes of `template` (see the module docs).
+pub fn decode_genotype<C>(bytes: &[u8], template: &Genotype<C>) -> Result<Genotype<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut reader = WireReader::new(bytes);
+    reader.header(GENOTYPE)?;
+    let genotype = read_genotype(&mut reader, template)?;
+    reader.finish()?;
+    Ok(genotype)
+}
+
+pub fn encode_phenotype<C>(phenotype: &Phenotype<C>) -> Vec<u8>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut out = header(PHENOTYPE);
+    write_phenotype(phenotype, &mut out);
+    out
+}
+
+/// Decode a phenotype, creating its genes from the genes of `template` (see the module docs).
+pub fn decode_phenotype<C>(bytes: &[u8], template: &Genotype<C>) -> Result<Phenotype<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut reader = WireReader::new(bytes);
+    reader.header(PHENOTYPE)?;
+    let phenotype = read_phenotype(&mut reader, template)?;
+    reader.finish()?;
+    Ok(phenotype)
+}
+
+pub fn encode_delta_archive<C>(archive: &DeltaArchive<C>) -> Vec<u8>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut out = header(DELTA_ARCHIVE);
+    write_genotype(archive.reference(), &mut out);
+
+    (archive.len() as u32).write(&mut out);
+    for entry in archive.entries() {
+        match entry {
+            DeltaEntry::Full(genotype) => {
+                true.write(&mut out);
+                write_genotype(genotype, &mut out);
+            }
+            DeltaEntry::Delta(changes) => {
+                false.write(&mut out);
+                (changes.len() as u32).write(&mut out);
+                for (chromosome, index, gene) in changes.iter() {
+                    (*chromosome as u32).write(&mut out);
+                    (*index as u32).write(&mut out);
+                    gene.allele().write(&mut out);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Encode a delta archive and compress the message with zstd at the given `level` (1 to 22, 0 is zstd's default).
+#[cfg(feature = "zstd")]
+pub fn encode_delta_archive_zstd<C>(archive: &DeltaArchive<C>, level: i32) -> Result<Vec<u8>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    zstd::encode_all(encode_delta_archive(archive).as_slice(), level)
+}
+
+/// Decode a delta archive, creating its genes from the genes of `template` (see the module docs) - the
+/// reference and the genotypes stored whole must have the template's shape. Messages compressed with zstd
+/// are decompressed first, which requires the `zstd` feature.
+pub fn decode_delta_archive<C>(bytes: &[u8], template: &Genotype<C>) -> Result<DeltaArchive<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    if bytes.starts_with(ZSTD_MAGIC) {
+        return decode_delta_archive(&decompress(bytes)?, template);
+    }
+
+    let mut reader = WireReader::new(bytes);
+    reader.header(DELTA_ARCHIVE)?;
+    let reference = read_genotype(&mut reader, template)?;
+
+    let len = reader.read_len()?;
+    let mut entries = Vec::with_capacity(len.min(bytes.len()));
+    for _ in 0..len {
+        let entry = match reader.read::<bool>()? {
+            true => DeltaEntry::Full(read_genotype(&mut reader, template)?),
+            false => {
+                let changes = reader.read_len()?;
+                let mut delta = Vec::with_capacity(changes.min(bytes.len()));
+                for _ in 0..changes {
+                    let chromosome = reader.read_len()?;
+                    let index = reader.read_len()?;
+                    let gene = reference
+                        .as_ref()
+                        .get(chromosome)
+                        .and_then(|genes| genes.as_ref().get(index))
+                        .ok_or_else(|| {
+                            invalid(format!("no gene {} in chromosome {}", index, chromosome))
+                        })?;
+
+                    delta.push((chromosome, index, gene.with_allele(&reader.read()?)));
+                }
+
+                DeltaEntry::Delta(delta)
+            }
+        };
+
+        entries.push(entry);
+    }
+
+    reader.finish()?;
+    Ok(DeltaArchive::from_entries(reference, entries))
+}
+
+/// Encode the pending and quarantined genotypes of an evaluation queue, so an interrupted evaluation can
+/// be resumed.
+pub fn encode_evaluation_queue<C>(queue: &EvaluationQueue<C>) -> Vec<u8>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut out = header(EVALUATION_QUEUE);
+
+    (queue.len() as u32).write(&mut out);
+    for (genotype, crashes) in queue.pending() {
+        (crashes as u32).write(&mut out);
+        write_genotype(genotype, &mut out);
+    }
+
+    (queue.quarantined().len() as u32).write(&mut out);
+    for genotype in queue.quarantined() {
+        write_genotype(genotype, &mut out);
+    }
+
+    out
+}
+
+/// Decode an evaluation queue, creating its genes from the genes of `template` (see the module docs).
+pub fn decode_evaluation_queue<C>(
+    bytes: &[u8],
+    template: &Genotype<C>,
+) -> Result<EvaluationQueue<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut reader = WireReader::new(bytes);
+    reader.header(EVALUATION_QUEUE)?;
+
+    let len = reader.read_len()?;
+    let mut pending = std::collections::VecDeque::with_capacity(len.min(bytes.len()));
+    for _ in 0..len {
+        let crashes = reader.read_len()?;
+        pending.push_back((read_genotype(&mut reader, template)?, crashes));
+    }
+
+    let len = reader.read_len()?;
+    let quarantined = (0..len)
+        .map(|_| read_genotype(&mut reader, template))
+        .collect::<Result<Vec<Genotype<C>>>>()?;
+
+    reader.finish()?;
+    Ok(EvaluationQueue::from_parts(pending, quarantined))
+}
+
+/// Encode the state of an engine (see `Checkpoint`).
+pub fn encode_checkpoint<C>(checkpoint: &Checkpoint<C>) -> Vec<u8>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut out = header(CHECKPOINT);
+    checkpoint.index.write(&mut out);
+    checkpoint.stagnation.write(&mut out);
+    match &checkpoint.score {
+        Some(score) => {
+            true.write(&mut out);
+            write_score(score, &mut out);
+        }
+        None => false.write(&mut out),
+    }
+    match &checkpoint.rng {
+        Some(rng) => {
+            true.write(&mut out);
+            out.extend_from_slice(&rng.seed);
+            rng.stream.write(&mut out);
+            rng.word_pos.write(&mut out);
+        }
+        None => false.write(&mut out),
+    }
+
+    (checkpoint.population.len() as u32).write(&mut out);
+    for phenotype in checkpoint.population.iter() {
+        write_phenotype(phenotype, &mut out);
+    }
+
+    (checkpoint.front.len() as u32).write(&mut out);
+    for score in checkpoint.front.iter() {
+        write_score(score, &mut out);
+    }
+
+    let names = checkpoint.metrics.names();
+    (names.len() as u32).write(&mut out);
+    for name in names {
+        write_metric(checkpoint.metrics.get(name).unwrap(), &mut out);
+    }
+
+    out
+}
+
+/// Decode the state of an engine, creating its genes from the genes of `template` (see the module docs).
+pub fn decode_checkpoint<C>(bytes: &[u8], template: &Genotype<C>) -> Result<Checkpoint<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut reader = WireReader::new(bytes);
+    reader.header(CHECKPOINT)?;
+
+    let index = reader.read::<i32>()?;
+    let stagnation = reader.read::<i32>()?;
+    let score = match reader.read::<bool>()? {
+        true => Some(read_score(&mut reader)?),
+        false => None,
+    };
+    let rng = match reader.read::<bool>()? {
+        true => Some(RngState {
+            seed: reader.take(32)?.try_into().unwrap(),
+            stream: reader.read::<u64>()?,
+            word_pos: reader.read::<u128>()?,
+        }),
+        false => None,
+    };
+
+    let len = reader.read_len()?;
+    let population = (0..len)
+        .map(|_| read_phenotype(&mut reader, template))
+        .collect::<Result<Population<C>>>()?;
+
+    let len = reader.read_len()?;
+    let front = (0..len)
+        .map(|_| read_score(&mut reader))
+        .collect::<Result<Vec<Score>>>()?;
+
+    let len = reader.read_len()?;
+    let mut metrics = MetricSet::new();
+    for _ in 0..len {
+        metrics.add(read_metric(&mut reader)?);
+    }
+
+    reader.finish()?;
+    Ok(Checkpoint {
+        index,
+        stagnation,
+        score,
+        rng,
+        population,
+        front,
+        metrics,
+    })
+}
+
+/// Encode the population of a generation (see `GenerationSample`).
+pub fn encode_sample<C>(index: i32, population: &Population<C>) -> Vec<u8>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut out = header(SAMPLE);
+    index.write(&mut out);
+    (population.len() as u32).write(&mut out);
+    for phenotype in population.iter() {
+        write_phenotype(phenotype, &mut out);
+    }
+
+    out
+}
+
+/// Decode the population of a generation, creating its genes from the genes of `template` (see the
+/// module docs).
+pub fn decode_sample<C>(bytes: &[u8], template: &Genotype<C>) -> Result<GenerationSample<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let mut reader = WireReader::new(bytes);
+    reader.header(SAMPLE)?;
+
+    let index = reader.read::<i32>()?;
+    let len = reader.read_len()?;
+    let population = (0..len)
+        .map(|_| read_phenotype(&mut reader, template))
+        .collect::<Result<Population<C>>>()?;
+
+    reader.finish()?;
+    Ok(GenerationSample { index, population })
+}
+
+#[cfg(feature = "zstd")]
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+    zstd::decode_all(bytes)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress(_: &[u8]) -> Result<Vec<u8>> {
+    Err(invalid(
+        "the message is zstd-compressed, which requires the `zstd` feature",
+    ))
+}
+
+fn header(kind: u8) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    VERSION.write(&mut out);
+    kind.write(&mut out);
+    out
+}
+
+fn write_score(score: &Score, out: &mut Vec<u8>) {
+    (score.values.len() as u32).write(out);
+    score.values.iter().for_each(|value| value.write(out));
+
+    match score.stats() {
+        Some(stats) => {
+            true.write(out);
+            (stats.samples as u32).write(out);
+            stats.variances.iter().for_each(|value| value.write(out));
+        }
+        None => false.write(out),
+    }
+}
+
+fn read_score(reader: &mut WireReader) -> Result<Score> {
+    let len = reader.read_len()?;
+    let values = (0..len)
+        .map(|_| reader.read::<f32>())
+        .collect::<Result<Vec<f32>>>()?;
+
+    let stats = match reader.read::<bool>()? {
+        true => Some(ScoreStats {
+            samples: reader.read_len()?,
+            variances: (0..len)
+                .map(|_| reader.read::<f32>())
+                .collect::<Result<Vec<f32>>>()?,
+        }),
+        false => None,
+    };
+
+    Ok(Score { values, stats })
+}
+
+fn write_phenotype<C>(phenotype: &Phenotype<C>, out: &mut Vec<u8>)
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    phenotype.generation.write(out);
+    match phenotype.score() {
+        Some(score) => {
+            true.write(out);
+            write_score(score, out);
+        }
+        None => false.write(out),
+    }
+
+    write_genotype(phenotype.genotype(), out);
+}
+
+fn read_phenotype<C>(reader: &mut WireReader, template: &Genotype<C>) -> Result<Phenotype<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let generation = reader.read::<i32>()?;
+    let score = match reader.read::<bool>()? {
+        true => Some(read_score(reader)?),
+        false => None,
+    };
+    let genotype = read_genotype(reader, template)?;
+
+    let mut phenotype = Phenotype::from_genotype(genotype, generation);
+    phenotype.set_score(score);
+    Ok(phenotype)
+}
+
+fn write_metric(metric: &Metric, out: &mut Vec<u8>) {
+    let name = metric.name().as_bytes();
+    (name.len() as u32).write(out);
+    out.extend_from_slice(name);
+
+    match metric {
+        Metric::Value(_, stat) => {
+            0_u8.write(out);
+            write_statistic(stat, out);
+        }
+        Metric::Time(_, time) => {
+            1_u8.write(out);
+            write_statistic(&time.statistic, out);
+            (time.last_time.as_nanos() as u64).write(out);
+        }
+        Metric::Distribution(_, dist) => {
+            2_u8.write(out);
+            write_statistic(&dist.statistic, out);
+            (dist.last_sequence.len() as u32).write(out);
+            dist.last_sequence.iter().for_each(|value| value.write(out));
+        }
+        Metric::Operations(_, stat, time) => {
+            3_u8.write(out);
+            write_statistic(stat, out);
+            write_statistic(&time.statistic, out);
+            (time.last_time.as_nanos() as u64).write(out);
+        }
+    }
+}
+
+fn read_metric(reader: &mut WireReader) -> Result<Metric> {
+    let len = reader.read_len()?;
+    let name = std::str::from_utf8(reader.take(len)?)
+        .map_err(|error| invalid(format!("invalid metric name: {}", error)))?;
+    let name = intern(name);
+
+    let read_time = |reader: &mut WireReader| -> Result<TimeStatistic> {
+        Ok(TimeStatistic {
+            statistic: read_statistic(reader)?,
+            last_time: Duration::from_nanos(reader.read::<u64>()?),
+        })
+    };
+
+    Ok(match reader.read::<u8>()? {
+        0 => Metric::Value(name, read_statistic(reader)?),
+        1 => Metric::Time(name, read_time(reader)?),
+        2 => {
+            let statistic = read_statistic(reader)?;
+            let len = reader.read_len()?;
+            let last_sequence = (0..len)
+                .map(|_| reader.read::<f32>())
+                .collect::<Result<Vec<f32>>>()?;
+
+            Metric::Distribution(
+                name,
+                Distribution {
+                    statistic,
+                    last_sequence,
+                },
+            )
+        }
+        3 => {
+            let stat = read_statistic(reader)?;
+            Metric::Operations(name, stat, read_time(reader)?)
+        }
+        other => return Err(invalid(format!("invalid metric kind {}", other))),
+    })
+}
+
+fn write_statistic(statistic: &Statistic, out: &mut Vec<u8>) {
+    let (count, values) = statistic.to_raw();
+    count.write(out);
+    values.iter().for_each(|value| value.write(out));
+}
+
+fn read_statistic(reader: &mut WireReader) -> Result<Statistic> {
+    let count = reader.read::<i32>()?;
+    let mut values = [0.0; 18];
+    for value in values.iter_mut() {
+        *value = reader.read::<f32>()?;
+    }
+
+    Ok(Statistic::from_raw(count, values))
+}
+
+/// Metrics are named with `&'static str`s, so the names of decoded metrics are leaked - once per
+/// distinct name, however many messages are decoded.
+fn intern(name: &str) -> &'static str {
+    static NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let mut names = NAMES.lock().unwrap();
+    match names.get(name) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+            names.insert(interned);
+            interned
+        }
+    }
+}
+
+fn read_genotype<C>(reader: &mut WireReader, template: &Genotype<C>) -> Result<Genotype<C>>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    let chromosomes = reader.read_len()?;
+    if chromosomes != template.len() {
+        return Err(invalid(format!(
+            "expected {} chromosomes but found {}",
+            template.len(),
+            chromosomes
+        )));
+    }
+
+    let mut genotype = template.clone();
+    for chromosome in genotype.iter_mut() {
+        let allele_type = reader.read::<u8>()?;
+        if allele_type != <<C::Gene as Gene>::Allele as WireAllele>::TYPE {
+            return Err(invalid(format!("unexpected allele type {}", allele_type)));
+        }
+
+        let genes = reader.read_len()?;
+        if genes != chromosome.len() {
+            return Err(invalid(format!(
+                "expected {} genes but found {}",
+                chromosome.len(),
+                genes
+            )));
+        }
+
+        for gene in chromosome.iter_mut() {
+            let allele = reader.read()?;
+            *gene = gene.with_allele(&allele);
+        }
+    }
+
+    Ok(genotype)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random_provider::RngHandle;
+    use crate::{BitChromosome, CharChromosome, Codex, IntCodex};
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_wire_round_trips_scores_and_genotypes() {
+        let samples = [
+            Score::from_vec(vec![1.0, 2.0]),
+            Score::from_vec(vec![3.0, 6.0]),
+        ];
+        let score = Score::from_samples(&samples);
+        let decoded = decode_score(&encode_score(&score)).unwrap();
+
+        assert_eq!(decoded, score);
+        assert_eq!(decoded.stats(), score.stats());
+
+        let codex = IntCodex::<u8>::new(2, 4, 0, 255);
+        let genotype = codex.encode();
+        let decoded = decode_genotype(&encode_genotype(&genotype), &codex.encode()).unwrap();
+        assert!(decoded == genotype);
+
+        let bits = Genotype::new(vec![BitChromosome::from(vec![true, false, true, true])]);
+        assert!(decode_genotype(&encode_genotype(&bits), &bits).unwrap() == bits);
+
+        let chars = Genotype::new(vec![CharChromosome::from("radiate")]);
+        assert!(decode_genotype(&encode_genotype(&chars), &chars).unwrap() == chars);
+    }
+
+    #[test]
+    fn test_wire_rejects_mismatched_messages() {
+        let codex = IntCodex::<i32>::new(1, 3, 0, 10);
+        let bytes = encode_genotype(&codex.encode());
+
+        // Different shape and different allele type.
+        let longer = IntCodex::<i32>::new(1, 4, 0, 10).encode();
+        let other_type = IntCodex::<i64>::new(1, 3, 0, 10).encode();
+        assert!(decode_genotype(&bytes, &longer).is_err());
+        assert!(decode_genotype(&bytes, &other_type).is_err());
+
+        // Wrong kind, truncated, and a newer version.
+        assert!(decode_score(&bytes).is_err());
+        assert!(decode_genotype(&bytes[..bytes.len() - 1], &codex.encode()).is_err());
+
+        let mut newer = bytes.clone();
+        newer[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        let error = decode_genotype(&newer, &codex.encode()).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_wire_round_trips_delta_archives() {
+        let codex = IntCodex::<i32>::new(2, 200, 0, 100);
+        let reference = codex.encode();
+
+        let mut archive = DeltaArchive::new(reference.clone());
+        archive.push(&codex.encode());
+        for i in 0..50 {
+            let mut genotype = reference.clone();
+            let gene = genotype[1].get_gene(i).with_allele(&-1);
+            genotype[1].set_gene(i, gene);
+            archive.push(&genotype);
+        }
+
+        let bytes = encode_delta_archive(&archive);
+        let decoded = decode_delta_archive(&bytes, &codex.encode()).unwrap();
+        assert!(decoded.iter().eq(archive.iter()));
+
+        // Every genotype whole would take 50 times the reference's size.
+        assert!(bytes.len() * 10 < encode_genotype(&reference).len() * 51);
+
+        #[cfg(feature = "zstd")]
+        {
+            let compressed = encode_delta_archive_zstd(&archive, 3).unwrap();
+            assert!(compressed.len() < bytes.len());
+
+            let decoded = decode_delta_archive(&compressed, &codex.encode()).unwrap();
+            assert!(decoded.iter().eq(archive.iter()));
+        }
+
+        #[cfg(not(feature = "zstd"))]
+        assert!(decode_delta_archive(&[0x28, 0xB5, 0x2F, 0xFD, 0], &reference).is_err());
+    }
+
+    #[test]
+    fn test_wire_round_trips_evaluation_queues() {
+        let codex = IntCodex::<i32>::new(1, 10, 0, 100);
+        let pending = (0..5).map(|crashes| (codex.encode(), crashes)).collect();
+        let queue = EvaluationQueue::from_parts(pending, vec![codex.encode()]);
+
+        let bytes = encode_evaluation_queue(&queue);
+        let decoded = decode_evaluation_queue(&bytes, &codex.encode()).unwrap();
+
+        assert!(decoded == queue);
+        assert_eq!(
+            decoded.pending().map(|(_, crashes)| crashes).sum::<usize>(),
+            10
+        );
+        assert!(
+            decode_evaluation_queue(&encode_genotype(&codex.encode()), &codex.encode()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_wire_round_trips_checkpoints() {
+        let codex = IntCodex::<i32>::new(1, 10, 0, 100);
+        let population = (0..5)
+            .map(|i| {
+                let mut individual = Phenotype::from_genotype(codex.encode(), i);
+                individual.set_score(Some(Score::from_int(i)));
+                individual
+            })
+            .collect::<Population<_>>();
+
+        let mut metrics = MetricSet::new();
+        metrics.upsert_value("Custom Value", 1.5);
+        metrics.upsert_value("Custom Value", 2.5);
+        metrics.upsert_time("Custom Time", Duration::from_millis(3));
+        metrics.upsert_sequence("Custom Sequence", &[1.0, 2.0, 3.0]);
+        metrics.upsert_operations("Custom Operation", 4.0, Duration::from_micros(7));
+
+        let checkpoint = Checkpoint {
+            index: 12,
+            stagnation: 3,
+            score: Some(Score::from_int(0)),
+            rng: Some(RngHandle::seeded(99).state().unwrap()),
+            population,
+            front: vec![Score::from_vec(vec![1.0, 2.0])],
+            metrics,
+        };
+
+        let bytes = encode_checkpoint(&checkpoint);
+        let decoded = decode_checkpoint(&bytes, &codex.encode()).unwrap();
+
+        assert_eq!((decoded.index, decoded.stagnation), (12, 3));
+        assert_eq!(
+            (decoded.score, decoded.rng),
+            (checkpoint.score, checkpoint.rng)
+        );
+        assert_eq!(decoded.front, checkpoint.front);
+        assert!(decoded
+            .population
+            .iter()
+            .zip(checkpoint.population.iter())
+            .all(|(one, two)| one.genotype() == two.genotype() && one.score() == two.score()));
+        assert_eq!(decoded.metrics.names(), checkpoint.metrics.names());
+        for name in checkpoint.metrics.names() {
+            let one = decoded.metrics.get(name).unwrap();
+            let two = checkpoint.metrics.get(name).unwrap();
+            assert_eq!(one.value_stats(), two.value_stats());
+            assert_eq!(one.time_stats(), two.time_stats());
+            assert_eq!(one.sequence_stats(), two.sequence_stats());
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! A stable, versioned binary wire format for exchanging individuals between engines, tools written in
+//! other languages and remote workers, without depending on the in-memory layout of the Rust types.
+//!
+//! Every message starts with a header - the magic bytes `RDWF`, the format version as a `u16` and a
+//! `u8` message kind (1 = `Score`, 2 = `Genotype`, 3 = `Phenotype`, 4 = `DeltaArchive`,
+//! 5 = `EvaluationQueue`, 6 = `Checkpoint`, 7 = `GenerationSample`). All numbers
+//! are little-endian and every sequence is prefixed with its length as a `u32`:
+//!
+//! ```text
+//! score     := u32 n, n * f32 values, u8 has_stats, [u32 samples, n * f32 variances]
+//! genotype  := u32 chromosomes, chromosomes * (u8 allele_type, u32 genes, genes * allele)
+//! phenotype := i32 generation, u8 has_score, [score], genotype
+//! archive   := genotype reference, u32 n, n * (u8 whole, [genotype] | [u32 changes, changes * change])
+//! change    := u32 chromosome, u32 gene, allele
+//! queue     := u32 n, n * (u32 crashes, genotype), u32 q, q * genotype
+//! checkpoint:= i32 index, i32 stagnation, u8 has_score, [score], u8 has_rng, [rng],
+//!              u32 n, n * phenotype, u32 f, f * score, u32 m, m * metric
+//! rng       := 32 * u8 seed, u64 stream, u128 word_pos (the state of a ChaCha12 generator)
+//! sample    := i32 index, u32 n, n * phenotype
+//! metric    := string name, u8 kind, statistic [, statistic, u64 nanos] | [u64 nanos] | [u32 n, n * f32]
+//! statistic := i32 count, 18 * f32 (its running sums, last value, max and min)
+//! string    := u32 n, n * u8 (utf-8)
+//! allele    := f32 | f64 | bool (u8) | char (u32) | integer (its own width)
+//! ```
+//!
+//! With the `zstd` feature, a delta archive can be written zstd-compressed
+//! (`encode_delta_archive_zstd`). `decode_delta_archive` decompresses such messages transparently.
+//!
+//! Only alleles are sent. A receiver decodes genes against a template genotype with the same shape -
+//! usually `codex.encode()` - so bounds and other gene settings come from the receiver's own codex.
+//! Metadata is not part of the format. Decoding fails with `std::io::ErrorKind::InvalidData` for a
+//! message that is malformed, of a different kind or version, or doesn't match the template.
+//!
+//! The messages are behind the `wire` feature. `WireAllele` is always available - the fitness cache
+//! keys genotypes by the bytes of their alleles.
+//!
+//! # Example
+//! ``` rust
+//! # #[cfg(feature = "wire")]
+//! # {
+//! use radiate::*;
+//!
+//! let codex = FloatCodex::new(2, 3, 0.0, 1.0);
+//! let mut individual = Phenotype::from_genotype(codex.encode(), 4);
+//! individual.set_score(Some(Score::from_vec(vec![0.5, 1.5])));
+//!
+//! let bytes = wire::encode_phenotype(&individual);
+//! let decoded = wire::decode_phenotype(&bytes, &codex.encode()).unwrap();
+//!
+//! assert!(decoded.genotype() == individual.genotype());
+//! assert_eq!(decoded.score(), individual.score());
+//! assert_eq!(decoded.generation, 4);
+//! # }
+//! ```
+
+use std::io::{Error, ErrorKind, Result};
+
+use super::{Chromosome, Gene, Genotype};
+
+#[cfg(feature = "wire")]
+mod message;
+
+#[cfg(feature = "wire")]
+pub use message::*;
+
+/// An allele type that can be written to the wire format. `TYPE` identifies the type on the wire, so
+/// a genotype can't be decoded into genes with a different allele type.
+pub trait WireAllele: Sized {
+    const TYPE: u8;
+
+    fn write(&self, out: &mut Vec<u8>);
+    fn read(reader: &mut WireReader) -> Result<Self>;
+}
+
+macro_rules! impl_wire_allele {
+    ($($t:ty => $code:expr),*) => {
+        $(
+            impl WireAllele for $t {
+                const TYPE: u8 = $code;
+
+                fn write(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read(reader: &mut WireReader) -> Result<Self> {
+                    let bytes = reader.take(std::mem::size_of::<$t>())?;
+                    Ok(<$t>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_allele!(
+    f32 => 1, f64 => 2,
+    i8 => 10, i16 => 11, i32 => 12, i64 => 13, i128 => 14,
+    u8 => 20, u16 => 21, u32 => 22, u64 => 23, u128 => 24
+);
+
+impl WireAllele for bool {
+    const TYPE: u8 = 3;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn read(reader: &mut WireReader) -> Result<Self> {
+        match u8::read(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(invalid(format!("invalid bool {}", other))),
+        }
+    }
+}
+
+impl WireAllele for char {
+    const TYPE: u8 = 4;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        (*self as u32).write(out);
+    }
+
+    fn read(reader: &mut WireReader) -> Result<Self> {
+        let value = u32::read(reader)?;
+        char::from_u32(value).ok_or_else(|| invalid(format!("invalid char {}", value)))
+    }
+}
+
+/// Reads values from a wire message, failing instead of panicking when the message is too short.
+pub struct WireReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> WireReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        WireReader { bytes, position: 0 }
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position + len;
+        if end > self.bytes.len() {
+            return Err(invalid("unexpected end of message"));
+        }
+
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read<T: WireAllele>(&mut self) -> Result<T> {
+        T::read(self)
+    }
+}
+
+/// Write the alleles of a genotype, as in a genotype message without the header.
+pub(crate) fn write_genotype<C>(genotype: &Genotype<C>, out: &mut Vec<u8>)
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    (genotype.len() as u32).write(out);
+    for chromosome in genotype.iter() {
+        <<C::Gene as Gene>::Allele as WireAllele>::TYPE.write(out);
+        (chromosome.len() as u32).write(out);
+        chromosome.iter().for_each(|gene| gene.allele().write(out));
+    }
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidData, message.into())
+}
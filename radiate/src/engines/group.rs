@@ -0,0 +1,360 @@
+use std::sync::Arc;
+
+use super::{random_provider, EpisodeAggregate, Score};
+
+type GroupFn<T> = Arc<dyn Fn(&[T]) -> Vec<Score> + Send + Sync>;
+
+/// How a `GroupEvaluator` partitions the population into groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum GroupSampling {
+    /// The individuals are shuffled before every round, so they meet different group mates each time.
+    #[default]
+    Random,
+    /// Consecutive individuals of the population form a group, shifted by one position every round.
+    Sequential,
+}
+
+/// How a cooperative team's score is credited to its members (see `GroupEvaluator::cooperative`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CreditAssignment {
+    /// Every member gets the team's score.
+    #[default]
+    Shared,
+    /// Every member gets its difference reward - the team's score minus the score of the team without
+    /// it (a leave-one-out evaluation), i.e. the member's own contribution. Members that make no difference
+    /// get 0, so free-riders aren't rewarded for the work of their teammates.
+    Difference,
+}
+
+impl CreditAssignment {
+    /// Score every member of a team with `team_fn` according to this credit assignment. With
+    /// `Difference` the team is evaluated once in full and once without every member, so `team_fn`
+    /// has to accept a team one member short (an empty one for a team of one).
+    pub fn assign<T, F>(&self, members: &[T], team_fn: F) -> Vec<Score>
+    where
+        T: Clone,
+        F: Fn(&[T]) -> Score,
+    {
+        let team = team_fn(members);
+        match self {
+            CreditAssignment::Shared => vec![team; members.len()],
+            CreditAssignment::Difference => {
+                let without = leave_one_out(members)
+                    .iter()
+                    .map(|others| team_fn(others))
+                    .collect::<Vec<Score>>();
+                difference_rewards(&team, &without)
+            }
+        }
+    }
+}
+
+/// The leave-one-out teams of `members` - the `i`th team is every member except the `i`th.
+pub fn leave_one_out<T: Clone>(members: &[T]) -> Vec<Vec<T>> {
+    (0..members.len())
+        .map(|i| {
+            members
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, member)| member.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// The difference reward of every member - the `team` score minus the score of the team `without`
+/// that member, value by value.
+pub fn difference_rewards(team: &Score, without: &[Score]) -> Vec<Score> {
+    without
+        .iter()
+        .map(|other| {
+            Score::from_vec(
+                team.values
+                    .iter()
+                    .zip(other.values.iter())
+                    .map(|(with, without)| with - without)
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Evaluates individuals that interact with each other (a game of several players, agents sharing an
+/// environment, ...) by scoring them together in groups. The unscored individuals are partitioned into
+/// groups of `group_size`, every group is evaluated jointly by the group function - which returns one
+/// score per member, in order - and each group is its own job on the engine's thread pool.
+///
+/// Every individual plays `rounds` groups (each round is a new partition) and its score is the
+/// aggregate of its scores over those groups. With `EpisodeAggregate::Mean` (the default) the score keeps
+/// the variance of the rounds, like `GeneticEngineParams::repeat_evaluations`. A group that comes up short
+/// is filled with other individuals of the population, whose results in that group are ignored.
+///
+/// Set it with `GeneticEngineParams::group_evaluator` - it replaces the `fitness_fn`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Every player scores the number of group mates it beats.
+/// let evaluator = GroupEvaluator::new(4, |players: &[Vec<Vec<i32>>]| {
+///     players
+///         .iter()
+///         .map(|me| players.iter().filter(|other| me[0][0] > other[0][0]).count())
+///         .collect::<Vec<usize>>()
+/// })
+/// .rounds(3)
+/// .sampling(GroupSampling::Random)
+/// .aggregate(EpisodeAggregate::Mean);
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 1, 0, 100))
+///     .population_size(20)
+///     .group_evaluator(evaluator)
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// assert_eq!(result.score().stats().unwrap().samples, 3);
+/// ```
+pub struct GroupEvaluator<T> {
+    group_fn: GroupFn<T>,
+    group_size: usize,
+    rounds: usize,
+    sampling: GroupSampling,
+    aggregate: EpisodeAggregate,
+}
+
+impl<T> GroupEvaluator<T> {
+    /// Create a `GroupEvaluator` that scores groups of `group_size` individuals with `group_fn`.
+    /// Panics if `group_size` is 0.
+    ///
+    /// Defaults:
+    /// * rounds: 1
+    /// * sampling: GroupSampling::Random
+    /// * aggregate: EpisodeAggregate::Mean
+    pub fn new<F, S>(group_size: usize, group_fn: F) -> Self
+    where
+        F: Fn(&[T]) -> Vec<S> + Send + Sync + 'static,
+        S: Into<Score>,
+    {
+        if group_size < 1 {
+            panic!("group_size must be greater than 0");
+        }
+
+        GroupEvaluator {
+            group_fn: Arc::new(move |members| {
+                group_fn(members).into_iter().map(Into::into).collect()
+            }),
+            group_size,
+            rounds: 1,
+            sampling: GroupSampling::default(),
+            aggregate: EpisodeAggregate::Mean,
+        }
+    }
+
+    /// Create a `GroupEvaluator` for cooperative problems, where `team_fn` scores a whole team of
+    /// `group_size` individuals and `credit` decides how the team's score is split among its members.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// // The team is as strong as the sum of its members, but only the positive ones help.
+    /// let evaluator = GroupEvaluator::cooperative(
+    ///     3,
+    ///     CreditAssignment::Difference,
+    ///     |team: &[Vec<Vec<i32>>]| team.iter().map(|member| member[0][0].max(0)).sum::<i32>(),
+    /// );
+    ///
+    /// let scores = evaluator.evaluate(&[vec![vec![5]], vec![vec![-3]], vec![vec![2]]]);
+    /// assert_eq!(scores[0].as_f32(), 5.0);
+    /// assert_eq!(scores[1].as_f32(), 0.0);
+    /// assert_eq!(scores[2].as_f32(), 2.0);
+    /// ```
+    pub fn cooperative<F, S>(group_size: usize, credit: CreditAssignment, team_fn: F) -> Self
+    where
+        T: Clone,
+        F: Fn(&[T]) -> S + Send + Sync + 'static,
+        S: Into<Score>,
+    {
+        GroupEvaluator::new(group_size, move |members: &[T]| {
+            credit.assign(members, |team| team_fn(team).into())
+        })
+    }
+
+    /// Set the number of groups every individual is evaluated in. Panics if `rounds` is 0.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        if rounds < 1 {
+            panic!("rounds must be greater than 0");
+        }
+
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn sampling(mut self, sampling: GroupSampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Set how the scores of an individual's groups are combined - applied to every value of the score.
+    pub fn aggregate(mut self, aggregate: EpisodeAggregate) -> Self {
+        self.aggregate = aggregate;
+        self
+    }
+
+    pub fn group_size(&self) -> usize {
+        self.group_size
+    }
+
+    /// Evaluate one group, returning one score per member. Panics if the group function doesn't return
+    /// exactly one score per member.
+    pub fn evaluate(&self, members: &[T]) -> Vec<Score> {
+        let scores = (self.group_fn)(members);
+        if scores.len() != members.len() {
+            panic!(
+                "Group function returned {} scores for {} members",
+                scores.len(),
+                members.len()
+            );
+        }
+
+        scores
+    }
+
+    /// Partition the `unscored` individuals of a population of `population_size` into the groups of every
+    /// round. Each group is returned with the number of its leading members that are scored by it - the
+    /// rest are only there to fill the group.
+    pub fn groups(&self, unscored: &[usize], population_size: usize) -> Vec<(Vec<usize>, usize)> {
+        let mut groups = Vec::new();
+        if unscored.is_empty() {
+            return groups;
+        }
+
+        let mut order = unscored.to_vec();
+        for round in 0..self.rounds {
+            match self.sampling {
+                GroupSampling::Random => random_provider::shuffle(&mut order),
+                GroupSampling::Sequential if round > 0 => order.rotate_left(1),
+                GroupSampling::Sequential => {}
+            }
+
+            for chunk in order.chunks(self.group_size) {
+                let mut members = chunk.to_vec();
+                let fillers = order
+                    .iter()
+                    .copied()
+                    .chain(0..population_size)
+                    .filter(|idx| !chunk.contains(idx));
+
+                for idx in fillers {
+                    if members.len() >= self.group_size {
+                        break;
+                    }
+
+                    if !members.contains(&idx) {
+                        members.push(idx);
+                    }
+                }
+
+                groups.push((members, chunk.len()));
+            }
+        }
+
+        groups
+    }
+
+    /// Combine the scores an individual got in its groups into its score.
+    pub fn combine(&self, scores: &[Score]) -> Score {
+        if self.aggregate == EpisodeAggregate::Mean {
+            return Score::from_samples(scores);
+        }
+
+        let width = scores
+            .iter()
+            .map(|score| score.values.len())
+            .max()
+            .unwrap_or(0);
+        Score::from_vec(
+            (0..width)
+                .map(|i| {
+                    let values = scores
+                        .iter()
+                        .filter_map(|score| score.values.get(i).copied())
+                        .collect::<Vec<f32>>();
+                    self.aggregate.apply(&values)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T> Clone for GroupEvaluator<T> {
+    fn clone(&self) -> Self {
+        GroupEvaluator {
+            group_fn: Arc::clone(&self.group_fn),
+            group_size: self.group_size,
+            rounds: self.rounds,
+            sampling: self.sampling,
+            aggregate: self.aggregate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_cover_every_unscored_individual_each_round() {
+        let evaluator = GroupEvaluator::new(3, |members: &[i32]| members.to_vec())
+            .rounds(2)
+            .sampling(GroupSampling::Sequential);
+
+        let groups = evaluator.groups(&[1, 2, 3, 4], 6);
+        assert_eq!(
+            groups,
+            vec![
+                (vec![1, 2, 3], 3),
+                (vec![4, 1, 2], 1),
+                (vec![2, 3, 4], 3),
+                (vec![1, 2, 3], 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_difference_rewards_credit_each_members_contribution() {
+        let team_fn = |team: &[i32]| Score::from_int(team.iter().sum::<i32>() * 2);
+
+        let shared = CreditAssignment::Shared.assign(&[1, 0, 3], team_fn);
+        assert!(shared.iter().all(|score| score.as_f32() == 8.0));
+
+        let difference = CreditAssignment::Difference.assign(&[1, 0, 3], team_fn);
+        let values = difference
+            .iter()
+            .map(|score| score.as_f32())
+            .collect::<Vec<f32>>();
+        assert_eq!(values, vec![2.0, 0.0, 6.0]);
+        assert_eq!(
+            leave_one_out(&[1, 2, 3]),
+            vec![vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+    }
+
+    #[test]
+    fn test_combine_aggregates_every_value() {
+        let scores = vec![
+            Score::from_vec(vec![1.0, 4.0]),
+            Score::from_vec(vec![3.0, 2.0]),
+        ];
+
+        let mean = GroupEvaluator::new(2, |members: &[i32]| members.to_vec()).combine(&scores);
+        assert_eq!(mean.values, vec![2.0, 3.0]);
+        assert_eq!(mean.stats().unwrap().samples, 2);
+
+        let max = GroupEvaluator::new(2, |members: &[i32]| members.to_vec())
+            .aggregate(EpisodeAggregate::Max)
+            .combine(&scores);
+        assert_eq!(max.values, vec![3.0, 4.0]);
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+/// The result of a single game between an individual and a champion from the `HallOfFame`,
+/// from the point of view of the individual.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    /// Points awarded for the outcome: 1 for a win, 0.5 for a draw and 0 for a loss.
+    pub fn points(&self) -> f32 {
+        match self {
+            Outcome::Win => 1.0,
+            Outcome::Draw => 0.5,
+            Outcome::Loss => 0.0,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Record {
+    points: f32,
+    games: usize,
+}
+
+/// A `HallOfFame` is an archive of past champions used to evaluate individuals in competitive
+/// domains (games, adversarial problems) where fitness is relative to an opponent. Evaluating
+/// against a fixed set of past champions instead of only the current population keeps the
+/// search from cycling between strategies that beat each other.
+///
+/// The `HallOfFame` is cheap to clone and all clones share the same archive, so the usual pattern
+/// is to give one clone to the engine (which adds the champion of every generation to the archive)
+/// and move another into the fitness function.
+///
+/// The opponents used for evaluation are always the most recent `sample_size` champions, so every
+/// individual within a generation is evaluated against exactly the same opponents.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let hall_of_fame = HallOfFame::new(5);
+/// let archive = hall_of_fame.clone();
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 1, 0, 100))
+///     .hall_of_fame(hall_of_fame)
+///     .fitness_fn(move |player: Vec<Vec<i32>>| {
+///         archive.evaluate(&player, |me, champion| match me[0][0].cmp(&champion[0][0]) {
+///             std::cmp::Ordering::Greater => Outcome::Win,
+///             std::cmp::Ordering::Less => Outcome::Loss,
+///             std::cmp::Ordering::Equal => Outcome::Draw,
+///         })
+///     })
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 10);
+/// ```
+pub struct HallOfFame<T> {
+    champions: Arc<RwLock<Vec<T>>>,
+    record: Arc<Mutex<Record>>,
+    sample_size: usize,
+    capacity: usize,
+    interval: usize,
+}
+
+impl<T: Clone> HallOfFame<T> {
+    /// Create a new `HallOfFame` where individuals are evaluated against the `sample_size`
+    /// most recent champions.
+    ///
+    /// Defaults:
+    /// * capacity: 100 - the maximum number of champions kept in the archive
+    /// * interval: 1 - a champion is added every `interval` generations
+    pub fn new(sample_size: usize) -> Self {
+        if sample_size < 1 {
+            panic!("sample_size must be greater than 0");
+        }
+
+        HallOfFame {
+            champions: Arc::new(RwLock::new(Vec::new())),
+            record: Arc::new(Mutex::new(Record::default())),
+            sample_size,
+            capacity: 100,
+            interval: 1,
+        }
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        if capacity < 1 {
+            panic!("capacity must be greater than 0");
+        }
+
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn interval(mut self, interval: usize) -> Self {
+        if interval < 1 {
+            panic!("interval must be greater than 0");
+        }
+
+        self.interval = interval;
+        self
+    }
+
+    /// Add a champion at the given generation. Only generations that are a multiple of the
+    /// interval are archived. When the archive is full the oldest champion is removed.
+    pub fn add(&self, champion: T, generation: usize) {
+        if !generation.is_multiple_of(self.interval) {
+            return;
+        }
+
+        let mut champions = self.champions.write().unwrap();
+        champions.push(champion);
+
+        if champions.len() > self.capacity {
+            let overflow = champions.len() - self.capacity;
+            champions.drain(0..overflow);
+        }
+    }
+
+    /// The champions the next evaluation will play against.
+    pub fn opponents(&self) -> Vec<T> {
+        let champions = self.champions.read().unwrap();
+        let start = champions.len().saturating_sub(self.sample_size);
+        champions[start..].to_vec()
+    }
+
+    /// Play the individual against the sampled champions and return its win rate - the points
+    /// from each game (see `Outcome::points`) divided by the number of games. If the archive is
+    /// still empty the individual is given a neutral win rate of 0.5.
+    pub fn evaluate<F>(&self, individual: &T, play: F) -> f32
+    where
+        F: Fn(&T, &T) -> Outcome,
+    {
+        let opponents = self.opponents();
+        if opponents.is_empty() {
+            return 0.5;
+        }
+
+        let points = opponents
+            .iter()
+            .map(|champion| play(individual, champion).points())
+            .sum::<f32>();
+
+        let mut record = self.record.lock().unwrap();
+        record.points += points;
+        record.games += opponents.len();
+
+        points / opponents.len() as f32
+    }
+
+    /// The win rate across every game played since the last call to `take_win_rate`, resetting
+    /// the record. Returns `None` if no games were played.
+    pub fn take_win_rate(&self) -> Option<f32> {
+        let mut record = self.record.lock().unwrap();
+        let result = match record.games {
+            0 => None,
+            games => Some(record.points / games as f32),
+        };
+
+        *record = Record::default();
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.champions.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for HallOfFame<T> {
+    fn clone(&self) -> Self {
+        HallOfFame {
+            champions: Arc::clone(&self.champions),
+            record: Arc::clone(&self.record),
+            sample_size: self.sample_size,
+            capacity: self.capacity,
+            interval: self.interval,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(a: &i32, b: &i32) -> Outcome {
+        match a.cmp(b) {
+            std::cmp::Ordering::Greater => Outcome::Win,
+            std::cmp::Ordering::Less => Outcome::Loss,
+            std::cmp::Ordering::Equal => Outcome::Draw,
+        }
+    }
+
+    #[test]
+    fn test_hall_of_fame_evaluates_against_recent_champions() {
+        let hall_of_fame = HallOfFame::new(2).capacity(3);
+
+        assert_eq!(hall_of_fame.evaluate(&1, play), 0.5);
+
+        for (generation, champion) in [1, 5, 3, 7].into_iter().enumerate() {
+            hall_of_fame.add(champion, generation);
+        }
+
+        assert_eq!(hall_of_fame.len(), 3);
+        assert_eq!(hall_of_fame.opponents(), vec![3, 7]);
+        assert_eq!(hall_of_fame.evaluate(&5, play), 0.5);
+        assert_eq!(hall_of_fame.evaluate(&7, play), 0.75);
+        assert_eq!(hall_of_fame.take_win_rate(), Some(0.625));
+        assert_eq!(hall_of_fame.take_win_rate(), None);
+    }
+}
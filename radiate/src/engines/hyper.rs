@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::codexes::Codex;
+use super::{Chromosome, FloatChromosome, FloatGene, GeneticEngine, GeneticEngineParams, Genotype};
+use crate::{BoundGene, Gene};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Parameter {
+    Float(f32, f32),
+    Int(i32, i32),
+    Choice(usize),
+}
+
+/// The parameters a `HyperHeuristic` tunes, along with the range each one can take. A `ParameterSpace`
+/// is a `Codex` - every parameter is a gene between 0 and 1 that is decoded into its own range, so the
+/// outer engine can use any float alterers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParameterSpace {
+    parameters: Vec<(&'static str, Parameter)>,
+}
+
+impl ParameterSpace {
+    pub fn new() -> Self {
+        ParameterSpace::default()
+    }
+
+    /// Add a float parameter between `min` and `max`, e.g. an alterer rate.
+    pub fn float(self, name: &'static str, min: f32, max: f32) -> Self {
+        if min > max {
+            panic!(
+                "min must be less than or equal to max for parameter {}",
+                name
+            );
+        }
+
+        self.add(name, Parameter::Float(min, max))
+    }
+
+    /// Add an integer parameter between `min` and `max` (inclusive), e.g. the population size.
+    pub fn int(self, name: &'static str, min: i32, max: i32) -> Self {
+        if min > max {
+            panic!(
+                "min must be less than or equal to max for parameter {}",
+                name
+            );
+        }
+
+        self.add(name, Parameter::Int(min, max))
+    }
+
+    /// Add a categorical parameter with `options` options, e.g. which selector to use. It decodes to
+    /// the index of the chosen option.
+    pub fn choice(self, name: &'static str, options: usize) -> Self {
+        if options == 0 {
+            panic!("choice parameter {} must have at least one option", name);
+        }
+
+        self.add(name, Parameter::Choice(options))
+    }
+
+    pub fn len(&self) -> usize {
+        self.parameters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    fn add(mut self, name: &'static str, parameter: Parameter) -> Self {
+        if self
+            .parameters
+            .iter()
+            .any(|(existing, _)| *existing == name)
+        {
+            panic!("Duplicate parameter name: {}", name);
+        }
+
+        self.parameters.push((name, parameter));
+        self
+    }
+}
+
+impl Codex<FloatChromosome, Configuration> for ParameterSpace {
+    fn encode(&self) -> Genotype<FloatChromosome> {
+        Genotype {
+            chromosomes: vec![FloatChromosome {
+                genes: (0..self.parameters.len())
+                    .map(|_| FloatGene::new(0.0, 1.0).with_bounds(0.0, 1.0))
+                    .collect(),
+            }],
+        }
+    }
+
+    fn decode(&self, genotype: &Genotype<FloatChromosome>) -> Configuration {
+        let values = self
+            .parameters
+            .iter()
+            .zip(genotype[0].genes.iter())
+            .map(|((name, parameter), gene)| {
+                let unit = gene.allele().clamp(0.0, 1.0);
+                let value = match parameter {
+                    Parameter::Float(min, max) => min + unit * (max - min),
+                    Parameter::Int(min, max) => (*min as f32 + unit * (*max - *min) as f32).round(),
+                    Parameter::Choice(options) => {
+                        ((unit * *options as f32) as usize).min(options - 1) as f32
+                    }
+                };
+
+                (*name, value)
+            })
+            .collect();
+
+        Configuration { values }
+    }
+}
+
+/// A point in a `ParameterSpace` - the values a `HyperHeuristic` picked for each parameter.
+/// Getters panic if the parameter doesn't exist.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Configuration {
+    values: BTreeMap<&'static str, f32>,
+}
+
+impl Configuration {
+    pub fn float(&self, name: &str) -> f32 {
+        match self.values.get(name) {
+            Some(value) => *value,
+            None => panic!("Unknown parameter: {}", name),
+        }
+    }
+
+    pub fn int(&self, name: &str) -> i32 {
+        self.float(name) as i32
+    }
+
+    /// The index of the chosen option of a `choice` parameter.
+    pub fn choice(&self, name: &str) -> usize {
+        self.float(name) as usize
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&&'static str, &f32)> {
+        self.values.iter()
+    }
+}
+
+/// Meta-evolution of engine configurations. A `HyperHeuristic` creates an outer engine whose individuals
+/// are `Configuration`s drawn from a `ParameterSpace`. Each configuration is scored by building an inner
+/// engine from it with the given factory, running it for a short budget of generations, and taking the
+/// inner engine's best score (averaged over a number of trials, since a single short run is noisy).
+///
+/// The outer engine is returned as `GeneticEngineParams`, so it's configured like any other engine -
+/// in particular it should optimize in the same direction as the inner engines.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let space = ParameterSpace::new()
+///     .float("mutation_rate", 0.001, 0.5)
+///     .int("population_size", 10, 50)
+///     .choice("selector", 2);
+///
+/// let engine = HyperHeuristic::new(space, |config: &Configuration| {
+///     let params = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+///         .minimizing()
+///         .population_size(config.int("population_size") as usize)
+///         .alter(alters![UniformMutator::new(config.float("mutation_rate"))])
+///         .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>());
+///
+///     match config.choice("selector") {
+///         0 => params.offspring_selector(TournamentSelector::new(3)).build(),
+///         _ => params.offspring_selector(RouletteSelector::new()).build(),
+///     }
+/// })
+/// .budget(5)
+/// .into_params()
+/// .minimizing()
+/// .population_size(10)
+/// .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 2);
+/// let best = result.best;
+/// assert!(best.choice("selector") < 2);
+/// assert!((10..=50).contains(&best.int("population_size")));
+/// ```
+pub struct HyperHeuristic<C, T, F>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+    F: Fn(&Configuration) -> GeneticEngine<C, T> + Send + Sync + 'static,
+{
+    space: ParameterSpace,
+    factory: Arc<F>,
+    budget: i32,
+    trials: usize,
+}
+
+impl<C, T, F> HyperHeuristic<C, T, F>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+    F: Fn(&Configuration) -> GeneticEngine<C, T> + Send + Sync + 'static,
+{
+    /// Create a new `HyperHeuristic` over `space`, building inner engines with `factory`. By default
+    /// each configuration is scored with a single run of 10 generations. Panics if `space` is empty.
+    pub fn new(space: ParameterSpace, factory: F) -> Self {
+        if space.is_empty() {
+            panic!("ParameterSpace must have at least one parameter");
+        }
+
+        HyperHeuristic {
+            space,
+            factory: Arc::new(factory),
+            budget: 10,
+            trials: 1,
+        }
+    }
+
+    /// The number of generations each inner engine runs for. Panics if `generations` is 0.
+    pub fn budget(mut self, generations: i32) -> Self {
+        if generations <= 0 {
+            panic!("budget must be greater than 0");
+        }
+
+        self.budget = generations;
+        self
+    }
+
+    /// The number of inner runs averaged to score a configuration. Panics if `trials` is 0.
+    pub fn trials(mut self, trials: usize) -> Self {
+        if trials == 0 {
+            panic!("trials must be greater than 0");
+        }
+
+        self.trials = trials;
+        self
+    }
+
+    /// The outer engine's parameters, with the codex and fitness function already set.
+    pub fn into_params(self) -> GeneticEngineParams<FloatChromosome, Configuration> {
+        let factory = self.factory;
+        let budget = self.budget;
+        let trials = self.trials;
+
+        GeneticEngineParams::new()
+            .codex(self.space)
+            .fitness_fn(move |config: Configuration| {
+                let total = (0..trials)
+                    .map(|_| {
+                        let engine = factory(&config);
+                        engine.run(|ctx| ctx.index >= budget).score().as_f32()
+                    })
+                    .sum::<f32>();
+
+                total / trials as f32
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter_space_decodes_into_ranges() {
+        let space = ParameterSpace::new()
+            .float("rate", 0.1, 0.5)
+            .int("size", 10, 20)
+            .choice("selector", 3);
+
+        let low = space.decode(&Genotype {
+            chromosomes: vec![FloatChromosome::from(&[0.0, 0.0, 0.0][..])],
+        });
+        let high = space.decode(&Genotype {
+            chromosomes: vec![FloatChromosome::from(&[1.0, 1.0, 1.0][..])],
+        });
+
+        assert_eq!(low.float("rate"), 0.1);
+        assert_eq!(low.int("size"), 10);
+        assert_eq!(low.choice("selector"), 0);
+        assert_eq!(high.float("rate"), 0.5);
+        assert_eq!(high.int("size"), 20);
+        assert_eq!(high.choice("selector"), 2);
+        assert_eq!(space.encode()[0].len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parameter_space_rejects_duplicate_names() {
+        ParameterSpace::new()
+            .float("rate", 0.0, 1.0)
+            .int("rate", 0, 1);
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::VecDeque;
+use std::io::Write;
+#[cfg(feature = "wire")]
+use std::path::PathBuf;
+
+#[cfg(feature = "wire")]
+use super::{metric_names, wire::WireAllele, Checkpoint, Gene};
+use super::{Chromosome, EngineContext, GeneticEngine};
+
+/// An iterator over the generations of a `GeneticEngine`, created with `GeneticEngine::iter`.
+/// Every item is a snapshot of the `EngineContext` after a generation.
+///
+/// Skipping generations with `nth` (and so `sample_every`) runs the skipped generations without
+/// cloning their context.
+pub struct EngineIterator<'a, C, T>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    engine: &'a GeneticEngine<C, T>,
+    context: Option<EngineContext<C, T>>,
+}
+
+impl<'a, C, T> EngineIterator<'a, C, T>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    pub fn new(engine: &'a GeneticEngine<C, T>) -> Self {
+        EngineIterator {
+            engine,
+            context: None,
+        }
+    }
+
+    fn advance(&mut self) -> &EngineContext<C, T> {
+        let engine = self.engine;
+        let context = self.context.get_or_insert_with(|| engine.start());
+        engine.step(context);
+        context
+    }
+}
+
+impl<C, T> Iterator for EngineIterator<'_, C, T>
+where
+    C: Chromosome + 'static,
+    T: Clone + Send + 'static,
+{
+    type Item = EngineContext<C, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut context = self.advance().clone();
+        context.timer.stop();
+        Some(context)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            self.advance();
+        }
+
+        self.next()
+    }
+}
+
+/// Adapters for iterators over `EngineContext`s, e.g. `GeneticEngine::iter`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine
+///     .iter()
+///     .take(500)
+///     .converged(25, 0.0)
+///     .sample_every(5)
+///     .last()
+///     .unwrap();
+///
+/// assert!(result.index <= 500);
+/// ```
+pub trait EngineIteratorExt<C, T>: Iterator<Item = EngineContext<C, T>> + Sized
+where
+    C: Chromosome,
+{
+    /// Stop once the best score has changed by no more than `epsilon` over the last `window`
+    /// generations. The generation that converged is the last one yielded. Panics if `window` is 0.
+    fn converged(self, window: usize, epsilon: f32) -> Converged<Self> {
+        if window == 0 {
+            panic!("window must be greater than 0");
+        }
+
+        Converged {
+            iter: self,
+            window,
+            epsilon,
+            scores: VecDeque::with_capacity(window + 1),
+            done: false,
+        }
+    }
+
+    /// Write a `Checkpoint` of every `n`th generation to `path` (replacing the previous one) as it
+    /// passes through, so the run can be resumed with `GeneticEngineParams::resume_from`. The iterator
+    /// doesn't see the engine's random number generator, so unlike `GeneticEngineParams::checkpoint_every`
+    /// the checkpoint doesn't store it. A checkpoint that can't be written doesn't stop the iterator - the
+    /// previous one is kept and the failure is counted in the metrics of the generation's context (see
+    /// `metric_names::WRITE_ERRORS`). Panics if `n` is 0.
+    #[cfg(feature = "wire")]
+    fn checkpoint_every(self, n: usize, path: impl Into<PathBuf>) -> CheckpointEvery<Self>
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        if n == 0 {
+            panic!("n must be greater than 0");
+        }
+
+        CheckpointEvery {
+            iter: self,
+            n,
+            path: path.into(),
+        }
+    }
+
+    /// Yield only every `n`th generation. Panics if `n` is 0.
+    fn sample_every(self, n: usize) -> SampleEvery<Self> {
+        if n == 0 {
+            panic!("n must be greater than 0");
+        }
+
+        SampleEvery { iter: self, n }
+    }
+
+    /// Render a progress line to stderr for every generation - a progress bar if the number of
+    /// generations is known (e.g. after `take`), otherwise the generation count. Apply it last
+    /// so the total accounts for every other adapter.
+    fn with_progress(self) -> WithProgress<Self> {
+        let total = self.size_hint().1;
+        WithProgress {
+            iter: self,
+            total,
+            count: 0,
+        }
+    }
+}
+
+impl<C, T, I> EngineIteratorExt<C, T> for I
+where
+    C: Chromosome,
+    I: Iterator<Item = EngineContext<C, T>>,
+{
+}
+
+pub struct Converged<I> {
+    iter: I,
+    window: usize,
+    epsilon: f32,
+    scores: VecDeque<f32>,
+    done: bool,
+}
+
+impl<C, T, I> Iterator for Converged<I>
+where
+    C: Chromosome,
+    I: Iterator<Item = EngineContext<C, T>>,
+{
+    type Item = EngineContext<C, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let context = self.iter.next()?;
+        self.scores.push_back(context.score().as_f32());
+        if self.scores.len() > self.window + 1 {
+            self.scores.pop_front();
+        }
+
+        if self.scores.len() == self.window + 1 {
+            let first = self.scores.front().unwrap();
+            let last = self.scores.back().unwrap();
+            self.done = (last - first).abs() <= self.epsilon;
+        }
+
+        Some(context)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.done {
+            true => (0, Some(0)),
+            false => (0, self.iter.size_hint().1),
+        }
+    }
+}
+
+#[cfg(feature = "wire")]
+pub struct CheckpointEvery<I> {
+    iter: I,
+    n: usize,
+    path: PathBuf,
+}
+
+#[cfg(feature = "wire")]
+impl<C, T, I> Iterator for CheckpointEvery<I>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+    I: Iterator<Item = EngineContext<C, T>>,
+{
+    type Item = EngineContext<C, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut context = self.iter.next()?;
+
+        if (context.index.max(0) as usize).is_multiple_of(self.n)
+            && Checkpoint::from(&context).write(&self.path).is_err()
+        {
+            context
+                .metrics
+                .upsert_value(metric_names::WRITE_ERRORS, 1.0);
+        }
+
+        Some(context)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct SampleEvery<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<C, T, I> Iterator for SampleEvery<I>
+where
+    C: Chromosome,
+    I: Iterator<Item = EngineContext<C, T>>,
+{
+    type Item = EngineContext<C, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.nth(self.n - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower / self.n, upper.map(|upper| upper / self.n))
+    }
+}
+
+pub struct WithProgress<I> {
+    iter: I,
+    total: Option<usize>,
+    count: usize,
+}
+
+impl<C, T, I> Iterator for WithProgress<I>
+where
+    C: Chromosome,
+    I: Iterator<Item = EngineContext<C, T>>,
+{
+    type Item = EngineContext<C, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(context) = self.iter.next() else {
+            if self.count > 0 {
+                eprintln!();
+            }
+
+            return None;
+        };
+
+        self.count += 1;
+        let line = progress_line(self.count, self.total, &context);
+        let mut stderr = std::io::stderr();
+        let _ = write!(stderr, "\r{}", line);
+        let _ = stderr.flush();
+
+        Some(context)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+fn progress_line<C: Chromosome, T>(
+    count: usize,
+    total: Option<usize>,
+    context: &EngineContext<C, T>,
+) -> String {
+    const WIDTH: usize = 30;
+
+    let stats = format!(
+        "generation {} | score {:.4} | {:.2}s",
+        context.index,
+        context.score().as_f32(),
+        context.seconds()
+    );
+
+    match total {
+        Some(total) if total > 0 => {
+            let filled = (count.min(total) * WIDTH) / total;
+            format!(
+                "[{}{}] {}/{} | {}",
+                "#".repeat(filled),
+                "-".repeat(WIDTH - filled),
+                count.min(total),
+                total,
+                stats
+            )
+        }
+        _ => stats,
+    }
+}
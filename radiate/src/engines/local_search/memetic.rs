@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use super::LocalSearch;
+use crate::Chromosome;
+
+/// What a memetic algorithm keeps of a local search's refinement (see `Memetic`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Inheritance {
+    /// The refined genotype and its score replace the individual's, so improvements are passed on to
+    /// its offspring.
+    Lamarckian,
+    /// Only the refined score is kept - the individual is selected for how good it can become, but its
+    /// offspring inherit the unrefined genotype. Keeps the diversity Lamarckian learning tends to lose.
+    Baldwinian,
+}
+
+/// When a `Memetic` local search refines the offspring of a generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemeticStage {
+    /// Once the offspring have been evaluated with the rest of the population.
+    AfterEvaluation,
+    /// Right after the offspring are altered - each one is evaluated by the search itself, before the
+    /// offspring join the population, are repaired and filtered.
+    AfterAlteration,
+}
+
+/// A memetic algorithm - a `GeneticEngine` whose offspring are refined by a `LocalSearch`, e.g. evolved
+/// neural network weights polished with a few hill-climbing steps. Every generation, each offspring is
+/// refined with probability `rate`. Only the first value of a score is considered by the search.
+///
+/// Set with `GeneticEngineParams::memetic`. The refinements run in parallel on the engine's thread pool,
+/// and the number of evaluations they took is recorded in the metrics. Default is `Lamarckian` refinement
+/// of every offspring after evaluation.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let search = TrajectorySearch::new(UniformMutator::new(0.2), Acceptance::HillClimbing).iterations(10);
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .population_size(20)
+///     .memetic(Memetic::new(search).rate(0.5))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 10);
+///
+/// assert!(result.score().as_i32() < 100);
+/// assert!(result.metrics.get("Local Search").is_some());
+/// ```
+pub struct Memetic<C: Chromosome, T> {
+    search: Arc<dyn LocalSearch<C, T> + Send + Sync>,
+    inheritance: Inheritance,
+    stage: MemeticStage,
+    rate: f32,
+}
+
+impl<C: Chromosome, T> Memetic<C, T> {
+    pub fn new<S>(search: S) -> Self
+    where
+        S: LocalSearch<C, T> + Send + Sync + 'static,
+    {
+        Memetic {
+            search: Arc::new(search),
+            inheritance: Inheritance::Lamarckian,
+            stage: MemeticStage::AfterEvaluation,
+            rate: 1.0,
+        }
+    }
+
+    /// Default is Lamarckian.
+    pub fn lamarckian(mut self) -> Self {
+        self.inheritance = Inheritance::Lamarckian;
+        self
+    }
+
+    pub fn baldwinian(mut self) -> Self {
+        self.inheritance = Inheritance::Baldwinian;
+        self
+    }
+
+    /// Set when the offspring are refined. Default is `MemeticStage::AfterEvaluation`.
+    pub fn stage(mut self, stage: MemeticStage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    /// Set the probability that an offspring is refined. Default is 1.
+    pub fn rate(mut self, rate: f32) -> Self {
+        if !(0.0..=1.0).contains(&rate) {
+            panic!("rate must be between 0 and 1");
+        }
+
+        self.rate = rate;
+        self
+    }
+
+    pub fn search(&self) -> Arc<dyn LocalSearch<C, T> + Send + Sync> {
+        Arc::clone(&self.search)
+    }
+
+    pub fn inheritance(&self) -> Inheritance {
+        self.inheritance
+    }
+
+    pub fn memetic_stage(&self) -> MemeticStage {
+        self.stage
+    }
+
+    pub fn refinement_rate(&self) -> f32 {
+        self.rate
+    }
+}
@@ -0,0 +1,9 @@
+pub mod memetic;
+pub mod search;
+pub mod tabu;
+pub mod trajectory;
+
+pub use memetic::*;
+pub use search::*;
+pub use tabu::*;
+pub use trajectory::*;
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::engines::problem::EngineProblem;
+use crate::{Chromosome, Codex, Genotype, Optimize, Problem, Score};
+
+/// Refines a single scored individual - the building block of single-solution searches and of the local
+/// search step of memetic algorithms. Only the first value of a score is considered.
+///
+/// Every local search also runs on its own, as a baseline, with `run` and `run_codex`.
+pub trait LocalSearch<C: Chromosome, T> {
+    /// Search the neighbourhood of `genotype`, whose score is `score`, for a better genotype. Returns the
+    /// best genotype found, which is `genotype` itself if nothing better was found.
+    fn refine(
+        &self,
+        problem: &dyn Problem<C, T>,
+        optimize: Optimize,
+        genotype: Genotype<C>,
+        score: Score,
+    ) -> Refinement<C>;
+
+    /// Search from a new genotype of the problem, returning the best solution found.
+    fn run(&self, problem: &dyn Problem<C, T>, optimize: Optimize) -> LocalSearchResult<C, T> {
+        let genotype = problem.encode();
+        let score = problem.eval(&genotype);
+        let refinement = self.refine(problem, optimize, genotype, score);
+
+        LocalSearchResult {
+            value: problem.decode(&refinement.genotype),
+            genotype: refinement.genotype,
+            score: refinement.score,
+            evaluations: refinement.evaluations + 1,
+        }
+    }
+
+    /// Search the genotypes of a codex, scored with a fitness function like `GeneticEngineParams::fitness_fn`.
+    fn run_codex<S>(
+        &self,
+        codex: impl Codex<C, T> + 'static,
+        fitness_fn: impl Fn(T) -> S + Send + Sync + 'static,
+        optimize: Optimize,
+    ) -> LocalSearchResult<C, T>
+    where
+        Self: Sized,
+        T: Clone,
+        S: Into<Score>,
+    {
+        let problem = EngineProblem {
+            codex: Arc::new(Box::new(codex)),
+            fitness_fn: Arc::new(move |value| fitness_fn(value).into()),
+            parts: Vec::new(),
+        };
+
+        self.run(&problem, optimize)
+    }
+}
+
+/// The best genotype a `LocalSearch` found, its score and the number of evaluations it took.
+#[derive(Clone, Debug)]
+pub struct Refinement<C: Chromosome> {
+    pub genotype: Genotype<C>,
+    pub score: Score,
+    pub evaluations: usize,
+}
+
+/// The best solution a standalone `LocalSearch` found, decoded, with the number of evaluations the
+/// search took.
+#[derive(Clone, Debug)]
+pub struct LocalSearchResult<C: Chromosome, T> {
+    pub value: T,
+    pub genotype: Genotype<C>,
+    pub score: Score,
+    pub evaluations: usize,
+}
+
+/// How much worse `a` is than `b` - negative when it's better.
+pub(crate) fn worse_by(optimize: Optimize, a: f32, b: f32) -> f32 {
+    match optimize {
+        Optimize::Minimize => a - b,
+        Optimize::Maximize => b - a,
+    }
+}
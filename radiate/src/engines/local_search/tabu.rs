@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{worse_by, LocalSearch, Refinement};
+use crate::{random_provider, Genotype, Optimize, PermutationChromosome, Problem, Score};
+
+/// A neighbourhood move of a permutation, identified by a pair of positions. Problems plug their own
+/// moves into a `TabuSearch` by implementing it.
+pub trait PermutationMove: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// The moves of a permutation of `len` elements. Defaults to every pair of positions `i < j`.
+    fn moves(&self, len: usize) -> Vec<(usize, usize)> {
+        (0..len)
+            .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+            .collect()
+    }
+
+    /// Apply the move at positions `i` and `j` to the permutation's order - the indexes of its alleles.
+    fn apply(&self, order: &mut [usize], i: usize, j: usize);
+}
+
+/// Swap the elements at the two positions.
+#[derive(Clone, Debug, Default)]
+pub struct SwapMove;
+
+impl PermutationMove for SwapMove {
+    fn name(&self) -> &'static str {
+        "swap"
+    }
+
+    fn apply(&self, order: &mut [usize], i: usize, j: usize) {
+        order.swap(i, j);
+    }
+}
+
+/// Reverse the elements between the two positions, inclusive - the 2-opt move of a tour, which replaces
+/// the two edges at the ends of the reversed run.
+#[derive(Clone, Debug, Default)]
+pub struct TwoOptMove;
+
+impl PermutationMove for TwoOptMove {
+    fn name(&self) -> &'static str {
+        "two_opt"
+    }
+
+    fn apply(&self, order: &mut [usize], i: usize, j: usize) {
+        order[i..=j].reverse();
+    }
+}
+
+/// A tabu search over the `PermutationChromosome`s of routing and scheduling problems. Every step
+/// evaluates the neighbourhood of the current permutation - every move of every `PermutationMove` - and
+/// moves to its best neighbour, even if it's worse. A move is remembered by the pair of elements it moved,
+/// and moving the same pair again is tabu for the next `tenure` steps, unless it finds a permutation
+/// better than the best found so far.
+///
+/// Large neighbourhoods can be sampled with `candidates`, evaluating that many random moves per step.
+/// A `TabuSearch` runs on its own (see `LocalSearch::run`) or as the `LocalSearch` that refines
+/// individuals of a population.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // visit the points of a line in order
+/// let search = TabuSearch::new().tenure(5).iterations(20);
+///
+/// let result = search.run_codex(
+///     PermutationCodex::new((0..8).collect::<Vec<i32>>()),
+///     |order: Vec<i32>| order.windows(2).map(|pair| (pair[0] - pair[1]).abs()).sum::<i32>(),
+///     Optimize::Minimize,
+/// );
+///
+/// assert_eq!(result.score.as_i32(), 7);
+/// ```
+pub struct TabuSearch {
+    moves: Vec<Arc<dyn PermutationMove>>,
+    tenure: usize,
+    iterations: usize,
+    candidates: Option<usize>,
+}
+
+impl TabuSearch {
+    /// Create a tabu search with swap and 2-opt moves, a tenure of 10 and 100 steps.
+    pub fn new() -> Self {
+        TabuSearch {
+            moves: vec![Arc::new(SwapMove), Arc::new(TwoOptMove)],
+            tenure: 10,
+            iterations: 100,
+            candidates: None,
+        }
+    }
+
+    /// Set the moves that make up the neighbourhood. Default is `SwapMove` and `TwoOptMove`.
+    pub fn moves(mut self, moves: Vec<Arc<dyn PermutationMove>>) -> Self {
+        if moves.is_empty() {
+            panic!("moves must not be empty");
+        }
+
+        self.moves = moves;
+        self
+    }
+
+    /// Set the number of steps a move stays tabu. Default is 10.
+    pub fn tenure(mut self, tenure: usize) -> Self {
+        self.tenure = tenure;
+        self
+    }
+
+    /// Set the number of steps per search. Default is 100.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        if iterations < 1 {
+            panic!("iterations must be greater than 0");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    /// Evaluate at most this many random moves per step instead of the whole neighbourhood. Default is
+    /// the whole neighbourhood.
+    pub fn candidates(mut self, candidates: usize) -> Self {
+        if candidates < 1 {
+            panic!("candidates must be greater than 0");
+        }
+
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// The moves of a step - the chromosome, the move and its positions.
+    fn neighbourhood<A: PartialEq + Clone>(
+        &self,
+        genotype: &Genotype<PermutationChromosome<A>>,
+    ) -> Vec<(usize, usize, (usize, usize))> {
+        let mut moves = Vec::new();
+        for (chromosome, permutation) in genotype.iter().enumerate() {
+            for (kind, neighbourhood_move) in self.moves.iter().enumerate() {
+                for positions in neighbourhood_move.moves(permutation.genes.len()) {
+                    moves.push((chromosome, kind, positions));
+                }
+            }
+        }
+
+        if let Some(candidates) = self.candidates {
+            if candidates < moves.len() {
+                random_provider::shuffle(&mut moves);
+                moves.truncate(candidates);
+            }
+        }
+
+        moves
+    }
+}
+
+impl Default for TabuSearch {
+    fn default() -> Self {
+        TabuSearch::new()
+    }
+}
+
+impl<A: PartialEq + Clone, T> LocalSearch<PermutationChromosome<A>, T> for TabuSearch {
+    fn refine(
+        &self,
+        problem: &dyn Problem<PermutationChromosome<A>, T>,
+        optimize: Optimize,
+        genotype: Genotype<PermutationChromosome<A>>,
+        score: Score,
+    ) -> Refinement<PermutationChromosome<A>> {
+        // (chromosome, move, first element, second element) -> the step the move stops being tabu
+        let mut tabu = HashMap::<(usize, usize, usize, usize), usize>::new();
+        let mut current = genotype.clone();
+        let mut best = Refinement {
+            genotype,
+            score,
+            evaluations: 0,
+        };
+
+        for step in 0..self.iterations {
+            let mut chosen: Option<(_, Genotype<PermutationChromosome<A>>, Score)> = None;
+            for (chromosome, kind, (i, j)) in self.neighbourhood(&current) {
+                let mut order = current[chromosome]
+                    .genes
+                    .iter()
+                    .map(|gene| gene.index)
+                    .collect::<Vec<usize>>();
+                let key = (
+                    chromosome,
+                    kind,
+                    order[i].min(order[j]),
+                    order[i].max(order[j]),
+                );
+
+                self.moves[kind].apply(&mut order, i, j);
+                let mut neighbour = current.clone();
+                for (gene, index) in neighbour[chromosome].genes.iter_mut().zip(order) {
+                    gene.index = index;
+                }
+
+                let neighbour_score = problem.eval(&neighbour);
+                let value = neighbour_score.as_f32();
+                best.evaluations += 1;
+
+                let is_tabu = tabu.get(&key).is_some_and(|until| *until > step);
+                if is_tabu && worse_by(optimize, value, best.score.as_f32()) >= 0.0 {
+                    continue;
+                }
+
+                let is_better = match &chosen {
+                    Some((_, _, chosen_score)) => {
+                        worse_by(optimize, value, chosen_score.as_f32()) < 0.0
+                    }
+                    None => true,
+                };
+
+                if is_better {
+                    chosen = Some((key, neighbour, neighbour_score));
+                }
+            }
+
+            let Some((key, neighbour, neighbour_score)) = chosen else {
+                break;
+            };
+
+            tabu.insert(key, step + 1 + self.tenure);
+            if worse_by(optimize, neighbour_score.as_f32(), best.score.as_f32()) < 0.0 {
+                best.genotype = neighbour.clone();
+                best.score = neighbour_score;
+            }
+
+            current = neighbour;
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PermutationCodex;
+
+    /// The length of a closed tour of points on a circle - shortest when they're visited in order.
+    fn tour_length(order: Vec<usize>) -> f32 {
+        let point = |i: usize| {
+            let angle = i as f32 / order.len() as f32 * std::f32::consts::TAU;
+            (angle.cos(), angle.sin())
+        };
+
+        (0..order.len())
+            .map(|i| {
+                let (a, b) = (point(order[i]), point(order[(i + 1) % order.len()]));
+                ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_tabu_search_finds_the_shortest_tour() {
+        let optimum = tour_length((0..12).collect());
+        let result = TabuSearch::new().tenure(7).iterations(60).run_codex(
+            PermutationCodex::new((0..12).collect::<Vec<usize>>()),
+            tour_length,
+            Optimize::Minimize,
+        );
+
+        assert!((result.score.as_f32() - optimum).abs() < 1e-4);
+        assert_eq!(result.value.len(), 12);
+    }
+
+    #[test]
+    fn test_moves_and_candidates() {
+        let mut order = vec![0, 1, 2, 3, 4];
+        TwoOptMove.apply(&mut order, 1, 3);
+        assert_eq!(order, vec![0, 3, 2, 1, 4]);
+        SwapMove.apply(&mut order, 0, 4);
+        assert_eq!(order, vec![4, 3, 2, 1, 0]);
+        assert_eq!(SwapMove.moves(5).len(), 10);
+
+        // without tabu moves every step evaluates all of its candidates
+        let result = TabuSearch::new()
+            .moves(vec![Arc::new(SwapMove)])
+            .tenure(0)
+            .candidates(3)
+            .iterations(10)
+            .run_codex(
+                PermutationCodex::new((0..6).collect::<Vec<usize>>()),
+                tour_length,
+                Optimize::Minimize,
+            );
+
+        assert_eq!(result.evaluations, 31);
+    }
+}
@@ -0,0 +1,261 @@
+use super::{worse_by, LocalSearch, Refinement};
+use crate::random_provider::RngHandle;
+use crate::{Chromosome, Genotype, Mutate, Optimize, Problem, Score};
+
+/// The number of times a neighbour is mutated before giving up on the mutator changing anything.
+const MAX_NEIGHBOUR_TRIES: usize = 100;
+
+/// When a `TrajectorySearch` moves from the current solution to a worse neighbour. A neighbour at least
+/// as good as the current solution is always accepted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Acceptance {
+    /// Only accept neighbours at least as good as the current solution.
+    HillClimbing,
+    /// Threshold accepting - accept a neighbour at most `threshold` worse than the current solution. The
+    /// threshold is multiplied by `decay` after every step, so the search settles into hill climbing.
+    Threshold { threshold: f32, decay: f32 },
+    /// Record-to-record travel - accept a neighbour at most `deviation` worse than the best solution found.
+    RecordToRecord { deviation: f32 },
+    /// Late acceptance hill climbing - accept a neighbour at least as good as the solution that was current
+    /// `history` steps ago.
+    LateAcceptance { history: usize },
+}
+
+impl Acceptance {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Acceptance::HillClimbing => "hill_climbing",
+            Acceptance::Threshold { .. } => "threshold",
+            Acceptance::RecordToRecord { .. } => "record_to_record",
+            Acceptance::LateAcceptance { .. } => "late_acceptance",
+        }
+    }
+}
+
+/// A single-solution search that walks from one genotype to a neighbour, produced by a `Mutate`
+/// alterer, whenever its `Acceptance` criterion allows. It shares the `Problem` and `Codex`
+/// abstractions with the `GeneticEngine`, so it runs on its own as a baseline (see `LocalSearch::run`)
+/// or as the `LocalSearch` that refines individuals of a population.
+///
+/// Every step evaluates one neighbour. Mutated neighbours are repaired with `Problem::repair`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let search = TrajectorySearch::new(
+///     UniformMutator::new(0.2),
+///     Acceptance::LateAcceptance { history: 20 },
+/// )
+/// .iterations(2000);
+///
+/// let result = search.run_codex(
+///     IntCodex::new(1, 5, 0, 100),
+///     |geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>(),
+///     Optimize::Minimize,
+/// );
+///
+/// assert!(result.score.as_i32() < 100);
+/// assert_eq!(result.evaluations, 2001);
+/// ```
+pub struct TrajectorySearch<C: Chromosome, M: Mutate<C>> {
+    mutator: M,
+    acceptance: Acceptance,
+    iterations: usize,
+    _chromosome: std::marker::PhantomData<C>,
+}
+
+impl<C: Chromosome, M: Mutate<C>> TrajectorySearch<C, M> {
+    /// Create a search that runs for 1000 steps.
+    pub fn new(mutator: M, acceptance: Acceptance) -> Self {
+        match acceptance {
+            Acceptance::Threshold { threshold, decay } => {
+                if threshold < 0.0 {
+                    panic!("threshold must be greater than or equal to 0");
+                }
+
+                if decay <= 0.0 || decay > 1.0 {
+                    panic!("decay must be in (0, 1]");
+                }
+            }
+            Acceptance::RecordToRecord { deviation } if deviation < 0.0 => {
+                panic!("deviation must be greater than or equal to 0");
+            }
+            Acceptance::LateAcceptance { history } if history < 1 => {
+                panic!("history must be greater than 0");
+            }
+            _ => {}
+        }
+
+        TrajectorySearch {
+            mutator,
+            acceptance,
+            iterations: 1000,
+            _chromosome: std::marker::PhantomData,
+        }
+    }
+
+    /// Set the number of steps - neighbours evaluated - per search. Default is 1000.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        if iterations < 1 {
+            panic!("iterations must be greater than 0");
+        }
+
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn acceptance(&self) -> &Acceptance {
+        &self.acceptance
+    }
+
+    fn neighbour<T>(
+        &self,
+        problem: &dyn Problem<C, T>,
+        genotype: &Genotype<C>,
+        rng: &RngHandle,
+    ) -> Genotype<C> {
+        let mut neighbour = genotype.clone();
+        for _ in 0..MAX_NEIGHBOUR_TRIES {
+            if self.mutator.mutate_genotype(&mut neighbour, rng) > 0 {
+                break;
+            }
+        }
+
+        problem.repair(&mut neighbour);
+        neighbour
+    }
+}
+
+impl<C: Chromosome, T, M: Mutate<C>> LocalSearch<C, T> for TrajectorySearch<C, M> {
+    fn refine(
+        &self,
+        problem: &dyn Problem<C, T>,
+        optimize: Optimize,
+        genotype: Genotype<C>,
+        score: Score,
+    ) -> Refinement<C> {
+        let worse_by = |a: f32, b: f32| worse_by(optimize, a, b);
+        // The engine runs the search with its generator scoped on the worker thread.
+        let rng = RngHandle::current();
+
+        let (mut current, mut current_score) = (genotype.clone(), score.as_f32());
+        let mut best = Refinement {
+            genotype,
+            score,
+            evaluations: 0,
+        };
+
+        let mut threshold = match self.acceptance {
+            Acceptance::Threshold { threshold, .. } => threshold,
+            _ => 0.0,
+        };
+        let mut history = match self.acceptance {
+            Acceptance::LateAcceptance { history } => vec![current_score; history],
+            _ => Vec::new(),
+        };
+
+        for step in 0..self.iterations {
+            let candidate = self.neighbour(problem, &current, &rng);
+            let candidate_score = problem.eval(&candidate);
+            let value = candidate_score.as_f32();
+            best.evaluations += 1;
+
+            let accepted = worse_by(value, current_score) <= 0.0
+                || match &self.acceptance {
+                    Acceptance::HillClimbing => false,
+                    Acceptance::Threshold { .. } => worse_by(value, current_score) <= threshold,
+                    Acceptance::RecordToRecord { deviation } => {
+                        worse_by(value, best.score.as_f32()) <= *deviation
+                    }
+                    Acceptance::LateAcceptance { .. } => {
+                        worse_by(value, history[step % history.len()]) <= 0.0
+                    }
+                };
+
+            if accepted {
+                if worse_by(value, best.score.as_f32()) < 0.0 {
+                    best.genotype = candidate.clone();
+                    best.score = candidate_score;
+                }
+
+                current = candidate;
+                current_score = value;
+            }
+
+            if let Acceptance::Threshold { decay, .. } = self.acceptance {
+                threshold *= decay;
+            }
+
+            if !history.is_empty() {
+                let index = step % history.len();
+                history[index] = current_score;
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::problem::EngineProblem;
+    use crate::{FloatChromosome, FloatCodex, GaussianMutator, LocalSearchResult};
+    use std::sync::Arc;
+
+    fn sphere(acceptance: Acceptance) -> LocalSearchResult<FloatChromosome, Vec<Vec<f32>>> {
+        TrajectorySearch::new(GaussianMutator::new(0.5), acceptance)
+            .iterations(3000)
+            .run_codex(
+                FloatCodex::new(1, 4, -5.0, 5.0),
+                |geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>(),
+                Optimize::Minimize,
+            )
+    }
+
+    #[test]
+    fn test_every_acceptance_minimizes_the_sphere() {
+        let acceptances = vec![
+            Acceptance::HillClimbing,
+            Acceptance::Threshold {
+                threshold: 1.0,
+                decay: 0.995,
+            },
+            Acceptance::RecordToRecord { deviation: 0.5 },
+            Acceptance::LateAcceptance { history: 10 },
+        ];
+
+        for acceptance in acceptances {
+            let result = sphere(acceptance.clone());
+            assert_eq!(result.evaluations, 3001);
+            assert!(result.score.as_f32() < 1.0, "{}", acceptance.label());
+        }
+    }
+
+    #[test]
+    fn test_refine_never_returns_a_worse_genotype() {
+        let codex = FloatCodex::new(1, 3, -5.0, 5.0);
+        let problem = EngineProblem {
+            codex: Arc::new(Box::new(codex)),
+            fitness_fn: Arc::new(|geno: Vec<Vec<f32>>| Score::from_f32(geno[0].iter().sum())),
+            parts: Vec::new(),
+        };
+
+        let search = TrajectorySearch::new(
+            GaussianMutator::new(1.0),
+            Acceptance::RecordToRecord { deviation: 10.0 },
+        )
+        .iterations(50);
+
+        for _ in 0..10 {
+            let genotype = problem.encode();
+            let score = problem.eval(&genotype);
+            let refined = search.refine(&problem, Optimize::Maximize, genotype, score.clone());
+
+            assert_eq!(refined.evaluations, 50);
+            assert!(refined.score.as_f32() >= score.as_f32());
+            assert_eq!(problem.eval(&refined.genotype), refined.score);
+        }
+    }
+}
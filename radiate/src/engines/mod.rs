@@ -1,30 +1,197 @@
+pub mod domain;
+pub mod genome;
+
+pub use domain::*;
+pub use genome::*;
+
+#[cfg(feature = "std")]
 pub mod alterers;
+#[cfg(feature = "std")]
+pub mod ant_colony;
+#[cfg(feature = "std")]
+pub mod archive;
+#[cfg(feature = "std")]
+pub mod artifacts;
+#[cfg(feature = "std")]
+pub mod ask_tell;
+#[cfg(feature = "std")]
+pub mod benchmark;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod calibration;
+#[cfg(feature = "std")]
+pub mod checkpoint;
+#[cfg(feature = "std")]
 pub mod codexes;
+#[cfg(feature = "std")]
+pub mod coevolution;
+#[cfg(feature = "std")]
+pub mod constraints;
+#[cfg(feature = "std")]
 pub mod context;
-pub mod domain;
+#[cfg(feature = "std")]
+pub mod control;
+#[cfg(feature = "std")]
+pub mod delta;
+#[cfg(feature = "std")]
+pub mod description;
+#[cfg(feature = "std")]
+pub mod driver;
+#[cfg(feature = "std")]
+pub mod elites;
+#[cfg(feature = "std")]
 pub mod engine;
-pub mod genome;
+#[cfg(feature = "std")]
+pub mod environment;
+#[cfg(feature = "std")]
+pub mod events;
+#[cfg(feature = "std")]
+pub mod fuzzing;
+#[cfg(feature = "std")]
+pub mod games;
+#[cfg(feature = "std")]
+pub mod group;
+#[cfg(feature = "std")]
+pub mod hall_of_fame;
+#[cfg(feature = "std")]
+pub mod hyper;
+#[cfg(feature = "std")]
+pub mod iter;
+#[cfg(feature = "std")]
+pub mod local_search;
+#[cfg(feature = "std")]
 pub mod objectives;
+#[cfg(feature = "std")]
 pub mod params;
-
+#[cfg(feature = "std")]
+pub mod presets;
+#[cfg(feature = "std")]
+pub mod prior;
+#[cfg(feature = "std")]
 pub mod problem;
+#[cfg(feature = "std")]
+pub mod qubo;
+#[cfg(feature = "std")]
+pub mod racing;
+#[cfg(feature = "std")]
+pub mod repairs;
+#[cfg(feature = "std")]
+pub mod restart;
+#[cfg(feature = "wire")]
+pub mod sampling;
+#[cfg(feature = "std")]
+pub mod schedule;
+#[cfg(feature = "std")]
 pub mod selectors;
+#[cfg(feature = "std")]
+pub mod speciation;
+#[cfg(feature = "std")]
 pub mod stats;
+#[cfg(feature = "std")]
+pub mod steady_state;
+#[cfg(all(feature = "std", any(test, feature = "test-util")))]
+pub mod testing;
 
+#[cfg(feature = "std")]
 pub use alterers::*;
+#[cfg(feature = "std")]
+pub use ant_colony::*;
+#[cfg(feature = "std")]
+pub use archive::*;
+#[cfg(feature = "std")]
+pub use artifacts::*;
+#[cfg(feature = "std")]
+pub use ask_tell::*;
+#[cfg(feature = "std")]
+pub use benchmark::*;
+#[cfg(feature = "std")]
+pub use builder::*;
+#[cfg(feature = "std")]
+pub use cache::*;
+#[cfg(feature = "std")]
+pub use calibration::*;
+#[cfg(feature = "std")]
+pub use checkpoint::*;
+#[cfg(feature = "std")]
 pub use codexes::{
-    BitCodex, CharCodex, Codex, FloatCodex, FnCodex, IntCodex, PermutationCodex, SubSetCodex,
+    BitCodex, BytesCodex, CharCodex, Codex, DescribedCodex, FloatCodex, FnCodex, Grammar,
+    GrammarCodex, IntCodex, PermutationCodex, QuantizedCodex, RepairedCodex, SequenceCodex,
+    StrategyCodex, SubSetCodex, Symbol, TokenCodex,
 };
+#[cfg(feature = "std")]
+pub use coevolution::*;
+#[cfg(feature = "std")]
+pub use constraints::*;
+#[cfg(feature = "std")]
 pub use context::*;
-pub use domain::*;
+#[cfg(feature = "std")]
+pub use control::*;
+#[cfg(feature = "std")]
+pub use delta::*;
+#[cfg(feature = "std")]
+pub use description::*;
+#[cfg(feature = "std")]
+pub use driver::*;
+#[cfg(feature = "std")]
+pub use elites::*;
+#[cfg(feature = "std")]
 pub use engine::*;
-pub use genome::*;
+#[cfg(feature = "std")]
+pub use environment::*;
+#[cfg(feature = "std")]
+pub use events::*;
+#[cfg(feature = "std")]
+pub use fuzzing::*;
+#[cfg(feature = "std")]
+pub use games::*;
+#[cfg(feature = "std")]
+pub use group::*;
+#[cfg(feature = "std")]
+pub use hall_of_fame::*;
+#[cfg(feature = "std")]
+pub use hyper::*;
+#[cfg(feature = "std")]
+pub use iter::*;
+#[cfg(feature = "std")]
+pub use local_search::*;
+#[cfg(feature = "std")]
 pub use objectives::*;
+#[cfg(feature = "std")]
 pub use params::*;
+#[cfg(feature = "std")]
+pub use prior::*;
+#[cfg(feature = "std")]
 pub use problem::*;
+#[cfg(feature = "std")]
+pub use qubo::*;
+#[cfg(feature = "std")]
+pub use racing::*;
+#[cfg(feature = "std")]
+pub use repairs::*;
+#[cfg(feature = "std")]
+pub use restart::*;
+#[cfg(feature = "wire")]
+pub use sampling::*;
+#[cfg(feature = "std")]
+pub use schedule::*;
+#[cfg(feature = "std")]
 pub use selectors::*;
+#[cfg(feature = "std")]
+pub use speciation::*;
+#[cfg(feature = "std")]
 pub use stats::*;
+#[cfg(feature = "std")]
+pub use steady_state::*;
 
+#[cfg(feature = "std")]
 pub trait EngineCompoment {
     fn name(&self) -> &'static str;
+
+    /// The name and parameters of the component. Defaults to just the name.
+    fn describe(&self) -> Description {
+        Description::new(self.name())
+    }
 }
@@ -0,0 +1,76 @@
+use crate::{Chromosome, FloatChromosome, Genotype};
+
+/// A ready-made measure of how complex a genotype is, added as an extra (minimized) objective with
+/// `GeneticEngineParams::complexity_objective` to push the search towards parsimonious solutions.
+///
+/// Each measure applies to the chromosomes that implement it through `Measurable` - `L0` and `L1` to
+/// float chromosomes, `GraphEdges` and `TreeSize` to the graph and tree chromosomes of `radiate-gp`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    /// The number of connections in the part of a graph that can affect its outputs - connections
+    /// that pruning would remove don't count.
+    GraphEdges,
+    /// The number of nodes in a tree.
+    TreeSize,
+    /// The number of non-zero values.
+    L0,
+    /// The sum of the absolute values.
+    L1,
+}
+
+/// A chromosome whose complexity can be measured. Returns `None` for measures that don't apply to it.
+pub trait Measurable: Chromosome {
+    fn complexity(&self, measure: Complexity) -> Option<f32>;
+}
+
+impl Complexity {
+    /// The complexity of the genotype - the sum over its chromosomes. Panics if the measure doesn't
+    /// apply to the chromosome.
+    pub fn measure<C: Measurable>(&self, genotype: &Genotype<C>) -> f32 {
+        genotype
+            .iter()
+            .map(|chromosome| {
+                chromosome.complexity(*self).unwrap_or_else(|| {
+                    panic!(
+                        "Complexity::{:?} doesn't apply to {}",
+                        self,
+                        std::any::type_name::<C>()
+                    )
+                })
+            })
+            .sum()
+    }
+}
+
+impl Measurable for FloatChromosome {
+    fn complexity(&self, measure: Complexity) -> Option<f32> {
+        match measure {
+            Complexity::L0 => {
+                Some(self.genes.iter().filter(|gene| gene.allele != 0.0).count() as f32)
+            }
+            Complexity::L1 => Some(self.genes.iter().map(|gene| gene.allele.abs()).sum()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_float_norms() {
+        let chromosome = FloatChromosome::from(&[0.0, -2.0, 0.5, 0.0][..]);
+        let genotype = Genotype::new(vec![chromosome.clone(), chromosome]);
+
+        assert_eq!(Complexity::L0.measure(&genotype), 4.0);
+        assert_eq!(Complexity::L1.measure(&genotype), 5.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_measure_that_does_not_apply_panics() {
+        let genotype = Genotype::new(vec![FloatChromosome::new(vec![])]);
+        Complexity::TreeSize.measure(&genotype);
+    }
+}
@@ -0,0 +1,555 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Score;
+
+type Term<T> = Arc<dyn Fn(&T) -> f32 + Send + Sync>;
+
+/// How the weight of a `FitnessExpr::Penalty` changes over the generations.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PenaltySchedule {
+    /// The same weight every generation.
+    Constant(f32),
+    /// Move linearly from `start` to `end` over `generations`, then stay at `end`.
+    Linear {
+        start: f32,
+        end: f32,
+        generations: i32,
+    },
+    /// Start at `initial` and multiply by `factor` every generation.
+    Exponential { initial: f32, factor: f32 },
+    /// Adapt the weight to the share of individuals that satisfy the constraint, as in ASCHEA: every
+    /// generation the share is above `target` the weight is divided by `factor`, otherwise it is multiplied
+    /// by it - so the search is kept around the boundary of the feasible region. Starts at `initial`.
+    /// The adapted weight is kept by the `CompositeFitnessFn` (see `CompositeFitnessFn::set_generation`).
+    Adaptive {
+        initial: f32,
+        target: f32,
+        factor: f32,
+    },
+}
+
+impl PenaltySchedule {
+    pub fn weight(&self, generation: i32) -> f32 {
+        match self {
+            PenaltySchedule::Constant(weight) => *weight,
+            PenaltySchedule::Linear {
+                start,
+                end,
+                generations,
+            } => {
+                if *generations <= 0 || generation >= *generations {
+                    return *end;
+                }
+
+                let progress = generation.max(0) as f32 / *generations as f32;
+                start + (end - start) * progress
+            }
+            PenaltySchedule::Exponential { initial, factor } => {
+                initial * factor.powi(generation.max(0))
+            }
+            PenaltySchedule::Adaptive { initial, .. } => *initial,
+        }
+    }
+
+    /// ASCHEA's adaptive penalty (see `PenaltySchedule::Adaptive`) with its usual target of half of the
+    /// individuals being feasible and a factor of 1.1.
+    pub fn adaptive(initial: f32) -> Self {
+        PenaltySchedule::Adaptive {
+            initial,
+            target: 0.5,
+            factor: 1.1,
+        }
+    }
+}
+
+/// The state of an adaptive penalty - its current weight and how many of the individuals evaluated since
+/// the last adaptation satisfied the constraint.
+#[derive(Clone, Debug)]
+struct AdaptiveState {
+    weight: f32,
+    target: f32,
+    factor: f32,
+    feasible: usize,
+    total: usize,
+}
+
+impl AdaptiveState {
+    fn adapt(&mut self) {
+        if self.total == 0 {
+            return;
+        }
+
+        let ratio = self.feasible as f32 / self.total as f32;
+        if ratio > self.target {
+            self.weight /= self.factor;
+        } else {
+            self.weight *= self.factor;
+        }
+
+        self.weight = self.weight.clamp(f32::EPSILON, f32::MAX);
+        self.feasible = 0;
+        self.total = 0;
+    }
+}
+
+/// What an expression needs to know besides the term values - the generation, the current weight of
+/// every adaptive penalty (in the order they appear in the expression) and whether each one's
+/// constraint was satisfied.
+struct Evaluation {
+    generation: i32,
+    weights: Vec<f32>,
+    feasible: Vec<bool>,
+}
+
+/// A declarative definition of how a fitness value is composed from named terms. Because a `FitnessExpr`
+/// only refers to terms by name it is plain data - with the `serde` feature enabled it can be serialized
+/// and stored alongside an experiment's configuration, then paired with the term functions again
+/// in a `CompositeFitnessFn`.
+///
+/// Every expression evaluates to a single value except `Lexicographic`, which produces one value per
+/// child. Since scores are compared element by element, a lexicographic expression at the top level
+/// orders individuals by the first child, then the second, and so on. `Lexicographic` can't be nested
+/// inside the other (scalar) expressions.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FitnessExpr {
+    /// The value of the named term.
+    Term(String),
+    Constant(f32),
+    /// The sum of each expression multiplied by its weight.
+    WeightedSum(Vec<(f32, FitnessExpr)>),
+    Product(Vec<FitnessExpr>),
+    Lexicographic(Vec<FitnessExpr>),
+    /// The natural log of the expression. Values are clamped to `f32::EPSILON` first so the result is always finite.
+    Log(Box<FitnessExpr>),
+    Clip {
+        expr: Box<FitnessExpr>,
+        min: f32,
+        max: f32,
+    },
+    /// A constraint violation (negative values are treated as no violation) multiplied by the
+    /// weight given by the schedule for the current generation.
+    Penalty {
+        violation: Box<FitnessExpr>,
+        schedule: PenaltySchedule,
+    },
+}
+
+impl FitnessExpr {
+    pub fn term(name: impl Into<String>) -> Self {
+        FitnessExpr::Term(name.into())
+    }
+
+    pub fn constant(value: f32) -> Self {
+        FitnessExpr::Constant(value)
+    }
+
+    pub fn weighted_sum(terms: Vec<(f32, FitnessExpr)>) -> Self {
+        FitnessExpr::WeightedSum(terms)
+    }
+
+    pub fn product(terms: Vec<FitnessExpr>) -> Self {
+        FitnessExpr::Product(terms)
+    }
+
+    pub fn lexicographic(terms: Vec<FitnessExpr>) -> Self {
+        FitnessExpr::Lexicographic(terms)
+    }
+
+    pub fn log(self) -> Self {
+        FitnessExpr::Log(Box::new(self))
+    }
+
+    pub fn clip(self, min: f32, max: f32) -> Self {
+        if min > max {
+            panic!("Clip min must be less than or equal to max");
+        }
+
+        FitnessExpr::Clip {
+            expr: Box::new(self),
+            min,
+            max,
+        }
+    }
+
+    pub fn penalty(self, schedule: PenaltySchedule) -> Self {
+        FitnessExpr::Penalty {
+            violation: Box::new(self),
+            schedule,
+        }
+    }
+
+    /// The names of all terms used in this expression, sorted and without duplicates.
+    pub fn term_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.collect_terms(&mut names);
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn collect_terms(&self, names: &mut Vec<String>) {
+        match self {
+            FitnessExpr::Term(name) => names.push(name.clone()),
+            FitnessExpr::Constant(_) => {}
+            FitnessExpr::WeightedSum(terms) => {
+                terms.iter().for_each(|(_, expr)| expr.collect_terms(names))
+            }
+            FitnessExpr::Product(terms) | FitnessExpr::Lexicographic(terms) => {
+                terms.iter().for_each(|expr| expr.collect_terms(names))
+            }
+            FitnessExpr::Log(expr) | FitnessExpr::Clip { expr, .. } => expr.collect_terms(names),
+            FitnessExpr::Penalty { violation, .. } => violation.collect_terms(names),
+        }
+    }
+
+    /// The adaptive penalties of this expression, in the order they are evaluated.
+    fn adaptive_penalties(&self, states: &mut Vec<AdaptiveState>) {
+        match self {
+            FitnessExpr::Term(_) | FitnessExpr::Constant(_) => {}
+            FitnessExpr::WeightedSum(terms) => terms
+                .iter()
+                .for_each(|(_, expr)| expr.adaptive_penalties(states)),
+            FitnessExpr::Product(terms) | FitnessExpr::Lexicographic(terms) => terms
+                .iter()
+                .for_each(|expr| expr.adaptive_penalties(states)),
+            FitnessExpr::Log(expr) | FitnessExpr::Clip { expr, .. } => {
+                expr.adaptive_penalties(states)
+            }
+            FitnessExpr::Penalty {
+                violation,
+                schedule,
+            } => {
+                violation.adaptive_penalties(states);
+                if let PenaltySchedule::Adaptive {
+                    initial,
+                    target,
+                    factor,
+                } = schedule
+                {
+                    states.push(AdaptiveState {
+                        weight: *initial,
+                        target: *target,
+                        factor: *factor,
+                        feasible: 0,
+                        total: 0,
+                    });
+                }
+            }
+        }
+    }
+
+    fn evaluate(&self, values: &BTreeMap<&str, f32>, eval: &mut Evaluation) -> Vec<f32> {
+        match self {
+            FitnessExpr::Lexicographic(terms) => terms
+                .iter()
+                .flat_map(|expr| expr.evaluate(values, eval))
+                .collect(),
+            _ => vec![self.scalar(values, eval)],
+        }
+    }
+
+    fn scalar(&self, values: &BTreeMap<&str, f32>, eval: &mut Evaluation) -> f32 {
+        match self {
+            FitnessExpr::Term(name) => match values.get(name.as_str()) {
+                Some(value) => *value,
+                None => panic!("Fitness term '{}' is not defined", name),
+            },
+            FitnessExpr::Constant(value) => *value,
+            FitnessExpr::WeightedSum(terms) => terms
+                .iter()
+                .map(|(weight, expr)| weight * expr.scalar(values, eval))
+                .sum(),
+            FitnessExpr::Product(terms) => {
+                terms.iter().map(|expr| expr.scalar(values, eval)).product()
+            }
+            FitnessExpr::Lexicographic(_) => {
+                panic!("Lexicographic fitness expressions can only be used at the top level")
+            }
+            FitnessExpr::Log(expr) => expr.scalar(values, eval).max(f32::EPSILON).ln(),
+            FitnessExpr::Clip { expr, min, max } => expr.scalar(values, eval).clamp(*min, *max),
+            FitnessExpr::Penalty {
+                violation,
+                schedule,
+            } => {
+                let violation = violation.scalar(values, eval).max(0.0);
+                let weight = match schedule {
+                    PenaltySchedule::Adaptive { .. } => {
+                        let index = eval.feasible.len();
+                        eval.feasible.push(violation == 0.0);
+                        eval.weights[index]
+                    }
+                    _ => schedule.weight(eval.generation),
+                };
+
+                violation * weight
+            }
+        }
+    }
+}
+
+/// A fitness function composed from named terms according to a `FitnessExpr`. The terms are plain
+/// functions of the decoded individual, each evaluated at most once per individual, while the
+/// `FitnessExpr` describes how they are combined - weighted sums, products, lexicographic ordering,
+/// scheduled penalties and transformations.
+///
+/// Penalty schedules need to know the current generation. The `CompositeFitnessFn` is cheap to
+/// clone and all clones share the same generation counter, so keep a clone outside of the engine
+/// and update it with `set_generation` from the `run` closure.
+/// Note that individuals are only scored when they are evaluated, so survivors keep the score
+/// they were given under the penalty weight of the generation they were evaluated in.
+///
+/// `PenaltySchedule::Adaptive` penalties are adapted by `set_generation` whenever the generation
+/// advances, using the share of the individuals evaluated since the last adaptation that satisfied
+/// their constraint.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Maximize the sum of the values while penalizing (more and more) any value above 5.
+/// let expr = FitnessExpr::weighted_sum(vec![
+///     (1.0, FitnessExpr::term("sum")),
+///     (-1.0, FitnessExpr::term("excess").penalty(PenaltySchedule::Linear { start: 0.5, end: 10.0, generations: 20 })),
+/// ]);
+///
+/// let fitness = CompositeFitnessFn::new(expr)
+///     .term("sum", |values: &Vec<Vec<f32>>| values[0].iter().sum())
+///     .term("excess", |values: &Vec<Vec<f32>>| values[0].iter().map(|v| (v - 5.0).max(0.0)).sum());
+///
+/// let schedule = fitness.clone();
+/// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 3, 0.0, 10.0))
+///     .fitness_fn(move |values: Vec<Vec<f32>>| fitness.evaluate(&values))
+///     .build();
+///
+/// let result = engine.run(|ctx| {
+///     schedule.set_generation(ctx.index);
+///     ctx.index > 30
+/// });
+///
+/// assert_eq!(result.best[0].len(), 3);
+/// assert!(schedule.generation() > 30);
+/// ```
+pub struct CompositeFitnessFn<T> {
+    expr: FitnessExpr,
+    terms: BTreeMap<String, Term<T>>,
+    generation: Arc<AtomicI32>,
+    adaptive: Arc<Mutex<Vec<AdaptiveState>>>,
+}
+
+impl<T> CompositeFitnessFn<T> {
+    pub fn new(expr: FitnessExpr) -> Self {
+        let mut adaptive = Vec::new();
+        expr.adaptive_penalties(&mut adaptive);
+
+        CompositeFitnessFn {
+            expr,
+            terms: BTreeMap::new(),
+            generation: Arc::new(AtomicI32::new(0)),
+            adaptive: Arc::new(Mutex::new(adaptive)),
+        }
+    }
+
+    /// Define the function for the term with the given name.
+    pub fn term(
+        mut self,
+        name: impl Into<String>,
+        term: impl Fn(&T) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        self.terms.insert(name.into(), Arc::new(term));
+        self
+    }
+
+    pub fn expr(&self) -> &FitnessExpr {
+        &self.expr
+    }
+
+    /// The names of the terms used by the expression that have no function defined.
+    pub fn missing_terms(&self) -> Vec<String> {
+        self.expr
+            .term_names()
+            .into_iter()
+            .filter(|name| !self.terms.contains_key(name))
+            .collect()
+    }
+
+    /// Set the current generation. When it advances, every adaptive penalty is adapted to the share of
+    /// the individuals evaluated in the meantime that satisfied its constraint.
+    pub fn set_generation(&self, generation: i32) {
+        let previous = self.generation.swap(generation, Ordering::Relaxed);
+        if generation > previous {
+            let mut adaptive = self.adaptive.lock().unwrap();
+            adaptive.iter_mut().for_each(AdaptiveState::adapt);
+        }
+    }
+
+    /// The current weights of the adaptive penalties, in the order they appear in the expression.
+    pub fn adaptive_weights(&self) -> Vec<f32> {
+        let adaptive = self.adaptive.lock().unwrap();
+        adaptive.iter().map(|state| state.weight).collect()
+    }
+
+    pub fn generation(&self) -> i32 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Evaluate the composed fitness of the individual. Panics if the expression uses a term that isn't defined.
+    pub fn evaluate(&self, individual: &T) -> Score {
+        let values = self
+            .terms
+            .iter()
+            .map(|(name, term)| (name.as_str(), term(individual)))
+            .collect::<BTreeMap<&str, f32>>();
+
+        let mut eval = Evaluation {
+            generation: self.generation(),
+            weights: self.adaptive_weights(),
+            feasible: Vec::new(),
+        };
+        let score = self.expr.evaluate(&values, &mut eval);
+
+        if !eval.feasible.is_empty() {
+            let mut adaptive = self.adaptive.lock().unwrap();
+            for (state, feasible) in adaptive.iter_mut().zip(eval.feasible) {
+                state.feasible += feasible as usize;
+                state.total += 1;
+            }
+        }
+
+        Score::from_vec(score)
+    }
+}
+
+impl<T> Clone for CompositeFitnessFn<T> {
+    fn clone(&self) -> Self {
+        CompositeFitnessFn {
+            expr: self.expr.clone(),
+            terms: self.terms.clone(),
+            generation: Arc::clone(&self.generation),
+            adaptive: Arc::clone(&self.adaptive),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn composite(expr: FitnessExpr) -> CompositeFitnessFn<(f32, f32)> {
+        CompositeFitnessFn::new(expr)
+            .term("a", |pair: &(f32, f32)| pair.0)
+            .term("b", |pair: &(f32, f32)| pair.1)
+    }
+
+    #[test]
+    fn test_weighted_sum_and_product() {
+        let sum = composite(FitnessExpr::weighted_sum(vec![
+            (2.0, FitnessExpr::term("a")),
+            (-1.0, FitnessExpr::term("b")),
+        ]));
+        let product = composite(FitnessExpr::product(vec![
+            FitnessExpr::term("a"),
+            FitnessExpr::term("b"),
+            FitnessExpr::constant(0.5),
+        ]));
+
+        assert_eq!(sum.evaluate(&(3.0, 1.0)).as_f32(), 5.0);
+        assert_eq!(product.evaluate(&(3.0, 4.0)).as_f32(), 6.0);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let clipped = composite(FitnessExpr::term("a").clip(0.0, 1.0));
+        let logged = composite(FitnessExpr::term("a").log());
+
+        assert_eq!(clipped.evaluate(&(3.0, 0.0)).as_f32(), 1.0);
+        assert_eq!(logged.evaluate(&(1.0, 0.0)).as_f32(), 0.0);
+        assert!(logged.evaluate(&(-1.0, 0.0)).as_f32().is_finite());
+    }
+
+    #[test]
+    fn test_lexicographic_ordering() {
+        let fitness = composite(FitnessExpr::lexicographic(vec![
+            FitnessExpr::term("a"),
+            FitnessExpr::term("b"),
+        ]));
+
+        let one = fitness.evaluate(&(1.0, 0.0));
+        let two = fitness.evaluate(&(0.0, 100.0));
+        assert_eq!(one.values, vec![1.0, 0.0]);
+        assert!(one > two);
+    }
+
+    #[test]
+    fn test_penalty_schedule() {
+        let fitness = composite(FitnessExpr::term("a").penalty(PenaltySchedule::Linear {
+            start: 0.0,
+            end: 10.0,
+            generations: 10,
+        }));
+
+        assert_eq!(fitness.evaluate(&(2.0, 0.0)).as_f32(), 0.0);
+
+        fitness.clone().set_generation(5);
+        assert_eq!(fitness.evaluate(&(2.0, 0.0)).as_f32(), 10.0);
+        assert_eq!(fitness.evaluate(&(-2.0, 0.0)).as_f32(), 0.0);
+
+        fitness.set_generation(50);
+        assert_eq!(fitness.evaluate(&(2.0, 0.0)).as_f32(), 20.0);
+    }
+
+    #[test]
+    fn test_adaptive_penalty_follows_the_feasible_ratio() {
+        let fitness = composite(FitnessExpr::term("a").penalty(PenaltySchedule::adaptive(1.0)));
+
+        fitness.evaluate(&(2.0, 0.0));
+        fitness.evaluate(&(1.0, 0.0));
+        fitness.evaluate(&(-1.0, 0.0));
+        fitness.set_generation(1);
+        assert!((fitness.adaptive_weights()[0] - 1.1).abs() < 1e-6);
+        assert_eq!(fitness.evaluate(&(2.0, 0.0)).as_f32(), 2.0 * 1.1);
+
+        fitness.evaluate(&(-1.0, 0.0));
+        fitness.evaluate(&(-2.0, 0.0));
+        fitness.set_generation(2);
+        assert!((fitness.adaptive_weights()[0] - 1.0).abs() < 1e-6);
+
+        fitness.set_generation(3);
+        assert!((fitness.adaptive_weights()[0] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_missing_terms() {
+        let fitness = composite(FitnessExpr::weighted_sum(vec![
+            (1.0, FitnessExpr::term("a")),
+            (1.0, FitnessExpr::term("c")),
+        ]));
+
+        assert_eq!(fitness.missing_terms(), vec!["c".to_string()]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let expr = FitnessExpr::weighted_sum(vec![
+            (1.0, FitnessExpr::term("a").log()),
+            (
+                -1.0,
+                FitnessExpr::term("b").penalty(PenaltySchedule::Exponential {
+                    initial: 1.0,
+                    factor: 1.1,
+                }),
+            ),
+        ]);
+
+        let json = serde_json::to_string(&expr).unwrap();
+        let parsed: FitnessExpr = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, expr);
+    }
+}
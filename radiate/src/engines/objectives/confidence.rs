@@ -0,0 +1,275 @@
+use super::{Objective, Optimize, Score};
+
+/// Sampling statistics of a `Score` that is the mean of repeated evaluations (see `Score::from_samples`) -
+/// the sample variance of every objective and the number of samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreStats {
+    pub variances: Vec<f32>,
+    pub samples: usize,
+}
+
+impl ScoreStats {
+    /// The standard error of the mean of the objective at `index`.
+    pub fn std_error(&self, index: usize) -> f32 {
+        (self.variances[index] / self.samples as f32).sqrt()
+    }
+}
+
+/// The result of Welch's unequal variances t-test between the means of two scores.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WelchTest {
+    pub t: f32,
+    pub df: f32,
+}
+
+impl WelchTest {
+    /// Whether the means differ at significance level `alpha` (two-sided).
+    pub fn is_significant(&self, alpha: f32) -> bool {
+        self.t.abs() > t_quantile(1.0 - alpha / 2.0, self.df)
+    }
+
+    /// Whether the first mean is significantly better than the second for `optimize` at
+    /// significance level `alpha` (one-sided).
+    pub fn favors_first(&self, optimize: Optimize, alpha: f32) -> bool {
+        let critical = t_quantile(1.0 - alpha, self.df);
+        match optimize {
+            Optimize::Maximize => self.t > critical,
+            Optimize::Minimize => self.t < -critical,
+        }
+    }
+}
+
+impl Score {
+    /// Build a score from repeated evaluations of the same individual - the values are the per
+    /// objective means, and the variances and sample count are kept for confidence intervals and
+    /// significance tests. Panics if `samples` is empty or the samples have different lengths.
+    pub fn from_samples(samples: &[Score]) -> Self {
+        if samples.is_empty() {
+            panic!("Cannot build a score from no samples");
+        }
+
+        let len = samples[0].values.len();
+        if samples.iter().any(|sample| sample.values.len() != len) {
+            panic!("All samples must have the same number of values");
+        }
+
+        let count = samples.len() as f32;
+        let means = (0..len)
+            .map(|i| samples.iter().map(|sample| sample.values[i]).sum::<f32>() / count)
+            .collect::<Vec<f32>>();
+        let variances = (0..len)
+            .map(|i| match samples.len() > 1 {
+                true => {
+                    samples
+                        .iter()
+                        .map(|sample| (sample.values[i] - means[i]).powi(2))
+                        .sum::<f32>()
+                        / (count - 1.0)
+                }
+                false => 0.0,
+            })
+            .collect::<Vec<f32>>();
+
+        Score {
+            values: means,
+            stats: Some(ScoreStats {
+                variances,
+                samples: samples.len(),
+            }),
+        }
+    }
+
+    /// The sampling statistics of the score, if it was built from repeated evaluations.
+    pub fn stats(&self) -> Option<&ScoreStats> {
+        self.stats.as_ref()
+    }
+
+    /// The `level` (e.g. 0.95) confidence interval of the mean of every objective, if the score
+    /// was built from more than one sample.
+    pub fn confidence_interval(&self, level: f32) -> Option<Vec<(f32, f32)>> {
+        let stats = self.stats.as_ref().filter(|stats| stats.samples > 1)?;
+        let critical = t_quantile(0.5 + level / 2.0, (stats.samples - 1) as f32);
+
+        Some(
+            self.values
+                .iter()
+                .enumerate()
+                .map(|(i, mean)| {
+                    let half_width = critical * stats.std_error(i);
+                    (mean - half_width, mean + half_width)
+                })
+                .collect(),
+        )
+    }
+
+    /// Welch's t-test between the means of the objective at `index` of this score and `other`.
+    /// Both scores need to be built from more than one sample.
+    pub fn welch(&self, other: &Score, index: usize) -> Option<WelchTest> {
+        let one = self.stats.as_ref().filter(|stats| stats.samples > 1)?;
+        let two = other.stats.as_ref().filter(|stats| stats.samples > 1)?;
+
+        let (n_one, n_two) = (one.samples as f32, two.samples as f32);
+        let (se_one, se_two) = (one.variances[index] / n_one, two.variances[index] / n_two);
+        let difference = self.values[index] - other.values[index];
+
+        if se_one + se_two == 0.0 {
+            let t = match difference {
+                d if d > 0.0 => f32::INFINITY,
+                d if d < 0.0 => f32::NEG_INFINITY,
+                _ => 0.0,
+            };
+
+            return Some(WelchTest {
+                t,
+                df: n_one + n_two - 2.0,
+            });
+        }
+
+        let df = (se_one + se_two).powi(2)
+            / (se_one.powi(2) / (n_one - 1.0) + se_two.powi(2) / (n_two - 1.0));
+
+        Some(WelchTest {
+            t: difference / (se_one + se_two).sqrt(),
+            df,
+        })
+    }
+}
+
+impl Optimize {
+    /// Whether `a` is significantly better than `b` at significance level `alpha` according to a
+    /// one-sided Welch's t-test. Scores without sampling statistics fall back to `is_better`.
+    pub fn is_significantly_better(&self, a: &Score, b: &Score, alpha: f32) -> bool {
+        match a.welch(b, 0) {
+            Some(test) => test.favors_first(*self, alpha),
+            None => self.is_better(a, b),
+        }
+    }
+}
+
+impl Objective {
+    /// Whether `a` is significantly better than `b` on every objective (see
+    /// `Optimize::is_significantly_better`).
+    pub fn is_significantly_better(&self, a: &Score, b: &Score, alpha: f32) -> bool {
+        match self {
+            Objective::Single(opt) => opt.is_significantly_better(a, b, alpha),
+            Objective::Multi(opts) => opts.iter().enumerate().all(|(i, opt)| match a.welch(b, i) {
+                Some(test) => test.favors_first(*opt, alpha),
+                None => opt.is_better(&a.values[i], &b.values[i]),
+            }),
+        }
+    }
+}
+
+/// The `p` quantile of Student's t distribution with `df` degrees of freedom, from the normal
+/// quantile with a Cornish-Fisher expansion. Accurate to a few percent from 3 degrees of freedom.
+fn t_quantile(p: f32, df: f32) -> f32 {
+    let z = normal_quantile(p as f64);
+    if !df.is_finite() {
+        return z as f32;
+    }
+
+    let df = df.max(1.0) as f64;
+    let (z3, z5, z7, z9) = (z.powi(3), z.powi(5), z.powi(7), z.powi(9));
+
+    (z + (z3 + z) / (4.0 * df)
+        + (5.0 * z5 + 16.0 * z3 + 3.0 * z) / (96.0 * df.powi(2))
+        + (3.0 * z7 + 19.0 * z5 + 17.0 * z3 - 15.0 * z) / (384.0 * df.powi(3))
+        + (79.0 * z9 + 776.0 * z7 + 1482.0 * z5 - 1920.0 * z3 - 945.0 * z) / (92160.0 * df.powi(4)))
+        as f32
+}
+
+/// The `p` quantile of the standard normal distribution (Acklam's rational approximation).
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.383_577_518_672_69e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let low = 0.02425;
+
+    if p < low {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - low {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_t_quantile_matches_tables() {
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-5);
+        assert!((t_quantile(0.975, 10.0) - 2.228).abs() < 0.01);
+        assert!((t_quantile(0.95, 30.0) - 1.697).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_score_from_samples_and_welch() {
+        let noisy = |mean: f32| {
+            let samples = [-1.0, 0.0, 1.0, -0.5, 0.5]
+                .iter()
+                .map(|noise| Score::from_f32(mean + noise))
+                .collect::<Vec<Score>>();
+            Score::from_samples(&samples)
+        };
+
+        let low = noisy(10.0);
+        let close = noisy(10.2);
+        let high = noisy(15.0);
+
+        assert_eq!(low.as_f32(), 10.0);
+        assert_eq!(low.stats().unwrap().samples, 5);
+        assert!((low.stats().unwrap().variances[0] - 0.625).abs() < 1e-6);
+
+        let (lower, upper) = low.confidence_interval(0.95).unwrap()[0];
+        assert!(lower < 10.0 && upper > 10.0);
+
+        assert!(Optimize::Maximize.is_significantly_better(&high, &low, 0.05));
+        assert!(!Optimize::Maximize.is_significantly_better(&close, &low, 0.05));
+        assert!(Optimize::Minimize.is_significantly_better(&low, &high, 0.05));
+        assert!(high.welch(&low, 0).unwrap().is_significant(0.01));
+        assert!(!close.welch(&low, 0).unwrap().is_significant(0.05));
+
+        // Without sampling statistics the plain comparison is used.
+        assert!(Optimize::Maximize.is_significantly_better(
+            &Score::from_f32(10.2),
+            &Score::from_f32(10.0),
+            0.05
+        ));
+    }
+}
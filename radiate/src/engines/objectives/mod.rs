@@ -1,9 +1,17 @@
+pub mod complexity;
+pub mod composite;
+pub mod confidence;
 pub mod front;
 pub mod optimize;
 pub mod pareto;
 pub mod score;
+pub mod shaping;
 
+pub use complexity::*;
+pub use composite::*;
+pub use confidence::*;
 pub use front::*;
 pub use optimize::*;
 pub use pareto::*;
 pub use score::*;
+pub use shaping::*;
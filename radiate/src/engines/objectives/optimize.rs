@@ -1,4 +1,6 @@
-use crate::{Chromosome, Population};
+use std::cmp::Ordering;
+
+use crate::{Chromosome, Phenotype, Population, Score};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Objective {
@@ -7,40 +9,61 @@ pub enum Objective {
 }
 
 impl Objective {
+    /// Sort the population into the order the engine keeps it in (see `compare`). After every
+    /// evaluation the engine's population is sorted this way, and selectors may rely on it.
     pub fn sort<C: Chromosome>(&self, population: &mut Population<C>) {
-        match self {
-            Objective::Single(opt) => opt.sort(population),
-            Objective::Multi(_) => population.sort_by(|a, b| {
-                let a = a.score().unwrap();
-                let b = b.score().unwrap();
-                self.dominance_cmp(&a.values, &b.values)
-            }),
-        }
+        population.sort_by(|a, b| self.compare(a, b));
+    }
+
+    /// The ordering contract of a sorted `Population`: the best score comes first, ties are broken
+    /// by age - older individuals (lower generation) first - and any remaining ties keep their
+    /// current relative order, since the sort is stable. Individuals without a score come last.
+    /// With multiple objectives, scores are compared objective by objective in order.
+    pub fn compare<C: Chromosome>(&self, a: &Phenotype<C>, b: &Phenotype<C>) -> Ordering {
+        let by_score = match (a.score(), b.score()) {
+            (Some(one), Some(two)) => match self {
+                Objective::Single(opt) => opt.compare(one, two),
+                Objective::Multi(_) => self.dominance_cmp(&one.values, &two.values),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+
+        by_score.then_with(|| a.generation.cmp(&b.generation))
     }
 
-    fn dominance_cmp<T>(&self, a: &[T], b: &[T]) -> std::cmp::Ordering
+    /// Check whether the population is in the order given by `compare`.
+    pub fn is_sorted<C: Chromosome>(&self, population: &Population<C>) -> bool {
+        population
+            .as_ref()
+            .windows(2)
+            .all(|pair| self.compare(&pair[0], &pair[1]) != Ordering::Greater)
+    }
+
+    fn dominance_cmp<T>(&self, a: &[T], b: &[T]) -> Ordering
     where
         T: PartialOrd,
     {
         match self {
             Objective::Single(opt) => {
                 if opt.is_better(&a[0], &b[0]) {
-                    std::cmp::Ordering::Less
+                    Ordering::Less
                 } else if opt.is_better(&b[0], &a[0]) {
-                    std::cmp::Ordering::Greater
+                    Ordering::Greater
                 } else {
-                    std::cmp::Ordering::Equal
+                    Ordering::Equal
                 }
             }
             Objective::Multi(opts) => {
                 for ((a, b), opt) in a.iter().zip(b.iter()).zip(opts) {
                     if opt.is_better(a, b) {
-                        return std::cmp::Ordering::Less;
+                        return Ordering::Less;
                     } else if opt.is_better(b, a) {
-                        return std::cmp::Ordering::Greater;
+                        return Ordering::Greater;
                     }
                 }
-                std::cmp::Ordering::Equal
+                Ordering::Equal
             }
         }
     }
@@ -61,6 +84,12 @@ impl Objective {
             }
         }
     }
+
+    /// The worst possible score, one value per objective - every other score is better. Given to
+    /// individuals whose evaluation failed (see `Problem::eval_batch`).
+    pub fn worst_score(&self) -> Score {
+        Score::from_vec(self.as_ref().iter().map(|opt| opt.worst_value()).collect())
+    }
 }
 
 impl AsRef<[Optimize]> for Objective {
@@ -79,10 +108,17 @@ pub enum Optimize {
 }
 
 impl Optimize {
+    /// Sort the population best first, with the same tie-breaking as `Objective::sort`.
     pub fn sort<C: Chromosome>(&self, population: &mut Population<C>) {
+        Objective::Single(*self).sort(population);
+    }
+
+    /// Compare two scores, with the better score ordered first.
+    pub fn compare(&self, a: &Score, b: &Score) -> Ordering {
+        let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
         match self {
-            Optimize::Minimize => population.sort_by(|a, b| a.partial_cmp(b).unwrap()),
-            Optimize::Maximize => population.sort_by(|a, b| b.partial_cmp(a).unwrap()),
+            Optimize::Minimize => ordering,
+            Optimize::Maximize => ordering.reverse(),
         }
     }
 
@@ -95,12 +131,53 @@ impl Optimize {
             Optimize::Maximize => a > b,
         }
     }
+
+    /// The worst finite value of a score - `f32::MAX` when minimizing, `f32::MIN` when maximizing. It is
+    /// finite so distances between scores (e.g. the crowding distance) stay well defined.
+    pub fn worst_value(&self) -> f32 {
+        match self {
+            Optimize::Minimize => f32::MAX,
+            Optimize::Maximize => f32::MIN,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_objective_sort_breaks_ties_by_age() {
+        use crate::{FloatChromosome, Genotype};
+
+        let individual = |score: f32, generation: i32| {
+            let genotype = Genotype::new(vec![FloatChromosome::from(&[score][..])]);
+            let mut phenotype = Phenotype::from_genotype(genotype, generation);
+            phenotype.set_score(Some(Score::from_f32(score)));
+            phenotype
+        };
+
+        let mut population = Population::new(vec![
+            individual(1.0, 3),
+            individual(2.0, 5),
+            individual(2.0, 1),
+            individual(1.0, 0),
+        ]);
+        population[1].set_score(None);
+
+        let objective = Objective::Single(Optimize::Maximize);
+        assert!(!objective.is_sorted(&population));
+
+        objective.sort(&mut population);
+        let order = population
+            .iter()
+            .map(|individual| individual.generation)
+            .collect::<Vec<i32>>();
+
+        assert_eq!(order, vec![1, 0, 3, 5]);
+        assert!(objective.is_sorted(&population));
+    }
+
     #[test]
     fn test_optimize_is_better() {
         assert!(Optimize::Minimize.is_better(&1, &2));
@@ -70,11 +70,11 @@ pub fn rank<C: Chromosome>(population: &Population<C>, objective: &Objective) ->
     }
 
     for i in 0..population.len() {
-        for j in 0..population.len() {
+        for (j, relation) in dominance_matrix[i].iter().enumerate() {
             if i != j {
-                if dominance_matrix[i][j] == 1 {
+                if *relation == 1 {
                     dominates[i].push(j);
-                } else if dominance_matrix[i][j] == -1 {
+                } else if *relation == -1 {
                     dominated_counts[i] += 1;
                 }
             }
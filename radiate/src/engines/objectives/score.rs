@@ -1,6 +1,8 @@
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use super::ScoreStats;
+
 /// A score is a value that can be used to compare the fitness of two individuals and represents
 /// the 'fitness' of an individual within the genetic algorithm.
 /// The score can be a single value or multiple values, depending on the problem being solved.
@@ -9,9 +11,14 @@ use std::hash::Hash;
 ///
 /// Note: The reason it is a Vec is for multi-objective optimization problems. This allows for multiple
 /// fitness values to be returned from the fitness function.
-#[derive(Clone, PartialEq)]
+///
+/// A score built from repeated evaluations (see `Score::from_samples`) also carries its sampling
+/// statistics, which are used for confidence intervals and significance tests. Equality, ordering and
+/// hashing only consider the values.
+#[derive(Clone)]
 pub struct Score {
     pub values: Vec<f32>,
+    pub stats: Option<ScoreStats>,
 }
 
 impl Score {
@@ -34,7 +41,10 @@ impl Score {
     }
 
     pub fn from_vec(values: Vec<f32>) -> Self {
-        Score { values }
+        Score {
+            values,
+            stats: None,
+        }
     }
 
     pub fn from_f32(value: f32) -> Self {
@@ -44,24 +54,28 @@ impl Score {
 
         Score {
             values: vec![value],
+            stats: None,
         }
     }
 
     pub fn from_int(value: i32) -> Self {
         Score {
             values: vec![value as f32],
+            stats: None,
         }
     }
 
     pub fn from_usize(value: usize) -> Self {
         Score {
             values: vec![value as f32],
+            stats: None,
         }
     }
 
     pub fn from_string(value: &str) -> Self {
         Score {
             values: vec![value.parse::<f32>().unwrap()],
+            stats: None,
         }
     }
 
@@ -88,6 +102,12 @@ impl AsRef<[f32]> for Score {
     }
 }
 
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
 impl PartialOrd for Score {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.values.partial_cmp(&other.values)
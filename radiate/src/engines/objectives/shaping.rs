@@ -0,0 +1,276 @@
+use super::{Objective, Optimize, Score};
+use crate::{Chromosome, Diversity, FloatChromosome, Gene, Genotype, Population};
+use std::sync::Arc;
+
+type Penalty<C> = Arc<dyn Fn(&Genotype<C>) -> f32 + Send + Sync>;
+type Transform<C> = Arc<dyn Fn(&mut Population<C>, &Objective) + Send + Sync>;
+
+/// Fitness shaping transforms the raw scores of a population before selection. The raw scores are
+/// left untouched - the shaped scores are only used by the selectors to decide which individuals
+/// survive and which become parents. This is most useful for neuroevolution where the raw fitness
+/// is often noisy or badly scaled, e.g. in Evolution Strategies (OpenAI-ES, ARS).
+///
+/// Three kinds of transformations are supported and are applied in this order:
+/// * `penalty` - a value computed from the genotype which is subtracted from (when maximizing)
+///   or added to (when minimizing) each score. `weight_decay` is a penalty of this kind.
+/// * `transform` - a function of the whole population, for techniques that need global information
+///   like novelty blending. `duplicate_penalty` and `crowding_penalty` are transformations of this
+///   kind. They are applied in the order they were added.
+/// * `centered_rank` - replaces each score with its rank in the population, scaled to `[-0.5, 0.5]`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let shaping = FitnessShaping::<FloatChromosome>::new()
+///     .weight_decay(0.01)
+///     .duplicate_penalty(1.0)
+///     .centered_rank();
+/// ```
+pub struct FitnessShaping<C: Chromosome> {
+    centered_rank: bool,
+    penalty: Option<Penalty<C>>,
+    transforms: Vec<Transform<C>>,
+}
+
+impl<C: Chromosome> FitnessShaping<C> {
+    pub fn new() -> Self {
+        FitnessShaping {
+            centered_rank: false,
+            penalty: None,
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Replace each score with its centered rank in the population.
+    pub fn centered_rank(mut self) -> Self {
+        self.centered_rank = true;
+        self
+    }
+
+    /// Penalize each individual by the value returned from `penalty`.
+    pub fn penalty<F>(mut self, penalty: F) -> Self
+    where
+        F: Fn(&Genotype<C>) -> f32 + Send + Sync + 'static,
+    {
+        self.penalty = Some(Arc::new(penalty));
+        self
+    }
+
+    /// Transform the scores of the whole population with `transform`. The population it is given is a
+    /// copy of the engine's, sorted best first by the raw scores, and every individual has a score.
+    pub fn transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&mut Population<C>, &Objective) + Send + Sync + 'static,
+    {
+        self.transforms.push(Arc::new(transform));
+        self
+    }
+
+    /// Penalize every copy of a genotype but the best by `penalty`, so duplicates don't crowd the
+    /// selectors' picks.
+    pub fn duplicate_penalty(self, penalty: f32) -> Self {
+        self.transform(move |population, objective| {
+            for i in 1..population.len() {
+                let genotype = population[i].genotype();
+                if (0..i).any(|j| population[j].genotype() == genotype) {
+                    let score = penalize(population[i].score().unwrap(), penalty, objective);
+                    population[i].set_score(Some(score));
+                }
+            }
+        })
+    }
+
+    /// Penalize every individual by `penalty` for each other individual closer to it than `radius`
+    /// by the `diversity` distance, so crowded regions of the search space lose selection pressure.
+    pub fn crowding_penalty<D>(self, diversity: D, radius: f32, penalty: f32) -> Self
+    where
+        D: Diversity<C> + Send + Sync + 'static,
+    {
+        self.transform(move |population, objective| {
+            let neighbours = (0..population.len())
+                .map(|i| {
+                    (0..population.len())
+                        .filter(|j| *j != i)
+                        .filter(|j| {
+                            let (one, two) = (population[i].genotype(), population[*j].genotype());
+                            diversity.distance(one, two) < radius
+                        })
+                        .count()
+                })
+                .collect::<Vec<usize>>();
+
+            for (individual, count) in population.iter_mut().zip(neighbours) {
+                let score = penalize(
+                    individual.score().unwrap(),
+                    penalty * count as f32,
+                    objective,
+                );
+                individual.set_score(Some(score));
+            }
+        })
+    }
+
+    /// Shape the scores of the given population in place. Every individual in the population
+    /// must already have a score.
+    pub fn shape(&self, population: &mut Population<C>, objective: &Objective) {
+        if let Some(penalty) = &self.penalty {
+            for individual in population.iter_mut() {
+                let cost = penalty(individual.genotype());
+                let score = penalize(individual.score().unwrap(), cost, objective);
+                individual.set_score(Some(score));
+            }
+        }
+
+        for transform in self.transforms.iter() {
+            transform(population, objective);
+        }
+
+        if self.centered_rank {
+            let num_values = objective.as_ref().len();
+            let mut shaped = vec![Vec::with_capacity(num_values); population.len()];
+
+            for i in 0..num_values {
+                let values = population
+                    .iter()
+                    .map(|individual| individual.score().unwrap().values[i])
+                    .collect::<Vec<f32>>();
+
+                for (idx, value) in centered_ranks(&values).into_iter().enumerate() {
+                    shaped[idx].push(value);
+                }
+            }
+
+            for (individual, values) in population.iter_mut().zip(shaped) {
+                individual.set_score(Some(Score::from_vec(values)));
+            }
+        }
+    }
+}
+
+impl FitnessShaping<FloatChromosome> {
+    /// L2 weight decay - penalize each individual by `coefficient * sum(x^2)` over all of its genes.
+    pub fn weight_decay(self, coefficient: f32) -> Self {
+        self.penalty(move |genotype: &Genotype<FloatChromosome>| {
+            coefficient
+                * genotype
+                    .iter()
+                    .flat_map(|chromosome| chromosome.iter())
+                    .map(|gene| gene.allele().powi(2))
+                    .sum::<f32>()
+        })
+    }
+}
+
+impl<C: Chromosome> Default for FitnessShaping<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Make every value of the score worse by `cost` - lower when maximizing, higher when minimizing.
+fn penalize(score: &Score, cost: f32, objective: &Objective) -> Score {
+    let values = score
+        .values
+        .iter()
+        .zip(objective.as_ref().iter().cycle())
+        .map(|(value, opt)| match opt {
+            Optimize::Maximize => value - cost,
+            Optimize::Minimize => value + cost,
+        })
+        .collect::<Vec<f32>>();
+
+    Score::from_vec(values)
+}
+
+/// Map each value to its rank within `values` scaled to `[-0.5, 0.5]`. The smallest value
+/// gets `-0.5` and the largest gets `0.5`, so the direction of optimization is preserved. Values are
+/// ordered with `f32::total_cmp`, so a NaN score ranks above every number instead of panicking.
+pub fn centered_ranks(values: &[f32]) -> Vec<f32> {
+    if values.len() < 2 {
+        return vec![0.0; values.len()];
+    }
+
+    let mut order = (0..values.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+
+    let denom = (values.len() - 1) as f32;
+    let mut result = vec![0.0; values.len()];
+    for (rank, idx) in order.into_iter().enumerate() {
+        result[idx] = rank as f32 / denom - 0.5;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatGene, HammingDistance, Phenotype};
+
+    fn scored(alleles: &[f32], score: f32) -> Population<FloatChromosome> {
+        alleles
+            .iter()
+            .map(|allele| {
+                let genes = vec![FloatGene::new(0.0, 10.0).with_allele(allele)];
+                let mut individual =
+                    Phenotype::from_chromosomes(vec![FloatChromosome { genes }], 0);
+                individual.set_score(Some(Score::from_f32(score)));
+                individual
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_centered_ranks() {
+        let ranks = centered_ranks(&[10.0, -3.0, 100.0, 0.0, 5.0]);
+        assert_eq!(ranks, vec![0.25, -0.5, 0.5, -0.25, 0.0]);
+
+        let ranks = centered_ranks(&[1.0, f32::NAN, -1.0]);
+        assert_eq!(ranks, vec![0.0, 0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_weight_decay_penalizes_large_weights() {
+        let mut population = (0..2)
+            .map(|i| {
+                let genes = vec![FloatGene::new(0.0, 1.0).with_allele(&(i as f32 * 2.0)); 2];
+                let mut individual =
+                    Phenotype::from_chromosomes(vec![FloatChromosome { genes }], 0);
+                individual.set_score(Some(Score::from_f32(1.0)));
+                individual
+            })
+            .collect::<Population<FloatChromosome>>();
+
+        FitnessShaping::new()
+            .weight_decay(0.5)
+            .shape(&mut population, &Objective::Single(Optimize::Maximize));
+
+        assert_eq!(population[0].score().unwrap().as_f32(), 1.0);
+        assert_eq!(population[1].score().unwrap().as_f32(), -3.0);
+    }
+
+    #[test]
+    fn test_population_transforms() {
+        let objective = Objective::Single(Optimize::Minimize);
+        let scores = |population: &Population<FloatChromosome>| {
+            population
+                .iter()
+                .map(|individual| individual.score().unwrap().as_f32())
+                .collect::<Vec<f32>>()
+        };
+
+        let mut population = scored(&[1.0, 2.0, 1.0, 1.0], 0.0);
+        FitnessShaping::new()
+            .duplicate_penalty(1.0)
+            .shape(&mut population, &objective);
+        assert_eq!(scores(&population), vec![0.0, 0.0, 1.0, 1.0]);
+
+        let mut population = scored(&[1.0, 2.0, 1.0, 1.0], 0.0);
+        FitnessShaping::new()
+            .crowding_penalty(HammingDistance, 0.5, 2.0)
+            .transform(|population, _| population[1].set_score(Some(Score::from_f32(-1.0))))
+            .shape(&mut population, &objective);
+        assert_eq!(scores(&population), vec![4.0, -1.0, 4.0, 4.0]);
+    }
+}
@@ -1,17 +1,39 @@
 use super::codexes::Codex;
-use super::thread_pool::ThreadPool;
+use super::random_provider::RngHandle;
+use super::scratch::{FitnessCtx, ScratchPool};
+use super::thread_pool::{Job, ThreadPool};
+#[cfg(feature = "wire")]
+use super::SampleWriter;
 use super::{
-    Alter, AlterAction, EngineProblem, Problem, RouletteSelector, Select, TournamentSelector,
+    Alter, AlterAction, BatchEngineProblem, BatchFitnessFn, BatchedProblem, CachedProblem,
+    Calibration, CalibrationResult, Checkpoint, CheckpointReader, CheckpointWriter, ComplexityFn,
+    ComplexityProblem, Constraint, ConstraintEntry, ConstraintMode, ConstraintProblem,
+    ControlPanel, DeltaFitness, EmbeddingTrace, EngineContext, EngineProblem, FitnessCache,
+    FitnessInput, GeneSchema, GroupEvaluator, HallOfFame, Memetic, MemoryBudget, ObjectiveFn,
+    PopulationPrior, PopulationSchedule, Problem, Racing, Recording, Replacement, Restart,
+    RestartStrategy, RouletteSelector, RunArtifacts, Seeds, Select, SelectorBenchmark,
+    SelectorBenchmarkResult, Speciation, SteadyState, Subscriber, TournamentSelector,
 };
 use crate::engines::engine::GeneticEngine;
 use crate::engines::genome::phenotype::Phenotype;
 use crate::engines::genome::population::Population;
 use crate::engines::objectives::Score;
-use crate::objectives::{Objective, Optimize};
+use crate::objectives::{Complexity, FitnessShaping, Measurable, Objective, Optimize};
 use crate::uniform::{UniformCrossover, UniformMutator};
-use crate::Chromosome;
+use crate::wire::WireAllele;
+use crate::{Chromosome, Gene, Genotype};
+use rand::RngCore;
+use std::fmt::Debug;
+#[cfg(feature = "wire")]
+use std::path::PathBuf;
 use std::sync::Arc;
 
+type Recorder<T> = Arc<dyn Fn(T, &mut Recording) + Send + Sync>;
+type GeneValue<C> = Arc<dyn Fn(&<C as Chromosome>::Gene) -> f32 + Send + Sync>;
+type GenotypeMetric<C> = Arc<dyn Fn(&Genotype<C>) -> f32 + Send + Sync>;
+type PostRun<C, T> = Arc<dyn Fn(&mut EngineContext<C, T>) -> std::io::Result<()> + Send + Sync>;
+type Sampler<C> = Arc<dyn Fn(i32, &Population<C>) + Send + Sync>;
+
 /// Parameters for the genetic engine.
 /// This struct is used to configure the genetic engine before it is created.
 ///
@@ -31,6 +53,7 @@ where
     T: Clone + 'static,
 {
     pub population_size: usize,
+    pub population_schedule: Option<PopulationSchedule>,
     pub max_age: i32,
     pub min_front_size: usize,
     pub max_front_size: usize,
@@ -43,7 +66,45 @@ where
     pub population: Option<Population<C>>,
     pub codex: Option<Arc<Box<dyn Codex<C, T>>>>,
     pub fitness_fn: Option<Arc<dyn Fn(T) -> Score + Send + Sync>>,
+    pub objective_fns: Vec<ObjectiveFn<T>>,
+    pub batch_fitness_fn: Option<BatchFitnessFn<T>>,
+    pub batch_size: usize,
+    pub group_evaluator: Option<GroupEvaluator<T>>,
+    pub repeat_evaluations: usize,
+    pub stochastic_fitness: bool,
+    pub racing: Option<Racing>,
+    pub prior: Option<PopulationPrior<C>>,
+    pub seeds: Option<Seeds<C, T>>,
+    pub subscribers: Vec<Arc<dyn Subscriber<T>>>,
+    pub post_run: Vec<PostRun<C, T>>,
     pub problem: Option<Arc<Box<dyn Problem<C, T>>>>,
+    pub shaping: Option<FitnessShaping<C>>,
+    pub speciation: Option<Speciation<C>>,
+    pub memetic: Option<Memetic<C, T>>,
+    pub hall_of_fame: Option<HallOfFame<T>>,
+    pub elite_archive: Option<usize>,
+    pub steady_state: Option<SteadyState<C>>,
+    pub control: Option<ControlPanel>,
+    pub restart: Option<Restart>,
+    pub recorder: Option<Recorder<T>>,
+    pub gene_value: Option<GeneValue<C>>,
+    pub embedding: Option<(EmbeddingTrace, GeneValue<C>)>,
+    pub memory_budget: Option<MemoryBudget>,
+    pub genotype_metrics: Vec<(&'static str, GenotypeMetric<C>)>,
+    pub complexity: Vec<ComplexityFn<C>>,
+    pub constraints: Vec<ConstraintEntry<T>>,
+    pub schema: Option<GeneSchema>,
+    pub calibration: Option<Calibration>,
+    pub calibrated: Option<CalibrationResult>,
+    pub selector_benchmark: Option<SelectorBenchmark<C>>,
+    pub benchmarked: Option<SelectorBenchmarkResult>,
+    pub delta_fitness: Option<Arc<dyn DeltaFitness<C, T>>>,
+    pub fitness_cache: Option<Arc<FitnessCache<C>>>,
+    pub rng: Option<RngHandle>,
+    pub checkpointing: Option<(usize, CheckpointWriter<C>)>,
+    pub sampling: Option<(usize, Sampler<C>)>,
+    pub resume: Option<CheckpointReader<C>>,
+    pub resumed: Option<Checkpoint<C>>,
 }
 
 impl<C, T> GeneticEngineParams<C, T>
@@ -69,6 +130,7 @@ where
     pub fn new() -> Self {
         GeneticEngineParams {
             population_size: 100,
+            population_schedule: None,
             max_age: 20,
             offspring_fraction: 0.8,
             min_front_size: 800,
@@ -81,7 +143,45 @@ where
             codex: None,
             population: None,
             fitness_fn: None,
+            objective_fns: Vec::new(),
+            batch_fitness_fn: None,
+            batch_size: 1,
+            group_evaluator: None,
+            repeat_evaluations: 1,
+            stochastic_fitness: false,
+            racing: None,
+            prior: None,
+            seeds: None,
+            subscribers: Vec::new(),
+            post_run: Vec::new(),
             problem: None,
+            shaping: None,
+            speciation: None,
+            memetic: None,
+            hall_of_fame: None,
+            elite_archive: None,
+            steady_state: None,
+            control: None,
+            restart: None,
+            recorder: None,
+            gene_value: None,
+            embedding: None,
+            memory_budget: None,
+            genotype_metrics: Vec::new(),
+            complexity: Vec::new(),
+            constraints: Vec::new(),
+            schema: None,
+            calibration: None,
+            calibrated: None,
+            selector_benchmark: None,
+            benchmarked: None,
+            delta_fitness: None,
+            fitness_cache: None,
+            rng: None,
+            checkpointing: None,
+            sampling: None,
+            resume: None,
+            resumed: None,
         }
     }
 
@@ -95,6 +195,13 @@ where
         self
     }
 
+    /// Let the population size change over the run according to `schedule` (see `PopulationSchedule`).
+    /// The initial population is still `population_size` individuals. Default is a fixed size.
+    pub fn population_schedule(mut self, schedule: PopulationSchedule) -> Self {
+        self.population_schedule = Some(schedule);
+        self
+    }
+
     /// Set the maximum age of an individual in the population. Default is 25.
     pub fn max_age(mut self, max_age: i32) -> Self {
         if max_age < 1 {
@@ -109,7 +216,7 @@ where
     /// Default is 0.8. This is a value from 0...=1 that represents the fraction of
     /// population that will be replaced by offspring each generation. The remainder will 'survive' to the next generation.
     pub fn offspring_fraction(mut self, offspring_fraction: f32) -> Self {
-        if offspring_fraction < 0.0 || offspring_fraction > 1.0 {
+        if !(0.0..=1.0).contains(&offspring_fraction) {
             panic!("offspring_fraction must be between 0 and 1");
         }
 
@@ -119,6 +226,10 @@ where
 
     /// Set the codex that will be used to encode and decode the genotype of the population.
     pub fn codex<E: Codex<C, T> + 'static>(mut self, codex: E) -> Self {
+        if let Some(schema) = codex.schema() {
+            self.schema = Some(schema);
+        }
+
         self.codex = Some(Arc::new(Box::new(codex)));
         self
     }
@@ -129,6 +240,13 @@ where
         self
     }
 
+    /// Describe the genes of the genotype (see `GeneSchema`). A codex with a schema attached sets
+    /// this already - it's mainly for problems, which don't carry one.
+    pub fn schema(mut self, schema: GeneSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
     /// Set the population of the genetic engine. This is useful if you want to provide a custom population.
     /// If this is not set, the genetic engine will create a new population of `population_size` using the codex.
     pub fn population(mut self, population: Population<C>) -> Self {
@@ -148,6 +266,274 @@ where
         self
     }
 
+    /// Set the fitness function, naming the type it takes - `fitness_fn_for::<Vec<f32>, _>(...)`. The
+    /// closure's argument doesn't need a type annotation, and if the codex decodes to a different type the
+    /// compiler says which one instead of failing to infer the closure's types.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+    ///     .minimizing()
+    ///     .fitness_fn_for::<Vec<Vec<i32>>, _>(|geno| geno[0].iter().sum::<i32>())
+    ///     .build();
+    ///
+    /// let result = engine.run(|ctx| ctx.index > 5);
+    /// ```
+    ///
+    /// The codex decodes to `Vec<Vec<i32>>`, not `Vec<i32>`:
+    /// ``` compile_fail
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+    ///     .fitness_fn_for::<Vec<i32>, _>(|geno| geno.iter().sum::<i32>())
+    ///     .build();
+    /// ```
+    pub fn fitness_fn_for<X, S>(self, fitness_func: impl Fn(X) -> S + Send + Sync + 'static) -> Self
+    where
+        T: FitnessInput<X>,
+        S: Into<Score>,
+    {
+        self.fitness_fn(move |value: T| fitness_func(value.into_input()))
+    }
+
+    /// Set a fitness function that can score an altered individual from its parent's score and the genes
+    /// that changed, instead of from scratch (see `DeltaFitness`). Replaces the `fitness_fn`. Can't be
+    /// combined with `repeat_evaluations` or complexity objectives, whose scores aren't a sum over the genes.
+    pub fn delta_fitness(mut self, delta_fitness: impl DeltaFitness<C, T> + 'static) -> Self {
+        let delta_fitness = Arc::new(delta_fitness);
+        let fitness = Arc::clone(&delta_fitness);
+
+        self.fitness_fn = Some(Arc::new(move |value: T| fitness.fitness(value)));
+        self.delta_fitness = Some(delta_fitness);
+        self
+    }
+
+    /// Set a fitness function that is given reusable scratch state along with the value to evaluate.
+    /// The state is created with `init` at most once per evaluation running at the same time, and is
+    /// handed back to later evaluations instead of being created again (see `ScratchPool`).
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    /// use radiate::scratch::FitnessCtx;
+    ///
+    /// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+    ///     .num_threads(2)
+    ///     .fitness_fn_with_ctx(
+    ///         || Vec::<i32>::with_capacity(5),
+    ///         |buffer: &mut FitnessCtx<Vec<i32>>, geno: Vec<Vec<i32>>| {
+    ///             buffer.clear();
+    ///             buffer.extend(geno[0].iter().map(|x| x * x));
+    ///             buffer.iter().sum::<i32>()
+    ///         },
+    ///     )
+    ///     .build();
+    ///
+    /// let result = engine.run(|ctx| ctx.index > 5);
+    /// ```
+    pub fn fitness_fn_with_ctx<S, R>(
+        self,
+        init: impl Fn() -> S + Send + Sync + 'static,
+        fitness_func: impl Fn(&mut FitnessCtx<S>, T) -> R + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: Send + 'static,
+        R: Into<Score>,
+    {
+        let pool = ScratchPool::new(init);
+        self.fitness_fn(move |value| fitness_func(&mut pool.get(), value))
+    }
+
+    /// Add one part of a multi-part score, as an alternative to a single `fitness_fn`. Each call adds the
+    /// next value of the `Score`, and every part of an individual is evaluated as its own job on the
+    /// thread pool - useful when the parts are independent, expensive computations (e.g. separate
+    /// simulations) that would otherwise run one after the other inside a single fitness function.
+    /// The parts are usually paired with `multi_objective`, in the same order.
+    pub fn objective_fn(mut self, objective_fn: impl Fn(T) -> f32 + Send + Sync + 'static) -> Self {
+        self.objective_fns.push(Arc::new(objective_fn));
+        self
+    }
+
+    /// Set a fitness function that scores a whole batch of individuals at once, as an alternative to a
+    /// single `fitness_fn` - useful when the evaluation is a simulation that runs many individuals together.
+    /// The unscored individuals are evaluated in batches of at most `batch_size`, each batch as its own job
+    /// on the thread pool. The function returns one result per individual, in order. Scores may have any
+    /// number of values, so batches work with `multi_objective` too. An `Err` (or a missing result) fails
+    /// only that individual: it gets the worst possible score, the error is kept in its metadata and it is
+    /// left out of the Pareto front. Panics if `batch_size` is 0.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 2, 0.0, 1.0))
+    ///     .population_size(20)
+    ///     .multi_objective(vec![Optimize::Minimize, Optimize::Minimize])
+    ///     .offspring_selector(TournamentSelector::new(3))
+    ///     .survivor_selector(NSGA2Selector::new())
+    ///     .batch_fitness_fn(8, |batch: Vec<Vec<Vec<f32>>>| {
+    ///         batch
+    ///             .iter()
+    ///             .map(|geno| match geno[0][0] > 0.99 {
+    ///                 true => Err("simulation diverged".to_string()),
+    ///                 false => Ok(vec![geno[0][0], 1.0 - geno[0][1]]),
+    ///             })
+    ///             .collect()
+    ///     })
+    ///     .build();
+    ///
+    /// let result = engine.run(|ctx| ctx.index > 5);
+    /// assert!(!result.front.lock().unwrap().scores().is_empty());
+    /// ```
+    pub fn batch_fitness_fn<S: Into<Score>>(
+        mut self,
+        batch_size: usize,
+        batch_fitness_func: impl Fn(Vec<T>) -> Vec<Result<S, String>> + Send + Sync + 'static,
+    ) -> Self {
+        if batch_size < 1 {
+            panic!("batch_size must be greater than 0");
+        }
+
+        self.batch_size = batch_size;
+        self.batch_fitness_fn = Some(Arc::new(move |batch| {
+            batch_fitness_func(batch)
+                .into_iter()
+                .map(|result| result.map(Into::into))
+                .collect()
+        }));
+        self
+    }
+
+    /// Cache the scores of the last `capacity` distinct genotypes evaluated, keyed by their genes,
+    /// so an individual identical to one evaluated before isn't evaluated again (see `FitnessCache`) - for
+    /// expensive fitness functions over genomes with few distinct values. The hits and misses of every
+    /// generation are recorded as the `Cache Hits` and `Cache Misses` metrics. The fitness function must
+    /// be deterministic, so a cache can't be combined with `stochastic_fitness`, `racing` or a
+    /// `group_evaluator`. Default is no cache. Panics if `capacity` is 0.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(BitCodex::new(1, 3))
+    ///     .population_size(50)
+    ///     .cache_fitness(16)
+    ///     .fitness_fn(|geno: Vec<Vec<bool>>| geno[0].iter().filter(|bit| **bit).count())
+    ///     .build();
+    ///
+    /// let result = engine.run(|ctx| ctx.index >= 5);
+    /// assert!(result.metrics.get(metric_names::CACHE_HITS).is_some());
+    /// ```
+    pub fn cache_fitness(mut self, capacity: usize) -> Self
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        self.fitness_cache = Some(Arc::new(FitnessCache::new(capacity)));
+        self
+    }
+
+    /// Evaluate individuals that interact with each other together, in groups, with a `GroupEvaluator`
+    /// instead of a `fitness_fn`. Takes precedence over `racing`. Default is no group evaluation.
+    pub fn group_evaluator(mut self, group_evaluator: GroupEvaluator<T>) -> Self {
+        self.group_evaluator = Some(group_evaluator);
+        self
+    }
+
+    /// Evaluate every individual `repeats` times and score it with the mean of the evaluations, for noisy
+    /// fitness functions. The score keeps the variance of the evaluations (see `Score::from_samples`), so
+    /// confidence intervals are reported for the best individual and selectors can compare individuals
+    /// with a significance test (e.g. `TournamentSelector::with_significance`). Applies to the
+    /// `fitness_fn` - each `objective_fn` part is evaluated once. Default is 1. Panics if `repeats` is 0.
+    pub fn repeat_evaluations(mut self, repeats: usize) -> Self {
+        if repeats < 1 {
+            panic!("repeats must be greater than 0");
+        }
+
+        self.repeat_evaluations = repeats;
+        self
+    }
+
+    /// Re-evaluate every individual every generation. By default an individual is only evaluated when
+    /// it is new or an alterer changed it, which assumes the fitness function is deterministic - set this
+    /// for stochastic (noisy) fitness functions so survivors don't keep a lucky score forever.
+    pub fn stochastic_fitness(mut self) -> Self {
+        self.stochastic_fitness = true;
+        self
+    }
+
+    /// Evaluate new individuals with a `Racing` evaluation - replication by replication, dropping the
+    /// individuals that are clearly worse early so the evaluation budget goes to the close contenders.
+    /// Does not apply when the score is evaluated in parts (see `objective_fn`). Default is no racing.
+    pub fn racing(mut self, racing: Racing) -> Self {
+        self.racing = Some(racing);
+        self
+    }
+
+    /// Seed the initial population from a `PopulationPrior` of an earlier run on a problem with the same
+    /// codex shape. The population starts with the prior's elites, then the prior's sample fraction of the
+    /// remaining individuals is sampled from the prior and the rest is encoded by the codex. Ignored if a
+    /// population is set. Default is no prior.
+    pub fn prior(mut self, prior: PopulationPrior<C>) -> Self {
+        self.prior = Some(prior);
+        self
+    }
+
+    /// Start the run from known solutions - genotypes, decoded values or a population (see `Seeds`). The
+    /// seeds replace the first individuals of the initial population, the rest is built as usual. Seeds
+    /// beyond the population size are ignored. Default is no seeds.
+    pub fn seed_population(mut self, seeds: impl Into<Seeds<C, T>>) -> Self {
+        self.seeds = Some(seeds.into());
+        self
+    }
+
+    /// Add a subscriber to the engine's events - the start of a run, every finished generation and the
+    /// end of a run. Subscribers are called on the engine's thread, so wrap slow ones in a
+    /// `BufferedSubscriber`. Default is no subscribers.
+    pub fn subscribe(mut self, subscriber: impl Subscriber<T> + 'static) -> Self {
+        self.subscribers.push(Arc::new(subscriber));
+        self
+    }
+
+    /// Add a stage that post-processes the result of a run - e.g. simplifying or compressing the best
+    /// individual. Stages run in the order they were added, when the run stops and before the `Stop`
+    /// event is published, and may change the context's `best`, `score` and `metrics`. Default is no stages.
+    pub fn post_run(
+        mut self,
+        stage: impl Fn(&mut EngineContext<C, T>) + Send + Sync + 'static,
+    ) -> Self {
+        self.post_run.push(Arc::new(move |ctx| {
+            stage(ctx);
+            Ok(())
+        }));
+        self
+    }
+
+    /// Write the artifacts of the run into the `RunArtifacts`' directory - the metrics of every generation
+    /// as the engine runs, and the front, a report and the best individual when it stops, as a post-run
+    /// stage. Add it after the other post-run stages to keep their result. Checkpoints go into the
+    /// directory with `.checkpoint_every(generations, artifacts.checkpoint())`. An artifact that can't be
+    /// written doesn't stop the run - the failure is reported as an `EngineEvent::Error` when the run
+    /// stops. Default is no artifacts.
+    pub fn artifacts(self, artifacts: RunArtifacts) -> Self
+    where
+        T: Debug,
+    {
+        let writer = artifacts.clone();
+        let mut params = self.subscribe(artifacts);
+        params.post_run.push(Arc::new(move |ctx| {
+            writer.write_result(ctx).map_err(|error| {
+                std::io::Error::other(format!(
+                    "Failed to write artifacts to {}: {}",
+                    writer.dir().display(),
+                    error
+                ))
+            })
+        }));
+        params
+    }
+
     /// Set the survivor selector of the genetic engine. This is the selector that will be used to select the survivors of the population.
     /// Default is TournamentSelector with a group size of 3.
     pub fn survivor_selector<S: Select<C> + 'static>(mut self, selector: S) -> Self {
@@ -188,6 +574,71 @@ where
         self
     }
 
+    /// Add the `Complexity` of the genotype as an extra objective to minimize. Its value is appended to
+    /// every score after the problem's own values, and a `Minimize` is appended to the objective - a single
+    /// objective becomes a multi-objective one - so pair it with selectors that support multiple objectives,
+    /// such as `NSGA2Selector` for the survivors and `TournamentSelector` for the offspring. Can be called more than once to add several measures.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 5, -1.0, 1.0))
+    ///     .population_size(20)
+    ///     .fitness_fn(|genes: Vec<Vec<f32>>| genes[0][0])
+    ///     .complexity_objective(Complexity::L1)
+    ///     .offspring_selector(TournamentSelector::new(3))
+    ///     .survivor_selector(NSGA2Selector::new())
+    ///     .build();
+    ///
+    /// let result = engine.run(|ctx| ctx.index > 5);
+    /// assert_eq!(result.population[0].score().unwrap().values.len(), 2);
+    /// ```
+    pub fn complexity_objective(mut self, complexity: Complexity) -> Self
+    where
+        C: Measurable,
+    {
+        self.complexity
+            .push(Arc::new(move |genotype| complexity.measure(genotype)));
+        self
+    }
+
+    /// Add a `Constraint` on the decoded values, handled as the `mode` says - as a penalty, by repairing
+    /// the individuals, by discarding them or by ranking every feasible individual above the infeasible
+    /// ones (see `ConstraintMode`). Constraints are applied to the problem's scores, so they work with
+    /// any selector and with multiple objectives. Can be called more than once. Default is no constraints.
+    ///
+    /// # Example
+    /// ``` rust
+    /// use radiate::*;
+    ///
+    /// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 2, 0.0, 1.0))
+    ///     .population_size(20)
+    ///     .fitness_fn(|value: Vec<Vec<f32>>| value[0][0] + value[0][1])
+    ///     .constraint(
+    ///         |value: &Vec<Vec<f32>>| value[0][0] + value[0][1] - 1.0,
+    ///         ConstraintMode::Feasibility,
+    ///     )
+    ///     .build();
+    ///
+    /// let result = engine.run(|ctx| ctx.index > 10);
+    /// assert!(result.best[0][0] + result.best[0][1] <= 1.0);
+    /// ```
+    pub fn constraint(
+        mut self,
+        constraint: impl Constraint<T> + 'static,
+        mode: ConstraintMode,
+    ) -> Self {
+        if let ConstraintMode::Penalty(weight) = mode {
+            if weight < 0.0 {
+                panic!("weight must not be negative");
+            }
+        }
+
+        self.constraints.push((Arc::new(constraint), mode));
+        self
+    }
+
     pub fn front_size(mut self, min_size: usize, max_size: usize) -> Self {
         if min_size > max_size {
             panic!("min_size must be less than or equal to max_size");
@@ -204,10 +655,264 @@ where
         self
     }
 
+    /// Set the fitness shaping of the genetic engine. The shaping is applied to a copy of the population's
+    /// scores before selection, so the selectors see the shaped scores while the raw scores are kept
+    /// for the best individual and metrics. Default is no shaping.
+    pub fn fitness_shaping(mut self, shaping: FitnessShaping<C>) -> Self {
+        self.shaping = Some(shaping);
+        self
+    }
+
+    /// Refine the offspring of every generation with a local search (see `Memetic`), e.g.
+    /// `Memetic::new(search).baldwinian()`. Default is no local search.
+    pub fn memetic(mut self, memetic: Memetic<C, T>) -> Self {
+        self.memetic = Some(memetic);
+        self
+    }
+
+    /// Group the population into species before selection and share the fitness within them (see
+    /// `Speciation`). The species are available on the `EngineContext` as `species`. Default is no
+    /// speciation.
+    pub fn speciation(mut self, speciation: Speciation<C>) -> Self {
+        self.speciation = Some(speciation);
+        self
+    }
+
+    /// Set the hall of fame of the genetic engine. At the end of each generation the champion of the
+    /// population is added to the hall of fame and the win rate of the games played against it
+    /// during evaluation is recorded as a metric. Default is no hall of fame.
+    pub fn hall_of_fame(mut self, hall_of_fame: HallOfFame<T>) -> Self {
+        self.hall_of_fame = Some(hall_of_fame);
+        self
+    }
+
+    /// Evolve the population in steady-state mode: every step breeds `replacements` offspring and merges
+    /// them into the population with the replacement `policy`, instead of replacing the population with a
+    /// new generation (see `SteadyState`). Default is generational evolution.
+    pub fn steady_state(mut self, replacements: usize, policy: Replacement<C>) -> Self {
+        self.steady_state = Some(SteadyState::new(replacements, policy));
+        self
+    }
+
+    /// Restart part of the population every `window` generations the best score doesn't improve,
+    /// replacing the individuals chosen by the `strategy` with new individuals from the codex while
+    /// keeping the best (see `Restart`). Default is no restarts.
+    pub fn restart_on_stagnation(mut self, window: i32, strategy: RestartStrategy) -> Self {
+        self.restart = Some(Restart::new(window, strategy));
+        self
+    }
+
+    /// Steer the engine while it runs with a `ControlPanel`. Changes made through the panel are applied
+    /// before each generation and recorded in the metrics. Default is no panel.
+    pub fn control(mut self, panel: ControlPanel) -> Self {
+        self.control = Some(panel);
+        self
+    }
+
+    /// Keep the best `capacity` individuals ever seen in an `EliteArchive`, available on the
+    /// `EngineContext` as `elites`. The archive is updated with the population at the end of each
+    /// generation and is independent of survivor selection. Default is no archive.
+    pub fn elite_archive(mut self, capacity: usize) -> Self {
+        if capacity < 1 {
+            panic!("capacity must be greater than 0");
+        }
+
+        self.elite_archive = Some(capacity);
+        self
+    }
+
+    /// Set the recorder of the genetic engine. At the end of each generation the best individual of
+    /// that generation (and only that individual) is decoded and passed to the recorder along with
+    /// an empty `Recording`. This allows the recorder to re-run the evaluation and emit a trace
+    /// of it without paying that cost for every individual. The recording is available on the
+    /// `EngineContext`. Default is no recorder.
+    pub fn recorder<F>(mut self, recorder: F) -> Self
+    where
+        F: Fn(T, &mut Recording) + Send + Sync + 'static,
+    {
+        self.recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Track how the population moves through gene space from one generation to the next. `gene_value` maps
+    /// a gene to a number (e.g. `|gene| *gene.allele()` for a `FloatGene`). Each generation the per gene
+    /// mean and variance of the population are compared to the previous generation and recorded as the
+    /// `Mean Allele Change`, `Centroid Drift` and `Variance Shrinkage` metrics. Default is no tracking.
+    pub fn population_movement<F>(mut self, gene_value: F) -> Self
+    where
+        F: Fn(&C::Gene) -> f32 + Send + Sync + 'static,
+    {
+        self.gene_value = Some(Arc::new(gene_value));
+        self
+    }
+
+    /// Project the population to a low-dimensional embedding every generation (or every few, see
+    /// `EmbeddingTrace::every`) and add the frame to the `trace`, for visualizing how the population moves
+    /// through the search space. `gene_value` maps a gene to a number, like for `population_movement`.
+    /// Default is no embedding.
+    pub fn population_embedding<F>(mut self, trace: EmbeddingTrace, gene_value: F) -> Self
+    where
+        F: Fn(&C::Gene) -> f32 + Send + Sync + 'static,
+    {
+        self.embedding = Some((trace, Arc::new(gene_value)));
+        self
+    }
+
+    /// Record a metric computed from every individual's genotype. Each generation the value of every
+    /// individual is recorded as a sequence under `name`, so the metric shows the distribution over the
+    /// population (e.g. the size of evolved graphs). Can be called any number of times.
+    pub fn genotype_metric<F>(mut self, name: &'static str, metric: F) -> Self
+    where
+        F: Fn(&Genotype<C>) -> f32 + Send + Sync + 'static,
+    {
+        self.genotype_metrics.push((name, Arc::new(metric)));
+        self
+    }
+
+    /// Track the approximate memory of the population against a `MemoryBudget`. Every generation the
+    /// population's `MemoryFootprint` is reported to the budget and recorded as the `Population Memory` metric,
+    /// so `SpillArchive`s sharing the budget spill to disk as the population grows. Default is no budget.
+    pub fn memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.memory_budget = Some(budget);
+        self
+    }
+
     /// Set the thread pool of the genetic engine. This is the thread pool that will be used to execute the fitness function in parallel.
     /// Some fitness functions may be computationally expensive and can benefit from parallel execution.
     pub fn num_threads(mut self, num_threads: usize) -> Self {
-        self.thread_pool = ThreadPool::new(num_threads);
+        self.thread_pool =
+            ThreadPool::new(num_threads).with_dedicated(self.thread_pool.num_dedicated());
+        self
+    }
+
+    /// Add dedicated threads to the thread pool for fitness evaluations. When set, every evaluation is pinned to
+    /// one of these threads, leaving the regular workers free for the engine's own background work (e.g. updating the front),
+    /// which is submitted with a high priority. Default is 0 - evaluations share the regular workers.
+    pub fn dedicated_threads(mut self, num_threads: usize) -> Self {
+        self.thread_pool = match self.thread_pool.spawner() {
+            Some(spawner) => {
+                let spawner = Arc::clone(spawner);
+                ThreadPool::delegating(move |job| spawner(job))
+            }
+            None => ThreadPool::new(self.thread_pool.size()),
+        }
+        .with_dedicated(num_threads);
+        self
+    }
+
+    /// Run the engine's jobs on an existing executor instead of the engine's own threads, e.g. rayon's global pool
+    /// with `.delegate_to(|job| rayon::spawn(job))`. This avoids running two thread pools side by side in applications
+    /// that already parallelize with another executor. Overrides `num_threads`, dedicated threads are kept.
+    pub fn delegate_to<S>(mut self, spawn: S) -> Self
+    where
+        S: Fn(Job) + Send + Sync + 'static,
+    {
+        self.thread_pool =
+            ThreadPool::delegating(spawn).with_dedicated(self.thread_pool.num_dedicated());
+        self
+    }
+
+    /// Calibrate the executor when the engine is built - time a sample of evaluations under the candidate
+    /// thread counts and batch sizes of the `Calibration`, and run with the fastest. Overrides `num_threads`
+    /// (and the batch size of `batch_fitness_fn`) with the configuration it picks. Default is no calibration.
+    pub fn calibrate(mut self, calibration: Calibration) -> Self {
+        self.calibration = Some(calibration);
+        self
+    }
+
+    /// Benchmark the survivor and offspring selectors (and any candidates) on synthetic landscapes when
+    /// the engine is built, reporting their selection intensity and diversity loss (see
+    /// `SelectorBenchmark`). Costs no fitness evaluations. Default is no benchmark.
+    pub fn benchmark_selectors(mut self, benchmark: SelectorBenchmark<C>) -> Self {
+        self.selector_benchmark = Some(benchmark);
+        self
+    }
+
+    /// Set the random number generator of the genetic engine - any `RngCore`, e.g. a `Xoshiro` generator for
+    /// speed or a `ChaCha` generator for reproducibility across platforms. The engine hands it to the alterers
+    /// and scopes it (see `random_provider::RngHandle`) around building the population and every generation,
+    /// so everything the engine runs draws from it instead of the global `random_provider`. Default is the
+    /// global generator.
+    ///
+    /// **Note**: Evaluations running on several threads share the generator in whatever order they
+    /// draw from it, so a run is only reproducible if the fitness function doesn't draw random numbers
+    /// or evaluates on a single thread.
+    pub fn rng(mut self, rng: impl RngCore + Send + 'static) -> Self {
+        self.rng = Some(RngHandle::new(rng));
+        self
+    }
+
+    /// Seed the genetic engine with its own `ChaCha12Rng` - the generator behind `StdRng`, so this draws the
+    /// same numbers as `.rng(StdRng::seed_from_u64(seed))`, but its state can be stored in checkpoints.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Some(RngHandle::seeded(seed));
+        self
+    }
+
+    /// Write a `Checkpoint` of the engine to `path` after every `generations`-th generation, replacing the
+    /// previous one, so the run can be resumed with `resume_from` after a crash. A checkpoint that can't be
+    /// written doesn't stop the run - the previous one is kept and the failure is reported as an
+    /// `EngineEvent::Error`. If the engine has no generator of its own (see `seed`), it gets one seeded from
+    /// the operating system, so the checkpoints can store it. Panics if `generations` is 0.
+    #[cfg(feature = "wire")]
+    pub fn checkpoint_every(mut self, generations: usize, path: impl Into<PathBuf>) -> Self
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        if generations < 1 {
+            panic!("generations must be greater than 0");
+        }
+
+        let path = path.into();
+        self.checkpointing = Some((
+            generations,
+            Arc::new(move |checkpoint: &Checkpoint<C>| checkpoint.write(&path)),
+        ));
+        self
+    }
+
+    /// Record the population of every `generations`-th generation with the `SampleWriter`, which is
+    /// flushed as a post-run stage when the engine stops. Samples that can't be written don't stop the
+    /// run - the failure is reported as an `EngineEvent::Error` when the run stops. Panics if
+    /// `generations` is 0.
+    #[cfg(feature = "wire")]
+    pub fn sample_every(mut self, generations: usize, writer: SampleWriter) -> Self
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        if generations < 1 {
+            panic!("generations must be greater than 0");
+        }
+
+        let sampler = writer.clone();
+        self.sampling = Some((
+            generations,
+            Arc::new(move |index: i32, population: &Population<C>| {
+                sampler.write(index, population)
+            }),
+        ));
+
+        self.post_run.push(Arc::new(move |_| {
+            writer.flush().map_err(|error| {
+                std::io::Error::other(format!("Failed to write samples: {}", error))
+            })
+        }));
+        self
+    }
+
+    /// Resume the run from the `Checkpoint` at `path` - the engine starts from its population, generation,
+    /// metrics and random number generator instead of a new population. The checkpoint is read when the
+    /// engine is built, which panics if it can't be read or doesn't match the codex. See also
+    /// `GeneticEngine::from_checkpoint`.
+    #[cfg(feature = "wire")]
+    pub fn resume_from(mut self, path: impl Into<PathBuf>) -> Self
+    where
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        let path = path.into();
+        self.resume = Some(Arc::new(move |template: &Genotype<C>| {
+            Checkpoint::read(&path, template)
+        }));
         self
     }
 
@@ -218,34 +923,272 @@ where
                 panic!("Codex not set");
             }
 
-            if self.fitness_fn.is_none() {
-                panic!("Fitness function not set");
+            if self.fitness_fn.is_some() && !self.objective_fns.is_empty() {
+                panic!("Set either a fitness function or objective functions, not both");
+            }
+
+            if let Some(group_evaluator) = self.group_evaluator.clone() {
+                if self.fitness_fn.is_some() || self.batch_fitness_fn.is_some() {
+                    panic!("Set either a group evaluator or a fitness function, not both");
+                }
+
+                // Outside of the engine's group evaluation (e.g. `Problem::eval`) an individual plays alone.
+                self.fitness_fn = Some(Arc::new(move |value: T| {
+                    group_evaluator.evaluate(&[value]).pop().unwrap()
+                }));
+            }
+
+            if let Some(batch_fitness_fn) = self.batch_fitness_fn.clone() {
+                if self.fitness_fn.is_some() || !self.objective_fns.is_empty() {
+                    panic!("Set either a batch fitness function or a fitness function, not both");
+                }
+
+                let problem = BatchEngineProblem {
+                    codex: self.codex.clone().unwrap(),
+                    batch_fitness_fn,
+                    batch_size: self.batch_size,
+                };
+
+                return self.problem(problem).build();
             }
 
+            let fitness_fn = match self.fitness_fn.clone() {
+                Some(fitness_fn) if self.repeat_evaluations > 1 => {
+                    let repeats = self.repeat_evaluations;
+                    Arc::new(move |value: T| {
+                        let samples = (0..repeats)
+                            .map(|_| fitness_fn(value.clone()))
+                            .collect::<Vec<Score>>();
+                        Score::from_samples(&samples)
+                    })
+                }
+                Some(fitness_fn) => fitness_fn,
+                None if !self.objective_fns.is_empty() => {
+                    let parts = self.objective_fns.clone();
+                    Arc::new(move |value: T| {
+                        Score::from_vec(parts.iter().map(|part| part(value.clone())).collect())
+                    })
+                }
+                None => panic!("Fitness function not set"),
+            };
+
             let problem = EngineProblem {
                 codex: self.codex.clone().unwrap(),
-                fitness_fn: self.fitness_fn.clone().unwrap(),
+                fitness_fn,
+                parts: self.objective_fns.clone(),
             };
 
             self.problem(problem).build()
         } else {
-            self.build_population();
-            self.build_alterer();
+            if self.delta_fitness.is_some()
+                && (self.repeat_evaluations > 1 || !self.complexity.is_empty())
+            {
+                panic!("A delta fitness can't be combined with repeated evaluations or complexity objectives");
+            }
+
+            if self.checkpointing.is_some() && self.rng.is_none() {
+                self.rng = Some(RngHandle::from_entropy());
+            }
+
+            match self.rng.clone() {
+                Some(rng) => rng.scope(|| self.build_parts()),
+                None => self.build_parts(),
+            }
+
             GeneticEngine::new(self)
         }
     }
 
+    fn build_parts(&mut self) {
+        self.build_complexity();
+        self.build_constraints();
+        self.build_calibration();
+        self.build_cache();
+        self.build_selector_benchmark();
+        self.build_population();
+        self.build_resume();
+        self.build_alterer();
+    }
+
+    /// Wrap the problem so its scores include the complexity objectives, and add them to the objective.
+    fn build_complexity(&mut self) {
+        if self.complexity.is_empty() {
+            return;
+        }
+
+        let complexity = std::mem::take(&mut self.complexity);
+        let added = vec![Optimize::Minimize; complexity.len()];
+
+        self.objective = match &self.objective {
+            Objective::Single(optimize) => Objective::Multi([vec![*optimize], added].concat()),
+            Objective::Multi(objectives) => Objective::Multi([objectives.clone(), added].concat()),
+        };
+
+        self.problem = Some(Arc::new(Box::new(ComplexityProblem {
+            problem: self.problem.take().unwrap(),
+            complexity,
+        })));
+    }
+
+    /// Wrap the problem so its scores take the constraints into account. Comes after the complexity
+    /// objectives, so infeasible individuals are worse on those too.
+    fn build_constraints(&mut self) {
+        if self.constraints.is_empty() {
+            return;
+        }
+
+        let problem = self.problem.take().unwrap();
+        let repairs = self
+            .constraints
+            .iter()
+            .any(|(_, mode)| *mode == ConstraintMode::Repair);
+
+        if repairs
+            && problem
+                .encode_value(&problem.decode(&problem.encode()))
+                .is_none()
+        {
+            panic!("Repair constraints need a codex that can encode values (see `Codex::encode_value`)");
+        }
+
+        self.problem = Some(Arc::new(Box::new(ConstraintProblem {
+            problem,
+            constraints: self.constraints.clone(),
+            objective: self.objective.clone(),
+        })));
+    }
+
+    /// Run the calibration (if any) and switch to the thread pool and batch size it picked.
+    fn build_calibration(&mut self) {
+        let Some(calibration) = self.calibration.take() else {
+            return;
+        };
+
+        let problem = self.problem.take().unwrap();
+        let fixed = self.thread_pool.spawner().is_some() || self.thread_pool.num_dedicated() > 0;
+        let tune_batches =
+            problem.parts() == 1 && self.racing.is_none() && self.group_evaluator.is_none();
+
+        let result = calibration.run(
+            &problem,
+            fixed.then_some(&self.thread_pool),
+            self.population_size,
+            tune_batches,
+        );
+
+        if !fixed {
+            self.thread_pool = ThreadPool::new(result.threads);
+        }
+
+        self.problem = match result.batch_size == problem.batch_size() {
+            true => Some(problem),
+            false => Some(Arc::new(Box::new(BatchedProblem {
+                problem,
+                batch_size: result.batch_size,
+            }))),
+        };
+
+        self.calibrated = Some(result);
+    }
+
+    /// Wrap the problem so its scores are looked up in (and added to) the fitness cache. Comes after the
+    /// calibration so its sample evaluations aren't served from the cache.
+    fn build_cache(&mut self) {
+        let Some(cache) = self.fitness_cache.clone() else {
+            return;
+        };
+
+        if self.stochastic_fitness || self.racing.is_some() || self.group_evaluator.is_some() {
+            panic!("A fitness cache can't be combined with stochastic fitness, racing or group evaluation");
+        }
+
+        self.problem = Some(Arc::new(Box::new(CachedProblem {
+            problem: self.problem.take().unwrap(),
+            cache,
+        })));
+    }
+
+    /// Run the selector benchmark (if any).
+    fn build_selector_benchmark(&mut self) {
+        let Some(benchmark) = self.selector_benchmark.take() else {
+            return;
+        };
+
+        self.benchmarked = Some(benchmark.run(
+            self.problem.as_ref().unwrap(),
+            self.survivor_selector.as_ref(),
+            self.offspring_selector.as_ref(),
+            &self.objective,
+            self.population_size,
+        ));
+    }
+
     /// Build the population of the genetic engine. This will create a new population using the codex if the population is not set.
     fn build_population(&mut self) {
         self.population = match &self.population {
-            None => Some(match self.problem.as_ref() {
-                Some(codex) => Population::from_fn(self.population_size, || {
+            None => Some(match (self.problem.as_ref(), self.prior.as_ref()) {
+                (Some(codex), Some(prior)) => {
+                    let elites = prior.len().min(self.population_size);
+                    let sampled = ((self.population_size - elites) as f32 * prior.sample_fraction())
+                        .round() as usize;
+
+                    (0..self.population_size)
+                        .map(|i| {
+                            let genotype = match i {
+                                i if i < elites => prior.elites()[i].clone(),
+                                i if i < elites + sampled => prior.sample(),
+                                _ => codex.encode(),
+                            };
+
+                            Phenotype::from_genotype(genotype, 0)
+                        })
+                        .collect()
+                }
+                (Some(codex), None) => Population::from_fn(self.population_size, || {
                     Phenotype::from_genotype(codex.encode(), 0)
                 }),
-                None => panic!("Codex not set"),
+                (None, _) => panic!("Codex not set"),
             }),
             Some(pop) => Some(pop.clone()),
         };
+
+        if let Some(seeds) = self.seeds.take() {
+            let problem = self.problem.as_ref().unwrap();
+            let population = self.population.as_mut().unwrap();
+            let genotypes = seeds.into_genotypes(problem.as_ref().as_ref(), &self.objective);
+
+            for (i, genotype) in genotypes.into_iter().take(population.len()).enumerate() {
+                population[i] = Phenotype::from_genotype(genotype, 0);
+            }
+        }
+    }
+
+    /// Read the checkpoint to resume from (if any) and continue from its population and generator.
+    fn build_resume(&mut self) {
+        let Some(resume) = self.resume.take() else {
+            return;
+        };
+
+        let template = self.problem.as_ref().unwrap().encode();
+        let mut checkpoint = match resume(&template) {
+            Ok(checkpoint) => checkpoint,
+            Err(error) => panic!("Failed to resume from checkpoint: {}", error),
+        };
+
+        if checkpoint.population.is_empty() {
+            panic!("Failed to resume from checkpoint: the population is empty");
+        }
+
+        self.population = Some(std::mem::replace(
+            &mut checkpoint.population,
+            Population::new(Vec::new()),
+        ));
+
+        if let Some(state) = &checkpoint.rng {
+            self.rng = Some(RngHandle::from_state(state));
+        }
+
+        self.resumed = Some(checkpoint);
     }
 
     /// Build the alterer of the genetic engine. This will create a new alterer if the alterer is not set.
@@ -261,3 +1204,13 @@ where
         self.alterers.push(mutator);
     }
 }
+
+impl<C, T> Default for GeneticEngineParams<C, T>
+where
+    C: Chromosome,
+    T: Clone + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,68 @@
+use super::codexes::{FloatCodex, PermutationCodex};
+use super::params::GeneticEngineParams;
+use super::GeneticEngine;
+use crate::{
+    alters, Alter, AlterAction, FloatChromosome, GaussianMutator, IntermediateCrossover,
+    PMXCrossover, PermutationChromosome, SwapMutator, TournamentSelector,
+};
+use std::ops::Range;
+
+/// Presets for common problem archetypes. Each preset returns a `GeneticEngineParams` with
+/// a codex, selectors, and alterers that work well for that kind of problem, so the only thing
+/// left to do is supply a fitness function and call `build`. Because the preset is just a
+/// `GeneticEngineParams`, any of its defaults can be overridden before building.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Minimize the sphere function in 5 dimensions.
+/// let engine = GeneticEngine::preset_continuous(5, -5.0..5.0)
+///     .minimizing()
+///     .fitness_fn(|genotype: Vec<Vec<f32>>| {
+///         genotype[0].iter().map(|x| x * x).sum::<f32>()
+///     })
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 10);
+/// ```
+impl GeneticEngine<FloatChromosome, Vec<Vec<f32>>> {
+    /// Create a `GeneticEngineParams` for a continuous (real valued) problem with `dim` variables,
+    /// each initialized within `range`. The genes are bounded by `range` as well, so individuals
+    /// that drift outside of it are considered invalid and replaced.
+    ///
+    /// Defaults:
+    /// * offspring_selector: TournamentSelector::new(3)
+    /// * alterers: IntermediateCrossover::new(0.5, 0.5), GaussianMutator::new(0.1)
+    pub fn preset_continuous(
+        dim: usize,
+        range: Range<f32>,
+    ) -> GeneticEngineParams<FloatChromosome, Vec<Vec<f32>>> {
+        let codex = FloatCodex::new(1, dim, range.start, range.end);
+
+        GeneticEngine::from_codex(codex)
+            .offspring_selector(TournamentSelector::new(3))
+            .alter(alters![
+                IntermediateCrossover::new(0.5, 0.5),
+                GaussianMutator::new(0.1)
+            ])
+    }
+}
+
+impl GeneticEngine<PermutationChromosome<usize>, Vec<usize>> {
+    /// Create a `GeneticEngineParams` for a permutation problem (e.g. TSP, scheduling) over the
+    /// indexes `0..n`. The alterers used here always produce valid permutations.
+    ///
+    /// Defaults:
+    /// * offspring_selector: TournamentSelector::new(3)
+    /// * alterers: PMXCrossover::new(0.4), SwapMutator::new(0.05)
+    pub fn preset_permutation(
+        n: usize,
+    ) -> GeneticEngineParams<PermutationChromosome<usize>, Vec<usize>> {
+        let codex = PermutationCodex::new((0..n).collect());
+
+        GeneticEngine::from_codex(codex)
+            .offspring_selector(TournamentSelector::new(3))
+            .alter(alters![PMXCrossover::new(0.4), SwapMutator::new(0.05)])
+    }
+}
@@ -0,0 +1,300 @@
+use super::{
+    random_provider, Chromosome, FloatChromosome, Gene, Genotype, Objective, Population, Problem,
+    Statistic,
+};
+use crate::BoundGene;
+
+/// A compact summary of a finished run that can start a new run on a related problem - one with the same
+/// codex shape. Distilling a population keeps its `top_k` best genotypes (the elites). New genotypes can be
+/// sampled from the elites' per-gene distribution (`sample`), so the new run starts around the region the
+/// old one converged to without being limited to exact copies of its elites.
+///
+/// Seed a new engine with `GeneticEngineParams::prior` - the initial population is then made of the elites,
+/// genotypes sampled from the prior and (for diversity) fresh genotypes from the codex.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let codex = IntCodex::new(1, 5, 0, 100);
+/// let first = GeneticEngine::from_codex(codex.clone())
+///     .population_size(20)
+///     .minimizing()
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build()
+///     .run(|ctx| ctx.index >= 20);
+///
+/// let prior = PopulationPrior::distill(&first.population, &Objective::Single(Optimize::Minimize), 5);
+/// let stats = prior.allele_statistics(|gene| *gene.allele() as f32);
+/// assert_eq!(stats[0].len(), 5);
+///
+/// let second = GeneticEngine::from_codex(codex)
+///     .population_size(20)
+///     .minimizing()
+///     .prior(prior.with_sample_fraction(0.5))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().map(|x| x * 2).sum::<i32>())
+///     .build();
+///
+/// let result = second.run(|ctx| ctx.index >= 5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopulationPrior<C: Chromosome> {
+    elites: Vec<Genotype<C>>,
+    sample_fraction: f32,
+}
+
+impl<C: Chromosome> PopulationPrior<C> {
+    /// Keep the `top_k` best genotypes of `population` according to `objective`. Unscored individuals
+    /// are only kept if there aren't enough scored ones. Panics if `top_k` is 0 or the population is empty.
+    pub fn distill(population: &Population<C>, objective: &Objective, top_k: usize) -> Self {
+        if top_k == 0 {
+            panic!("top_k must be greater than 0");
+        }
+
+        if population.is_empty() {
+            panic!("Cannot distill an empty population");
+        }
+
+        let mut sorted = population.clone();
+        objective.sort(&mut sorted);
+
+        PopulationPrior::from_genotypes(
+            sorted
+                .iter()
+                .take(top_k)
+                .map(|individual| individual.genotype().clone())
+                .collect(),
+        )
+    }
+
+    /// A prior made of the given genotypes, best first. Panics if `elites` is empty.
+    pub fn from_genotypes(elites: Vec<Genotype<C>>) -> Self {
+        if elites.is_empty() {
+            panic!("A prior needs at least one genotype");
+        }
+
+        PopulationPrior {
+            elites,
+            sample_fraction: 0.5,
+        }
+    }
+
+    /// The fraction of the non-elite part of a seeded population that is sampled from the prior - the rest
+    /// is encoded fresh by the codex. Default is 0.5. Panics if `fraction` is not between 0 and 1.
+    pub fn with_sample_fraction(mut self, fraction: f32) -> Self {
+        if !(0.0..=1.0).contains(&fraction) {
+            panic!("fraction must be between 0 and 1");
+        }
+
+        self.sample_fraction = fraction;
+        self
+    }
+
+    pub fn elites(&self) -> &[Genotype<C>] {
+        &self.elites
+    }
+
+    pub fn sample_fraction(&self) -> f32 {
+        self.sample_fraction
+    }
+
+    pub fn len(&self) -> usize {
+        self.elites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elites.is_empty()
+    }
+
+    /// The distribution of every gene of the elites, by position (one `Vec` per chromosome), with
+    /// `gene_value` mapping a gene to a number (e.g. `|gene| *gene.allele()` for a `FloatGene`).
+    pub fn allele_statistics<F>(&self, gene_value: F) -> Vec<Vec<Statistic>>
+    where
+        F: Fn(&C::Gene) -> f32,
+    {
+        let mut statistics: Vec<Vec<Statistic>> = Vec::new();
+        for genotype in self.elites.iter() {
+            for (chromosome_index, chromosome) in genotype.iter().enumerate() {
+                if statistics.len() <= chromosome_index {
+                    statistics.push(Vec::new());
+                }
+
+                let chromosome_stats = &mut statistics[chromosome_index];
+                for (gene_index, gene) in chromosome.iter().enumerate() {
+                    if chromosome_stats.len() <= gene_index {
+                        chromosome_stats.push(Statistic::default());
+                    }
+
+                    chromosome_stats[gene_index].add(gene_value(gene));
+                }
+            }
+        }
+
+        statistics
+    }
+
+    /// Sample a genotype from the prior's per-gene (marginal) distribution - every gene is taken from
+    /// the same position of a randomly chosen elite. Chromosomes keep the length of a random elite, so
+    /// variable length chromosomes are supported.
+    pub fn sample(&self) -> Genotype<C> {
+        let mut genotype = random_provider::choose(&self.elites).clone();
+        for (chromosome_index, chromosome) in genotype.iter_mut().enumerate() {
+            for gene_index in 0..chromosome.len() {
+                let donor = random_provider::choose(&self.elites);
+                if let Some(gene) = donor
+                    .chromosomes
+                    .get(chromosome_index)
+                    .and_then(|donor| donor.as_ref().get(gene_index))
+                {
+                    chromosome.set_gene(gene_index, gene.clone());
+                }
+            }
+        }
+
+        genotype
+    }
+}
+
+impl PopulationPrior<FloatChromosome> {
+    /// Sample a genotype from an independent normal distribution per gene, with the mean and standard
+    /// deviation of the elites' alleles at that position, clamped to the gene's bounds.
+    pub fn sample_gaussian(&self) -> Genotype<FloatChromosome> {
+        let statistics = self.allele_statistics(|gene| *gene.allele());
+        let mut genotype = self.elites[0].clone();
+
+        for (chromosome, stats) in genotype.iter_mut().zip(statistics.iter()) {
+            for (gene, stat) in chromosome.iter_mut().zip(stats.iter()) {
+                let std_dev = match stat.count() > 1 {
+                    true => stat.std_dev(),
+                    false => 0.0,
+                };
+                let value = random_provider::gaussian(stat.mean() as f64, std_dev as f64) as f32;
+                *gene = gene.with_allele(&value.clamp(*gene.lower_bound(), *gene.upper_bound()));
+            }
+        }
+
+        genotype
+    }
+}
+
+/// Known solutions to start a run from - part of the initial population is made of them instead of
+/// genotypes encoded by the codex. Seeds can be genotypes, values of the type the codex decodes to
+/// (encoded with `Codex::encode_value`), or a population, e.g. the result of an earlier run.
+///
+/// Set with `GeneticEngineParams::seed_population`. Seeds replace the first individuals of the initial
+/// population and are evaluated like any other individual - the scores of a seeded population are not
+/// kept. A seeded population is sorted by the engine's objective first, so if there are more seeds than
+/// the population size, its best individuals are the ones used.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 3, 0, 100))
+///     .minimizing()
+///     .population_size(20)
+///     .survivor_selector(EliteSelector::new())
+///     .seed_population(Seeds::Values(vec![vec![vec![0, 0, 1]]]))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let first = engine.iter().next().unwrap();
+/// assert!(first.score().as_i32() <= 1);
+/// ```
+pub enum Seeds<C: Chromosome, T> {
+    Genotypes(Vec<Genotype<C>>),
+    Values(Vec<T>),
+    Population(Population<C>),
+}
+
+impl<C: Chromosome, T> Seeds<C, T> {
+    /// The seeds as genotypes, best first. Panics if values are seeded and the `problem` can't encode them.
+    pub(crate) fn into_genotypes(
+        self,
+        problem: &dyn Problem<C, T>,
+        objective: &Objective,
+    ) -> Vec<Genotype<C>> {
+        match self {
+            Seeds::Genotypes(genotypes) => genotypes,
+            Seeds::Values(values) => values
+                .iter()
+                .map(|value| match problem.encode_value(value) {
+                    Some(genotype) => genotype,
+                    None => panic!(
+                        "The codex can't encode seed values - implement `Codex::encode_value` or seed genotypes"
+                    ),
+                })
+                .collect(),
+            Seeds::Population(mut population) => {
+                objective.sort(&mut population);
+                population
+                    .iter()
+                    .map(|individual| individual.genotype().clone())
+                    .collect()
+            }
+        }
+    }
+}
+
+impl<C: Chromosome, T> From<Vec<Genotype<C>>> for Seeds<C, T> {
+    fn from(genotypes: Vec<Genotype<C>>) -> Self {
+        Seeds::Genotypes(genotypes)
+    }
+}
+
+impl<C: Chromosome, T> From<Population<C>> for Seeds<C, T> {
+    fn from(population: Population<C>) -> Self {
+        Seeds::Population(population)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Codex, FloatCodex, IntChromosome, IntGene, Optimize, Phenotype};
+
+    #[test]
+    fn test_prior_samples_from_elite_alleles() {
+        let elites = vec![
+            Genotype::new(vec![IntChromosome {
+                genes: vec![IntGene::from(1), IntGene::from(2)],
+            }]),
+            Genotype::new(vec![IntChromosome {
+                genes: vec![IntGene::from(3), IntGene::from(4)],
+            }]),
+        ];
+        let prior = PopulationPrior::from_genotypes(elites);
+
+        for _ in 0..20 {
+            let sample = prior.sample();
+            assert!([1, 3].contains(sample[0].get_gene(0).allele()));
+            assert!([2, 4].contains(sample[0].get_gene(1).allele()));
+        }
+
+        let stats = prior.allele_statistics(|gene| *gene.allele() as f32);
+        assert_eq!(stats[0][0].mean(), 2.0);
+        assert_eq!(stats[0][1].mean(), 3.0);
+    }
+
+    #[test]
+    fn test_prior_keeps_the_best_and_samples_within_bounds() {
+        let codex = FloatCodex::new(1, 3, 0.0, 1.0);
+        let population = (0..10)
+            .map(|i| {
+                let mut individual = Phenotype::from_genotype(codex.encode(), 0);
+                individual.set_score(Some((i as f32).into()));
+                individual
+            })
+            .collect::<Population<FloatChromosome>>();
+
+        let prior =
+            PopulationPrior::distill(&population, &Objective::Single(Optimize::Maximize), 3);
+        assert_eq!(prior.len(), 3);
+        assert!(prior.elites()[0] == *population[9].genotype());
+
+        let sample = prior.sample_gaussian();
+        assert!(sample[0]
+            .iter()
+            .all(|gene| (0.0..=1.0).contains(gene.allele())));
+    }
+}
@@ -2,10 +2,77 @@ use std::sync::Arc;
 
 use super::{Chromosome, Codex, Genotype, Score};
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isn't a problem over `{C}` genotypes decoding to `{T}`",
+    note = "a problem must be `Send + Sync` and implement `encode`, `decode` and `eval`"
+)]
 pub trait Problem<C: Chromosome, T>: Send + Sync {
     fn encode(&self) -> Genotype<C>;
     fn decode(&self, genotype: &Genotype<C>) -> T;
     fn eval(&self, individual: &Genotype<C>) -> Score;
+
+    /// Encode a decoded value back into a genotype (see `Codex::encode_value`). Defaults to `None`.
+    fn encode_value(&self, _value: &T) -> Option<Genotype<C>> {
+        None
+    }
+
+    /// Repair a genotype an alterer changed (see `Codex::repair`). Defaults to leaving it as it is.
+    fn repair(&self, _genotype: &mut Genotype<C>) {}
+
+    /// The number of independent parts the score is made of. When there is more than one, the engine
+    /// evaluates every part of an individual as its own job with `eval_part`, so expensive objectives
+    /// can run on different threads. Defaults to 1 - the whole score comes from `eval`.
+    fn parts(&self) -> usize {
+        1
+    }
+
+    /// Evaluate a single part of the score. Defaults to evaluating the whole score and taking the part.
+    fn eval_part(&self, individual: &Genotype<C>, part: usize) -> f32 {
+        self.eval(individual).values[part]
+    }
+
+    /// The number of individuals evaluated together with `eval_batch`. When it is more than one, the
+    /// engine evaluates the unscored individuals in batches of this size, each batch as its own job.
+    /// Defaults to 1 - every individual is evaluated on its own with `eval`.
+    fn batch_size(&self) -> usize {
+        1
+    }
+
+    /// Evaluate a batch of individuals, returning one result per individual in the same order. An `Err`
+    /// marks an individual whose evaluation failed - the engine gives it the objective's worst score,
+    /// keeps the error in its metadata and leaves it out of the Pareto front. Missing results are
+    /// treated as failures. Defaults to evaluating every individual with `eval`.
+    fn eval_batch(&self, individuals: &[Genotype<C>]) -> Vec<Result<Score, String>> {
+        individuals
+            .iter()
+            .map(|individual| Ok(self.eval(individual)))
+            .collect()
+    }
+}
+
+/// A fitness function that scores a whole batch of decoded individuals at once (see
+/// `GeneticEngineParams::batch_fitness_fn`).
+pub type BatchFitnessFn<T> = Arc<dyn Fn(Vec<T>) -> Vec<Result<Score, String>> + Send + Sync>;
+
+/// One independently evaluated part of a multi-part score (see `GeneticEngineParams::objective_fn`).
+pub type ObjectiveFn<T> = Arc<dyn Fn(T) -> f32 + Send + Sync>;
+
+/// Ties the argument type of a fitness function to the type the codex decodes to (see
+/// `GeneticEngineParams::fitness_fn_for`). It is only implemented for the type itself, so naming the wrong
+/// type reports which type the codex actually decodes to instead of an inference error.
+#[diagnostic::on_unimplemented(
+    message = "the codex decodes genotypes to `{Self}`, but the fitness function takes `{X}`",
+    label = "the fitness function's input doesn't match the codex",
+    note = "the fitness function must take exactly the type the codex decodes to"
+)]
+pub trait FitnessInput<X> {
+    fn into_input(self) -> X;
+}
+
+impl<X> FitnessInput<X> for X {
+    fn into_input(self) -> X {
+        self
+    }
 }
 
 pub(crate) struct EngineProblem<C, T>
@@ -15,6 +82,7 @@ where
 {
     pub codex: Arc<Box<dyn Codex<C, T>>>,
     pub fitness_fn: Arc<dyn Fn(T) -> Score + Send + Sync>,
+    pub parts: Vec<ObjectiveFn<T>>,
 }
 
 unsafe impl<C, T> Send for EngineProblem<C, T>
@@ -44,8 +112,221 @@ where
         self.codex.decode(genotype)
     }
 
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.codex.encode_value(value)
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.codex.repair(genotype);
+    }
+
     fn eval(&self, individual: &Genotype<C>) -> Score {
         let phenotype = self.decode(individual);
         (self.fitness_fn)(phenotype)
     }
+
+    fn parts(&self) -> usize {
+        self.parts.len().max(1)
+    }
+
+    fn eval_part(&self, individual: &Genotype<C>, part: usize) -> f32 {
+        match self.parts.get(part) {
+            Some(objective_fn) => objective_fn(self.decode(individual)),
+            None => self.eval(individual).values[part],
+        }
+    }
+}
+
+/// A `Problem` scored by a `BatchFitnessFn` - e.g. a simulation that runs a whole batch of individuals
+/// in one call. Scores may have any number of values, so batches work with multi-objective problems too.
+pub(crate) struct BatchEngineProblem<C, T>
+where
+    C: Chromosome,
+    T: Clone,
+{
+    pub codex: Arc<Box<dyn Codex<C, T>>>,
+    pub batch_fitness_fn: BatchFitnessFn<T>,
+    pub batch_size: usize,
+}
+
+unsafe impl<C, T> Send for BatchEngineProblem<C, T>
+where
+    C: Chromosome,
+    T: Clone,
+{
+}
+
+unsafe impl<C, T> Sync for BatchEngineProblem<C, T>
+where
+    C: Chromosome,
+    T: Clone,
+{
+}
+
+impl<C, T> Problem<C, T> for BatchEngineProblem<C, T>
+where
+    C: Chromosome,
+    T: Clone,
+{
+    fn encode(&self) -> Genotype<C> {
+        self.codex.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.codex.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.codex.encode_value(value)
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.codex.repair(genotype);
+    }
+
+    fn eval(&self, individual: &Genotype<C>) -> Score {
+        match self.eval_batch(std::slice::from_ref(individual)).pop() {
+            Some(Ok(score)) => score,
+            Some(Err(error)) => panic!("Batch evaluation failed: {}", error),
+            None => panic!("Batch fitness function returned no score"),
+        }
+    }
+
+    fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    fn eval_batch(&self, individuals: &[Genotype<C>]) -> Vec<Result<Score, String>> {
+        let decoded = individuals
+            .iter()
+            .map(|individual| self.decode(individual))
+            .collect::<Vec<T>>();
+
+        let mut results = (self.batch_fitness_fn)(decoded);
+        if results.len() != individuals.len() {
+            let error = format!(
+                "Batch fitness function returned {} results for {} individuals",
+                results.len(),
+                individuals.len()
+            );
+            results.truncate(individuals.len());
+            results.resize(individuals.len(), Err(error));
+        }
+
+        results
+    }
+}
+
+/// The complexity of a genotype, appended to its score as an extra objective (see
+/// `GeneticEngineParams::complexity_objective`).
+pub(crate) type ComplexityFn<C> = Arc<dyn Fn(&Genotype<C>) -> f32 + Send + Sync>;
+
+/// A `Problem` whose scores get the complexity of the genotype appended as extra values, so they can
+/// be minimized alongside the problem's own objectives.
+pub(crate) struct ComplexityProblem<C: Chromosome, T> {
+    pub problem: Arc<Box<dyn Problem<C, T>>>,
+    pub complexity: Vec<ComplexityFn<C>>,
+}
+
+impl<C: Chromosome, T> ComplexityProblem<C, T> {
+    fn extend(&self, individual: &Genotype<C>, score: Score) -> Score {
+        let mut values = score.values;
+        values.extend(self.complexity.iter().map(|measure| measure(individual)));
+        Score::from_vec(values)
+    }
+}
+
+impl<C: Chromosome, T> Problem<C, T> for ComplexityProblem<C, T> {
+    fn encode(&self) -> Genotype<C> {
+        self.problem.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.problem.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.problem.encode_value(value)
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.problem.repair(genotype);
+    }
+
+    fn eval(&self, individual: &Genotype<C>) -> Score {
+        self.extend(individual, self.problem.eval(individual))
+    }
+
+    fn parts(&self) -> usize {
+        match self.problem.parts() {
+            1 => 1,
+            parts => parts + self.complexity.len(),
+        }
+    }
+
+    fn eval_part(&self, individual: &Genotype<C>, part: usize) -> f32 {
+        let parts = self.problem.parts();
+        match part < parts {
+            true => self.problem.eval_part(individual, part),
+            false => (self.complexity[part - parts])(individual),
+        }
+    }
+
+    fn batch_size(&self) -> usize {
+        self.problem.batch_size()
+    }
+
+    fn eval_batch(&self, individuals: &[Genotype<C>]) -> Vec<Result<Score, String>> {
+        self.problem
+            .eval_batch(individuals)
+            .into_iter()
+            .zip(individuals.iter())
+            .map(|(result, individual)| result.map(|score| self.extend(individual, score)))
+            .collect()
+    }
+}
+
+/// A `Problem` evaluated in batches of a different size than its own, e.g. the one picked by a
+/// `Calibration`. Batches of individuals that aren't scored together are evaluated one by one.
+pub(crate) struct BatchedProblem<C: Chromosome, T> {
+    pub problem: Arc<Box<dyn Problem<C, T>>>,
+    pub batch_size: usize,
+}
+
+impl<C: Chromosome, T> Problem<C, T> for BatchedProblem<C, T> {
+    fn encode(&self) -> Genotype<C> {
+        self.problem.encode()
+    }
+
+    fn decode(&self, genotype: &Genotype<C>) -> T {
+        self.problem.decode(genotype)
+    }
+
+    fn encode_value(&self, value: &T) -> Option<Genotype<C>> {
+        self.problem.encode_value(value)
+    }
+
+    fn repair(&self, genotype: &mut Genotype<C>) {
+        self.problem.repair(genotype);
+    }
+
+    fn eval(&self, individual: &Genotype<C>) -> Score {
+        self.problem.eval(individual)
+    }
+
+    fn parts(&self) -> usize {
+        self.problem.parts()
+    }
+
+    fn eval_part(&self, individual: &Genotype<C>, part: usize) -> f32 {
+        self.problem.eval_part(individual, part)
+    }
+
+    fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    fn eval_batch(&self, individuals: &[Genotype<C>]) -> Vec<Result<Score, String>> {
+        self.problem.eval_batch(individuals)
+    }
 }
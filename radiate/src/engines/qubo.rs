@@ -0,0 +1,453 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::{BitChromosome, BitCodex, BitGene, DeltaFitness, Gene, GeneChange, Genotype, Score};
+
+/// A quadratic unconstrained binary optimization problem - minimize the energy
+/// `x^T Q x + offset` over bit vectors `x`. The matrix is kept as the linear terms (its diagonal)
+/// and the couplings between pairs of bits (the sum of `Q_ij` and `Q_ji`), so evaluating a
+/// sparse problem costs `O(bits + couplings)`.
+///
+/// A `Qubo` is a `DeltaFitness` over `BitChromosome`s - altered individuals are scored from their
+/// parent's energy, in time proportional to the couplings of the flipped bits rather than the
+/// whole matrix. An Ising model (spins of -1 and 1) converts to an equivalent QUBO with `from_ising`,
+/// and benchmark instances load with `load`, `parse_qubo` (the qbsolv `.qubo` format) or
+/// `parse_biqmac` (the Biq Mac library format).
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // minimized by picking exactly one of the three bits
+/// let mut qubo = Qubo::new(3);
+/// for i in 0..3 {
+///     qubo.add(i, i, -1.0);
+///     for j in i + 1..3 {
+///         qubo.add(i, j, 2.0);
+///     }
+/// }
+///
+/// let engine = GeneticEngine::from_codex(qubo.codex())
+///     .minimizing()
+///     .delta_fitness(qubo.clone())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.score().as_f32() == -1.0 || ctx.index > 100);
+/// assert_eq!(result.best[0].iter().filter(|bit| **bit).count(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Qubo {
+    linear: Vec<f32>,
+    couplings: Vec<Vec<(usize, f32)>>,
+    offset: f32,
+}
+
+impl Qubo {
+    /// Create a QUBO over `size` bits with every coefficient 0.
+    pub fn new(size: usize) -> Self {
+        Qubo {
+            linear: vec![0.0; size],
+            couplings: vec![Vec::new(); size],
+            offset: 0.0,
+        }
+    }
+
+    /// Create a QUBO from a dense, square matrix. Panics if the matrix isn't square.
+    pub fn from_dense(matrix: &[Vec<f32>]) -> Self {
+        let mut qubo = Qubo::new(matrix.len());
+        for (i, row) in matrix.iter().enumerate() {
+            if row.len() != matrix.len() {
+                panic!("QUBO matrix must be square");
+            }
+
+            for (j, value) in row.iter().enumerate() {
+                qubo.add(i, j, *value);
+            }
+        }
+
+        qubo
+    }
+
+    /// Convert an Ising model - minimize `sum(h_i * s_i) + sum(J_ij * s_i * s_j)` over spins `s_i` of
+    /// -1 or 1 - to the QUBO with the same energy for the bits `x_i = (s_i + 1) / 2`.
+    /// Panics if a coupling refers to a spin outside of `fields`.
+    pub fn from_ising(fields: &[f32], couplings: &[(usize, usize, f32)]) -> Self {
+        let mut qubo = Qubo::new(fields.len());
+        for (i, field) in fields.iter().enumerate() {
+            qubo.add(i, i, 2.0 * field);
+            qubo.offset -= field;
+        }
+
+        for &(i, j, coupling) in couplings {
+            if i == j {
+                qubo.offset += coupling;
+                continue;
+            }
+
+            qubo.add(i, j, 4.0 * coupling);
+            qubo.add(i, i, -2.0 * coupling);
+            qubo.add(j, j, -2.0 * coupling);
+            qubo.offset += coupling;
+        }
+
+        qubo
+    }
+
+    /// Add `value` to the coefficient `Q_ij`. Panics if either index is out of bounds.
+    pub fn add(&mut self, i: usize, j: usize, value: f32) {
+        if i >= self.size() || j >= self.size() {
+            panic!(
+                "QUBO index ({}, {}) out of bounds for size {}",
+                i,
+                j,
+                self.size()
+            );
+        }
+
+        if i == j {
+            self.linear[i] += value;
+            return;
+        }
+
+        for (from, to) in [(i, j), (j, i)] {
+            match self.couplings[from]
+                .iter_mut()
+                .find(|(other, _)| *other == to)
+            {
+                Some((_, coupling)) => *coupling += value,
+                None => self.couplings[from].push((to, value)),
+            }
+        }
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The number of bits.
+    pub fn size(&self) -> usize {
+        self.linear.len()
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// The linear term of bit `i` - the diagonal coefficient `Q_ii`.
+    pub fn linear(&self, i: usize) -> f32 {
+        self.linear[i]
+    }
+
+    /// The coupling between bits `i` and `j` - `Q_ij + Q_ji`.
+    pub fn coupling(&self, i: usize, j: usize) -> f32 {
+        self.couplings[i]
+            .iter()
+            .find(|(other, _)| *other == j)
+            .map(|(_, coupling)| *coupling)
+            .unwrap_or(0.0)
+    }
+
+    /// The energy of the bits - `x^T Q x + offset`. Panics if there are fewer bits than the QUBO's size.
+    pub fn energy(&self, bits: &[bool]) -> f32 {
+        let mut energy = self.offset;
+        for i in (0..self.size()).filter(|i| bits[*i]) {
+            energy += self.linear[i];
+            energy += self.couplings[i]
+                .iter()
+                .filter(|(j, _)| *j > i && bits[*j])
+                .map(|(_, coupling)| coupling)
+                .sum::<f32>();
+        }
+
+        energy
+    }
+
+    /// The change in energy from flipping bit `i` of `bits`.
+    pub fn flip_delta(&self, bits: &[bool], i: usize) -> f32 {
+        let field = self.linear[i]
+            + self.couplings[i]
+                .iter()
+                .filter(|(j, _)| bits[*j])
+                .map(|(_, coupling)| coupling)
+                .sum::<f32>();
+
+        match bits[i] {
+            true => -field,
+            false => field,
+        }
+    }
+
+    /// A codex for a single chromosome with a bit per variable.
+    pub fn codex(&self) -> BitCodex {
+        BitCodex::new(1, self.size())
+    }
+
+    /// Load a benchmark instance - the qbsolv format if the file's extension is `qubo`, the Biq Mac
+    /// format otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("qubo") => Qubo::parse_qubo(&source),
+            _ => Qubo::parse_biqmac(&source),
+        }
+    }
+
+    /// Parse the qbsolv `.qubo` format: comment lines starting with `c`, a program line
+    /// `p qubo <topology> <max nodes> <nodes> <couplers>`, then one `i j value` line (0-based) per
+    /// coefficient of the upper triangular matrix - the nodes (`i == j`) first, then the couplers.
+    pub fn parse_qubo(source: &str) -> Result<Self, String> {
+        let mut lines = data_lines(source).filter(|(_, line)| !line.starts_with('c'));
+
+        let (number, header) = lines
+            .next()
+            .ok_or_else(|| "Missing program line".to_string())?;
+        let header = header.split_whitespace().collect::<Vec<&str>>();
+        if header.len() != 6 || header[0] != "p" || header[1] != "qubo" {
+            return Err(format!(
+                "Line {}: expected 'p qubo <topology> <max nodes> <nodes> <couplers>'",
+                number
+            ));
+        }
+
+        let size = parse::<usize>(header[3], number)?;
+        let expected = parse::<usize>(header[4], number)? + parse::<usize>(header[5], number)?;
+
+        let mut qubo = Qubo::new(size);
+        let count = qubo.read_entries(lines, 0)?;
+        if count != expected {
+            return Err(format!("Expected {} entries but found {}", expected, count));
+        }
+
+        Ok(qubo)
+    }
+
+    /// Parse the Biq Mac library format: a first line `<variables> <entries>`, then one `i j value`
+    /// line (1-based) per entry of the upper triangle of a symmetric matrix - so every off-diagonal
+    /// entry stands for both `Q_ij` and `Q_ji`.
+    pub fn parse_biqmac(source: &str) -> Result<Self, String> {
+        let mut lines = data_lines(source);
+
+        let (number, header) = lines
+            .next()
+            .ok_or_else(|| "Missing header line".to_string())?;
+        let header = header.split_whitespace().collect::<Vec<&str>>();
+        if header.len() != 2 {
+            return Err(format!("Line {}: expected '<variables> <entries>'", number));
+        }
+
+        let mut symmetric = Qubo::new(parse::<usize>(header[0], number)?);
+        let expected = parse::<usize>(header[1], number)?;
+        let count = symmetric.read_entries(lines, 1)?;
+        if count != expected {
+            return Err(format!("Expected {} entries but found {}", expected, count));
+        }
+
+        for couplings in symmetric.couplings.iter_mut() {
+            for (_, coupling) in couplings.iter_mut() {
+                *coupling *= 2.0;
+            }
+        }
+
+        Ok(symmetric)
+    }
+
+    /// Add the `i j value` entries of the lines, with indices starting at `base`. Returns the number of entries.
+    fn read_entries<'a>(
+        &mut self,
+        lines: impl Iterator<Item = (usize, &'a str)>,
+        base: usize,
+    ) -> Result<usize, String> {
+        let mut count = 0;
+        for (number, line) in lines {
+            let parts = line.split_whitespace().collect::<Vec<&str>>();
+            if parts.len() != 3 {
+                return Err(format!("Line {}: expected 'i j value'", number));
+            }
+
+            let i = parse::<usize>(parts[0], number)?;
+            let j = parse::<usize>(parts[1], number)?;
+            let value = parse::<f32>(parts[2], number)?;
+
+            let (i, j) = match (i.checked_sub(base), j.checked_sub(base)) {
+                (Some(i), Some(j)) if i < self.size() && j < self.size() => (i, j),
+                _ => return Err(format!("Line {}: index out of bounds", number)),
+            };
+
+            self.add(i, j, value);
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+impl DeltaFitness<BitChromosome, Vec<Vec<bool>>> for Qubo {
+    fn fitness(&self, individual: Vec<Vec<bool>>) -> Score {
+        Score::from_f32(self.energy(&individual.concat()))
+    }
+
+    /// Only the linear terms of the flipped bits and the couplings that touch them change, so the
+    /// energy is updated in time proportional to their couplings. Genotypes with more than one
+    /// chromosome are left to a full evaluation.
+    fn delta(
+        &self,
+        parent: &Score,
+        changes: &[GeneChange<BitGene>],
+        genotype: &Genotype<BitChromosome>,
+    ) -> Option<Score> {
+        if genotype.len() != 1 {
+            return None;
+        }
+
+        let genes = &genotype[0].genes;
+        let flipped = changes
+            .iter()
+            .map(|change| change.index)
+            .collect::<HashSet<usize>>();
+
+        let new = |i: usize| *genes[i].allele();
+        let old = |i: usize| new(i) != flipped.contains(&i);
+
+        let mut delta = 0.0;
+        for &i in flipped.iter() {
+            delta += self.linear[i] * (new(i) as i32 - old(i) as i32) as f32;
+
+            for &(j, coupling) in self.couplings[i].iter() {
+                // a coupling between two flipped bits is counted once, from its lower bit
+                if j < i && flipped.contains(&j) {
+                    continue;
+                }
+
+                let before = (old(i) && old(j)) as i32;
+                let after = (new(i) && new(j)) as i32;
+                delta += coupling * (after - before) as f32;
+            }
+        }
+
+        Some(Score::from_f32(parent.as_f32() + delta))
+    }
+}
+
+/// The non-empty, trimmed lines of a source with their (1-based) line numbers.
+fn data_lines(source: &str) -> impl Iterator<Item = (usize, &str)> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+fn parse<V: std::str::FromStr>(value: &str, line: usize) -> Result<V, String> {
+    value
+        .parse::<V>()
+        .map_err(|_| format!("Line {}: invalid number '{}'", line, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Codex;
+
+    fn brute_force_minimum(qubo: &Qubo) -> f32 {
+        (0..1 << qubo.size())
+            .map(|mask: usize| {
+                let bits = (0..qubo.size())
+                    .map(|i| mask & (1 << i) != 0)
+                    .collect::<Vec<bool>>();
+                qubo.energy(&bits)
+            })
+            .fold(f32::MAX, f32::min)
+    }
+
+    #[test]
+    fn test_energy_matches_the_dense_matrix() {
+        let matrix = vec![
+            vec![1.0, -2.0, 0.0],
+            vec![0.5, -1.0, 3.0],
+            vec![0.0, 1.0, 2.0],
+        ];
+        let qubo = Qubo::from_dense(&matrix);
+
+        let bits = [true, true, true];
+        let dense = matrix.iter().flatten().sum::<f32>();
+
+        assert_eq!(qubo.energy(&bits), dense);
+        assert_eq!(qubo.coupling(0, 1), -1.5);
+        assert_eq!(qubo.energy(&[true, false, true]), 3.0);
+        assert_eq!(
+            qubo.flip_delta(&bits, 1),
+            qubo.energy(&[true, false, true]) - qubo.energy(&bits)
+        );
+    }
+
+    #[test]
+    fn test_ising_conversion_keeps_the_energy() {
+        let fields = [0.5, -1.0, 0.25];
+        let couplings = [(0, 1, 1.0), (1, 2, -0.5), (0, 2, 2.0)];
+        let qubo = Qubo::from_ising(&fields, &couplings);
+
+        for mask in 0..8 {
+            let bits = (0..3).map(|i| mask & (1 << i) != 0).collect::<Vec<bool>>();
+            let spins = bits
+                .iter()
+                .map(|bit| if *bit { 1.0 } else { -1.0 })
+                .collect::<Vec<f32>>();
+
+            let ising = fields.iter().zip(&spins).map(|(h, s)| h * s).sum::<f32>()
+                + couplings
+                    .iter()
+                    .map(|(i, j, coupling)| coupling * spins[*i] * spins[*j])
+                    .sum::<f32>();
+
+            assert!((qubo.energy(&bits) - ising).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_delta_matches_full_evaluation() {
+        let mut qubo = Qubo::new(12);
+        for i in 0..12 {
+            qubo.add(i, i, i as f32 - 6.0);
+            qubo.add(i, (i * 5 + 3) % 12, 1.5 - (i % 3) as f32);
+        }
+
+        let codex = qubo.codex();
+        for _ in 0..50 {
+            let parent = codex.encode();
+            let mut child = parent.clone();
+            for gene in child[0].genes.iter_mut().step_by(3) {
+                *gene = gene.new_instance();
+            }
+
+            let changes = child.changes_from(&parent).unwrap();
+            let parent_score = qubo.fitness(codex.decode(&parent));
+            let delta = qubo.delta(&parent_score, &changes, &child).unwrap();
+            let full = qubo.fitness(codex.decode(&child));
+
+            assert!((delta.as_f32() - full.as_f32()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_parse_benchmark_formats() {
+        let qubo = Qubo::parse_qubo(
+            "c a small instance\np qubo 0 3 2 2\n0 0 -1\n2 2 -1\n0 1 2\n1 2 -0.5\n",
+        )
+        .unwrap();
+        assert_eq!(qubo.size(), 3);
+        assert_eq!(qubo.coupling(1, 2), -0.5);
+        assert_eq!(brute_force_minimum(&qubo), -2.0);
+
+        let biqmac = Qubo::parse_biqmac("3 3\n1 1 -1\n1 2 1\n3 3 -2\n").unwrap();
+        assert_eq!(biqmac.coupling(0, 1), 2.0);
+        assert_eq!(biqmac.energy(&[true, true, true]), -1.0);
+
+        assert!(Qubo::parse_qubo("p qubo 0 3 1 1\n0 0 1\n").is_err());
+        assert!(Qubo::parse_biqmac("2 1\n3 1 1\n").is_err());
+        assert!(Qubo::parse_biqmac("2 1\n1 x 1\n").is_err());
+    }
+}
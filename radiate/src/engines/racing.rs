@@ -0,0 +1,127 @@
+use super::{Objective, Score};
+
+/// Racing evaluation (F-Race style) for expensive, noisy fitness functions. Instead of evaluating every
+/// individual a fixed number of times, the engine evaluates all new individuals one replication at a time.
+/// After `min_replications` rounds, every individual that another individual is significantly better
+/// than (Welch's t-test at level `alpha`) is dropped from the race and keeps the score it has so far,
+/// so the remaining budget goes to the close contenders. The race ends once `max_replications` rounds
+/// have run or a single contender is left. Every score is the mean of its replications, along with its
+/// variance (see `Score::from_samples`).
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .racing(Racing::new(10).min_replications(3).alpha(0.05))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| {
+///         let noise = random_provider::gen_range(-5.0..5.0);
+///         geno[0].iter().sum::<i32>() as f32 + noise
+///     })
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+/// assert!(result.score().stats().unwrap().samples >= 3);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Racing {
+    min_replications: usize,
+    max_replications: usize,
+    alpha: f32,
+}
+
+impl Racing {
+    /// Create a race of at most `max_replications` replications per individual, with the defaults of
+    /// 2 replications before any elimination and a significance level of 0.05.
+    /// Panics if `max_replications` is less than 2.
+    pub fn new(max_replications: usize) -> Self {
+        if max_replications < 2 {
+            panic!("max_replications must be at least 2");
+        }
+
+        Racing {
+            min_replications: 2,
+            max_replications,
+            alpha: 0.05,
+        }
+    }
+
+    /// The number of replications every individual gets before any can be eliminated.
+    /// Panics if it is less than 2 or greater than `max_replications`.
+    pub fn min_replications(mut self, replications: usize) -> Self {
+        if replications < 2 || replications > self.max_replications {
+            panic!("min_replications must be between 2 and max_replications");
+        }
+
+        self.min_replications = replications;
+        self
+    }
+
+    /// The significance level of the test used to eliminate individuals. Panics if `alpha` is not in (0, 1].
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        if alpha <= 0.0 || alpha > 1.0 {
+            panic!("alpha must be in (0, 1]");
+        }
+
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn max_replications(&self) -> usize {
+        self.max_replications
+    }
+
+    /// The contenders that stay in the race after `round` rounds (1-based) - all of them until
+    /// `min_replications` rounds have run, then those no other contender is significantly better than.
+    /// `scores` pairs each contender with the score of its replications so far.
+    pub fn survivors(
+        &self,
+        round: usize,
+        scores: &[(usize, Score)],
+        objective: &Objective,
+    ) -> Vec<usize> {
+        if round < self.min_replications {
+            return scores.iter().map(|(idx, _)| *idx).collect();
+        }
+
+        scores
+            .iter()
+            .filter(|(idx, score)| {
+                !scores.iter().any(|(other_idx, other)| {
+                    other_idx != idx && objective.is_significantly_better(other, score, self.alpha)
+                })
+            })
+            .map(|(idx, _)| *idx)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Optimize;
+
+    fn samples(mean: f32) -> Score {
+        let samples = [-1.0, 0.0, 1.0]
+            .iter()
+            .map(|noise| Score::from_f32(mean + noise))
+            .collect::<Vec<Score>>();
+        Score::from_samples(&samples)
+    }
+
+    #[test]
+    fn test_racing_eliminates_clearly_worse_contenders() {
+        let racing = Racing::new(10).min_replications(3);
+        let objective = Objective::Single(Optimize::Maximize);
+        let scores = vec![
+            (0, samples(10.0)),
+            (1, samples(10.5)),
+            (2, samples(30.0)),
+            (3, samples(29.5)),
+        ];
+
+        assert_eq!(racing.survivors(2, &scores, &objective), vec![0, 1, 2, 3]);
+        assert_eq!(racing.survivors(3, &scores, &objective), vec![2, 3]);
+    }
+}
@@ -0,0 +1,295 @@
+use super::{BitChromosome, Gene, Genotype};
+
+/// A greedy repair for knapsack problems encoded with one bit per item (e.g. by a `SubSetCodex`).
+/// While the selected items weigh more than the capacity, the selected item with the lowest
+/// value-to-weight ratio is dropped. With `fill`, the unselected items that still fit are then added,
+/// highest ratio first. Every repaired individual is feasible, so the fitness function doesn't need a
+/// penalty for overweight knapsacks.
+///
+/// The engine repairs the individuals the alterers change once attached to the codex with
+/// `Codex::with_repair`. Newly encoded individuals aren't repaired, so to start from feasible
+/// knapsacks too, repair them in the encoder as well.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let repair = KnapsackRepair::new(vec![60.0, 100.0, 120.0], vec![10.0, 20.0, 30.0], 50.0);
+///
+/// let mut genotype = BitCodex::new(1, 3).encode();
+/// repair.repair(&mut genotype);
+/// assert!(repair.weight(&genotype) <= 50.0);
+///
+/// let codex = BitCodex::new(1, 3)
+///     .with_repair(move |genotype: &mut Genotype<BitChromosome>| repair.repair(genotype));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnapsackRepair {
+    values: Vec<f32>,
+    weights: Vec<f32>,
+    capacity: f32,
+    fill: bool,
+    order: Vec<usize>,
+}
+
+impl KnapsackRepair {
+    /// Create a repair for items with the given values and weights. Panics if there aren't as many
+    /// values as weights, or any weight is negative.
+    pub fn new(values: Vec<f32>, weights: Vec<f32>, capacity: f32) -> Self {
+        if values.len() != weights.len() {
+            panic!("values and weights must have the same length");
+        }
+
+        if weights.iter().any(|weight| *weight < 0.0) {
+            panic!("weights must not be negative");
+        }
+
+        let ratio = |i: usize| match weights[i] {
+            weight if weight > 0.0 => values[i] / weight,
+            _ => f32::INFINITY,
+        };
+
+        // highest ratio first, ties broken by index so the repair is deterministic
+        let mut order = (0..values.len()).collect::<Vec<usize>>();
+        order.sort_by(|one, two| ratio(*two).total_cmp(&ratio(*one)).then(one.cmp(two)));
+
+        KnapsackRepair {
+            values,
+            weights,
+            capacity,
+            fill: false,
+            order,
+        }
+    }
+
+    /// Create a repair for a list of items, reading each item's value and weight with the given functions.
+    pub fn from_items<I>(
+        items: &[I],
+        value: impl Fn(&I) -> f32,
+        weight: impl Fn(&I) -> f32,
+        capacity: f32,
+    ) -> Self {
+        KnapsackRepair::new(
+            items.iter().map(value).collect(),
+            items.iter().map(weight).collect(),
+            capacity,
+        )
+    }
+
+    /// Also add the unselected items that still fit after dropping, highest value-to-weight ratio first.
+    pub fn fill(mut self) -> Self {
+        self.fill = true;
+        self
+    }
+
+    pub fn capacity(&self) -> f32 {
+        self.capacity
+    }
+
+    /// The total weight of the selected items.
+    pub fn weight(&self, genotype: &Genotype<BitChromosome>) -> f32 {
+        selected(genotype).map(|i| self.weights[i]).sum()
+    }
+
+    /// The total value of the selected items.
+    pub fn value(&self, genotype: &Genotype<BitChromosome>) -> f32 {
+        selected(genotype).map(|i| self.values[i]).sum()
+    }
+
+    /// Repair the first chromosome of the genotype, its bits being the items in order.
+    pub fn repair(&self, genotype: &mut Genotype<BitChromosome>) {
+        let mut weight = self.weight(genotype);
+        let genes = &mut genotype[0].genes;
+
+        for &i in self.order.iter().rev() {
+            if weight <= self.capacity {
+                break;
+            }
+
+            if *genes[i].allele() {
+                genes[i] = genes[i].with_allele(&false);
+                weight -= self.weights[i];
+            }
+        }
+
+        if !self.fill {
+            return;
+        }
+
+        for &i in self.order.iter() {
+            if !*genes[i].allele() && weight + self.weights[i] <= self.capacity {
+                genes[i] = genes[i].with_allele(&true);
+                weight += self.weights[i];
+            }
+        }
+    }
+}
+
+/// A repair for subset-sum problems encoded with one bit per value (e.g. by a `SubSetCodex`) - find
+/// a subset of non-negative values that sums to the target. While the selected values sum to more
+/// than the target, the selected value closest to the excess is dropped (the smallest one covering it,
+/// or else the largest). Then, while there's a shortfall, the largest unselected value that fits is
+/// added. The result never exceeds the target, and often hits it.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let values = vec![3, 34, 4, 12, 5, 2];
+/// let repair = SubsetSumRepair::new(values.iter().map(|value| *value as f32).collect(), 9.0);
+///
+/// let mut genotype = SubSetCodex::new(&values).encode();
+/// repair.repair(&mut genotype);
+/// assert!(repair.sum(&genotype) <= 9.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubsetSumRepair {
+    values: Vec<f32>,
+    target: f32,
+}
+
+impl SubsetSumRepair {
+    /// Panics if any value is negative.
+    pub fn new(values: Vec<f32>, target: f32) -> Self {
+        if values.iter().any(|value| *value < 0.0) {
+            panic!("values must not be negative");
+        }
+
+        SubsetSumRepair { values, target }
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// The sum of the selected values.
+    pub fn sum(&self, genotype: &Genotype<BitChromosome>) -> f32 {
+        selected(genotype).map(|i| self.values[i]).sum()
+    }
+
+    /// Repair the first chromosome of the genotype, its bits being the values in order.
+    pub fn repair(&self, genotype: &mut Genotype<BitChromosome>) {
+        let mut sum = self.sum(genotype);
+        let genes = &mut genotype[0].genes;
+
+        while sum > self.target {
+            let excess = sum - self.target;
+            let selected = (0..genes.len()).filter(|i| *genes[*i].allele());
+
+            let drop = selected
+                .map(|i| (i, self.values[i]))
+                .min_by(
+                    |(_, one), (_, two)| match (*one >= excess, *two >= excess) {
+                        (true, true) => one.total_cmp(two),
+                        (false, false) => two.total_cmp(one),
+                        (true, false) => std::cmp::Ordering::Less,
+                        (false, true) => std::cmp::Ordering::Greater,
+                    },
+                )
+                .map(|(i, _)| i);
+
+            match drop {
+                Some(i) => {
+                    genes[i] = genes[i].with_allele(&false);
+                    sum -= self.values[i];
+                }
+                None => break,
+            }
+        }
+
+        loop {
+            let shortfall = self.target - sum;
+            let add = (0..genes.len())
+                .filter(|i| !*genes[*i].allele() && self.values[*i] <= shortfall)
+                .max_by(|one, two| self.values[*one].total_cmp(&self.values[*two]));
+
+            match add {
+                Some(i) if self.values[i] > 0.0 => {
+                    genes[i] = genes[i].with_allele(&true);
+                    sum += self.values[i];
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+/// The indices of the set bits of the genotype's first chromosome.
+fn selected(genotype: &Genotype<BitChromosome>) -> impl Iterator<Item = usize> + '_ {
+    genotype[0]
+        .genes
+        .iter()
+        .enumerate()
+        .filter(|(_, gene)| *gene.allele())
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BitGene;
+
+    fn genotype(bits: &[bool]) -> Genotype<BitChromosome> {
+        Genotype::new(vec![BitChromosome {
+            genes: bits
+                .iter()
+                .map(|bit| BitGene::new().with_allele(bit))
+                .collect(),
+        }])
+    }
+
+    fn bits(genotype: &Genotype<BitChromosome>) -> Vec<bool> {
+        genotype[0]
+            .genes
+            .iter()
+            .map(|gene| *gene.allele())
+            .collect()
+    }
+
+    #[test]
+    fn test_knapsack_repair_drops_the_worst_ratio_first() {
+        // ratios: 6, 5, 4, 1
+        let repair = KnapsackRepair::new(
+            vec![60.0, 100.0, 120.0, 10.0],
+            vec![10.0, 20.0, 30.0, 10.0],
+            50.0,
+        );
+
+        let mut overweight = genotype(&[true, true, true, true]);
+        repair.repair(&mut overweight);
+
+        assert_eq!(bits(&overweight), vec![true, true, false, false]);
+        assert_eq!(repair.weight(&overweight), 30.0);
+
+        let mut feasible = genotype(&[false, false, true, true]);
+        repair.repair(&mut feasible);
+        assert_eq!(bits(&feasible), vec![false, false, true, true]);
+
+        let mut filled = genotype(&[false, false, false, true]);
+        repair.clone().fill().repair(&mut filled);
+        assert_eq!(bits(&filled), vec![true, true, false, true]);
+        assert_eq!(repair.value(&filled), 170.0);
+    }
+
+    #[test]
+    fn test_subset_sum_repair_adjusts_towards_the_target() {
+        let repair = SubsetSumRepair::new(vec![3.0, 34.0, 4.0, 12.0, 5.0, 2.0], 9.0);
+
+        let mut everything = genotype(&[true; 6]);
+        repair.repair(&mut everything);
+        assert_eq!(repair.sum(&everything), 9.0);
+
+        let mut nothing = genotype(&[false; 6]);
+        repair.repair(&mut nothing);
+        assert_eq!(repair.sum(&nothing), 9.0);
+
+        for _ in 0..20 {
+            let bits = (0..6)
+                .map(|_| crate::random_provider::random::<f32>() < 0.5)
+                .collect::<Vec<bool>>();
+            let mut random = genotype(&bits);
+            repair.repair(&mut random);
+            assert!(repair.sum(&random) <= 9.0);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/// Which individuals a `Restart` replaces with new individuals from the codex.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RestartStrategy {
+    /// Keep the best `n` individuals and replace the rest of the population.
+    KeepTop(usize),
+    /// Replace the worst `fraction` of the population (between 0 and 1).
+    KillWorst(f32),
+}
+
+impl RestartStrategy {
+    /// A short label for the strategy, e.g. `keep_top(5)` or `kill_worst(0.5)`.
+    pub fn label(&self) -> String {
+        match self {
+            RestartStrategy::KeepTop(count) => format!("keep_top({})", count),
+            RestartStrategy::KillWorst(fraction) => format!("kill_worst({})", fraction),
+        }
+    }
+}
+
+/// Restarts part of the population when the search stagnates. Every `window` generations the best
+/// score doesn't improve, the individuals chosen by the `RestartStrategy` are replaced with new
+/// individuals from the codex, while the best individuals are preserved - so the search can escape a
+/// plateau without losing what it has found. The new individuals are evaluated with the next generation.
+///
+/// Set with `GeneticEngineParams::restart_on_stagnation`. A restart doesn't reset the stagnation count,
+/// so if the best score still doesn't improve the next restart follows `window` generations later. The
+/// number of individuals replaced is recorded in the metrics. Keep enough of the population for the
+/// search to make progress within a window - a `KeepTop(2)` of a population of 100 with a short window
+/// replaces nearly everything every few generations and rarely converges.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .seed(1)
+///     .restart_on_stagnation(5, RestartStrategy::KillWorst(0.5))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.score().as_i32() == 0 || ctx.index >= 500);
+///
+/// assert_eq!(result.score().as_i32(), 0);
+/// assert!(result.metrics.get(metric_names::RESTART).is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Restart {
+    window: i32,
+    strategy: RestartStrategy,
+}
+
+impl Restart {
+    pub fn new(window: i32, strategy: RestartStrategy) -> Self {
+        if window < 1 {
+            panic!("window must be greater than 0");
+        }
+
+        if let RestartStrategy::KillWorst(fraction) = strategy {
+            if !(0.0..=1.0).contains(&fraction) {
+                panic!("fraction must be between 0 and 1");
+            }
+        }
+
+        Restart { window, strategy }
+    }
+
+    pub fn window(&self) -> i32 {
+        self.window
+    }
+
+    pub fn strategy(&self) -> RestartStrategy {
+        self.strategy
+    }
+
+    /// Whether the population should be restarted after `stagnation` generations without improvement.
+    pub fn is_due(&self, stagnation: i32) -> bool {
+        stagnation > 0 && stagnation % self.window == 0
+    }
+
+    /// The number of best individuals of a population of `size` that survive a restart.
+    pub fn survivors(&self, size: usize) -> usize {
+        match self.strategy {
+            RestartStrategy::KeepTop(count) => count.min(size),
+            RestartStrategy::KillWorst(fraction) => {
+                size - ((size as f32 * fraction).round() as usize).min(size)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_is_due_every_window() {
+        let restart = Restart::new(3, RestartStrategy::KeepTop(2));
+        let due = (0..10)
+            .filter(|stagnation| restart.is_due(*stagnation))
+            .collect::<Vec<i32>>();
+
+        assert_eq!(due, vec![3, 6, 9]);
+    }
+
+    #[test]
+    fn test_restart_survivors() {
+        assert_eq!(
+            Restart::new(1, RestartStrategy::KeepTop(2)).survivors(10),
+            2
+        );
+        assert_eq!(
+            Restart::new(1, RestartStrategy::KeepTop(20)).survivors(10),
+            10
+        );
+        assert_eq!(
+            Restart::new(1, RestartStrategy::KillWorst(0.3)).survivors(10),
+            7
+        );
+        assert_eq!(
+            Restart::new(1, RestartStrategy::KillWorst(1.0)).survivors(10),
+            0
+        );
+    }
+}
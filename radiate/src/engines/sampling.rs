@@ -0,0 +1,208 @@
+use super::genome::wire::{self, WireAllele};
+use super::{Chromosome, Gene, Genotype, Population};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Result, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// The full population of a generation - genotypes, scores and ages - as recorded by a `SampleWriter`.
+#[derive(Clone)]
+pub struct GenerationSample<C: Chromosome> {
+    pub index: i32,
+    pub population: Population<C>,
+}
+
+enum Message {
+    Sample(Vec<u8>),
+    Flush(Sender<Result<()>>),
+}
+
+/// Records the population of every `k`-th generation of a run into a file, for offline analysis. Given
+/// to an engine with `GeneticEngineParams::sample_every`, the population of a due generation is encoded
+/// in the `wire` format on the engine's thread - about the cost of cloning it - and written to the file
+/// by a background thread, so the engine doesn't wait on the disk. When the engine stops, the file is
+/// flushed as a post-run stage.
+///
+/// A sample file is a sequence of `wire` sample messages, each prefixed with its length as a
+/// little-endian `u32`. Read it back with a `SampleReader`.
+///
+/// Like the `RunArtifacts`, a `SampleWriter` is cheap to clone and all clones write to the same file. The
+/// background thread stops once every clone is dropped. A write error is returned by the next `flush`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let path = std::env::temp_dir().join("radiate-samples-doc.rdws");
+/// let codex = IntCodex::new(1, 10, 0, 100);
+/// let template = codex.encode();
+///
+/// let engine = GeneticEngine::from_codex(codex)
+///     .minimizing()
+///     .population_size(20)
+///     .sample_every(5, SampleWriter::create(&path).unwrap())
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// engine.run(|ctx| ctx.index >= 20);
+///
+/// let samples = SampleReader::open(&path, template)
+///     .unwrap()
+///     .collect::<std::io::Result<Vec<_>>>()
+///     .unwrap();
+///
+/// assert_eq!(samples.iter().map(|sample| sample.index).collect::<Vec<_>>(), vec![5, 10, 15, 20]);
+/// assert!(samples.iter().all(|sample| sample.population.len() == 20));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct SampleWriter {
+    sender: Arc<Mutex<Sender<Message>>>,
+}
+
+impl SampleWriter {
+    /// Create (or truncate) the file at `path` and start the thread writing to it.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || write_samples(file, receiver));
+
+        Ok(SampleWriter {
+            sender: Arc::new(Mutex::new(sender)),
+        })
+    }
+
+    /// Queue the population of generation `index` to be written.
+    pub fn write<C>(&self, index: i32, population: &Population<C>)
+    where
+        C: Chromosome,
+        <C::Gene as Gene>::Allele: WireAllele,
+    {
+        let bytes = wire::encode_sample(index, population);
+        // The thread only stops once every sender is dropped, so the send can't fail.
+        let _ = self.sender.lock().unwrap().send(Message::Sample(bytes));
+    }
+
+    /// Wait until every queued sample is written and flushed to the file. Returns the first error the
+    /// thread ran into since the last flush, if any.
+    pub fn flush(&self) -> Result<()> {
+        let (sender, receiver) = mpsc::channel();
+        let _ = self.sender.lock().unwrap().send(Message::Flush(sender));
+
+        receiver
+            .recv()
+            .unwrap_or_else(|_| Err(Error::other("the sample writer has stopped")))
+    }
+}
+
+fn write_samples(mut file: BufWriter<File>, receiver: Receiver<Message>) {
+    let mut failure = None;
+    for message in receiver {
+        match message {
+            Message::Sample(bytes) => {
+                if failure.is_none() {
+                    let written = file
+                        .write_all(&(bytes.len() as u32).to_le_bytes())
+                        .and_then(|_| file.write_all(&bytes));
+                    failure = written.err();
+                }
+            }
+            Message::Flush(reply) => {
+                let result = match failure.take() {
+                    Some(error) => Err(error),
+                    None => file.flush(),
+                };
+                let _ = reply.send(result);
+            }
+        }
+    }
+
+    let _ = file.flush();
+}
+
+/// Iterates over the samples of a file written by a `SampleWriter`, creating their genes from the genes
+/// of a template genotype (see `wire`) - usually `codex.encode()`. Fails with
+/// `std::io::ErrorKind::InvalidData` on a truncated or malformed sample.
+pub struct SampleReader<C: Chromosome> {
+    reader: BufReader<File>,
+    template: Genotype<C>,
+}
+
+impl<C: Chromosome> SampleReader<C> {
+    pub fn open(path: impl AsRef<Path>, template: Genotype<C>) -> Result<Self> {
+        Ok(SampleReader {
+            reader: BufReader::new(File::open(path)?),
+            template,
+        })
+    }
+}
+
+impl<C> Iterator for SampleReader<C>
+where
+    C: Chromosome,
+    <C::Gene as Gene>::Allele: WireAllele,
+{
+    type Item = Result<GenerationSample<C>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut len = [0_u8; 4];
+        match self.reader.read(&mut len[..1]) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(error) => return Some(Err(error)),
+        }
+
+        let mut read = || {
+            self.reader.read_exact(&mut len[1..])?;
+            let mut bytes = vec![0_u8; u32::from_le_bytes(len) as usize];
+            self.reader.read_exact(&mut bytes)?;
+            wire::decode_sample(&bytes, &self.template)
+        };
+
+        Some(read().map_err(|error| match error.kind() {
+            ErrorKind::UnexpectedEof => Error::new(ErrorKind::InvalidData, "truncated sample"),
+            _ => error,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Codex, IntCodex, Phenotype, Score};
+
+    #[test]
+    fn test_samples_round_trip_and_truncation_is_an_error() {
+        let path = std::env::temp_dir().join("radiate-samples-test.rdws");
+        let codex = IntCodex::<i32>::new(1, 4, 0, 10);
+        let population = (0..3)
+            .map(|i| {
+                let mut individual = Phenotype::from_genotype(codex.encode(), i);
+                individual.set_score(Some(Score::from_int(i)));
+                individual
+            })
+            .collect::<Population<_>>();
+
+        let writer = SampleWriter::create(&path).unwrap();
+        writer.write(2, &population);
+        writer.write(4, &population);
+        writer.flush().unwrap();
+
+        let samples = SampleReader::open(&path, codex.encode())
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[1].index, 4);
+        assert!(samples[1].population.iter().eq(population.iter()));
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        let mut reader = SampleReader::open(&path, codex.encode()).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+type SizeFn = Arc<dyn Fn(i32, usize, i32) -> usize + Send + Sync>;
+
+/// How the size of the population changes over a run. Before every generation the engine asks the
+/// schedule for the size of the next population - shrinking it simply selects fewer survivors and
+/// offspring, growing it selects more and fills any gap the selectors leave with new individuals.
+///
+/// Set it with `GeneticEngineParams::population_schedule`. The initial population is still
+/// `population_size` individuals, which is also the base size `Stagnation` returns to.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// // Explore with a large population, then shrink it down to refine the best solutions.
+/// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 2, -1.0, 1.0))
+///     .population_size(100)
+///     .population_schedule(PopulationSchedule::Linear { start: 100, end: 20, generations: 10 })
+///     .fitness_fn(|genes: Vec<Vec<f32>>| genes[0].iter().map(|v| v * v).sum::<f32>())
+///     .minimizing()
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 15);
+/// assert_eq!(result.population.len(), 20);
+/// ```
+#[derive(Clone)]
+pub enum PopulationSchedule {
+    /// Move linearly from `start` to `end` over `generations`, then stay at `end`.
+    Linear {
+        start: usize,
+        end: usize,
+        generations: i32,
+    },
+    /// A list of `(generation, size)` pairs - the size of the last pair whose generation has been reached
+    /// is used, or the current size before the first one.
+    Steps(Vec<(i32, usize)>),
+    /// Grow the population by `growth` every `patience` generations the best score doesn't improve, up to
+    /// `max`. As soon as it improves again the population returns to its base size.
+    Stagnation {
+        patience: i32,
+        growth: f32,
+        max: usize,
+    },
+    /// Compute the size from the generation, the current size and the number of generations since the
+    /// best score last improved.
+    Custom(SizeFn),
+}
+
+impl PopulationSchedule {
+    /// Create a `Custom` schedule from a function of (generation, current size, stagnant generations).
+    pub fn custom<F>(size_fn: F) -> Self
+    where
+        F: Fn(i32, usize, i32) -> usize + Send + Sync + 'static,
+    {
+        PopulationSchedule::Custom(Arc::new(size_fn))
+    }
+
+    /// The size of the population for `generation`, given its `base` (initial) and `current` size and
+    /// the number of generations the best score hasn't improved. Never less than 1.
+    pub fn size(&self, base: usize, generation: i32, current: usize, stagnation: i32) -> usize {
+        let size = match self {
+            PopulationSchedule::Linear {
+                start,
+                end,
+                generations,
+            } => {
+                if *generations <= 0 || generation >= *generations {
+                    *end
+                } else {
+                    let progress = generation.max(0) as f32 / *generations as f32;
+                    (*start as f32 + (*end as f32 - *start as f32) * progress).round() as usize
+                }
+            }
+            PopulationSchedule::Steps(steps) => steps
+                .iter()
+                .filter(|(from, _)| *from <= generation)
+                .max_by_key(|(from, _)| *from)
+                .map(|(_, size)| *size)
+                .unwrap_or(current),
+            PopulationSchedule::Stagnation {
+                patience,
+                growth,
+                max,
+            } => {
+                if stagnation == 0 {
+                    base
+                } else if *patience > 0 && stagnation % patience == 0 {
+                    ((current as f32 * growth).ceil() as usize)
+                        .max(current + 1)
+                        .min(*max)
+                } else {
+                    current
+                }
+            }
+            PopulationSchedule::Custom(size_fn) => size_fn(generation, current, stagnation),
+        };
+
+        size.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_and_step_schedules() {
+        let linear = PopulationSchedule::Linear {
+            start: 100,
+            end: 20,
+            generations: 4,
+        };
+        let sizes = (0..6)
+            .map(|generation| linear.size(100, generation, 100, 0))
+            .collect::<Vec<usize>>();
+        assert_eq!(sizes, vec![100, 80, 60, 40, 20, 20]);
+
+        let steps = PopulationSchedule::Steps(vec![(5, 50), (2, 80)]);
+        assert_eq!(steps.size(100, 0, 100, 0), 100);
+        assert_eq!(steps.size(100, 3, 100, 0), 80);
+        assert_eq!(steps.size(100, 7, 80, 0), 50);
+    }
+
+    #[test]
+    fn test_stagnation_schedule_grows_and_resets() {
+        let schedule = PopulationSchedule::Stagnation {
+            patience: 3,
+            growth: 1.5,
+            max: 40,
+        };
+
+        assert_eq!(schedule.size(20, 10, 20, 2), 20);
+        assert_eq!(schedule.size(20, 10, 20, 3), 30);
+        assert_eq!(schedule.size(20, 10, 30, 6), 40);
+        assert_eq!(schedule.size(20, 10, 40, 0), 20);
+    }
+}
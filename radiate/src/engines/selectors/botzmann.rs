@@ -1,15 +1,34 @@
 use super::Select;
 use crate::objectives::{Objective, Optimize};
 use crate::selectors::ProbabilityWheelIterator;
-use crate::{Chromosome, EngineCompoment, Population};
+use crate::Description;
+use crate::{Chromosome, ControlPanel, EngineCompoment, Knob, Population};
 
 pub struct BoltzmannSelector {
     temperature: f32,
+    control: Option<ControlPanel>,
 }
 
 impl BoltzmannSelector {
     pub fn new(temperature: f32) -> Self {
-        BoltzmannSelector { temperature }
+        BoltzmannSelector {
+            temperature,
+            control: None,
+        }
+    }
+
+    /// Follow the `Knob::Temperature` of a `ControlPanel`, so the temperature can be changed while the
+    /// engine runs. Until the knob is set, the temperature given to `new` is used.
+    pub fn with_control(mut self, panel: ControlPanel) -> Self {
+        self.control = Some(panel);
+        self
+    }
+
+    fn temperature(&self) -> f32 {
+        self.control
+            .as_ref()
+            .and_then(|panel| panel.get(Knob::Temperature))
+            .unwrap_or(self.temperature)
     }
 }
 
@@ -17,6 +36,10 @@ impl EngineCompoment for BoltzmannSelector {
     fn name(&self) -> &'static str {
         "BoltzmannSelector"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("temperature", self.temperature())
+    }
 }
 
 impl<C: Chromosome> Select<C> for BoltzmannSelector {
@@ -52,11 +75,12 @@ impl<C: Chromosome> Select<C> for BoltzmannSelector {
 
         // Calculate the fitness values for each individual (normalized)
         // and apply the Boltzmann distribution to get the probabilities (temp * fitness).exp()
+        let temperature = self.temperature();
         let mut result = Vec::with_capacity(population.len());
         for individual in population.iter() {
             let score = individual.score().as_ref().unwrap().as_f32();
             let fitness = (score - min) / diff;
-            let value = (self.temperature * fitness).exp();
+            let value = (temperature * fitness).exp();
 
             result.push(value);
         }
@@ -1,4 +1,5 @@
 use crate::objectives::{Objective, Optimize};
+use crate::Description;
 use crate::{random_provider, Chromosome, EngineCompoment, Population, Select};
 
 pub struct LinearRankSelector {
@@ -15,6 +16,10 @@ impl EngineCompoment for LinearRankSelector {
     fn name(&self) -> &'static str {
         "LinearRankSelector"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("selection_pressure", self.selection_pressure)
+    }
 }
 
 impl<C: Chromosome> Select<C> for LinearRankSelector {
@@ -12,6 +12,12 @@ use crate::{Chromosome, EngineCompoment, Population, Select};
 /// diverse solutions in a multi-objective optimization problem. It uses 'fast non-dominated sorting'
 pub struct NSGA2Selector;
 
+impl Default for NSGA2Selector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NSGA2Selector {
     pub fn new() -> Self {
         NSGA2Selector
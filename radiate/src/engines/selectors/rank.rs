@@ -7,6 +7,12 @@ use crate::{random_provider, Chromosome, EngineCompoment, Population, Select};
 // but only a sorting of the individuals according to quality.
 pub struct RankSelector;
 
+impl Default for RankSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RankSelector {
     pub fn new() -> Self {
         RankSelector
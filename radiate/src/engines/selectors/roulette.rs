@@ -5,6 +5,12 @@ use crate::{Chromosome, EngineCompoment, Population};
 
 pub struct RouletteSelector;
 
+impl Default for RouletteSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RouletteSelector {
     pub fn new() -> Self {
         RouletteSelector
@@ -1,6 +1,10 @@
 use crate::objectives::Objective;
+use crate::Description;
 use crate::{random_provider, Chromosome, EngineCompoment, Population, Select};
 
+/// Selects the population with `replacement_count` random individuals overwritten by random copies of
+/// others. For true steady-state dynamics, where every step only replaces a few individuals with new
+/// offspring, see `GeneticEngineParams::steady_state`.
 pub struct SteadyStateSelector {
     replacement_count: usize,
 }
@@ -15,6 +19,10 @@ impl EngineCompoment for SteadyStateSelector {
     fn name(&self) -> &'static str {
         "SteadyStateSelector"
     }
+
+    fn describe(&self) -> Description {
+        Description::new(self.name()).param("replacement_count", self.replacement_count)
+    }
 }
 
 impl<C: Chromosome> Select<C> for SteadyStateSelector {
@@ -3,6 +3,12 @@ use crate::{random_provider, Chromosome, EngineCompoment, Population, Select};
 
 pub struct StochasticUniversalSamplingSelector;
 
+impl Default for StochasticUniversalSamplingSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StochasticUniversalSamplingSelector {
     pub fn new() -> Self {
         StochasticUniversalSamplingSelector
@@ -1,14 +1,32 @@
 use super::Select;
 use crate::objectives::Objective;
+use crate::Description;
 use crate::{random_provider, Chromosome, EngineCompoment, Population};
 
 pub struct TournamentSelector {
     num: usize,
+    significance: Option<f32>,
 }
 
 impl TournamentSelector {
     pub fn new(num: usize) -> Self {
-        TournamentSelector { num }
+        TournamentSelector {
+            num,
+            significance: None,
+        }
+    }
+
+    /// Only let the best entrant of a tournament win if it is significantly better than the others at
+    /// significance level `alpha` - otherwise the winner is picked at random among the entrants the best
+    /// isn't significantly better than. Meant for noisy fitness with `repeat_evaluations`, so small
+    /// differences in the mean scores don't decide selection. Panics if `alpha` is not between 0 and 1.
+    pub fn with_significance(mut self, alpha: f32) -> Self {
+        if alpha <= 0.0 || alpha > 1.0 {
+            panic!("alpha must be in (0, 1]");
+        }
+
+        self.significance = Some(alpha);
+        self
     }
 }
 
@@ -16,10 +34,23 @@ impl EngineCompoment for TournamentSelector {
     fn name(&self) -> &'static str {
         "TournamentSelector"
     }
+
+    fn describe(&self) -> Description {
+        let description = Description::new(self.name()).param("num", self.num);
+        match self.significance {
+            Some(alpha) => description.param("significance", alpha),
+            None => description,
+        }
+    }
 }
 
 impl<C: Chromosome> Select<C> for TournamentSelector {
-    fn select(&self, population: &Population<C>, _: &Objective, count: usize) -> Population<C> {
+    fn select(
+        &self,
+        population: &Population<C>,
+        objective: &Objective,
+        count: usize,
+    ) -> Population<C> {
         let mut selected = Vec::with_capacity(count);
 
         for _ in 0..count {
@@ -31,7 +62,26 @@ impl<C: Chromosome> Select<C> for TournamentSelector {
 
             tournament.sort();
 
-            selected.push(population[tournament[0]].clone());
+            let winner = match (self.significance, population[tournament[0]].score()) {
+                (Some(alpha), Some(best)) => {
+                    let tied = tournament
+                        .iter()
+                        .filter(|idx| match population[**idx].score() {
+                            Some(score) => !objective.is_significantly_better(best, score, alpha),
+                            None => false,
+                        })
+                        .copied()
+                        .collect::<Vec<usize>>();
+
+                    match tied.is_empty() {
+                        true => tournament[0],
+                        false => *random_provider::choose(&tied),
+                    }
+                }
+                _ => tournament[0],
+            };
+
+            selected.push(population[winner].clone());
         }
 
         Population::new(selected)
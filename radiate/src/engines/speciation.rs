@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use super::objectives::{Objective, Optimize, Score};
+use super::{Chromosome, Diversity, Genotype, Population};
+
+/// A species of a `Speciation` - a group of individuals within the compatibility threshold of its
+/// representative.
+#[derive(Clone, Debug)]
+pub struct Species<C: Chromosome> {
+    id: usize,
+    representative: Genotype<C>,
+    size: usize,
+    age: usize,
+}
+
+impl<C: Chromosome> Species<C> {
+    /// The id of the species, as set on its members (see `Phenotype::species`). Ids are never reused.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The best member of the species in the last generation it was speciated.
+    pub fn representative(&self) -> &Genotype<C> {
+        &self.representative
+    }
+
+    /// The number of members of the species in the last generation.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of generations the species has existed for - 0 in the generation it appeared.
+    pub fn age(&self) -> usize {
+        self.age
+    }
+}
+
+/// NEAT-style speciation. Every generation, right before selection, the population is grouped into
+/// species by a `Diversity` distance: each individual (best first) joins the first species whose
+/// representative from the previous generation is closer than the compatibility `threshold`, or starts a
+/// new species. The representative of a species is then its best member, and species left without
+/// members die out. Every individual's species is set on it (see `Phenotype::species`), so species-aware
+/// alterers like `WithinSpecies` only mate individuals of the same species.
+///
+/// Selection then sees explicitly shared fitness - each score is divided by the size of its species
+/// (multiplied when minimizing, and the other way around for negative scores), so large species can't take
+/// over the population and small niches survive. Species younger than `protect_young` generations keep
+/// their raw scores, giving new structures time to optimize before they have to compete. Like the
+/// `FitnessShaping`, which is applied after the sharing, the raw scores of the population are left
+/// untouched.
+///
+/// Set with `GeneticEngineParams::speciation`. The species of the run are available on the
+/// `EngineContext` as `species`, and their number is recorded in the metrics.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .speciation(Speciation::new(HammingDistance, 3.0).protect_young(5))
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 20);
+/// let species = result.species.as_ref().unwrap();
+///
+/// assert!(!species.is_empty());
+/// assert!(result.population.iter().all(|individual| individual.species().is_some()));
+/// ```
+#[derive(Clone)]
+pub struct Speciation<C: Chromosome> {
+    distance: Arc<dyn Diversity<C> + Send + Sync>,
+    threshold: f32,
+    protect_young: usize,
+    species: Vec<Species<C>>,
+    next_id: usize,
+}
+
+impl<C: Chromosome> Speciation<C> {
+    /// Group individuals closer than `threshold` by the `distance`. Panics if `threshold` isn't positive.
+    pub fn new<D>(distance: D, threshold: f32) -> Self
+    where
+        D: Diversity<C> + Send + Sync + 'static,
+    {
+        if threshold <= 0.0 {
+            panic!("threshold must be greater than 0");
+        }
+
+        Speciation {
+            distance: Arc::new(distance),
+            threshold,
+            protect_young: 0,
+            species: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Exempt species from fitness sharing for their first `generations` generations. Default is 0.
+    pub fn protect_young(mut self, generations: usize) -> Self {
+        self.protect_young = generations;
+        self
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn species(&self) -> &[Species<C>] {
+        &self.species
+    }
+
+    pub fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+
+    /// Assign every individual of a sorted population to a species, ageing the surviving species by a
+    /// generation and removing the species that have no members left.
+    pub fn speciate(&mut self, population: &mut Population<C>) {
+        for species in self.species.iter_mut() {
+            species.size = 0;
+            species.age += 1;
+        }
+
+        // New representatives are only taken on once everyone is assigned, so every individual is
+        // compared with the representatives of the previous generation.
+        let mut representatives = vec![None; self.species.len()];
+        for individual in population.iter_mut() {
+            let genotype = individual.genotype();
+            let found = self.species.iter().position(|species| {
+                self.distance.distance(&species.representative, genotype) < self.threshold
+            });
+
+            let index = match found {
+                Some(index) => index,
+                None => {
+                    self.species.push(Species {
+                        id: self.next_id,
+                        representative: genotype.clone(),
+                        size: 0,
+                        age: 0,
+                    });
+                    self.next_id += 1;
+                    representatives.push(None);
+                    self.species.len() - 1
+                }
+            };
+
+            let species = &mut self.species[index];
+            species.size += 1;
+            if representatives[index].is_none() {
+                representatives[index] = Some(genotype.clone());
+            }
+
+            individual.set_species(Some(species.id));
+        }
+
+        self.species = std::mem::take(&mut self.species)
+            .into_iter()
+            .zip(representatives)
+            .filter_map(|(mut species, representative)| {
+                species.representative = representative?;
+                Some(species)
+            })
+            .collect();
+    }
+
+    /// Share the scores of a speciated population within its species, in place. Individuals without a
+    /// species or a score are left as they are.
+    pub fn share(&self, population: &mut Population<C>, objective: &Objective) {
+        for individual in population.iter_mut() {
+            let Some(species) = individual
+                .species()
+                .and_then(|id| self.species.iter().find(|species| species.id == id))
+            else {
+                continue;
+            };
+
+            if species.age < self.protect_young {
+                continue;
+            }
+
+            let Some(score) = individual.score() else {
+                continue;
+            };
+
+            let size = species.size.max(1) as f32;
+            let values = score
+                .values
+                .iter()
+                .zip(objective.as_ref().iter().cycle())
+                .map(|(value, opt)| match (opt, *value >= 0.0) {
+                    (Optimize::Maximize, true) | (Optimize::Minimize, false) => value / size,
+                    (Optimize::Maximize, false) | (Optimize::Minimize, true) => value * size,
+                })
+                .collect::<Vec<f32>>();
+
+            individual.set_score(Some(Score::from_vec(values)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, FloatGene, Gene, HammingDistance, Phenotype};
+
+    fn population(alleles: &[f32]) -> Population<FloatChromosome> {
+        alleles
+            .iter()
+            .map(|allele| {
+                let genes = vec![FloatGene::new(0.0, 10.0).with_allele(allele)];
+                let mut individual =
+                    Phenotype::from_chromosomes(vec![FloatChromosome { genes }], 0);
+                individual.set_score(Some(Score::from_f32(*allele)));
+                individual
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_speciate_groups_close_individuals() {
+        let mut population = population(&[1.0, 1.0, 1.0, 5.0]);
+        let mut speciation = Speciation::new(HammingDistance, 0.5);
+
+        speciation.speciate(&mut population);
+
+        assert_eq!(speciation.len(), 2);
+        assert_eq!(speciation.species()[0].size(), 3);
+        assert_eq!(population[3].species(), Some(1));
+
+        // the second species dies out, and a new one appears with a fresh id
+        let mut next = self::population(&[1.0, 7.0]);
+        speciation.speciate(&mut next);
+
+        let ids = speciation
+            .species()
+            .iter()
+            .map(Species::id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![0, 2]);
+        assert_eq!(speciation.species()[0].age(), 1);
+    }
+
+    #[test]
+    fn test_share_divides_by_species_size_except_for_young_species() {
+        let objective = Objective::Single(Optimize::Maximize);
+        let mut speciation = Speciation::new(HammingDistance, 0.5);
+
+        let mut population = population(&[4.0, 4.0, 2.0]);
+        speciation.speciate(&mut population);
+        speciation.share(&mut population, &objective);
+        assert_eq!(population[0].score().unwrap().as_f32(), 2.0);
+        assert_eq!(population[2].score().unwrap().as_f32(), 2.0);
+
+        let mut speciation = speciation.protect_young(1);
+        let mut population = self::population(&[4.0, 4.0, 2.0, 6.0]);
+        speciation.speciate(&mut population);
+        speciation.share(&mut population, &objective);
+        assert_eq!(population[0].score().unwrap().as_f32(), 2.0);
+        assert_eq!(population[3].score().unwrap().as_f32(), 6.0);
+    }
+}
@@ -0,0 +1,511 @@
+use crate::{random_provider, Chromosome, GeneticEngine, Optimize};
+use std::fmt::Display;
+
+/// The result of a rank test comparing two samples. The p-value is two-sided and comes from the normal
+/// approximation of the statistic (with a correction for ties and for continuity), which is accurate
+/// from around ten observations per sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RankTest {
+    /// The U statistic of the first sample for `mann_whitney_u`, the sum of the positive ranks for
+    /// `wilcoxon_signed_rank`.
+    pub statistic: f32,
+    pub z: f32,
+    pub p_value: f32,
+}
+
+impl RankTest {
+    /// Whether the difference between the samples is significant at level `alpha`, e.g. 0.05.
+    pub fn is_significant(&self, alpha: f32) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// A mean and its confidence interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfidenceInterval {
+    pub mean: f32,
+    pub lower: f32,
+    pub upper: f32,
+}
+
+/// The Mann-Whitney U test (Wilcoxon rank-sum test) of two independent samples, e.g. the final best
+/// scores of repeated runs of two configurations. Panics if either sample is empty.
+pub fn mann_whitney_u(one: &[f32], two: &[f32]) -> RankTest {
+    if one.is_empty() || two.is_empty() {
+        panic!("Both samples need at least one observation");
+    }
+
+    let combined = one.iter().chain(two.iter()).copied().collect::<Vec<f32>>();
+    let (ranks, ties) = rank(&combined);
+
+    let n1 = one.len() as f64;
+    let n2 = two.len() as f64;
+    let n = n1 + n2;
+    let rank_sum = ranks[..one.len()].iter().sum::<f64>();
+    let u = rank_sum - n1 * (n1 + 1.0) / 2.0;
+
+    let mean = n1 * n2 / 2.0;
+    let variance = if n > 1.0 {
+        n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))
+    } else {
+        0.0
+    };
+
+    rank_test(u, mean, variance)
+}
+
+/// The Wilcoxon signed-rank test of two paired samples, e.g. the final best scores of two
+/// configurations run with the same seeds. Pairs with equal values are dropped. Panics if the samples
+/// differ in length or are empty.
+pub fn wilcoxon_signed_rank(one: &[f32], two: &[f32]) -> RankTest {
+    if one.len() != two.len() {
+        panic!("Paired samples must have the same length");
+    }
+    if one.is_empty() {
+        panic!("Both samples need at least one observation");
+    }
+
+    let differences = one
+        .iter()
+        .zip(two.iter())
+        .map(|(a, b)| a - b)
+        .filter(|difference| *difference != 0.0)
+        .collect::<Vec<f32>>();
+
+    let magnitudes = differences.iter().map(|d| d.abs()).collect::<Vec<f32>>();
+    let (ranks, ties) = rank(&magnitudes);
+
+    let n = differences.len() as f64;
+    let positive = differences
+        .iter()
+        .zip(ranks.iter())
+        .filter(|(difference, _)| **difference > 0.0)
+        .map(|(_, rank)| rank)
+        .sum::<f64>();
+
+    let mean = n * (n + 1.0) / 4.0;
+    let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties / 48.0;
+
+    rank_test(positive, mean, variance)
+}
+
+/// The Vargha-Delaney A effect size - the probability that an observation of `one` is larger than an
+/// observation of `two`, counting ties as half. 0.5 means no effect; values around 0.71 (or 0.29) and
+/// beyond are usually considered large. Panics if either sample is empty.
+pub fn effect_size(one: &[f32], two: &[f32]) -> f32 {
+    if one.is_empty() || two.is_empty() {
+        panic!("Both samples need at least one observation");
+    }
+
+    let wins = one
+        .iter()
+        .flat_map(|a| two.iter().map(move |b| (a, b)))
+        .map(|(a, b)| match a.partial_cmp(b) {
+            Some(std::cmp::Ordering::Greater) => 1.0,
+            Some(std::cmp::Ordering::Equal) => 0.5,
+            _ => 0.0,
+        })
+        .sum::<f64>();
+
+    (wins / (one.len() * two.len()) as f64) as f32
+}
+
+/// The mean of the samples with a bootstrapped (percentile) confidence interval from `resamples`
+/// resamples, drawn from the `random_provider`. Panics if there are no samples, `confidence` isn't
+/// between 0 and 1 or `resamples` is 0.
+pub fn bootstrap_mean(samples: &[f32], confidence: f32, resamples: usize) -> ConfidenceInterval {
+    if samples.is_empty() {
+        panic!("Bootstrapping needs at least one sample");
+    }
+    if !(0.0..1.0).contains(&confidence) || confidence <= 0.0 {
+        panic!("confidence must be between 0 and 1");
+    }
+    if resamples < 1 {
+        panic!("resamples must be greater than 0");
+    }
+
+    let mut means = (0..resamples)
+        .map(|_| {
+            (0..samples.len())
+                .map(|_| samples[random_provider::gen_range(0..samples.len())] as f64)
+                .sum::<f64>()
+                / samples.len() as f64
+        })
+        .collect::<Vec<f64>>();
+    means.sort_by(|a, b| a.total_cmp(b));
+
+    let tail = (1.0 - confidence as f64) / 2.0;
+    let at = |quantile: f64| means[((quantile * (resamples - 1) as f64).round()) as usize] as f32;
+
+    ConfidenceInterval {
+        mean: (samples.iter().map(|x| *x as f64).sum::<f64>() / samples.len() as f64) as f32,
+        lower: at(tail),
+        upper: at(1.0 - tail),
+    }
+}
+
+/// The mean convergence curve of repeated runs with a bootstrapped confidence interval per generation
+/// (see `bootstrap_mean`). Runs shorter than others only count for the generations they reached.
+pub fn bootstrap_curve(
+    curves: &[Vec<f32>],
+    confidence: f32,
+    resamples: usize,
+) -> Vec<ConfidenceInterval> {
+    let length = curves.iter().map(|curve| curve.len()).max().unwrap_or(0);
+
+    (0..length)
+        .map(|generation| {
+            let samples = curves
+                .iter()
+                .filter_map(|curve| curve.get(generation).copied())
+                .collect::<Vec<f32>>();
+            bootstrap_mean(&samples, confidence, resamples)
+        })
+        .collect()
+}
+
+/// The best scores of repeated runs of one configuration - the final score of every run and its best
+/// score per generation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunSamples {
+    pub name: String,
+    pub final_scores: Vec<f32>,
+    pub curves: Vec<Vec<f32>>,
+}
+
+impl RunSamples {
+    pub fn new(name: impl Into<String>) -> Self {
+        RunSamples {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Run `runs` engines built by `factory` (given the index of the run, e.g. to seed the engine with)
+    /// for `generations` generations each, recording the best score of every generation.
+    pub fn collect<C, T, F>(
+        name: impl Into<String>,
+        runs: usize,
+        generations: usize,
+        factory: F,
+    ) -> Self
+    where
+        C: Chromosome + 'static,
+        T: Clone + Send + 'static,
+        F: Fn(usize) -> GeneticEngine<C, T>,
+    {
+        let mut samples = RunSamples::new(name);
+        for run in 0..runs {
+            let engine = factory(run);
+            let curve = engine
+                .iter()
+                .take(generations)
+                .map(|ctx| ctx.score().as_f32())
+                .collect();
+            samples.push(curve);
+        }
+
+        samples
+    }
+
+    /// Add a run by its best score per generation; its final score is the last one.
+    pub fn push(&mut self, curve: Vec<f32>) {
+        if let Some(last) = curve.last() {
+            self.final_scores.push(*last);
+        }
+
+        self.curves.push(curve);
+    }
+
+    pub fn runs(&self) -> usize {
+        self.final_scores.len()
+    }
+
+    pub fn median(&self) -> f32 {
+        let mut scores = self.final_scores.clone();
+        scores.sort_by(|a, b| a.total_cmp(b));
+        match scores.len() {
+            0 => f32::NAN,
+            n if n % 2 == 0 => (scores[n / 2 - 1] + scores[n / 2]) / 2.0,
+            n => scores[n / 2],
+        }
+    }
+}
+
+/// The comparison of the final scores of two configurations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairComparison {
+    pub one: String,
+    pub two: String,
+    pub test: RankTest,
+    /// The probability that a run of `one` ends with a better score than a run of `two` (see `effect_size`).
+    pub effect: f32,
+    /// The better configuration, if the difference is significant.
+    pub winner: Option<String>,
+}
+
+/// The statistical comparison of several configurations run repeatedly - the final scores of every
+/// configuration with the confidence interval of their mean, and the Mann-Whitney U test and effect
+/// size of every pair - so a claim like "configuration A beats B" is backed by a p-value.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = |mutation: f32| {
+///     move |run: usize| {
+///         GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+///             .minimizing()
+///             .seed(run as u64)
+///             .alter(alters![UniformCrossover::new(0.5), UniformMutator::new(mutation)])
+///             .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///             .build()
+///     }
+/// };
+///
+/// let low = RunSamples::collect("low", 10, 15, engine(0.001));
+/// let high = RunSamples::collect("high", 10, 15, engine(0.1));
+///
+/// let comparison = RunComparison::new(&[low, high], Optimize::Minimize, 0.05);
+/// assert_eq!(comparison.pairs().len(), 1);
+/// println!("{}", comparison);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunComparison {
+    summaries: Vec<(String, usize, f32, ConfidenceInterval)>,
+    pairs: Vec<PairComparison>,
+    alpha: f32,
+}
+
+impl RunComparison {
+    const CONFIDENCE: f32 = 0.95;
+    const RESAMPLES: usize = 1000;
+
+    /// Compare the configurations at significance level `alpha`. Panics if a configuration has no runs.
+    pub fn new(samples: &[RunSamples], optimize: Optimize, alpha: f32) -> Self {
+        let summaries = samples
+            .iter()
+            .map(|sample| {
+                let interval = bootstrap_mean(
+                    &sample.final_scores,
+                    RunComparison::CONFIDENCE,
+                    RunComparison::RESAMPLES,
+                );
+                (
+                    sample.name.clone(),
+                    sample.runs(),
+                    sample.median(),
+                    interval,
+                )
+            })
+            .collect();
+
+        let mut pairs = Vec::new();
+        for (i, one) in samples.iter().enumerate() {
+            for two in samples.iter().skip(i + 1) {
+                let test = mann_whitney_u(&one.final_scores, &two.final_scores);
+                let effect = match optimize {
+                    Optimize::Maximize => effect_size(&one.final_scores, &two.final_scores),
+                    Optimize::Minimize => effect_size(&two.final_scores, &one.final_scores),
+                };
+
+                let winner = test.is_significant(alpha).then(|| match effect > 0.5 {
+                    true => one.name.clone(),
+                    false => two.name.clone(),
+                });
+
+                pairs.push(PairComparison {
+                    one: one.name.clone(),
+                    two: two.name.clone(),
+                    test,
+                    effect,
+                    winner,
+                });
+            }
+        }
+
+        RunComparison {
+            summaries,
+            pairs,
+            alpha,
+        }
+    }
+
+    /// The name, number of runs, median final score and mean final score (with its 95% confidence
+    /// interval) of every configuration.
+    pub fn summaries(&self) -> &[(String, usize, f32, ConfidenceInterval)] {
+        &self.summaries
+    }
+
+    pub fn pairs(&self) -> &[PairComparison] {
+        &self.pairs
+    }
+
+    /// The comparison of two configurations by name, in either order.
+    pub fn pair(&self, one: &str, two: &str) -> Option<&PairComparison> {
+        self.pairs.iter().find(|pair| {
+            (pair.one == one && pair.two == two) || (pair.one == two && pair.two == one)
+        })
+    }
+}
+
+impl Display for RunComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:>6} {:>12} {:>12} {:>26}",
+            "Configuration", "Runs", "Median", "Mean", "95% CI"
+        )?;
+        for (name, runs, median, interval) in self.summaries.iter() {
+            writeln!(
+                f,
+                "{:<16} {:>6} {:>12.4} {:>12.4} {:>26}",
+                name,
+                runs,
+                median,
+                interval.mean,
+                format!("[{:.4}, {:.4}]", interval.lower, interval.upper)
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<16} {:<16} {:>10} {:>8} {:>16}",
+            "A",
+            "B",
+            "p-value",
+            "A12",
+            format!("Winner (a={})", self.alpha)
+        )?;
+        for pair in self.pairs.iter() {
+            writeln!(
+                f,
+                "{:<16} {:<16} {:>10.4} {:>8.3} {:>16}",
+                pair.one,
+                pair.two,
+                pair.test.p_value,
+                pair.effect,
+                pair.winner.as_deref().unwrap_or("-")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The ranks of the values (1-based, ties get their average rank) and the tie correction term - the
+/// sum of `t^3 - t` over every group of `t` tied values.
+fn rank(values: &[f32]) -> (Vec<f64>, f64) {
+    let mut order = (0..values.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+
+        let average = (start + end + 1) as f64 / 2.0;
+        order[start..end]
+            .iter()
+            .for_each(|index| ranks[*index] = average);
+
+        let tied = (end - start) as f64;
+        ties += tied * tied * tied - tied;
+        start = end;
+    }
+
+    (ranks, ties)
+}
+
+fn rank_test(statistic: f64, mean: f64, variance: f64) -> RankTest {
+    if variance <= 0.0 {
+        return RankTest {
+            statistic: statistic as f32,
+            z: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let difference = statistic - mean;
+    let corrected = (difference.abs() - 0.5).max(0.0) * difference.signum();
+    let z = corrected / variance.sqrt();
+
+    RankTest {
+        statistic: statistic as f32,
+        z: z as f32,
+        p_value: erfc(z.abs() / std::f64::consts::SQRT_2).min(1.0) as f32,
+    }
+}
+
+/// The complementary error function, with a fractional error below 1.2e-7 (Numerical Recipes, 6.2).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let value = t * polynomial.exp();
+
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_tests_detect_shifted_samples() {
+        let one = (0..20).map(|i| i as f32).collect::<Vec<f32>>();
+        let shifted = one.iter().map(|x| x + 15.0).collect::<Vec<f32>>();
+        let overlapping = one.iter().map(|x| x + 0.5).collect::<Vec<f32>>();
+
+        let test = mann_whitney_u(&one, &shifted);
+        assert!(test.p_value < 0.001);
+        assert!(test.z < 0.0);
+        assert!(!mann_whitney_u(&one, &overlapping).is_significant(0.05));
+        assert_eq!(mann_whitney_u(&one, &one).p_value, 1.0);
+
+        let paired = wilcoxon_signed_rank(&shifted, &one);
+        assert_eq!(paired.statistic, 210.0);
+        assert!(paired.p_value < 0.001);
+
+        assert_eq!(
+            effect_size(&shifted, &one),
+            1.0 - effect_size(&one, &shifted)
+        );
+        assert!((erfc(0.0) - 1.0).abs() < 1e-6);
+        assert!((erfc(1.959964 / std::f64::consts::SQRT_2) - 0.05).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_bootstrap_interval_contains_the_mean() {
+        random_provider::scoped_seed(42, || {
+            let samples = (0..50).map(|i| (i % 10) as f32).collect::<Vec<f32>>();
+            let interval = bootstrap_mean(&samples, 0.95, 500);
+
+            assert_eq!(interval.mean, 4.5);
+            assert!(interval.lower < 4.5 && interval.upper > 4.5);
+            assert!(interval.upper - interval.lower < 2.0);
+
+            let curves = vec![vec![3.0, 2.0, 1.0], vec![4.0, 2.0]];
+            let curve = bootstrap_curve(&curves, 0.9, 100);
+            assert_eq!(curve.len(), 3);
+            assert_eq!(curve[1].lower, 2.0);
+            assert_eq!(curve[2].mean, 1.0);
+        });
+    }
+}
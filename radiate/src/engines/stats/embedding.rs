@@ -0,0 +1,333 @@
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Projects high-dimensional points - one per individual, its genes mapped to numbers - to a few
+/// dimensions. A projection is called once per recorded generation and may keep state between
+/// generations, e.g. to keep its axes from flipping. `Pca` is built in; other methods such as UMAP
+/// plug in by implementing this trait.
+pub trait Projection: Send {
+    fn project(&mut self, points: &[Vec<f32>], dimensions: usize) -> Vec<Vec<f32>>;
+}
+
+/// Principal component analysis - projects points onto the directions of largest variance. The
+/// components are found by power iteration without forming the covariance matrix, so every generation
+/// takes `O(individuals * genes)` per iteration. Each generation's components are aligned (flipped)
+/// with the previous generation's, so consecutive frames of an animation don't mirror each other.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pca {
+    mean: Vec<f32>,
+    components: Vec<Vec<f32>>,
+    explained_variance: Vec<f32>,
+}
+
+impl Pca {
+    const ITERATIONS: usize = 100;
+    const TOLERANCE: f32 = 1e-6;
+
+    pub fn new() -> Self {
+        Pca::default()
+    }
+
+    /// The unit-length components of the last projection, largest variance first.
+    pub fn components(&self) -> &[Vec<f32>] {
+        &self.components
+    }
+
+    /// The variance of the points along each component of the last projection.
+    pub fn explained_variance(&self) -> &[f32] {
+        &self.explained_variance
+    }
+
+    /// Find the `dimensions` principal components of the points.
+    pub fn fit(&mut self, points: &[Vec<f32>], dimensions: usize) {
+        let width = points.iter().map(|point| point.len()).max().unwrap_or(0);
+        let mean = mean_of(points, width);
+        let centered = points
+            .iter()
+            .map(|point| (0..width).map(|i| value_at(point, i) - mean[i]).collect())
+            .collect::<Vec<Vec<f32>>>();
+
+        let previous = std::mem::take(&mut self.components);
+        self.explained_variance.clear();
+
+        for component in 0..dimensions.min(width) {
+            let mut vector = match previous.get(component) {
+                Some(previous) if previous.len() == width => previous.clone(),
+                _ => (0..width)
+                    .map(|i| 1.0 + ((i * 7919 + component * 104729) % 997) as f32 / 997.0)
+                    .collect(),
+            };
+
+            let mut variance = 0.0;
+            for _ in 0..Pca::ITERATIONS {
+                let mut next = covariance_times(&centered, &vector);
+                for found in self.components.iter() {
+                    let overlap = dot(&next, found);
+                    next.iter_mut()
+                        .zip(found.iter())
+                        .for_each(|(value, found)| *value -= overlap * found);
+                }
+
+                let norm = dot(&next, &next).sqrt();
+                if norm <= f32::EPSILON {
+                    variance = 0.0;
+                    break;
+                }
+
+                next.iter_mut().for_each(|value| *value /= norm);
+                let change = 1.0 - dot(&next, &vector).abs();
+                vector = next;
+                variance = norm;
+
+                if change < Pca::TOLERANCE {
+                    break;
+                }
+            }
+
+            if let Some(previous) = previous.get(component) {
+                if previous.len() == width && dot(previous, &vector) < 0.0 {
+                    vector.iter_mut().for_each(|value| *value = -*value);
+                }
+            }
+
+            self.components.push(vector);
+            self.explained_variance.push(variance);
+        }
+
+        self.mean = mean;
+    }
+}
+
+impl Projection for Pca {
+    fn project(&mut self, points: &[Vec<f32>], dimensions: usize) -> Vec<Vec<f32>> {
+        self.fit(points, dimensions);
+
+        points
+            .iter()
+            .map(|point| {
+                (0..dimensions)
+                    .map(|component| match self.components.get(component) {
+                        Some(axis) => axis
+                            .iter()
+                            .enumerate()
+                            .map(|(i, weight)| (value_at(point, i) - self.mean[i]) * weight)
+                            .sum(),
+                        None => 0.0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The embedding of one generation's population - a point per individual, in population order
+/// (best first), along with the individual's score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmbeddingFrame {
+    pub generation: i32,
+    pub points: Vec<Vec<f32>>,
+    pub scores: Vec<f32>,
+}
+
+struct TraceState {
+    dimensions: usize,
+    every: usize,
+    projection: Box<dyn Projection>,
+    frames: Vec<EmbeddingFrame>,
+}
+
+/// The trajectory of the population through the search space, projected to a few dimensions every
+/// generation - the frames of an animation of how the population moves and converges. Like the
+/// `HallOfFame`, an `EmbeddingTrace` is cheap to clone and all clones share the same frames, so one
+/// clone is given to the engine with `GeneticEngineParams::population_embedding` and another is kept to
+/// export the frames, e.g. with `write_csv`.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let trace = EmbeddingTrace::new(2).every(5);
+///
+/// let engine = GeneticEngine::from_codex(FloatCodex::new(1, 10, -1.0, 1.0))
+///     .minimizing()
+///     .population_embedding(trace.clone(), |gene: &FloatGene| *gene.allele())
+///     .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+///     .build();
+///
+/// engine.run(|ctx| ctx.index >= 20);
+///
+/// let frames = trace.frames();
+/// assert_eq!(frames.len(), 4);
+/// assert_eq!(frames[0].points[0].len(), 2);
+/// assert!(trace.to_csv().starts_with("generation,individual,score,x0,x1"));
+/// ```
+#[derive(Clone)]
+pub struct EmbeddingTrace {
+    state: Arc<Mutex<TraceState>>,
+}
+
+impl EmbeddingTrace {
+    /// Create a trace projecting to `dimensions` dimensions with `Pca`, every generation. Panics if
+    /// `dimensions` is 0.
+    pub fn new(dimensions: usize) -> Self {
+        if dimensions < 1 {
+            panic!("dimensions must be greater than 0");
+        }
+
+        EmbeddingTrace {
+            state: Arc::new(Mutex::new(TraceState {
+                dimensions,
+                every: 1,
+                projection: Box::new(Pca::new()),
+                frames: Vec::new(),
+            })),
+        }
+    }
+
+    /// Only record every `generations`-th generation. Panics if `generations` is 0.
+    pub fn every(self, generations: usize) -> Self {
+        if generations < 1 {
+            panic!("generations must be greater than 0");
+        }
+
+        self.state.lock().unwrap().every = generations;
+        self
+    }
+
+    /// Project with another method than `Pca`.
+    pub fn with_projection(self, projection: impl Projection + 'static) -> Self {
+        self.state.lock().unwrap().projection = Box::new(projection);
+        self
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.state.lock().unwrap().dimensions
+    }
+
+    /// Whether the given generation is recorded.
+    pub fn is_due(&self, generation: i32) -> bool {
+        (generation.max(0) as usize).is_multiple_of(self.state.lock().unwrap().every)
+    }
+
+    /// Project the points of a generation and add the frame. Points shorter than the longest one are
+    /// padded with zeros.
+    pub fn record(&self, generation: i32, points: &[Vec<f32>], scores: Vec<f32>) {
+        let mut state = self.state.lock().unwrap();
+        let dimensions = state.dimensions;
+        let points = state.projection.project(points, dimensions);
+
+        state.frames.push(EmbeddingFrame {
+            generation,
+            points,
+            scores,
+        });
+    }
+
+    pub fn frames(&self) -> Vec<EmbeddingFrame> {
+        self.state.lock().unwrap().frames.clone()
+    }
+
+    pub fn clear(&self) {
+        self.state.lock().unwrap().frames.clear();
+    }
+
+    /// The frames as CSV - a header and a row per individual per frame:
+    /// `generation,individual,score,x0,x1,...`.
+    pub fn to_csv(&self) -> String {
+        let state = self.state.lock().unwrap();
+        let mut csv = String::from("generation,individual,score");
+        (0..state.dimensions).for_each(|i| csv.push_str(&format!(",x{}", i)));
+        csv.push('\n');
+
+        for frame in state.frames.iter() {
+            for (individual, (point, score)) in
+                frame.points.iter().zip(frame.scores.iter()).enumerate()
+            {
+                csv.push_str(&format!("{},{},{}", frame.generation, individual, score));
+                point.iter().for_each(|x| csv.push_str(&format!(",{}", x)));
+                csv.push('\n');
+            }
+        }
+
+        csv
+    }
+
+    /// Write the frames as CSV (see `to_csv`) to a file.
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::File::create(path)?.write_all(self.to_csv().as_bytes())
+    }
+}
+
+fn value_at(point: &[f32], index: usize) -> f32 {
+    point.get(index).copied().unwrap_or(0.0)
+}
+
+fn dot(one: &[f32], two: &[f32]) -> f32 {
+    one.iter().zip(two.iter()).map(|(a, b)| a * b).sum()
+}
+
+fn mean_of(points: &[Vec<f32>], width: usize) -> Vec<f32> {
+    let mut mean = vec![0.0; width];
+    for point in points.iter() {
+        for (i, value) in point.iter().enumerate() {
+            mean[i] += value;
+        }
+    }
+
+    let count = points.len().max(1) as f32;
+    mean.iter_mut().for_each(|value| *value /= count);
+    mean
+}
+
+/// The covariance matrix of the centered points times `vector`, as `Xᵀ(Xv) / n`.
+fn covariance_times(centered: &[Vec<f32>], vector: &[f32]) -> Vec<f32> {
+    let mut result = vec![0.0; vector.len()];
+    for point in centered.iter() {
+        let projection = dot(point, vector);
+        result
+            .iter_mut()
+            .zip(point.iter())
+            .for_each(|(value, x)| *value += projection * x);
+    }
+
+    let count = centered.len().max(1) as f32;
+    result.iter_mut().for_each(|value| *value /= count);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pca_finds_the_direction_of_largest_variance() {
+        // Points spread along (1, 1, 0) with a little noise along (1, -1, 0).
+        let points = (0..50)
+            .map(|i| {
+                let along = i as f32 - 25.0;
+                let across = if i % 2 == 0 { 0.5 } else { -0.5 };
+                vec![along + across, along - across, 3.0]
+            })
+            .collect::<Vec<Vec<f32>>>();
+
+        let mut pca = Pca::new();
+        let projected = pca.project(&points, 2);
+
+        let first = &pca.components()[0];
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((first[0].abs() - diagonal).abs() < 1e-2);
+        assert!((first[1].abs() - diagonal).abs() < 1e-2);
+        assert!(first[2].abs() < 1e-3);
+        assert!(pca.explained_variance()[0] > 100.0 * pca.explained_variance()[1]);
+        assert_eq!(projected.len(), 50);
+        assert_eq!(projected[0].len(), 2);
+
+        // Fitting the same points again keeps the axes' orientation.
+        let again = pca.project(&points, 2);
+        assert!(again
+            .iter()
+            .zip(projected.iter())
+            .all(|(one, two)| (one[0] - two[0]).abs() < 1e-3));
+    }
+}
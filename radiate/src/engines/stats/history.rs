@@ -0,0 +1,303 @@
+use super::{Metric, MetricSet};
+use crate::{EngineEvent, Subscriber};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+struct HistoryState {
+    capacity: usize,
+    tracked: Option<BTreeSet<&'static str>>,
+    series: BTreeMap<&'static str, VecDeque<(i32, f32)>>,
+    paused: bool,
+    zoom: Option<usize>,
+}
+
+/// The per-generation history of the engine's metrics, for plotting any of them - built-in or
+/// user-defined (e.g. a `genotype_metric` or a value recorded under a `MetricKey`) - while the engine
+/// runs. Like the `HallOfFame`, a `MetricHistory` is cheap to clone and all clones share the same
+/// history, so one clone is given to the engine with `subscribe` and others to the code drawing it.
+///
+/// Every generation the history records one reading per tracked metric: the last value of value and
+/// operation metrics, the last time (in seconds) of time metrics and the mean of the last sequence of
+/// distribution metrics. All metrics are tracked until `track` picks some. The metrics can be switched
+/// at any time, as can pausing the recording and zooming in on the most recent generations.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let history = MetricHistory::new(100);
+/// history.track(metric_names::SCORE);
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .subscribe(history.clone())
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// engine.run(|ctx| ctx.index >= 20);
+///
+/// assert_eq!(history.series(metric_names::SCORE).len(), 20);
+/// let sparkline = history.sparkline(metric_names::SCORE, 10).unwrap();
+/// assert_eq!(sparkline.line.chars().count(), 10);
+/// println!("{}", sparkline);
+/// ```
+#[derive(Clone)]
+pub struct MetricHistory {
+    state: Arc<Mutex<HistoryState>>,
+}
+
+impl MetricHistory {
+    /// Create a history keeping the last `capacity` generations of every metric. Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        if capacity < 1 {
+            panic!("capacity must be greater than 0");
+        }
+
+        MetricHistory {
+            state: Arc::new(Mutex::new(HistoryState {
+                capacity,
+                tracked: None,
+                series: BTreeMap::new(),
+                paused: false,
+                zoom: None,
+            })),
+        }
+    }
+
+    /// Start recording the metric with the given name, and stop recording the metrics that weren't
+    /// picked (unless every metric is tracked again with `track_all`).
+    pub fn track(&self, name: &'static str) {
+        let mut state = self.state.lock().unwrap();
+        state.tracked.get_or_insert_with(BTreeSet::new).insert(name);
+        if let Some(tracked) = state.tracked.clone() {
+            state.series.retain(|name, _| tracked.contains(name));
+        }
+    }
+
+    /// Stop recording the metric with the given name and drop its history.
+    pub fn untrack(&self, name: &'static str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(tracked) = state.tracked.as_mut() {
+            tracked.remove(name);
+        }
+
+        state.series.remove(name);
+    }
+
+    /// Record every metric again.
+    pub fn track_all(&self) {
+        self.state.lock().unwrap().tracked = None;
+    }
+
+    /// The names of the metrics that have a history, in alphabetical order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.state.lock().unwrap().series.keys().copied().collect()
+    }
+
+    /// Stop recording new generations, so the history can be inspected while the engine keeps running.
+    pub fn pause(&self) {
+        self.state.lock().unwrap().paused = true;
+    }
+
+    pub fn resume(&self) {
+        self.state.lock().unwrap().paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().paused
+    }
+
+    /// Only show the last `generations` generations in `series` and `sparkline`. Panics if `generations` is 0.
+    pub fn zoom(&self, generations: usize) {
+        if generations < 1 {
+            panic!("generations must be greater than 0");
+        }
+
+        self.state.lock().unwrap().zoom = Some(generations);
+    }
+
+    /// Show every generation the history holds again.
+    pub fn reset_zoom(&self) {
+        self.state.lock().unwrap().zoom = None;
+    }
+
+    /// The recorded `(generation, reading)` pairs of the metric within the zoom, oldest first.
+    pub fn series(&self, name: &str) -> Vec<(i32, f32)> {
+        let state = self.state.lock().unwrap();
+        let Some(series) = state.series.get(name) else {
+            return Vec::new();
+        };
+
+        let skip = state
+            .zoom
+            .map_or(0, |zoom| series.len().saturating_sub(zoom));
+        series.iter().skip(skip).copied().collect()
+    }
+
+    /// A sparkline of the metric within the zoom, `width` characters wide - or fewer when there are fewer
+    /// generations. When there are more, neighbouring generations are averaged. The bars are scaled to the
+    /// smallest and largest value shown. `None` if the metric has no history. Panics if `width` is 0.
+    pub fn sparkline(&self, name: &str, width: usize) -> Option<Sparkline> {
+        if width < 1 {
+            panic!("width must be greater than 0");
+        }
+
+        let series = self.series(name);
+        if series.is_empty() {
+            return None;
+        }
+
+        let width = width.min(series.len());
+        let values = (0..width)
+            .map(|bucket| {
+                let start = bucket * series.len() / width;
+                let end = ((bucket + 1) * series.len() / width).max(start + 1);
+                let bucket = &series[start..end];
+                bucket.iter().map(|(_, value)| *value).sum::<f32>() / bucket.len() as f32
+            })
+            .collect::<Vec<f32>>();
+
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let line = values
+            .iter()
+            .map(|value| match max - min {
+                range if range > 0.0 && range.is_finite() => {
+                    let level = ((value - min) / range * (BARS.len() - 1) as f32).round();
+                    BARS[(level as usize).min(BARS.len() - 1)]
+                }
+                _ => BARS[BARS.len() / 2],
+            })
+            .collect();
+
+        Some(Sparkline {
+            name: name.to_string(),
+            line,
+            min,
+            max,
+            first: series[0].0,
+            last: series[series.len() - 1],
+        })
+    }
+
+    /// Record a reading of every tracked metric for the given generation.
+    pub fn record(&self, generation: i32, metrics: &MetricSet) {
+        let mut state = self.state.lock().unwrap();
+        if state.paused {
+            return;
+        }
+
+        let capacity = state.capacity;
+        for name in metrics.names() {
+            if let Some(tracked) = &state.tracked {
+                if !tracked.contains(name) {
+                    continue;
+                }
+            }
+
+            let Some(value) = metrics.get(name).and_then(reading) else {
+                continue;
+            };
+
+            let series = state.series.entry(name).or_default();
+            series.push_back((generation, value));
+            while series.len() > capacity {
+                series.pop_front();
+            }
+        }
+    }
+}
+
+impl<T> Subscriber<T> for MetricHistory {
+    fn on_event(&self, event: &EngineEvent<T>) {
+        if let EngineEvent::EpochComplete { index, metrics, .. } = event {
+            self.record(*index, metrics);
+        }
+    }
+}
+
+/// The reading of a metric a `MetricHistory` records every generation.
+pub(crate) fn reading(metric: &Metric) -> Option<f32> {
+    let value = match metric {
+        Metric::Value(_, _) | Metric::Operations(_, _, _) => metric.last_value(),
+        Metric::Time(_, _) => metric.last_time().as_secs_f32(),
+        Metric::Distribution(_, _) => metric.sequence_mean()?,
+    };
+
+    value.is_finite().then_some(value)
+}
+
+/// A sparkline of a metric's history (see `MetricHistory::sparkline`), scaled to `min` and `max`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sparkline {
+    pub name: String,
+    pub line: String,
+    pub min: f32,
+    pub max: f32,
+    /// The first generation shown.
+    pub first: i32,
+    /// The last generation shown, and its reading.
+    pub last: (i32, f32),
+}
+
+impl Display for Sparkline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<24} {} [{:.3} .. {:.3}] gen {}..{} last {:.3}",
+            self.name, self.line, self.min, self.max, self.first, self.last.0, self.last.1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_history_tracks_pauses_and_zooms() {
+        let history = MetricHistory::new(50);
+
+        for generation in 0..100 {
+            let mut metrics = MetricSet::new();
+            metrics.upsert_value("rising", generation as f32);
+            metrics.upsert_value("flat", 1.0);
+            history.record(generation, &metrics);
+        }
+
+        assert_eq!(history.names(), vec!["flat", "rising"]);
+        assert_eq!(history.series("rising").len(), 50);
+        assert_eq!(history.series("rising")[0], (50, 50.0));
+
+        let rising = history.sparkline("rising", 8).unwrap();
+        assert_eq!(rising.line, "▁▂▃▄▅▆▇█");
+        assert_eq!((rising.first, rising.last.0), (50, 99));
+
+        let flat = history.sparkline("flat", 100).unwrap();
+        assert_eq!(flat.line.chars().count(), 50);
+        assert!(flat
+            .line
+            .chars()
+            .all(|bar| bar == flat.line.chars().next().unwrap()));
+
+        history.zoom(4);
+        assert_eq!(history.sparkline("rising", 8).unwrap().line, "▁▃▆█");
+        history.reset_zoom();
+
+        history.track("rising");
+        history.pause();
+        let mut metrics = MetricSet::new();
+        metrics.upsert_value("rising", 100.0);
+        history.record(100, &metrics);
+        assert_eq!(history.names(), vec!["rising"]);
+        assert_eq!(history.series("rising").last(), Some(&(99, 99.0)));
+
+        history.resume();
+        history.record(100, &metrics);
+        assert_eq!(history.series("rising").last(), Some(&(100, 100.0)));
+        assert!(history.sparkline("flat", 8).is_none());
+    }
+}
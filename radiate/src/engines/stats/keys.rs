@@ -0,0 +1,280 @@
+use super::{metric_names, Metric, MetricSet};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// The summary of a value metric (or of the values of a sequence metric).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueStats {
+    pub count: i32,
+    pub last: f32,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// The summary of the durations of a time metric, or of an operation metric such as an engine `Step`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeStats {
+    pub count: i32,
+    pub last: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub total: Duration,
+}
+
+/// The operations the engine times every generation, each recorded under its `metric_name`. The stages
+/// of the generation's pipeline - `Selection`, `Alteration`, `Replacement` (merging the survivors and
+/// offspring, repairing and filtering them), `Audit` and the whole `Generation` - are time metrics. The
+/// other steps are operation metrics, which also hold the number of individuals the step processed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Step {
+    Selection,
+    Alteration,
+    Replacement,
+    Audit,
+    Generation,
+    Evaluate,
+    DeltaEvaluate,
+    FitnessShaping,
+    Filter,
+    Front,
+    Recording,
+}
+
+impl Step {
+    pub fn metric_name(&self) -> &'static str {
+        match self {
+            Step::Selection => metric_names::SELECTION_TIME,
+            Step::Alteration => metric_names::ALTERATION_TIME,
+            Step::Replacement => metric_names::REPLACEMENT_TIME,
+            Step::Audit => metric_names::AUDIT_TIME,
+            Step::Generation => metric_names::GENERATION_TIME,
+            Step::Evaluate => metric_names::EVALUATION,
+            Step::DeltaEvaluate => metric_names::DELTA_EVALUATIONS,
+            Step::FitnessShaping => metric_names::FITNESS_SHAPING,
+            Step::Filter => metric_names::AGE_FILTER,
+            Step::Front => metric_names::FRONT,
+            Step::Recording => metric_names::RECORDING,
+        }
+    }
+}
+
+/// The kind of a `MetricKey` - what is recorded under the key and the statistics read back from it.
+pub trait MetricKind {
+    type Input: ?Sized;
+    type Stats;
+
+    fn record(metrics: &mut MetricSet, name: &'static str, input: &Self::Input);
+    fn stats(metric: &Metric) -> Option<Self::Stats>;
+}
+
+/// A `MetricKey` for a metric recording one value at a time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueMetric {}
+
+/// A `MetricKey` for a metric recording durations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeMetric {}
+
+/// A `MetricKey` for a metric recording a sequence of values at a time, e.g. one per individual.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SequenceMetric {}
+
+impl MetricKind for ValueMetric {
+    type Input = f32;
+    type Stats = ValueStats;
+
+    fn record(metrics: &mut MetricSet, name: &'static str, input: &f32) {
+        metrics.upsert_value(name, *input);
+    }
+
+    fn stats(metric: &Metric) -> Option<ValueStats> {
+        metric.value_stats()
+    }
+}
+
+impl MetricKind for TimeMetric {
+    type Input = Duration;
+    type Stats = TimeStats;
+
+    fn record(metrics: &mut MetricSet, name: &'static str, input: &Duration) {
+        metrics.upsert_time(name, *input);
+    }
+
+    fn stats(metric: &Metric) -> Option<TimeStats> {
+        metric.time_stats()
+    }
+}
+
+impl MetricKind for SequenceMetric {
+    type Input = [f32];
+    type Stats = ValueStats;
+
+    fn record(metrics: &mut MetricSet, name: &'static str, input: &[f32]) {
+        metrics.upsert_sequence(name, input);
+    }
+
+    fn stats(metric: &Metric) -> Option<ValueStats> {
+        metric.sequence_stats()
+    }
+}
+
+/// A typed name for a user-defined metric. The key fixes the kind of the metric, so a value can't be
+/// recorded under a time metric by mistake and reading it back returns the statistics of its kind.
+/// Keys are usually declared as constants next to the code recording them.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// const RESTARTS: MetricKey<ValueMetric> = MetricKey::value("Restarts");
+/// const SIMULATION: MetricKey<TimeMetric> = MetricKey::time("Simulation");
+///
+/// let mut metrics = MetricSet::new();
+/// metrics.record(&RESTARTS, &1.0);
+/// metrics.record(&RESTARTS, &3.0);
+/// metrics.record(&SIMULATION, &std::time::Duration::from_millis(5));
+///
+/// assert_eq!(metrics.stats(&RESTARTS).unwrap().mean, 2.0);
+/// assert_eq!(metrics.stats(&SIMULATION).unwrap().count, 1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetricKey<K: MetricKind> {
+    name: &'static str,
+    kind: PhantomData<K>,
+}
+
+impl MetricKey<ValueMetric> {
+    pub const fn value(name: &'static str) -> Self {
+        MetricKey {
+            name,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl MetricKey<TimeMetric> {
+    pub const fn time(name: &'static str) -> Self {
+        MetricKey {
+            name,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl MetricKey<SequenceMetric> {
+    pub const fn sequence(name: &'static str) -> Self {
+        MetricKey {
+            name,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K: MetricKind> MetricKey<K> {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl MetricSet {
+    /// Record a value under a typed key.
+    pub fn record<K: MetricKind>(&mut self, key: &MetricKey<K>, input: &K::Input) {
+        K::record(self, key.name, input);
+    }
+
+    /// The statistics of the metric under a typed key, if anything was recorded under it (with its kind).
+    pub fn stats<K: MetricKind>(&self, key: &MetricKey<K>) -> Option<K::Stats> {
+        self.get(key.name).and_then(K::stats)
+    }
+
+    /// The statistics of the scores the engine records every generation (`metric_names::SCORE`).
+    pub fn score_stats(&self) -> Option<ValueStats> {
+        self.get(metric_names::SCORE)?.value_stats()
+    }
+
+    /// The statistics of the ages the engine records every generation (`metric_names::AGE`).
+    pub fn age_stats(&self) -> Option<ValueStats> {
+        self.get(metric_names::AGE)?.value_stats()
+    }
+
+    /// The number of genes of the individuals of the last generation.
+    pub fn genome_size_stats(&self) -> Option<ValueStats> {
+        self.get(metric_names::GENOME_SIZE)?.sequence_stats()
+    }
+
+    /// The number of unique scores in the last generation.
+    pub fn unique_scores(&self) -> Option<usize> {
+        self.get(metric_names::UNIQUE)
+            .map(|metric| metric.last_value() as usize)
+    }
+
+    /// The time the step took in the last generation it ran in.
+    pub fn duration(&self, step: Step) -> Option<Duration> {
+        self.step_times(step).map(|times| times.last)
+    }
+
+    /// The times the step took over the generations.
+    pub fn step_times(&self, step: Step) -> Option<TimeStats> {
+        self.get(step.metric_name())?.time_stats()
+    }
+
+    /// The number of individuals the step processed over the generations.
+    pub fn step_counts(&self, step: Step) -> Option<ValueStats> {
+        self.get(step.metric_name())?.value_stats()
+    }
+}
+
+impl Metric {
+    /// The summary of the values of a value or operation metric.
+    pub fn value_stats(&self) -> Option<ValueStats> {
+        let stat = match self {
+            Metric::Value(_, stat) | Metric::Operations(_, stat, _) => stat,
+            _ => return None,
+        };
+
+        Some(ValueStats {
+            count: stat.count(),
+            last: stat.last_value(),
+            mean: stat.mean(),
+            std_dev: stat.std_dev(),
+            min: stat.min(),
+            max: stat.max(),
+        })
+    }
+
+    /// The summary of the durations of a time or operation metric.
+    pub fn time_stats(&self) -> Option<TimeStats> {
+        let stat = match self {
+            Metric::Time(_, stat) | Metric::Operations(_, _, stat) => stat,
+            _ => return None,
+        };
+
+        Some(TimeStats {
+            count: stat.count(),
+            last: stat.last_time(),
+            mean: stat.mean(),
+            min: stat.min(),
+            max: stat.max(),
+            total: stat.sum(),
+        })
+    }
+
+    /// The summary of the values of the last sequence of a distribution metric.
+    pub fn sequence_stats(&self) -> Option<ValueStats> {
+        let Metric::Distribution(_, dist) = self else {
+            return None;
+        };
+
+        Some(ValueStats {
+            count: dist.count(),
+            last: dist.last_sequence().last().copied().unwrap_or_default(),
+            mean: dist.mean(),
+            std_dev: dist.standard_deviation(),
+            min: dist.min(),
+            max: dist.max(),
+        })
+    }
+}
@@ -96,13 +96,64 @@ impl MetricSet {
     pub fn names(&self) -> Vec<&'static str> {
         self.metrics.keys().copied().collect()
     }
+
+    /// A column-oriented summary of the metrics with one row per metric, ready to be turned into a
+    /// dataframe. A column is `None` for the metrics it doesn't apply to (e.g. the time columns of a
+    /// value metric). Times are in seconds.
+    pub fn to_columns(&self) -> MetricColumns {
+        let metrics = self.metrics.values().collect::<Vec<&Metric>>();
+        let column = |name: &'static str, value: &dyn Fn(&Metric) -> Option<f32>| {
+            (name, metrics.iter().map(|metric| value(metric)).collect())
+        };
+        let value_last = |metric: &Metric| match metric {
+            Metric::Value(_, _) | Metric::Operations(_, _, _) => Some(metric.last_value()),
+            _ => None,
+        };
+
+        MetricColumns {
+            names: self.names(),
+            columns: vec![
+                column("value_last", &value_last),
+                column("value_mean", &|metric| metric.value_mean()),
+                column("value_std_dev", &|metric| metric.value_std_dev()),
+                column("value_min", &|metric| metric.value_min()),
+                column("value_max", &|metric| metric.value_max()),
+                column("time_mean", &|metric| {
+                    metric.time_mean().map(|time| time.as_secs_f32())
+                }),
+                column("time_sum", &|metric| {
+                    metric.time_sum().map(|time| time.as_secs_f32())
+                }),
+                column("sequence_mean", &|metric| metric.sequence_mean()),
+                column("sequence_std_dev", &|metric| metric.sequence_std_dev()),
+            ],
+        }
+    }
+}
+
+/// Column-oriented summary of a `MetricSet` (see `MetricSet::to_columns`) - `names` holds the metric of
+/// every row and `columns` the named summary columns, each with one value per row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricColumns {
+    pub names: Vec<&'static str>,
+    pub columns: Vec<(&'static str, Vec<Option<f32>>)>,
+}
+
+impl MetricColumns {
+    /// The column with the given name, if there is one.
+    pub fn column(&self, name: &str) -> Option<&Vec<Option<f32>>> {
+        self.columns
+            .iter()
+            .find(|(column, _)| *column == name)
+            .map(|(_, values)| values)
+    }
 }
 
 impl std::fmt::Debug for MetricSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "MetricSet {{\n")?;
+        writeln!(f, "MetricSet {{")?;
         for name in self.names() {
-            write!(f, "  \t{:?},\n", self.get(name).unwrap())?;
+            writeln!(f, "  \t{:?},", self.get(name).unwrap())?;
         }
         write!(f, "}}")
     }
@@ -412,4 +463,22 @@ mod tests {
         assert_eq!(metric.value_max().unwrap(), 5.0);
         assert_eq!(metric.name(), "test");
     }
+
+    #[test]
+    fn test_metric_set_to_columns() {
+        let mut metrics = MetricSet::new();
+        metrics.upsert_value("value", 1.0);
+        metrics.upsert_value("value", 3.0);
+        metrics.upsert_time("time", Duration::from_secs(2));
+
+        let columns = metrics.to_columns();
+
+        assert_eq!(columns.names, vec!["time", "value"]);
+        assert_eq!(
+            columns.column("value_mean").unwrap(),
+            &vec![None, Some(2.0)]
+        );
+        assert_eq!(columns.column("time_sum").unwrap(), &vec![Some(2.0), None]);
+        assert!(columns.column("unknown").is_none());
+    }
 }
@@ -1,21 +1,75 @@
+pub mod comparison;
 pub mod distribution;
+pub mod embedding;
+pub mod history;
+pub mod keys;
 pub mod metrics;
+pub mod movement;
+pub mod recording;
 pub mod statistics;
 pub mod time_statistic;
+pub mod timing;
 
+pub use comparison::*;
 pub use distribution::*;
+pub use embedding::*;
+pub use history::*;
+pub use keys::*;
 pub use metric_names::*;
 pub use metrics::*;
+pub use movement::*;
+pub use recording::*;
 pub use statistics::*;
 pub use time_statistic::*;
+pub use timing::*;
 
 pub mod metric_names {
     pub const SCORE: &str = "Score";
     pub const AGE: &str = "Age";
     pub const EVALUATION: &str = "Evaluation";
+    pub const EVALUATION_ERRORS: &str = "Evaluation Errors";
+    pub const SKIPPED_EVALUATIONS: &str = "Skipped Evaluations";
+    pub const CACHE_HITS: &str = "Cache Hits";
+    pub const CACHE_MISSES: &str = "Cache Misses";
+    pub const DELTA_EVALUATIONS: &str = "Delta Evaluations";
     pub const AGE_FILTER: &str = "Age Filter";
     pub const INVALID_FILTER: &str = "Invalid Filter";
+    pub const INFEASIBLE: &str = "Infeasible";
     pub const UNIQUE: &str = "Unique";
     pub const GENOME_SIZE: &str = "Genome Size";
     pub const FRONT: &str = "Front";
+    pub const FITNESS_SHAPING: &str = "Fitness Shaping";
+    pub const SPECIATION: &str = "Speciation";
+    pub const LOCAL_SEARCH: &str = "Local Search";
+    pub const HALL_OF_FAME_WIN_RATE: &str = "Hall of Fame Win Rate";
+    pub const ELITE_ARCHIVE: &str = "Elite Archive";
+    pub const RECORDING: &str = "Recording";
+    pub const MEAN_ALLELE_CHANGE: &str = "Mean Allele Change";
+    pub const CENTROID_DRIFT: &str = "Centroid Drift";
+    pub const VARIANCE_SHRINKAGE: &str = "Variance Shrinkage";
+    pub const QUEUE_DEPTH: &str = "Queue Depth";
+    pub const POPULATION_MEMORY: &str = "Population Memory";
+    pub const POPULATION_SIZE: &str = "Population Size";
+    pub const OPERATOR_SELECTION: &str = "Operator Selection";
+    pub const OPERATOR_CREDIT: &str = "Operator Credit";
+    pub const RACE_ELIMINATIONS: &str = "Race Eliminations";
+    pub const STEADY_STATE_REPLACEMENTS: &str = "Steady State Replacements";
+    pub const RESTART: &str = "Restart";
+    pub const WRITE_ERRORS: &str = "Write Errors";
+    pub const CALIBRATED_THREADS: &str = "Calibrated Threads";
+    pub const CALIBRATED_BATCH_SIZE: &str = "Calibrated Batch Size";
+    pub const SURVIVOR_SELECTION_INTENSITY: &str = "Survivor Selection Intensity";
+    pub const SURVIVOR_DIVERSITY_LOSS: &str = "Survivor Diversity Loss";
+    pub const OFFSPRING_SELECTION_INTENSITY: &str = "Offspring Selection Intensity";
+    pub const OFFSPRING_DIVERSITY_LOSS: &str = "Offspring Diversity Loss";
+    pub const CONTROL_MUTATION_RATE: &str = "Control Mutation Rate";
+    pub const CONTROL_TEMPERATURE: &str = "Control Temperature";
+    pub const CONTROL_POPULATION_SIZE: &str = "Control Population Size";
+    pub const SELECTION_TIME: &str = "Selection Time";
+    pub const ALTERATION_TIME: &str = "Alteration Time";
+    pub const EVALUATION_TIME: &str = "Evaluation Time";
+    pub const REPLACEMENT_TIME: &str = "Replacement Time";
+    pub const AUDIT_TIME: &str = "Audit Time";
+    pub const GENERATION_TIME: &str = "Generation Time";
+    pub const PARALLEL_EFFICIENCY: &str = "Parallel Efficiency";
 }
@@ -0,0 +1,155 @@
+use crate::{Chromosome, Population};
+
+/// A compact summary of where a population is in gene space - the per gene mean (centroid) and
+/// variance of the population. Comparing the snapshots of consecutive generations shows how the
+/// population moves and converges without having to keep whole populations around.
+///
+/// Genes are indexed by their position in the flattened genotype (all chromosomes back to back). For
+/// variable-length genotypes each position only includes the individuals that have a gene there.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PopulationSnapshot {
+    pub centroid: Vec<f32>,
+    pub variance: Vec<f32>,
+}
+
+impl PopulationSnapshot {
+    /// Take a snapshot of the population, using `value` to map each gene to a number.
+    pub fn new<C, F>(population: &Population<C>, value: F) -> Self
+    where
+        C: Chromosome,
+        F: Fn(&C::Gene) -> f32,
+    {
+        let mut sums = Vec::new();
+        let mut squares = Vec::new();
+        let mut counts = Vec::new();
+
+        for phenotype in population.iter() {
+            let genes = phenotype
+                .genotype()
+                .iter()
+                .flat_map(|chromosome| chromosome.iter());
+
+            for (index, gene) in genes.enumerate() {
+                if index == sums.len() {
+                    sums.push(0.0);
+                    squares.push(0.0);
+                    counts.push(0.0);
+                }
+
+                let value = value(gene);
+                sums[index] += value;
+                squares[index] += value * value;
+                counts[index] += 1.0;
+            }
+        }
+
+        let centroid = sums
+            .iter()
+            .zip(counts.iter())
+            .map(|(sum, count)| sum / count)
+            .collect::<Vec<f32>>();
+
+        let variance = squares
+            .iter()
+            .zip(counts.iter())
+            .zip(centroid.iter())
+            .map(|((square, count), mean)| (square / count - mean * mean).max(0.0))
+            .collect();
+
+        PopulationSnapshot { centroid, variance }
+    }
+
+    pub fn len(&self) -> usize {
+        self.centroid.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroid.is_empty()
+    }
+
+    /// How the population moved from the `previous` snapshot to this one. Only the gene positions
+    /// present in both snapshots are compared.
+    pub fn movement(&self, previous: &PopulationSnapshot) -> PopulationMovement {
+        let len = self.len().min(previous.len());
+        if len == 0 {
+            return PopulationMovement::default();
+        }
+
+        let changes = (0..len)
+            .map(|i| self.centroid[i] - previous.centroid[i])
+            .collect::<Vec<f32>>();
+
+        let variance_shrinkage = (0..len)
+            .map(|i| {
+                if previous.variance[i] == 0.0 {
+                    1.0
+                } else {
+                    self.variance[i] / previous.variance[i]
+                }
+            })
+            .collect();
+
+        PopulationMovement {
+            mean_allele_change: changes.iter().map(|change| change.abs()).sum::<f32>() / len as f32,
+            centroid_drift: changes
+                .iter()
+                .map(|change| change * change)
+                .sum::<f32>()
+                .sqrt(),
+            variance_shrinkage,
+        }
+    }
+}
+
+/// The generation-over-generation movement of a population:
+/// * `mean_allele_change` - the mean absolute change of the per gene mean
+/// * `centroid_drift` - the euclidean distance the centroid moved
+/// * `variance_shrinkage` - the ratio of each gene's variance to its variance in the previous
+///   generation. Values below 1.0 mean the population is converging on that gene.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PopulationMovement {
+    pub mean_allele_change: f32,
+    pub centroid_drift: f32,
+    pub variance_shrinkage: Vec<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatChromosome, Gene, Genotype, Phenotype};
+
+    fn population(values: &[[f32; 2]]) -> Population<FloatChromosome> {
+        values
+            .iter()
+            .map(|pair| {
+                let genotype = Genotype::new(vec![FloatChromosome::from(&pair[..])]);
+                Phenotype::from_genotype(genotype, 0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let snapshot = PopulationSnapshot::new(&population(&[[0.0, 1.0], [2.0, 1.0]]), |gene| {
+            *gene.allele()
+        });
+
+        assert_eq!(snapshot.centroid, vec![1.0, 1.0]);
+        assert_eq!(snapshot.variance, vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_movement() {
+        let before = PopulationSnapshot::new(&population(&[[0.0, 0.0], [4.0, 0.0]]), |gene| {
+            *gene.allele()
+        });
+        let after = PopulationSnapshot::new(&population(&[[4.0, 4.0], [6.0, 4.0]]), |gene| {
+            *gene.allele()
+        });
+
+        let movement = after.movement(&before);
+        assert_eq!(movement.mean_allele_change, 3.5);
+        assert_eq!(movement.centroid_drift, 5.0);
+        assert_eq!(movement.variance_shrinkage, vec![0.25, 1.0]);
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+/// A `Recording` is a trace emitted while re-evaluating the best individual of a generation,
+/// e.g. the frames of a game, the intermediate predictions of a model, or the state of a
+/// simulation at each step. It is only produced for the best individual so the cost of recording
+/// isn't paid for every evaluation.
+///
+/// A recording holds two kinds of data:
+/// * `frames` - an ordered list of free-form text entries (one per step, for example)
+/// * `series` - named sequences of numeric values
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Recording {
+    pub generation: i32,
+    pub frames: Vec<String>,
+    pub series: BTreeMap<&'static str, Vec<f32>>,
+}
+
+impl Recording {
+    pub fn new(generation: i32) -> Self {
+        Recording {
+            generation,
+            frames: Vec::new(),
+            series: BTreeMap::new(),
+        }
+    }
+
+    /// Append a frame to the recording.
+    pub fn frame(&mut self, frame: impl Into<String>) {
+        self.frames.push(frame.into());
+    }
+
+    /// Append a value to the series with the given name.
+    pub fn value(&mut self, name: &'static str, value: f32) {
+        self.series.entry(name).or_default().push(value);
+    }
+
+    pub fn get_series(&self, name: &'static str) -> Option<&Vec<f32>> {
+        self.series.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty() && self.series.is_empty()
+    }
+}
@@ -51,6 +51,46 @@ pub struct Statistic {
 }
 
 impl Statistic {
+    /// The count and every other field of the statistic, for storing it exactly - see `from_raw`.
+    #[cfg(feature = "wire")]
+    pub(crate) fn to_raw(&self) -> (i32, [f32; 18]) {
+        let mut values = [0.0; 18];
+        for (i, adder) in [&self.m1, &self.m2, &self.m3, &self.m4, &self.sum]
+            .iter()
+            .enumerate()
+        {
+            values[i * 3] = adder.compensation;
+            values[i * 3 + 1] = adder.simple_sum;
+            values[i * 3 + 2] = adder.sum;
+        }
+
+        values[15] = self.last_value;
+        values[16] = self.max;
+        values[17] = self.min;
+        (self.count, values)
+    }
+
+    #[cfg(feature = "wire")]
+    pub(crate) fn from_raw(count: i32, values: [f32; 18]) -> Self {
+        let adder = |i: usize| Adder {
+            compensation: values[i * 3],
+            simple_sum: values[i * 3 + 1],
+            sum: values[i * 3 + 2],
+        };
+
+        Statistic {
+            m1: adder(0),
+            m2: adder(1),
+            m3: adder(2),
+            m4: adder(3),
+            sum: adder(4),
+            count,
+            last_value: values[15],
+            max: values[16],
+            min: values[17],
+        }
+    }
+
     pub fn last_value(&self) -> f32 {
         self.last_value
     }
@@ -74,7 +114,7 @@ impl Statistic {
             self.m1.value()
         }
     }
-    
+
     pub fn sum(&self) -> f32 {
         self.sum.value()
     }
@@ -111,7 +151,7 @@ impl Statistic {
 
         value
     }
-    
+
     pub fn kurtosis(&self) -> f32 {
         let mut value = f32::NAN;
         if self.count >= 4 {
@@ -119,8 +159,7 @@ impl Statistic {
             if temp < 10e-10_f32 {
                 value = 0_f32;
             } else {
-                value = self.count as f32 * (self.count as f32 + 1_f32)
-                    * self.m4.value()
+                value = self.count as f32 * (self.count as f32 + 1_f32) * self.m4.value()
                     / ((self.count as f32 - 1_f32)
                         * (self.count as f32 - 2_f32)
                         * (self.count as f32 - 3_f32)
@@ -156,7 +195,7 @@ impl Statistic {
         self.min = if value < self.min { value } else { self.min };
         self.sum.add(value);
     }
-    
+
     pub fn clear(&mut self) {
         self.m1 = Adder::default();
         self.m2 = Adder::default();
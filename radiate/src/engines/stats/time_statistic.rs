@@ -36,15 +36,15 @@ impl TimeStatistic {
     pub fn min(&self) -> Duration {
         Duration::from_secs_f32(self.statistic.min())
     }
-    
+
     pub fn max(&self) -> Duration {
         Duration::from_secs_f32(self.statistic.max())
     }
-    
+
     pub fn sum(&self) -> Duration {
         Duration::from_secs_f32(self.statistic.sum())
     }
-    
+
     pub fn clear(&mut self) {
         self.statistic.clear();
     }
@@ -0,0 +1,120 @@
+use super::{metric_names, MetricSet, Step, TimeStats};
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Where the time of the generations went - the times of the stages of the engine's pipeline and their
+/// share of the generation time, along with how busy the evaluation threads were. Created with
+/// `MetricSet::timing_breakdown`, and printed as a table to spot bottlenecks without a profiler.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+///     .minimizing()
+///     .num_threads(2)
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index > 5);
+///
+/// let timing = result.metrics.timing_breakdown();
+/// assert!(timing.stage("Evaluation").is_some());
+/// println!("{}", timing);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingBreakdown {
+    /// The times of every stage that ran, in pipeline order.
+    pub stages: Vec<(&'static str, TimeStats)>,
+    pub generation: Option<TimeStats>,
+    /// The mean time the evaluation jobs took over the evaluation wall time times the number of threads.
+    pub parallel_efficiency: Option<f32>,
+}
+
+impl TimingBreakdown {
+    /// The stages of the pipeline, with the metrics holding their times.
+    pub const STAGES: [(&'static str, &'static str); 6] = [
+        ("Selection", metric_names::SELECTION_TIME),
+        ("Alteration", metric_names::ALTERATION_TIME),
+        ("Evaluation", metric_names::EVALUATION_TIME),
+        ("Replacement", metric_names::REPLACEMENT_TIME),
+        ("Front", metric_names::FRONT),
+        ("Audit", metric_names::AUDIT_TIME),
+    ];
+
+    pub fn stage(&self, name: &str) -> Option<&TimeStats> {
+        self.stages
+            .iter()
+            .find(|(stage, _)| *stage == name)
+            .map(|(_, times)| times)
+    }
+
+    /// The fraction of the total generation time a stage took.
+    pub fn share(&self, name: &str) -> Option<f32> {
+        let total = self.generation?.total;
+        let stage = self.stage(name)?.total;
+        match total.is_zero() {
+            true => None,
+            false => Some(stage.as_secs_f32() / total.as_secs_f32()),
+        }
+    }
+}
+
+impl MetricSet {
+    /// The per-stage timing of the generations recorded in this metric set.
+    pub fn timing_breakdown(&self) -> TimingBreakdown {
+        TimingBreakdown {
+            stages: TimingBreakdown::STAGES
+                .iter()
+                .filter_map(|(stage, name)| Some((*stage, self.get(name)?.time_stats()?)))
+                .collect(),
+            generation: self.step_times(Step::Generation),
+            parallel_efficiency: self
+                .get(metric_names::PARALLEL_EFFICIENCY)
+                .and_then(|metric| metric.value_mean()),
+        }
+    }
+}
+
+impl Display for TimingBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<12} {:>12} {:>12} {:>12} {:>7}",
+            "Stage", "Last", "Mean", "Total", "Share"
+        )?;
+
+        let row =
+            |f: &mut std::fmt::Formatter<'_>, name: &str, times: &TimeStats, share: Option<f32>| {
+                let share =
+                    share.map_or(String::from("-"), |share| format!("{:.1}%", share * 100.0));
+                writeln!(
+                    f,
+                    "{:<12} {:>12} {:>12} {:>12} {:>7}",
+                    name,
+                    format_duration(times.last),
+                    format_duration(times.mean),
+                    format_duration(times.total),
+                    share
+                )
+            };
+
+        for (name, times) in self.stages.iter() {
+            row(f, name, times, self.share(name))?;
+        }
+
+        if let Some(generation) = &self.generation {
+            row(f, "Generation", generation, Some(1.0))?;
+        }
+
+        if let Some(efficiency) = self.parallel_efficiency {
+            writeln!(f, "Parallel efficiency: {:.1}%", efficiency * 100.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3?}", duration)
+}
@@ -0,0 +1,256 @@
+use std::cmp::Ordering;
+
+use super::objectives::Objective;
+use super::{Chromosome, Diversity, Phenotype, Population};
+
+/// Which individuals the offspring of a steady-state step replace (see `SteadyState`).
+pub enum Replacement<C: Chromosome> {
+    /// The offspring join the population and the worst individuals are removed - the offspring
+    /// themselves included, so an offspring that is worse than everyone else doesn't survive. Offspring
+    /// identical to an individual already in the population (e.g. a parent the alterers left unchanged)
+    /// are discarded, so copies don't crowd out the rest of the population.
+    Worst,
+    /// Every offspring replaces one of the oldest individuals, regardless of their scores.
+    Oldest,
+    /// Deterministic crowding: every offspring competes with the individual most similar to it, and
+    /// replaces it if it is at least as good. Keeps niches populated where `Worst` would let the best
+    /// niche take over.
+    Crowding(Box<dyn Diversity<C>>),
+}
+
+impl<C: Chromosome> Replacement<C> {
+    pub fn crowding<D: Diversity<C> + 'static>(diversity: D) -> Self {
+        Replacement::Crowding(Box::new(diversity))
+    }
+}
+
+/// Steady-state evolution: instead of breeding a whole new generation, every step of the engine
+/// selects `replacements` parents with the offspring selector, alters them, evaluates the offspring
+/// and merges them into the population according to the `Replacement` policy. The rest of the
+/// population carries over untouched, so the population changes gradually and good individuals are
+/// available as parents as soon as they are found.
+///
+/// Set with `GeneticEngineParams::steady_state`. The survivor selector and the offspring fraction
+/// aren't used in steady-state mode. Every step is a generation as far as the rest of the engine is
+/// concerned - the index, ages, metrics and limits count steps - so a steady-state run typically takes
+/// many more of them than a generational one. Only the `max_age` is scaled, to the number of steps it
+/// takes to breed as many offspring as the population has individuals.
+///
+/// # Example
+/// ``` rust
+/// use radiate::*;
+///
+/// let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+///     .minimizing()
+///     .population_size(30)
+///     .steady_state(2, Replacement::Worst)
+///     .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+///     .build();
+///
+/// let result = engine.run(|ctx| ctx.index >= 300);
+///
+/// assert_eq!(result.population.len(), 30);
+/// assert!(result.score().as_i32() < 100);
+/// ```
+pub struct SteadyState<C: Chromosome> {
+    replacements: usize,
+    policy: Replacement<C>,
+}
+
+impl<C: Chromosome> SteadyState<C> {
+    pub fn new(replacements: usize, policy: Replacement<C>) -> Self {
+        if replacements < 1 {
+            panic!("replacements must be greater than 0");
+        }
+
+        SteadyState {
+            replacements,
+            policy,
+        }
+    }
+
+    /// The number of offspring bred every step.
+    pub fn replacements(&self) -> usize {
+        self.replacements
+    }
+
+    pub fn policy(&self) -> &Replacement<C> {
+        &self.policy
+    }
+
+    /// Reduce a population made of the previous members (before `start`) followed by the evaluated
+    /// offspring to `size` individuals according to the policy. Returns the number of offspring that
+    /// made it into the population.
+    pub(crate) fn replace(
+        &self,
+        population: &mut Population<C>,
+        start: usize,
+        size: usize,
+        objective: &Objective,
+    ) -> usize {
+        let mut individuals = std::mem::replace(population, Population::new(Vec::new()))
+            .into_iter()
+            .enumerate()
+            .map(|(i, individual)| (i >= start, individual))
+            .collect::<Vec<(bool, Phenotype<C>)>>();
+
+        match &self.policy {
+            Replacement::Worst => {
+                let mut index = start.min(individuals.len());
+                while index < individuals.len() {
+                    let genotype = individuals[index].1.genotype();
+                    if individuals[..index]
+                        .iter()
+                        .any(|(_, other)| other.genotype() == genotype)
+                    {
+                        individuals.remove(index);
+                    } else {
+                        index += 1;
+                    }
+                }
+            }
+            Replacement::Oldest => {
+                let excess = individuals.len().saturating_sub(size);
+                let mut members = (0..start.min(individuals.len())).collect::<Vec<usize>>();
+                // The oldest first, and among individuals of the same age the worst first.
+                members.sort_by(|&one, &two| {
+                    let (one, two) = (&individuals[one].1, &individuals[two].1);
+                    one.generation
+                        .cmp(&two.generation)
+                        .then_with(|| objective.compare(two, one))
+                });
+
+                let mut removed = members.into_iter().take(excess).collect::<Vec<usize>>();
+                removed.sort_unstable_by(|one, two| two.cmp(one));
+                for index in removed {
+                    individuals.remove(index);
+                }
+            }
+            Replacement::Crowding(diversity) => {
+                let mut members = individuals
+                    .drain(..start.min(individuals.len()))
+                    .collect::<Vec<(bool, Phenotype<C>)>>();
+                let mut contested = vec![false; members.len()];
+
+                for (_, offspring) in individuals.drain(..) {
+                    let nearest =
+                        (0..members.len())
+                            .filter(|&i| !contested[i])
+                            .min_by(|&one, &two| {
+                                let one = diversity
+                                    .distance(offspring.genotype(), members[one].1.genotype());
+                                let two = diversity
+                                    .distance(offspring.genotype(), members[two].1.genotype());
+                                one.partial_cmp(&two).unwrap_or(Ordering::Equal)
+                            });
+
+                    match nearest {
+                        Some(i) => {
+                            contested[i] = true;
+                            let defended = match (members[i].1.score(), offspring.score()) {
+                                (Some(member), Some(challenger)) => {
+                                    objective.is_better(member, challenger)
+                                }
+                                (member, challenger) => member.is_some() || challenger.is_none(),
+                            };
+
+                            if !defended {
+                                members[i] = (true, offspring);
+                            }
+                        }
+                        None => members.push((true, offspring)),
+                    }
+                }
+
+                individuals = members;
+            }
+        }
+
+        individuals.sort_by(|(_, one), (_, two)| objective.compare(one, two));
+        individuals.truncate(size);
+
+        let entered = individuals.iter().filter(|(new, _)| *new).count();
+        *population = individuals
+            .into_iter()
+            .map(|(_, individual)| individual)
+            .collect::<Population<C>>();
+        population.is_sorted = true;
+
+        entered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objectives::Optimize;
+    use crate::{EuclideanDistance, FloatChromosome, Genotype, Score};
+
+    fn individual(allele: f32, score: f32, generation: i32) -> Phenotype<FloatChromosome> {
+        let genotype = Genotype::new(vec![FloatChromosome::from(&[allele][..])]);
+        let mut phenotype = Phenotype::from_genotype(genotype, generation);
+        phenotype.set_score(Some(Score::from_f32(score)));
+        phenotype
+    }
+
+    fn scores(population: &Population<FloatChromosome>) -> Vec<f32> {
+        population
+            .iter()
+            .map(|individual| individual.score().unwrap().as_f32())
+            .collect()
+    }
+
+    #[test]
+    fn test_replacement_policies() {
+        let objective = Objective::Single(Optimize::Maximize);
+        let population = || {
+            Population::new(vec![
+                individual(0.0, 5.0, 2),
+                individual(1.0, 4.0, 0),
+                individual(2.0, 3.0, 1),
+                individual(0.1, 1.0, 3),
+                individual(1.9, 6.0, 3),
+            ])
+        };
+
+        let mut worst = population();
+        let entered = SteadyState::new(2, Replacement::Worst).replace(&mut worst, 3, 3, &objective);
+        assert_eq!(entered, 1);
+        assert_eq!(scores(&worst), vec![6.0, 5.0, 4.0]);
+
+        let mut oldest = population();
+        let entered =
+            SteadyState::new(2, Replacement::Oldest).replace(&mut oldest, 3, 3, &objective);
+        assert_eq!(entered, 2);
+        assert_eq!(scores(&oldest), vec![6.0, 5.0, 1.0]);
+
+        let mut crowding = population();
+        let entered = SteadyState::new(2, Replacement::crowding(EuclideanDistance)).replace(
+            &mut crowding,
+            3,
+            3,
+            &objective,
+        );
+        assert_eq!(entered, 1);
+        assert_eq!(scores(&crowding), vec![6.0, 5.0, 4.0]);
+    }
+
+    #[test]
+    fn test_worst_replacement_discards_copies_of_members() {
+        let objective = Objective::Single(Optimize::Maximize);
+        let mut population = Population::new(vec![
+            individual(0.0, 5.0, 0),
+            individual(1.0, 4.0, 0),
+            individual(2.0, 3.0, 0),
+            individual(0.0, 5.0, 1),
+            individual(3.0, 4.5, 1),
+            individual(3.0, 4.5, 1),
+        ]);
+
+        let entered =
+            SteadyState::new(3, Replacement::Worst).replace(&mut population, 3, 3, &objective);
+
+        assert_eq!(entered, 1);
+        assert_eq!(scores(&population), vec![5.0, 4.5, 4.0]);
+    }
+}
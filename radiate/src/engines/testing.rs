@@ -0,0 +1,239 @@
+//! Helpers for testing operators, enabled with the `test-util` feature. They let crates that implement
+//! their own alterers check them against the same invariants the built-in ones keep - permutations stay
+//! permutations, bounded genes stay within their bounds, graphs and trees stay valid - and compare
+//! serialized values against golden files.
+
+use std::path::Path;
+
+use crate::random_provider::RngHandle;
+
+use super::{
+    Alter, AlterAction, BoundGene, Chromosome, Codex, Gene, Genotype, PermutationChromosome,
+    Phenotype, Population, Valid,
+};
+
+type Invariant<C> = Box<dyn Fn(&Genotype<C>) -> bool>;
+
+/// Runs an alterer over a population for a number of generations, checking after every generation
+/// that every individual still holds each of the invariants. `check` panics naming the alterer, the
+/// invariant and the generation it was first broken in. The alterer draws from the calling thread's
+/// generator (see `RngHandle::current`).
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "test-util")]
+/// # {
+/// use radiate::*;
+/// use radiate::testing::*;
+///
+/// OperatorCheck::from_codex(PermutationCodex::new((0..10).collect::<Vec<usize>>()))
+///     .invariant("permutation", is_permutation)
+///     .check(PMXCrossover::new(0.5));
+/// # }
+/// ```
+pub struct OperatorCheck<C: Chromosome> {
+    encode: Box<dyn Fn() -> Genotype<C>>,
+    invariants: Vec<(&'static str, Invariant<C>)>,
+    population_size: usize,
+    generations: usize,
+}
+
+impl<C: Chromosome + 'static> OperatorCheck<C> {
+    /// Check an alterer on populations of genotypes created by `encode`.
+    pub fn new<F>(encode: F) -> Self
+    where
+        F: Fn() -> Genotype<C> + 'static,
+    {
+        OperatorCheck {
+            encode: Box::new(encode),
+            invariants: Vec::new(),
+            population_size: 20,
+            generations: 50,
+        }
+    }
+
+    /// Check an alterer on populations of genotypes encoded by the `codex`.
+    pub fn from_codex<T: 'static>(codex: impl Codex<C, T> + 'static) -> Self {
+        Self::new(move || codex.encode())
+    }
+
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        if population_size < 2 {
+            panic!("population_size must be at least 2");
+        }
+
+        self.population_size = population_size;
+        self
+    }
+
+    pub fn generations(mut self, generations: usize) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    /// Add an invariant every individual must hold.
+    pub fn invariant<F>(mut self, name: &'static str, invariant: F) -> Self
+    where
+        F: Fn(&Genotype<C>) -> bool + 'static,
+    {
+        self.invariants.push((name, Box::new(invariant)));
+        self
+    }
+
+    pub fn check(&self, alterer: impl Alter<C>) {
+        let name = alterer.name();
+        let action: AlterAction<C> = alterer.to_alter();
+
+        let mut population = (0..self.population_size)
+            .map(|_| Phenotype::from_genotype((self.encode)(), 0))
+            .collect::<Population<C>>();
+
+        self.assert_invariants(&population, || {
+            "the initial population (check the encoder)".to_string()
+        });
+
+        let rng = RngHandle::current();
+        for generation in 0..self.generations {
+            action.alter(&mut population, generation as i32, &rng);
+
+            self.assert_invariants(&population, || {
+                format!("'{}' in generation {}", name, generation)
+            });
+        }
+    }
+
+    fn assert_invariants(&self, population: &Population<C>, source: impl Fn() -> String) {
+        for (index, individual) in population.iter().enumerate() {
+            for (invariant, holds) in self.invariants.iter() {
+                if !holds(individual.genotype()) {
+                    panic!(
+                        "Invariant '{}' is broken for individual {} by {}",
+                        invariant,
+                        index,
+                        source()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Every chromosome (and gene) of the genotype is valid - for graphs and trees this includes every
+/// node having as many inputs as its operation's arity.
+pub fn is_valid<C: Chromosome>(genotype: &Genotype<C>) -> bool {
+    genotype.is_valid()
+}
+
+/// Every chromosome holds each of its alleles exactly once.
+pub fn is_permutation<A>(genotype: &Genotype<PermutationChromosome<A>>) -> bool
+where
+    A: PartialEq + Clone,
+{
+    genotype.iter().all(|chromosome| {
+        let mut seen = vec![false; chromosome.alleles.len()];
+        chromosome.genes.len() == chromosome.alleles.len()
+            && chromosome.genes.iter().all(|gene| {
+                gene.index < seen.len() && !std::mem::replace(&mut seen[gene.index], true)
+            })
+    })
+}
+
+/// Every gene's allele lies within its lower and upper bound.
+pub fn within_bounds<C>(genotype: &Genotype<C>) -> bool
+where
+    C: Chromosome,
+    C::Gene: BoundGene,
+    <C::Gene as Gene>::Allele: PartialOrd,
+{
+    genotype.iter().all(|chromosome| {
+        chromosome
+            .iter()
+            .all(|gene| gene.allele() >= gene.lower_bound() && gene.allele() <= gene.upper_bound())
+    })
+}
+
+/// Compare `actual` with the contents of the golden file at `path`, panicking with both when they
+/// differ. When the file doesn't exist yet, or the `UPDATE_GOLDEN` environment variable is set, the
+/// file is (re)written with `actual` instead. Line endings are normalized before comparing.
+pub fn assert_golden(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+
+    if !path.exists() || std::env::var_os("UPDATE_GOLDEN").is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+
+        std::fs::write(path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).unwrap().replace("\r\n", "\n");
+    let actual = actual.replace("\r\n", "\n");
+
+    if expected != actual {
+        panic!(
+            "Golden file {} doesn't match (set UPDATE_GOLDEN to update it)\n--- expected\n{}\n--- actual\n{}",
+            path.display(),
+            expected,
+            actual
+        );
+    }
+}
+
+/// Compare the pretty printed JSON of `value` with the golden file at `path` (see `assert_golden`).
+#[cfg(feature = "serde")]
+pub fn assert_golden_json<T: serde::Serialize>(path: impl AsRef<Path>, value: &T) {
+    assert_golden(path, &serde_json::to_string_pretty(value).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_built_in_operators_keep_their_invariants() {
+        let permutation = || {
+            OperatorCheck::from_codex(PermutationCodex::new((0..12).collect::<Vec<usize>>()))
+                .invariant("permutation", is_permutation)
+        };
+
+        permutation().check(PMXCrossover::new(0.5));
+        permutation().check(SwapMutator::new(0.3));
+        permutation().check(InversionMutator::new(0.3));
+
+        let bounded = || {
+            OperatorCheck::from_codex(FloatCodex::new(2, 5, -1.0, 1.0))
+                .invariant("bounds", within_bounds)
+                .invariant("valid", is_valid)
+        };
+
+        bounded().check(GaussianMutator::new(0.5));
+        bounded().check(ArithmeticMutator::new(0.5));
+        bounded().check(MeanCrossover::new(0.5));
+        bounded().check(IntermediateCrossover::new(0.5, 0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invariant 'permutation' is broken")]
+    fn test_broken_invariant_panics() {
+        OperatorCheck::from_codex(PermutationCodex::new((0..8).collect::<Vec<usize>>()))
+            .invariant("permutation", is_permutation)
+            .check(UniformCrossover::new(0.5));
+    }
+
+    #[test]
+    fn test_golden_files_are_written_then_compared() {
+        let path = std::env::temp_dir()
+            .join(format!("radiate-golden-{}", std::process::id()))
+            .join("fixture.txt");
+
+        assert_golden(&path, "first\n");
+        assert_golden(&path, "first\r\n");
+
+        let changed = std::panic::catch_unwind(|| assert_golden(&path, "second\n"));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert!(changed.is_err());
+    }
+}
@@ -56,4 +56,1700 @@ mod engine_tests {
         let best = result.best.first().unwrap();
         assert_eq!(best, &vec![1, 2, 3, 4, 5]);
     }
+
+    #[test]
+    fn preset_continuous_can_minimize_sphere() {
+        let engine = GeneticEngine::preset_continuous(3, -5.0..5.0)
+            .minimizing()
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_f32() < 0.01 || ctx.index > 500);
+
+        assert_eq!(result.best[0].len(), 3);
+        assert!(result.score().as_f32() < 0.01);
+    }
+
+    #[test]
+    fn preset_permutation_can_sort() {
+        let engine = GeneticEngine::preset_permutation(6)
+            .minimizing()
+            .fitness_fn(|perm: Vec<usize>| {
+                perm.iter()
+                    .enumerate()
+                    .map(|(i, v)| (i as i32 - *v as i32).abs())
+                    .sum::<i32>()
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_i32() == 0 || ctx.index > 500);
+
+        assert_eq!(result.best, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn engine_can_minimize_with_fitness_shaping() {
+        let engine = GeneticEngine::from_codex(FloatCodex::new(1, 3, -5.0, 5.0))
+            .minimizing()
+            .fitness_shaping(FitnessShaping::new().weight_decay(0.01).centered_rank())
+            .alter(alters!(
+                IntermediateCrossover::new(0.5, 0.5),
+                GaussianMutator::new(0.1)
+            ))
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| (x - 1.0).abs()).sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_f32() < 0.1 || ctx.index > 500);
+
+        assert!(result.score().as_f32() < 0.1);
+    }
+
+    #[test]
+    fn engine_can_solve_environment() {
+        struct Target {
+            position: f32,
+            steps: usize,
+        }
+
+        impl Environment for Target {
+            type Observation = f32;
+            type Action = f32;
+
+            fn reset(&mut self) -> f32 {
+                self.position = 3.0;
+                self.steps = 0;
+                self.position
+            }
+
+            fn step(&mut self, action: f32) {
+                self.position += action.clamp(-1.0, 1.0);
+                self.steps += 1;
+            }
+
+            fn observation(&self) -> f32 {
+                self.position
+            }
+
+            fn reward(&self) -> f32 {
+                -self.position.abs()
+            }
+
+            fn done(&self) -> bool {
+                self.steps >= 5
+            }
+        }
+
+        let runner = EpisodeRunner::new(|| Target {
+            position: 0.0,
+            steps: 0,
+        })
+        .episodes(2)
+        .aggregate(EpisodeAggregate::Min);
+
+        let engine = GeneticEngine::from_codex(FloatCodex::new(1, 1, -2.0, 2.0))
+            .fitness_fn(move |weights: Vec<Vec<f32>>| runner.run(|obs: &f32| obs * weights[0][0]))
+            .build();
+
+        let result = engine.run(|ctx| ctx.index > 50);
+
+        assert!(result.best[0][0] <= -1.0);
+        assert_eq!(result.score().as_f32(), -3.0);
+    }
+
+    #[test]
+    fn engine_records_best_individual() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 3, 0, 10))
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .recorder(|best: Vec<Vec<i32>>, recording: &mut Recording| {
+                for value in best[0].iter() {
+                    recording.value("genes", *value as f32);
+                }
+
+                recording.frame(format!("{:?}", best[0]));
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index == 5);
+        let recording = result.recording.unwrap();
+
+        assert_eq!(recording.generation, result.index - 1);
+        assert_eq!(recording.frames.len(), 1);
+        assert_eq!(recording.get_series("genes").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn engine_collects_evaluation_metadata() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .minimizing()
+            .fitness_fn(|geno: Vec<Vec<i32>>| {
+                let sum = geno[0].iter().sum::<i32>();
+                if sum % 2 == 1 {
+                    metadata::error("odd sum");
+                }
+
+                metadata::insert("sum", sum);
+                sum
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index == 3);
+
+        assert_eq!(
+            result.population.metadata_values("sum").len(),
+            result.population.len()
+        );
+
+        for individual in result.population.with_errors() {
+            assert_eq!(individual.error().unwrap(), "odd sum");
+            assert_eq!(individual.score().unwrap().as_i32() % 2, 1);
+        }
+    }
+
+    #[test]
+    fn engine_can_evolve_quantized_values() {
+        let codex = QuantizedCodex::<i8>::new(1, 4, -2.0, 2.0, 0.125);
+        let target = [0.5, -1.25, 1.0, 0.0];
+
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .alter(alters!(
+                UniformCrossover::new(0.5),
+                ArithmeticMutator::new(0.1)
+            ))
+            .fitness_fn(move |values: Vec<Vec<f32>>| {
+                values[0]
+                    .iter()
+                    .zip(target.iter())
+                    .map(|(a, b)| (a - b).abs())
+                    .sum::<f32>()
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_f32() == 0.0 || ctx.index > 500);
+
+        assert_eq!(result.best[0], target.to_vec());
+    }
+
+    #[test]
+    fn engine_can_evolve_variable_length_sequence() {
+        let codex = SequenceCodex::new(1, CharGene::new(), 1, 12);
+        let target = "radiate".chars().collect::<Vec<char>>();
+
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .alter(alters!(
+                AlignmentCrossover::new(0.5),
+                UniformMutator::new(0.05),
+                InsertionMutator::new(0.1),
+                DeletionMutator::new(0.1)
+            ))
+            .fitness_fn(move |sequences: Vec<Vec<char>>| levenshtein(&sequences[0], &target) as i32)
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_i32() == 0 || ctx.index > 2000);
+
+        assert_eq!(result.best[0].iter().collect::<String>(), "radiate");
+    }
+
+    #[test]
+    fn engine_tracks_population_movement() {
+        let codex = FloatCodex::new(1, 5, 0.0, 10.0);
+
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .population_movement(|gene: &FloatGene| *gene.allele())
+            .fitness_fn(|values: Vec<Vec<f32>>| values[0].iter().sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index == 20);
+
+        let drift = result.metrics.get(metric_names::CENTROID_DRIFT).unwrap();
+        let shrinkage = result
+            .metrics
+            .get(metric_names::VARIANCE_SHRINKAGE)
+            .unwrap();
+
+        assert!(drift.value_mean().unwrap() > 0.0);
+        assert_eq!(shrinkage.last_sequence().unwrap().len(), 5);
+        assert_eq!(result.snapshot.unwrap().centroid.len(), 5);
+    }
+
+    #[test]
+    fn engine_can_pin_evaluations_to_dedicated_threads() {
+        let codex = IntCodex::new(1, 5, 0, 100).with_bounds(0, 100);
+
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .num_threads(2)
+            .dedicated_threads(2)
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_i32() == 0);
+
+        assert_eq!(result.best[0].iter().sum::<i32>(), 0);
+        assert!(result.metrics.get(metric_names::QUEUE_DEPTH).is_some());
+    }
+
+    #[test]
+    fn engine_can_delegate_to_an_external_executor() {
+        let external = std::sync::Arc::new(radiate::thread_pool::ThreadPool::new(2));
+        let codex = IntCodex::new(1, 5, 0, 100).with_bounds(0, 100);
+
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .delegate_to(move |job| external.submit(job))
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_i32() == 0);
+
+        assert_eq!(result.best[0].iter().sum::<i32>(), 0);
+    }
+
+    #[test]
+    fn engine_reports_population_memory_to_budget() {
+        let budget = MemoryBudget::new(1024 * 1024);
+        let codex = FloatCodex::new(2, 10, 0.0, 1.0);
+
+        let engine = GeneticEngine::from_codex(codex)
+            .memory_budget(budget.clone())
+            .fitness_fn(|values: Vec<Vec<f32>>| values.iter().flatten().sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index == 5);
+
+        let memory = result.metrics.get(metric_names::POPULATION_MEMORY).unwrap();
+        assert!(budget.population() > 100 * 20 * std::mem::size_of::<FloatGene>());
+        assert_eq!(memory.last_value(), budget.population() as f32);
+        assert_eq!(budget.available(), budget.limit() - budget.population());
+    }
+
+    #[test]
+    fn engine_iterator_adapters() {
+        let codex = IntCodex::new(1, 5, 0, 100).with_bounds(0, 100);
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let sampled = engine
+            .iter()
+            .take(20)
+            .sample_every(5)
+            .with_progress()
+            .map(|ctx| ctx.index)
+            .collect::<Vec<i32>>();
+        assert_eq!(sampled, vec![5, 10, 15, 20]);
+
+        let converged = engine.iter().take(1000).converged(10, 0.0).last().unwrap();
+        assert!(converged.index < 1000);
+    }
+
+    #[cfg(feature = "wire")]
+    #[test]
+    fn engine_iterator_writes_checkpoints_and_counts_failed_writes() {
+        let codex = IntCodex::new(1, 5, 0, 100);
+        let template = codex.encode();
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let path = std::env::temp_dir().join(format!("radiate_checkpoint_{}", std::process::id()));
+        engine
+            .iter()
+            .take(4)
+            .checkpoint_every(2, &path)
+            .for_each(drop);
+        let checkpoint = Checkpoint::read(&path, &template).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(checkpoint.index, 4);
+        assert_eq!(checkpoint.population.len(), 100);
+
+        let missing = std::env::temp_dir()
+            .join("radiate-missing-directory")
+            .join("checkpoint.rdwf");
+        let failed = engine
+            .iter()
+            .take(4)
+            .checkpoint_every(2, missing)
+            .filter(|ctx| ctx.metrics.get(metric_names::WRITE_ERRORS).is_some())
+            .map(|ctx| ctx.index)
+            .collect::<Vec<i32>>();
+        assert_eq!(failed, vec![2, 4]);
+    }
+
+    #[test]
+    fn engine_typed_builder_builds_once_configured() {
+        let engine = GeneticEngine::builder()
+            .codex(IntCodex::new(1, 5, 0, 100).with_bounds(0, 100))
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .configure(|params| params.minimizing().population_size(50))
+            .build();
+
+        let result = engine.run(|ctx| ctx.score().as_i32() == 0);
+
+        assert_eq!(result.population.len(), 50);
+        assert_eq!(result.best.first().unwrap().iter().sum::<i32>(), 0);
+    }
+
+    #[test]
+    fn engine_credits_adaptive_operators() {
+        let codex = FloatCodex::new(1, 5, -10.0, 10.0);
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .alter(alters![AdaptiveChoice::new(vec![
+                GaussianMutator::new(0.2).to_alter(),
+                UniformCrossover::new(0.5).to_alter(),
+            ])
+            .with_policy(AdaptivePolicy::UpperConfidenceBound {
+                exploration: 1.0
+            })])
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 10);
+
+        let credit = result.metrics.get(metric_names::OPERATOR_CREDIT).unwrap();
+        assert_eq!(credit.count(), 2);
+        assert!(credit
+            .last_sequence()
+            .unwrap()
+            .iter()
+            .all(|rate| (0.0..=1.0).contains(rate)));
+    }
+
+    #[test]
+    fn engine_evaluates_objective_parts_separately() {
+        let codex = IntCodex::new(1, 5, 0, 10);
+        let engine = GeneticEngine::from_codex(codex)
+            .num_threads(3)
+            .multi_objective(vec![Optimize::Minimize, Optimize::Maximize])
+            .offspring_selector(TournamentSelector::new(3))
+            .survivor_selector(NSGA2Selector::new())
+            .objective_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>() as f32)
+            .objective_fn(|geno: Vec<Vec<i32>>| {
+                metadata::insert("max", geno[0].iter().max().unwrap());
+                *geno[0].iter().max().unwrap() as f32
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 5);
+
+        for individual in result.population.iter() {
+            let genes = individual.genotype()[0]
+                .iter()
+                .map(|gene| gene.allele)
+                .collect::<Vec<i32>>();
+            let score = individual.score().unwrap();
+
+            assert_eq!(score.values[0], genes.iter().sum::<i32>() as f32);
+            assert_eq!(score.values[1], *genes.iter().max().unwrap() as f32);
+            assert!(individual.metadata().unwrap().get("max").is_some());
+        }
+    }
+
+    struct Step(f32);
+
+    impl EngineCompoment for Step {
+        fn name(&self) -> &'static str {
+            "Step"
+        }
+    }
+
+    impl Alter<FloatChromosome> for Step {
+        fn rate(&self) -> f32 {
+            1.0
+        }
+
+        fn to_alter(self) -> AlterAction<FloatChromosome> {
+            AlterAction::Mutate(Box::new(self))
+        }
+    }
+
+    impl Mutate<FloatChromosome> for Step {
+        fn mutate_gene(&self, gene: &FloatGene, _: &random_provider::RngHandle) -> FloatGene {
+            gene.with_allele(&(gene.allele + self.0))
+        }
+    }
+
+    #[test]
+    fn engine_credits_the_operator_that_produced_each_offspring() {
+        let codex = FloatCodex::new(1, 3, -1000.0, 1000.0);
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .alter(alters![AdaptiveChoice::new(vec![
+                Step(1.0).to_alter(),
+                Step(-1.0).to_alter()
+            ])])
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 15);
+
+        let selection = result
+            .metrics
+            .get(metric_names::OPERATOR_SELECTION)
+            .unwrap()
+            .last_sequence()
+            .unwrap()
+            .clone();
+        assert!(selection[1] > 0.8);
+    }
+
+    #[test]
+    fn engine_exposes_gene_schema() {
+        let schema = GeneSchema::from_names(&["x", "y"]);
+        let codex = FloatCodex::new(1, 2, 0.0, 1.0).with_schema(schema);
+        let engine = GeneticEngine::from_codex(codex)
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 2);
+        let schema = result.schema.as_ref().unwrap();
+        let described = schema.describe(result.population[0].genotype());
+
+        assert_eq!(schema.index_of("y"), Some((0, 1)));
+        assert_eq!(described.len(), 2);
+        assert_eq!(described[0].0, "x");
+        assert_eq!(described[1].0, "y");
+    }
+
+    #[test]
+    fn engine_scores_repeated_evaluations_with_confidence() {
+        let codex = IntCodex::new(1, 5, 0, 100);
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .repeat_evaluations(4)
+            .survivor_selector(TournamentSelector::new(3).with_significance(0.05))
+            .fitness_fn(|geno: Vec<Vec<i32>>| {
+                let noise = random_provider::gen_range(-1.0..1.0);
+                geno[0].iter().sum::<i32>() as f32 + noise
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 10);
+        let stats = result.score().stats().unwrap();
+        let (lower, upper) = result.score().confidence_interval(0.95).unwrap()[0];
+
+        assert_eq!(stats.samples, 4);
+        assert!(lower <= result.score().as_f32() && result.score().as_f32() <= upper);
+        assert!(format!("{:?}", result).contains("95% CI"));
+    }
+
+    #[test]
+    fn engine_races_noisy_evaluations() {
+        let codex = IntCodex::new(1, 5, 0, 100);
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .population_size(20)
+            .racing(Racing::new(8).min_replications(3))
+            .fitness_fn(|geno: Vec<Vec<i32>>| {
+                let noise = random_provider::gen_range(-1.0..1.0);
+                geno[0].iter().sum::<i32>() as f32 + noise
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 3);
+        let eliminations = result.metrics.get(metric_names::RACE_ELIMINATIONS).unwrap();
+        let samples = result
+            .population
+            .iter()
+            .map(|individual| individual.score().unwrap().stats().unwrap().samples)
+            .collect::<Vec<usize>>();
+
+        assert!(eliminations.value_max().unwrap() > 0.0);
+        assert!(samples.iter().all(|count| (3..=8).contains(count)));
+        assert!(samples.iter().any(|count| *count < 8));
+    }
+
+    #[test]
+    fn engine_seeds_initial_population_from_prior() {
+        let prior = PopulationPrior::from_genotypes(vec![Genotype::new(vec![IntChromosome {
+            genes: vec![IntGene::from_min_max(0, 100).with_allele(&7); 5],
+        }])])
+        .with_sample_fraction(0.5);
+
+        let evaluated = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = std::sync::Arc::clone(&evaluated);
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .population_size(21)
+            .prior(prior)
+            .fitness_fn(move |geno: Vec<Vec<i32>>| {
+                recorded.lock().unwrap().push(geno[0].clone());
+                geno[0].iter().sum::<i32>()
+            })
+            .build();
+
+        engine.run(|ctx| ctx.index >= 1);
+
+        let evaluated = evaluated.lock().unwrap();
+        let seeded = evaluated[..21]
+            .iter()
+            .filter(|geno| geno.iter().all(|x| *x == 7))
+            .count();
+        assert_eq!(seeded, 11);
+    }
+
+    #[test]
+    fn engine_publishes_events_to_subscribers() {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = std::sync::Arc::clone(&events);
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .subscribe(move |event: &EngineEvent<Vec<Vec<i32>>>| {
+                let name = match event {
+                    EngineEvent::Start => "start".to_string(),
+                    EngineEvent::EpochComplete { index, .. } => index.to_string(),
+                    EngineEvent::Stop { .. } => "stop".to_string(),
+                    EngineEvent::Error { message, .. } => message.clone(),
+                };
+                seen.lock().unwrap().push(name);
+            })
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        engine.run(|ctx| ctx.index >= 3);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["start", "1", "2", "3", "stop"]
+        );
+    }
+
+    #[test]
+    fn engine_evaluates_multi_objective_batches_with_failures() {
+        let batch_sizes = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = std::sync::Arc::clone(&batch_sizes);
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .num_threads(3)
+            .population_size(30)
+            .multi_objective(vec![Optimize::Minimize, Optimize::Maximize])
+            .offspring_selector(TournamentSelector::new(3))
+            .survivor_selector(NSGA2Selector::new())
+            .batch_fitness_fn(7, move |batch: Vec<Vec<Vec<i32>>>| {
+                recorded.lock().unwrap().push(batch.len());
+                batch
+                    .iter()
+                    .map(|geno| {
+                        let sum = geno[0].iter().sum::<i32>();
+                        match sum % 5 == 0 {
+                            true => Err(format!("sum {} failed", sum)),
+                            false => Ok(vec![sum as f32, *geno[0].iter().max().unwrap() as f32]),
+                        }
+                    })
+                    .collect()
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 5);
+
+        assert!(batch_sizes.lock().unwrap().iter().all(|size| *size <= 7));
+        assert!(batch_sizes.lock().unwrap().iter().any(|size| *size > 1));
+
+        let worst = Score::from_vec(vec![f32::MAX, f32::MIN]);
+        for individual in result.population.iter() {
+            let sum = individual.genotype()[0]
+                .iter()
+                .map(|gene| gene.allele)
+                .sum::<i32>();
+            let score = individual.score().unwrap();
+
+            if sum % 5 == 0 {
+                assert!(*score == worst);
+                let error = individual.metadata().unwrap().error().unwrap();
+                assert_eq!(*error, format!("sum {} failed", sum));
+            } else {
+                assert_eq!(score.values[0], sum as f32);
+                assert!(individual.metadata().is_none());
+            }
+        }
+
+        let front = result.front.lock().unwrap();
+        assert!(!front.scores().is_empty());
+        assert!(front.scores().iter().all(|score| *score != worst));
+    }
+
+    #[test]
+    fn engine_evaluates_individuals_together_in_groups() {
+        let group_sizes = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = std::sync::Arc::clone(&group_sizes);
+        let evaluator = GroupEvaluator::new(4, move |players: &[Vec<Vec<i32>>]| {
+            recorded.lock().unwrap().push(players.len());
+            let total = players.iter().map(|player| player[0][0]).sum::<i32>();
+            players
+                .iter()
+                .map(|player| player[0][0] as f32 / (total + 1) as f32)
+                .collect::<Vec<f32>>()
+        })
+        .rounds(3);
+
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 1, 0, 100))
+            .num_threads(2)
+            .population_size(18)
+            .group_evaluator(evaluator)
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 5);
+
+        assert!(group_sizes.lock().unwrap().iter().all(|size| *size == 4));
+        for individual in result.population.iter() {
+            let score = individual.score().unwrap();
+            assert_eq!(score.stats().unwrap().samples, 3);
+            assert!((0.0..=1.0).contains(&score.as_f32()));
+        }
+    }
+
+    #[test]
+    fn engine_only_evaluates_changed_individuals() {
+        let run = |stochastic: bool| {
+            let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let counter = std::sync::Arc::clone(&calls);
+            let mut builder = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+                .population_size(20)
+                .alter(alters!(UniformMutator::new(0.0)))
+                .fitness_fn(move |geno: Vec<Vec<i32>>| {
+                    counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    geno[0].iter().sum::<i32>()
+                });
+
+            if stochastic {
+                builder = builder.stochastic_fitness();
+            }
+
+            let result = builder.build().run(|ctx| ctx.index >= 5);
+            let calls = calls.load(std::sync::atomic::Ordering::SeqCst);
+            (calls, result)
+        };
+
+        let (calls, result) = run(false);
+        assert_eq!(calls, 20);
+        assert!(result
+            .metrics
+            .get(metric_names::SKIPPED_EVALUATIONS)
+            .is_some());
+
+        let (calls, _) = run(true);
+        assert_eq!(calls, 100);
+    }
+
+    #[test]
+    fn engine_resizes_population_on_schedule() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .population_size(20)
+            .survivor_selector(EliteSelector::new())
+            .population_schedule(PopulationSchedule::Steps(vec![(2, 40), (4, 12)]))
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let sizes = engine
+            .iter()
+            .take(6)
+            .map(|ctx| ctx.population.len())
+            .collect::<Vec<usize>>();
+
+        assert_eq!(sizes, vec![20, 20, 40, 40, 12, 12]);
+    }
+
+    #[test]
+    fn engine_repairs_altered_individuals_with_the_codex() {
+        let run = |rate: f32| {
+            let repairs = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let counter = std::sync::Arc::clone(&repairs);
+            let codex = PermutationCodex::new((0..8).collect::<Vec<usize>>()).with_repair(
+                move |_: &mut Genotype<PermutationChromosome<usize>>| {
+                    counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                },
+            );
+
+            let engine = GeneticEngine::from_codex(codex)
+                .population_size(20)
+                .alter(alters!(UniformMutator::new(rate)))
+                .fitness_fn(|order: Vec<usize>| order[0])
+                .build();
+
+            engine.run(|ctx| ctx.index >= 5);
+            repairs.load(std::sync::atomic::Ordering::SeqCst)
+        };
+
+        assert_eq!(run(0.0), 0);
+        assert!(run(1.0) > 0);
+
+        let codex = PermutationCodex::new(vec!['a', 'b', 'c', 'd']);
+        let mut genotype = codex.encode();
+        for (gene, index) in genotype[0].genes.iter_mut().zip([2, 2, 9, 0]) {
+            gene.index = index;
+        }
+
+        codex.repair(&mut genotype);
+        let indexes = genotype[0]
+            .genes
+            .iter()
+            .map(|gene| gene.index)
+            .collect::<Vec<usize>>();
+        assert_eq!(indexes, vec![2, 1, 3, 0]);
+        assert!(genotype.is_valid());
+    }
+
+    #[test]
+    fn engine_records_genotype_metrics_over_the_population() {
+        let engine = GeneticEngine::from_codex(IntCodex::<i32>::new(1, 4, 0, 10))
+            .population_size(12)
+            .genotype_metric("Gene Sum", |geno: &Genotype<IntChromosome<i32>>| {
+                geno[0].genes.iter().map(|gene| gene.allele).sum::<i32>() as f32
+            })
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 3);
+        let sums = result
+            .metrics
+            .get("Gene Sum")
+            .unwrap()
+            .last_sequence()
+            .unwrap();
+
+        assert_eq!(sums.len(), 12);
+        assert!(sums.iter().all(|sum| (0.0..=40.0).contains(sum)));
+    }
+
+    #[test]
+    fn engine_adds_complexity_objectives_to_the_score() {
+        let engine = GeneticEngine::from_codex(FloatCodex::new(1, 6, -1.0, 1.0))
+            .population_size(20)
+            .minimizing()
+            .fitness_fn(|genes: Vec<Vec<f32>>| (genes[0][0] - 0.5).abs())
+            .complexity_objective(Complexity::L0)
+            .complexity_objective(Complexity::L1)
+            .offspring_selector(TournamentSelector::new(3))
+            .survivor_selector(NSGA2Selector::new())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index > 5);
+
+        for individual in result.population.iter() {
+            let values = &individual.score().unwrap().values;
+            let genes = &individual.genotype()[0].genes;
+
+            assert_eq!(values.len(), 3);
+            assert_eq!(values[1], Complexity::L0.measure(individual.genotype()));
+            assert!(
+                (values[2] - genes.iter().map(|gene| gene.allele.abs()).sum::<f32>()).abs() < 1e-4
+            );
+        }
+    }
+
+    #[test]
+    fn alter_pipeline_previews_each_alterer() {
+        let codex = IntCodex::<i32>::new(2, 10, 0, 1000);
+        let pipeline = AlterPipeline::new(alters!(
+            MultiPointCrossover::new(1.0, 2),
+            UniformMutator::new(0.0),
+            UniformMutator::new(1.0),
+        ));
+
+        let genotypes = (0..20).map(|_| codex.encode()).collect::<Vec<_>>();
+        let preview = pipeline.preview_population(&genotypes);
+
+        assert_eq!(preview.samples, 20);
+        assert_eq!(preview.stages.len(), 3);
+        assert!(preview.stages[0].changed_individuals > 0);
+        assert_eq!(preview.stages[1].changed_genes, 0);
+        assert_eq!(preview.stages[1].operations, 0.0);
+        assert!(preview.stages[2].changed_individuals > 15);
+        assert_eq!(preview.position_rates.len(), 20);
+        assert_eq!(preview.histogram().iter().sum::<usize>(), 20);
+        assert!(preview.changed_fraction() > 0.75);
+
+        let untouched = AlterPipeline::from(alters!(UniformMutator::new(0.0)));
+        let preview = untouched.preview(&genotypes[0], 10);
+        assert_eq!(preview.changed_genes, vec![0; 10]);
+        assert_eq!(preview.histogram(), vec![10]);
+    }
+
+    #[test]
+    fn engine_calibrates_threads_and_batch_size() {
+        let engine = GeneticEngine::from_codex(IntCodex::<i32>::new(1, 5, 0, 100))
+            .population_size(20)
+            .minimizing()
+            .calibrate(
+                Calibration::new()
+                    .thread_counts(vec![1, 4])
+                    .batch_sizes(vec![1, 5])
+                    .rounds(1),
+            )
+            .fitness_fn(|geno: Vec<Vec<i32>>| {
+                std::thread::sleep(std::time::Duration::from_millis(2));
+                geno[0].iter().sum::<i32>()
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index > 3);
+
+        let threads = result
+            .metrics
+            .get(metric_names::CALIBRATED_THREADS)
+            .unwrap();
+        let batch_size = result
+            .metrics
+            .get(metric_names::CALIBRATED_BATCH_SIZE)
+            .unwrap();
+
+        assert_eq!(threads.last_value(), 4.0);
+        assert!([1.0, 5.0].contains(&batch_size.last_value()));
+        assert!(result
+            .population
+            .iter()
+            .all(|individual| individual.score().is_some()));
+    }
+
+    #[test]
+    fn engine_scores_altered_offspring_with_delta_fitness() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct WeightedSum {
+            weights: Vec<f32>,
+            full_evaluations: Arc<AtomicUsize>,
+        }
+
+        impl DeltaFitness<IntChromosome<i32>, Vec<Vec<i32>>> for WeightedSum {
+            fn fitness(&self, individual: Vec<Vec<i32>>) -> Score {
+                self.full_evaluations.fetch_add(1, Ordering::SeqCst);
+                let sum = individual[0]
+                    .iter()
+                    .zip(self.weights.iter())
+                    .map(|(value, weight)| *value as f32 * weight)
+                    .sum::<f32>();
+                Score::from_f32(sum)
+            }
+
+            fn delta(
+                &self,
+                parent: &Score,
+                changes: &[GeneChange<IntGene<i32>>],
+                _: &Genotype<IntChromosome<i32>>,
+            ) -> Option<Score> {
+                let diff = changes
+                    .iter()
+                    .map(|change| {
+                        (change.new.allele() - change.old.allele()) as f32
+                            * self.weights[change.index]
+                    })
+                    .sum::<f32>();
+                Some(Score::from_f32(parent.as_f32() + diff))
+            }
+        }
+
+        let weights = (0..20).map(|i| (i % 5) as f32 - 2.0).collect::<Vec<f32>>();
+        let full_evaluations = Arc::new(AtomicUsize::new(0));
+
+        let engine = GeneticEngine::from_codex(IntCodex::<i32>::new(1, 20, 0, 10))
+            .population_size(30)
+            .delta_fitness(WeightedSum {
+                weights: weights.clone(),
+                full_evaluations: Arc::clone(&full_evaluations),
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index > 20);
+
+        for individual in result.population.iter() {
+            let genes = &individual.genotype()[0].genes;
+            let expected = genes
+                .iter()
+                .zip(weights.iter())
+                .map(|(gene, weight)| gene.allele as f32 * weight)
+                .sum::<f32>();
+
+            assert!((individual.score().unwrap().as_f32() - expected).abs() < 1e-3);
+        }
+
+        let deltas = result.metrics.get(metric_names::DELTA_EVALUATIONS).unwrap();
+        assert!(deltas.value_max().unwrap() > 0.0);
+        assert!(full_evaluations.load(Ordering::SeqCst) < 30 * 21);
+    }
+
+    #[test]
+    fn coevolved_tic_tac_toe_agents_beat_a_random_player() {
+        let hall_of_fame = HallOfFame::new(3);
+        let engine = TicTacToe::coevolution(hall_of_fame.clone())
+            .population_size(30)
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 25);
+
+        assert!(!hall_of_fame.is_empty());
+        assert!(result
+            .metrics
+            .get(metric_names::HALL_OF_FAME_WIN_RATE)
+            .is_some());
+
+        let agent = &result.best[0];
+        let points = play_match(
+            &TicTacToe::new(),
+            |game| game.agent_move(agent),
+            TicTacToe::random_move,
+            100,
+        );
+
+        assert!(points > 0.7);
+    }
+
+    #[test]
+    fn engines_with_the_same_seed_evolve_the_same_population() {
+        let run = |seed: u64| {
+            let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+                .seed(seed)
+                .minimizing()
+                .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+                .build();
+
+            // reseeding the global generator mid-run doesn't reach the engine's own generator
+            random_provider::set_seed(seed + 1);
+            engine.run(|ctx| ctx.index >= 20)
+        };
+
+        let first = run(11);
+        let second = run(11);
+
+        assert_eq!(first.best, second.best);
+        assert_eq!(first.score(), second.score());
+
+        let population = |ctx: &EngineContext<IntChromosome<i32>, Vec<Vec<i32>>>| {
+            ctx.population
+                .iter()
+                .map(|individual| individual.genotype().clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(population(&first) == population(&second));
+    }
+
+    #[test]
+    fn engine_metrics_can_be_read_with_typed_accessors() {
+        const DECODED_SUM: MetricKey<ValueMetric> = MetricKey::value("Decoded Sum");
+
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .minimizing()
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let mut result = engine.run(|ctx| ctx.index >= 10);
+        let best = result.best[0].iter().sum::<i32>() as f32;
+        result.metrics.record(&DECODED_SUM, &best);
+
+        let scores = result.metrics.score_stats().unwrap();
+        assert!(scores.min <= scores.mean && scores.mean <= scores.max);
+
+        let evaluation = result.metrics.step_times(radiate::Step::Evaluate).unwrap();
+        assert!(evaluation.count > 0);
+        assert_eq!(
+            result.metrics.duration(radiate::Step::Evaluate),
+            Some(evaluation.last)
+        );
+        assert!(result.metrics.duration(radiate::Step::Front).is_none());
+
+        assert_eq!(result.metrics.genome_size_stats().unwrap().max, 5.0);
+        assert_eq!(result.metrics.stats(&DECODED_SUM).unwrap().last, best);
+    }
+
+    #[test]
+    fn engine_records_a_timing_breakdown_per_pipeline_step() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+            .minimizing()
+            .num_threads(2)
+            .fitness_fn(|geno: Vec<Vec<i32>>| {
+                std::thread::sleep(std::time::Duration::from_micros(50));
+                geno[0].iter().sum::<i32>()
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 5);
+        let timing = result.metrics.timing_breakdown();
+
+        for stage in [
+            "Selection",
+            "Alteration",
+            "Evaluation",
+            "Replacement",
+            "Audit",
+        ] {
+            assert_eq!(timing.stage(stage).unwrap().count, 5);
+        }
+
+        assert!(timing.stage("Front").is_none());
+        assert!(timing.share("Evaluation").unwrap() > timing.share("Selection").unwrap());
+
+        let shares = TimingBreakdown::STAGES
+            .iter()
+            .filter_map(|(stage, _)| timing.share(stage))
+            .sum::<f32>();
+        assert!(shares <= 1.0);
+
+        let efficiency = timing.parallel_efficiency.unwrap();
+        assert!(efficiency > 0.0 && efficiency <= 1.05);
+        assert!(timing.to_string().contains("Evaluation"));
+    }
+
+    #[test]
+    fn engine_evolves_with_externally_scored_candidates() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+            .minimizing()
+            .population_size(30)
+            .seed(7)
+            .fitness_fn(|_: Vec<Vec<i32>>| -> i32 { panic!("the fitness function isn't called") })
+            .build();
+
+        let mut session = engine.ask_tell();
+
+        let first = session.ask(20);
+        assert_eq!(first.len(), 20);
+        assert_eq!(session.remaining_initial(), 10);
+
+        let score = |genotype: &Genotype<IntChromosome<i32>>| {
+            Score::from_int(genotype[0].iter().map(|gene| *gene.allele()).sum::<i32>())
+        };
+
+        session.tell(first.into_iter().map(|g| (g.clone(), score(&g))).collect());
+        let initial_best = session.context().score().as_i32();
+
+        for _ in 0..100 {
+            let candidates = session.ask(15);
+            assert_eq!(candidates.len(), 15);
+            session.tell(
+                candidates
+                    .into_iter()
+                    .map(|g| (g.clone(), score(&g)))
+                    .collect(),
+            );
+            assert!(session.context().population.len() <= 30);
+        }
+
+        let context = session.into_context();
+        assert_eq!(context.index, 101);
+        assert!(context.score().as_i32() < initial_best);
+        assert_eq!(
+            context.best[0].iter().sum::<i32>(),
+            context.score().as_i32()
+        );
+    }
+
+    #[cfg(feature = "wire")]
+    #[test]
+    fn engine_resumes_from_a_checkpoint_where_it_left_off() {
+        let path =
+            std::env::temp_dir().join(format!("radiate-checkpoint-{}.rdwf", std::process::id()));
+
+        let params = || {
+            GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+                .minimizing()
+                .population_size(40)
+                .seed(11)
+                .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+        };
+
+        let original = params().checkpoint_every(5, &path).build();
+        let finished = original.run(|ctx| ctx.index >= 7);
+        assert_eq!(finished.index, 7);
+
+        let resumed = EngineBuilder::from_checkpoint(&path)
+            .codex(IntCodex::new(1, 10, 0, 100))
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .configure(|params| params.minimizing().population_size(40))
+            .build();
+
+        let checkpoint = Checkpoint::read(&path, &IntCodex::new(1, 10, 0, 100).encode()).unwrap();
+        assert_eq!(checkpoint.index, 5);
+        assert_eq!(checkpoint.population.len(), 40);
+        assert_eq!(
+            checkpoint.metrics.score_stats().unwrap().count,
+            finished.metrics.score_stats().unwrap().count - 2
+        );
+
+        let continued = resumed.run(|ctx| ctx.index >= 7);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(continued.index, 7);
+        assert_eq!(continued.score(), finished.score());
+        let scores = |ctx: &EngineContext<IntChromosome<i32>, Vec<Vec<i32>>>| {
+            ctx.population
+                .iter()
+                .map(|individual| individual.score().unwrap().as_i32())
+                .collect::<Vec<i32>>()
+        };
+        assert_eq!(scores(&continued), scores(&finished));
+    }
+
+    #[cfg(feature = "wire")]
+    #[test]
+    fn engine_checkpoints_dont_change_the_run_and_store_the_default_generator() {
+        let path = std::env::temp_dir().join(format!(
+            "radiate-checkpoint-rng-{}.rdwf",
+            std::process::id()
+        ));
+
+        let params = || {
+            GeneticEngine::from_codex(IntCodex::new(1, 10, 0, 100))
+                .minimizing()
+                .population_size(40)
+                .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+        };
+        let scores = |ctx: &EngineContext<IntChromosome<i32>, Vec<Vec<i32>>>| {
+            ctx.population
+                .iter()
+                .map(|individual| individual.score().unwrap().as_i32())
+                .collect::<Vec<i32>>()
+        };
+
+        let plain = params().seed(5).build().run(|ctx| ctx.index >= 8);
+        let checkpointed = params()
+            .seed(5)
+            .checkpoint_every(2, &path)
+            .build()
+            .run(|ctx| ctx.index >= 8);
+        assert_eq!(scores(&checkpointed), scores(&plain));
+
+        // without a seed the engine still gets a generator the checkpoint can store
+        let unseeded = params().checkpoint_every(4, &path).build();
+        let finished = unseeded.run(|ctx| ctx.index >= 6);
+
+        let continued = GeneticEngine::from_checkpoint(&path)
+            .codex(IntCodex::new(1, 10, 0, 100))
+            .minimizing()
+            .population_size(40)
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build()
+            .run(|ctx| ctx.index >= 6);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(scores(&continued), scores(&finished));
+    }
+
+    #[test]
+    fn engine_typed_builder_takes_fitness_fn_for_the_decoded_type() {
+        let engine = GeneticEngine::builder()
+            .codex(FloatCodex::new(1, 4, -1.0, 1.0))
+            .fitness_fn_for::<Vec<Vec<f32>>, _>(|geno| geno[0].iter().map(|x| x * x).sum::<f32>())
+            .configure(|params| params.minimizing().population_size(40))
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 50);
+
+        assert_eq!(result.population.len(), 40);
+        assert!(result.score().as_f32() < 0.1);
+    }
+
+    #[test]
+    fn engine_steady_state_replaces_a_few_individuals_per_step() {
+        let engine = GeneticEngine::from_codex(FloatCodex::new(1, 4, -1.0, 1.0))
+            .minimizing()
+            .population_size(40)
+            .max_age(100)
+            .steady_state(4, Replacement::Worst)
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+            .build();
+
+        let generations = engine.iter().take(400).collect::<Vec<_>>();
+        for pair in generations.windows(2) {
+            let changed = pair[1]
+                .population
+                .iter()
+                .filter(|one| {
+                    !pair[0]
+                        .population
+                        .iter()
+                        .any(|two| two.genotype() == one.genotype())
+                })
+                .count();
+            assert!(changed <= 4);
+        }
+
+        let result = generations.last().unwrap();
+        assert_eq!(result.population.len(), 40);
+        assert!(result.score().as_f32() < 0.05);
+    }
+
+    #[test]
+    fn engine_is_steered_through_a_watched_control_file() {
+        let path = std::env::temp_dir().join("radiate-engine-control-test.toml");
+        let _ = std::fs::remove_file(&path);
+
+        let panel = ControlPanel::new().watch(&path);
+        let engine = GeneticEngine::from_codex(FloatCodex::new(1, 4, 0.0, 1.0))
+            .population_size(30)
+            .control(panel.clone())
+            .alter(alters![ControlledMutator::new(
+                UniformMutator::new(0.1),
+                panel.clone()
+            )])
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| {
+            if ctx.index == 5 {
+                std::fs::write(&path, "mutation_rate = 0.3\npopulation_size = 12\n").unwrap();
+            }
+
+            ctx.index >= 10
+        });
+
+        std::fs::remove_file(&path).unwrap();
+
+        let rate = result
+            .metrics
+            .get(metric_names::CONTROL_MUTATION_RATE)
+            .unwrap();
+        assert_eq!(rate.last_value(), 0.3);
+        assert_eq!(panel.get(Knob::MutationRate), Some(0.3));
+        assert_eq!(result.population.len(), 12);
+    }
+
+    #[test]
+    fn engine_result_carries_the_configuration_that_produced_it() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .minimizing()
+            .population_size(20)
+            .offspring_selector(TournamentSelector::new(4))
+            .survivor_selector(EliteSelector::new())
+            .alter(alters![
+                MultiPointCrossover::new(0.4, 2),
+                Choice::new(vec![(1.0, SwapMutator::new(0.2).to_alter())])
+            ])
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 3);
+        let configuration = &result.configuration;
+
+        assert_eq!(configuration.name, "GeneticEngine");
+        assert_eq!(configuration.get("population_size"), Some("20"));
+        assert_eq!(
+            configuration.children[1].to_string(),
+            "offspring_selector[TournamentSelector(num=4)]"
+        );
+
+        let alterers = &configuration.children[2];
+        assert_eq!(alterers.children.len(), 2);
+        assert_eq!(
+            alterers.children[0].to_string(),
+            "MultiPointCrossover(rate=0.4, num_points=2)"
+        );
+        assert_eq!(
+            alterers.children[1].children[0].to_string(),
+            "SwapMutator(rate=0.2, weight=1)"
+        );
+    }
+
+    #[test]
+    fn engine_self_adapts_mutation_step_sizes() {
+        let codex = StrategyCodex::new(1, 5, -10.0, 10.0, 1.0);
+        let engine = GeneticEngine::from_codex(codex.clone())
+            .minimizing()
+            .population_size(50)
+            .alter(alters![
+                SelfAdaptiveMutator::new(1.0),
+                UniformCrossover::new(0.5)
+            ])
+            .fitness_fn(|geno: Vec<Vec<f32>>| geno[0].iter().map(|x| x * x).sum::<f32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 150);
+        let sigmas = codex.decode_sigmas(result.population[0].genotype());
+
+        assert!(result.score().as_f32() < 0.1);
+        assert!(sigmas[0].iter().all(|sigma| *sigma < 1.0));
+    }
+
+    #[test]
+    fn engine_evolves_individuals_of_different_structures() {
+        // Every chromosome is a layer: either a dense layer (width, dropout) or a conv layer
+        // (filters, pooling). Networks have a different number of layers of different kinds.
+        fn layer() -> MixedChromosome {
+            match random_provider::random::<f32>() < 0.5 {
+                true => MixedChromosome::new(vec![
+                    IntGene::from_min_max(1, 32).into(),
+                    FloatGene::new(0.0, 0.5).into(),
+                ]),
+                false => MixedChromosome::new(vec![
+                    IntGene::from_min_max(1, 32).into(),
+                    BitGene::new().into(),
+                ]),
+            }
+        }
+
+        let codex = FnCodex::new()
+            .with_encoder(|| {
+                let layers = random_provider::gen_range(1..6);
+                Genotype::new((0..layers).map(|_| layer()).collect())
+            })
+            .with_decoder(|genotype: &Genotype<MixedChromosome>| {
+                genotype
+                    .iter()
+                    .map(|layer| layer.genes[0].allele().as_i32().unwrap())
+                    .collect::<Vec<i32>>()
+            });
+
+        let engine = GeneticEngine::from_codex(codex)
+            .minimizing()
+            .population_size(60)
+            .alter(alters![
+                HomologousCrossover::new(0.5),
+                UniformMutator::new(0.05),
+                StructureMutator::new(0.1, layer).with_limits(1, 6)
+            ])
+            .fitness_fn(|widths: Vec<i32>| {
+                // Four layers with a total width of 40.
+                let layers = (widths.len() as i32 - 4).abs() * 10;
+                layers + (widths.iter().sum::<i32>() - 40).abs()
+            })
+            .build();
+
+        let first = engine.iter().next().unwrap();
+        let structures = first
+            .population
+            .iter()
+            .map(|individual| {
+                individual
+                    .genotype()
+                    .iter()
+                    .map(|layer| layer.structure())
+                    .collect::<Vec<Vec<GeneKind>>>()
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert!(structures.len() > 1);
+
+        let result = engine.run(|ctx| ctx.score().as_i32() == 0 || ctx.index >= 300);
+
+        assert_eq!(result.score().as_i32(), 0);
+        assert_eq!(result.best.len(), 4);
+        assert_eq!(result.best.iter().sum::<i32>(), 40);
+    }
+
+    #[test]
+    fn engine_restarts_the_population_when_it_stagnates() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .minimizing()
+            .population_size(20)
+            .max_age(1000)
+            .survivor_selector(EliteSelector::new())
+            .restart_on_stagnation(5, RestartStrategy::KeepTop(2))
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 100);
+        let restarts = result.metrics.get(metric_names::RESTART).unwrap();
+
+        assert_eq!(restarts.value_max(), Some(18.0));
+        assert_eq!(result.score().as_i32(), 0);
+        assert_eq!(result.population[0].score(), result.score.as_ref());
+        assert_eq!(result.configuration.get("restart"), Some("keep_top(2)"));
+    }
+
+    #[test]
+    fn engine_starts_from_seeded_genotypes_values_and_populations() {
+        let codex = FloatCodex::new(1, 3, -1.0, 1.0);
+        let known = vec![vec![0.0, 0.0, 0.0]];
+        assert_eq!(codex.decode(&codex.encode_value(&known).unwrap()), known);
+        assert!(codex.encode_value(&vec![vec![0.0]]).is_none());
+
+        let fitness = |values: Vec<Vec<f32>>| values[0].iter().map(|x| x * x).sum::<f32>();
+        let first_of = |seeds: Seeds<FloatChromosome, Vec<Vec<f32>>>| {
+            GeneticEngine::from_codex(codex.clone())
+                .minimizing()
+                .population_size(20)
+                .survivor_selector(EliteSelector::new())
+                .seed_population(seeds)
+                .fitness_fn(fitness)
+                .build()
+                .iter()
+                .next()
+                .unwrap()
+        };
+
+        let from_values = first_of(Seeds::Values(vec![known.clone()]));
+        assert_eq!(from_values.score().as_f32(), 0.0);
+
+        let from_genotypes = first_of(vec![codex.encode_value(&known).unwrap()].into());
+        assert_eq!(from_genotypes.score().as_f32(), 0.0);
+
+        let previous = first_of(Seeds::Values(vec![known.clone(); 30]));
+        let from_population = first_of(previous.population.into());
+        assert!(from_population
+            .population
+            .iter()
+            .any(|individual| individual.score().unwrap().as_f32() == 0.0));
+
+        let inverse = FnCodex::new()
+            .with_encoder(|| {
+                Genotype::new(vec![IntChromosome::new(vec![
+                    IntGene::from_min_max(0, 9);
+                    2
+                ])])
+            })
+            .with_decoder(|genotype: &Genotype<IntChromosome<i32>>| {
+                genotype[0]
+                    .iter()
+                    .map(|gene| *gene.allele())
+                    .collect::<Vec<i32>>()
+            })
+            .with_inverse(|value: &Vec<i32>| {
+                // keep the seeded genes within the bounds of the encoder's genes
+                let genes = value
+                    .iter()
+                    .map(|allele| IntGene::from_min_max(0, 9).with_allele(allele))
+                    .collect();
+                Genotype::new(vec![IntChromosome::new(genes)])
+            });
+
+        let engine = GeneticEngine::from_codex(inverse)
+            .population_size(10)
+            .survivor_selector(EliteSelector::new())
+            .seed_population(Seeds::Values(vec![vec![9, 9]]))
+            .fitness_fn(|values: Vec<i32>| values.iter().sum::<i32>())
+            .build();
+
+        assert_eq!(engine.iter().next().unwrap().score().as_i32(), 18);
+    }
+
+    #[test]
+    fn engine_ranks_feasible_individuals_above_infeasible_ones() {
+        let over_budget = |value: &Vec<Vec<i32>>| (value[0].iter().sum::<i32>() - 20) as f32;
+
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .population_size(50)
+            .multi_objective(vec![Optimize::Maximize, Optimize::Maximize])
+            .offspring_selector(TournamentSelector::new(3))
+            .survivor_selector(NSGA2Selector::new())
+            .fitness_fn(|value: Vec<Vec<i32>>| {
+                vec![value[0][0] as f32, value[0].iter().sum::<i32>() as f32]
+            })
+            .constraint(over_budget, ConstraintMode::Feasibility)
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 30);
+        let front = result.front.lock().unwrap();
+
+        assert!(front
+            .scores()
+            .iter()
+            .all(|score| score.values[1] <= 20.0 && score.values[0] >= 0.0));
+        assert_eq!(result.configuration.get("constraints"), Some("feasibility"));
+        assert!(result.metrics.get(metric_names::INFEASIBLE).is_some());
+
+        let repaired = FnConstraint::new(over_budget).with_repair(|value: &Vec<Vec<i32>>| {
+            let mut value = value.clone();
+            while value[0].iter().sum::<i32>() > 20 {
+                let max = value[0].iter_mut().max().unwrap();
+                *max -= 1;
+            }
+            value
+        });
+
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .population_size(20)
+            .fitness_fn(|value: Vec<Vec<i32>>| value[0].iter().sum::<i32>())
+            .constraint(repaired, ConstraintMode::Repair)
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 30);
+        assert_eq!(result.score().as_i32(), 20);
+    }
+
+    #[test]
+    fn engine_skips_evaluating_cached_genotypes() {
+        let evaluations = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = std::sync::Arc::clone(&evaluations);
+
+        let engine = GeneticEngine::from_codex(BitCodex::new(1, 3))
+            .population_size(40)
+            .cache_fitness(8)
+            .fitness_fn(move |geno: Vec<Vec<bool>>| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                geno[0].iter().filter(|bit| **bit).count()
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 20);
+        let hits = result.metrics.get(metric_names::CACHE_HITS).unwrap();
+        let misses = result.metrics.get(metric_names::CACHE_MISSES).unwrap();
+
+        assert!(evaluations.load(std::sync::atomic::Ordering::SeqCst) <= 8);
+        assert!(hits.value_max().unwrap() > 0.0);
+        assert!(misses.value_max().unwrap() <= 8.0);
+        assert_eq!(result.score().as_usize(), 3);
+    }
+
+    #[test]
+    fn engine_runs_post_run_stages_on_the_result() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .population_size(20)
+            .fitness_fn(|value: Vec<Vec<i32>>| value[0].iter().sum::<i32>())
+            .post_run(|ctx| ctx.best[0].sort())
+            .post_run(|ctx| {
+                let smallest = ctx.best[0][0];
+                ctx.metrics.upsert_value("Smallest Value", smallest as f32);
+            })
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 5);
+
+        assert!(result.best[0].windows(2).all(|pair| pair[0] <= pair[1]));
+        let smallest = result.metrics.get("Smallest Value").unwrap();
+        assert_eq!(smallest.value_max(), Some(result.best[0][0] as f32));
+    }
+
+    #[test]
+    fn engine_speciates_the_population_and_keeps_raw_scores() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 10))
+            .population_size(30)
+            .speciation(Speciation::new(HammingDistance, 2.0).protect_young(3))
+            .fitness_fn(|value: Vec<Vec<i32>>| value[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 10);
+        let speciation = result.species.as_ref().unwrap();
+
+        let ids = speciation
+            .species()
+            .iter()
+            .map(Species::id)
+            .collect::<Vec<usize>>();
+        let sizes = speciation
+            .species()
+            .iter()
+            .map(Species::size)
+            .sum::<usize>();
+        assert_eq!(sizes, 30);
+        assert!(result.metrics.get("Speciation").is_some());
+        assert_eq!(result.score().as_i32(), result.best[0].iter().sum::<i32>());
+        assert!(result
+            .population
+            .iter()
+            .filter_map(|individual| individual.species())
+            .all(|id| ids.contains(&id)));
+    }
+
+    #[test]
+    fn engine_refines_offspring_with_lamarckian_or_baldwinian_local_search() {
+        let codex = IntCodex::new(1, 5, 0, 100);
+        let fitness = |geno: &Genotype<IntChromosome<i32>>| {
+            geno.iter()
+                .flat_map(|chromosome| chromosome.iter())
+                .map(|gene| *gene.allele())
+                .sum::<i32>() as f32
+        };
+
+        for memetic in [
+            Memetic::new(TrajectorySearch::new(
+                UniformMutator::new(0.5),
+                Acceptance::HillClimbing,
+            ))
+            .stage(MemeticStage::AfterAlteration),
+            Memetic::new(TrajectorySearch::new(
+                UniformMutator::new(0.5),
+                Acceptance::HillClimbing,
+            ))
+            .baldwinian(),
+        ] {
+            let inheritance = memetic.inheritance();
+            let engine = GeneticEngine::from_codex(codex.clone())
+                .minimizing()
+                .population_size(20)
+                .memetic(memetic)
+                .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+                .build();
+
+            let result = engine.run(|ctx| ctx.index >= 3);
+            let (raw, refined) = result
+                .population
+                .iter()
+                .map(|individual| {
+                    let score = individual.score().unwrap().as_f32();
+                    (fitness(individual.genotype()), score)
+                })
+                .unzip::<f32, f32, Vec<f32>, Vec<f32>>();
+
+            assert!(result.metrics.get("Local Search").is_some());
+            match inheritance {
+                Inheritance::Lamarckian => assert_eq!(raw, refined),
+                Inheritance::Baldwinian => {
+                    assert!(raw
+                        .iter()
+                        .zip(refined.iter())
+                        .all(|(raw, refined)| refined <= raw));
+                    assert!(raw
+                        .iter()
+                        .zip(refined.iter())
+                        .any(|(raw, refined)| refined < raw));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "wire")]
+    #[test]
+    fn engine_keeps_running_when_a_checkpoint_cant_be_written() {
+        let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = std::sync::Arc::clone(&errors);
+        let path = std::env::temp_dir()
+            .join("radiate-missing-directory")
+            .join("checkpoint.rdwf");
+
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .checkpoint_every(2, path)
+            .subscribe(move |event: &EngineEvent<Vec<Vec<i32>>>| {
+                if let EngineEvent::Error { message, .. } = event {
+                    seen.lock().unwrap().push(message.clone());
+                }
+            })
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 6);
+
+        assert_eq!(result.index, 6);
+        assert_eq!(errors.lock().unwrap().len(), 3);
+        assert!(errors.lock().unwrap()[0].starts_with("Failed to write checkpoint"));
+        let write_errors = result.metrics.get("Write Errors").unwrap();
+        assert_eq!(write_errors.count(), 3);
+    }
+
+    #[test]
+    fn engine_reports_artifacts_that_cant_be_written_when_it_stops() {
+        let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = std::sync::Arc::clone(&errors);
+        let artifacts = RunArtifacts::open(std::env::temp_dir().join("radiate-missing-run"));
+
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .subscribe(move |event: &EngineEvent<Vec<Vec<i32>>>| {
+                if let EngineEvent::Error { message, .. } = event {
+                    seen.lock().unwrap().push(message.clone());
+                }
+            })
+            .artifacts(artifacts)
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 4);
+
+        assert_eq!(result.index, 4);
+        assert_eq!(errors.lock().unwrap().len(), 1);
+        assert!(errors.lock().unwrap()[0].starts_with("Failed to write artifacts"));
+        assert_eq!(result.metrics.get("Write Errors").unwrap().count(), 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "wire", target_os = "linux"))]
+    fn engine_reports_samples_that_cant_be_written_when_it_stops() {
+        let engine = GeneticEngine::from_codex(IntCodex::new(1, 5, 0, 100))
+            .sample_every(1, SampleWriter::create("/dev/full").unwrap())
+            .fitness_fn(|geno: Vec<Vec<i32>>| geno[0].iter().sum::<i32>())
+            .build();
+
+        let result = engine.run(|ctx| ctx.index >= 4);
+
+        assert_eq!(result.index, 4);
+        assert_eq!(result.metrics.get("Write Errors").unwrap().count(), 1);
+    }
 }